@@ -14,6 +14,8 @@ import android.content.SharedPreferences
 import android.content.pm.PackageManager
 import android.graphics.Color
 import android.graphics.drawable.Drawable
+import android.hardware.camera2.CameraCharacteristics
+import android.hardware.camera2.CameraManager
 import android.net.Uri
 import android.os.Build
 import android.os.VibrationEffect
@@ -24,6 +26,9 @@ import android.view.ViewGroup
 import android.webkit.WebView
 import android.widget.FrameLayout
 import androidx.activity.result.ActivityResult
+import androidx.annotation.OptIn
+import androidx.camera.camera2.interop.Camera2CameraInfo
+import androidx.camera.camera2.interop.ExperimentalCamera2Interop
 import androidx.camera.core.Camera
 import androidx.camera.core.CameraSelector
 import androidx.camera.core.ImageAnalysis
@@ -42,6 +47,7 @@ import app.tauri.annotation.Permission
 import app.tauri.annotation.PermissionCallback
 import app.tauri.annotation.TauriPlugin
 import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
 import com.google.common.util.concurrent.ListenableFuture
@@ -61,6 +67,25 @@ class ScanOptions {
     var formats: Array<String>? = null
     var windowed: Boolean = false
     var cameraDirection: String? = null
+    var cameraId: String? = null
+    var torch: Boolean = false
+    var zoom: Float? = null
+}
+
+@InvokeArg
+class SetTorchArgs {
+    var enabled: Boolean = false
+}
+
+@InvokeArg
+class SetZoomArgs {
+    var zoom: Float = 1.0f
+}
+
+@InvokeArg
+class SwitchCameraArgs {
+    var cameraId: String? = null
+    var cameraDirection: String? = null
 }
 
 @TauriPlugin(
@@ -83,6 +108,10 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
 
     private var requestPermissionResponse: JSObject? = null
     private var windowed = false
+    private var cameraDirection = "back"
+    private var cameraId: String? = null
+    private var initialTorch = false
+    private var initialZoom: Float? = null
 
     // declare a map constant for allowed barcode formats
     private val supportedFormats = supportedFormats()
@@ -118,7 +147,7 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
             .hasSystemFeature(PackageManager.FEATURE_CAMERA_ANY)
     }
 
-    private fun setupCamera(cameraDirection: String, windowed: Boolean) {
+    private fun setupCamera(windowed: Boolean) {
         activity
             .runOnUiThread {
                 val previewView = PreviewView(activity)
@@ -151,10 +180,7 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
                     {
                         try {
                             val cameraProvider = cameraProviderFuture.get()
-                            bindPreview(
-                                cameraProvider,
-                                if (cameraDirection == "front") CameraSelector.LENS_FACING_FRONT else CameraSelector.LENS_FACING_BACK
-                            )
+                            bindPreview(cameraProvider, cameraSelector())
                             this.cameraProvider = cameraProvider
                         } catch (e: InterruptedException) {
                             // ignored
@@ -168,12 +194,27 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
             }
     }
 
-    private fun bindPreview(cameraProvider: ProcessCameraProvider, cameraDirection: Int) {
+    @OptIn(ExperimentalCamera2Interop::class)
+    private fun cameraSelector(): CameraSelector {
+        val builder = CameraSelector.Builder()
+        val id = cameraId
+        if (id != null) {
+            builder.addCameraFilter { infos ->
+                infos.filter { Camera2CameraInfo.from(it).cameraId == id }
+            }
+        } else {
+            builder.requireLensFacing(
+                if (cameraDirection == "front") CameraSelector.LENS_FACING_FRONT else CameraSelector.LENS_FACING_BACK
+            )
+        }
+        return builder.build()
+    }
+
+    private fun bindPreview(cameraProvider: ProcessCameraProvider, cameraSelector: CameraSelector) {
         activity
             .runOnUiThread {
+                cameraProvider.unbindAll()
                 val preview = Preview.Builder().build()
-                val cameraSelector =
-                    CameraSelector.Builder().requireLensFacing(cameraDirection).build()
                 preview.setSurfaceProvider(previewView?.surfaceProvider)
                 val imageAnalysis = ImageAnalysis.Builder()
                     .setBackpressureStrategy(ImageAnalysis.STRATEGY_KEEP_ONLY_LATEST)
@@ -185,14 +226,19 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
                 )
 
                 try {
-                    camera = cameraProvider.bindToLifecycle(
+                    val camera = cameraProvider.bindToLifecycle(
                         activity as LifecycleOwner,
                         cameraSelector,
                         preview,
                         imageAnalysis
                     )
+                    if (initialTorch && camera.cameraInfo.hasFlashUnit()) {
+                        camera.cameraControl.enableTorch(true)
+                    }
+                    initialZoom?.let { camera.cameraControl.setZoomRatio(clampZoom(camera, it)) }
+                    this.camera = camera
                 } catch (e: Exception) {
-                    // TODO
+                    Logger.error(e.message ?: e.toString())
                 }
             }
     }
@@ -223,9 +269,13 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
         return formats
     }
 
-    private fun prepare(direction: String, windowed: Boolean) {
+    private fun prepare(args: ScanOptions) {
         dismantleCamera()
-        setupCamera(direction, windowed)
+        cameraDirection = args.cameraDirection ?: "back"
+        cameraId = args.cameraId
+        initialTorch = args.torch
+        initialZoom = args.zoom
+        setupCamera(args.windowed)
     }
 
     private fun destroy() {
@@ -348,12 +398,96 @@ class BarcodeScannerPlugin(private val activity: Activity) : Plugin(activity),
                 throw Exception("No permission to use camera. Did you request it yet?")
             } else {
                 webViewBackground = null
-                prepare(args.cameraDirection ?: "back", args.windowed)
+                prepare(args)
                 configureCamera(getFormats(args))
             }
         }
     }
 
+    @Command
+    fun listCameras(invoke: Invoke) {
+        val cameraManager = activity.getSystemService(Context.CAMERA_SERVICE) as CameraManager
+        val cameras = JSArray()
+        for (id in cameraManager.cameraIdList) {
+            val characteristics = cameraManager.getCameraCharacteristics(id)
+            val direction = when (characteristics.get(CameraCharacteristics.LENS_FACING)) {
+                CameraCharacteristics.LENS_FACING_FRONT -> "front"
+                CameraCharacteristics.LENS_FACING_BACK -> "back"
+                else -> "external"
+            }
+            val camera = JSObject()
+            camera.put("id", id)
+            camera.put("direction", direction)
+            camera.put(
+                "hasTorch",
+                characteristics.get(CameraCharacteristics.FLASH_INFO_AVAILABLE) == true
+            )
+            camera.put("minZoom", 1.0)
+            camera.put(
+                "maxZoom",
+                (characteristics.get(CameraCharacteristics.SCALER_AVAILABLE_MAX_DIGITAL_ZOOM)
+                    ?: 1.0f).toDouble()
+            )
+            cameras.put(camera)
+        }
+        val result = JSObject()
+        result.put("cameras", cameras)
+        invoke.resolve(result)
+    }
+
+    @Command
+    fun setTorch(invoke: Invoke) {
+        val args = invoke.parseArgs(SetTorchArgs::class.java)
+        val camera = this.camera
+        if (camera == null) {
+            invoke.reject("No active scan session")
+            return
+        }
+        if (!camera.cameraInfo.hasFlashUnit()) {
+            invoke.reject("The current camera does not have a torch")
+            return
+        }
+        activity.runOnUiThread {
+            camera.cameraControl.enableTorch(args.enabled)
+            invoke.resolve()
+        }
+    }
+
+    @Command
+    fun setZoom(invoke: Invoke) {
+        val args = invoke.parseArgs(SetZoomArgs::class.java)
+        val camera = this.camera
+        if (camera == null) {
+            invoke.reject("No active scan session")
+            return
+        }
+        activity.runOnUiThread {
+            camera.cameraControl.setZoomRatio(clampZoom(camera, args.zoom))
+            invoke.resolve()
+        }
+    }
+
+    private fun clampZoom(camera: Camera, zoom: Float): Float {
+        val zoomState = camera.cameraInfo.zoomState.value ?: return zoom
+        return zoom.coerceIn(zoomState.minZoomRatio, zoomState.maxZoomRatio)
+    }
+
+    @Command
+    fun switchCamera(invoke: Invoke) {
+        val args = invoke.parseArgs(SwitchCameraArgs::class.java)
+        val cameraProvider = this.cameraProvider
+        if (cameraProvider == null) {
+            invoke.reject("No active scan session")
+            return
+        }
+        cameraId = args.cameraId
+        cameraDirection = args.cameraDirection ?: cameraDirection
+        initialTorch = false
+        initialZoom = null
+        bindPreview(cameraProvider, cameraSelector())
+        invoke.resolve()
+    }
+
     private fun markFirstPermissionRequest() {
         val sharedPreference: SharedPreferences =
             activity.getSharedPreferences(PREFS_PERMISSION_FIRST_TIME_ASKING, MODE_PRIVATE)
@@ -9,6 +9,11 @@ const COMMANDS: &[&str] = &[
     "check_permissions",
     "open_app_settings",
     "vibrate",
+    "list_cameras",
+    "set_torch",
+    "set_zoom",
+    "switch_camera",
+    "generate",
 ];
 
 fn main() {
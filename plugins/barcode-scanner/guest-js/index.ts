@@ -26,10 +26,26 @@ export enum Format {
   PDF417 = 'PDF_417'
 }
 
+export type CameraDirection = 'back' | 'front' | 'external'
+
 export interface ScanOptions {
   cameraDirection?: 'back' | 'front'
+  /** Identifier of the camera to use, as returned by {@linkcode listCameras}. Takes precedence over `cameraDirection`. */
+  cameraId?: string
   formats?: Format[]
   windowed?: boolean
+  /** Turn the torch on when the scan session starts. */
+  torch?: boolean
+  /** Initial zoom factor. Clamped to the camera's supported range. */
+  zoom?: number
+}
+
+export interface CameraInfo {
+  id: string
+  direction: CameraDirection
+  hasTorch: boolean
+  minZoom: number
+  maxZoom: number
 }
 
 export interface Scanned {
@@ -53,6 +69,93 @@ export async function cancel(): Promise<void> {
   await invoke('plugin:barcode-scanner|cancel')
 }
 
+/**
+ * List the cameras available on the device and their capabilities.
+ */
+export async function listCameras(): Promise<CameraInfo[]> {
+  return await invoke<{ cameras: CameraInfo[] }>(
+    'plugin:barcode-scanner|list_cameras'
+  ).then((r) => r.cameras)
+}
+
+/**
+ * Toggle the torch of the camera used by the active scan session.
+ * @param enabled
+ */
+export async function setTorch(enabled: boolean): Promise<void> {
+  await invoke('plugin:barcode-scanner|set_torch', { enabled })
+}
+
+/**
+ * Set the zoom factor of the camera used by the active scan session.
+ * The value is clamped to the camera's supported range.
+ * @param zoom
+ */
+export async function setZoom(zoom: number): Promise<void> {
+  await invoke('plugin:barcode-scanner|set_zoom', { zoom })
+}
+
+/**
+ * Switch the active scan session to another camera.
+ * @param camera either a camera identifier from {@linkcode listCameras} or a direction.
+ */
+export async function switchCamera(
+  camera: { cameraId: string } | { cameraDirection: 'back' | 'front' }
+): Promise<void> {
+  await invoke('plugin:barcode-scanner|switch_camera', { ...camera })
+}
+
+export type GenerateFormat =
+  | Format.QRCode
+  | Format.Code128
+  | Format.EAN8
+  | Format.EAN13
+
+export interface GenerateOptions {
+  /** Output image format. Defaults to `png`. */
+  output?: 'png' | 'svg'
+  /** Size of a single module (bar or QR square) in pixels, at most 64. Defaults to 4. */
+  scale?: number
+  /** Width of the quiet zone around the code, in modules, at most 32. Defaults to 4 for QR codes and 10 for linear barcodes. */
+  margin?: number
+  /** Height of linear barcodes, in modules, at most 1024. Ignored for QR codes. Defaults to 50. */
+  height?: number
+  /** QR code error correction level. Ignored for linear barcodes. Defaults to `M`. */
+  errorCorrection?: 'L' | 'M' | 'Q' | 'H'
+}
+
+/**
+ * Generate a barcode image.
+ *
+ * For {@linkcode Format.EAN8} and {@linkcode Format.EAN13} the data must contain only digits, without the check digit.
+ *
+ * @example
+ * ```typescript
+ * import { generate, Format } from '@tauri-apps/plugin-barcode-scanner'
+ * const png = await generate(Format.QRCode, 'https://tauri.app')
+ * const svg = new TextDecoder().decode(
+ *   await generate(Format.Code128, 'TAURI', { output: 'svg' })
+ * )
+ * ```
+ *
+ * @param format the barcode format.
+ * @param data the content to encode.
+ * @param options
+ * @returns the PNG or SVG bytes.
+ */
+export async function generate(
+  format: GenerateFormat,
+  data: string,
+  options?: GenerateOptions
+): Promise<Uint8Array> {
+  const arr = await invoke<ArrayBuffer | number[]>(
+    'plugin:barcode-scanner|generate',
+    { format, data, options }
+  )
+
+  return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
+}
+
 /**
  * Get permission state.
  */
@@ -11,6 +11,22 @@ struct ScanOptions: Decodable {
   var formats: [SupportedFormat]?
   var windowed: Bool?
   var cameraDirection: String?
+  var cameraId: String?
+  var torch: Bool?
+  var zoom: CGFloat?
+}
+
+struct SetTorchArgs: Decodable {
+  let enabled: Bool
+}
+
+struct SetZoomArgs: Decodable {
+  let zoom: CGFloat
+}
+
+struct SwitchCameraArgs: Decodable {
+  var cameraId: String?
+  var cameraDirection: String?
 }
 
 enum SupportedFormat: String, CaseIterable, Decodable {
@@ -49,6 +65,7 @@ enum CaptureError: Error {
   case backCameraUnavailable
   case frontCameraUnavailable
   case couldNotCaptureInput(error: NSError)
+  case cameraNotFound
 }
 
 class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
@@ -61,6 +78,8 @@ class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
   var currentCamera = 0
   var frontCamera: AVCaptureDevice?
   var backCamera: AVCaptureDevice?
+  var currentDevice: AVCaptureDevice?
+  var currentInput: AVCaptureDeviceInput?
 
   var isScanning = false
 
@@ -107,7 +126,7 @@ class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
     }
   }
 
-  private func setupCamera(direction: String, windowed: Bool) {
+  private func setupCamera(direction: String, cameraId: String?, windowed: Bool) {
     do {
       var cameraDirection = direction
       cameraView.backgroundColor = UIColor.clear
@@ -138,10 +157,16 @@ class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
       }
 
       let input: AVCaptureDeviceInput
-      input = try createCaptureDeviceInput(
-        cameraDirection: cameraDirection, backCamera: backCamera, frontCamera: frontCamera)
+      if let cameraId = cameraId {
+        input = try createCaptureDeviceInput(cameraId: cameraId)
+      } else {
+        input = try createCaptureDeviceInput(
+          cameraDirection: cameraDirection, backCamera: backCamera, frontCamera: frontCamera)
+      }
       captureSession = AVCaptureSession()
       captureSession!.addInput(input)
+      currentInput = input
+      currentDevice = input.device
       metaOutput = AVCaptureMetadataOutput()
       captureSession!.addOutput(metaOutput!)
       metaOutput!.setMetadataObjectsDelegate(self, queue: DispatchQueue.main)
@@ -175,6 +200,8 @@ class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
       self.captureSession = nil
       self.frontCamera = nil
       self.backCamera = nil
+      self.currentDevice = nil
+      self.currentInput = nil
     }
 
     self.isScanning = false
@@ -287,12 +314,96 @@ class BarcodeScannerPlugin: Plugin, AVCaptureMetadataOutputObjectsDelegate {
       self.dismantleCamera()
       self.setupCamera(
         direction: args.cameraDirection ?? "back",
+        cameraId: args.cameraId,
         windowed: args.windowed ?? false
       )
+      if let device = self.currentDevice {
+        if args.torch ?? false {
+          try? setTorchMode(device: device, enabled: true)
+        }
+        if let zoom = args.zoom {
+          try? setZoomFactor(device: device, zoom: zoom)
+        }
+      }
       self.runScanner(invoke, args: args)
     }
   }
 
+  @objc private func listCameras(_ invoke: Invoke) {
+    let cameras = discoverCaptureDevices().map { device -> JsonObject in
+      var direction: String
+      switch device.position {
+      case .back:
+        direction = "back"
+      case .front:
+        direction = "front"
+      default:
+        direction = "external"
+      }
+      return [
+        "id": device.uniqueID,
+        "direction": direction,
+        "hasTorch": device.hasTorch,
+        "minZoom": device.minAvailableVideoZoomFactor,
+        "maxZoom": device.maxAvailableVideoZoomFactor,
+      ]
+    }
+    invoke.resolve(["cameras": cameras])
+  }
+
+  @objc private func setTorch(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(SetTorchArgs.self)
+    guard let device = currentDevice else {
+      invoke.reject("No active scan session")
+      return
+    }
+    guard device.hasTorch else {
+      invoke.reject("The current camera does not have a torch")
+      return
+    }
+    try setTorchMode(device: device, enabled: args.enabled)
+    invoke.resolve()
+  }
+
+  @objc private func setZoom(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(SetZoomArgs.self)
+    guard let device = currentDevice else {
+      invoke.reject("No active scan session")
+      return
+    }
+    try setZoomFactor(device: device, zoom: args.zoom)
+    invoke.resolve()
+  }
+
+  @objc private func switchCamera(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(SwitchCameraArgs.self)
+    guard let captureSession = captureSession else {
+      invoke.reject("No active scan session")
+      return
+    }
+
+    let input: AVCaptureDeviceInput
+    if let cameraId = args.cameraId {
+      input = try createCaptureDeviceInput(cameraId: cameraId)
+    } else {
+      input = try createCaptureDeviceInput(
+        cameraDirection: args.cameraDirection ?? "back", backCamera: backCamera,
+        frontCamera: frontCamera)
+    }
+
+    DispatchQueue.main.async { [self] in
+      captureSession.beginConfiguration()
+      if let currentInput = self.currentInput {
+        captureSession.removeInput(currentInput)
+      }
+      captureSession.addInput(input)
+      captureSession.commitConfiguration()
+      self.currentInput = input
+      self.currentDevice = input.device
+      invoke.resolve()
+    }
+  }
+
   @objc private func cancel(_ invoke: Invoke) {
     self.invoke?.reject("cancelled")
 
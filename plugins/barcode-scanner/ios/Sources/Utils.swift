@@ -32,6 +32,31 @@ func createCaptureDeviceInput(
   return captureDeviceInput
 }
 
+func createCaptureDeviceInput(cameraId: String) throws -> AVCaptureDeviceInput {
+  guard let captureDevice = discoverCaptureDevices().first(where: { $0.uniqueID == cameraId })
+  else {
+    throw CaptureError.cameraNotFound
+  }
+  do {
+    return try AVCaptureDeviceInput(device: captureDevice)
+  } catch let error as NSError {
+    throw CaptureError.couldNotCaptureInput(error: error)
+  }
+}
+
+func setTorchMode(device: AVCaptureDevice, enabled: Bool) throws {
+  try device.lockForConfiguration()
+  device.torchMode = enabled ? .on : .off
+  device.unlockForConfiguration()
+}
+
+func setZoomFactor(device: AVCaptureDevice, zoom: CGFloat) throws {
+  try device.lockForConfiguration()
+  device.videoZoomFactor = min(
+    max(zoom, device.minAvailableVideoZoomFactor), device.maxAvailableVideoZoomFactor)
+  device.unlockForConfiguration()
+}
+
 func discoverCaptureDevices() -> [AVCaptureDevice] {
   if #available(iOS 13.0, *) {
     return AVCaptureDevice.DiscoverySession(
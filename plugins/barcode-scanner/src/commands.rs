@@ -0,0 +1,16 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri::{command, ipc::Response};
+
+use crate::{GenerateFormat, GenerateOptions, Result};
+
+#[command]
+pub(crate) async fn generate(
+    format: GenerateFormat,
+    data: String,
+    options: Option<GenerateOptions>,
+) -> Result<Response> {
+    crate::generate(format, &data, &options.unwrap_or_default()).map(Response::new)
+}
@@ -13,6 +13,12 @@ pub enum Error {
     #[cfg(mobile)]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
+    #[error(transparent)]
+    Png(#[from] png::EncodingError),
+    #[error("failed to generate barcode: {0}")]
+    Generate(String),
+    #[error("invalid barcode option: {0}")]
+    InvalidOption(String),
 }
 
 impl Serialize for Error {
@@ -0,0 +1,299 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use barcoders::sym::{code128::Code128, ean13::EAN13, ean8::EAN8};
+use qrcode::{Color, EcLevel, QrCode};
+
+use crate::{Error, ErrorCorrectionLevel, GenerateFormat, GenerateOptions, ImageFormat, Result};
+
+/// Code 128 character set B selector, see [`Code128::new`].
+const CODE128_CHARSET_B: char = 'Ɓ';
+
+/// Upper bounds of the options, which come from the webview, keeping the image a reasonable size.
+const MAX_SCALE: u32 = 64;
+const MAX_MARGIN: u32 = 32;
+const MAX_HEIGHT: u32 = 1024;
+const MAX_PIXELS: usize = 64 * 1024 * 1024;
+
+/// A grid of modules where `true` is a dark module.
+struct Modules {
+    width: usize,
+    height: usize,
+    data: Vec<bool>,
+}
+
+impl Modules {
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.data[y * self.width + x]
+    }
+}
+
+/// Generates an image of the given barcode `format` encoding `data`.
+///
+/// Returns the PNG or SVG bytes depending on [`GenerateOptions::output`].
+pub fn generate(format: GenerateFormat, data: &str, options: &GenerateOptions) -> Result<Vec<u8>> {
+    check_option("scale", options.scale, MAX_SCALE)?;
+    check_option("margin", options.margin.unwrap_or_default(), MAX_MARGIN)?;
+    check_option("height", options.height, MAX_HEIGHT)?;
+    let (modules, default_margin) = match format {
+        GenerateFormat::QrCode => (qr_modules(data, options.error_correction)?, 4),
+        GenerateFormat::Code128 => {
+            let data = if data.starts_with(['À', 'Ɓ', 'Ć']) {
+                data.to_string()
+            } else {
+                format!("{CODE128_CHARSET_B}{data}")
+            };
+            let bars = Code128::new(data)
+                .map_err(|e| Error::Generate(e.to_string()))?
+                .encode();
+            (linear_modules(&bars, options.height), 10)
+        }
+        GenerateFormat::Ean8 => {
+            let bars = EAN8::new(data)
+                .map_err(|e| Error::Generate(e.to_string()))?
+                .encode();
+            (linear_modules(&bars, options.height), 10)
+        }
+        GenerateFormat::Ean13 => {
+            let bars = EAN13::new(data)
+                .map_err(|e| Error::Generate(e.to_string()))?
+                .encode();
+            (linear_modules(&bars, options.height), 10)
+        }
+    };
+
+    let scale = options.scale.max(1) as usize;
+    let margin = options.margin.unwrap_or(default_margin) as usize;
+
+    match options.output {
+        ImageFormat::Png => render_png(&modules, scale, margin),
+        ImageFormat::Svg => render_svg(&modules, scale, margin).map(String::into_bytes),
+    }
+}
+
+fn check_option(name: &str, value: u32, max: u32) -> Result<()> {
+    if value > max {
+        return Err(Error::InvalidOption(format!(
+            "{name} must be at most {max}, got {value}"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the size of the image in pixels, failing if it has more than [`MAX_PIXELS`].
+fn image_size(modules: &Modules, scale: usize, margin: usize) -> Result<(usize, usize)> {
+    let side = |modules: usize| {
+        margin
+            .checked_mul(2)
+            .and_then(|margin| margin.checked_add(modules))
+            .and_then(|side| side.checked_mul(scale))
+    };
+    match side(modules.width).zip(side(modules.height)) {
+        Some((width, height)) if width.checked_mul(height).is_some_and(|n| n <= MAX_PIXELS) => {
+            Ok((width, height))
+        }
+        _ => Err(Error::InvalidOption("the image is too large".into())),
+    }
+}
+
+fn qr_modules(data: &str, level: ErrorCorrectionLevel) -> Result<Modules> {
+    let level = match level {
+        ErrorCorrectionLevel::L => EcLevel::L,
+        ErrorCorrectionLevel::M => EcLevel::M,
+        ErrorCorrectionLevel::Q => EcLevel::Q,
+        ErrorCorrectionLevel::H => EcLevel::H,
+    };
+    let code = QrCode::with_error_correction_level(data, level)
+        .map_err(|e| Error::Generate(e.to_string()))?;
+    let width = code.width();
+    Ok(Modules {
+        width,
+        height: width,
+        data: code
+            .to_colors()
+            .into_iter()
+            .map(|c| c == Color::Dark)
+            .collect(),
+    })
+}
+
+fn linear_modules(bars: &[u8], height: u32) -> Modules {
+    let height = height.max(1) as usize;
+    let row = bars.iter().map(|b| *b == 1).collect::<Vec<_>>();
+    Modules {
+        width: row.len(),
+        height,
+        data: row.repeat(height),
+    }
+}
+
+fn render_png(modules: &Modules, scale: usize, margin: usize) -> Result<Vec<u8>> {
+    let (width, height) = image_size(modules, scale, margin)?;
+
+    let mut pixels = vec![0xFFu8; width * height];
+    for y in 0..modules.height {
+        for x in 0..modules.width {
+            if !modules.is_dark(x, y) {
+                continue;
+            }
+            for dy in 0..scale {
+                let row = ((y + margin) * scale + dy) * width;
+                let start = row + (x + margin) * scale;
+                pixels[start..start + scale].fill(0);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+    }
+    Ok(out)
+}
+
+fn render_svg(modules: &Modules, scale: usize, margin: usize) -> Result<String> {
+    let (width, height) = image_size(modules, scale, margin)?;
+
+    let mut path = String::new();
+    for y in 0..modules.height {
+        let mut x = 0;
+        while x < modules.width {
+            if !modules.is_dark(x, y) {
+                x += 1;
+                continue;
+            }
+            // merge horizontal runs of dark modules into a single rectangle
+            let start = x;
+            while x < modules.width && modules.is_dark(x, y) {
+                x += 1;
+            }
+            path.push_str(&format!(
+                "M{} {}h{}v{}h-{}z",
+                (start + margin) * scale,
+                (y + margin) * scale,
+                (x - start) * scale,
+                scale,
+                (x - start) * scale
+            ));
+        }
+    }
+
+    Ok(format!(
+        r##"<?xml version="1.0" encoding="UTF-8"?><svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges"><rect width="100%" height="100%" fill="#ffffff"/><path fill="#000000" d="{path}"/></svg>"##
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard(width: usize, height: usize) -> Modules {
+        Modules {
+            width,
+            height,
+            data: (0..width * height).map(|i| i % 2 == 0).collect(),
+        }
+    }
+
+    #[test]
+    fn svg_dimensions() {
+        let svg = render_svg(&checkerboard(21, 21), 4, 2).unwrap();
+        assert!(svg.contains(r#"width="100" height="100" viewBox="0 0 100 100""#));
+
+        let svg = render_svg(&checkerboard(95, 50), 2, 10).unwrap();
+        assert!(svg.contains(r#"width="230" height="140" viewBox="0 0 230 140""#));
+    }
+
+    #[test]
+    fn png_dimensions() {
+        let png = render_png(&checkerboard(21, 21), 4, 2).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (100, 100));
+
+        let png = render_png(&checkerboard(95, 50), 2, 10).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (230, 140));
+    }
+
+    #[test]
+    fn png_pixels() {
+        let modules = Modules {
+            width: 2,
+            height: 1,
+            data: vec![true, false],
+        };
+        let png = render_png(&modules, 2, 1).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        #[rustfmt::skip]
+        assert_eq!(pixels, [
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+    }
+
+    #[test]
+    fn overflowing_size() {
+        let modules = checkerboard(1, 1);
+        assert!(matches!(
+            render_png(&modules, usize::MAX, 1),
+            Err(Error::InvalidOption(_))
+        ));
+        assert!(matches!(
+            render_svg(&modules, 2, usize::MAX),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+
+    #[test]
+    fn options_out_of_range() {
+        for options in [
+            GenerateOptions {
+                scale: MAX_SCALE + 1,
+                ..Default::default()
+            },
+            GenerateOptions {
+                margin: Some(u32::MAX),
+                ..Default::default()
+            },
+            GenerateOptions {
+                height: MAX_HEIGHT + 1,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                generate(GenerateFormat::QrCode, "tauri", &options),
+                Err(Error::InvalidOption(_))
+            ));
+        }
+
+        let options = GenerateOptions {
+            scale: MAX_SCALE,
+            margin: Some(MAX_MARGIN),
+            ..Default::default()
+        };
+        assert!(generate(GenerateFormat::QrCode, "tauri", &options).is_ok());
+
+        let options = GenerateOptions {
+            scale: MAX_SCALE,
+            height: MAX_HEIGHT,
+            ..Default::default()
+        };
+        assert!(matches!(
+            generate(GenerateFormat::Ean13, "590123412345", &options),
+            Err(Error::InvalidOption(_))
+        ));
+    }
+}
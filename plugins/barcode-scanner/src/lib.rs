@@ -11,10 +11,13 @@ use tauri::{
 
 pub use models::*;
 
+mod commands;
 mod error;
+mod generate;
 mod models;
 
 pub use error::{Error, Result};
+pub use generate::generate;
 
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "app.tauri.barcodescanner";
@@ -41,6 +44,7 @@ impl<R: Runtime, T: Manager<R>> crate::BarcodeScannerExt<R> for T {
 /// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
     Builder::new("barcode-scanner")
+        .invoke_handler(tauri::generate_handler![commands::generate])
         .setup(|app, api| {
             #[cfg(target_os = "android")]
             let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "BarcodeScannerPlugin")?;
@@ -1,3 +1,70 @@
 // Copyright 2019-2023 Tauri Programme within The Commons Conservancy
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+
+/// Barcode formats supported by [`crate::generate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum GenerateFormat {
+    #[serde(rename = "QR_CODE")]
+    QrCode,
+    #[serde(rename = "CODE_128")]
+    Code128,
+    #[serde(rename = "EAN_8")]
+    Ean8,
+    #[serde(rename = "EAN_13")]
+    Ean13,
+}
+
+/// Image format of a generated barcode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+/// QR code error correction level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum ErrorCorrectionLevel {
+    /// Recovers 7% of the data.
+    L,
+    /// Recovers 15% of the data.
+    #[default]
+    M,
+    /// Recovers 25% of the data.
+    Q,
+    /// Recovers 30% of the data.
+    H,
+}
+
+/// Options for [`crate::generate`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GenerateOptions {
+    /// Output image format. Defaults to PNG.
+    pub output: ImageFormat,
+    /// Size of a single module (bar or QR square) in pixels, at most 64. Defaults to 4.
+    pub scale: u32,
+    /// Width of the quiet zone around the code, in modules, at most 32.
+    /// Defaults to 4 for QR codes and 10 for linear barcodes.
+    pub margin: Option<u32>,
+    /// Height of linear barcodes, in modules, at most 1024. Ignored for QR codes. Defaults to 50.
+    pub height: u32,
+    /// QR code error correction level. Ignored for linear barcodes. Defaults to `M`.
+    pub error_correction: ErrorCorrectionLevel,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            output: ImageFormat::Png,
+            scale: 4,
+            margin: None,
+            height: 50,
+            error_correction: ErrorCorrectionLevel::M,
+        }
+    }
+}
@@ -16,8 +16,13 @@ import android.os.Handler
 import androidx.appcompat.app.AppCompatActivity
 import androidx.biometric.BiometricPrompt
 import java.util.concurrent.Executor
+import kotlin.math.max
 
 class BiometricActivity : AppCompatActivity() {
+    private var prompt: BiometricPrompt? = null
+    private var maxAttempts = 1
+    private var failedAttempts = 0
+
     @SuppressLint("WrongConstant")
     override fun onCreate(savedInstanceState: Bundle?) {
         super.onCreate(savedInstanceState)
@@ -38,6 +43,7 @@ class BiometricActivity : AppCompatActivity() {
         var title = intent.getStringExtra(BiometricPlugin.TITLE)
         val subtitle = intent.getStringExtra(BiometricPlugin.SUBTITLE)
         val description = intent.getStringExtra(BiometricPlugin.REASON)
+        maxAttempts = intent.getIntExtra(BiometricPlugin.MAX_ATTEMPTS, 1)
         allowDeviceCredential = false
         // Android docs say we should check if the device is secure before enabling device credential fallback
         val manager = getSystemService(
@@ -53,8 +59,19 @@ class BiometricActivity : AppCompatActivity() {
         }
 
         builder.setTitle(title).setSubtitle(subtitle).setDescription(description)
+        // keys that require user authentication can only be unlocked by a strong biometric
+        val crypto = cryptoObject
+        if (crypto != null) {
+            allowDeviceCredential = false
+        }
+
         if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
-            var authenticators = BiometricManager.Authenticators.BIOMETRIC_WEAK
+            val strength = intent.getStringExtra(BiometricPlugin.BIOMETRIC_STRENGTH)
+            var authenticators = if (crypto != null || strength == "strong") {
+                BiometricManager.Authenticators.BIOMETRIC_STRONG
+            } else {
+                BiometricManager.Authenticators.BIOMETRIC_WEAK
+            }
             if (allowDeviceCredential) {
                 authenticators = authenticators or BiometricManager.Authenticators.DEVICE_CREDENTIAL
             }
@@ -93,15 +110,36 @@ class BiometricActivity : AppCompatActivity() {
                     )
                 }
 
+                override fun onAuthenticationFailed() {
+                    super.onAuthenticationFailed()
+                    failedAttempts++
+                    val remainingAttempts = max(maxAttempts - failedAttempts, 0)
+                    onAuthenticationFailed?.invoke(remainingAttempts)
+                    if (remainingAttempts == 0) {
+                        prompt?.cancelAuthentication()
+                        finishActivity(
+                            BiometryResultType.FAILURE,
+                            0,
+                            "Too many failed attempts"
+                        )
+                    }
+                }
+
                 override fun onAuthenticationSucceeded(
                     result: BiometricPrompt.AuthenticationResult
                 ) {
                     super.onAuthenticationSucceeded(result)
+                    authenticatedCryptoObject = result.cryptoObject
                     finishActivity()
                 }
             }
         )
-        prompt.authenticate(promptInfo)
+        this.prompt = prompt
+        if (crypto != null) {
+            prompt.authenticate(promptInfo, crypto)
+        } else {
+            prompt.authenticate(promptInfo)
+        }
     }
 
     @JvmOverloads
@@ -110,6 +148,10 @@ class BiometricActivity : AppCompatActivity() {
         errorCode: Int = 0,
         errorMessage: String? = ""
     ) {
+        // canceling the prompt after the last failed attempt also reports an error
+        if (isFinishing) {
+            return
+        }
         val intent = Intent()
         val prefix = BiometricPlugin.RESULT_EXTRA_PREFIX
         intent
@@ -125,5 +167,12 @@ class BiometricActivity : AppCompatActivity() {
 
     companion object {
         var allowDeviceCredential = false
+
+        // crypto operation to unlock with the prompt, set by the plugin before starting the activity
+        var cryptoObject: BiometricPrompt.CryptoObject? = null
+        var authenticatedCryptoObject: BiometricPrompt.CryptoObject? = null
+
+        // notifies the plugin of each failed attempt with the number of remaining attempts
+        var onAuthenticationFailed: ((Int) -> Unit)? = null
     }
 }
\ No newline at end of file
@@ -8,6 +8,10 @@ import android.app.Activity
 import android.content.Intent
 import android.content.pm.PackageManager
 import android.os.Build
+import android.os.SystemClock
+import android.security.keystore.KeyGenParameterSpec
+import android.security.keystore.KeyPermanentlyInvalidatedException
+import android.security.keystore.KeyProperties
 import android.webkit.WebView
 import androidx.activity.result.ActivityResult
 import androidx.biometric.BiometricManager
@@ -20,6 +24,15 @@ import app.tauri.plugin.Invoke
 import app.tauri.plugin.JSArray
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
+import java.security.KeyPairGenerator
+import java.security.KeyStore
+import java.security.PrivateKey
+import java.security.Signature
+import java.security.spec.ECGenParameterSpec
+import javax.crypto.Cipher
+import javax.crypto.KeyGenerator
+import javax.crypto.SecretKey
+import javax.crypto.spec.GCMParameterSpec
 import java.util.EnumMap
 import java.util.HashMap
 import kotlin.math.max
@@ -28,24 +41,71 @@ enum class BiometryResultType {
     SUCCESS, FAILURE, ERROR
 }
 
-private const val MAX_ATTEMPTS = "maxAttemps"
 private const val BIOMETRIC_FAILURE = "authenticationFailed"
 private const val INVALID_CONTEXT_ERROR = "invalidContext"
+private const val USER_FALLBACK_ERROR = "userFallback"
+private const val KEY_NOT_FOUND_ERROR = "keyNotFound"
+private const val KEY_INVALIDATED_ERROR = "keyInvalidated"
+private const val INVALID_TOKEN_ERROR = "invalidToken"
+private const val KEYSTORE_PROVIDER = "AndroidKeyStore"
+private const val SECRET_KEY_SUFFIX = ".secret"
+private const val SECRET_CIPHER = "AES/GCM/NoPadding"
+private const val GCM_IV_SIZE = 12
+private const val GCM_TAG_SIZE = 128
+private const val ENROLLMENT_KEY_ALIAS = "app.tauri.biometric.enrollment"
+// BiometricPrompt disables biometric authentication for 30 seconds after a temporary lockout
+private const val TEMPORARY_LOCKOUT_DURATION = 30_000L
 
 @InvokeArg
 class AuthOptions {
     lateinit var reason: String
     var allowDeviceCredential: Boolean = false
+    var biometricStrength: String? = null
     var title: String? = null
     var subtitle: String? = null
     var cancelTitle: String? = null
     var confirmationRequired: Boolean? = null
     var maxAttemps: Int = 3
+    var negativeButton: String? = null
+}
+
+@InvokeArg
+class CreateKeyOptions {
+    var invalidateOnEnrollment: Boolean = true
+}
+
+@InvokeArg
+class CreateKeyArgs {
+    lateinit var name: String
+    var options: CreateKeyOptions = CreateKeyOptions()
+}
+
+@InvokeArg
+class KeyArgs {
+    lateinit var name: String
+}
+
+@InvokeArg
+class KeyOperationArgs {
+    lateinit var name: String
+    lateinit var data: ByteArray
+    lateinit var reason: String
+    var title: String? = null
+    var subtitle: String? = null
+    var cancelTitle: String? = null
+    var confirmationRequired: Boolean? = null
+    var maxAttemps: Int = 3
+    var negativeButton: String? = null
 }
 
 @TauriPlugin
 class BiometricPlugin(private val activity: Activity): Plugin(activity) {
     private var biometryTypes: ArrayList<BiometryType> = arrayListOf()
+    private var pendingCryptoOperation: ((BiometricPrompt.CryptoObject) -> JSObject)? = null
+    private var temporaryLockoutUntil = 0L
+    private var permanentLockout = false
+    private var enrollmentChangeNotified = false
+    private var negativeButtonFallback = false
 
     companion object {
         var RESULT_EXTRA_PREFIX = ""
@@ -58,6 +118,8 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
         const val RESULT_ERROR_MESSAGE = "errorMessage"
         const val DEVICE_CREDENTIAL = "allowDeviceCredential"
         const val CONFIRMATION_REQUIRED = "confirmationRequired"
+        const val MAX_ATTEMPTS = "maxAttemps"
+        const val BIOMETRIC_STRENGTH = "biometricStrength"
 
         // Maps biometry error numbers to string error codes
         private var biometryErrorCodeMap: MutableMap<Int, String> = HashMap()
@@ -104,6 +166,74 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
         if (biometryTypes.size == 0) {
             biometryTypes.add(BiometryType.NONE)
         }
+
+        BiometricActivity.onAuthenticationFailed = { remainingAttempts ->
+            val event = JSObject()
+            event.put("remainingAttempts", remainingAttempts)
+            trigger("authenticationFailed", event)
+        }
+    }
+
+    private fun lockout(): String? {
+        return when {
+            permanentLockout -> "permanent"
+            SystemClock.elapsedRealtime() < temporaryLockoutUntil -> "temporary"
+            else -> null
+        }
+    }
+
+    private fun setLockout(errorCode: Int) {
+        val kind = when (errorCode) {
+            BiometricPrompt.ERROR_LOCKOUT -> {
+                temporaryLockoutUntil = SystemClock.elapsedRealtime() + TEMPORARY_LOCKOUT_DURATION
+                "temporary"
+            }
+            BiometricPrompt.ERROR_LOCKOUT_PERMANENT -> {
+                permanentLockout = true
+                "permanent"
+            }
+            else -> return
+        }
+        val event = JSObject()
+        event.put("kind", kind)
+        trigger("lockout", event)
+    }
+
+    /**
+     * Detects enrolled biometrics changes using a key that is invalidated when a new biometric is enrolled.
+     * The key is created the first time this is called and recreated after each successful authentication.
+     */
+    private fun enrollmentChanged(): Boolean {
+        val changed = try {
+            val key = keyStore().getKey(ENROLLMENT_KEY_ALIAS, null) as? SecretKey
+            if (key == null) {
+                generateSecretKey(ENROLLMENT_KEY_ALIAS)
+            } else {
+                Cipher.getInstance(SECRET_CIPHER).init(Cipher.ENCRYPT_MODE, key)
+            }
+            false
+        } catch (e: KeyPermanentlyInvalidatedException) {
+            true
+        } catch (e: Exception) {
+            // no biometrics enrolled yet
+            false
+        }
+
+        if (changed && !enrollmentChangeNotified) {
+            enrollmentChangeNotified = true
+            trigger("enrollmentChanged", JSObject())
+        }
+        return changed
+    }
+
+    private fun resetEnrollmentState() {
+        try {
+            keyStore().deleteEntry(ENROLLMENT_KEY_ALIAS)
+            generateSecretKey(ENROLLMENT_KEY_ALIAS)
+        } catch (e: Exception) {
+            // no biometrics enrolled, the key is created on the next check
+        }
+        enrollmentChangeNotified = false
     }
 
     /**
@@ -127,6 +257,8 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
         )
 
         ret.put("biometryType", biometryTypes[0].type)
+        ret.put("lockout", lockout())
+        ret.put("enrollmentChanged", enrollmentChanged())
 
         if (!available) {
             var reason = ""
@@ -161,36 +293,72 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
      */
     @Command
     fun authenticate(invoke: Invoke) {
+        val args = invoke.parseArgs(AuthOptions::class.java)
+        // notifies listeners before the prompt is displayed
+        enrollmentChanged()
+
+        val intent = authIntent(
+            args.reason,
+            args.title,
+            args.subtitle,
+            args.cancelTitle,
+            args.allowDeviceCredential,
+            args.confirmationRequired,
+            args.maxAttemps,
+            args.biometricStrength,
+            args.negativeButton
+        )
+        startActivityForResult(invoke, intent, "authenticateResult")
+    }
+
+    private fun authIntent(
+        reason: String,
+        title: String?,
+        subtitle: String?,
+        cancelTitle: String?,
+        allowDeviceCredential: Boolean,
+        confirmationRequired: Boolean?,
+        maxAttemptsConfig: Int,
+        biometricStrength: String?,
+        negativeButton: String?
+    ): Intent {
         // The result of an intent is supposed to have the package name as a prefix
         RESULT_EXTRA_PREFIX = activity.packageName + "."
         val intent = Intent(
             activity,
             BiometricActivity::class.java
         )
-        
-        val args = invoke.parseArgs(AuthOptions::class.java)
 
         // Pass the options to the activity
         intent.putExtra(
             TITLE,
-            args.title ?: (biometryNameMap[biometryTypes[0]] ?: "")
+            title ?: (biometryNameMap[biometryTypes[0]] ?: "")
         )
-        intent.putExtra(SUBTITLE, args.subtitle)
-        intent.putExtra(REASON, args.reason)
-        intent.putExtra(CANCEL_TITLE, args.cancelTitle)
-        intent.putExtra(DEVICE_CREDENTIAL, args.allowDeviceCredential)
-        args.confirmationRequired?.let {
+        intent.putExtra(SUBTITLE, subtitle)
+        intent.putExtra(REASON, reason)
+        intent.putExtra(CANCEL_TITLE, cancelTitle)
+        intent.putExtra(DEVICE_CREDENTIAL, allowDeviceCredential)
+        confirmationRequired?.let {
             intent.putExtra(CONFIRMATION_REQUIRED, it)
         }
 
-        val maxAttemptsConfig = args.maxAttemps
         val maxAttempts = max(maxAttemptsConfig, 1)
         intent.putExtra(MAX_ATTEMPTS, maxAttempts)
-        startActivityForResult(invoke, intent, "authenticateResult")
+        intent.putExtra(BIOMETRIC_STRENGTH, biometricStrength)
+
+        // the negative button is only displayed when device credential fallback is not allowed
+        negativeButtonFallback = negativeButton == "fallback"
+        return intent
     }
 
     @ActivityCallback
     private fun authenticateResult(invoke: Invoke, result: ActivityResult) {
+        processAuthResult(invoke, result) {
+            invoke.resolve()
+        }
+    }
+
+    private fun processAuthResult(invoke: Invoke, result: ActivityResult, onSuccess: () -> Unit) {
         val resultCode = result.resultCode
 
         // If the system canceled the activity, we might get RESULT_CANCELED in resultCode.
@@ -232,7 +400,12 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
             RESULT_EXTRA_PREFIX + RESULT_ERROR_MESSAGE
         )
         when (resultType) {
-            BiometryResultType.SUCCESS -> invoke.resolve()
+            BiometryResultType.SUCCESS -> {
+                temporaryLockoutUntil = 0L
+                permanentLockout = false
+                resetEnrollmentState()
+                onSuccess()
+            }
             BiometryResultType.FAILURE ->         // Biometry was successfully presented but was not recognized
                 invoke.reject(errorMessage, BIOMETRIC_FAILURE)
 
@@ -241,13 +414,236 @@ class BiometricPlugin(private val activity: Activity): Plugin(activity) {
                 // If the user cancelled, errorMessage is the text of the "negative" button,
                 // which is not especially descriptive.
                 if (errorCode == BiometricPrompt.ERROR_NEGATIVE_BUTTON) {
+                    if (negativeButtonFallback) {
+                        invoke.reject("Fallback button was pressed", USER_FALLBACK_ERROR)
+                        return
+                    }
                     errorMessage = "Cancel button was pressed"
                 }
+                setLockout(errorCode)
                 invoke.reject(errorMessage, biometryErrorCodeMap[errorCode])
             }
         }
     }
 
+    private fun keyStore(): KeyStore {
+        val keyStore = KeyStore.getInstance(KEYSTORE_PROVIDER)
+        keyStore.load(null)
+        return keyStore
+    }
+
+    /**
+     * Create an EC P-256 key pair in the Android Keystore that requires biometric authentication for every use.
+     */
+    @Command
+    fun createKey(invoke: Invoke) {
+        val args = invoke.parseArgs(CreateKeyArgs::class.java)
+
+        val spec = KeyGenParameterSpec.Builder(args.name, KeyProperties.PURPOSE_SIGN)
+            .setAlgorithmParameterSpec(ECGenParameterSpec("secp256r1"))
+            .setDigests(KeyProperties.DIGEST_SHA256)
+            .setUserAuthenticationRequired(true)
+            .setInvalidatedByBiometricEnrollment(args.options.invalidateOnEnrollment)
+            .build()
+        val generator = KeyPairGenerator.getInstance(KeyProperties.KEY_ALGORITHM_EC, KEYSTORE_PROVIDER)
+        generator.initialize(spec)
+        val keyPair = generator.generateKeyPair()
+
+        val ret = JSObject()
+        ret.put("publicKey", toJSArray(keyPair.public.encoded))
+        ret.put("algorithm", "ES256")
+        invoke.resolve(ret)
+    }
+
+    @Command
+    fun deleteKey(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyArgs::class.java)
+        val keyStore = keyStore()
+        keyStore.deleteEntry(args.name)
+        keyStore.deleteEntry(args.name + SECRET_KEY_SUFFIX)
+        invoke.resolve()
+    }
+
+    @Command
+    fun keyExists(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyArgs::class.java)
+        val ret = JSObject()
+        ret.put("exists", keyStore().containsAlias(args.name))
+        invoke.resolve(ret)
+    }
+
+    /**
+     * Prompt the user for biometric authentication and sign the data with the given key.
+     */
+    @Command
+    fun sign(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyOperationArgs::class.java)
+
+        val privateKey = keyStore().getKey(args.name, null) as? PrivateKey
+        if (privateKey == null) {
+            invoke.reject("Key ${args.name} does not exist", KEY_NOT_FOUND_ERROR)
+            return
+        }
+
+        val signature = Signature.getInstance("SHA256withECDSA")
+        try {
+            signature.initSign(privateKey)
+        } catch (e: KeyPermanentlyInvalidatedException) {
+            invoke.reject("The key was invalidated by a biometric enrollment change", KEY_INVALIDATED_ERROR)
+            return
+        }
+
+        authenticateCryptoOperation(invoke, args, BiometricPrompt.CryptoObject(signature)) {
+            val authenticatedSignature = it.signature!!
+            authenticatedSignature.update(args.data)
+            val ret = JSObject()
+            ret.put("signature", toJSArray(authenticatedSignature.sign()))
+            ret
+        }
+    }
+
+    private fun generateSecretKey(alias: String) {
+        val spec = KeyGenParameterSpec.Builder(
+            alias,
+            KeyProperties.PURPOSE_ENCRYPT or KeyProperties.PURPOSE_DECRYPT
+        )
+            .setBlockModes(KeyProperties.BLOCK_MODE_GCM)
+            .setEncryptionPaddings(KeyProperties.ENCRYPTION_PADDING_NONE)
+            .setKeySize(256)
+            .setUserAuthenticationRequired(true)
+            .setInvalidatedByBiometricEnrollment(true)
+            .build()
+        val generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES, KEYSTORE_PROVIDER)
+        generator.init(spec)
+        generator.generateKey()
+    }
+
+    /**
+     * Initializes the cipher of the secret key with the given name.
+     * Returns null and rejects the invoke if the key was invalidated by a biometric enrollment change.
+     */
+    private fun initSecretCipher(invoke: Invoke, name: String, create: Boolean, init: (Cipher, SecretKey) -> Unit): Cipher? {
+        val alias = name + SECRET_KEY_SUFFIX
+        val keyStore = keyStore()
+        if (!keyStore.containsAlias(alias)) {
+            if (create) {
+                generateSecretKey(alias)
+            } else {
+                invoke.reject("Key $name does not exist", KEY_NOT_FOUND_ERROR)
+                return null
+            }
+        }
+
+        val cipher = Cipher.getInstance(SECRET_CIPHER)
+        try {
+            init(cipher, keyStore.getKey(alias, null) as SecretKey)
+        } catch (e: KeyPermanentlyInvalidatedException) {
+            keyStore.deleteEntry(alias)
+            invoke.reject("The key was invalidated by a biometric enrollment change", KEY_INVALIDATED_ERROR)
+            return null
+        }
+        return cipher
+    }
+
+    /**
+     * Prompt the user for biometric authentication and encrypt the data with the secret key with the given name.
+     * The key is created if it does not exist yet.
+     */
+    @Command
+    fun encrypt(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyOperationArgs::class.java)
+
+        val cipher = initSecretCipher(invoke, args.name, true) { cipher, key ->
+            cipher.init(Cipher.ENCRYPT_MODE, key)
+        } ?: return
+
+        authenticateCryptoOperation(invoke, args, BiometricPrompt.CryptoObject(cipher)) {
+            val authenticatedCipher = it.cipher!!
+            val ciphertext = authenticatedCipher.doFinal(args.data)
+            val ret = JSObject()
+            ret.put("data", toJSArray(authenticatedCipher.iv + ciphertext))
+            ret
+        }
+    }
+
+    /**
+     * Prompt the user for biometric authentication and decrypt a token returned by [encrypt].
+     */
+    @Command
+    fun decrypt(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyOperationArgs::class.java)
+
+        if (args.data.size <= GCM_IV_SIZE) {
+            invoke.reject("The token is invalid", INVALID_TOKEN_ERROR)
+            return
+        }
+        val iv = args.data.copyOfRange(0, GCM_IV_SIZE)
+        val ciphertext = args.data.copyOfRange(GCM_IV_SIZE, args.data.size)
+
+        val cipher = initSecretCipher(invoke, args.name, false) { cipher, key ->
+            cipher.init(Cipher.DECRYPT_MODE, key, GCMParameterSpec(GCM_TAG_SIZE, iv))
+        } ?: return
+
+        authenticateCryptoOperation(invoke, args, BiometricPrompt.CryptoObject(cipher)) {
+            val ret = JSObject()
+            ret.put("data", toJSArray(it.cipher!!.doFinal(ciphertext)))
+            ret
+        }
+    }
+
+    private fun authenticateCryptoOperation(
+        invoke: Invoke,
+        args: KeyOperationArgs,
+        cryptoObject: BiometricPrompt.CryptoObject,
+        operation: (BiometricPrompt.CryptoObject) -> JSObject
+    ) {
+        pendingCryptoOperation = operation
+        BiometricActivity.cryptoObject = cryptoObject
+
+        val intent = authIntent(
+            args.reason,
+            args.title,
+            args.subtitle,
+            args.cancelTitle,
+            false,
+            args.confirmationRequired,
+            args.maxAttemps,
+            // keys that require user authentication can only be unlocked by a strong biometric
+            "strong",
+            args.negativeButton
+        )
+        startActivityForResult(invoke, intent, "cryptoOperationResult")
+    }
+
+    @ActivityCallback
+    private fun cryptoOperationResult(invoke: Invoke, result: ActivityResult) {
+        val cryptoObject = BiometricActivity.authenticatedCryptoObject
+        val operation = pendingCryptoOperation
+        BiometricActivity.cryptoObject = null
+        BiometricActivity.authenticatedCryptoObject = null
+        pendingCryptoOperation = null
+
+        processAuthResult(invoke, result) {
+            if (cryptoObject == null || operation == null) {
+                invoke.reject("Missing crypto object in the result of the activity", INVALID_CONTEXT_ERROR)
+            } else {
+                try {
+                    invoke.resolve(operation(cryptoObject))
+                } catch (e: Exception) {
+                    invoke.reject(e.message ?: "The crypto operation failed", INVALID_TOKEN_ERROR)
+                }
+            }
+        }
+    }
+
+    private fun toJSArray(bytes: ByteArray): JSArray {
+        val array = JSArray()
+        for (byte in bytes) {
+            array.put(byte.toInt() and 0xff)
+        }
+        return array
+    }
+
     internal enum class BiometryType(val type: Int) {
         NONE(0), FINGERPRINT(1), FACE(2), IRIS(3);
     }
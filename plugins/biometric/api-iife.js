@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_BIOMETRIC__=function(e){"use strict";async function i(e,i={},t){return window.__TAURI_INTERNALS__.invoke(e,i,t)}var t;return"function"==typeof SuppressedError&&SuppressedError,e.BiometryType=void 0,(t=e.BiometryType||(e.BiometryType={}))[t.None=0]="None",t[t.TouchID=1]="TouchID",t[t.FaceID=2]="FaceID",t[t.Iris=3]="Iris",e.authenticate=async function(e,t){await i("plugin:biometric|authenticate",{reason:e,...t})},e.checkStatus=async function(){return await i("plugin:biometric|status")},e}({});Object.defineProperty(window.__TAURI__,"biometric",{value:__TAURI_PLUGIN_BIOMETRIC__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_BIOMETRIC__=function(t){"use strict";function e(t,e,n,i){if("a"===n&&!i)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof e?t!==e||!i:!e.has(t))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===n?i:"a"===n?i.call(t):i?i.value:e.get(t)}function n(t,e,n,i,o){if("function"==typeof e?t!==e||!o:!e.has(t))throw new TypeError("Cannot write private member to an object whose class did not declare it");return e.set(t,n),n}var i,o,s;"function"==typeof SuppressedError&&SuppressedError;const r="__TAURI_TO_IPC_KEY__";class a{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,i.set(this,(()=>{})),o.set(this,0),s.set(this,{}),this.id=function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}((({message:t,id:r})=>{if(r===e(this,o,"f")){n(this,o,r+1),e(this,i,"f").call(this,t);const a=Object.keys(e(this,s,"f"));if(a.length>0){let t=r+1;for(const n of a.sort()){if(parseInt(n)!==t)break;{const o=e(this,s,"f")[n];delete e(this,s,"f")[n],e(this,i,"f").call(this,o),t+=1}}n(this,o,t)}}else e(this,s,"f")[r.toString()]=t}))}set onmessage(t){n(this,i,t)}get onmessage(){return e(this,i,"f")}[(i=new WeakMap,o=new WeakMap,s=new WeakMap,r)](){return`__CHANNEL__:${this.id}`}toJSON(){return this[r]()}}class l{constructor(t,e,n){this.plugin=t,this.event=e,this.channelId=n}async unregister(){return c(`plugin:${this.plugin}|remove_listener`,{event:this.event,channelId:this.channelId})}}async function u(t,e,n){const i=new a;return i.onmessage=n,c(`plugin:${t}|registerListener`,{event:e,handler:i}).then((()=>new l(t,e,i.id)))}async function c(t,e={},n){return window.__TAURI_INTERNALS__.invoke(t,e,n)}var d;return d=t.BiometryType||(t.BiometryType={}),d[d.None=0]="None",d[d.TouchID=1]="TouchID",d[d.FaceID=2]="FaceID",d[d.Iris=3]="Iris",t.authenticate=async function(t,e){await c("plugin:biometric|authenticate",{reason:t,...e})},t.checkStatus=async function(){return await c("plugin:biometric|status")},t.createKey=async function(t,e){const n=await c("plugin:biometric|create_key",{name:t,options:e});return{publicKey:Uint8Array.from(n.publicKey),algorithm:n.algorithm}},t.decrypt=async function(t,e,n,i){const o=await c("plugin:biometric|decrypt",{name:t,data:Array.from(e),reason:n,...i});return Uint8Array.from(o.data)},t.deleteKey=async function(t){await c("plugin:biometric|delete_key",{name:t})},t.encrypt=async function(t,e,n,i){const o=await c("plugin:biometric|encrypt",{name:t,data:Array.from(e),reason:n,...i});return Uint8Array.from(o.data)},t.keyExists=async function(t){return await c("plugin:biometric|key_exists",{name:t}).then((t=>t.exists))},t.onAuthenticationFailed=async function(t){return await u("biometric","authenticationFailed",t)},t.onEnrollmentChange=async function(t){return await u("biometric","enrollmentChanged",t)},t.onLockout=async function(t){return await u("biometric","lockout",t)},t.sign=async function(t,e,n,i){const{signature:o}=await c("plugin:biometric|sign",{name:t,data:Array.from(e),reason:n,...i});return Uint8Array.from(o)},t}({});Object.defineProperty(window.__TAURI__,"biometric",{value:__TAURI_PLUGIN_BIOMETRIC__})}
\ No newline at end of file
@@ -2,7 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["authenticate", "status"];
+const COMMANDS: &[&str] = &[
+    "authenticate",
+    "status",
+    "create_key",
+    "delete_key",
+    "key_exists",
+    "sign",
+    "encrypt",
+    "decrypt",
+    "register_listener",
+];
 
 fn main() {
     let result = tauri_plugin::Builder::new(COMMANDS)
@@ -2,15 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-import { invoke } from '@tauri-apps/api/core'
+import {
+  addPluginListener,
+  invoke,
+  type PluginListener
+} from '@tauri-apps/api/core'
 
 export enum BiometryType {
   None = 0,
-  // Apple TouchID or Android fingerprint
+  // Apple TouchID, Android fingerprint or Windows Hello fingerprint reader
   TouchID = 1,
-  // Apple FaceID or Android face authentication
+  // Apple FaceID, Android face authentication or Windows Hello camera
   FaceID = 2,
-  // Android iris authentication
+  // Android iris authentication or Windows Hello iris scanner
   Iris = 3
 }
 
@@ -30,13 +34,95 @@ export interface Status {
     | 'biometryLockout'
     | 'biometryNotAvailable'
     | 'biometryNotEnrolled'
+  /**
+   * The current biometric lockout, if any.
+   * On Android it is only known after an authentication attempt failed with a lockout.
+   * Unsupported on Windows and Linux.
+   */
+  lockout?: Lockout
+  /**
+   * Whether the enrolled biometrics changed since the last successful authentication,
+   * e.g. a fingerprint was added or Face ID was reset. Always `false` on desktop.
+   */
+  enrollmentChanged: boolean
+}
+
+/**
+ * Biometric lockout after too many failed authentication attempts.
+ *
+ * - `temporary`: biometric authentication is disabled for a short period of time.
+ * - `permanent`: biometric authentication is disabled until the user unlocks the device with their passcode, PIN, pattern or password.
+ */
+export type Lockout = 'temporary' | 'permanent'
+
+export interface LockoutEvent {
+  kind: Lockout
+}
+
+export interface AuthenticationFailedEvent {
+  /** Number of attempts left before the prompt is dismissed, based on the `maxAttemps` option. */
+  remainingAttempts: number
+}
+
+export interface CreateKeyOptions {
+  /**
+   * Invalidates the key when a new biometry is enrolled on the device. Defaults to `true`.
+   * Unsupported on Windows.
+   */
+  invalidateOnEnrollment?: boolean
+}
+
+/**
+ * Class of biometric authenticators, as defined by the Android Compatibility Definition.
+ *
+ * - `strong`: class 3 biometrics, which can unlock keys stored in the Android Keystore.
+ * - `weak`: class 2 biometrics, which include less secure face authentication implementations.
+ */
+export type BiometricStrength = 'strong' | 'weak'
+
+/**
+ * Behavior of the negative button of the authentication prompt when device credential fallback is not allowed.
+ *
+ * - `cancel`: cancels the authentication with the `userCancel` error code. On iOS and macOS the fallback button is hidden.
+ * - `fallback`: fails the authentication with the `userFallback` error code so the app can display its own password login.
+ *   On iOS and macOS the fallback button is displayed.
+ */
+export type NegativeButton = 'cancel' | 'fallback'
+
+export interface SignOptions {
+  cancelTitle?: string
+  negativeButton?: NegativeButton
+
+  // iOS options
+  fallbackTitle?: string
+
+  // android options
+  title?: string
+  subtitle?: string
+  confirmationRequired?: boolean
+  maxAttemps?: number
+}
+
+/**
+ * Signature algorithm of a biometry-protected key.
+ *
+ * - `ES256`: ECDSA using the P-256 curve and SHA-256, used on Android and iOS.
+ * - `RS256`: RSASSA-PKCS1-v1_5 using SHA-256, used by Windows Hello.
+ */
+export type KeyAlgorithm = 'ES256' | 'RS256'
+
+export interface KeyInfo {
+  /** DER-encoded X.509 SubjectPublicKeyInfo of the public key. */
+  publicKey: Uint8Array
+  algorithm: KeyAlgorithm
 }
 
 export interface AuthOptions {
   allowDeviceCredential?: boolean
   cancelTitle?: string
+  negativeButton?: NegativeButton
 
-  // iOS options
+  // iOS and macOS options
   fallbackTitle?: string
 
   // android options
@@ -44,6 +130,8 @@ export interface AuthOptions {
   subtitle?: string
   confirmationRequired?: boolean
   maxAttemps?: number
+  /** Defaults to `weak`. Touch ID and Face ID are always strong. */
+  biometricStrength?: BiometricStrength
 }
 
 /**
@@ -55,9 +143,11 @@ export async function checkStatus(): Promise<Status> {
 }
 
 /**
- * Prompts the user for authentication using the system interface (touchID, faceID or Android Iris).
+ * Prompts the user for authentication using the system interface (touchID, faceID, Android Iris or Windows Hello).
  * Rejects if the authentication fails.
  *
+ * On Windows only the `reason` is used, the options are ignored.
+ *
  * ```javascript
  * import { authenticate } from "@tauri-apps/plugin-biometric";
  * await authenticate('Open your wallet');
@@ -75,3 +165,185 @@ export async function authenticate(
     ...options
   })
 }
+
+/**
+ * Creates a hardware-backed key pair that requires biometric authentication for every use,
+ * replacing any existing key with the same name.
+ *
+ * The key is stored in the Android Keystore, the iOS Secure Enclave or as a Windows Hello key credential.
+ *
+ * ```javascript
+ * import { createKey } from "@tauri-apps/plugin-biometric";
+ * const { publicKey, algorithm } = await createKey('wallet');
+ * ```
+ * @param name the key identifier.
+ * @param options
+ * @returns the public key and its signature algorithm.
+ */
+export async function createKey(
+  name: string,
+  options?: CreateKeyOptions
+): Promise<KeyInfo> {
+  const key = await invoke<{ publicKey: number[]; algorithm: KeyAlgorithm }>(
+    'plugin:biometric|create_key',
+    { name, options }
+  )
+  return { publicKey: Uint8Array.from(key.publicKey), algorithm: key.algorithm }
+}
+
+/**
+ * Deletes the key pair with the given name.
+ * @param name the key identifier.
+ */
+export async function deleteKey(name: string): Promise<void> {
+  await invoke('plugin:biometric|delete_key', { name })
+}
+
+/**
+ * Checks whether a key pair with the given name exists.
+ * @param name the key identifier.
+ */
+export async function keyExists(name: string): Promise<boolean> {
+  return await invoke<{ exists: boolean }>('plugin:biometric|key_exists', {
+    name
+  }).then((r) => r.exists)
+}
+
+/**
+ * Prompts the user for biometric authentication and signs the data with the given key.
+ * Rejects if the authentication fails or if the key does not exist.
+ *
+ * ```javascript
+ * import { sign } from "@tauri-apps/plugin-biometric";
+ * const challenge = new TextEncoder().encode('server challenge');
+ * const signature = await sign('wallet', challenge, 'Confirm the transaction');
+ * ```
+ * @param name the key identifier.
+ * @param data the data to sign.
+ * @param reason
+ * @param options
+ * @returns the signature.
+ */
+export async function sign(
+  name: string,
+  data: Uint8Array | number[],
+  reason: string,
+  options?: SignOptions
+): Promise<Uint8Array> {
+  const { signature } = await invoke<{ signature: number[] }>(
+    'plugin:biometric|sign',
+    {
+      name,
+      data: Array.from(data),
+      reason,
+      ...options
+    }
+  )
+  return Uint8Array.from(signature)
+}
+
+/**
+ * Encrypts the data with a secret bound to biometric authentication.
+ * The secret is created the first time it is used and is removed by {@linkcode deleteKey}.
+ *
+ * On Android and Windows encrypting the data prompts the user for biometric authentication.
+ * On iOS only decrypting it requires authentication.
+ *
+ * ```javascript
+ * import { encrypt } from "@tauri-apps/plugin-biometric";
+ * const token = await encrypt('vault', new TextEncoder().encode(password), 'Save your password');
+ * ```
+ * @param name the name of the secret.
+ * @param data the data to encrypt.
+ * @param reason the reason for requesting biometric authentication.
+ * @param options auth configuration.
+ * @returns an opaque token that can be passed to {@linkcode decrypt}.
+ */
+export async function encrypt(
+  name: string,
+  data: Uint8Array | number[],
+  reason: string,
+  options?: SignOptions
+): Promise<Uint8Array> {
+  const res = await invoke<{ data: number[] }>('plugin:biometric|encrypt', {
+    name,
+    data: Array.from(data),
+    reason,
+    ...options
+  })
+  return Uint8Array.from(res.data)
+}
+
+/**
+ * Prompts the user for biometric authentication and decrypts a token returned by {@linkcode encrypt}.
+ *
+ * ```javascript
+ * import { decrypt } from "@tauri-apps/plugin-biometric";
+ * const password = new TextDecoder().decode(await decrypt('vault', token, 'Unlock your password'));
+ * ```
+ * @param name the name of the secret.
+ * @param token the token returned by {@linkcode encrypt}.
+ * @param reason the reason for requesting biometric authentication.
+ * @param options auth configuration.
+ * @returns the decrypted data.
+ */
+export async function decrypt(
+  name: string,
+  token: Uint8Array | number[],
+  reason: string,
+  options?: SignOptions
+): Promise<Uint8Array> {
+  const res = await invoke<{ data: number[] }>('plugin:biometric|decrypt', {
+    name,
+    data: Array.from(token),
+    reason,
+    ...options
+  })
+  return Uint8Array.from(res.data)
+}
+
+/**
+ * Listens to biometric lockouts, so the app can fall back to another authentication method.
+ * Only available on Android and iOS.
+ *
+ * ```javascript
+ * import { onLockout } from "@tauri-apps/plugin-biometric";
+ * await onLockout(({ kind }) => showPasswordLogin(kind));
+ * ```
+ */
+export async function onLockout(
+  cb: (event: LockoutEvent) => void
+): Promise<PluginListener> {
+  return await addPluginListener('biometric', 'lockout', cb)
+}
+
+/**
+ * Listens to changes of the enrolled biometrics, detected when checking the status or before authenticating.
+ * Keys created with `invalidateOnEnrollment` can no longer be used after such a change.
+ * Only available on Android and iOS.
+ *
+ * ```javascript
+ * import { onEnrollmentChange } from "@tauri-apps/plugin-biometric";
+ * await onEnrollmentChange(() => requirePasswordLogin());
+ * ```
+ */
+export async function onEnrollmentChange(
+  cb: () => void
+): Promise<PluginListener> {
+  return await addPluginListener('biometric', 'enrollmentChanged', cb)
+}
+
+/**
+ * Listens to biometrics that were presented but not recognized during an authentication prompt.
+ * Only available on Android.
+ *
+ * ```javascript
+ * import { onAuthenticationFailed } from "@tauri-apps/plugin-biometric";
+ * await onAuthenticationFailed(({ remainingAttempts }) => console.log(remainingAttempts));
+ * ```
+ */
+export async function onAuthenticationFailed(
+  cb: (event: AuthenticationFailedEvent) => void
+): Promise<PluginListener> {
+  return await addPluginListener('biometric', 'authenticationFailed', cb)
+}
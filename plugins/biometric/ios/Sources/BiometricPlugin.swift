@@ -13,22 +13,67 @@ class BiometricStatus {
   let biometryType: LABiometryType
   let errorReason: String?
   let errorCode: String?
+  let lockout: Bool
+  let enrollmentChanged: Bool
 
-  init(available: Bool, biometryType: LABiometryType, errorReason: String?, errorCode: String?) {
+  init(
+    available: Bool, biometryType: LABiometryType, errorReason: String?, errorCode: String?,
+    lockout: Bool, enrollmentChanged: Bool
+  ) {
     self.available = available
     self.biometryType = biometryType
     self.errorReason = errorReason
     self.errorCode = errorCode
+    self.lockout = lockout
+    self.enrollmentChanged = enrollmentChanged
   }
 }
 
+struct LockoutEvent: Encodable {
+  let kind: String
+}
+
+struct EnrollmentChangedEvent: Encodable {}
+
+// UserDefaults key of the biometric enrollment state at the last successful authentication
+let domainStateKey = "app.tauri.biometric.domainState"
+
 struct AuthOptions: Decodable {
   let reason: String
   var allowDeviceCredential: Bool?
   var fallbackTitle: String?
   var cancelTitle: String?
+  var negativeButton: String?
+}
+
+struct CreateKeyOptions: Decodable {
+  var invalidateOnEnrollment: Bool?
+}
+
+struct CreateKeyArgs: Decodable {
+  let name: String
+  var options: CreateKeyOptions?
+}
+
+struct KeyArgs: Decodable {
+  let name: String
 }
 
+struct KeyOperationArgs: Decodable {
+  let name: String
+  let data: [UInt8]
+  let reason: String
+  var fallbackTitle: String?
+  var cancelTitle: String?
+  var negativeButton: String?
+}
+
+// DER header of a SubjectPublicKeyInfo for an uncompressed P-256 public key
+let p256SubjectPublicKeyInfoHeader: [UInt8] = [
+  0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+  0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+]
+
 class BiometricPlugin: Plugin {
   let authenticationErrorCodeMap: [Int: String] = [
     0: "",
@@ -46,8 +91,32 @@ class BiometricPlugin: Plugin {
   ]
 
   var status: BiometricStatus!
+  var enrollmentChangeNotified = false
 
   public override func load(webview: WKWebView) {
+    updateStatus()
+  }
+
+  /// Compares the enrolled biometrics with the state saved after the last successful authentication.
+  /// The context must have evaluated the policy with `canEvaluatePolicy` first.
+  private func enrollmentChanged(_ context: LAContext) -> Bool {
+    guard let domainState = context.evaluatedPolicyDomainState else {
+      return false
+    }
+    guard let savedDomainState = UserDefaults.standard.data(forKey: domainStateKey) else {
+      UserDefaults.standard.set(domainState, forKey: domainStateKey)
+      return false
+    }
+
+    let changed = savedDomainState != domainState
+    if changed && !enrollmentChangeNotified {
+      enrollmentChangeNotified = true
+      try? trigger("enrollmentChanged", data: EnrollmentChangedEvent())
+    }
+    return changed
+  }
+
+  private func updateStatus() {
     let context = LAContext()
     var error: NSError?
     var available = context.canEvaluatePolicy(
@@ -77,27 +146,49 @@ class BiometricPlugin: Plugin {
       available: available,
       biometryType: context.biometryType,
       errorReason: reason,
-      errorCode: errorCode
+      errorCode: errorCode,
+      lockout: error?.code == LAError.biometryLockout.rawValue,
+      enrollmentChanged: enrollmentChanged(context)
     )
   }
 
+  /// Shows or hides the fallback button, which fails the authentication with the `userFallback` error code.
+  private func applyNegativeButton(_ context: LAContext, _ negativeButton: String?) {
+    switch negativeButton {
+    case "cancel":
+      // an empty title hides the fallback button
+      context.localizedFallbackTitle = ""
+    case "fallback":
+      if context.localizedFallbackTitle?.isEmpty ?? false {
+        context.localizedFallbackTitle = nil
+      }
+    default:
+      break
+    }
+  }
+
   @objc func status(_ invoke: Invoke) {
-    if self.status.available {
-      invoke.resolve([
-        "isAvailable": self.status.available,
-        "biometryType": self.status.biometryType.rawValue,
-      ])
-    } else {
-      invoke.resolve([
-        "isAvailable": self.status.available,
-        "biometryType": self.status.biometryType.rawValue,
-        "error": self.status.errorReason ?? "",
-        "errorCode": self.status.errorCode ?? "",
-      ])
+    updateStatus()
+
+    var ret: JsonObject = [
+      "isAvailable": self.status.available,
+      "biometryType": self.status.biometryType.rawValue,
+      "enrollmentChanged": self.status.enrollmentChanged,
+    ]
+    if !self.status.available {
+      ret["error"] = self.status.errorReason ?? ""
+      ret["errorCode"] = self.status.errorCode ?? ""
+    }
+    // the lockout is cleared when the user enters their passcode
+    if self.status.lockout {
+      ret["lockout"] = "permanent"
     }
+    invoke.resolve(ret)
   }
 
   @objc func authenticate(_ invoke: Invoke) throws {
+    updateStatus()
+
     guard self.status.available else {
       invoke.reject(
         self.status.errorReason ?? "",
@@ -122,6 +213,9 @@ class BiometricPlugin: Plugin {
     {
       context.localizedFallbackTitle = nil
     }
+    if !allowDeviceCredential {
+      applyNegativeButton(context, args.negativeButton)
+    }
 
     context.evaluatePolicy(
       allowDeviceCredential
@@ -129,9 +223,16 @@ class BiometricPlugin: Plugin {
       localizedReason: args.reason
     ) { success, error in
       if success {
+        if let domainState = context.evaluatedPolicyDomainState {
+          UserDefaults.standard.set(domainState, forKey: domainStateKey)
+        }
+        self.enrollmentChangeNotified = false
         invoke.resolve()
       } else {
         if let policyError = error as? LAError {
+          if policyError.code == .biometryLockout {
+            try? self.trigger("lockout", data: LockoutEvent(kind: "permanent"))
+          }
           let code = self.authenticationErrorCodeMap[policyError.code.rawValue]
           invoke.reject(policyError.localizedDescription, code: code)
         } else {
@@ -146,6 +247,220 @@ class BiometricPlugin: Plugin {
   }
 }
 
+extension BiometricPlugin {
+  private func keyTag(_ name: String) -> Data {
+    return "app.tauri.biometric.\(name)".data(using: .utf8)!
+  }
+
+  private func keyQuery(_ name: String) -> [String: Any] {
+    return [
+      kSecClass as String: kSecClassKey,
+      kSecAttrApplicationTag as String: keyTag(name),
+      kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+    ]
+  }
+
+  /// Creates an EC P-256 key pair in the Secure Enclave that requires biometric authentication for every use.
+  @objc func createKey(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(CreateKeyArgs.self)
+
+    SecItemDelete(keyQuery(args.name) as CFDictionary)
+
+    var error: Unmanaged<CFError>?
+    guard
+      let privateKey = createEnclaveKey(
+        args.name, invalidateOnEnrollment: args.options?.invalidateOnEnrollment ?? true,
+        error: &error),
+      let publicKey = SecKeyCopyPublicKey(privateKey),
+      let rawPublicKey = SecKeyCopyExternalRepresentation(publicKey, &error) as Data?
+    else {
+      invoke.reject(error?.takeRetainedValue().localizedDescription ?? "Failed to create the key")
+      return
+    }
+
+    invoke.resolve([
+      "publicKey": p256SubjectPublicKeyInfoHeader + [UInt8](rawPublicKey),
+      "algorithm": "ES256",
+    ])
+  }
+
+  private func createEnclaveKey(
+    _ name: String, invalidateOnEnrollment: Bool, error: inout Unmanaged<CFError>?
+  ) -> SecKey? {
+    var flags: SecAccessControlCreateFlags = [.privateKeyUsage]
+    if invalidateOnEnrollment {
+      flags.insert(.biometryCurrentSet)
+    } else {
+      flags.insert(.biometryAny)
+    }
+
+    guard
+      let access = SecAccessControlCreateWithFlags(
+        kCFAllocatorDefault, kSecAttrAccessibleWhenUnlockedThisDeviceOnly, flags, &error)
+    else {
+      return nil
+    }
+
+    let attributes: [String: Any] = [
+      kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+      kSecAttrKeySizeInBits as String: 256,
+      kSecAttrTokenID as String: kSecAttrTokenIDSecureEnclave,
+      kSecPrivateKeyAttrs as String: [
+        kSecAttrIsPermanent as String: true,
+        kSecAttrApplicationTag as String: keyTag(name),
+        kSecAttrAccessControl as String: access,
+      ],
+    ]
+
+    return SecKeyCreateRandomKey(attributes as CFDictionary, &error)
+  }
+
+  /// Loads the private key with the given name, rejecting the invoke if it cannot be found.
+  private func loadKey(_ invoke: Invoke, _ name: String, context: LAContext) -> SecKey? {
+    var query = keyQuery(name)
+    query[kSecReturnRef as String] = true
+    query[kSecUseAuthenticationContext as String] = context
+
+    var item: CFTypeRef?
+    let status = SecItemCopyMatching(query as CFDictionary, &item)
+    guard status == errSecSuccess, let item = item else {
+      if status == errSecItemNotFound {
+        invoke.reject("Key \(name) does not exist", code: "keyNotFound")
+      } else {
+        invoke.reject(SecCopyErrorMessageString(status, nil) as String? ?? "Failed to load the key")
+      }
+      return nil
+    }
+    return (item as! SecKey)
+  }
+
+  private func rejectKeyOperation(
+    _ invoke: Invoke, _ error: Unmanaged<CFError>?, fallbackMessage: String
+  ) {
+    let error = error?.takeRetainedValue() as Error?
+    if let policyError = error as? LAError {
+      invoke.reject(
+        policyError.localizedDescription,
+        code: self.authenticationErrorCodeMap[policyError.code.rawValue])
+    } else {
+      invoke.reject(
+        error?.localizedDescription ?? fallbackMessage,
+        code: self.authenticationErrorCodeMap[LAError.authenticationFailed.rawValue])
+    }
+  }
+
+  private func operationContext(_ args: KeyOperationArgs) -> LAContext {
+    let context = LAContext()
+    context.localizedReason = args.reason
+    context.localizedCancelTitle = args.cancelTitle
+    context.localizedFallbackTitle = args.fallbackTitle
+    applyNegativeButton(context, args.negativeButton)
+    return context
+  }
+
+  @objc func deleteKey(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyArgs.self)
+    SecItemDelete(keyQuery(args.name) as CFDictionary)
+    SecItemDelete(keyQuery(secretKeyName(args.name)) as CFDictionary)
+    invoke.resolve()
+  }
+
+  @objc func keyExists(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyArgs.self)
+    let context = LAContext()
+    // do not prompt the user, we only need to know whether the item exists
+    context.interactionNotAllowed = true
+    var query = keyQuery(args.name)
+    query[kSecUseAuthenticationContext as String] = context
+    let status = SecItemCopyMatching(query as CFDictionary, nil)
+    invoke.resolve(["exists": status == errSecSuccess || status == errSecInteractionNotAllowed])
+  }
+
+  /// Prompts the user for biometric authentication and signs the data with the given key.
+  @objc func sign(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyOperationArgs.self)
+
+    guard let key = loadKey(invoke, args.name, context: operationContext(args)) else {
+      return
+    }
+
+    DispatchQueue.global(qos: .userInitiated).async {
+      var error: Unmanaged<CFError>?
+      guard
+        let signature = SecKeyCreateSignature(
+          key, .ecdsaSignatureMessageX962SHA256, Data(args.data) as CFData, &error) as Data?
+      else {
+        self.rejectKeyOperation(invoke, error, fallbackMessage: "Failed to sign the data")
+        return
+      }
+      invoke.resolve(["signature": [UInt8](signature)])
+    }
+  }
+
+  private func secretKeyName(_ name: String) -> String {
+    return "\(name).secret"
+  }
+
+  /// Encrypts the data with the public part of the Secure Enclave key backing the secret with the given name.
+  /// The key is created if it does not exist yet. Encrypting does not require user authentication.
+  @objc func encrypt(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyOperationArgs.self)
+    let name = secretKeyName(args.name)
+
+    let context = LAContext()
+    context.interactionNotAllowed = true
+    var query = keyQuery(name)
+    query[kSecReturnRef as String] = true
+    query[kSecUseAuthenticationContext as String] = context
+
+    var error: Unmanaged<CFError>?
+    var item: CFTypeRef?
+    let privateKey: SecKey?
+    if SecItemCopyMatching(query as CFDictionary, &item) == errSecSuccess, let item = item {
+      privateKey = (item as! SecKey)
+    } else {
+      privateKey = createEnclaveKey(name, invalidateOnEnrollment: true, error: &error)
+    }
+
+    guard let privateKey = privateKey, let publicKey = SecKeyCopyPublicKey(privateKey),
+      let token = SecKeyCreateEncryptedData(
+        publicKey, .eciesEncryptionCofactorVariableIVX963SHA256AESGCM, Data(args.data) as CFData,
+        &error) as Data?
+    else {
+      invoke.reject(
+        error?.takeRetainedValue().localizedDescription ?? "Failed to encrypt the data",
+        code: "encryptionFailed")
+      return
+    }
+
+    invoke.resolve(["data": [UInt8](token)])
+  }
+
+  /// Prompts the user for biometric authentication and decrypts a token returned by `encrypt`.
+  @objc func decrypt(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyOperationArgs.self)
+
+    guard
+      let key = loadKey(invoke, secretKeyName(args.name), context: operationContext(args))
+    else {
+      return
+    }
+
+    DispatchQueue.global(qos: .userInitiated).async {
+      var error: Unmanaged<CFError>?
+      guard
+        let data = SecKeyCreateDecryptedData(
+          key, .eciesEncryptionCofactorVariableIVX963SHA256AESGCM, Data(args.data) as CFData,
+          &error) as Data?
+      else {
+        self.rejectKeyOperation(invoke, error, fallbackMessage: "Failed to decrypt the data")
+        return
+      }
+      invoke.resolve(["data": [UInt8](data)])
+    }
+  }
+}
+
 @_cdecl("init_plugin_biometric")
 func initPlugin() -> Plugin {
   return BiometricPlugin()
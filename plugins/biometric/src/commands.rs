@@ -0,0 +1,98 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::de::DeserializeOwned;
+use tauri::{
+    command,
+    ipc::{InvokeBody, Request},
+    AppHandle, Runtime,
+};
+
+use crate::{
+    AuthenticatePayload, BiometricExt, CreateKeyOptions, DataResponse, Error, KeyExistsResponse,
+    KeyInfo, KeyOperationPayload, Result, SignResponse, Status,
+};
+
+// the options are flattened in the payload to match the arguments expected by the mobile plugins
+fn flattened_payload<T: DeserializeOwned>(request: &Request<'_>) -> Result<T> {
+    match request.body() {
+        InvokeBody::Json(payload) => T::deserialize(payload).map_err(Into::into),
+        _ => Err(Error::InvalidPayload),
+    }
+}
+
+#[command]
+pub(crate) async fn status<R: Runtime>(app: AppHandle<R>) -> Result<Status> {
+    app.biometric().status()
+}
+
+#[command]
+pub(crate) async fn authenticate<R: Runtime>(
+    app: AppHandle<R>,
+    request: Request<'_>,
+) -> Result<()> {
+    let payload: AuthenticatePayload = flattened_payload(&request)?;
+    app.biometric()
+        .authenticate(payload.reason, payload.options)
+}
+
+#[command]
+pub(crate) async fn create_key<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+    options: Option<CreateKeyOptions>,
+) -> Result<KeyInfo> {
+    app.biometric()
+        .create_key(name, options.unwrap_or_default())
+}
+
+#[command]
+pub(crate) async fn delete_key<R: Runtime>(app: AppHandle<R>, name: String) -> Result<()> {
+    app.biometric().delete_key(name)
+}
+
+// the responses match the payloads resolved by the mobile plugins
+
+#[command]
+pub(crate) async fn key_exists<R: Runtime>(
+    app: AppHandle<R>,
+    name: String,
+) -> Result<KeyExistsResponse> {
+    app.biometric()
+        .key_exists(name)
+        .map(|exists| KeyExistsResponse { exists })
+}
+
+#[command]
+pub(crate) async fn sign<R: Runtime>(
+    app: AppHandle<R>,
+    request: Request<'_>,
+) -> Result<SignResponse> {
+    let payload: KeyOperationPayload = flattened_payload(&request)?;
+    app.biometric()
+        .sign(payload.name, payload.data, payload.reason, payload.options)
+        .map(|signature| SignResponse { signature })
+}
+
+#[command]
+pub(crate) async fn encrypt<R: Runtime>(
+    app: AppHandle<R>,
+    request: Request<'_>,
+) -> Result<DataResponse> {
+    let payload: KeyOperationPayload = flattened_payload(&request)?;
+    app.biometric()
+        .encrypt(payload.name, payload.data, payload.reason, payload.options)
+        .map(|data| DataResponse { data })
+}
+
+#[command]
+pub(crate) async fn decrypt<R: Runtime>(
+    app: AppHandle<R>,
+    request: Request<'_>,
+) -> Result<DataResponse> {
+    let payload: KeyOperationPayload = flattened_payload(&request)?;
+    app.biometric()
+        .decrypt(payload.name, payload.data, payload.reason, payload.options)
+        .map(|data| DataResponse { data })
+}
@@ -0,0 +1,642 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::de::DeserializeOwned;
+use tauri::{plugin::PluginApi, AppHandle, Runtime};
+
+use crate::models::*;
+
+pub fn init<R: Runtime, C: DeserializeOwned>(
+    app: &AppHandle<R>,
+    _api: PluginApi<R, C>,
+) -> crate::Result<Biometric<R>> {
+    Ok(Biometric(app.clone()))
+}
+
+/// Access to the biometric APIs.
+pub struct Biometric<R: Runtime>(AppHandle<R>);
+
+impl<R: Runtime> Biometric<R> {
+    /// Checks if the biometric authentication is available.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses Windows Hello.
+    /// - **macOS:** Uses the LocalAuthentication framework (Touch ID).
+    /// - **Linux:** Unsupported, always reports biometry as unavailable.
+    pub fn status(&self) -> crate::Result<Status> {
+        imp::status()
+    }
+
+    /// Prompts the user for authentication using the system interface.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Only the `reason` is used, [`AuthOptions`] are ignored.
+    /// - **Linux:** Unsupported.
+    pub fn authenticate(&self, reason: String, options: AuthOptions) -> crate::Result<()> {
+        imp::authenticate(&self.0, &reason, &options)
+    }
+
+    /// Creates a key pair protected by biometric authentication, replacing any existing key with the same name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses a Windows Hello key credential (RSA 2048). Windows Hello prompts the user on creation.
+    /// - **macOS / Linux:** Unsupported.
+    pub fn create_key(&self, name: String, options: CreateKeyOptions) -> crate::Result<KeyInfo> {
+        imp::create_key(&name, &options)
+    }
+
+    /// Deletes the key pair with the given name and the secret created by [`Self::encrypt`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Linux:** Unsupported.
+    pub fn delete_key(&self, name: String) -> crate::Result<()> {
+        imp::delete_key(&name)
+    }
+
+    /// Checks whether a key pair with the given name exists.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Linux:** Unsupported.
+    pub fn key_exists(&self, name: String) -> crate::Result<bool> {
+        imp::key_exists(&name)
+    }
+
+    /// Prompts the user for biometric authentication and signs `data` with the key pair with the given name.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The prompt is displayed by Windows Hello, `reason` and [`AuthOptions`] are ignored.
+    /// - **macOS / Linux:** Unsupported.
+    pub fn sign(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        imp::sign(&name, &data, &reason, &options)
+    }
+
+    /// Prompts the user for biometric authentication and encrypts `data` with the secret key with the given name,
+    /// creating the key if it does not exist yet.
+    ///
+    /// Returns an opaque token that can only be decrypted with [`Self::decrypt`] on the same device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The wrapping key is derived from a Windows Hello key credential signature.
+    ///   The prompt is displayed by Windows Hello, `reason` and [`AuthOptions`] are ignored.
+    /// - **macOS / Linux:** Unsupported.
+    pub fn encrypt(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        imp::encrypt(&name, &data, &reason, &options)
+    }
+
+    /// Prompts the user for biometric authentication and decrypts a token returned by [`Self::encrypt`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The prompt is displayed by Windows Hello, `reason` and [`AuthOptions`] are ignored.
+    /// - **macOS / Linux:** Unsupported.
+    pub fn decrypt(
+        &self,
+        name: String,
+        token: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        imp::decrypt(&name, &token, &reason, &options)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use aes_gcm::{
+        aead::{Aead, AeadCore, KeyInit, OsRng},
+        Aes256Gcm, Nonce,
+    };
+    use sha2::{Digest, Sha256};
+    use tauri::{AppHandle, Runtime};
+    use windows::{
+        core::{factory, Array, HSTRING},
+        Foundation::IAsyncOperation,
+        Security::{
+            Credentials::{
+                KeyCredential, KeyCredentialCreationOption, KeyCredentialManager,
+                KeyCredentialStatus,
+                UI::{
+                    UserConsentVerificationResult, UserConsentVerifier,
+                    UserConsentVerifierAvailability,
+                },
+            },
+            Cryptography::CryptographicBuffer,
+        },
+        Storage::Streams::IBuffer,
+        Win32::{
+            Devices::BiometricFramework::{
+                WinBioEnumBiometricUnits, WinBioFree, WINBIO_TYPE_FACIAL_FEATURES,
+                WINBIO_TYPE_FINGERPRINT, WINBIO_TYPE_IRIS, WINBIO_UNIT_SCHEMA,
+            },
+            Foundation::HWND,
+            System::WinRT::IUserConsentVerifierInterop,
+        },
+    };
+
+    use crate::{
+        AuthOptions, BiometryType, CreateKeyOptions, Error, KeyAlgorithm, KeyInfo, Status,
+    };
+
+    pub fn status() -> crate::Result<Status> {
+        let availability = UserConsentVerifier::CheckAvailabilityAsync()?.get()?;
+        let error = match availability {
+            UserConsentVerifierAvailability::Available => None,
+            UserConsentVerifierAvailability::DeviceNotPresent => Some((
+                "No Windows Hello device is present.",
+                "biometryNotAvailable",
+            )),
+            UserConsentVerifierAvailability::NotConfiguredForUser => Some((
+                "Windows Hello is not configured for the current user.",
+                "biometryNotEnrolled",
+            )),
+            UserConsentVerifierAvailability::DisabledByPolicy => Some((
+                "Windows Hello is disabled by group policy.",
+                "biometryNotAvailable",
+            )),
+            UserConsentVerifierAvailability::DeviceBusy => {
+                Some(("The Windows Hello device is busy.", "biometryNotAvailable"))
+            }
+            _ => Some(("Unknown Windows Hello state.", "biometryNotAvailable")),
+        };
+
+        Ok(Status {
+            is_available: error.is_none(),
+            biometry_type: biometry_type(),
+            error: error.map(|(message, _)| message.into()),
+            error_code: error.map(|(_, code)| code.into()),
+            lockout: None,
+            enrollment_changed: false,
+        })
+    }
+
+    /// Windows Hello does not expose the sensor type, so we look for the first
+    /// biometric unit registered in the Windows Biometric Framework.
+    fn biometry_type() -> BiometryType {
+        for (factor, biometry_type) in [
+            (WINBIO_TYPE_FINGERPRINT, BiometryType::TouchID),
+            (WINBIO_TYPE_FACIAL_FEATURES, BiometryType::FaceID),
+            (WINBIO_TYPE_IRIS, BiometryType::Iris),
+        ] {
+            let mut units: *mut WINBIO_UNIT_SCHEMA = std::ptr::null_mut();
+            let mut count = 0;
+            if unsafe { WinBioEnumBiometricUnits(factor, &mut units, &mut count) }.is_ok() {
+                if !units.is_null() {
+                    let _ = unsafe { WinBioFree(units as _) };
+                }
+                if count > 0 {
+                    return biometry_type;
+                }
+            }
+        }
+        BiometryType::None
+    }
+
+    pub fn authenticate<R: Runtime>(
+        app: &AppHandle<R>,
+        reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<()> {
+        let message = HSTRING::from(reason);
+
+        // parent the prompt to the focused window so it is not displayed behind the app
+        let hwnd = app
+            .webview_windows()
+            .values()
+            .find(|w| w.is_focused().unwrap_or(false))
+            .and_then(|w| w.hwnd().ok());
+        let operation: IAsyncOperation<UserConsentVerificationResult> = match hwnd {
+            Some(hwnd) => {
+                let interop = factory::<UserConsentVerifier, IUserConsentVerifierInterop>()?;
+                unsafe { interop.RequestVerificationForWindowAsync(HWND(hwnd.0 as _), &message) }?
+            }
+            None => UserConsentVerifier::RequestVerificationAsync(&message)?,
+        };
+
+        let (message, code) = match operation.get()? {
+            UserConsentVerificationResult::Verified => return Ok(()),
+            UserConsentVerificationResult::DeviceNotPresent => (
+                "No Windows Hello device is present.",
+                "biometryNotAvailable",
+            ),
+            UserConsentVerificationResult::NotConfiguredForUser => (
+                "Windows Hello is not configured for the current user.",
+                "biometryNotEnrolled",
+            ),
+            UserConsentVerificationResult::DisabledByPolicy => (
+                "Windows Hello is disabled by group policy.",
+                "biometryNotAvailable",
+            ),
+            UserConsentVerificationResult::DeviceBusy => {
+                ("The Windows Hello device is busy.", "systemCancel")
+            }
+            UserConsentVerificationResult::RetriesExhausted => {
+                ("Too many failed attempts.", "biometryLockout")
+            }
+            UserConsentVerificationResult::Canceled => ("The user canceled.", "userCancel"),
+            _ => ("Authentication failed.", "authenticationFailed"),
+        };
+
+        Err(Error::Authentication {
+            code,
+            message: message.into(),
+        })
+    }
+
+    fn key_error(status: KeyCredentialStatus) -> Error {
+        let (message, code) = match status {
+            KeyCredentialStatus::NotFound => ("The key does not exist.", "keyNotFound"),
+            KeyCredentialStatus::UserCanceled => ("The user canceled.", "userCancel"),
+            KeyCredentialStatus::UserPrefersPassword => {
+                ("The user prefers to use a password.", "userFallback")
+            }
+            KeyCredentialStatus::SecurityDeviceLocked => {
+                ("The security device is locked.", "biometryLockout")
+            }
+            _ => (
+                "Windows Hello key operation failed.",
+                "authenticationFailed",
+            ),
+        };
+        Error::Authentication {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn buffer_to_vec(buffer: &IBuffer) -> crate::Result<Vec<u8>> {
+        let mut array = Array::<u8>::new();
+        CryptographicBuffer::CopyToByteArray(buffer, &mut array)?;
+        Ok(array.to_vec())
+    }
+
+    pub fn create_key(name: &str, _options: &CreateKeyOptions) -> crate::Result<KeyInfo> {
+        let result = KeyCredentialManager::RequestCreateAsync(
+            &HSTRING::from(name),
+            KeyCredentialCreationOption::ReplaceExisting,
+        )?
+        .get()?;
+        let status = result.Status()?;
+        if status != KeyCredentialStatus::Success {
+            return Err(key_error(status));
+        }
+        let public_key = result
+            .Credential()?
+            .RetrievePublicKeyWithDefaultBlobType()?;
+        Ok(KeyInfo {
+            public_key: buffer_to_vec(&public_key)?,
+            algorithm: KeyAlgorithm::RS256,
+        })
+    }
+
+    pub fn delete_key(name: &str) -> crate::Result<()> {
+        KeyCredentialManager::DeleteAsync(&HSTRING::from(name))?.get()?;
+        // the secret used by `encrypt` only exists once it has been used
+        if let Ok(operation) =
+            KeyCredentialManager::DeleteAsync(&HSTRING::from(secret_credential_name(name)))
+        {
+            let _ = operation.get();
+        }
+        Ok(())
+    }
+
+    pub fn key_exists(name: &str) -> crate::Result<bool> {
+        let result = KeyCredentialManager::OpenAsync(&HSTRING::from(name))?.get()?;
+        match result.Status()? {
+            KeyCredentialStatus::Success => Ok(true),
+            KeyCredentialStatus::NotFound => Ok(false),
+            status => Err(key_error(status)),
+        }
+    }
+
+    fn open_credential(name: &str) -> crate::Result<KeyCredential> {
+        let result = KeyCredentialManager::OpenAsync(&HSTRING::from(name))?.get()?;
+        let status = result.Status()?;
+        if status != KeyCredentialStatus::Success {
+            return Err(key_error(status));
+        }
+        result.Credential().map_err(Into::into)
+    }
+
+    fn sign_with(credential: &KeyCredential, data: &[u8]) -> crate::Result<Vec<u8>> {
+        let data = CryptographicBuffer::CreateFromByteArray(data)?;
+        let result = credential.RequestSignAsync(&data)?.get()?;
+        let status = result.Status()?;
+        if status != KeyCredentialStatus::Success {
+            return Err(key_error(status));
+        }
+        buffer_to_vec(&result.Result()?)
+    }
+
+    pub fn sign(
+        name: &str,
+        data: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        sign_with(&open_credential(name)?, data)
+    }
+
+    /// Data signed to derive the secret key. Windows Hello keys are RSA PKCS#1 v1.5 keys,
+    /// so signing the same data always produces the same signature.
+    const SECRET_KEY_CHALLENGE: &[u8] = b"tauri-plugin-biometric secret key";
+    const NONCE_SIZE: usize = 12;
+
+    fn secret_credential_name(name: &str) -> String {
+        format!("{name}.secret")
+    }
+
+    fn secret_cipher(credential: &KeyCredential) -> crate::Result<Aes256Gcm> {
+        let signature = sign_with(credential, SECRET_KEY_CHALLENGE)?;
+        let key = Sha256::digest(signature);
+        Ok(Aes256Gcm::new(&key))
+    }
+
+    pub fn encrypt(
+        name: &str,
+        data: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        let credential_name = HSTRING::from(secret_credential_name(name));
+        let result = KeyCredentialManager::OpenAsync(&credential_name)?.get()?;
+        let credential = match result.Status()? {
+            KeyCredentialStatus::Success => result.Credential()?,
+            KeyCredentialStatus::NotFound => {
+                let result = KeyCredentialManager::RequestCreateAsync(
+                    &credential_name,
+                    KeyCredentialCreationOption::FailIfExists,
+                )?
+                .get()?;
+                let status = result.Status()?;
+                if status != KeyCredentialStatus::Success {
+                    return Err(key_error(status));
+                }
+                result.Credential()?
+            }
+            status => return Err(key_error(status)),
+        };
+
+        let cipher = secret_cipher(&credential)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, data)
+            .map_err(|_| Error::Authentication {
+                code: "encryptionFailed",
+                message: "Failed to encrypt the data.".into(),
+            })?;
+
+        let mut token = nonce.to_vec();
+        token.extend(ciphertext);
+        Ok(token)
+    }
+
+    pub fn decrypt(
+        name: &str,
+        token: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        let invalid_token = || Error::Authentication {
+            code: "invalidToken",
+            message: "The token is invalid or was encrypted with another key.".into(),
+        };
+        if token.len() < NONCE_SIZE {
+            return Err(invalid_token());
+        }
+        let (nonce, ciphertext) = token.split_at(NONCE_SIZE);
+
+        let credential = open_credential(&secret_credential_name(name))?;
+        secret_cipher(&credential)?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| invalid_token())
+    }
+}
+
+#[cfg(not(windows))]
+mod unsupported_keys {
+    use crate::{AuthOptions, CreateKeyOptions, Error, KeyInfo};
+
+    pub fn create_key(_name: &str, _options: &CreateKeyOptions) -> crate::Result<KeyInfo> {
+        Err(Error::UnsupportedPlatform)
+    }
+
+    pub fn delete_key(_name: &str) -> crate::Result<()> {
+        Err(Error::UnsupportedPlatform)
+    }
+
+    pub fn key_exists(_name: &str) -> crate::Result<bool> {
+        Err(Error::UnsupportedPlatform)
+    }
+
+    pub fn sign(
+        _name: &str,
+        _data: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        Err(Error::UnsupportedPlatform)
+    }
+
+    pub fn encrypt(
+        _name: &str,
+        _data: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        Err(Error::UnsupportedPlatform)
+    }
+
+    pub fn decrypt(
+        _name: &str,
+        _token: &[u8],
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        Err(Error::UnsupportedPlatform)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::sync::mpsc::channel;
+
+    use block2::RcBlock;
+    use objc2::runtime::Bool;
+    use objc2_foundation::{NSError, NSString};
+    use objc2_local_authentication::{LAContext, LAPolicy};
+    use tauri::{AppHandle, Runtime};
+
+    use crate::{AuthOptions, BiometryType, Error, Lockout, NegativeButton, Status};
+
+    pub use super::unsupported_keys::*;
+
+    /// Maps `LAError` codes to the error codes used by the iOS plugin.
+    fn error_code(code: isize) -> &'static str {
+        match code {
+            -1 => "authenticationFailed",
+            -2 => "userCancel",
+            -3 => "userFallback",
+            -4 => "systemCancel",
+            -5 => "passcodeNotSet",
+            -7 => "biometryNotEnrolled",
+            -8 => "biometryLockout",
+            -9 => "appCancel",
+            -10 => "invalidContext",
+            -1004 => "notInteractive",
+            _ => "biometryNotAvailable",
+        }
+    }
+
+    fn to_error(error: &NSError) -> Error {
+        Error::Authentication {
+            code: error_code(error.code()),
+            message: error.localizedDescription().to_string(),
+        }
+    }
+
+    pub fn status() -> crate::Result<Status> {
+        let context = unsafe { LAContext::new() };
+        let result = unsafe {
+            context.canEvaluatePolicy_error(LAPolicy::DeviceOwnerAuthenticationWithBiometrics)
+        };
+        // `biometryType` is only set after `canEvaluatePolicy` is called
+        let biometry_type = match unsafe { context.biometryType() }.0 {
+            1 => BiometryType::TouchID,
+            2 => BiometryType::FaceID,
+            _ => BiometryType::None,
+        };
+
+        Ok(match result {
+            Ok(()) => Status {
+                is_available: true,
+                biometry_type,
+                error: None,
+                error_code: None,
+                lockout: None,
+                enrollment_changed: false,
+            },
+            Err(error) => Status {
+                is_available: false,
+                biometry_type,
+                error: Some(error.localizedDescription().to_string()),
+                error_code: Some(error_code(error.code()).into()),
+                // `LAError.biometryLockout` is cleared when the user enters their password
+                lockout: (error.code() == -8).then_some(Lockout::Permanent),
+                enrollment_changed: false,
+            },
+        })
+    }
+
+    pub fn authenticate<R: Runtime>(
+        _app: &AppHandle<R>,
+        reason: &str,
+        options: &AuthOptions,
+    ) -> crate::Result<()> {
+        let context = unsafe { LAContext::new() };
+
+        let cancel_title = options.cancel_title.as_deref().map(NSString::from_str);
+        // force system default fallback title if an empty string is provided (the OS hides the fallback button in this case)
+        let fallback_title = match (options.allow_device_credential, options.negative_button) {
+            (false, Some(NegativeButton::Cancel)) => Some(""),
+            (false, Some(NegativeButton::Fallback)) | (true, _) => {
+                options.fallback_title.as_deref().filter(|t| !t.is_empty())
+            }
+            (false, None) => options.fallback_title.as_deref(),
+        }
+        .map(NSString::from_str);
+        unsafe {
+            context.setLocalizedCancelTitle(cancel_title.as_deref());
+            context.setLocalizedFallbackTitle(fallback_title.as_deref());
+        }
+
+        let policy = if options.allow_device_credential {
+            LAPolicy::DeviceOwnerAuthentication
+        } else {
+            LAPolicy::DeviceOwnerAuthenticationWithBiometrics
+        };
+
+        let (tx, rx) = channel();
+        let reply = RcBlock::new(move |success: Bool, error: *mut NSError| {
+            let result = if success.as_bool() {
+                Ok(())
+            } else {
+                match unsafe { error.as_ref() } {
+                    Some(error) => Err(to_error(error)),
+                    None => Err(Error::Authentication {
+                        code: "authenticationFailed",
+                        message: "Unknown error".into(),
+                    }),
+                }
+            };
+            let _ = tx.send(result);
+        });
+
+        unsafe {
+            context.evaluatePolicy_localizedReason_reply(
+                policy,
+                &NSString::from_str(reason),
+                &reply,
+            )
+        };
+
+        rx.recv().unwrap_or_else(|_| {
+            Err(Error::Authentication {
+                code: "systemCancel",
+                message: "The authentication reply was dropped".into(),
+            })
+        })
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    use tauri::{AppHandle, Runtime};
+
+    use crate::{AuthOptions, BiometryType, Error, Status};
+
+    pub use super::unsupported_keys::*;
+
+    const UNSUPPORTED: &str = "Biometric authentication is not supported on this platform.";
+
+    pub fn status() -> crate::Result<Status> {
+        Ok(Status {
+            is_available: false,
+            biometry_type: BiometryType::None,
+            error: Some(UNSUPPORTED.into()),
+            error_code: Some("biometryNotAvailable".into()),
+            lockout: None,
+            enrollment_changed: false,
+        })
+    }
+
+    pub fn authenticate<R: Runtime>(
+        _app: &AppHandle<R>,
+        _reason: &str,
+        _options: &AuthOptions,
+    ) -> crate::Result<()> {
+        Err(Error::Authentication {
+            code: "biometryNotAvailable",
+            message: UNSUPPORTED.into(),
+        })
+    }
+}
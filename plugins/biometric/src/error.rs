@@ -10,9 +10,22 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
     #[cfg(mobile)]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
+    #[cfg(windows)]
+    #[error(transparent)]
+    Windows(#[from] windows::core::Error),
+    /// Authentication failed or could not be performed. `code` matches the error codes of the mobile plugins.
+    #[error("{message}")]
+    Authentication { code: &'static str, message: String },
+    #[error("unexpected invoke payload")]
+    InvalidPayload,
+    #[cfg(desktop)]
+    #[error("this feature is not supported on this platform")]
+    UnsupportedPlatform,
 }
 
 impl Serialize for Error {
@@ -2,48 +2,29 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-#![cfg(mobile)]
-
-use serde::Serialize;
 use tauri::{
-    plugin::{Builder, PluginHandle, TauriPlugin},
+    plugin::{Builder, TauriPlugin},
     Manager, Runtime,
 };
 
 pub use models::*;
 
+#[cfg(desktop)]
+mod commands;
+#[cfg(desktop)]
+mod desktop;
+#[cfg(mobile)]
+mod mobile;
+
 mod error;
 mod models;
 
 pub use error::{Error, Result};
 
-#[cfg(target_os = "android")]
-const PLUGIN_IDENTIFIER: &str = "app.tauri.biometric";
-
-#[cfg(target_os = "ios")]
-tauri::ios_plugin_binding!(init_plugin_biometric);
-
-/// Access to the biometric APIs.
-pub struct Biometric<R: Runtime>(PluginHandle<R>);
-
-#[derive(Serialize)]
-struct AuthenticatePayload {
-    reason: String,
-    #[serde(flatten)]
-    options: AuthOptions,
-}
-
-impl<R: Runtime> Biometric<R> {
-    pub fn status(&self) -> crate::Result<Status> {
-        self.0.run_mobile_plugin("status", ()).map_err(Into::into)
-    }
-
-    pub fn authenticate(&self, reason: String, options: AuthOptions) -> crate::Result<()> {
-        self.0
-            .run_mobile_plugin("authenticate", AuthenticatePayload { reason, options })
-            .map_err(Into::into)
-    }
-}
+#[cfg(desktop)]
+pub use desktop::Biometric;
+#[cfg(mobile)]
+pub use mobile::Biometric;
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the biometric APIs.
 pub trait BiometricExt<R: Runtime> {
@@ -58,13 +39,28 @@ impl<R: Runtime, T: Manager<R>> crate::BiometricExt<R> for T {
 
 /// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("biometric")
+    let builder = Builder::new("biometric");
+
+    // on mobile the commands are handled by the Kotlin and Swift plugins
+    #[cfg(desktop)]
+    let builder = builder.invoke_handler(tauri::generate_handler![
+        commands::authenticate,
+        commands::status,
+        commands::create_key,
+        commands::delete_key,
+        commands::key_exists,
+        commands::sign,
+        commands::encrypt,
+        commands::decrypt
+    ]);
+
+    builder
         .setup(|app, api| {
-            #[cfg(target_os = "android")]
-            let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "BiometricPlugin")?;
-            #[cfg(target_os = "ios")]
-            let handle = api.register_ios_plugin(init_plugin_biometric)?;
-            app.manage(Biometric(handle));
+            #[cfg(mobile)]
+            let biometric = mobile::init(app, api)?;
+            #[cfg(desktop)]
+            let biometric = desktop::init(app, api)?;
+            app.manage(biometric);
             Ok(())
         })
         .build()
@@ -0,0 +1,126 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::de::DeserializeOwned;
+use tauri::{
+    plugin::{PluginApi, PluginHandle},
+    AppHandle, Runtime,
+};
+
+use crate::models::*;
+
+#[cfg(target_os = "android")]
+const PLUGIN_IDENTIFIER: &str = "app.tauri.biometric";
+
+#[cfg(target_os = "ios")]
+tauri::ios_plugin_binding!(init_plugin_biometric);
+
+// initializes the Kotlin or Swift plugin classes
+pub fn init<R: Runtime, C: DeserializeOwned>(
+    _app: &AppHandle<R>,
+    api: PluginApi<R, C>,
+) -> crate::Result<Biometric<R>> {
+    #[cfg(target_os = "android")]
+    let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "BiometricPlugin")?;
+    #[cfg(target_os = "ios")]
+    let handle = api.register_ios_plugin(init_plugin_biometric)?;
+    Ok(Biometric(handle))
+}
+
+/// Access to the biometric APIs.
+pub struct Biometric<R: Runtime>(PluginHandle<R>);
+
+impl<R: Runtime> Biometric<R> {
+    pub fn status(&self) -> crate::Result<Status> {
+        self.0.run_mobile_plugin("status", ()).map_err(Into::into)
+    }
+
+    pub fn authenticate(&self, reason: String, options: AuthOptions) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("authenticate", AuthenticatePayload { reason, options })
+            .map_err(Into::into)
+    }
+
+    pub fn create_key(&self, name: String, options: CreateKeyOptions) -> crate::Result<KeyInfo> {
+        self.0
+            .run_mobile_plugin("createKey", CreateKeyPayload { name, options })
+            .map_err(Into::into)
+    }
+
+    pub fn delete_key(&self, name: String) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("deleteKey", KeyPayload { name })
+            .map_err(Into::into)
+    }
+
+    pub fn key_exists(&self, name: String) -> crate::Result<bool> {
+        self.0
+            .run_mobile_plugin::<KeyExistsResponse>("keyExists", KeyPayload { name })
+            .map(|r| r.exists)
+            .map_err(Into::into)
+    }
+
+    pub fn sign(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        self.0
+            .run_mobile_plugin::<SignResponse>(
+                "sign",
+                KeyOperationPayload {
+                    name,
+                    data,
+                    reason,
+                    options,
+                },
+            )
+            .map(|r| r.signature)
+            .map_err(Into::into)
+    }
+
+    pub fn encrypt(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        self.0
+            .run_mobile_plugin::<DataResponse>(
+                "encrypt",
+                KeyOperationPayload {
+                    name,
+                    data,
+                    reason,
+                    options,
+                },
+            )
+            .map(|r| r.data)
+            .map_err(Into::into)
+    }
+
+    pub fn decrypt(
+        &self,
+        name: String,
+        token: Vec<u8>,
+        reason: String,
+        options: AuthOptions,
+    ) -> crate::Result<Vec<u8>> {
+        self.0
+            .run_mobile_plugin::<DataResponse>(
+                "decrypt",
+                KeyOperationPayload {
+                    name,
+                    data: token,
+                    reason,
+                    options,
+                },
+            )
+            .map(|r| r.data)
+            .map_err(Into::into)
+    }
+}
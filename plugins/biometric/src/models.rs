@@ -4,14 +4,14 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct AuthOptions {
-    /// Enables authentication using the device's password. This feature is available on both Android and iOS.
+    /// Enables authentication using the device's password. This feature is available on Android, iOS and macOS.
     pub allow_device_credential: bool,
-    /// Label for the Cancel button. This feature is available on both Android and iOS.
+    /// Label for the Cancel button. This feature is available on Android, iOS and macOS.
     pub cancel_title: Option<String>,
-    /// Specifies the text displayed on the fallback button if biometric authentication fails. This feature is available iOS only.
+    /// Specifies the text displayed on the fallback button if biometric authentication fails. This feature is available on iOS and macOS only.
     pub fallback_title: Option<String>,
     /// Title indicating the purpose of biometric verification. This feature is available Android only.
     pub title: Option<String>,
@@ -19,21 +19,157 @@ pub struct AuthOptions {
     pub subtitle: Option<String>,
     /// Specifies whether additional user confirmation is required, such as pressing a button after successful biometric authentication. This feature is available Android only.
     pub confirmation_required: Option<bool>,
+    /// Class of the biometric authenticators allowed to authenticate the user. Defaults to [`BiometricStrength::Weak`]. This feature is available on Android only, Touch ID and Face ID are always strong.
+    pub biometric_strength: Option<BiometricStrength>,
+    /// Behavior of the negative button when `allow_device_credential` is disabled. This feature is available on Android, iOS and macOS.
+    pub negative_button: Option<NegativeButton>,
+}
+
+/// Class of biometric authenticators, as defined by the Android Compatibility Definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BiometricStrength {
+    /// Class 3 biometrics, which can unlock keys stored in the Android Keystore.
+    Strong,
+    /// Class 2 biometrics, which include less secure face authentication implementations.
+    Weak,
+}
+
+/// Behavior of the negative button of the authentication prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NegativeButton {
+    /// Cancels the authentication with the `userCancel` error code.
+    /// On iOS and macOS the fallback button is hidden.
+    Cancel,
+    /// Fails the authentication with the `userFallback` error code so the app can display its own password login.
+    /// On iOS and macOS the fallback button is displayed.
+    Fallback,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AuthenticatePayload {
+    pub reason: String,
+    #[serde(flatten)]
+    pub options: AuthOptions,
 }
 
-#[derive(Debug, Clone, serde_repr::Deserialize_repr)]
+#[derive(Debug, Clone, serde_repr::Deserialize_repr, serde_repr::Serialize_repr)]
 #[repr(u8)]
 pub enum BiometryType {
     None = 0,
+    /// Apple Touch ID, Android fingerprint or a Windows Hello fingerprint reader.
     TouchID = 1,
+    /// Apple Face ID, Android face authentication or a Windows Hello camera.
     FaceID = 2,
+    /// Android iris authentication or a Windows Hello iris scanner.
+    Iris = 3,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Biometric lockout after too many failed authentication attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Lockout {
+    /// Biometric authentication is disabled for a short period of time.
+    Temporary,
+    /// Biometric authentication is disabled until the user unlocks the device with their passcode, PIN, pattern or password.
+    Permanent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Status {
     pub is_available: bool,
     pub biometry_type: BiometryType,
     pub error: Option<String>,
     pub error_code: Option<String>,
+    /// The current biometric lockout, if any.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Only known after an authentication attempt failed with a lockout.
+    /// - **Windows / Linux:** Unsupported.
+    pub lockout: Option<Lockout>,
+    /// Whether the enrolled biometrics changed since the last successful authentication,
+    /// e.g. a fingerprint was added or Face ID was reset.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Linux:** Unsupported, always `false`.
+    #[serde(default)]
+    pub enrollment_changed: bool,
+}
+
+/// Options for creating a biometry-protected key pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CreateKeyOptions {
+    /// Invalidates the key when a new biometry is enrolled on the device. Defaults to `true`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Unsupported, Windows Hello keys are not invalidated.
+    pub invalidate_on_enrollment: bool,
+}
+
+impl Default for CreateKeyOptions {
+    fn default() -> Self {
+        Self {
+            invalidate_on_enrollment: true,
+        }
+    }
+}
+
+/// Signature algorithm of a biometry-protected key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyAlgorithm {
+    /// ECDSA using the P-256 curve and SHA-256. Used on Android and iOS.
+    ES256,
+    /// RSASSA-PKCS1-v1_5 using SHA-256. Used by Windows Hello.
+    RS256,
+}
+
+/// A biometry-protected key pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyInfo {
+    /// DER-encoded X.509 SubjectPublicKeyInfo of the public key.
+    pub public_key: Vec<u8>,
+    pub algorithm: KeyAlgorithm,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CreateKeyPayload {
+    pub name: String,
+    #[serde(default)]
+    pub options: CreateKeyOptions,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct KeyOperationPayload {
+    pub name: String,
+    pub data: Vec<u8>,
+    pub reason: String,
+    #[serde(flatten)]
+    pub options: AuthOptions,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct KeyPayload {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SignResponse {
+    pub signature: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DataResponse {
+    pub data: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct KeyExistsResponse {
+    pub exists: bool,
 }
@@ -9,6 +9,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import { type UnlistenFn, listen } from '@tauri-apps/api/event'
 
 /**
  * @since 2.0.0
@@ -24,6 +25,16 @@ interface ArgMatch {
    * Number of occurrences
    */
   occurrences: number
+  /**
+   * Where the value comes from, `null` if the argument is absent.
+   *
+   * - `commandLine`: the arguments of the process.
+   * - `env`: the environment variable of the argument.
+   * - `config`: the config file key of the argument.
+   *
+   * @since 2.3.0
+   */
+  source: 'commandLine' | 'env' | 'config' | null
 }
 
 /**
@@ -42,6 +53,38 @@ interface CliMatches {
   subcommand: SubcommandMatch | null
 }
 
+/**
+ * The reason a value did not pass a validator.
+ *
+ * @since 2.3.0
+ */
+type ValidationError =
+  | { type: 'notAnInteger' }
+  | { type: 'outOfRange'; min: number | null; max: number | null }
+  | { type: 'notInEnum'; values: string[] }
+  | { type: 'noMatch'; pattern: string }
+
+/**
+ * The error {@link getMatches} rejects with.
+ *
+ * - `parse`: the arguments do not match the CLI definition.
+ * - `validation`: a value did not pass the validators of its argument.
+ * - `invalidValidator`: a validator of the CLI definition is invalid, e.g. a regex that does not compile.
+ * - `configFile`: the config file the arguments fall back to cannot be read.
+ *
+ * @since 2.3.0
+ */
+interface CliError {
+  kind: 'parse' | 'validation' | 'invalidValidator' | 'configFile'
+  message: string
+  /** The argument that failed, if known. */
+  arg?: string | null
+  /** The path of the command defining the argument, starting with the app name. */
+  command?: string[]
+  value?: string
+  reason?: ValidationError
+}
+
 /**
  * Parse the arguments provided to the current process and get the matches using the configuration defined [`tauri.cli`](https://tauri.app/v1/api/config/#tauriconfig.cli) in `tauri.conf.json`
  *
@@ -67,6 +110,55 @@ async function getMatches(): Promise<CliMatches> {
   return await invoke('plugin:cli|cli_matches')
 }
 
-export type { ArgMatch, SubcommandMatch, CliMatches }
+/**
+ * The arguments of a subsequent launch of the app, see {@link onMatches}.
+ *
+ * @since 2.3.0
+ */
+interface SecondInstanceMatches {
+  /** The raw arguments, starting with the binary path. */
+  args: string[]
+  /** The working directory of the launch. */
+  cwd: string
+  /** The matches, or `null` if parsing failed. */
+  matches: CliMatches | null
+  /** Why parsing failed, or `null` if it succeeded. */
+  error: CliError | null
+}
+
+/**
+ * Listens to the arguments of subsequent launches of the app,
+ * parsed against the same configuration as {@link getMatches}.
+ *
+ * Requires the `cli` feature of the single-instance plugin.
+ *
+ * @example
+ * ```typescript
+ * import { onMatches } from '@tauri-apps/plugin-cli';
+ * await onMatches(({ matches, cwd }) => {
+ *   if (matches?.subcommand?.name === 'open') {
+ *     // `./your-app open $ARGS` was executed from `cwd`
+ *   }
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function onMatches(
+  handler: (matches: SecondInstanceMatches) => void
+): Promise<UnlistenFn> {
+  return await listen<SecondInstanceMatches>('cli://matches', (event) => {
+    handler(event.payload)
+  })
+}
+
+export type {
+  ArgMatch,
+  SubcommandMatch,
+  CliMatches,
+  CliError,
+  ValidationError,
+  SecondInstanceMatches
+}
 
-export { getMatches }
+export { getMatches, onMatches }
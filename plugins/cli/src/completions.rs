@@ -0,0 +1,94 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{ffi::OsString, io::Write};
+
+use clap::{builder::PossibleValuesParser, Arg as ClapArg, Command};
+use clap_complete::Shell;
+use tauri::PackageInfo;
+
+use crate::{parser, Config};
+
+const MAN: &str = "man";
+
+/// The name completions are registered for: the executable name, which can differ from the product name.
+fn bin_name(package_info: &PackageInfo) -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            exe.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| package_info.name.clone())
+}
+
+pub(crate) fn completions<W: Write>(
+    config: &Config,
+    package_info: &PackageInfo,
+    shell: Shell,
+    buf: &mut W,
+) {
+    let mut command = parser::command(config, package_info);
+    clap_complete::generate(shell, &mut command, bin_name(package_info), buf);
+}
+
+pub(crate) fn man_page<W: Write>(
+    config: &Config,
+    package_info: &PackageInfo,
+    buf: &mut W,
+) -> crate::Result<()> {
+    let command = parser::command(config, package_info).name(bin_name(package_info));
+    clap_mangen::Man::new(command).render(buf)?;
+    Ok(())
+}
+
+/// Prints the completions or man page and exits if the process was started with the hidden subcommand,
+/// e.g. `app completions bash` or `app completions man`.
+///
+/// The subcommand is ignored when the CLI definition has a subcommand with the same name.
+pub(crate) fn handle_subcommand(config: &Config, package_info: &PackageInfo, name: &str) {
+    if config
+        .subcommands()
+        .is_some_and(|subcommands| subcommands.contains_key(name))
+    {
+        return;
+    }
+
+    let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    if args.first().map(|arg| arg == name) != Some(true) {
+        return;
+    }
+
+    let targets = [
+        Shell::Bash,
+        Shell::Zsh,
+        Shell::Fish,
+        Shell::PowerShell,
+        Shell::Elvish,
+    ]
+    .map(|shell| shell.to_string())
+    .into_iter()
+    .chain([MAN.to_string()]);
+    let command = Command::new(name.to_string()).arg(
+        ClapArg::new("target")
+            .required(true)
+            .value_parser(PossibleValuesParser::new(targets)),
+    );
+    let matches = match command.try_get_matches_from(args) {
+        Ok(matches) => matches,
+        Err(e) => e.exit(),
+    };
+
+    let mut stdout = std::io::stdout();
+    let target = matches.get_one::<String>("target").unwrap();
+    if target == MAN {
+        if let Err(e) = man_page(config, package_info, &mut stdout) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    } else if let Ok(shell) = target.parse::<Shell>() {
+        completions(config, package_info, shell, &mut stdout);
+    }
+    std::process::exit(0);
+}
@@ -108,6 +108,18 @@ pub struct Arg {
     /// i.e. an equals between the option and associated value.
     #[serde(alias = "requires-equals")]
     pub require_equals: Option<bool>,
+    /// The environment variable the argument falls back to when it is absent from the command line.
+    ///
+    /// Flags are enabled by `1`, `true`, `yes` or `on`, and the values of arguments with `multiple` are separated by commas.
+    pub env: Option<String>,
+    /// The dot-separated key of the config file the argument falls back to when it is absent from the command line
+    /// and its environment variable is not set, see `Builder::config_file`.
+    #[serde(alias = "config-key")]
+    pub config_key: Option<String>,
+    /// Validators the values of the argument must pass, evaluated after parsing.
+    ///
+    /// When validation fails, the matches are not returned and a structured error describes the argument and value that failed.
+    pub validators: Option<Vec<Validator>>,
     /// The positional argument index, starting at 1.
     ///
     /// The index refers to position according to other positional argument.
@@ -116,6 +128,26 @@ pub struct Arg {
     pub index: Option<usize>,
 }
 
+/// A validator for the values of an argument.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+pub enum Validator {
+    /// The value must be an integer in the given inclusive range.
+    Range { min: Option<i64>, max: Option<i64> },
+    /// The value must be one of the given values.
+    ///
+    /// Unlike `possibleValues`, the comparison can ignore the case and the failure is reported as a structured error.
+    Enum {
+        values: Vec<String>,
+        #[serde(default, rename = "ignoreCase", alias = "ignore-case")]
+        ignore_case: bool,
+    },
+    /// The value must match the given regular expression.
+    ///
+    /// The pattern is not anchored, use `^` and `$` to match the whole value.
+    Regex { pattern: String },
+}
+
 /// describes a CLI configuration
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -138,7 +170,14 @@ pub struct Config {
     /// List of arguments for the command
     pub args: Option<Vec<Arg>>,
     /// List of subcommands of this command
+    ///
+    /// Subcommands can define their own subcommands, forming a tree of arbitrary depth.
     pub subcommands: Option<HashMap<String, Config>>,
+    /// Alternative names of the subcommand, shown on the help information.
+    pub aliases: Option<Vec<String>>,
+    /// Whether a subcommand must be used, failing to parse otherwise.
+    #[serde(default, alias = "subcommand-required")]
+    pub subcommand_required: bool,
 }
 
 impl Config {
@@ -2,20 +2,111 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use serde::{Serialize, Serializer};
+use std::{fmt, path::PathBuf};
+
+use clap::error::ContextKind;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("failed to parse arguments: {0}")]
     ParseCli(#[from] clap::Error),
+    /// A value did not pass the validators of its argument.
+    #[error("invalid value '{value}' for '{arg}': {reason}")]
+    Validation {
+        /// The path of the command defining the argument, starting with the app name.
+        command: Vec<String>,
+        arg: String,
+        value: String,
+        reason: ValidationError,
+    },
+    /// A validator of the configuration is invalid, e.g. a regex that does not compile.
+    #[error("invalid validator for '{arg}': {message}")]
+    InvalidValidator { arg: String, message: String },
+    /// The config file the arguments fall back to cannot be read.
+    #[error("failed to read the config file {}: {message}", path.display())]
+    ConfigFile { path: PathBuf, message: String },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// The reason a value did not pass a validator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ValidationError {
+    NotAnInteger,
+    OutOfRange { min: Option<i64>, max: Option<i64> },
+    NotInEnum { values: Vec<String> },
+    NoMatch { pattern: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnInteger => write!(f, "expected an integer"),
+            Self::OutOfRange {
+                min: Some(min),
+                max: Some(max),
+            } => write!(f, "must be between {min} and {max}"),
+            Self::OutOfRange {
+                min: Some(min),
+                max: None,
+            } => write!(f, "must be at least {min}"),
+            Self::OutOfRange {
+                min: None,
+                max: Some(max),
+            } => write!(f, "must be at most {max}"),
+            Self::OutOfRange {
+                min: None,
+                max: None,
+            } => write!(f, "out of range"),
+            Self::NotInEnum { values } => write!(f, "must be one of {}", values.join(", ")),
+            Self::NoMatch { pattern } => write!(f, "must match {pattern}"),
+        }
+    }
 }
 
+/// Errors are serialized as objects with a `kind` and a `message`,
+/// along with the argument that failed when it is known.
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        let mut error = serializer.serialize_struct("Error", 6)?;
+        error.serialize_field("message", &self.to_string())?;
+        match self {
+            Self::ParseCli(e) => {
+                error.serialize_field("kind", "parse")?;
+                error.serialize_field(
+                    "arg",
+                    &e.get(ContextKind::InvalidArg).map(|arg| arg.to_string()),
+                )?;
+            }
+            Self::Validation {
+                command,
+                arg,
+                value,
+                reason,
+            } => {
+                error.serialize_field("kind", "validation")?;
+                error.serialize_field("command", command)?;
+                error.serialize_field("arg", arg)?;
+                error.serialize_field("value", value)?;
+                error.serialize_field("reason", reason)?;
+            }
+            Self::InvalidValidator { arg, .. } => {
+                error.serialize_field("kind", "invalidValidator")?;
+                error.serialize_field("arg", arg)?;
+            }
+            Self::ConfigFile { .. } => {
+                error.serialize_field("kind", "configFile")?;
+            }
+            Self::Io(_) => {
+                error.serialize_field("kind", "io")?;
+            }
+        }
+        error.end()
     }
 }
 
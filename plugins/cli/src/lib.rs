@@ -11,25 +11,103 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
+use std::{io::Write, path::PathBuf};
+
+use serde::Serialize;
 use tauri::{
-    plugin::{Builder, PluginApi, TauriPlugin},
-    AppHandle, Manager, Runtime, State,
+    plugin::{Builder as PluginBuilder, PluginApi, TauriPlugin},
+    AppHandle, Emitter, Manager, Runtime, State,
 };
 
+mod completions;
 mod config;
 mod error;
 mod parser;
 
 use config::{Arg, Config};
 
-pub use error::{Error, Result};
-pub use parser::{ArgData, Matches, SubcommandMatches};
+pub use clap_complete::Shell;
+pub use error::{Error, Result, ValidationError};
+pub use parser::{ArgData, ArgSource, Matches, SubcommandMatches};
+
+/// Event emitted with the matches of the arguments of a subsequent launch, see [`Cli::handle_second_instance`].
+pub const MATCHES_EVENT: &str = "cli://matches";
 
-pub struct Cli<R: Runtime>(PluginApi<R, Config>);
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MatchesEvent {
+    args: Vec<String>,
+    cwd: String,
+    matches: Option<Matches>,
+    error: Option<serde_json::Value>,
+}
+
+pub struct Cli<R: Runtime> {
+    api: PluginApi<R, Config>,
+    config_file: Option<PathBuf>,
+}
 
 impl<R: Runtime> Cli<R> {
     pub fn matches(&self) -> Result<parser::Matches> {
-        parser::get_matches(self.0.config(), self.0.app().package_info())
+        let fallbacks = parser::Fallbacks::load(self.config_file.as_deref())?;
+        parser::get_matches(self.api.config(), self.api.app().package_info(), &fallbacks)
+    }
+
+    /// Parses the arguments of a subsequent launch of the app against the CLI definition
+    /// and emits them to the webviews as the [`MATCHES_EVENT`], with the working directory of the launch.
+    ///
+    /// This is called by the single-instance plugin when its `cli` feature is enabled.
+    /// Environment variable fallbacks are resolved from the environment of the running instance.
+    pub fn handle_second_instance<S: AsRef<str>, I: Iterator<Item = S>>(
+        &self,
+        args: I,
+        cwd: &str,
+    ) -> Result<Matches> {
+        let args: Vec<String> = args.map(|arg| arg.as_ref().to_string()).collect();
+        let result = parser::Fallbacks::load(self.config_file.as_deref()).and_then(|fallbacks| {
+            parser::get_matches_from(
+                self.api.config(),
+                self.api.app().package_info(),
+                &fallbacks,
+                &args,
+            )
+        });
+
+        let _ = self.api.app().emit(
+            MATCHES_EVENT,
+            MatchesEvent {
+                args,
+                cwd: cwd.into(),
+                matches: result.as_ref().ok().cloned(),
+                error: result
+                    .as_ref()
+                    .err()
+                    .and_then(|e| serde_json::to_value(e).ok()),
+            },
+        );
+
+        result
+    }
+
+    /// Writes the completion script of the shell for the CLI definition.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_cli::{CliExt, Shell};
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     app.cli().completions(Shell::Bash, &mut std::io::stdout());
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn completions<W: Write>(&self, shell: Shell, buf: &mut W) {
+        completions::completions(self.api.config(), self.api.app().package_info(), shell, buf)
+    }
+
+    /// Writes the man page of the CLI definition, in the roff format.
+    pub fn man_page<W: Write>(&self, buf: &mut W) -> Result<()> {
+        completions::man_page(self.api.config(), self.api.app().package_info(), buf)
     }
 }
 
@@ -48,12 +126,58 @@ fn cli_matches<R: Runtime>(_app: AppHandle<R>, cli: State<'_, Cli<R>>) -> Result
     cli.matches()
 }
 
+/// The cli plugin Builder.
+#[derive(Default)]
+pub struct Builder {
+    config_file: Option<PathBuf>,
+    completions_subcommand: Option<String>,
+}
+
+impl Builder {
+    /// Create a new cli plugin Builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the JSON config file the arguments with a `configKey` fall back to.
+    ///
+    /// The file is read when the arguments are parsed and ignored if it does not exist.
+    pub fn config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_file.replace(path.into());
+        self
+    }
+
+    /// Enables the hidden subcommand printing the shell completions and the man page with the given name,
+    /// e.g. `completions`, or disables it with `None`, the default.
+    ///
+    /// The subcommand takes the target as argument, e.g. `app completions zsh` or `app completions man`,
+    /// then exits without starting the app, so packaging scripts can install the completions.
+    /// It is ignored when the CLI definition has a subcommand with the same name.
+    pub fn completions_subcommand(mut self, name: Option<&str>) -> Self {
+        self.completions_subcommand = name.map(Into::into);
+        self
+    }
+
+    /// Build and Initializes the plugin.
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R, Config> {
+        PluginBuilder::new("cli")
+            .invoke_handler(tauri::generate_handler![cli_matches])
+            .setup(|app, api| {
+                if let Some(name) = &self.completions_subcommand {
+                    completions::handle_subcommand(api.config(), app.package_info(), name);
+                }
+
+                app.manage(Cli {
+                    api,
+                    config_file: self.config_file,
+                });
+                Ok(())
+            })
+            .build()
+    }
+}
+
+/// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R, Config> {
-    Builder::new("cli")
-        .invoke_handler(tauri::generate_handler![cli_matches])
-        .setup(|app, api| {
-            app.manage(Cli(api));
-            Ok(())
-        })
-        .build()
+    Builder::default().build()
 }
@@ -5,21 +5,25 @@
 use clap::{
     builder::{PossibleValue, PossibleValuesParser},
     error::ErrorKind,
+    parser::ValueSource,
     Arg as ClapArg, ArgAction, ArgMatches, Command,
 };
 use serde::Serialize;
 use serde_json::Value;
 use tauri::PackageInfo;
 
-use crate::{Arg, Config};
+use crate::{config::Validator, Arg, Config, Error, ValidationError};
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::OsString};
 
 #[macro_use]
 mod macros;
+mod fallback;
+
+pub(crate) use fallback::Fallbacks;
 
 /// The resolution of a argument match.
-#[derive(Default, Debug, Serialize)]
+#[derive(Default, Debug, Clone, Serialize)]
 #[non_exhaustive]
 pub struct ArgData {
     /// - [`Value::Bool`] if it's a flag,
@@ -30,10 +34,23 @@ pub struct ArgData {
     /// The number of occurrences of the argument.
     /// e.g. `./app --arg 1 --arg 2 --arg 2 3 4` results in three occurrences.
     pub occurrences: u8,
+    /// Where the value comes from, `None` if the argument is absent.
+    pub source: Option<ArgSource>,
+}
+
+/// Where the value of an argument comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArgSource {
+    CommandLine,
+    /// The environment variable of the argument, see `env`.
+    Env,
+    /// The config file key of the argument, see `configKey`.
+    Config,
 }
 
 /// The matched subcommand.
-#[derive(Default, Debug, Serialize)]
+#[derive(Default, Debug, Clone, Serialize)]
 #[non_exhaustive]
 pub struct SubcommandMatches {
     /// The subcommand name.
@@ -43,7 +60,7 @@ pub struct SubcommandMatches {
 }
 
 /// The argument matches of a command.
-#[derive(Default, Debug, Serialize)]
+#[derive(Default, Debug, Clone, Serialize)]
 #[non_exhaustive]
 pub struct Matches {
     /// Data structure mapping each found arg with its resolution.
@@ -79,21 +96,30 @@ impl Matches {
 ///     Ok(())
 ///   });
 /// ```
-pub fn get_matches(cli: &Config, package_info: &PackageInfo) -> crate::Result<Matches> {
-    let about = cli
-        .description()
-        .unwrap_or(&package_info.description.to_string())
-        .to_string();
-    let version = package_info.version.to_string();
-    let app = get_app(
-        package_info,
-        version,
-        package_info.name.clone(),
-        Some(&about),
-        cli,
-    );
-    match app.try_get_matches() {
-        Ok(matches) => Ok(get_matches_internal(cli, &matches)),
+pub fn get_matches(
+    cli: &Config,
+    package_info: &PackageInfo,
+    fallbacks: &Fallbacks,
+) -> crate::Result<Matches> {
+    get_matches_from(cli, package_info, fallbacks, std::env::args_os())
+}
+
+/// Gets the argument matches of the given arguments, starting with the binary name.
+pub(crate) fn get_matches_from<I, T>(
+    cli: &Config,
+    package_info: &PackageInfo,
+    fallbacks: &Fallbacks,
+    args: I,
+) -> crate::Result<Matches>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let app = root_command(cli, package_info, fallbacks);
+    match app.try_get_matches_from(args) {
+        Ok(matches) => {
+            get_matches_internal(cli, &matches, vec![package_info.name.clone()], fallbacks)
+        }
         Err(e) => match e.kind() {
             ErrorKind::DisplayHelp => {
                 let mut matches = Matches::default();
@@ -103,6 +129,7 @@ pub fn get_matches(cli: &Config, package_info: &PackageInfo) -> crate::Result<Ma
                     ArgData {
                         value: Value::String(help_text),
                         occurrences: 0,
+                        source: None,
                     },
                 );
                 Ok(matches)
@@ -119,9 +146,35 @@ pub fn get_matches(cli: &Config, package_info: &PackageInfo) -> crate::Result<Ma
     }
 }
 
-fn get_matches_internal(config: &Config, matches: &ArgMatches) -> Matches {
+/// The command of the CLI definition, used to generate completions and man pages.
+pub(crate) fn command(cli: &Config, package_info: &PackageInfo) -> Command {
+    root_command(cli, package_info, &Fallbacks::default())
+}
+
+fn root_command(cli: &Config, package_info: &PackageInfo, fallbacks: &Fallbacks) -> Command {
+    let about = cli
+        .description()
+        .unwrap_or(&package_info.description.to_string())
+        .to_string();
+    let version = package_info.version.to_string();
+    get_app(
+        package_info,
+        version,
+        package_info.name.clone(),
+        Some(&about),
+        cli,
+        fallbacks,
+    )
+}
+
+fn get_matches_internal(
+    config: &Config,
+    matches: &ArgMatches,
+    command: Vec<String>,
+    fallbacks: &Fallbacks,
+) -> crate::Result<Matches> {
     let mut cli_matches = Matches::default();
-    map_matches(config, matches, &mut cli_matches);
+    map_matches(config, matches, &mut cli_matches, &command, fallbacks)?;
 
     if let Some((subcommand_name, subcommand_matches)) = matches.subcommand() {
         if let Some(subcommand_config) = config
@@ -129,45 +182,157 @@ fn get_matches_internal(config: &Config, matches: &ArgMatches) -> Matches {
             .as_ref()
             .and_then(|s| s.get(subcommand_name))
         {
+            let mut subcommand = command.clone();
+            subcommand.push(subcommand_name.to_string());
             cli_matches.set_subcommand(
                 subcommand_name.to_string(),
-                get_matches_internal(subcommand_config, subcommand_matches),
+                get_matches_internal(subcommand_config, subcommand_matches, subcommand, fallbacks)?,
             );
         }
     }
 
-    cli_matches
+    Ok(cli_matches)
 }
 
-fn map_matches(config: &Config, matches: &ArgMatches, cli_matches: &mut Matches) {
+fn map_matches(
+    config: &Config,
+    matches: &ArgMatches,
+    cli_matches: &mut Matches,
+    command: &[String],
+    fallbacks: &Fallbacks,
+) -> crate::Result<()> {
     if let Some(args) = config.args() {
         for arg in args {
-            let (occurrences, value) = if arg.takes_value {
-                if arg.multiple {
-                    matches
-                        .get_many::<String>(&arg.name)
-                        .map(|v| {
-                            let mut values = Vec::new();
-                            for value in v {
-                                values.push(Value::String(value.into()));
-                            }
-                            (values.len() as u8, Value::Array(values))
-                        })
-                        .unwrap_or((0, Value::Null))
+            let fallback = (matches.value_source(&arg.name) != Some(ValueSource::CommandLine))
+                .then(|| fallbacks.resolve(arg))
+                .flatten();
+
+            let (occurrences, value, source) = if let Some((source, values)) = fallback {
+                let (occurrences, value) = if arg.takes_value && arg.multiple {
+                    (
+                        values.len() as u8,
+                        Value::Array(values.into_iter().map(Value::String).collect()),
+                    )
+                } else if arg.takes_value {
+                    (
+                        1,
+                        values.into_iter().next().map_or(Value::Null, Value::String),
+                    )
                 } else {
-                    matches
-                        .get_one::<String>(&arg.name)
-                        .map(|v| (1, Value::String(v.clone())))
-                        .unwrap_or((0, Value::Null))
-                }
+                    let enabled = values.first().is_some_and(|v| fallback::is_truthy(v));
+                    (enabled as u8, Value::Bool(enabled))
+                };
+                (occurrences, value, Some(source))
             } else {
-                let occurrences = matches.get_count(&arg.name);
-                (occurrences, Value::Bool(occurrences > 0))
+                let (occurrences, value) = if arg.takes_value {
+                    if arg.multiple {
+                        matches
+                            .get_many::<String>(&arg.name)
+                            .map(|v| {
+                                let mut values = Vec::new();
+                                for value in v {
+                                    values.push(Value::String(value.into()));
+                                }
+                                (values.len() as u8, Value::Array(values))
+                            })
+                            .unwrap_or((0, Value::Null))
+                    } else {
+                        matches
+                            .get_one::<String>(&arg.name)
+                            .map(|v| (1, Value::String(v.clone())))
+                            .unwrap_or((0, Value::Null))
+                    }
+                } else {
+                    let occurrences = matches.get_count(&arg.name);
+                    (occurrences, Value::Bool(occurrences > 0))
+                };
+                let source = (occurrences > 0).then_some(ArgSource::CommandLine);
+                (occurrences, value, source)
             };
 
-            cli_matches.set_arg(arg.name.clone(), ArgData { value, occurrences });
+            if let Some(validators) = &arg.validators {
+                let values = match &value {
+                    Value::String(value) => vec![value.as_str()],
+                    Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+                    _ => Vec::new(),
+                };
+                for value in values {
+                    for validator in validators {
+                        validate(validator, value).map_err(|e| match e {
+                            ValidatorError::Invalid(reason) => Error::Validation {
+                                command: command.to_vec(),
+                                arg: arg.name.clone(),
+                                value: value.into(),
+                                reason,
+                            },
+                            ValidatorError::Regex(e) => Error::InvalidValidator {
+                                arg: arg.name.clone(),
+                                message: e.to_string(),
+                            },
+                        })?;
+                    }
+                }
+            }
+
+            cli_matches.set_arg(
+                arg.name.clone(),
+                ArgData {
+                    value,
+                    occurrences,
+                    source,
+                },
+            );
         }
     }
+
+    Ok(())
+}
+
+enum ValidatorError {
+    Invalid(ValidationError),
+    Regex(regex::Error),
+}
+
+fn validate(validator: &Validator, value: &str) -> Result<(), ValidatorError> {
+    match validator {
+        Validator::Range { min, max } => {
+            let number: i64 = value
+                .parse()
+                .map_err(|_| ValidatorError::Invalid(ValidationError::NotAnInteger))?;
+            if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+                return Err(ValidatorError::Invalid(ValidationError::OutOfRange {
+                    min: *min,
+                    max: *max,
+                }));
+            }
+        }
+        Validator::Enum {
+            values,
+            ignore_case,
+        } => {
+            let found = values.iter().any(|v| {
+                if *ignore_case {
+                    v.eq_ignore_ascii_case(value)
+                } else {
+                    v == value
+                }
+            });
+            if !found {
+                return Err(ValidatorError::Invalid(ValidationError::NotInEnum {
+                    values: values.clone(),
+                }));
+            }
+        }
+        Validator::Regex { pattern } => {
+            let regex = regex::Regex::new(pattern).map_err(ValidatorError::Regex)?;
+            if !regex.is_match(value) {
+                return Err(ValidatorError::Invalid(ValidationError::NoMatch {
+                    pattern: pattern.clone(),
+                }));
+            }
+        }
+    }
+    Ok(())
 }
 
 fn get_app(
@@ -176,6 +341,7 @@ fn get_app(
     command_name: String,
     about: Option<&String>,
     config: &Config,
+    fallbacks: &Fallbacks,
 ) -> Command {
     let mut app = Command::new(command_name)
         .author(package_info.authors)
@@ -193,10 +359,16 @@ fn get_app(
     if let Some(after_help) = config.after_help() {
         app = app.after_help(after_help);
     }
+    if let Some(aliases) = &config.aliases {
+        app = app.visible_aliases(aliases.iter().cloned());
+    }
+    if config.subcommand_required {
+        app = app.subcommand_required(true);
+    }
 
     if let Some(args) = config.args() {
         for arg in args {
-            app = app.arg(get_arg(arg.name.clone(), arg));
+            app = app.arg(get_arg(arg.name.clone(), arg, fallbacks));
         }
     }
 
@@ -208,6 +380,7 @@ fn get_app(
                 subcommand_name.to_string(),
                 subcommand.description(),
                 subcommand,
+                fallbacks,
             );
             app = app.subcommand(clap_subcommand);
         }
@@ -216,7 +389,7 @@ fn get_app(
     app
 }
 
-fn get_arg(arg_name: String, arg: &Arg) -> ClapArg {
+fn get_arg(arg_name: String, arg: &Arg, fallbacks: &Fallbacks) -> ClapArg {
     let mut clap_arg = ClapArg::new(arg_name.clone());
 
     if arg.index.is_none() {
@@ -256,7 +429,8 @@ fn get_arg(arg_name: String, arg: &Arg) -> ClapArg {
         (None, Some(max)) => clap_arg.num_args(0..max),
         (None, None) => clap_arg,
     };
-    clap_arg = clap_arg.required(arg.required);
+    // required arguments can be provided by their fallback
+    clap_arg = clap_arg.required(arg.required && fallbacks.resolve(arg).is_none());
     clap_arg = bind_string_arg!(
         arg,
         clap_arg,
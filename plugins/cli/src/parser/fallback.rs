@@ -0,0 +1,72 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::ArgSource;
+use crate::{Arg, Error};
+
+/// The values arguments fall back to when they are absent from the command line.
+#[derive(Default)]
+pub(crate) struct Fallbacks {
+    config: Option<Value>,
+}
+
+impl Fallbacks {
+    /// Reads the config file, which is ignored if it does not exist.
+    pub(crate) fn load(config_file: Option<&Path>) -> crate::Result<Self> {
+        let Some(path) = config_file else {
+            return Ok(Self::default());
+        };
+
+        let error = |message: String| Error::ConfigFile {
+            path: path.to_path_buf(),
+            message,
+        };
+        let config = match std::fs::read(path) {
+            Ok(data) => Some(serde_json::from_slice(&data).map_err(|e| error(e.to_string()))?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(error(e.to_string())),
+        };
+        Ok(Self { config })
+    }
+
+    /// Resolves the fallback values of the argument, from its environment variable first and its config key otherwise.
+    pub(crate) fn resolve(&self, arg: &Arg) -> Option<(ArgSource, Vec<String>)> {
+        if let Some(value) = arg.env.as_ref().and_then(|env| std::env::var(env).ok()) {
+            let values = if arg.multiple {
+                value.split(',').map(Into::into).collect()
+            } else {
+                vec![value]
+            };
+            return Some((ArgSource::Env, values));
+        }
+
+        let key = arg.config_key.as_ref()?;
+        let pointer = format!("/{}", key.replace('.', "/"));
+        let values = match self.config.as_ref()?.pointer(&pointer)? {
+            Value::Null => return None,
+            Value::Array(values) => values.iter().map(to_string).collect(),
+            value => vec![to_string(value)],
+        };
+        Some((ArgSource::Config, values))
+    }
+}
+
+fn to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Whether the fallback value enables a flag.
+pub(crate) fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
@@ -82,6 +82,7 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("create", &[]),
     ("copy_file", &[]),
     ("remove", &[]),
+    ("trash", &[]),
     ("rename", &[]),
     ("truncate", &[]),
     ("ftruncate", &[]),
@@ -95,6 +96,8 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("read_text_file", &[]),
     ("read_text_file_lines", &["read_text_file_lines_next"]),
     ("read_text_file_lines_next", &[]),
+    ("read_file_stream", &["read_file_stream_pull"]),
+    ("read_file_stream_pull", &[]),
     ("seek", &[]),
     ("stat", &[]),
     ("lstat", &[]),
@@ -102,7 +105,30 @@ const COMMANDS: &[(&str, &[&str])] = &[
     ("exists", &[]),
     ("watch", &[]),
     ("unwatch", &[]),
+    ("set_watch_paths", &[]),
     ("size", &[]),
+    ("copy", &[]),
+    ("move_path", &[]),
+    ("search", &[]),
+    ("hash", &[]),
+    ("allow_directory", &[]),
+    ("allow_file", &[]),
+    ("forbid_directory", &[]),
+    ("forbid_file", &[]),
+    ("scope_entries", &[]),
+    ("lock", &[]),
+    ("unlock", &[]),
+    ("symlink", &[]),
+    ("hardlink", &[]),
+    ("readlink", &[]),
+    ("dir_size", &[]),
+    ("disk_usage", &[]),
+    ("create_cancellation_token", &[]),
+    ("cancel", &[]),
+    ("compress", &[]),
+    ("extract", &[]),
+    ("set_permissions", &[]),
+    ("set_times", &[]),
 ];
 
 fn main() {
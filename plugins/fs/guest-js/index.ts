@@ -74,6 +74,7 @@
 
 import { BaseDirectory } from '@tauri-apps/api/path'
 import { Channel, invoke, Resource } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
 enum SeekMode {
   Start = 0,
@@ -136,6 +137,47 @@ interface FileInfo {
    * - **macOS / Linux / Android / iOS:** Unsupported.
    */
   fileAttributes: number | null
+  /**
+   * The `st_flags` of the file, such as `UF_HIDDEN` or `UF_IMMUTABLE`.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows / Linux / Android:** Unsupported.
+   *
+   * @since 2.3.0
+   */
+  flags: number | null
+  /**
+   * The last change of the status of the file, such as its permissions or owner.
+   * This corresponds to the `ctime` field from `stat`.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows:** Unsupported.
+   *
+   * @since 2.3.0
+   */
+  ctime: Date | null
+  /**
+   * Name of the user owning this file.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows:** Unsupported.
+   *
+   * @since 2.3.0
+   */
+  owner: string | null
+  /**
+   * Name of the group owning this file.
+   *
+   * #### Platform-specific
+   *
+   * - **Windows:** Unsupported.
+   *
+   * @since 2.3.0
+   */
+  group: string | null
   /**
    * ID of the device containing the file.
    *
@@ -220,7 +262,11 @@ interface UnparsedFileInfo {
   atime: number | null
   birthtime: number | null
   readonly: boolean
-  fileAttributes: number
+  fileAttributes: number | null
+  flags: number | null
+  ctime: number | null
+  owner: string | null
+  group: string | null
   dev: number | null
   ino: number | null
   mode: number | null
@@ -242,6 +288,10 @@ function parseFileInfo(r: UnparsedFileInfo): FileInfo {
     birthtime: r.birthtime !== null ? new Date(r.birthtime) : null,
     readonly: r.readonly,
     fileAttributes: r.fileAttributes,
+    flags: r.flags,
+    ctime: r.ctime !== null ? new Date(r.ctime) : null,
+    owner: r.owner,
+    group: r.group,
     dev: r.dev,
     ino: r.ino,
     mode: r.mode,
@@ -617,6 +667,130 @@ async function copyFile(
   })
 }
 
+/**
+ * What to do when the destination of a file or directory already exists:
+ * - `overwrite` replaces the files and merges the directories.
+ * - `skip` keeps the existing file or directory.
+ * - `rename` copies with a new name such as `file (1).txt`.
+ *
+ * @since 2.3.0
+ */
+type ConflictStrategy = 'overwrite' | 'skip' | 'rename'
+
+/**
+ * The progress of a {@linkcode copy} or {@linkcode move}, emitted with the `fs://copy-progress` event.
+ *
+ * @since 2.3.0
+ */
+interface TransferProgress {
+  /** The file being copied. */
+  path: string
+  /** The bytes of the files copied or skipped so far. */
+  bytes: number
+  totalBytes: number
+  /** The number of files copied or skipped so far. */
+  files: number
+  totalFiles: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface CopyOptions {
+  /** The file or directory to copy. */
+  from: string | URL
+  /** The path of the copy. */
+  to: string | URL
+  /** Base directory for `from`. */
+  fromPathBaseDir?: BaseDirectory
+  /** Base directory for `to`. */
+  toPathBaseDir?: BaseDirectory
+  /** Must be set to copy a directory and its contents. Defaults to `false`. */
+  recursive?: boolean
+  /** Defaults to `overwrite`. */
+  conflict?: ConflictStrategy
+  /** Called with the progress, at most every 100ms and once done. */
+  onProgress?: (progress: TransferProgress) => void
+}
+
+async function transfer(command: string, options: CopyOptions): Promise<void> {
+  const { from, to, onProgress, ...rest } = options
+  if (
+    (from instanceof URL && from.protocol !== 'file:') ||
+    (to instanceof URL && to.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  // identifies the events of this operation
+  const id = Math.floor(Math.random() * 0xffffffff)
+  const unlisten = onProgress
+    ? await listen<TransferProgress & { id: number | null }>(
+        'fs://copy-progress',
+        ({ payload: { id: progressId, ...progress } }) => {
+          if (progressId === id) {
+            onProgress(progress)
+          }
+        }
+      )
+    : null
+
+  try {
+    await invoke(command, {
+      fromPath: from instanceof URL ? from.toString() : from,
+      toPath: to instanceof URL ? to.toString() : to,
+      options: { ...rest, id }
+    })
+  } finally {
+    unlisten?.()
+  }
+}
+
+/**
+ * Copies a file or a directory with its contents, keeping the permissions and modification times.
+ * @example
+ * ```typescript
+ * import { copy, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await copy({
+ *   from: 'projects',
+ *   to: 'projects.bk',
+ *   fromPathBaseDir: BaseDirectory.AppData,
+ *   toPathBaseDir: BaseDirectory.AppData,
+ *   recursive: true,
+ *   conflict: 'rename',
+ *   onProgress: ({ bytes, totalBytes }) => console.log(`${bytes}/${totalBytes}`)
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function copy(options: CopyOptions): Promise<void> {
+  await transfer('plugin:fs|copy', options)
+}
+
+/**
+ * Moves a file or a directory with its contents.
+ *
+ * The entries are renamed when possible, and copied then removed otherwise, such as across file systems.
+ * The entries skipped with the `skip` conflict strategy are left in place.
+ * @example
+ * ```typescript
+ * import { move, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await move({
+ *   from: 'downloads/project',
+ *   to: 'projects/project',
+ *   fromPathBaseDir: BaseDirectory.AppData,
+ *   toPathBaseDir: BaseDirectory.AppData,
+ *   recursive: true
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function move(options: CopyOptions): Promise<void> {
+  await transfer('plugin:fs|move_path', options)
+}
+
 /**
  * @since 2.0.0
  */
@@ -661,6 +835,13 @@ async function mkdir(
 interface ReadDirOptions {
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Whether the symlinks report if their target is a file or a directory, when the target is in scope.
+   * They still report `isSymlink`. Defaults to `false`.
+   *
+   * @since 2.3.0
+   */
+  followSymlinks?: boolean
 }
 
 /**
@@ -852,6 +1033,85 @@ async function readTextFileLines(
   })
 }
 
+/**
+ * @since 2.3.0
+ */
+interface ReadFileStreamOptions extends ReadFileOptions {
+  /** The size of the chunks in bytes. Defaults to 1 MiB. */
+  chunkSize?: number
+}
+
+/**
+ * Returns a {@linkcode ReadableStream} over the contents of a file, read in chunks.
+ *
+ * Unlike {@linkcode readFile}, the file is never fully loaded in memory:
+ * the next chunk is only read once the stream asks for it, so a slow consumer is not flooded.
+ * @example
+ * ```typescript
+ * import { readFileStream, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const stream = await readFileStream('video.mp4', { baseDir: BaseDirectory.Video, chunkSize: 64 * 1024 });
+ * for await (const chunk of stream) {
+ *   console.log(chunk.byteLength);
+ * }
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function readFileStream(
+  path: string | URL,
+  options?: ReadFileStreamOptions
+): Promise<ReadableStream<Uint8Array>> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  // resolves the pending `pull` once its chunk arrived
+  let onPulled: (() => void) | null = null
+  let controller: ReadableStreamDefaultController<Uint8Array> | null = null
+
+  const onChunk = new Channel<ArrayBuffer | number[] | string>()
+  onChunk.onmessage = (chunk) => {
+    if (typeof chunk === 'string') {
+      controller?.error(new Error(chunk))
+    } else {
+      const bytes =
+        chunk instanceof ArrayBuffer
+          ? new Uint8Array(chunk)
+          : Uint8Array.from(chunk)
+      // an empty chunk signals the end of the file
+      if (bytes.byteLength === 0) {
+        controller?.close()
+      } else {
+        controller?.enqueue(bytes)
+      }
+    }
+    onPulled?.()
+    onPulled = null
+  }
+
+  const rid = await invoke<number>('plugin:fs|read_file_stream', {
+    path: path instanceof URL ? path.toString() : path,
+    options,
+    onChunk
+  })
+
+  return new ReadableStream<Uint8Array>({
+    start(c) {
+      controller = c
+    },
+    async pull() {
+      const pulled = new Promise<void>((resolve) => {
+        onPulled = resolve
+      })
+      await invoke('plugin:fs|read_file_stream_pull', { rid })
+      await pulled
+    },
+    async cancel() {
+      await new Resource(rid).close()
+    }
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -888,6 +1148,43 @@ async function remove(
   })
 }
 
+/**
+ * @since 2.3.0
+ */
+interface TrashOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Moves the named file or directory to the recycle bin of the operating system, so it can be restored.
+ * Directories are moved with their contents.
+ * @example
+ * ```typescript
+ * import { trash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await trash('users/file.txt', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * #### Platform-specific
+ *
+ * - **Android / iOS:** Unsupported.
+ *
+ * @since 2.3.0
+ */
+async function trash(
+  path: string | URL,
+  options?: TrashOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|trash', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -932,17 +1229,135 @@ async function rename(
   })
 }
 
+/**
+ * @since 2.3.0
+ */
+interface LinkOptions {
+  /** Base directory for `target`. */
+  targetBaseDir?: BaseDirectory
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+  /**
+   * Whether the target is a file or a directory, only used by {@linkcode symlink} on Windows
+   * which distinguishes them. Detected from the target if not set.
+   */
+  type?: 'file' | 'directory'
+}
+
+/**
+ * Creates a symbolic link at `path` pointing to `target`.
+ *
+ * A relative `target` is kept relative in the link, resolved from the directory of the link.
+ * Both the link and its target must be in scope.
+ *
+ * On Windows, creating symbolic links requires the developer mode or administrator privileges.
+ * @example
+ * ```typescript
+ * import { symlink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await symlink('releases/1.2.0', 'current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function symlink(
+  target: string | URL,
+  path: string | URL,
+  options?: LinkOptions
+): Promise<void> {
+  if (
+    (target instanceof URL && target.protocol !== 'file:') ||
+    (path instanceof URL && path.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|symlink', {
+    target: target instanceof URL ? target.toString() : target,
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Creates a hard link at `path` to the `target` file, both must be on the same file system.
+ *
+ * A relative `target` is resolved from the directory of the link. Both the link and its target must be in scope.
+ * @example
+ * ```typescript
+ * import { hardlink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await hardlink('cache/avatar.png', 'avatar.png', { targetBaseDir: BaseDirectory.AppCache, baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function hardlink(
+  target: string | URL,
+  path: string | URL,
+  options?: Omit<LinkOptions, 'type'>
+): Promise<void> {
+  if (
+    (target instanceof URL && target.protocol !== 'file:') ||
+    (path instanceof URL && path.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|hardlink', {
+    target: target instanceof URL ? target.toString() : target,
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.3.0
+ */
+interface ReadlinkOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Resolves to the target of the symbolic link at `path`, as stored in the link.
+ * @example
+ * ```typescript
+ * import { readlink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const target = await readlink('current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function readlink(
+  path: string | URL,
+  options?: ReadlinkOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|readlink', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
 /**
  * @since 2.0.0
  */
 interface StatOptions {
   /** Base directory for `path`. */
   baseDir?: BaseDirectory
+  /**
+   * Whether {@linkcode stat} follows the symlinks, {@linkcode lstat} never does. Defaults to `true`.
+   *
+   * @since 2.3.0
+   */
+  followSymlinks?: boolean
 }
 
 /**
- * Resolves to a {@linkcode FileInfo} for the specified `path`. Will always
- * follow symlinks but will reject if the symlink points to a path outside of the scope.
+ * Resolves to a {@linkcode FileInfo} for the specified `path`. Will follow
+ * symlinks unless `followSymlinks` is `false`, but will reject if the symlink points to a path outside of the scope.
  *
  * @example
  * ```typescript
@@ -991,6 +1406,81 @@ async function lstat(
   return parseFileInfo(res)
 }
 
+/**
+ * @since 2.3.0
+ */
+interface SetPermissionsOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Sets the Unix permission bits of a file or directory, such as `0o644`.
+ *
+ * #### Platform-specific
+ *
+ * - **Windows:** Only sets the read-only attribute, when none of the write bits are set.
+ * @example
+ * ```typescript
+ * import { setPermissions, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await setPermissions('bin/tool', 0o755, { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function setPermissions(
+  path: string | URL,
+  mode: number,
+  options?: SetPermissionsOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|set_permissions', {
+    path: path instanceof URL ? path.toString() : path,
+    mode,
+    options
+  })
+}
+
+/**
+ * @since 2.3.0
+ */
+interface SetTimesOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Sets the access and modification times of a file or directory, the times set to `null` are kept.
+ * @example
+ * ```typescript
+ * import { setTimes, stat, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { atime, mtime } = await stat('notes.md', { baseDir: BaseDirectory.AppData });
+ * await setTimes('backup/notes.md', atime, mtime, { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function setTimes(
+  path: string | URL,
+  atime: Date | null,
+  mtime: Date | null,
+  options?: SetTimesOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|set_times', {
+    path: path instanceof URL ? path.toString() : path,
+    atime: atime?.getTime() ?? null,
+    mtime: mtime?.getTime() ?? null,
+    options
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -1049,9 +1539,26 @@ interface WriteFileOptions {
   mode?: number
   /** Base directory for `path` */
   baseDir?: BaseDirectory
-}
-
-/**
+  /**
+   * Writes to a temporary file in the same directory and renames it over `path`,
+   * so a crash mid-write never leaves the file partially written. Cannot be combined with `append`.
+   *
+   * Not supported when `data` is a `ReadableStream`.
+   *
+   * @since 2.3.0
+   */
+  atomic?: boolean
+  /**
+   * Flushes the contents to the disk with `fsync` before resolving.
+   *
+   * Not supported when `data` is a `ReadableStream`.
+   *
+   * @since 2.3.0
+   */
+  sync?: boolean
+}
+
+/**
  * Write `data` to the given `path`, by default creating a new file if needed, else overwriting.
  * @example
  * ```typescript
@@ -1074,6 +1581,11 @@ async function writeFile(
   }
 
   if (data instanceof ReadableStream) {
+    if (options?.atomic || options?.sync) {
+      throw new TypeError(
+        'The `atomic` and `sync` options are not supported with a ReadableStream.'
+      )
+    }
     const file = await open(path, options)
     for await (const chunk of data) {
       await file.write(chunk)
@@ -1160,13 +1672,34 @@ interface WatchOptions {
   recursive?: boolean
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Glob patterns of the paths to ignore, such as `node_modules` or `*.tmp`.
+   * Matched against the names of the path and its parents if they have no `/`,
+   * or against the path relative to the watched path otherwise.
+   *
+   * @since 2.3.0
+   */
+  ignore?: string[]
+  /**
+   * The kinds of events to send, all of them if empty.
+   *
+   * @since 2.3.0
+   */
+  kinds?: WatchEventFilter[]
+  /**
+   * Whether to send a rename-from event followed by its rename-to event as a single
+   * `{ renamed: { from, to } }` event. Defaults to `false`.
+   *
+   * @since 2.3.0
+   */
+  pairRenames?: boolean
 }
 
 /**
  * @since 2.0.0
  */
 interface DebouncedWatchOptions extends WatchOptions {
-  /** Debounce delay */
+  /** Debounce delay in milliseconds, the events of a path within this window are gathered. Defaults to `2000`. */
   delayMs?: number
 }
 
@@ -1188,6 +1721,20 @@ type WatchEventKind =
   | { create: WatchEventKindCreate }
   | { modify: WatchEventKindModify }
   | { remove: WatchEventKindRemove }
+  | { renamed: { from: string; to: string } }
+  | 'other'
+
+/**
+ * The kinds of events of {@linkcode WatchOptions.kinds}, `modify` excludes the renames.
+ *
+ * @since 2.3.0
+ */
+type WatchEventFilter =
+  | 'access'
+  | 'create'
+  | 'modify'
+  | 'rename'
+  | 'remove'
   | 'other'
 
 /**
@@ -1242,12 +1789,68 @@ type WatchEventKindRemove =
  */
 type UnwatchFn = () => void
 
+/**
+ * Stops watching when called, like {@linkcode UnwatchFn}.
+ *
+ * @since 2.3.0
+ */
+type Watcher = UnwatchFn & {
+  /**
+   * Replaces the watched paths, without recreating the watcher.
+   * The paths are resolved from the `baseDir` of the watcher.
+   */
+  setPaths: (paths: string | string[] | URL | URL[]) => Promise<void>
+}
+
 async function unwatch(rid: number): Promise<void> {
   await invoke('plugin:fs|unwatch', { rid })
 }
 
+function toWatchPaths(paths: string | string[] | URL | URL[]): string[] {
+  const watchPaths = Array.isArray(paths) ? paths : [paths]
+
+  for (const path of watchPaths) {
+    if (path instanceof URL && path.protocol !== 'file:') {
+      throw new TypeError('Must be a file URL.')
+    }
+  }
+
+  return watchPaths.map((p) => (p instanceof URL ? p.toString() : p))
+}
+
+function createWatcher(rid: number, baseDir?: BaseDirectory): Watcher {
+  return Object.assign(
+    () => {
+      void unwatch(rid)
+    },
+    {
+      setPaths: async (paths: string | string[] | URL | URL[]) => {
+        await invoke('plugin:fs|set_watch_paths', {
+          rid,
+          paths: toWatchPaths(paths),
+          options: { baseDir }
+        })
+      }
+    }
+  )
+}
+
 /**
  * Watch changes (after a delay) on files or directories.
+ * @example
+ * ```typescript
+ * import { watch, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const watcher = await watch('project', (event) => console.log(event), {
+ *   baseDir: BaseDirectory.AppData,
+ *   recursive: true,
+ *   delayMs: 500,
+ *   ignore: ['node_modules', '.git'],
+ *   kinds: ['create', 'modify', 'rename', 'remove'],
+ *   pairRenames: true,
+ * });
+ * await watcher.setPaths(['project', 'notes']);
+ * watcher();
+ * ```
  *
  * @since 2.0.0
  */
@@ -1255,33 +1858,23 @@ async function watch(
   paths: string | string[] | URL | URL[],
   cb: (event: WatchEvent) => void,
   options?: DebouncedWatchOptions
-): Promise<UnwatchFn> {
+): Promise<Watcher> {
   const opts = {
     recursive: false,
     delayMs: 2000,
     ...options
   }
 
-  const watchPaths = Array.isArray(paths) ? paths : [paths]
-
-  for (const path of watchPaths) {
-    if (path instanceof URL && path.protocol !== 'file:') {
-      throw new TypeError('Must be a file URL.')
-    }
-  }
-
   const onEvent = new Channel<WatchEvent>()
   onEvent.onmessage = cb
 
   const rid: number = await invoke('plugin:fs|watch', {
-    paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+    paths: toWatchPaths(paths),
     options: opts,
     onEvent
   })
 
-  return () => {
-    void unwatch(rid)
-  }
+  return createWatcher(rid, opts.baseDir)
 }
 
 /**
@@ -1293,33 +1886,182 @@ async function watchImmediate(
   paths: string | string[] | URL | URL[],
   cb: (event: WatchEvent) => void,
   options?: WatchOptions
-): Promise<UnwatchFn> {
+): Promise<Watcher> {
   const opts = {
     recursive: false,
     ...options,
     delayMs: null
   }
 
-  const watchPaths = Array.isArray(paths) ? paths : [paths]
-
-  for (const path of watchPaths) {
-    if (path instanceof URL && path.protocol !== 'file:') {
-      throw new TypeError('Must be a file URL.')
-    }
-  }
-
   const onEvent = new Channel<WatchEvent>()
   onEvent.onmessage = cb
 
   const rid: number = await invoke('plugin:fs|watch', {
-    paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
+    paths: toWatchPaths(paths),
     options: opts,
     onEvent
   })
 
-  return () => {
-    void unwatch(rid)
+  return createWatcher(rid, opts.baseDir)
+}
+
+/**
+ * @since 2.3.0
+ */
+interface SearchOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /**
+   * Glob patterns such as `*.md` or `docs/*.md`, matched against the file name,
+   * or against the path relative to the searched directory if they contain a `/`.
+   * All the entries match if empty.
+   */
+  patterns?: string[]
+  /** How deep to search, `1` only searches the direct children of the directory. Unlimited by default. */
+  maxDepth?: number
+  /** Whether to search the hidden files and directories. Defaults to `false`. */
+  includeHidden?: boolean
+  /** Whether the directories can match too. Defaults to `false`. */
+  includeDirectories?: boolean
+  /** Minimum size of the files in bytes. */
+  minSize?: number
+  /** Maximum size of the files in bytes. */
+  maxSize?: number
+  /** Only matches the entries modified after this date. */
+  modifiedAfter?: Date
+  /** Only matches the entries modified before this date. */
+  modifiedBefore?: Date
+  /** A regular expression, in the syntax of the Rust `regex` crate, that a line of the files must match. */
+  content?: string
+}
+
+/**
+ * A line matching the content regular expression of a {@linkcode search}.
+ *
+ * @since 2.3.0
+ */
+interface LineMatch {
+  /** The line number, starting at 1. */
+  line: number
+  text: string
+}
+
+/**
+ * An entry found by {@linkcode search}.
+ *
+ * @since 2.3.0
+ */
+interface SearchMatch {
+  path: string
+  isDirectory: boolean
+  size: number
+  mtime: Date | null
+  /** The lines matching the `content` regular expression, empty without it. */
+  lines: LineMatch[]
+}
+
+/**
+ * Searches a directory recursively, calling `onMatch` with the entries as they are found.
+ *
+ * The symbolic links are not followed. Resolves to the number of matches once the search is over.
+ * @example
+ * ```typescript
+ * import { search, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const count = await search('notes', (match) => console.log(match.path, match.lines), {
+ *   baseDir: BaseDirectory.AppData,
+ *   patterns: ['*.md'],
+ *   content: 'TODO',
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function search(
+  path: string | URL,
+  onMatch: (match: SearchMatch) => void,
+  options?: SearchOptions
+): Promise<number> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const channel = new Channel<Omit<SearchMatch, 'mtime'> & { mtime: number | null }>()
+  channel.onmessage = ({ mtime, ...match }) => {
+    onMatch({ ...match, mtime: mtime !== null ? new Date(mtime) : null })
   }
+
+  return await invoke('plugin:fs|search', {
+    path: path instanceof URL ? path.toString() : path,
+    options: options && {
+      ...options,
+      modifiedAfter: options.modifiedAfter?.getTime(),
+      modifiedBefore: options.modifiedBefore?.getTime()
+    },
+    onMatch: channel
+  })
+}
+
+/**
+ * The algorithms supported by {@linkcode hash}.
+ *
+ * @since 2.3.0
+ */
+type HashAlgorithm = 'SHA-256' | 'SHA-1' | 'BLAKE3'
+
+/**
+ * The progress of a {@linkcode hash}.
+ *
+ * @since 2.3.0
+ */
+interface HashProgress {
+  /** The bytes hashed so far. */
+  bytes: number
+  totalBytes: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface HashOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Called with the progress of the hashing, at most every 100 milliseconds. */
+  onProgress?: (progress: HashProgress) => void
+}
+
+/**
+ * Computes the digest of a file, without sending its contents to the webview.
+ *
+ * Resolves to the digest as a lowercase hexadecimal string.
+ * @example
+ * ```typescript
+ * import { hash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const digest = await hash('installer.dmg', 'SHA-256', { baseDir: BaseDirectory.Download });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function hash(
+  path: string | URL,
+  algorithm: HashAlgorithm,
+  options?: HashOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  let onProgress: Channel<HashProgress> | undefined
+  if (options?.onProgress) {
+    onProgress = new Channel<HashProgress>()
+    onProgress.onmessage = options.onProgress
+  }
+
+  return await invoke('plugin:fs|hash', {
+    path: path instanceof URL ? path.toString() : path,
+    algorithm,
+    options: options && { baseDir: options.baseDir },
+    onProgress
+  })
 }
 
 /**
@@ -1347,15 +2089,521 @@ async function size(path: string | URL): Promise<number> {
   })
 }
 
+/**
+ * @since 2.3.0
+ */
+interface ScopeOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Whether the entries of the sub directories are included, only used with directories. Defaults to `false`. */
+  recursive?: boolean
+}
+
+/**
+ * The patterns of the plugin scope, including the paths added at runtime.
+ *
+ * @since 2.3.0
+ */
+interface ScopeEntries {
+  allowed: string[]
+  forbidden: string[]
+}
+
+/**
+ * A path allowed or forbidden at runtime.
+ *
+ * @since 2.3.0
+ */
+interface ScopeChange {
+  kind: 'allowed' | 'forbidden'
+  path: string
+}
+
+async function changeScope(
+  command: string,
+  path: string | URL,
+  options?: ScopeOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke(`plugin:fs|${command}`, {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Allows the webview to access a directory until the app exits, for instance a workspace folder picked by the user.
+ *
+ * Requires the `fs:manage-scope` permission, only the paths allowed by its scope,
+ * or by the global `fs:scope` when it has none, can be allowed.
+ * @example
+ * ```typescript
+ * import { allowDirectory } from '@tauri-apps/plugin-fs';
+ * await allowDirectory('/home/tauri/projects/app', { recursive: true });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function allowDirectory(
+  path: string | URL,
+  options?: ScopeOptions
+): Promise<void> {
+  await changeScope('allow_directory', path, options)
+}
+
+/**
+ * Allows the webview to access a file until the app exits.
+ *
+ * Requires the `fs:manage-scope` permission, only the paths allowed by its scope,
+ * or by the global `fs:scope` when it has none, can be allowed.
+ *
+ * @since 2.3.0
+ */
+async function allowFile(
+  path: string | URL,
+  options?: Omit<ScopeOptions, 'recursive'>
+): Promise<void> {
+  await changeScope('allow_file', path, options)
+}
+
+/**
+ * Forbids the webview to access a directory until the app exits, even if it is allowed.
+ *
+ * Requires the `fs:manage-scope` permission.
+ *
+ * @since 2.3.0
+ */
+async function forbidDirectory(
+  path: string | URL,
+  options?: ScopeOptions
+): Promise<void> {
+  await changeScope('forbid_directory', path, options)
+}
+
+/**
+ * Forbids the webview to access a file until the app exits, even if it is allowed.
+ *
+ * Requires the `fs:manage-scope` permission.
+ *
+ * @since 2.3.0
+ */
+async function forbidFile(
+  path: string | URL,
+  options?: Omit<ScopeOptions, 'recursive'>
+): Promise<void> {
+  await changeScope('forbid_file', path, options)
+}
+
+/**
+ * Lists the patterns of the plugin scope.
+ *
+ * Requires the `fs:manage-scope` permission.
+ *
+ * @since 2.3.0
+ */
+async function scopeEntries(): Promise<ScopeEntries> {
+  return await invoke('plugin:fs|scope_entries')
+}
+
+/**
+ * Listens to the paths allowed or forbidden at runtime, from Rust or from a webview.
+ * @example
+ * ```typescript
+ * import { onScopeChanged } from '@tauri-apps/plugin-fs';
+ * const unlisten = await onScopeChanged(({ kind, path }) => console.log(kind, path));
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function onScopeChanged(
+  handler: (change: ScopeChange) => void
+): Promise<UnlistenFn> {
+  return await listen<ScopeChange>('fs://scope-changed', (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * @since 2.3.0
+ */
+interface LockOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /**
+   * Whether only one process can hold the lock at a time.
+   * Otherwise many processes can share it, but none while the file is locked exclusively. Defaults to `false`.
+   */
+  exclusive?: boolean
+  /** Whether to wait for the lock when the file is already locked instead of failing. Defaults to `false`. */
+  blocking?: boolean
+  /** The maximum time to wait for the lock in milliseconds, only used when `blocking`. */
+  timeout?: number
+}
+
+/**
+ * An advisory lock on a file, returned by {@linkcode lock}.
+ *
+ * The lock only coordinates the processes locking the file, it does not prevent the other processes from reading or writing it.
+ * It is released by {@linkcode FileLock.unlock}, or when the app exits.
+ *
+ * @since 2.3.0
+ */
+class FileLock extends Resource {
+  /**
+   * Releases the lock.
+   *
+   * @since 2.3.0
+   */
+  async unlock(): Promise<void> {
+    await invoke('plugin:fs|unlock', { rid: this.rid })
+  }
+}
+
+/**
+ * Takes an advisory lock on a file, to coordinate the access to shared data files with other app instances or a sidecar.
+ * @example
+ * ```typescript
+ * import { lock, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const fileLock = await lock('data.json', {
+ *   baseDir: BaseDirectory.AppData,
+ *   exclusive: true,
+ *   blocking: true,
+ *   timeout: 5000,
+ * });
+ * // read and write the file
+ * await fileLock.unlock();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function lock(
+  path: string | URL,
+  options?: LockOptions
+): Promise<FileLock> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const rid = await invoke<number>('plugin:fs|lock', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+
+  return new FileLock(rid)
+}
+
+/**
+ * The progress of a {@linkcode dirSize}.
+ *
+ * @since 2.3.0
+ */
+interface DirSizeProgress {
+  /** The bytes of the files counted so far. */
+  bytes: number
+  /** The number of files counted so far. */
+  files: number
+  /** The directory being read. */
+  path: string
+}
+
+/**
+ * @since 2.3.0
+ */
+interface DirSizeOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Called with the progress of the computation, at most every 100 milliseconds. */
+  onProgress?: (progress: DirSizeProgress) => void
+  /** Cancels the computation, rejecting with the reason of the signal. */
+  signal?: AbortSignal
+}
+
+/**
+ * Computes the size of the files in a directory and its sub directories, reporting the progress along the way.
+ *
+ * The symlinks are not followed, and the entries that cannot be read are skipped.
+ * @example
+ * ```typescript
+ * import { dirSize, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const controller = new AbortController();
+ * const bytes = await dirSize('cache', {
+ *   baseDir: BaseDirectory.AppData,
+ *   onProgress: ({ bytes, files }) => console.log(bytes, files),
+ *   signal: controller.signal,
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function dirSize(
+  path: string | URL,
+  options?: DirSizeOptions
+): Promise<number> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const signal = options?.signal
+  if (signal?.aborted) {
+    throw signal.reason
+  }
+
+  let onProgress: Channel<DirSizeProgress> | undefined
+  if (options?.onProgress) {
+    onProgress = new Channel<DirSizeProgress>()
+    onProgress.onmessage = options.onProgress
+  }
+
+  let token: Resource | undefined
+  const abort = () => {
+    if (token) {
+      void invoke('plugin:fs|cancel', { rid: token.rid })
+    }
+  }
+  if (signal) {
+    token = new Resource(
+      await invoke<number>('plugin:fs|create_cancellation_token')
+    )
+    signal.addEventListener('abort', abort)
+  }
+
+  try {
+    return await invoke('plugin:fs|dir_size', {
+      path: path instanceof URL ? path.toString() : path,
+      options: options && { baseDir: options.baseDir },
+      onProgress,
+      cancellationToken: token?.rid
+    })
+  } catch (e) {
+    if (signal?.aborted) {
+      throw signal.reason
+    }
+    throw e
+  } finally {
+    signal?.removeEventListener('abort', abort)
+    await token?.close()
+  }
+}
+
+/**
+ * The usage of a volume, in bytes.
+ *
+ * @since 2.3.0
+ */
+interface DiskUsage {
+  total: number
+  /** The free bytes, including the bytes reserved to the privileged users. */
+  free: number
+  /** The free bytes the current user can use. */
+  available: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface DiskUsageOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Resolves to the usage of the volume containing the path.
+ * @example
+ * ```typescript
+ * import { diskUsage, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { available, total } = await diskUsage('', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function diskUsage(
+  path: string | URL,
+  options?: DiskUsageOptions
+): Promise<DiskUsage> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|disk_usage', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * The archive formats supported by {@linkcode compress} and {@linkcode extract}.
+ *
+ * @since 2.3.0
+ */
+type ArchiveFormat = 'zip' | 'tar.gz'
+
+/**
+ * The progress of a {@linkcode compress} or {@linkcode extract}.
+ *
+ * @since 2.3.0
+ */
+interface ArchiveProgress {
+  /** The entry being written. */
+  path: string
+  /** The bytes of the files written so far, before compression or after extraction. */
+  bytes: number
+  /** Unknown when extracting a tar archive, as it is read as a stream. */
+  totalBytes: number | null
+  /** The number of entries written so far. */
+  entries: number
+  /** Unknown when extracting a tar archive, as it is read as a stream. */
+  totalEntries: number | null
+}
+
+/**
+ * @since 2.3.0
+ */
+interface CompressOptions {
+  /** Base directory for `paths`. */
+  baseDir?: BaseDirectory
+  /** Base directory for `destination`. */
+  destinationBaseDir?: BaseDirectory
+  /** Detected from the extension of `destination` if not set. */
+  format?: ArchiveFormat
+  /** Called with the progress, at most every 100 milliseconds. */
+  onProgress?: (progress: ArchiveProgress) => void
+}
+
+/**
+ * Writes the files and directories to an archive, each at the root of the archive under its name.
+ *
+ * The entries of the directories out of scope are skipped, the symlinks are stored as links.
+ * Requires the `archive` feature of the Rust crate.
+ * @example
+ * ```typescript
+ * import { compress, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await compress(['notes', 'settings.json'], 'backup.zip', {
+ *   baseDir: BaseDirectory.AppData,
+ *   destinationBaseDir: BaseDirectory.Download,
+ *   onProgress: ({ bytes, totalBytes }) => console.log(bytes, totalBytes),
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function compress(
+  paths: string | string[] | URL | URL[],
+  destination: string | URL,
+  options?: CompressOptions
+): Promise<void> {
+  const sourcePaths = Array.isArray(paths) ? paths : [paths]
+  if (
+    sourcePaths.some((p) => p instanceof URL && p.protocol !== 'file:') ||
+    (destination instanceof URL && destination.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  let onProgress: Channel<ArchiveProgress> | undefined
+  if (options?.onProgress) {
+    onProgress = new Channel<ArchiveProgress>()
+    onProgress.onmessage = options.onProgress
+  }
+
+  await invoke('plugin:fs|compress', {
+    paths: sourcePaths.map((p) => (p instanceof URL ? p.toString() : p)),
+    destination:
+      destination instanceof URL ? destination.toString() : destination,
+    options: options && {
+      baseDir: options.baseDir,
+      destinationBaseDir: options.destinationBaseDir,
+      format: options.format
+    },
+    onProgress
+  })
+}
+
+/**
+ * @since 2.3.0
+ */
+interface ExtractOptions {
+  /** Base directory for `archive`. */
+  baseDir?: BaseDirectory
+  /** Base directory for `destination`. */
+  destinationBaseDir?: BaseDirectory
+  /** Detected from the extension of `archive` if not set. */
+  format?: ArchiveFormat
+  /** The number of leading components removed from the paths of the entries, the entries with none left are skipped. */
+  stripComponents?: number
+  /** Whether to replace the existing files, they are kept otherwise. Defaults to `false`. */
+  overwrite?: boolean
+  /** Called with the progress, at most every 100 milliseconds. */
+  onProgress?: (progress: ArchiveProgress) => void
+}
+
+/**
+ * Extracts an archive to a directory, creating it if needed.
+ *
+ * All the entries must be in scope, including the targets of the symlinks, and the entries escaping the destination are rejected.
+ * Requires the `archive` feature of the Rust crate.
+ * @example
+ * ```typescript
+ * import { extract, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await extract('plugin.tar.gz', 'plugins/example', {
+ *   baseDir: BaseDirectory.Download,
+ *   destinationBaseDir: BaseDirectory.AppData,
+ *   stripComponents: 1,
+ *   overwrite: true,
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function extract(
+  archive: string | URL,
+  destination: string | URL,
+  options?: ExtractOptions
+): Promise<void> {
+  if (
+    (archive instanceof URL && archive.protocol !== 'file:') ||
+    (destination instanceof URL && destination.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  let onProgress: Channel<ArchiveProgress> | undefined
+  if (options?.onProgress) {
+    onProgress = new Channel<ArchiveProgress>()
+    onProgress.onmessage = options.onProgress
+  }
+
+  await invoke('plugin:fs|extract', {
+    archive: archive instanceof URL ? archive.toString() : archive,
+    destination:
+      destination instanceof URL ? destination.toString() : destination,
+    options: options && {
+      baseDir: options.baseDir,
+      destinationBaseDir: options.destinationBaseDir,
+      format: options.format,
+      stripComponents: options.stripComponents,
+      overwrite: options.overwrite
+    },
+    onProgress
+  })
+}
+
 export type {
   CreateOptions,
   OpenOptions,
   CopyFileOptions,
+  CopyOptions,
+  ConflictStrategy,
+  TransferProgress,
   MkdirOptions,
   DirEntry,
   ReadDirOptions,
   ReadFileOptions,
+  ReadFileStreamOptions,
   RemoveOptions,
+  TrashOptions,
   RenameOptions,
   StatOptions,
   TruncateOptions,
@@ -1370,7 +2618,31 @@ export type {
   WatchEventKindCreate,
   WatchEventKindModify,
   WatchEventKindRemove,
-  UnwatchFn
+  UnwatchFn,
+  Watcher,
+  WatchEventFilter,
+  SearchOptions,
+  SearchMatch,
+  LineMatch,
+  HashAlgorithm,
+  HashProgress,
+  HashOptions,
+  ScopeOptions,
+  ScopeEntries,
+  ScopeChange,
+  LockOptions,
+  LinkOptions,
+  ReadlinkOptions,
+  DirSizeProgress,
+  DirSizeOptions,
+  DiskUsage,
+  DiskUsageOptions,
+  ArchiveFormat,
+  ArchiveProgress,
+  CompressOptions,
+  ExtractOptions,
+  SetPermissionsOptions,
+  SetTimesOptions
 }
 
 export {
@@ -1379,12 +2651,16 @@ export {
   create,
   open,
   copyFile,
+  copy,
+  move,
   mkdir,
   readDir,
   readFile,
   readTextFile,
   readTextFileLines,
+  readFileStream,
   remove,
+  trash,
   rename,
   SeekMode,
   stat,
@@ -1395,5 +2671,24 @@ export {
   exists,
   watch,
   watchImmediate,
-  size
+  size,
+  search,
+  hash,
+  allowDirectory,
+  allowFile,
+  forbidDirectory,
+  forbidFile,
+  scopeEntries,
+  onScopeChanged,
+  FileLock,
+  lock,
+  symlink,
+  hardlink,
+  readlink,
+  dirSize,
+  diskUsage,
+  compress,
+  extract,
+  setPermissions,
+  setTimes
 }
@@ -0,0 +1,928 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Runtime, Webview,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use std::{
+    fs::{self, File, Metadata},
+    io::{self, ErrorKind, Read, Seek, Write},
+    path::{Component, Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    commands::{is_allowed, normalize, resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+/// The minimum time between two progress messages, except for the last one.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The Unix file type bits of a symbolic link.
+const SYMLINK_MODE: u32 = 0o120000;
+const FILE_TYPE_MASK: u32 = 0o170000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Detects the format from the extension of the archive.
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveProgress {
+    /// The entry being written.
+    path: PathBuf,
+    /// The bytes of the files written so far, before compression or after extraction.
+    bytes: u64,
+    /// Unknown when extracting a tar archive, as it is read as a stream.
+    total_bytes: Option<u64>,
+    /// The number of entries written so far.
+    entries: u64,
+    /// Unknown when extracting a tar archive, as it is read as a stream.
+    total_entries: Option<u64>,
+}
+
+struct Progress {
+    on_progress: Option<Channel<ArchiveProgress>>,
+    progress: ArchiveProgress,
+    last_progress: Option<Instant>,
+}
+
+impl Progress {
+    fn send(&mut self, last: bool) {
+        let Some(on_progress) = &self.on_progress else {
+            return;
+        };
+        if !last
+            && self
+                .last_progress
+                .is_some_and(|sent| sent.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        let _ = on_progress.send(self.progress.clone());
+        self.last_progress = Some(Instant::now());
+    }
+
+    fn entry(&mut self) {
+        self.progress.entries += 1;
+        self.send(false);
+    }
+}
+
+/// Counts the bytes read for the progress.
+struct ProgressReader<'a, R> {
+    reader: R,
+    progress: &'a mut Progress,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.progress.progress.bytes += n as u64;
+        self.progress.send(false);
+        Ok(n)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressOptions {
+    base_dir: Option<BaseDirectory>,
+    destination_base_dir: Option<BaseDirectory>,
+    /// Detected from the extension of the destination if not set.
+    format: Option<ArchiveFormat>,
+}
+
+/// Writes the files and directories to an archive, each at its root under its name.
+///
+/// The entries of the directories out of scope are skipped, the symbolic links are stored as links.
+#[tauri::command]
+pub async fn compress<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    paths: Vec<SafeFilePath>,
+    destination: SafeFilePath,
+    options: Option<CompressOptions>,
+    on_progress: Option<Channel<ArchiveProgress>>,
+) -> CommandResult<()> {
+    let mut resolved_paths = Vec::with_capacity(paths.len());
+    for path in paths {
+        resolved_paths.push(resolve_path(
+            &webview,
+            &global_scope,
+            &command_scope,
+            path,
+            options.as_ref().and_then(|o| o.base_dir),
+        )?);
+    }
+    let destination = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        destination,
+        options.as_ref().and_then(|o| o.destination_base_dir),
+    )?;
+    let format = options
+        .as_ref()
+        .and_then(|o| o.format)
+        .or_else(|| ArchiveFormat::from_path(&destination))
+        .ok_or_else(|| {
+            format!(
+                "unknown archive format of path: {}, set `format`",
+                destination.display()
+            )
+        })?;
+    let is_allowed = is_allowed(&webview, &global_scope, &command_scope)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (total_entries, total_bytes) =
+            resolved_paths.iter().try_fold((0, 0), |total, path| {
+                let (entries, bytes) = measure(path, &is_allowed)?;
+                io::Result::Ok((total.0 + entries, total.1 + bytes))
+            })?;
+        let mut compressor = Compressor {
+            is_allowed,
+            progress: Progress {
+                on_progress,
+                progress: ArchiveProgress {
+                    total_bytes: Some(total_bytes),
+                    total_entries: Some(total_entries),
+                    ..Default::default()
+                },
+                last_progress: None,
+            },
+        };
+
+        let file = File::create(&destination)?;
+        let result = match format {
+            ArchiveFormat::Zip => {
+                let mut writer = ZipWriter::new(file);
+                compressor
+                    .entries(&mut writer, &resolved_paths)
+                    .and_then(|()| writer.finish().map_err(io::Error::from))
+                    .map(|_| ())
+            }
+            ArchiveFormat::TarGz => {
+                let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+                builder.follow_symlinks(false);
+                compressor
+                    .entries(&mut builder, &resolved_paths)
+                    .and_then(|()| builder.into_inner()?.finish().map(|_| ()))
+            }
+        };
+        if result.is_err() {
+            // do not leave a partial archive behind
+            let _ = fs::remove_file(&destination);
+        }
+        compressor.progress.send(true);
+        result
+    })
+    .await?
+    .map_err(|e| format!("failed to compress with error: {e}"))
+    .map_err(Into::into)
+}
+
+/// Counts the entries and the bytes of the files, the symbolic links are not followed.
+fn measure(path: &Path, is_allowed: &impl Fn(&Path) -> bool) -> io::Result<(u64, u64)> {
+    if !is_allowed(path) {
+        return Ok((0, 0));
+    }
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok((
+            1,
+            if metadata.is_file() {
+                metadata.len()
+            } else {
+                0
+            },
+        ));
+    }
+
+    let (mut entries, mut bytes) = (1, 0);
+    for entry in fs::read_dir(path)? {
+        let (entry_entries, entry_bytes) = measure(&entry?.path(), is_allowed)?;
+        entries += entry_entries;
+        bytes += entry_bytes;
+    }
+    Ok((entries, bytes))
+}
+
+trait ArchiveWriter {
+    fn add_dir(&mut self, name: &str, metadata: &Metadata) -> io::Result<()>;
+    fn add_file(
+        &mut self,
+        name: &str,
+        metadata: &Metadata,
+        reader: &mut dyn Read,
+    ) -> io::Result<()>;
+    fn add_symlink(&mut self, name: &str, target: &Path, metadata: &Metadata) -> io::Result<()>;
+}
+
+impl<W: Write + io::Seek> ArchiveWriter for ZipWriter<W> {
+    fn add_dir(&mut self, name: &str, metadata: &Metadata) -> io::Result<()> {
+        self.add_directory(name, zip_options(metadata))
+            .map_err(Into::into)
+    }
+
+    fn add_file(
+        &mut self,
+        name: &str,
+        metadata: &Metadata,
+        reader: &mut dyn Read,
+    ) -> io::Result<()> {
+        self.start_file(
+            name,
+            zip_options(metadata)
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(metadata.len() >= u32::MAX as u64),
+        )?;
+        io::copy(reader, self).map(|_| ())
+    }
+
+    fn add_symlink(&mut self, name: &str, target: &Path, metadata: &Metadata) -> io::Result<()> {
+        ZipWriter::add_symlink(self, name, target.to_string_lossy(), zip_options(metadata))
+            .map_err(Into::into)
+    }
+}
+
+fn zip_options(#[allow(unused)] metadata: &Metadata) -> SimpleFileOptions {
+    let options = SimpleFileOptions::default();
+    #[cfg(unix)]
+    let options = {
+        use std::os::unix::fs::PermissionsExt;
+        options.unix_permissions(metadata.permissions().mode())
+    };
+    options
+}
+
+impl<W: Write> ArchiveWriter for tar::Builder<W> {
+    fn add_dir(&mut self, name: &str, metadata: &Metadata) -> io::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(metadata);
+        self.append_data(&mut header, name, io::empty())
+    }
+
+    fn add_file(
+        &mut self,
+        name: &str,
+        metadata: &Metadata,
+        reader: &mut dyn Read,
+    ) -> io::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(metadata);
+        self.append_data(&mut header, name, reader)
+    }
+
+    fn add_symlink(&mut self, name: &str, target: &Path, metadata: &Metadata) -> io::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(metadata);
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        self.append_link(&mut header, name, target)
+    }
+}
+
+struct Compressor<F> {
+    is_allowed: F,
+    progress: Progress,
+}
+
+impl<F: Fn(&Path) -> bool> Compressor<F> {
+    fn entries(&mut self, writer: &mut dyn ArchiveWriter, paths: &[PathBuf]) -> io::Result<()> {
+        for path in paths {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .ok_or_else(|| {
+                    io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("path: {} has no name", path.display()),
+                    )
+                })?;
+            self.entry(writer, path, name)?;
+        }
+        Ok(())
+    }
+
+    fn entry(
+        &mut self,
+        writer: &mut dyn ArchiveWriter,
+        path: &Path,
+        name: String,
+    ) -> io::Result<()> {
+        if !(self.is_allowed)(path) {
+            return Ok(());
+        }
+        self.progress.progress.path = path.to_path_buf();
+
+        let metadata = fs::symlink_metadata(path)?;
+        if metadata.is_dir() {
+            writer.add_dir(&format!("{name}/"), &metadata)?;
+            self.progress.entry();
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                let entry_name = format!("{name}/{}", entry.file_name().to_string_lossy());
+                self.entry(writer, &entry.path(), entry_name)?;
+            }
+        } else if metadata.is_symlink() {
+            writer.add_symlink(&name, &fs::read_link(path)?, &metadata)?;
+            self.progress.entry();
+        } else {
+            let mut reader = ProgressReader {
+                reader: File::open(path)?,
+                progress: &mut self.progress,
+            };
+            writer.add_file(&name, &metadata, &mut reader)?;
+            self.progress.entry();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractOptions {
+    base_dir: Option<BaseDirectory>,
+    destination_base_dir: Option<BaseDirectory>,
+    /// Detected from the extension of the archive if not set.
+    format: Option<ArchiveFormat>,
+    /// The number of leading components removed from the paths of the entries,
+    /// the entries with no component left are skipped.
+    #[serde(default)]
+    strip_components: usize,
+    /// Whether to replace the existing files, they are kept otherwise.
+    #[serde(default)]
+    overwrite: bool,
+}
+
+/// Extracts the archive to the destination directory, creating it if needed.
+///
+/// All the entries must be in scope, the symbolic links must point in scope too.
+#[tauri::command]
+pub async fn extract<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    archive: SafeFilePath,
+    destination: SafeFilePath,
+    options: Option<ExtractOptions>,
+    on_progress: Option<Channel<ArchiveProgress>>,
+) -> CommandResult<()> {
+    let archive = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        archive,
+        options.as_ref().and_then(|o| o.base_dir),
+    )?;
+    let destination = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        destination,
+        options.as_ref().and_then(|o| o.destination_base_dir),
+    )?;
+    let format = options
+        .as_ref()
+        .and_then(|o| o.format)
+        .or_else(|| ArchiveFormat::from_path(&archive))
+        .ok_or_else(|| {
+            format!(
+                "unknown archive format of path: {}, set `format`",
+                archive.display()
+            )
+        })?;
+    let is_allowed = is_allowed(&webview, &global_scope, &command_scope)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut extractor = Extractor {
+            destination,
+            strip_components: options.as_ref().map_or(0, |o| o.strip_components),
+            overwrite: options.as_ref().is_some_and(|o| o.overwrite),
+            is_allowed,
+            progress: Progress {
+                on_progress,
+                progress: ArchiveProgress::default(),
+                last_progress: None,
+            },
+        };
+        fs::create_dir_all(&extractor.destination)?;
+
+        let file = File::open(&archive)?;
+        let result = match format {
+            ArchiveFormat::Zip => extractor.zip(file),
+            ArchiveFormat::TarGz => extractor.tar(GzDecoder::new(file)),
+        };
+        extractor.progress.send(true);
+        result
+    })
+    .await?
+    .map_err(|e| format!("failed to extract with error: {e}"))
+    .map_err(Into::into)
+}
+
+struct Extractor<F> {
+    destination: PathBuf,
+    strip_components: usize,
+    overwrite: bool,
+    is_allowed: F,
+    progress: Progress,
+}
+
+impl<F: Fn(&Path) -> bool> Extractor<F> {
+    fn zip(&mut self, reader: impl Read + Seek) -> io::Result<()> {
+        let mut archive = ZipArchive::new(reader)?;
+        let mut total_bytes = 0;
+        for i in 0..archive.len() {
+            total_bytes += archive.by_index_raw(i)?.size();
+        }
+        self.progress.progress.total_bytes = Some(total_bytes);
+        self.progress.progress.total_entries = Some(archive.len() as u64);
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let Some(path) = self.entry_path(Path::new(file.name()))? else {
+                continue;
+            };
+            let mode = file.unix_mode();
+
+            if file.is_dir() {
+                fs::create_dir_all(&path)?;
+            } else if mode.is_some_and(|mode| mode & FILE_TYPE_MASK == SYMLINK_MODE) {
+                let mut target = String::new();
+                file.read_to_string(&mut target)?;
+                self.symlink(&path, Path::new(&target))?;
+            } else {
+                self.file(&path, &mut file, mode, None)?;
+            }
+            self.progress.entry();
+        }
+        Ok(())
+    }
+
+    fn tar(&mut self, reader: impl Read) -> io::Result<()> {
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let Some(path) = self.entry_path(&entry.path()?)? else {
+                continue;
+            };
+            let header = entry.header();
+            let mode = header.mode().ok();
+            let mtime = header
+                .mtime()
+                .ok()
+                .map(|mtime| UNIX_EPOCH + Duration::from_secs(mtime));
+
+            match header.entry_type() {
+                tar::EntryType::Directory => fs::create_dir_all(&path)?,
+                tar::EntryType::Symlink => {
+                    let Some(target) = entry.link_name()? else {
+                        continue;
+                    };
+                    self.symlink(&path, &target)?;
+                }
+                tar::EntryType::Regular | tar::EntryType::Continuous => {
+                    self.file(&path, &mut entry, mode, mtime)?;
+                }
+                // the hard links, devices and pipes are not extracted
+                _ => continue,
+            }
+            self.progress.entry();
+        }
+        Ok(())
+    }
+
+    /// Resolves the path of an entry in the destination, `None` if the entry is skipped.
+    fn entry_path(&mut self, name: &Path) -> io::Result<Option<PathBuf>> {
+        let mut components = Vec::new();
+        for component in name.components() {
+            match component {
+                Component::Normal(component) => components.push(component),
+                Component::CurDir => {}
+                // the entries must not be written out of the destination
+                _ => {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidData,
+                        format!("invalid archive entry path: {}", name.display()),
+                    ))
+                }
+            }
+        }
+        if components.len() <= self.strip_components {
+            return Ok(None);
+        }
+
+        let path = components[self.strip_components..]
+            .iter()
+            .fold(self.destination.clone(), |path, component| {
+                path.join(component)
+            });
+        if !(self.is_allowed)(&path) || !self.is_in_destination(&path)? {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("forbidden path: {}", path.display()),
+            ));
+        }
+        self.progress.progress.path = path.clone();
+        Ok(Some(path))
+    }
+
+    /// Whether the existing directories above the path are in the destination once resolved,
+    /// so that nothing is written through the links extracted before.
+    fn is_in_destination(&self, path: &Path) -> io::Result<bool> {
+        let Some(mut ancestor) = path.parent() else {
+            return Ok(false);
+        };
+        while fs::symlink_metadata(ancestor).is_err() {
+            match ancestor.parent() {
+                Some(parent) => ancestor = parent,
+                None => return Ok(false),
+            }
+        }
+        let destination = self.destination.canonicalize()?;
+        Ok(ancestor
+            .canonicalize()
+            .is_ok_and(|ancestor| ancestor.starts_with(destination)))
+    }
+
+    /// Returns `false` if the existing entry is kept, removes it otherwise.
+    fn replace(&self, path: &Path) -> io::Result<bool> {
+        let Ok(metadata) = fs::symlink_metadata(path) else {
+            return Ok(true);
+        };
+        if !self.overwrite {
+            return Ok(false);
+        }
+        // a link is replaced rather than written through
+        if metadata.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+        Ok(true)
+    }
+
+    fn file(
+        &mut self,
+        path: &Path,
+        reader: &mut dyn Read,
+        #[allow(unused)] mode: Option<u32>,
+        mtime: Option<SystemTime>,
+    ) -> io::Result<()> {
+        if !self.replace(path)? {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = File::create(path)?;
+        io::copy(
+            &mut ProgressReader {
+                reader,
+                progress: &mut self.progress,
+            },
+            &mut file,
+        )?;
+
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(mode & 0o7777))?;
+        }
+        if let Some(mtime) = mtime {
+            file.set_modified(mtime)?;
+        }
+        Ok(())
+    }
+
+    fn symlink(&mut self, path: &Path, target: &Path) -> io::Result<()> {
+        let resolved_target = match path.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target.to_path_buf(),
+        };
+        // the target may not exist yet, so its `..` components are resolved lexically
+        let allowed = normalize(&resolved_target).is_some_and(|target| (self.is_allowed)(&target));
+        if !allowed {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "forbidden link target: {} of entry: {}",
+                    target.display(),
+                    path.display()
+                ),
+            ));
+        }
+        if !self.replace(path)? {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, path)
+        }
+        #[cfg(windows)]
+        {
+            // Windows distinguishes the links to directories from the links to files
+            if resolved_target.is_dir() {
+                std::os::windows::fs::symlink_dir(target, path)
+            } else {
+                std::os::windows::fs::symlink_file(target, path)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// An empty directory for the files extracted by a test.
+    fn destination(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-fs-archive-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("destination")).unwrap();
+        dir.join("destination")
+    }
+
+    /// An extractor allowing the paths in the destination only.
+    fn extractor(destination: &Path, strip_components: usize) -> Extractor<impl Fn(&Path) -> bool> {
+        scoped_extractor(destination, destination, strip_components)
+    }
+
+    /// An extractor allowing the paths in `scope` only.
+    fn scoped_extractor(
+        destination: &Path,
+        scope: &Path,
+        strip_components: usize,
+    ) -> Extractor<impl Fn(&Path) -> bool> {
+        let scope = scope.to_path_buf();
+        Extractor {
+            destination: destination.to_path_buf(),
+            strip_components,
+            overwrite: false,
+            is_allowed: move |path: &Path| path.starts_with(&scope),
+            progress: Progress {
+                on_progress: None,
+                progress: ArchiveProgress::default(),
+                last_progress: None,
+            },
+        }
+    }
+
+    fn zip(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        let mut archive = writer.finish().unwrap();
+        archive.set_position(0);
+        archive
+    }
+
+    /// A tar archive with the raw entry names, which `tar::Header::set_path` validates.
+    fn tar(entries: &[(&str, tar::EntryType, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, entry_type, content) in entries {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_entry_type(*entry_type);
+            header.set_mode(0o644);
+            if *entry_type == tar::EntryType::Symlink {
+                header.set_link_name(content).unwrap();
+                header.set_size(0);
+                header.set_cksum();
+                builder.append(&header, io::empty()).unwrap();
+            } else {
+                header.set_size(content.len() as u64);
+                header.set_cksum();
+                builder.append(&header, content.as_bytes()).unwrap();
+            }
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn entry_paths() {
+        let destination = destination("entry-paths");
+        let mut extractor = extractor(&destination, 0);
+
+        assert_eq!(
+            extractor.entry_path(Path::new("dir/file.txt")).unwrap(),
+            Some(destination.join("dir").join("file.txt"))
+        );
+        assert_eq!(
+            extractor.entry_path(Path::new("./dir/./file.txt")).unwrap(),
+            Some(destination.join("dir").join("file.txt"))
+        );
+    }
+
+    #[test]
+    fn zip_slip_is_rejected() {
+        let destination = destination("zip-slip");
+        let mut extractor = extractor(&destination, 0);
+
+        for name in ["../evil.txt", "dir/../../evil.txt", "/evil.txt"] {
+            let error = extractor.entry_path(Path::new(name)).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{name}");
+        }
+        #[cfg(windows)]
+        for name in ["C:\\evil.txt", "C:evil.txt", "\\\\server\\share\\evil.txt"] {
+            let error = extractor.entry_path(Path::new(name)).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData, "{name}");
+        }
+    }
+
+    #[test]
+    fn out_of_scope_entries_are_rejected() {
+        let destination = destination("out-of-scope");
+        let mut extractor = scoped_extractor(&destination, &destination.join("allowed"), 0);
+
+        assert!(extractor.entry_path(Path::new("allowed/file.txt")).is_ok());
+        let error = extractor
+            .entry_path(Path::new("forbidden/file.txt"))
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn strip_components() {
+        let destination = destination("strip-components");
+        let mut extractor = extractor(&destination, 1);
+
+        assert_eq!(
+            extractor
+                .entry_path(Path::new("root/dir/file.txt"))
+                .unwrap(),
+            Some(destination.join("dir").join("file.txt"))
+        );
+        // `.` is not a component
+        assert_eq!(
+            extractor.entry_path(Path::new("./root/file.txt")).unwrap(),
+            Some(destination.join("file.txt"))
+        );
+        // the entries with no component left are skipped
+        assert_eq!(extractor.entry_path(Path::new("root")).unwrap(), None);
+        assert_eq!(extractor.entry_path(Path::new("root/")).unwrap(), None);
+
+        extractor.strip_components = 3;
+        assert_eq!(
+            extractor
+                .entry_path(Path::new("root/dir/file.txt"))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn escaping_symlinks_are_rejected() {
+        let destination = destination("escaping-symlinks");
+        let mut extractor = extractor(&destination, 0);
+        let link = destination.join("dir").join("link");
+
+        for target in ["../../outside", "../dir/../../outside", "/etc/passwd"] {
+            let error = extractor.symlink(&link, Path::new(target)).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::PermissionDenied, "{target}");
+        }
+        assert!(fs::symlink_metadata(&link).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_in_scope() {
+        let destination = destination("symlinks-in-scope");
+        let mut extractor = extractor(&destination, 0);
+        let link = destination.join("dir").join("link");
+
+        extractor.symlink(&link, Path::new("../file.txt")).unwrap();
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("../file.txt"));
+    }
+
+    #[test]
+    fn extracts_zip() {
+        let destination = destination("extract-zip");
+        let mut extractor = extractor(&destination, 1);
+
+        extractor
+            .zip(zip(&[("root/a.txt", b"a"), ("root/sub/b.txt", b"b")]))
+            .unwrap();
+        assert_eq!(fs::read(destination.join("a.txt")).unwrap(), b"a");
+        assert_eq!(
+            fs::read(destination.join("sub").join("b.txt")).unwrap(),
+            b"b"
+        );
+    }
+
+    #[test]
+    fn zip_slip_is_not_extracted() {
+        let destination = destination("extract-zip-slip");
+        let mut extractor = extractor(&destination, 0);
+
+        let error = extractor.zip(zip(&[("../evil.txt", b"evil")])).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(!destination.parent().unwrap().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn extracts_tar() {
+        let destination = destination("extract-tar");
+        let mut extractor = extractor(&destination, 1);
+
+        extractor
+            .tar(Cursor::new(tar(&[
+                ("root/a.txt", tar::EntryType::Regular, "a"),
+                ("root/sub/b.txt", tar::EntryType::Regular, "b"),
+            ])))
+            .unwrap();
+        assert_eq!(fs::read(destination.join("a.txt")).unwrap(), b"a");
+        assert_eq!(
+            fs::read(destination.join("sub").join("b.txt")).unwrap(),
+            b"b"
+        );
+    }
+
+    #[test]
+    fn tar_slip_is_not_extracted() {
+        let destination = destination("extract-tar-slip");
+
+        let error = extractor(&destination, 0)
+            .tar(Cursor::new(tar(&[(
+                "../evil.txt",
+                tar::EntryType::Regular,
+                "evil",
+            )])))
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let error = extractor(&destination, 0)
+            .tar(Cursor::new(tar(&[(
+                "link",
+                tar::EntryType::Symlink,
+                "../outside",
+            )])))
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+
+        let parent = destination.parent().unwrap();
+        assert!(!parent.join("evil.txt").exists());
+        assert!(fs::symlink_metadata(destination.join("link")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tar_symlink_chain_is_not_extracted() {
+        let destination = destination("extract-tar-symlink-chain");
+
+        // `x` resolves to the parent of the destination through `y`
+        let error = extractor(&destination, 0)
+            .tar(Cursor::new(tar(&[
+                ("y", tar::EntryType::Symlink, "."),
+                ("x", tar::EntryType::Symlink, "y/.."),
+                ("x/evil.txt", tar::EntryType::Regular, "evil"),
+            ])))
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+        assert!(!destination.parent().unwrap().join("evil.txt").exists());
+    }
+}
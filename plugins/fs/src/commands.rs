@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use tauri::{
-    ipc::{CommandScope, GlobalScope},
+    ipc::{Channel, CommandScope, GlobalScope, InvokeResponseBody},
     path::BaseDirectory,
     utils::config::FsScope,
     Manager, Resource, ResourceId, Runtime, Webview,
@@ -16,9 +16,9 @@ use std::{
     borrow::Cow,
     fs::File,
     io::{BufRead, BufReader, Read, Write},
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     str::FromStr,
-    sync::Mutex,
+    sync::{Arc, Condvar, Mutex},
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -251,21 +251,36 @@ pub struct DirEntry {
     pub is_symlink: bool,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadDirOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Whether the symbolic links report the type of their target, if it is in scope.
+    #[serde(default)]
+    follow_symlinks: bool,
+}
+
 #[tauri::command]
 pub async fn read_dir<R: Runtime>(
     webview: Webview<R>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
-    options: Option<BaseOptions>,
+    options: Option<ReadDirOptions>,
 ) -> CommandResult<Vec<DirEntry>> {
     let resolved_path = resolve_path(
         &webview,
         &global_scope,
         &command_scope,
         path,
-        options.as_ref().and_then(|o| o.base_dir),
+        options.as_ref().and_then(|o| o.base.base_dir),
     )?;
+    let is_allowed = if options.as_ref().is_some_and(|o| o.follow_symlinks) {
+        Some(is_allowed(&webview, &global_scope, &command_scope)?)
+    } else {
+        None
+    };
 
     let entries = std::fs::read_dir(&resolved_path).map_err(|e| {
         format!(
@@ -279,9 +294,19 @@ pub async fn read_dir<R: Runtime>(
             let entry = entry.ok()?;
             let name = entry.file_name().into_string().ok()?;
             let metadata = entry.file_type();
+            let is_symlink = metadata.as_ref().is_ok_and(|m| m.is_symlink());
+            let target = match &is_allowed {
+                Some(is_allowed) if is_symlink => dunce::canonicalize(entry.path())
+                    .ok()
+                    .filter(|target| is_allowed(target))
+                    .and_then(|target| std::fs::metadata(target).ok()),
+                _ => None,
+            };
             macro_rules! method_or_false {
                 ($method:ident) => {
-                    if let Ok(metadata) = &metadata {
+                    if let Some(target) = &target {
+                        target.$method()
+                    } else if let Ok(metadata) = &metadata {
                         metadata.$method()
                     } else {
                         false
@@ -292,7 +317,7 @@ pub async fn read_dir<R: Runtime>(
                 name,
                 is_file: method_or_false!(is_file),
                 is_directory: method_or_false!(is_dir),
-                is_symlink: method_or_false!(is_symlink),
+                is_symlink,
             })
         })
         .collect();
@@ -441,6 +466,98 @@ pub async fn read_text_file_lines_next<R: Runtime>(
     ret.map(tauri::ipc::Response::new)
 }
 
+/// The size of the chunks sent by [`read_file_stream`] if not specified.
+const DEFAULT_STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadFileStreamOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    chunk_size: Option<usize>,
+}
+
+/// Reads the file in chunks sent over the channel, each chunk is only read
+/// once requested with [`read_file_stream_pull`].
+///
+/// The end of the file is signaled with an empty chunk and errors are sent as a string.
+#[tauri::command]
+pub fn read_file_stream<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<ReadFileStreamOptions>,
+    on_chunk: Channel<InvokeResponseBody>,
+) -> CommandResult<ResourceId> {
+    let (mut file, path) = resolve_file(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        OpenOptions {
+            base: BaseOptions {
+                base_dir: options.as_ref().and_then(|o| o.base.base_dir),
+            },
+            options: crate::OpenOptions {
+                read: true,
+                ..Default::default()
+            },
+        },
+    )?;
+    let chunk_size = options
+        .and_then(|o| o.chunk_size)
+        .unwrap_or(DEFAULT_STREAM_CHUNK_SIZE)
+        .max(1);
+
+    let stream = Arc::new(FileStreamResource::new());
+    let rid = webview.resources_table().add_arc(stream.clone());
+
+    std::thread::spawn(move || {
+        let mut buf = vec![0; chunk_size];
+        while stream.wait_for_pull() {
+            match file.read(&mut buf) {
+                Ok(0) => {
+                    let _ = on_chunk.send(InvokeResponseBody::Raw(Vec::new()));
+                    break;
+                }
+                Ok(n) => {
+                    if on_chunk
+                        .send(InvokeResponseBody::Raw(buf[..n].to_vec()))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let error = format!(
+                        "failed to read file at path: {} with error: {e}",
+                        path.display()
+                    );
+                    let _ = on_chunk.send(InvokeResponseBody::Json(
+                        serde_json::to_string(&error).unwrap_or_default(),
+                    ));
+                    break;
+                }
+            }
+        }
+        let _ = webview.resources_table().close(rid);
+    });
+
+    Ok(rid)
+}
+
+/// Allows the [`read_file_stream`] to read and send the next chunk.
+#[tauri::command]
+pub fn read_file_stream_pull<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+) -> CommandResult<()> {
+    let stream = webview.resources_table().get::<FileStreamResource>(rid)?;
+    stream.pull();
+    Ok(())
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct RemoveOptions {
     #[serde(flatten)]
@@ -509,6 +626,46 @@ pub fn remove<R: Runtime>(
     .map_err(Into::into)
 }
 
+#[tauri::command]
+pub fn trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.as_ref().and_then(|o| o.base_dir),
+    )?;
+
+    #[cfg(desktop)]
+    {
+        use crate::FsExt;
+        webview
+            .fs()
+            .trash(resolved_path.as_path())
+            .map_err(|e| {
+                format!(
+                    "failed to move path: {} to the trash with error: {e}",
+                    resolved_path.display()
+                )
+            })
+            .map_err(Into::into)
+    }
+    #[cfg(mobile)]
+    {
+        Err(format!(
+            "failed to move path: {} to the trash: not supported on mobile",
+            resolved_path.display()
+        )
+        .into())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RenameOptions {
@@ -550,6 +707,165 @@ pub fn rename<R: Runtime>(
         .map_err(Into::into)
 }
 
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkType {
+    File,
+    Directory,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkOptions {
+    target_base_dir: Option<BaseDirectory>,
+    base_dir: Option<BaseDirectory>,
+    /// Whether the link points to a file or a directory, only used on Windows.
+    /// Detected from the target if not set.
+    #[serde(rename = "type")]
+    #[cfg_attr(not(windows), allow(dead_code))]
+    kind: Option<SymlinkType>,
+}
+
+/// Resolves the target and the path of a link, returns the target as given,
+/// the target resolved from the directory of the link if relative, and the path.
+fn resolve_link<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    target: SafeFilePath,
+    path: SafeFilePath,
+    options: Option<&LinkOptions>,
+) -> CommandResult<(PathBuf, PathBuf, PathBuf)> {
+    let resolved_path = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    let target = target.into_path()?;
+    let target = match options.and_then(|o| o.target_base_dir) {
+        Some(base_dir) => webview.path().resolve(&target, base_dir)?,
+        None => target,
+    };
+    let resolved_target = match resolved_path.parent() {
+        Some(parent) if target.is_relative() => parent.join(&target),
+        _ => target.clone(),
+    };
+    // the target may not exist yet, so its `..` components are resolved lexically
+    let is_allowed = is_allowed(webview, global_scope, command_scope)?;
+    if !normalize(&resolved_target).is_some_and(|target| is_allowed(&target)) {
+        return Err(CommandError::Plugin(Error::PathForbidden(resolved_target)));
+    }
+
+    Ok((target, resolved_target, resolved_path))
+}
+
+#[tauri::command]
+pub fn symlink<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    target: SafeFilePath,
+    path: SafeFilePath,
+    options: Option<LinkOptions>,
+) -> CommandResult<()> {
+    let (target, resolved_target, resolved_path) = resolve_link(
+        &webview,
+        &global_scope,
+        &command_scope,
+        target,
+        path,
+        options.as_ref(),
+    )?;
+
+    // the link is created with the target as given, a relative target is kept relative
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(&target, &resolved_path);
+    #[cfg(windows)]
+    let result = {
+        let kind = options.as_ref().and_then(|o| o.kind).unwrap_or_else(|| {
+            if resolved_target.is_dir() {
+                SymlinkType::Directory
+            } else {
+                SymlinkType::File
+            }
+        });
+        // Windows distinguishes the links to directories from the links to files
+        match kind {
+            SymlinkType::File => std::os::windows::fs::symlink_file(&target, &resolved_path),
+            SymlinkType::Directory => std::os::windows::fs::symlink_dir(&target, &resolved_path),
+        }
+    };
+
+    result
+        .map_err(|e| {
+            format!(
+                "failed to create symbolic link at path: {} to target: {} with error: {e}",
+                resolved_path.display(),
+                target.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn hardlink<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    target: SafeFilePath,
+    path: SafeFilePath,
+    options: Option<LinkOptions>,
+) -> CommandResult<()> {
+    // unlike a symbolic link, a relative target would be resolved from the current directory
+    let (_, target, resolved_path) = resolve_link(
+        &webview,
+        &global_scope,
+        &command_scope,
+        target,
+        path,
+        options.as_ref(),
+    )?;
+
+    std::fs::hard_link(&target, &resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to create hard link at path: {} to target: {} with error: {e}",
+                resolved_path.display(),
+                target.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn readlink<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<PathBuf> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.as_ref().and_then(|o| o.base_dir),
+    )?;
+
+    std::fs::read_link(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to read symbolic link at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
 #[derive(Serialize_repr, Deserialize_repr, Clone, Copy, Debug)]
 #[repr(u16)]
 pub enum SeekMode {
@@ -664,21 +980,51 @@ fn get_fs_metadata<R: Runtime, F: FnOnce(&PathBuf) -> std::io::Result<std::fs::M
     Ok(metadata)
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    follow_symlinks: Option<bool>,
+}
+
 #[tauri::command]
 pub fn stat<R: Runtime>(
     webview: Webview<R>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
     path: SafeFilePath,
-    options: Option<BaseOptions>,
+    options: Option<StatOptions>,
 ) -> CommandResult<FileInfo> {
+    let follow_symlinks = options
+        .as_ref()
+        .and_then(|o| o.follow_symlinks)
+        .unwrap_or(true);
+    let is_allowed = is_allowed(&webview, &global_scope, &command_scope)?;
     let metadata = get_metadata(
-        |p| std::fs::metadata(p),
+        |p| {
+            if !follow_symlinks {
+                return std::fs::symlink_metadata(p);
+            }
+            if !p.is_symlink() {
+                return std::fs::metadata(p);
+            }
+            // the link must not give access to the metadata of a path out of scope
+            let target = dunce::canonicalize(p)?;
+            if is_allowed(&target) {
+                std::fs::metadata(target)
+            } else {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    format!("forbidden link target: {}", target.display()),
+                ))
+            }
+        },
         &webview,
         &global_scope,
         &command_scope,
         path,
-        options,
+        options.map(|o| o.base),
     )?;
 
     Ok(get_stat(metadata))
@@ -758,6 +1104,111 @@ pub async fn ftruncate<R: Runtime>(
         .map_err(Into::into)
 }
 
+/// Sets the Unix permission bits of the path.
+///
+/// On Windows, only the read-only attribute is set, when none of the write bits are.
+#[tauri::command]
+pub fn set_permissions<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    mode: u32,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.as_ref().and_then(|o| o.base_dir),
+    )?;
+
+    #[cfg(unix)]
+    let permissions = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(mode)
+    };
+    #[cfg(not(unix))]
+    let permissions = {
+        let mut permissions = std::fs::metadata(&resolved_path)
+            .map_err(|e| {
+                format!(
+                    "failed to get metadata of path: {} with error: {e}",
+                    resolved_path.display()
+                )
+            })?
+            .permissions();
+        permissions.set_readonly(mode & 0o222 == 0);
+        permissions
+    };
+
+    std::fs::set_permissions(&resolved_path, permissions)
+        .map_err(|e| {
+            format!(
+                "failed to set permissions of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Sets the access and modification times of the path, in milliseconds since the Unix epoch.
+///
+/// The times that are not set are kept.
+#[tauri::command]
+pub fn set_times<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    atime: Option<u64>,
+    mtime: Option<u64>,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.as_ref().and_then(|o| o.base_dir),
+    )?;
+
+    let mut times = std::fs::FileTimes::new();
+    if let Some(atime) = atime {
+        times = times.set_accessed(UNIX_EPOCH + std::time::Duration::from_millis(atime));
+    }
+    if let Some(mtime) = mtime {
+        times = times.set_modified(UNIX_EPOCH + std::time::Duration::from_millis(mtime));
+    }
+
+    #[allow(unused_mut)]
+    let mut open_options = std::fs::OpenOptions::new();
+    #[cfg(not(windows))]
+    open_options.read(true);
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        const FILE_WRITE_ATTRIBUTES: u32 = 0x0100;
+        // required to open directories
+        const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x02000000;
+        open_options
+            .access_mode(FILE_WRITE_ATTRIBUTES)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS);
+    }
+
+    open_options
+        .open(&resolved_path)
+        .and_then(|file| file.set_times(times))
+        .map_err(|e| {
+            format!(
+                "failed to set times of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
 #[tauri::command]
 pub async fn write<R: Runtime>(
     webview: Webview<R>,
@@ -783,6 +1234,10 @@ pub struct WriteFileOptions {
     create_new: bool,
     #[allow(unused)]
     mode: Option<u32>,
+    #[serde(default)]
+    atomic: bool,
+    #[serde(default)]
+    sync: bool,
 }
 
 fn default_create_value() -> bool {
@@ -822,6 +1277,11 @@ pub async fn write_file<R: Runtime>(
         .and_then(|p| p.to_str().ok())
         .and_then(|opts| serde_json::from_str(opts).ok());
 
+    if let Some(opts) = options.as_ref().filter(|o| o.atomic) {
+        return write_file_atomic(&webview, &global_scope, &command_scope, path, opts, &data);
+    }
+    let sync = options.as_ref().is_some_and(|o| o.sync);
+
     let (mut file, path) = resolve_file(
         &webview,
         &global_scope,
@@ -859,6 +1319,43 @@ pub async fn write_file<R: Runtime>(
     )?;
 
     file.write_all(&data)
+        .and_then(|_| if sync { file.sync_all() } else { Ok(()) })
+        .map_err(|e| {
+            format!(
+                "failed to write bytes to file at path: {} with error: {e}",
+                path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+fn write_file_atomic<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: SafeFilePath,
+    options: &WriteFileOptions,
+    data: &[u8],
+) -> CommandResult<()> {
+    if options.append {
+        return Err(anyhow::anyhow!("atomic writes cannot append to a file").into());
+    }
+
+    let path = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        path,
+        options.base.base_dir,
+    )?;
+    if options.create_new && path.exists() {
+        return Err(format!("file already exists at path: {}", path.display()).into());
+    }
+    if !options.create && !options.create_new && !path.exists() {
+        return Err(format!("file does not exist at path: {}", path.display()).into());
+    }
+
+    crate::write_atomic(&path, data, options.sync, options.mode)
         .map_err(|e| {
             format!(
                 "failed to write bytes to file at path: {} with error: {e}",
@@ -1025,6 +1522,20 @@ pub fn resolve_path<R: Runtime>(
         path
     };
 
+    if is_allowed(webview, global_scope, command_scope)?(&path) {
+        Ok(path)
+    } else {
+        Err(CommandError::Plugin(Error::PathForbidden(path)))
+    }
+}
+
+/// Returns a function checking whether the paths are allowed by the plugin and command scopes,
+/// to check many paths without building the scopes for each.
+pub(crate) fn is_allowed<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+) -> CommandResult<impl Fn(&Path) -> bool + Send + 'static> {
     let fs_scope = webview.state::<crate::Scope>();
 
     let scope = tauri::scope::fs::Scope::new(
@@ -1047,18 +1558,35 @@ pub fn resolve_path<R: Runtime>(
     )?;
 
     let require_literal_leading_dot = fs_scope.require_literal_leading_dot.unwrap_or(cfg!(unix));
+    let plugin_scope = fs_scope.scope.clone();
 
-    if is_forbidden(&fs_scope.scope, &path, require_literal_leading_dot)
-        || is_forbidden(&scope, &path, require_literal_leading_dot)
-    {
-        return Err(CommandError::Plugin(Error::PathForbidden(path)));
-    }
+    Ok(move |path: &Path| {
+        !is_forbidden(&plugin_scope, path, require_literal_leading_dot)
+            && !is_forbidden(&scope, path, require_literal_leading_dot)
+            && (plugin_scope.is_allowed(path) || scope.is_allowed(path))
+    })
+}
 
-    if fs_scope.scope.is_allowed(&path) || scope.is_allowed(&path) {
-        Ok(path)
-    } else {
-        Err(CommandError::Plugin(Error::PathForbidden(path)))
+/// Resolves the `.` and `..` components of the path without accessing the file system,
+/// `None` if it goes above the root.
+pub(crate) fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    return None;
+                }
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
     }
+    Some(normalized)
 }
 
 fn is_forbidden<P: AsRef<Path>>(
@@ -1154,6 +1682,50 @@ impl StdLinesResource {
 
 impl Resource for StdLinesResource {}
 
+/// The chunks requested by the stream reader but not sent yet, `None` once the stream is closed.
+struct FileStreamResource {
+    pulls: Mutex<Option<usize>>,
+    condvar: Condvar,
+}
+
+impl FileStreamResource {
+    fn new() -> Self {
+        Self {
+            pulls: Mutex::new(Some(0)),
+            condvar: Condvar::new(),
+        }
+    }
+
+    fn pull(&self) {
+        if let Some(pulls) = self.pulls.lock().unwrap().as_mut() {
+            *pulls += 1;
+            self.condvar.notify_one();
+        }
+    }
+
+    /// Blocks until a chunk is requested, returns `false` if the stream was closed instead.
+    fn wait_for_pull(&self) -> bool {
+        let mut pulls = self.pulls.lock().unwrap();
+        loop {
+            match pulls.as_mut() {
+                None => return false,
+                Some(0) => pulls = self.condvar.wait(pulls).unwrap(),
+                Some(n) => {
+                    *n -= 1;
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+impl Resource for FileStreamResource {
+    fn close(self: Arc<Self>) {
+        self.pulls.lock().unwrap().take();
+        self.condvar.notify_all();
+    }
+}
+
 // taken from deno source code: https://github.com/denoland/deno/blob/ffffa2f7c44bd26aec5ae1957e0534487d099f48/runtime/ops/fs.rs#L913
 #[inline]
 fn to_msec(maybe_time: std::result::Result<SystemTime, std::io::Error>) -> Option<u64> {
@@ -1183,8 +1755,13 @@ pub struct FileInfo {
     birthtime: Option<u64>,
     readonly: bool,
     // Following are only valid under Windows.
-    file_attribues: Option<u32>,
+    file_attributes: Option<u32>,
+    // Following are only valid under macOS and iOS.
+    flags: Option<u32>,
     // Following are only valid under Unix.
+    ctime: Option<u64>,
+    owner: Option<String>,
+    group: Option<String>,
     dev: Option<u64>,
     ino: Option<u64>,
     mode: Option<u32>,
@@ -1229,10 +1806,31 @@ fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
         readonly: metadata.permissions().readonly(),
         // Following are only valid under Windows.
         #[cfg(windows)]
-        file_attribues: Some(metadata.file_attributes()),
+        file_attributes: Some(metadata.file_attributes()),
         #[cfg(not(windows))]
-        file_attribues: None,
+        file_attributes: None,
+        // Following are only valid under macOS and iOS.
+        #[cfg(target_os = "macos")]
+        flags: Some(std::os::macos::fs::MetadataExt::st_flags(&metadata)),
+        #[cfg(target_os = "ios")]
+        flags: Some(std::os::ios::fs::MetadataExt::st_flags(&metadata)),
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        flags: None,
         // Following are only valid under Unix.
+        #[cfg(unix)]
+        ctime: u64::try_from(metadata.ctime())
+            .ok()
+            .map(|secs| secs * 1000 + metadata.ctime_nsec() as u64 / 1_000_000),
+        #[cfg(not(unix))]
+        ctime: None,
+        #[cfg(unix)]
+        owner: owner::user_name(metadata.uid()),
+        #[cfg(not(unix))]
+        owner: None,
+        #[cfg(unix)]
+        group: owner::group_name(metadata.gid()),
+        #[cfg(not(unix))]
+        group: None,
         dev: usm!(dev),
         ino: usm!(ino),
         mode: usm!(mode),
@@ -1245,6 +1843,61 @@ fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
     }
 }
 
+/// Resolves the names of the owners from their ids.
+#[cfg(unix)]
+mod owner {
+    use std::ffi::CStr;
+
+    /// The names are rarely longer, the buffer grows up to this size otherwise.
+    const MAX_BUFFER_SIZE: usize = 1024 * 1024;
+
+    pub fn user_name(uid: u32) -> Option<String> {
+        let mut buf = vec![0; 1024];
+        loop {
+            // SAFETY: the buffer outlives the entry, which is only read on success
+            unsafe {
+                let mut passwd: libc::passwd = std::mem::zeroed();
+                let mut result = std::ptr::null_mut();
+                let code =
+                    libc::getpwuid_r(uid, &mut passwd, buf.as_mut_ptr(), buf.len(), &mut result);
+                if code == libc::ERANGE && buf.len() < MAX_BUFFER_SIZE {
+                    buf.resize(buf.len() * 2, 0);
+                    continue;
+                }
+                if code != 0 || result.is_null() {
+                    return None;
+                }
+                return Some(
+                    CStr::from_ptr(passwd.pw_name)
+                        .to_string_lossy()
+                        .into_owned(),
+                );
+            }
+        }
+    }
+
+    pub fn group_name(gid: u32) -> Option<String> {
+        let mut buf = vec![0; 1024];
+        loop {
+            // SAFETY: the buffer outlives the entry, which is only read on success
+            unsafe {
+                let mut group: libc::group = std::mem::zeroed();
+                let mut result = std::ptr::null_mut();
+                let code =
+                    libc::getgrgid_r(gid, &mut group, buf.as_mut_ptr(), buf.len(), &mut result);
+                if code == libc::ERANGE && buf.len() < MAX_BUFFER_SIZE {
+                    buf.resize(buf.len() * 2, 0);
+                    continue;
+                }
+                if code != 0 || result.is_null() {
+                    return None;
+                }
+                return Some(CStr::from_ptr(group.gr_name).to_string_lossy().into_owned());
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::io::{BufRead, BufReader};
@@ -1284,4 +1937,17 @@ mod test {
         assert_eq!(string1, string3);
         assert_eq!(string2, string3);
     }
+
+    #[test]
+    fn normalizes_paths() {
+        use super::normalize;
+        use std::path::{Path, PathBuf};
+
+        assert_eq!(
+            normalize(Path::new("/a/b/../c/./d")),
+            Some(PathBuf::from("/a/c/d"))
+        );
+        assert_eq!(normalize(Path::new("/a/../..")), None);
+        assert_eq!(normalize(Path::new("../a")), None);
+    }
 }
@@ -32,4 +32,12 @@ impl<R: Runtime> Fs<R> {
         let path = path_or_err(path)?;
         std::fs::OpenOptions::from(opts).open(path)
     }
+
+    /// Moves the file or directory to the recycle bin of the operating system instead of deleting it.
+    #[cfg(desktop)]
+    pub fn trash<P: Into<FilePath>>(&self, path: P) -> crate::Result<()> {
+        let path = path_or_err(path)?;
+        trash::delete(path)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,272 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    commands::{resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+/// The minimum time between two progress messages, except for the last one.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Cancels the long running commands it is given to, such as `dir_size`.
+#[derive(Default)]
+pub struct CancellationToken(AtomicBool);
+
+impl CancellationToken {
+    fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Resource for CancellationToken {}
+
+#[tauri::command]
+pub fn create_cancellation_token<R: Runtime>(webview: Webview<R>) -> ResourceId {
+    webview.resources_table().add(CancellationToken::default())
+}
+
+#[tauri::command]
+pub fn cancel<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    let token = webview.resources_table().get::<CancellationToken>(rid)?;
+    token.0.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirSizeOptions {
+    base_dir: Option<BaseDirectory>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirSizeProgress {
+    /// The bytes of the files counted so far.
+    bytes: u64,
+    /// The number of files counted so far.
+    files: u64,
+    /// The directory being read.
+    path: PathBuf,
+}
+
+/// Resolves to the size of the files in the directory and its sub directories.
+///
+/// The symbolic links are not followed, and the entries that cannot be read are skipped.
+#[tauri::command]
+pub async fn dir_size<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<DirSizeOptions>,
+    on_progress: Option<Channel<DirSizeProgress>>,
+    cancellation_token: Option<ResourceId>,
+) -> CommandResult<u64> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+    let token = cancellation_token
+        .map(|rid| webview.resources_table().get::<CancellationToken>(rid))
+        .transpose()?;
+
+    let mut dir_size = DirSize {
+        on_progress,
+        token,
+        progress: DirSizeProgress::default(),
+        last_progress: None,
+    };
+    let completed = tauri::async_runtime::spawn_blocking(move || {
+        let completed = dir_size.dir(&resolved_path);
+        dir_size.send_progress(true);
+        completed.then_some(dir_size.progress.bytes)
+    })
+    .await?;
+
+    completed.ok_or_else(|| "the directory size computation was cancelled".into())
+}
+
+struct DirSize {
+    on_progress: Option<Channel<DirSizeProgress>>,
+    token: Option<Arc<CancellationToken>>,
+    progress: DirSizeProgress,
+    last_progress: Option<Instant>,
+}
+
+impl DirSize {
+    /// Returns `false` once cancelled.
+    fn dir(&mut self, path: &Path) -> bool {
+        let Ok(entries) = fs::read_dir(path) else {
+            return true;
+        };
+        self.progress.path = path.to_path_buf();
+
+        for entry in entries.flatten() {
+            if self
+                .token
+                .as_ref()
+                .is_some_and(|token| token.is_cancelled())
+            {
+                return false;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_file() {
+                self.progress.bytes += metadata.len();
+                self.progress.files += 1;
+                self.send_progress(false);
+            } else if metadata.is_dir() && !self.dir(&entry.path()) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn send_progress(&mut self, last: bool) {
+        let Some(on_progress) = &self.on_progress else {
+            return;
+        };
+        if !last
+            && self
+                .last_progress
+                .is_some_and(|sent| sent.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        let _ = on_progress.send(self.progress.clone());
+        self.last_progress = Some(Instant::now());
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    /// The size of the volume in bytes.
+    total: u64,
+    /// The free bytes of the volume, including the bytes reserved to the privileged users.
+    free: u64,
+    /// The free bytes the current user can use.
+    available: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageOptions {
+    base_dir: Option<BaseDirectory>,
+}
+
+/// Resolves to the usage of the volume containing the path.
+#[tauri::command]
+pub fn disk_usage<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<DiskUsageOptions>,
+) -> CommandResult<DiskUsage> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    sys::disk_usage(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to get disk usage at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt, path::Path};
+
+    use super::DiskUsage;
+
+    // the types of the fields depend on the platform
+    #[allow(clippy::unnecessary_cast)]
+    pub fn disk_usage(path: &Path) -> std::io::Result<DiskUsage> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        // SAFETY: the path is a valid C string and the struct is only written by the call
+        let stat = unsafe {
+            let mut stat: libc::statvfs = std::mem::zeroed();
+            if libc::statvfs(path.as_ptr(), &mut stat) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            stat
+        };
+
+        let fragment_size = stat.f_frsize as u64;
+        Ok(DiskUsage {
+            total: stat.f_blocks as u64 * fragment_size,
+            free: stat.f_bfree as u64 * fragment_size,
+            available: stat.f_bavail as u64 * fragment_size,
+        })
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{os::windows::ffi::OsStrExt, path::Path};
+
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    use super::DiskUsage;
+
+    pub fn disk_usage(path: &Path) -> std::io::Result<DiskUsage> {
+        // the function expects a directory
+        let path = if path.is_file() {
+            path.parent().unwrap_or(path)
+        } else {
+            path
+        };
+        let path = path
+            .as_os_str()
+            .encode_wide()
+            .chain(Some(0))
+            .collect::<Vec<_>>();
+
+        let (mut available, mut total, mut free) = (0, 0, 0);
+        // SAFETY: the path is null terminated and the sizes are only written by the call
+        if unsafe { GetDiskFreeSpaceExW(path.as_ptr(), &mut available, &mut total, &mut free) } == 0
+        {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(DiskUsage {
+            total,
+            free,
+            available,
+        })
+    }
+}
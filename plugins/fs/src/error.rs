@@ -24,6 +24,10 @@ pub enum Error {
     #[cfg(feature = "watch")]
     #[error(transparent)]
     Watch(#[from] notify::Error),
+    /// Trash error.
+    #[cfg(desktop)]
+    #[error(transparent)]
+    Trash(#[from] trash::Error),
     #[cfg(target_os = "android")]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
@@ -0,0 +1,152 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Runtime, Webview,
+};
+
+use std::{
+    fmt::Write,
+    fs::File,
+    io::{ErrorKind, Read},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    commands::{resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The minimum time between two progress messages, except for the last one.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum HashAlgorithm {
+    #[serde(rename = "SHA-256")]
+    Sha256,
+    #[serde(rename = "SHA-1")]
+    Sha1,
+    #[serde(rename = "BLAKE3")]
+    Blake3,
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha1(sha1::Sha1),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::default()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Sha1(hasher) => hasher.finalize().to_vec(),
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashOptions {
+    base_dir: Option<BaseDirectory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashProgress {
+    bytes: u64,
+    total_bytes: u64,
+}
+
+/// Hashes the file as it is read, resolves to the lowercase hexadecimal digest.
+#[tauri::command]
+pub async fn hash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    algorithm: HashAlgorithm,
+    options: Option<HashOptions>,
+    on_progress: Option<Channel<HashProgress>>,
+) -> CommandResult<String> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    let digest = tauri::async_runtime::spawn_blocking({
+        let path = resolved_path.clone();
+        move || {
+            let mut file = File::open(&path)?;
+            let total_bytes = file.metadata()?.len();
+            let mut hasher = Hasher::new(algorithm);
+            let mut buf = vec![0; BUFFER_SIZE];
+            let mut bytes = 0;
+            let mut last_progress: Option<Instant> = None;
+            loop {
+                let n = match file.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                };
+                hasher.update(&buf[..n]);
+                bytes += n as u64;
+
+                if let Some(on_progress) = &on_progress {
+                    if last_progress.map_or(true, |sent| sent.elapsed() >= PROGRESS_INTERVAL) {
+                        let _ = on_progress.send(HashProgress { bytes, total_bytes });
+                        last_progress = Some(Instant::now());
+                    }
+                }
+            }
+            if let Some(on_progress) = &on_progress {
+                let _ = on_progress.send(HashProgress { bytes, total_bytes });
+            }
+            Ok(hasher.finalize())
+        }
+    })
+    .await?
+    .map_err(|e| {
+        format!(
+            "failed to hash file at path: {} with error: {e}",
+            resolved_path.display()
+        )
+    })?;
+
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    Ok(hex)
+}
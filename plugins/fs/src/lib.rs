@@ -9,27 +9,39 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
-use std::io::Read;
+use std::{
+    ffi::OsString,
+    io::{Read, Write},
+    path::Path,
+};
 
 use serde::Deserialize;
 use tauri::{
     ipc::ScopeObject,
     plugin::{Builder as PluginBuilder, TauriPlugin},
     utils::{acl::Value, config::FsScope},
-    AppHandle, DragDropEvent, Manager, RunEvent, Runtime, WindowEvent,
+    AppHandle, DragDropEvent, Emitter, Manager, RunEvent, Runtime, WindowEvent,
 };
 
+#[cfg(feature = "archive")]
+mod archive;
 mod commands;
 mod config;
 #[cfg(not(target_os = "android"))]
 mod desktop;
+mod disk;
 mod error;
 mod file_path;
+mod hash;
+mod lock;
 #[cfg(target_os = "android")]
 mod mobile;
 #[cfg(target_os = "android")]
 mod models;
+mod runtime_scope;
 mod scope;
+mod search;
+mod transfer;
 #[cfg(feature = "watch")]
 mod watcher;
 
@@ -43,6 +55,11 @@ pub use error::Error;
 pub use file_path::FilePath;
 pub use file_path::SafeFilePath;
 
+pub use lock::{FileLock, LockOptions};
+pub use runtime_scope::SCOPE_CHANGED_EVENT;
+pub use scope::Entry;
+pub use transfer::COPY_PROGRESS_EVENT;
+
 type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Default, Clone, Deserialize)]
@@ -314,7 +331,117 @@ impl OpenOptions {
     }
 }
 
+/// Options of [`Fs::write`].
+#[derive(Debug, Default, Clone)]
+pub struct WriteOptions {
+    atomic: bool,
+    sync: bool,
+}
+
+impl WriteOptions {
+    /// Creates the options of a plain write, neither atomic nor synced.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the option to write to a temporary file in the same directory and rename it over the file.
+    ///
+    /// A crash mid-write then leaves the file with either its previous or its new contents, never partially written.
+    /// Only supported for file system paths.
+    pub fn atomic(&mut self, atomic: bool) -> &mut Self {
+        self.atomic = atomic;
+        self
+    }
+
+    /// Sets the option to flush the contents to the disk with `fsync` before returning.
+    pub fn sync(&mut self, sync: bool) -> &mut Self {
+        self.sync = sync;
+        self
+    }
+}
+
 impl<R: Runtime> Fs<R> {
+    /// Writes the contents to the file, creating it if needed and replacing its contents.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tauri_plugin_fs::{FsExt, WriteOptions};
+    ///
+    /// tauri::Builder::default()
+    ///     .plugin(tauri_plugin_fs::init())
+    ///     .setup(|app| {
+    ///         app.fs().write(
+    ///             "config.json",
+    ///             "{}",
+    ///             WriteOptions::new().atomic(true).sync(true),
+    ///         )?;
+    ///         Ok(())
+    ///     });
+    /// ```
+    pub fn write<P: Into<FilePath>, C: AsRef<[u8]>>(
+        &self,
+        path: P,
+        contents: C,
+        options: &WriteOptions,
+    ) -> std::io::Result<()> {
+        if options.atomic {
+            let path = path.into().into_path().map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string())
+            })?;
+            return write_atomic(&path, contents.as_ref(), options.sync, None);
+        }
+
+        let mut file = self.open(
+            path,
+            OpenOptions {
+                write: true,
+                create: true,
+                truncate: true,
+                ..Default::default()
+            },
+        )?;
+        file.write_all(contents.as_ref())?;
+        if options.sync {
+            file.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Takes an advisory lock on the file, released when the returned guard is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tauri_plugin_fs::{FsExt, LockOptions};
+    ///
+    /// tauri::Builder::default()
+    ///     .plugin(tauri_plugin_fs::init())
+    ///     .setup(|app| {
+    ///         let lock = app
+    ///             .fs()
+    ///             .lock("data.db", LockOptions::new().exclusive(true).blocking(true))?;
+    ///         // other processes locking the file wait until the lock is dropped
+    ///         drop(lock);
+    ///         Ok(())
+    ///     });
+    /// ```
+    pub fn lock<P: Into<FilePath>>(
+        &self,
+        path: P,
+        options: &LockOptions,
+    ) -> std::io::Result<FileLock> {
+        let file = self.open(
+            path,
+            OpenOptions {
+                read: true,
+                ..Default::default()
+            },
+        )?;
+        FileLock::new(file, options)
+    }
+
     pub fn read_to_string<P: Into<FilePath>>(&self, path: P) -> std::io::Result<String> {
         let mut s = String::new();
         self.open(
@@ -342,6 +469,66 @@ impl<R: Runtime> Fs<R> {
     }
 }
 
+/// Writes to a temporary file in the same directory, then renames it over `path`.
+///
+/// The permissions of the replaced file are kept unless `mode` is set.
+pub(crate) fn write_atomic(
+    path: &Path,
+    contents: &[u8],
+    sync: bool,
+    mode: Option<u32>,
+) -> std::io::Result<()> {
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no file name")
+    })?;
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    // the rename is only atomic within the same file system
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+    let temp_path = dir.join(temp_name);
+
+    let write_and_rename = || {
+        let mut opts = std::fs::OpenOptions::new();
+        opts.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            if let Some(mode) = mode {
+                opts.mode(mode);
+            }
+        }
+        let mut file = opts.open(&temp_path)?;
+        if mode.is_none() {
+            if let Ok(metadata) = std::fs::metadata(path) {
+                file.set_permissions(metadata.permissions())?;
+            }
+        }
+        file.write_all(contents)?;
+        if sync {
+            file.sync_all()?;
+        }
+        drop(file);
+        std::fs::rename(&temp_path, path)
+    };
+    if let Err(e) = write_and_rename() {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    // persist the rename itself, directories cannot be opened as files on Windows
+    #[cfg(unix)]
+    if sync {
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
 // implement ScopeObject here instead of in the scope module because it is also used on the build script
 // and we don't want to add tauri as a build dependency
 impl ScopeObject for scope::Entry {
@@ -370,6 +557,8 @@ pub(crate) struct Scope {
 }
 
 pub trait FsExt<R: Runtime> {
+    /// The plugin scope, the paths allowed or forbidden with it at runtime
+    /// emit the [`SCOPE_CHANGED_EVENT`] to the app.
     fn fs_scope(&self) -> tauri::fs::Scope;
     fn try_fs_scope(&self) -> Option<tauri::fs::Scope>;
 
@@ -405,22 +594,51 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::read_text_file,
             commands::read_text_file_lines,
             commands::read_text_file_lines_next,
+            commands::read_file_stream,
+            commands::read_file_stream_pull,
             commands::remove,
+            commands::trash,
             commands::rename,
+            commands::symlink,
+            commands::hardlink,
+            commands::readlink,
             commands::seek,
             commands::stat,
             commands::lstat,
             commands::fstat,
             commands::truncate,
             commands::ftruncate,
+            commands::set_permissions,
+            commands::set_times,
             commands::write,
             commands::write_file,
             commands::write_text_file,
             commands::exists,
             commands::size,
+            transfer::copy,
+            transfer::move_path,
+            search::search,
+            hash::hash,
+            runtime_scope::allow_directory,
+            runtime_scope::allow_file,
+            runtime_scope::forbid_directory,
+            runtime_scope::forbid_file,
+            runtime_scope::scope_entries,
+            lock::lock,
+            lock::unlock,
+            disk::dir_size,
+            disk::disk_usage,
+            disk::create_cancellation_token,
+            disk::cancel,
+            #[cfg(feature = "archive")]
+            archive::compress,
+            #[cfg(feature = "archive")]
+            archive::extract,
             #[cfg(feature = "watch")]
             watcher::watch,
             #[cfg(feature = "watch")]
+            watcher::set_watch_paths,
+            #[cfg(feature = "watch")]
             watcher::unwatch
         ])
         .setup(|app, api| {
@@ -440,6 +658,11 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             #[cfg(not(target_os = "android"))]
             app.manage(Fs(app.clone()));
 
+            let app_ = app.clone();
+            scope.scope.listen(move |event| {
+                let _ = app_.emit(SCOPE_CHANGED_EVENT, runtime_scope::ScopeChange::from(event));
+            });
+
             app.manage(scope);
             Ok(())
         })
@@ -0,0 +1,275 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+use std::{
+    fs::File,
+    io::ErrorKind,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    commands::{resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+/// The time between two attempts to take a lock with a timeout.
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Options of [`Fs::lock`](crate::Fs::lock).
+#[derive(Debug, Default, Clone)]
+pub struct LockOptions {
+    exclusive: bool,
+    blocking: bool,
+    timeout: Option<Duration>,
+}
+
+impl LockOptions {
+    /// Creates the options of a shared lock, failing if the file is locked exclusively.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the option to take an exclusive lock, only one process can hold it at a time.
+    ///
+    /// Many processes can hold a shared lock at the same time, but none while the file is locked exclusively.
+    pub fn exclusive(&mut self, exclusive: bool) -> &mut Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Sets the option to wait for the lock when the file is already locked instead of failing.
+    pub fn blocking(&mut self, blocking: bool) -> &mut Self {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Sets the maximum time to wait for the lock, only used when blocking.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout.replace(timeout);
+        self
+    }
+}
+
+/// An advisory lock on a file, released when dropped.
+///
+/// The lock only coordinates the processes locking the file,
+/// it does not prevent the other processes from reading or writing it.
+#[derive(Debug)]
+pub struct FileLock {
+    file: Option<File>,
+}
+
+impl FileLock {
+    /// Locks the file, which must be open for reading or writing.
+    pub fn new(file: File, options: &LockOptions) -> std::io::Result<Self> {
+        if !options.blocking {
+            sys::lock(&file, options.exclusive, false)?;
+        } else if let Some(timeout) = options.timeout {
+            let start = Instant::now();
+            loop {
+                match sys::lock(&file, options.exclusive, false) {
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        if start.elapsed() >= timeout {
+                            return Err(std::io::Error::new(
+                                ErrorKind::TimedOut,
+                                "timed out waiting for the file lock",
+                            ));
+                        }
+                        std::thread::sleep(RETRY_INTERVAL);
+                    }
+                    result => {
+                        result?;
+                        break;
+                    }
+                }
+            }
+        } else {
+            sys::lock(&file, options.exclusive, true)?;
+        }
+
+        Ok(Self { file: Some(file) })
+    }
+
+    /// The locked file.
+    pub fn file(&self) -> &File {
+        self.file.as_ref().unwrap()
+    }
+
+    /// Releases the lock, unlike dropping it this reports the errors.
+    pub fn unlock(mut self) -> std::io::Result<()> {
+        sys::unlock(&self.file.take().unwrap())
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        if let Some(file) = &self.file {
+            let _ = sys::unlock(file);
+        }
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::{fs::File, os::unix::io::AsRawFd};
+
+    pub fn lock(file: &File, exclusive: bool, blocking: bool) -> std::io::Result<()> {
+        let mut operation = if exclusive {
+            libc::LOCK_EX
+        } else {
+            libc::LOCK_SH
+        };
+        if !blocking {
+            operation |= libc::LOCK_NB;
+        }
+        flock(file, operation)
+    }
+
+    pub fn unlock(file: &File) -> std::io::Result<()> {
+        flock(file, libc::LOCK_UN)
+    }
+
+    fn flock(file: &File, operation: libc::c_int) -> std::io::Result<()> {
+        // SAFETY: the file descriptor is valid as long as the file is borrowed
+        if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::{fs::File, io::ErrorKind, os::windows::io::AsRawHandle};
+
+    use windows_sys::Win32::{
+        Foundation::ERROR_LOCK_VIOLATION,
+        Storage::FileSystem::{
+            LockFileEx, UnlockFile, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+        },
+        System::IO::OVERLAPPED,
+    };
+
+    pub fn lock(file: &File, exclusive: bool, blocking: bool) -> std::io::Result<()> {
+        let mut flags = 0;
+        if exclusive {
+            flags |= LOCKFILE_EXCLUSIVE_LOCK;
+        }
+        if !blocking {
+            flags |= LOCKFILE_FAIL_IMMEDIATELY;
+        }
+        // SAFETY: the handle is valid as long as the file is borrowed, the overlapped structure is only read during the call
+        let locked = unsafe {
+            let mut overlapped: OVERLAPPED = std::mem::zeroed();
+            // locks the whole file, whatever its size
+            LockFileEx(
+                file.as_raw_handle(),
+                flags,
+                0,
+                u32::MAX,
+                u32::MAX,
+                &mut overlapped,
+            )
+        };
+        if locked != 0 {
+            return Ok(());
+        }
+
+        let e = std::io::Error::last_os_error();
+        if e.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) {
+            Err(std::io::Error::new(ErrorKind::WouldBlock, e))
+        } else {
+            Err(e)
+        }
+    }
+
+    pub fn unlock(file: &File) -> std::io::Result<()> {
+        // SAFETY: the handle is valid as long as the file is borrowed
+        if unsafe { UnlockFile(file.as_raw_handle(), 0, 0, u32::MAX, u32::MAX) } != 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockCommandOptions {
+    base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    exclusive: bool,
+    #[serde(default)]
+    blocking: bool,
+    /// In milliseconds.
+    timeout: Option<u64>,
+}
+
+struct FileLockResource(FileLock);
+
+impl Resource for FileLockResource {}
+
+#[tauri::command]
+pub async fn lock<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<LockCommandOptions>,
+) -> CommandResult<ResourceId> {
+    let options = options.unwrap_or_default();
+    let path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+
+    let mut lock_options = LockOptions::new();
+    lock_options
+        .exclusive(options.exclusive)
+        .blocking(options.blocking);
+    if let Some(timeout) = options.timeout {
+        lock_options.timeout(Duration::from_millis(timeout));
+    }
+
+    // waiting for the lock blocks the thread
+    let lock = tauri::async_runtime::spawn_blocking({
+        let path = path.clone();
+        move || FileLock::new(File::open(path)?, &lock_options)
+    })
+    .await?
+    .map_err(|e| {
+        format!(
+            "failed to lock file at path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+
+    Ok(webview.resources_table().add(FileLockResource(lock)))
+}
+
+#[tauri::command]
+pub fn unlock<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    let lock = webview.resources_table().take::<FileLockResource>(rid)?;
+    // the lock is released when the last reference is dropped otherwise
+    match Arc::into_inner(lock) {
+        Some(FileLockResource(lock)) => lock
+            .unlock()
+            .map_err(|e| format!("failed to unlock file with error: {e}").into()),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    path::BaseDirectory,
+    utils::config::FsScope,
+    Manager, Runtime, Webview,
+};
+
+use std::path::PathBuf;
+
+use crate::{commands::CommandResult, scope::Entry, Error, FsExt, SafeFilePath};
+
+/// The event emitted to the app when a path is allowed or forbidden at runtime,
+/// from Rust with [`FsExt::fs_scope`] or from the webview.
+pub const SCOPE_CHANGED_EVENT: &str = "fs://scope-changed";
+
+/// The payload of the scope changed event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "path", rename_all = "camelCase")]
+pub enum ScopeChange {
+    Allowed(PathBuf),
+    Forbidden(PathBuf),
+}
+
+impl From<&tauri::scope::fs::Event> for ScopeChange {
+    fn from(event: &tauri::scope::fs::Event) -> Self {
+        match event {
+            tauri::scope::fs::Event::PathAllowed(path) => Self::Allowed(path.clone()),
+            tauri::scope::fs::Event::PathForbidden(path) => Self::Forbidden(path.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeOptions {
+    base_dir: Option<BaseDirectory>,
+    /// Whether the entries of the sub directories are included, only used with directories.
+    #[serde(default)]
+    recursive: bool,
+}
+
+/// The patterns of the plugin scope, including the paths added at runtime.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeEntries {
+    allowed: Vec<String>,
+    forbidden: Vec<String>,
+}
+
+#[tauri::command]
+pub fn allow_directory<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<ScopeOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let path = resolve_grantable_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    webview
+        .fs_scope()
+        .allow_directory(path, options.recursive)
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn allow_file<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<ScopeOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let path = resolve_grantable_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    webview.fs_scope().allow_file(path).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn forbid_directory<R: Runtime>(
+    webview: Webview<R>,
+    path: SafeFilePath,
+    options: Option<ScopeOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let path = resolve(&webview, path, options.base_dir)?;
+    webview
+        .fs_scope()
+        .forbid_directory(path, options.recursive)
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn forbid_file<R: Runtime>(
+    webview: Webview<R>,
+    path: SafeFilePath,
+    options: Option<ScopeOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let path = resolve(&webview, path, options.base_dir)?;
+    webview.fs_scope().forbid_file(path).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn scope_entries<R: Runtime>(webview: Webview<R>) -> ScopeEntries {
+    let scope = webview.fs_scope();
+    let mut allowed = scope
+        .allowed_patterns()
+        .iter()
+        .map(|pattern| pattern.as_str().to_string())
+        .collect::<Vec<_>>();
+    let mut forbidden = scope
+        .forbidden_patterns()
+        .iter()
+        .map(|pattern| pattern.as_str().to_string())
+        .collect::<Vec<_>>();
+    allowed.sort();
+    forbidden.sort();
+    ScopeEntries { allowed, forbidden }
+}
+
+fn resolve<R: Runtime>(
+    webview: &Webview<R>,
+    path: SafeFilePath,
+    base_dir: Option<BaseDirectory>,
+) -> CommandResult<PathBuf> {
+    let path = path.into_path()?;
+    if let Some(base_dir) = base_dir {
+        Ok(webview.path().resolve(&path, base_dir)?)
+    } else {
+        Ok(path)
+    }
+}
+
+/// Resolves a path the webview wants to allow.
+///
+/// The path must be allowed by the scope of the permission, or by the global `fs:scope` when the
+/// permission is unscoped, so the webview can't grant itself more than its capabilities allow.
+fn resolve_grantable_path<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: SafeFilePath,
+    base_dir: Option<BaseDirectory>,
+) -> CommandResult<PathBuf> {
+    let path = resolve(webview, path, base_dir)?;
+
+    let allows = if command_scope.allows().is_empty() {
+        global_scope.allows()
+    } else {
+        command_scope.allows()
+    };
+    if allows.is_empty() {
+        return Err(Error::PathForbidden(path).into());
+    }
+    let denies = command_scope
+        .denies()
+        .iter()
+        .chain(global_scope.denies())
+        .filter_map(|e| e.path.clone())
+        .collect();
+
+    let scope = tauri::fs::Scope::new(
+        webview,
+        &FsScope::Scope {
+            allow: paths(allows),
+            deny: denies,
+            require_literal_leading_dot: webview
+                .state::<crate::Scope>()
+                .require_literal_leading_dot,
+        },
+    )?;
+    if scope.is_allowed(&path) {
+        Ok(path)
+    } else {
+        Err(Error::PathForbidden(path).into())
+    }
+}
+
+fn paths(entries: &[Entry]) -> Vec<PathBuf> {
+    entries.iter().filter_map(|e| e.path.clone()).collect()
+}
@@ -0,0 +1,305 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Runtime, Webview,
+};
+
+use std::{
+    fs::{self, File, Metadata},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::{
+    commands::{is_allowed, resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchOptions {
+    base_dir: Option<BaseDirectory>,
+    /// Glob patterns matched against the path relative to the searched directory,
+    /// or against the file name if they have no separator. All the entries match if empty.
+    #[serde(default)]
+    patterns: Vec<String>,
+    /// `1` only searches the direct children of the directory.
+    max_depth: Option<usize>,
+    #[serde(default)]
+    include_hidden: bool,
+    #[serde(default)]
+    include_directories: bool,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    /// In milliseconds since the Unix epoch.
+    modified_after: Option<u64>,
+    /// In milliseconds since the Unix epoch.
+    modified_before: Option<u64>,
+    /// A regular expression the lines of the files must match, the directories never match it.
+    content: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    path: PathBuf,
+    is_directory: bool,
+    size: u64,
+    mtime: Option<u64>,
+    /// The lines matching the content regular expression.
+    lines: Vec<LineMatch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineMatch {
+    /// Starting at 1.
+    line: u64,
+    text: String,
+}
+
+/// Searches the directory, sending the matches over the channel as they are found.
+///
+/// Resolves to the number of matches once the search is over.
+#[tauri::command]
+pub async fn search<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<SearchOptions>,
+    on_match: Channel<SearchMatch>,
+) -> CommandResult<u64> {
+    let options = options.unwrap_or_default();
+    let root = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+    // the entries are checked one by one, the scope may deny some of the directory
+    let is_allowed = is_allowed(&webview, &global_scope, &command_scope)?;
+
+    let patterns = options
+        .patterns
+        .iter()
+        .map(String::as_str)
+        .map(glob::Pattern::new)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(crate::Error::from)?;
+    let content = options
+        .content
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .map_err(|e| format!("invalid content regular expression: {e}"))?;
+
+    let search = Search {
+        options,
+        patterns,
+        content,
+        is_allowed,
+        on_match,
+        matches: 0,
+    };
+    tauri::async_runtime::spawn_blocking(move || search.run(&root))
+        .await?
+        .map_err(|e| format!("failed to search with error: {e}"))
+        .map_err(Into::into)
+}
+
+struct Search<F> {
+    options: SearchOptions,
+    patterns: Vec<glob::Pattern>,
+    content: Option<Regex>,
+    is_allowed: F,
+    on_match: Channel<SearchMatch>,
+    matches: u64,
+}
+
+impl<F: Fn(&Path) -> bool> Search<F> {
+    fn run(mut self, root: &Path) -> std::io::Result<u64> {
+        self.dir(root, root, 1)?;
+        Ok(self.matches)
+    }
+
+    /// Returns `false` once the channel is closed.
+    fn dir(&mut self, root: &Path, dir: &Path, depth: usize) -> std::io::Result<bool> {
+        if self.options.max_depth.is_some_and(|max| depth > max) {
+            return Ok(true);
+        }
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            // unreadable directories are skipped, as the other entries can still match
+            return Ok(true);
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            // the symbolic links are not followed, they could lead out of the directory or loop
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if (!self.options.include_hidden && is_hidden(&path, &metadata))
+                || !(self.is_allowed)(&path)
+            {
+                continue;
+            }
+
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if metadata.is_dir() {
+                if self.options.include_directories
+                    && self.options.content.is_none()
+                    && self.matches(relative, &metadata)
+                    && !self.send(&path, &metadata, Vec::new())
+                {
+                    return Ok(false);
+                }
+                if !self.dir(root, &path, depth + 1)? {
+                    return Ok(false);
+                }
+            } else if self.matches(relative, &metadata) {
+                let lines = match &self.content {
+                    Some(content) => {
+                        let lines = content_matches(&path, content);
+                        if lines.is_empty() {
+                            continue;
+                        }
+                        lines
+                    }
+                    None => Vec::new(),
+                };
+                if !self.send(&path, &metadata, lines) {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Whether the entry matches the patterns and the filters, except for its content.
+    fn matches(&self, relative: &Path, metadata: &Metadata) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let name = relative.file_name().map(Path::new).unwrap_or(relative);
+        let pattern = self.patterns.is_empty()
+            || self.patterns.iter().any(|pattern| {
+                let path = if pattern.as_str().contains('/') {
+                    relative
+                } else {
+                    name
+                };
+                pattern.matches_path_with(path, options)
+            });
+        if !pattern {
+            return false;
+        }
+
+        if !metadata.is_dir() {
+            let size = metadata.len();
+            if self.options.min_size.is_some_and(|min| size < min)
+                || self.options.max_size.is_some_and(|max| size > max)
+            {
+                return false;
+            }
+        }
+
+        if self.options.modified_after.is_some() || self.options.modified_before.is_some() {
+            let Some(mtime) = mtime(metadata) else {
+                return false;
+            };
+            if self
+                .options
+                .modified_after
+                .is_some_and(|after| mtime < after)
+                || self
+                    .options
+                    .modified_before
+                    .is_some_and(|before| mtime > before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns `false` if the channel is closed.
+    fn send(&mut self, path: &Path, metadata: &Metadata, lines: Vec<LineMatch>) -> bool {
+        self.matches += 1;
+        self.on_match
+            .send(SearchMatch {
+                path: path.to_path_buf(),
+                is_directory: metadata.is_dir(),
+                size: metadata.len(),
+                mtime: mtime(metadata),
+                lines,
+            })
+            .is_ok()
+    }
+}
+
+/// The lines of the file matching the regular expression, none for the binary files.
+fn content_matches(path: &Path, content: &Regex) -> Vec<LineMatch> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    let mut reader = BufReader::new(file);
+    let mut lines = Vec::new();
+    let mut buf = Vec::new();
+    let mut line = 0;
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        line += 1;
+        if buf.contains(&0) {
+            return Vec::new();
+        }
+        let text = String::from_utf8_lossy(&buf);
+        let text = text.trim_end_matches(['\n', '\r']);
+        if content.is_match(text) {
+            lines.push(LineMatch {
+                line,
+                text: text.to_string(),
+            });
+        }
+    }
+    lines
+}
+
+fn mtime(metadata: &Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|mtime| mtime.as_millis() as u64)
+}
+
+fn is_hidden(path: &Path, #[allow(unused)] metadata: &Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x00000002;
+        if metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0 {
+            return true;
+        }
+    }
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
@@ -0,0 +1,410 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Emitter, EventTarget, Runtime, Webview,
+};
+
+use std::{
+    fs::{self, File, FileTimes, Metadata},
+    io::{ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    commands::{resolve_path, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+/// The event emitted to the webview with the progress of the `copy` and `move` APIs.
+pub const COPY_PROGRESS_EVENT: &str = "fs://copy-progress";
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The minimum time between two progress events, except for the last one.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// What to do when the destination of a file or directory already exists.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictStrategy {
+    /// Replaces the files, and merges the directories.
+    #[default]
+    Overwrite,
+    /// Keeps the existing file or directory.
+    Skip,
+    /// Copies with a new name such as `file (1).txt`.
+    Rename,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferOptions {
+    from_path_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    recursive: bool,
+    #[serde(default)]
+    conflict: ConflictStrategy,
+    /// Identifies the operation in the progress events.
+    id: Option<u32>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProgress {
+    id: Option<u32>,
+    /// The file being copied.
+    path: PathBuf,
+    /// The bytes of the files copied or skipped so far.
+    bytes: u64,
+    total_bytes: u64,
+    /// The number of files copied or skipped so far.
+    files: u64,
+    total_files: u64,
+}
+
+#[tauri::command]
+pub async fn copy<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<TransferOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let (from, to) = resolve_paths(
+        &webview,
+        &global_scope,
+        &command_scope,
+        from_path,
+        to_path,
+        &options,
+    )?;
+    transfer(webview, from, to, options, false).await
+}
+
+// `move` is a keyword
+#[tauri::command]
+pub async fn move_path<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<TransferOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let (from, to) = resolve_paths(
+        &webview,
+        &global_scope,
+        &command_scope,
+        from_path,
+        to_path,
+        &options,
+    )?;
+    transfer(webview, from, to, options, true).await
+}
+
+fn resolve_paths<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: &TransferOptions,
+) -> CommandResult<(PathBuf, PathBuf)> {
+    let from = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        from_path,
+        options.from_path_base_dir,
+    )?;
+    let to = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+
+    let metadata = fs::symlink_metadata(&from).map_err(|e| {
+        format!(
+            "failed to get metadata of path: {} with error: {e}",
+            from.display()
+        )
+    })?;
+    if metadata.is_dir() {
+        if !options.recursive {
+            return Err(format!(
+                "path: {} is a directory, set `recursive` to copy it",
+                from.display()
+            )
+            .into());
+        }
+        if absolute(&to).starts_with(absolute(&from)) {
+            return Err(format!(
+                "cannot copy directory: {} into itself at path: {}",
+                from.display(),
+                to.display()
+            )
+            .into());
+        }
+    }
+
+    Ok((from, to))
+}
+
+async fn transfer<R: Runtime>(
+    webview: Webview<R>,
+    from: PathBuf,
+    to: PathBuf,
+    options: TransferOptions,
+    remove_source: bool,
+) -> CommandResult<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let (total_files, total_bytes) = measure(&from)?;
+        let mut transfer = Transfer {
+            webview,
+            conflict: options.conflict,
+            remove_source,
+            progress: TransferProgress {
+                id: options.id,
+                total_files,
+                total_bytes,
+                ..Default::default()
+            },
+            last_progress: None,
+            buf: Vec::new(),
+        };
+        transfer.entry(&from, &to)?;
+        transfer.emit_progress(true);
+        Ok::<_, std::io::Error>(())
+    })
+    .await?
+    .map_err(|e| format!("failed to copy with error: {e}"))
+    .map_err(Into::into)
+}
+
+struct Transfer<R: Runtime> {
+    webview: Webview<R>,
+    conflict: ConflictStrategy,
+    remove_source: bool,
+    progress: TransferProgress,
+    last_progress: Option<Instant>,
+    buf: Vec<u8>,
+}
+
+impl<R: Runtime> Transfer<R> {
+    fn emit_progress(&mut self, last: bool) {
+        if !last
+            && self
+                .last_progress
+                .is_some_and(|emitted| emitted.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+        let _ = self.webview.emit_to(
+            EventTarget::webview(self.webview.label()),
+            COPY_PROGRESS_EVENT,
+            &self.progress,
+        );
+        self.last_progress = Some(Instant::now());
+    }
+
+    fn entry(&mut self, from: &Path, to: &Path) -> std::io::Result<()> {
+        let metadata = fs::symlink_metadata(from)?;
+        if metadata.is_dir() {
+            self.dir(from, to, &metadata)
+        } else {
+            self.file(from, to, &metadata)
+        }
+    }
+
+    /// Returns the path to copy to, `None` if the entry must be skipped.
+    fn destination(&self, to: &Path) -> Option<PathBuf> {
+        if fs::symlink_metadata(to).is_err() {
+            return Some(to.to_path_buf());
+        }
+        match self.conflict {
+            ConflictStrategy::Overwrite => Some(to.to_path_buf()),
+            ConflictStrategy::Skip => None,
+            ConflictStrategy::Rename => Some(unique_path(to)),
+        }
+    }
+
+    fn dir(&mut self, from: &Path, to: &Path, metadata: &Metadata) -> std::io::Result<()> {
+        let Some(to) = self.destination(to) else {
+            let (files, bytes) = measure(from)?;
+            self.progress.files += files;
+            self.progress.bytes += bytes;
+            self.emit_progress(false);
+            return Ok(());
+        };
+
+        // moving within the same file system does not need to copy the contents
+        if self.remove_source && fs::symlink_metadata(&to).is_err() && fs::rename(from, &to).is_ok()
+        {
+            let (files, bytes) = measure(&to)?;
+            self.progress.files += files;
+            self.progress.bytes += bytes;
+            self.emit_progress(false);
+            return Ok(());
+        }
+
+        if !to.is_dir() {
+            fs::create_dir(&to)?;
+        }
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            self.entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+
+        // after the contents, as the permissions could deny writing to the directory
+        fs::set_permissions(&to, metadata.permissions())?;
+        #[cfg(unix)]
+        if let Ok(dir) = File::open(&to) {
+            let _ = dir.set_times(file_times(metadata));
+        }
+
+        if self.remove_source {
+            // the skipped entries are kept
+            let _ = fs::remove_dir(from);
+        }
+        Ok(())
+    }
+
+    fn file(&mut self, from: &Path, to: &Path, metadata: &Metadata) -> std::io::Result<()> {
+        self.progress.path = from.to_path_buf();
+        let Some(to) = self.destination(to) else {
+            self.progress.files += 1;
+            self.progress.bytes += metadata.len();
+            self.emit_progress(false);
+            return Ok(());
+        };
+
+        if self.remove_source && fs::rename(from, &to).is_ok() {
+            self.progress.bytes += metadata.len();
+        } else {
+            if metadata.is_symlink() {
+                copy_symlink(from, &to)?;
+                self.progress.bytes += metadata.len();
+            } else {
+                self.copy_file(from, &to, metadata)?;
+            }
+            if self.remove_source {
+                fs::remove_file(from)?;
+            }
+        }
+
+        self.progress.files += 1;
+        self.emit_progress(false);
+        Ok(())
+    }
+
+    /// Copies the contents, then the permissions and the access and modification times.
+    fn copy_file(&mut self, from: &Path, to: &Path, metadata: &Metadata) -> std::io::Result<()> {
+        let mut reader = File::open(from)?;
+        let mut writer = File::create(to)?;
+        self.buf.resize(BUFFER_SIZE, 0);
+        loop {
+            let n = match reader.read(&mut self.buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            writer.write_all(&self.buf[..n])?;
+            self.progress.bytes += n as u64;
+            self.emit_progress(false);
+        }
+
+        writer.set_permissions(metadata.permissions())?;
+        writer.set_times(file_times(metadata))
+    }
+}
+
+fn file_times(metadata: &Metadata) -> FileTimes {
+    let mut times = FileTimes::new();
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    if let Ok(modified) = metadata.modified() {
+        times = times.set_modified(modified);
+    }
+    times
+}
+
+/// Recreates the link, pointing to the same target.
+fn copy_symlink(from: &Path, to: &Path) -> std::io::Result<()> {
+    let target = fs::read_link(from)?;
+    if fs::symlink_metadata(to).is_ok() {
+        fs::remove_file(to)?;
+    }
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, to)
+    }
+    #[cfg(windows)]
+    {
+        // Windows distinguishes the links to directories from the links to files
+        if fs::metadata(from).is_ok_and(|metadata| metadata.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, to)
+        } else {
+            std::os::windows::fs::symlink_file(target, to)
+        }
+    }
+}
+
+/// Counts the files and their bytes, the symbolic links are not followed.
+fn measure(path: &Path) -> std::io::Result<(u64, u64)> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Ok((1, metadata.len()));
+    }
+
+    let (mut files, mut bytes) = (0, 0);
+    for entry in fs::read_dir(path)? {
+        let (entry_files, entry_bytes) = measure(&entry?.path())?;
+        files += entry_files;
+        bytes += entry_bytes;
+    }
+    Ok((files, bytes))
+}
+
+/// Appends ` (1)`, ` (2)`... to the file stem until the path does not exist.
+fn unique_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..)
+        .map(|i| path.with_file_name(format!("{stem} ({i}){extension}")))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap()
+}
+
+/// Resolves the path, which may not exist, to check whether a directory contains it.
+fn absolute(path: &Path) -> PathBuf {
+    if let Ok(path) = dunce::canonicalize(path) {
+        return path;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => absolute(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
 use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, RecommendedCache};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tauri::{
     ipc::{Channel, CommandScope, GlobalScope},
     path::BaseDirectory,
@@ -12,10 +15,10 @@ use tauri::{
 };
 
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        mpsc::{channel, Receiver},
-        Mutex,
+        mpsc::{channel, Receiver, RecvTimeoutError},
+        Arc, Mutex,
     },
     thread::spawn,
     time::Duration,
@@ -27,15 +30,28 @@ use crate::{
     SafeFilePath,
 };
 
+/// How long a rename-from event waits for its rename-to event when pairing the renames.
+const RENAME_TIMEOUT: Duration = Duration::from_millis(50);
+
 struct InnerWatcher {
     pub kind: WatcherKind,
-    paths: Vec<PathBuf>,
+    /// Shared with the thread sending the events, to match the ignore patterns.
+    paths: Arc<Mutex<Vec<PathBuf>>>,
+    recursive_mode: RecursiveMode,
 }
 
 pub struct WatcherResource(Mutex<InnerWatcher>);
 impl WatcherResource {
-    fn new(kind: WatcherKind, paths: Vec<PathBuf>) -> Self {
-        Self(Mutex::new(InnerWatcher { kind, paths }))
+    fn new(
+        kind: WatcherKind,
+        paths: Arc<Mutex<Vec<PathBuf>>>,
+        recursive_mode: RecursiveMode,
+    ) -> Self {
+        Self(Mutex::new(InnerWatcher {
+            kind,
+            paths,
+            recursive_mode,
+        }))
     }
 
     fn with_lock<R, F: FnMut(&mut InnerWatcher) -> R>(&self, mut f: F) -> R {
@@ -51,24 +67,203 @@ enum WatcherKind {
     Watcher(RecommendedWatcher),
 }
 
-fn watch_raw(on_event: Channel<Event>, rx: Receiver<notify::Result<Event>>) {
-    spawn(move || {
-        while let Ok(event) = rx.recv() {
-            if let Ok(event) = event {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event);
+impl WatcherKind {
+    fn watch(&mut self, path: &Path, recursive_mode: RecursiveMode) -> notify::Result<()> {
+        match self {
+            Self::Debouncer(debouncer) => debouncer.watch(path, recursive_mode),
+            Self::Watcher(watcher) => watcher.watch(path, recursive_mode),
+        }
+    }
+
+    fn unwatch(&mut self, path: &Path) -> notify::Result<()> {
+        match self {
+            Self::Debouncer(debouncer) => debouncer.unwatch(path),
+            Self::Watcher(watcher) => watcher.unwatch(path),
+        }
+    }
+}
+
+/// The kinds of events, to only send some of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchEventKind {
+    Access,
+    Create,
+    /// The modifications other than the renames.
+    Modify,
+    Rename,
+    Remove,
+    Other,
+}
+
+impl From<&EventKind> for WatchEventKind {
+    fn from(kind: &EventKind) -> Self {
+        match kind {
+            EventKind::Access(_) => Self::Access,
+            EventKind::Create(_) => Self::Create,
+            EventKind::Modify(ModifyKind::Name(_)) => Self::Rename,
+            EventKind::Modify(_) => Self::Modify,
+            EventKind::Remove(_) => Self::Remove,
+            EventKind::Any | EventKind::Other => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum WatchEvent {
+    Event(Event),
+    /// A rename-from and a rename-to events paired, shaped like the other events.
+    Renamed {
+        #[serde(rename = "type")]
+        kind: RenamedKind,
+        paths: [PathBuf; 2],
+        attrs: serde_json::Map<String, serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenamedKind {
+    renamed: Rename,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Rename {
+    from: PathBuf,
+    to: PathBuf,
+}
+
+/// Filters the events and pairs the renames before sending them.
+struct EventSender {
+    on_event: Channel<WatchEvent>,
+    paths: Arc<Mutex<Vec<PathBuf>>>,
+    ignore: Vec<glob::Pattern>,
+    kinds: Vec<WatchEventKind>,
+    pair_renames: bool,
+    /// A rename-from event waiting for its rename-to event.
+    rename_from: Option<Event>,
+}
+
+impl EventSender {
+    fn push(&mut self, event: Event) {
+        if self.pair_renames {
+            match event.kind {
+                EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                    self.flush();
+                    let mut paths = event.paths.into_iter();
+                    self.send_renamed(paths.next().unwrap(), paths.next().unwrap());
+                    return;
+                }
+                EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    self.flush();
+                    self.rename_from.replace(event);
+                    return;
+                }
+                EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                    let paired = self
+                        .rename_from
+                        .as_ref()
+                        .is_some_and(|from| from.tracker() == event.tracker());
+                    if paired {
+                        let from = self.rename_from.take().unwrap();
+                        if let (Some(from), Some(to)) = (from.paths.first(), event.paths.first()) {
+                            self.send_renamed(from.clone(), to.clone());
+                            return;
+                        }
+                        self.send(from);
+                    }
+                }
+                _ => {}
             }
+            self.flush();
+        }
+
+        self.send(event);
+    }
+
+    /// Sends the rename-from event that was not paired.
+    fn flush(&mut self) {
+        if let Some(event) = self.rename_from.take() {
+            self.send(event);
+        }
+    }
+
+    fn send(&self, event: Event) {
+        if self.is_filtered(WatchEventKind::from(&event.kind), &event.paths) {
+            return;
+        }
+        // TODO: Should errors be emitted too?
+        let _ = self.on_event.send(WatchEvent::Event(event));
+    }
+
+    fn send_renamed(&self, from: PathBuf, to: PathBuf) {
+        if self.is_filtered(WatchEventKind::Rename, &[from.clone(), to.clone()]) {
+            return;
+        }
+        let _ = self.on_event.send(WatchEvent::Renamed {
+            kind: RenamedKind {
+                renamed: Rename {
+                    from: from.clone(),
+                    to: to.clone(),
+                },
+            },
+            paths: [from, to],
+            attrs: Default::default(),
+        });
+    }
+
+    /// Whether the event must not be sent, because of its kind or because all its paths are ignored.
+    fn is_filtered(&self, kind: WatchEventKind, paths: &[PathBuf]) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&kind) {
+            return true;
+        }
+        !self.ignore.is_empty() && !paths.is_empty() && paths.iter().all(|p| self.is_ignored(p))
+    }
+
+    /// Whether a pattern matches the path or one of its parents, relative to the watched path.
+    fn is_ignored(&self, path: &Path) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let paths = self.paths.lock().unwrap();
+        let relative = paths
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        relative.ancestors().any(|ancestor| {
+            self.ignore.iter().any(|pattern| {
+                if pattern.as_str().contains('/') {
+                    pattern.matches_path_with(ancestor, options)
+                } else {
+                    ancestor
+                        .file_name()
+                        .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options))
+                }
+            })
+        })
+    }
+}
+
+fn watch_raw(mut sender: EventSender, rx: Receiver<notify::Result<Event>>) {
+    spawn(move || loop {
+        match rx.recv_timeout(RENAME_TIMEOUT) {
+            Ok(Ok(event)) => sender.push(event),
+            Ok(Err(_)) => {}
+            Err(RecvTimeoutError::Timeout) => sender.flush(),
+            Err(RecvTimeoutError::Disconnected) => break,
         }
     });
 }
 
-fn watch_debounced(on_event: Channel<Event>, rx: Receiver<DebounceEventResult>) {
+fn watch_debounced(mut sender: EventSender, rx: Receiver<DebounceEventResult>) {
     spawn(move || {
         while let Ok(Ok(events)) = rx.recv() {
             for event in events {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event.event);
+                sender.push(event.event);
             }
+            // the debouncer already gathers the rename events occurring together
+            sender.flush();
         }
     });
 }
@@ -79,6 +274,36 @@ pub struct WatchOptions {
     base_dir: Option<BaseDirectory>,
     recursive: bool,
     delay_ms: Option<u64>,
+    /// Glob patterns of the paths to ignore, matched against the names of the path and its parents
+    /// if they have no separator, or against the paths relative to the watched path.
+    #[serde(default)]
+    ignore: Vec<String>,
+    /// The kinds of events to send, all of them if empty.
+    #[serde(default)]
+    kinds: Vec<WatchEventKind>,
+    /// Whether to send the rename-from and rename-to events as a single renamed event.
+    #[serde(default)]
+    pair_renames: bool,
+}
+
+fn resolve_paths<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    paths: Vec<SafeFilePath>,
+    base_dir: Option<BaseDirectory>,
+) -> CommandResult<Vec<PathBuf>> {
+    let mut resolved_paths = Vec::with_capacity(paths.capacity());
+    for path in paths {
+        resolved_paths.push(resolve_path(
+            webview,
+            global_scope,
+            command_scope,
+            path,
+            base_dir,
+        )?);
+    }
+    Ok(resolved_paths)
 }
 
 #[tauri::command]
@@ -86,20 +311,17 @@ pub async fn watch<R: Runtime>(
     webview: Webview<R>,
     paths: Vec<SafeFilePath>,
     options: WatchOptions,
-    on_event: Channel<Event>,
+    on_event: Channel<WatchEvent>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
 ) -> CommandResult<ResourceId> {
-    let mut resolved_paths = Vec::with_capacity(paths.capacity());
-    for path in paths {
-        resolved_paths.push(resolve_path(
-            &webview,
-            &global_scope,
-            &command_scope,
-            path,
-            options.base_dir,
-        )?);
-    }
+    let resolved_paths = resolve_paths(
+        &webview,
+        &global_scope,
+        &command_scope,
+        paths,
+        options.base_dir,
+    )?;
 
     let recursive_mode = if options.recursive {
         RecursiveMode::Recursive
@@ -107,13 +329,29 @@ pub async fn watch<R: Runtime>(
         RecursiveMode::NonRecursive
     };
 
+    let ignore = options
+        .ignore
+        .iter()
+        .map(|pattern| glob::Pattern::new(pattern))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(crate::Error::from)?;
+    let paths = Arc::new(Mutex::new(resolved_paths.clone()));
+    let sender = EventSender {
+        on_event,
+        paths: paths.clone(),
+        ignore,
+        kinds: options.kinds,
+        pair_renames: options.pair_renames,
+        rename_from: None,
+    };
+
     let kind = if let Some(delay) = options.delay_ms {
         let (tx, rx) = channel();
         let mut debouncer = new_debouncer(Duration::from_millis(delay), None, tx)?;
         for path in &resolved_paths {
             debouncer.watch(path, recursive_mode)?;
         }
-        watch_debounced(on_event, rx);
+        watch_debounced(sender, rx);
         WatcherKind::Debouncer(debouncer)
     } else {
         let (tx, rx) = channel();
@@ -121,36 +359,69 @@ pub async fn watch<R: Runtime>(
         for path in &resolved_paths {
             watcher.watch(path, recursive_mode)?;
         }
-        watch_raw(on_event, rx);
+        watch_raw(sender, rx);
         WatcherKind::Watcher(watcher)
     };
 
     let rid = webview
         .resources_table()
-        .add(WatcherResource::new(kind, resolved_paths));
+        .add(WatcherResource::new(kind, paths, recursive_mode));
 
     Ok(rid)
 }
 
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWatchPathsOptions {
+    base_dir: Option<BaseDirectory>,
+}
+
+/// Replaces the paths of the watcher, only watching the added paths and unwatching the removed ones.
+#[tauri::command]
+pub async fn set_watch_paths<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    paths: Vec<SafeFilePath>,
+    options: Option<SetWatchPathsOptions>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+) -> CommandResult<()> {
+    let resolved_paths = resolve_paths(
+        &webview,
+        &global_scope,
+        &command_scope,
+        paths,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    let watcher = webview.resources_table().get::<WatcherResource>(rid)?;
+    WatcherResource::with_lock(&watcher, |watcher| {
+        let mut paths = watcher.paths.lock().unwrap();
+        for path in paths.iter().filter(|p| !resolved_paths.contains(p)) {
+            watcher.kind.unwatch(path).map_err(|e| {
+                format!("failed to unwatch path: {} with error: {e}", path.display())
+            })?;
+        }
+        for path in resolved_paths.iter().filter(|p| !paths.contains(p)) {
+            watcher
+                .kind
+                .watch(path, watcher.recursive_mode)
+                .map_err(|e| format!("failed to watch path: {} with error: {e}", path.display()))?;
+        }
+        *paths = resolved_paths.clone();
+
+        Ok(())
+    })
+}
+
 #[tauri::command]
 pub async fn unwatch<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
     let watcher = webview.resources_table().take::<WatcherResource>(rid)?;
     WatcherResource::with_lock(&watcher, |watcher| {
-        match &mut watcher.kind {
-            WatcherKind::Debouncer(ref mut debouncer) => {
-                for path in &watcher.paths {
-                    debouncer.unwatch(path).map_err(|e| {
-                        format!("failed to unwatch path: {} with error: {e}", path.display())
-                    })?;
-                }
-            }
-            WatcherKind::Watcher(ref mut w) => {
-                for path in &watcher.paths {
-                    w.unwatch(path).map_err(|e| {
-                        format!("failed to unwatch path: {} with error: {e}", path.display())
-                    })?;
-                }
-            }
+        for path in watcher.paths.lock().unwrap().iter() {
+            watcher.kind.unwatch(path).map_err(|e| {
+                format!("failed to unwatch path: {} with error: {e}", path.display())
+            })?;
         }
 
         Ok(())
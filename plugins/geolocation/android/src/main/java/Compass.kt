@@ -0,0 +1,79 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.geolocation
+
+import android.content.Context
+import android.hardware.GeomagneticField
+import android.hardware.Sensor
+import android.hardware.SensorEvent
+import android.hardware.SensorEventListener
+import android.hardware.SensorManager
+import android.location.Location
+import app.tauri.plugin.JSObject
+
+// Minimum change of the heading in degrees before reporting it again
+private const val HEADING_FILTER = 1.0
+
+// Reads the compass heading from the rotation vector sensor
+class Compass(context: Context) : SensorEventListener {
+    private val sensorManager = context.getSystemService(Context.SENSOR_SERVICE) as SensorManager
+    private val sensor: Sensor? = sensorManager.getDefaultSensor(Sensor.TYPE_ROTATION_VECTOR)
+    private var callback: ((JSObject) -> Unit)? = null
+    private var lastHeading: Double? = null
+
+    // The last position, to correct the magnetic heading with the declination
+    var location: Location? = null
+    var last: JSObject? = null
+        private set
+
+    fun isAvailable(): Boolean {
+        return sensor != null
+    }
+
+    fun start(callback: (JSObject) -> Unit) {
+        this.callback = callback
+        sensor?.let { sensorManager.registerListener(this, it, SensorManager.SENSOR_DELAY_UI) }
+    }
+
+    fun stop() {
+        sensorManager.unregisterListener(this)
+        callback = null
+        lastHeading = null
+        last = null
+    }
+
+    override fun onSensorChanged(event: SensorEvent) {
+        val rotation = FloatArray(9)
+        val orientation = FloatArray(3)
+        SensorManager.getRotationMatrixFromVector(rotation, event.values)
+        SensorManager.getOrientation(rotation, orientation)
+        val magneticHeading = (Math.toDegrees(orientation[0].toDouble()) + 360) % 360
+
+        val previous = lastHeading
+        if (previous != null) {
+            val delta = Math.abs(magneticHeading - previous)
+            if (minOf(delta, 360 - delta) < HEADING_FILTER) {
+                return
+            }
+        }
+        lastHeading = magneticHeading
+
+        val heading = JSObject()
+        heading.put("timestamp", System.currentTimeMillis())
+        heading.put("magneticHeading", magneticHeading)
+        location?.let {
+            val field = GeomagneticField(it.latitude.toFloat(), it.longitude.toFloat(), it.altitude.toFloat(), it.time)
+            heading.put("trueHeading", (magneticHeading + field.declination + 360) % 360)
+        }
+        // the estimated accuracy in radians, -1 if unavailable
+        if (event.values.size > 4 && event.values[4] >= 0) {
+            heading.put("accuracy", Math.toDegrees(event.values[4].toDouble()))
+        }
+        last = heading
+        callback?.invoke(heading)
+    }
+
+    override fun onAccuracyChanged(sensor: Sensor, accuracy: Int) {}
+}
@@ -0,0 +1,203 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.geolocation
+
+import android.annotation.SuppressLint
+import android.app.PendingIntent
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.os.Build
+import app.tauri.Logger
+import app.tauri.annotation.InvokeArg
+import app.tauri.plugin.JSArray
+import app.tauri.plugin.JSObject
+import com.google.android.gms.location.Geofence
+import com.google.android.gms.location.GeofenceStatusCodes
+import com.google.android.gms.location.GeofencingEvent
+import com.google.android.gms.location.GeofencingRequest
+import com.google.android.gms.location.LocationServices
+import org.json.JSONArray
+import org.json.JSONObject
+
+private const val PREFERENCES_NAME = "app.tauri.geolocation.geofences"
+private const val GEOFENCES_KEY = "geofences"
+private const val PENDING_EVENTS_KEY = "pendingEvents"
+
+@InvokeArg
+class GeofenceArgs {
+    lateinit var id: String
+    var latitude: Double = 0.0
+    var longitude: Double = 0.0
+    var radius: Double = 100.0
+    var notifyOnEnter: Boolean = true
+    var notifyOnExit: Boolean = true
+    var notifyOnDwell: Boolean = false
+    var loiteringDelay: Int = 30000
+
+    fun toJSON(): JSObject {
+        val json = JSObject()
+        json.put("id", id)
+        json.put("latitude", latitude)
+        json.put("longitude", longitude)
+        json.put("radius", radius)
+        json.put("notifyOnEnter", notifyOnEnter)
+        json.put("notifyOnExit", notifyOnExit)
+        json.put("notifyOnDwell", notifyOnDwell)
+        json.put("loiteringDelay", loiteringDelay)
+        return json
+    }
+}
+
+@InvokeArg
+class RemoveGeofenceArgs {
+    lateinit var id: String
+}
+
+// Registers the geofences with Google Play Services and persists them,
+// since they are cleared when the device reboots or the app data is cleared.
+class Geofencing(private val context: Context) {
+    private val client = LocationServices.getGeofencingClient(context)
+    private val preferences = context.getSharedPreferences(PREFERENCES_NAME, Context.MODE_PRIVATE)
+
+    fun stored(): JSONObject {
+        return JSONObject(preferences.getString(GEOFENCES_KEY, null) ?: "{}")
+    }
+
+    @SuppressLint("MissingPermission")
+    fun add(geofence: JSONObject, successCallback: () -> Unit, errorCallback: (error: String) -> Unit) {
+        client.addGeofences(request(listOf(geofence)), pendingIntent())
+            .addOnSuccessListener {
+                val geofences = stored()
+                geofences.put(geofence.getString("id"), geofence)
+                preferences.edit().putString(GEOFENCES_KEY, geofences.toString()).apply()
+                successCallback()
+            }
+            .addOnFailureListener { e -> errorCallback(e.message ?: "Failed to add the geofence.") }
+    }
+
+    fun remove(id: String) {
+        client.removeGeofences(listOf(id))
+        val geofences = stored()
+        geofences.remove(id)
+        preferences.edit().putString(GEOFENCES_KEY, geofences.toString()).apply()
+    }
+
+    // Registers the persisted geofences again
+    @SuppressLint("MissingPermission")
+    fun restore() {
+        val stored = stored()
+        val geofences = stored.keys().asSequence().map { stored.getJSONObject(it) }.toList()
+        if (geofences.isEmpty()) {
+            return
+        }
+        try {
+            client.addGeofences(request(geofences), pendingIntent())
+                .addOnFailureListener { e -> Logger.error("Failed to restore the geofences: ${e.message}") }
+        } catch (e: SecurityException) {
+            Logger.error("Failed to restore the geofences: ${e.message}")
+        }
+    }
+
+    private fun request(geofences: List<JSONObject>): GeofencingRequest {
+        val builder = GeofencingRequest.Builder()
+            .setInitialTrigger(GeofencingRequest.INITIAL_TRIGGER_ENTER)
+        for (geofence in geofences) {
+            var transitions = 0
+            if (geofence.getBoolean("notifyOnEnter")) {
+                transitions = transitions or Geofence.GEOFENCE_TRANSITION_ENTER
+            }
+            if (geofence.getBoolean("notifyOnExit")) {
+                transitions = transitions or Geofence.GEOFENCE_TRANSITION_EXIT
+            }
+            if (geofence.getBoolean("notifyOnDwell")) {
+                transitions = transitions or Geofence.GEOFENCE_TRANSITION_DWELL
+            }
+            builder.addGeofence(
+                Geofence.Builder()
+                    .setRequestId(geofence.getString("id"))
+                    .setCircularRegion(
+                        geofence.getDouble("latitude"),
+                        geofence.getDouble("longitude"),
+                        geofence.getDouble("radius").toFloat()
+                    )
+                    .setExpirationDuration(Geofence.NEVER_EXPIRE)
+                    .setLoiteringDelay(geofence.getInt("loiteringDelay"))
+                    .setTransitionTypes(transitions)
+                    .build()
+            )
+        }
+        return builder.build()
+    }
+
+    private fun pendingIntent(): PendingIntent {
+        val intent = Intent(context, GeofenceReceiver::class.java)
+        // the geofencing client fills the intent, it must be mutable
+        val flags = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+            PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_MUTABLE
+        } else {
+            PendingIntent.FLAG_UPDATE_CURRENT
+        }
+        return PendingIntent.getBroadcast(context, 0, intent, flags)
+    }
+
+    companion object {
+        // Set once the app listens to the events, they are queued until then
+        var listener: ((JSObject) -> Unit)? = null
+
+        fun dispatch(context: Context, event: JSObject) {
+            val listener = listener
+            if (listener != null) {
+                listener(event)
+                return
+            }
+
+            val preferences = context.getSharedPreferences(PREFERENCES_NAME, Context.MODE_PRIVATE)
+            val pending = JSONArray(preferences.getString(PENDING_EVENTS_KEY, null) ?: "[]")
+            pending.put(event)
+            preferences.edit().putString(PENDING_EVENTS_KEY, pending.toString()).apply()
+        }
+
+        fun takePending(context: Context): JSArray {
+            val preferences = context.getSharedPreferences(PREFERENCES_NAME, Context.MODE_PRIVATE)
+            val pending = JSArray(preferences.getString(PENDING_EVENTS_KEY, null) ?: "[]")
+            preferences.edit().remove(PENDING_EVENTS_KEY).apply()
+            return pending
+        }
+    }
+}
+
+class GeofenceReceiver : BroadcastReceiver() {
+    override fun onReceive(context: Context, intent: Intent) {
+        val event = GeofencingEvent.fromIntent(intent) ?: return
+        if (event.hasError()) {
+            Logger.error("Geofencing error: ${GeofenceStatusCodes.getStatusCodeString(event.errorCode)}")
+            return
+        }
+
+        val transition = when (event.geofenceTransition) {
+            Geofence.GEOFENCE_TRANSITION_ENTER -> "enter"
+            Geofence.GEOFENCE_TRANSITION_EXIT -> "exit"
+            Geofence.GEOFENCE_TRANSITION_DWELL -> "dwell"
+            else -> return
+        }
+        for (geofence in event.triggeringGeofences ?: emptyList()) {
+            val data = JSObject()
+            data.put("id", geofence.requestId)
+            data.put("transition", transition)
+            event.triggeringLocation?.let { data.put("position", convertLocation(it)) }
+            Geofencing.dispatch(context, data)
+        }
+    }
+}
+
+// The geofences are cleared when the device reboots
+class GeofenceBootReceiver : BroadcastReceiver() {
+    override fun onReceive(context: Context, intent: Intent) {
+        if (intent.action == Intent.ACTION_BOOT_COMPLETED) {
+            Geofencing(context).restore()
+        }
+    }
+}
@@ -8,9 +8,11 @@ import android.annotation.SuppressLint
 import android.content.Context
 import android.location.Location
 import android.location.LocationManager
+import android.os.Build
 import android.os.SystemClock
 import androidx.core.location.LocationManagerCompat
 import app.tauri.Logger
+import app.tauri.plugin.JSObject
 import com.google.android.gms.common.ConnectionResult
 import com.google.android.gms.common.GoogleApiAvailability
 import com.google.android.gms.location.FusedLocationProviderClient
@@ -145,4 +147,33 @@ public class Geolocation(private val context: Context) {
 
         return lastLoc
     }
+}
+
+fun convertLocation(location: Location, compass: JSObject? = null, activity: String? = null): JSObject {
+    val ret = JSObject()
+    val coords = JSObject()
+
+    coords.put("latitude", location.latitude)
+    coords.put("longitude", location.longitude)
+    coords.put("accuracy", location.accuracy)
+    coords.put("altitude", location.altitude)
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+        coords.put("altitudeAccuracy", location.verticalAccuracyMeters)
+    }
+    coords.put("speed", location.speed)
+    coords.put("heading", location.bearing)
+    if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+        if (location.hasSpeedAccuracy()) {
+            coords.put("speedAccuracy", location.speedAccuracyMetersPerSecond)
+        }
+        if (location.hasBearingAccuracy()) {
+            coords.put("headingAccuracy", location.bearingAccuracyDegrees)
+        }
+    }
+    ret.put("timestamp", location.time)
+    ret.put("coords", coords)
+    compass?.let { ret.put("compass", it) }
+    activity?.let { ret.put("activity", it) }
+
+    return ret
 }
\ No newline at end of file
@@ -18,6 +18,7 @@ import app.tauri.annotation.PermissionCallback
 import app.tauri.annotation.TauriPlugin
 import app.tauri.plugin.Channel
 import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
 import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
 
@@ -26,6 +27,7 @@ class PositionOptions {
   var enableHighAccuracy: Boolean = false
   var maximumAge: Long = 0
   var timeout: Long = 10000
+  var includeActivity: Boolean = false
 }
 
 @InvokeArg
@@ -34,6 +36,11 @@ class WatchArgs {
     lateinit var channel: Channel
 }
 
+@InvokeArg
+class WatchHeadingArgs {
+    lateinit var channel: Channel
+}
+
 @InvokeArg
 class ClearWatchArgs {
     var channelId: Long = 0
@@ -43,6 +50,8 @@ class ClearWatchArgs {
 
 private const val ALIAS_LOCATION: String = "location"
 private const val ALIAS_COARSE_LOCATION: String = "coarseLocation"
+private const val ALIAS_BACKGROUND_LOCATION: String = "backgroundLocation"
+private const val ALIAS_MOTION: String = "motion"
 
 @TauriPlugin(
     permissions = [
@@ -56,22 +65,47 @@ private const val ALIAS_COARSE_LOCATION: String = "coarseLocation"
             Manifest.permission.ACCESS_COARSE_LOCATION
         ],
             alias = ALIAS_COARSE_LOCATION
+        ),
+        Permission(strings = [
+            Manifest.permission.ACCESS_BACKGROUND_LOCATION
+        ],
+            alias = ALIAS_BACKGROUND_LOCATION
+        ),
+        Permission(strings = [
+            Manifest.permission.ACTIVITY_RECOGNITION
+        ],
+            alias = ALIAS_MOTION
         )
     ]
 )
 class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
     private lateinit var implementation: Geolocation
+    private lateinit var geofencing: Geofencing
+    private lateinit var compass: Compass
+    private lateinit var motionActivity: MotionActivityDetector
     private var watchers = hashMapOf<Long, Pair<Invoke, WatchArgs>>()
+    private var headingWatchers = hashMapOf<Long, Channel>()
 
     override fun load(webView: WebView) {
         super.load(webView)
         implementation = Geolocation(activity.applicationContext)
+        geofencing = Geofencing(activity.applicationContext)
+        geofencing.restore()
+        compass = Compass(activity.applicationContext)
+        motionActivity = MotionActivityDetector(activity.applicationContext)
+    }
+
+    override fun onDestroy() {
+        super.onDestroy()
+        Geofencing.listener = null
     }
 
     override fun onPause() {
         super.onPause()
         // Clear all location updates on pause to avoid possible background location calls
         implementation.clearLocationUpdates()
+        compass.stop()
+        motionActivity.stop()
     }
 
     override fun onResume() {
@@ -80,14 +114,20 @@ class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
         for ((watcher, args) in watchers.values) {
             startWatch(watcher, args)
         }
+        if (headingWatchers.isNotEmpty()) {
+            startCompass()
+        }
     }
 
     @Command
     override fun checkPermissions(invoke: Invoke) {
-        if (implementation.isLocationServicesEnabled()) {
-            super.checkPermissions(invoke)
-        } else {
+        if (!implementation.isLocationServicesEnabled()) {
             invoke.reject("Location services are disabled.")
+        } else if (Build.VERSION.SDK_INT < Build.VERSION_CODES.Q) {
+            // the background location is granted with the location and the motion is granted at install time before Android 10
+            positionPermissionCallback(invoke)
+        } else {
+            super.checkPermissions(invoke)
         }
     }
 
@@ -103,13 +143,16 @@ class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
     @Command
     fun getCurrentPosition(invoke: Invoke) {
         val args = invoke.parseArgs(PositionOptions::class.java)
+        if (args.includeActivity) {
+            motionActivity.start()
+        }
 
         val location = implementation.getLastLocation(args.maximumAge)
         if (location != null) {
-            invoke.resolve(convertLocation(location))
+            invoke.resolve(convertPosition(location, args))
         } else {
             implementation.sendLocation(args.enableHighAccuracy,
-                { loc -> invoke.resolve(convertLocation(loc)) },
+                { loc -> invoke.resolve(convertPosition(loc, args)) },
                 { error -> invoke.reject(error) })
         }
     }
@@ -119,6 +162,14 @@ class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
         val permissionsResultJSON = JSObject()
         permissionsResultJSON.put("location", getPermissionState(ALIAS_LOCATION))
         permissionsResultJSON.put("coarseLocation", getPermissionState(ALIAS_COARSE_LOCATION))
+        permissionsResultJSON.put(
+            "backgroundLocation",
+            getPermissionState(if (Build.VERSION.SDK_INT < Build.VERSION_CODES.Q) ALIAS_LOCATION else ALIAS_BACKGROUND_LOCATION)
+        )
+        permissionsResultJSON.put(
+            "motion",
+            if (Build.VERSION.SDK_INT < Build.VERSION_CODES.Q) PermissionState.GRANTED else getPermissionState(ALIAS_MOTION)
+        )
         invoke.resolve(permissionsResultJSON)
     }
 
@@ -129,10 +180,13 @@ class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
     }
 
     private fun startWatch(invoke: Invoke, args: WatchArgs) {
+        if (args.options.includeActivity) {
+            motionActivity.start()
+        }
         implementation.requestLocationUpdates(
             args.options.enableHighAccuracy,
             args.options.timeout,
-            { location -> args.channel.send(convertLocation(location)) },
+            { location -> args.channel.send(convertPosition(location, args.options)) },
             { error -> args.channel.sendObject(error) })
 
         watchers[args.channel.id] = Pair(invoke, args)
@@ -143,30 +197,90 @@ class GeolocationPlugin(private val activity: Activity): Plugin(activity) {
         val args = invoke.parseArgs(ClearWatchArgs::class.java)
 
         watchers.remove(args.channelId)
+        headingWatchers.remove(args.channelId)
 
         if (watchers.isEmpty()) {
             implementation.clearLocationUpdates()
         }
+        if (watchers.values.none { (_, watch) -> watch.options.includeActivity }) {
+            motionActivity.stop()
+        }
+        if (headingWatchers.isEmpty()) {
+            compass.stop()
+        }
 
         invoke.resolve()
     }
 
-    private fun convertLocation(location: Location): JSObject {
-        val ret = JSObject()
-        val coords = JSObject()
-
-        coords.put("latitude", location.latitude)
-        coords.put("longitude", location.longitude)
-        coords.put("accuracy", location.accuracy)
-        coords.put("altitude", location.altitude)
-        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
-            coords.put("altitudeAccuracy", location.verticalAccuracyMeters)
+    @Command
+    fun watchHeading(invoke: Invoke) {
+        val args = invoke.parseArgs(WatchHeadingArgs::class.java)
+        if (!compass.isAvailable()) {
+            invoke.reject("Compass unavailable.")
+            return
         }
-        coords.put("speed", location.speed)
-        coords.put("heading", location.bearing)
-        ret.put("timestamp", location.time)
-        ret.put("coords", coords)
 
-        return ret
+        if (headingWatchers.isEmpty()) {
+            compass.location = implementation.getLastLocation(Long.MAX_VALUE / 1000000L)
+            startCompass()
+        }
+        headingWatchers[args.channel.id] = args.channel
+
+        invoke.resolve()
+    }
+
+    private fun startCompass() {
+        compass.start { heading ->
+            for (channel in headingWatchers.values) {
+                channel.send(heading)
+            }
+        }
+    }
+
+    private fun convertPosition(location: Location, options: PositionOptions): JSObject {
+        compass.location = location
+        return convertLocation(
+            location,
+            compass.last,
+            if (options.includeActivity) MotionActivityReceiver.last else null
+        )
+    }
+
+    @Command
+    fun addGeofence(invoke: Invoke) {
+        val args = invoke.parseArgs(GeofenceArgs::class.java)
+        try {
+            geofencing.add(args.toJSON(), { invoke.resolve() }, { error -> invoke.reject(error) })
+        } catch (e: SecurityException) {
+            invoke.reject("Location permission not granted.")
+        }
+    }
+
+    @Command
+    fun removeGeofence(invoke: Invoke) {
+        val args = invoke.parseArgs(RemoveGeofenceArgs::class.java)
+        geofencing.remove(args.id)
+        invoke.resolve()
+    }
+
+    @Command
+    fun geofences(invoke: Invoke) {
+        val stored = geofencing.stored()
+        val geofences = JSArray()
+        for (id in stored.keys()) {
+            geofences.put(stored.getJSONObject(id))
+        }
+        val ret = JSObject()
+        ret.put("geofences", geofences)
+        invoke.resolve(ret)
+    }
+
+    @Command
+    fun takeGeofenceEvents(invoke: Invoke) {
+        // deliver the next events directly to the listeners
+        Geofencing.listener = { event -> trigger("geofence", event) }
+        val ret = JSObject()
+        ret.put("events", Geofencing.takePending(activity.applicationContext))
+        invoke.resolve(ret)
     }
 }
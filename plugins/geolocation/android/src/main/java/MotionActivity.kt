@@ -0,0 +1,81 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.geolocation
+
+import android.annotation.SuppressLint
+import android.app.PendingIntent
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.os.Build
+import app.tauri.Logger
+import com.google.android.gms.location.ActivityRecognition
+import com.google.android.gms.location.ActivityRecognitionResult
+import com.google.android.gms.location.DetectedActivity
+
+// Interval of the activity detection in milliseconds
+private const val DETECTION_INTERVAL = 10000L
+
+// Detects the motion activity with the activity recognition API of Google Play Services
+class MotionActivityDetector(private val context: Context) {
+    private val client = ActivityRecognition.getClient(context)
+    private var started = false
+
+    @SuppressLint("MissingPermission")
+    fun start() {
+        if (started) {
+            return
+        }
+        try {
+            client.requestActivityUpdates(DETECTION_INTERVAL, pendingIntent())
+                .addOnFailureListener { e -> Logger.error("Failed to detect the motion activity: ${e.message}") }
+            started = true
+        } catch (e: SecurityException) {
+            Logger.error("Motion permission not granted.")
+        }
+    }
+
+    @SuppressLint("MissingPermission")
+    fun stop() {
+        if (started) {
+            try {
+                client.removeActivityUpdates(pendingIntent())
+            } catch (_: SecurityException) {
+            }
+            started = false
+            MotionActivityReceiver.last = null
+        }
+    }
+
+    private fun pendingIntent(): PendingIntent {
+        val intent = Intent(context, MotionActivityReceiver::class.java)
+        // the activity recognition client fills the intent, it must be mutable
+        val flags = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+            PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_MUTABLE
+        } else {
+            PendingIntent.FLAG_UPDATE_CURRENT
+        }
+        return PendingIntent.getBroadcast(context, 1, intent, flags)
+    }
+}
+
+class MotionActivityReceiver : BroadcastReceiver() {
+    override fun onReceive(context: Context, intent: Intent) {
+        val result = ActivityRecognitionResult.extractResult(intent) ?: return
+        last = when (result.mostProbableActivity.type) {
+            DetectedActivity.STILL -> "stationary"
+            DetectedActivity.WALKING, DetectedActivity.ON_FOOT -> "walking"
+            DetectedActivity.RUNNING -> "running"
+            DetectedActivity.ON_BICYCLE -> "cycling"
+            DetectedActivity.IN_VEHICLE -> "automotive"
+            else -> "unknown"
+        }
+    }
+
+    companion object {
+        // The last detected activity
+        var last: String? = null
+    }
+}
@@ -5,9 +5,19 @@
 const COMMANDS: &[&str] = &[
     "get_current_position",
     "watch_position",
+    "watch_heading",
     "clear_watch",
     "check_permissions",
     "request_permissions",
+    "capabilities",
+    "add_geofence",
+    "remove_geofence",
+    "geofences",
+    "take_geofence_events",
+    "set_mock_route",
+    "set_mock_gpx",
+    "clear_mock_route",
+    "register_listener",
 ];
 
 fn main() {
@@ -6,6 +6,8 @@ import {
   Channel,
   invoke,
   PermissionState,
+  PluginListener,
+  addPluginListener,
   checkPermissions as checkPluginPermissions
 } from '@tauri-apps/api/core'
 
@@ -32,12 +34,55 @@ export type Coordinates = {
    */
   altitude: number | null
   speed: number | null
+  /**
+   * Accuracy level of the speed in meters per second, if available.
+   * Available on all iOS versions and on Android 8 and above.
+   */
+  speedAccuracy: number | null
   /**
    * The heading the user is facing, if available.
    */
   heading: number | null
+  /**
+   * Accuracy level of the heading in degrees, if available.
+   * Available on iOS 13.4 and above and on Android 8 and above.
+   */
+  headingAccuracy: number | null
 }
 
+/**
+ * The direction the device is pointing to, measured by the compass.
+ */
+export type Heading = {
+  /**
+   * Creation time for this heading.
+   */
+  timestamp: number
+  /**
+   * The heading relative to the magnetic north in degrees.
+   */
+  magneticHeading: number
+  /**
+   * The heading relative to the geographic north in degrees, if the position is known.
+   */
+  trueHeading: number | null
+  /**
+   * The maximum deviation of the magnetic heading in degrees, if available.
+   */
+  accuracy: number | null
+}
+
+/**
+ * The motion activity of the user.
+ */
+export type MotionActivity =
+  | 'stationary'
+  | 'walking'
+  | 'running'
+  | 'cycling'
+  | 'automotive'
+  | 'unknown'
+
 export type PermissionStatus = {
   /**
    * Permission state for the location alias.
@@ -57,9 +102,30 @@ export type PermissionStatus = {
    * On iOS it will have the same value as the `location` alias.
    */
   coarseLocation: PermissionState
+  /**
+   * Permission state for the backgroundLocation alias, required to receive geofence events while the app is in the background.
+   *
+   * On Android it requests/checks ACCESS_BACKGROUND_LOCATION, which must be added to the app manifest.
+   * It can only be granted after the `location` alias on Android 11+.
+   *
+   * On iOS it requests/checks the "Always" location authorization.
+   */
+  backgroundLocation: PermissionState
+  /**
+   * Permission state for the motion alias, required for the motion activity of the positions.
+   *
+   * On Android it requests/checks ACTIVITY_RECOGNITION, which must be added to the app manifest.
+   *
+   * On iOS it checks the motion and fitness authorization, which is requested on the first use.
+   */
+  motion: PermissionState
 }
 
-export type PermissionType = 'location' | 'coarseLocation'
+export type PermissionType =
+  | 'location'
+  | 'coarseLocation'
+  | 'backgroundLocation'
+  | 'motion'
 
 export type Position = {
   /**
@@ -70,6 +136,14 @@ export type Position = {
    * The GPD coordinates along with the accuracy of the data.
    */
   coords: Coordinates
+  /**
+   * The last compass heading, only available while a `watchHeading` watcher is active.
+   */
+  compass?: Heading | null
+  /**
+   * The last detected motion activity, only available with `includeActivity`.
+   */
+  activity?: MotionActivity | null
 }
 
 export type PositionOptions = {
@@ -82,14 +156,164 @@ export type PositionOptions = {
    * The maximum wait time in milliseconds for location updates.
    * On Android the timeout gets ignored for getCurrentPosition.
    * Ignored on iOS
+   * On desktop a timeout of 0 waits indefinitely.
    */
   timeout: number
   /**
    * The maximum age in milliseconds of a possible cached position that is acceptable to return.
    * Default: 0
-   * Ignored on iOS
+   * Ignored on iOS, macOS and Linux
    */
   maximumAge: number
+  /**
+   * Whether to detect the motion activity of the user, reported in `Position.activity`.
+   * Requires the `motion` permission.
+   * Default: false
+   */
+  includeActivity?: boolean
+}
+
+/**
+ * A circular region monitored by the system, even while the app is in the background.
+ *
+ * The geofences are persisted and registered again when the app starts and after a reboot.
+ */
+export type Geofence = {
+  /**
+   * The identifier of the geofence, a geofence with the same identifier is replaced.
+   */
+  id: string
+  /**
+   * Latitude of the center in decimal degrees.
+   */
+  latitude: number
+  /**
+   * Longitude of the center in decimal degrees.
+   */
+  longitude: number
+  /**
+   * The radius in meters.
+   * The system may not report regions smaller than 100 to 150 meters reliably.
+   */
+  radius: number
+  /**
+   * Whether to report entering the geofence. Default: true
+   */
+  notifyOnEnter?: boolean
+  /**
+   * Whether to report leaving the geofence. Default: true
+   */
+  notifyOnExit?: boolean
+  /**
+   * Whether to report staying in the geofence for the `loiteringDelay`. Default: false
+   *
+   * On iOS the dwell is only detected while the app is running.
+   */
+  notifyOnDwell?: boolean
+  /**
+   * The time in milliseconds to stay in the geofence before reporting a dwell.
+   * Default: 30000
+   */
+  loiteringDelay?: number
+}
+
+export type GeofenceTransition = 'enter' | 'exit' | 'dwell'
+
+export type GeofenceEvent = {
+  /**
+   * The identifier of the geofence.
+   */
+  id: string
+  transition: GeofenceTransition
+  /**
+   * The position that triggered the transition, if known.
+   */
+  position: Position | null
+}
+
+/**
+ * The service providing the positions.
+ */
+export type Backend = 'android' | 'ios' | 'windows' | 'coreLocation' | 'geoClue'
+
+/**
+ * The features supported by the positioning backend of the platform.
+ */
+export type Capabilities = {
+  backend: Backend
+  /**
+   * Whether the positioning service is installed and enabled.
+   */
+  available: boolean
+  /**
+   * Whether the positions may include the altitude.
+   */
+  altitude: boolean
+  /**
+   * Whether the positions may include the speed.
+   */
+  speed: boolean
+  /**
+   * Whether the positions may include the direction of travel.
+   */
+  heading: boolean
+  /**
+   * Whether `watchHeading` is supported.
+   */
+  compass: boolean
+  /**
+   * Whether geofences are supported.
+   */
+  geofencing: boolean
+  /**
+   * Whether `PositionOptions.includeActivity` is supported.
+   */
+  motionActivity: boolean
+}
+
+/**
+ * A point of a {@link MockRoute}.
+ */
+export type MockWaypoint = {
+  /**
+   * Latitude in decimal degrees.
+   */
+  latitude: number
+  /**
+   * Longitude in decimal degrees.
+   */
+  longitude: number
+  /**
+   * The altitude in meters, if known.
+   */
+  altitude?: number | null
+  /**
+   * The reported accuracy in meters. Default: 5
+   */
+  accuracy?: number
+  /**
+   * The time in milliseconds since the start of the route when the point is reached.
+   */
+  time: number
+}
+
+/**
+ * A scripted route replacing the positions of the device, to test location features without a positioning device.
+ * The positions are interpolated between the waypoints, with the speed and heading of the current segment.
+ */
+export type MockRoute = {
+  /**
+   * The waypoints, ordered by time.
+   */
+  waypoints: MockWaypoint[]
+  /**
+   * Whether to start over once the last waypoint is reached. Default: false
+   */
+  repeat?: boolean
+  /**
+   * The speed of the playback, 2 plays the route twice as fast. Default: 1
+   */
+  playbackRate?: number
 }
 
 export async function watchPosition(
@@ -111,6 +335,27 @@ export async function watchPosition(
   return channel.id
 }
 
+/**
+ * Watches the compass heading, for instance to orient a map in a navigation UI.
+ * Use `clearWatch` with the returned id to stop watching.
+ */
+export async function watchHeading(
+  cb: (heading: Heading | null, error?: string) => void
+): Promise<number> {
+  const channel = new Channel<Heading | string>()
+  channel.onmessage = (message) => {
+    if (typeof message === 'string') {
+      cb(null, message)
+    } else {
+      cb(message)
+    }
+  }
+  await invoke('plugin:geolocation|watch_heading', {
+    channel
+  })
+  return channel.id
+}
+
 export async function getCurrentPosition(
   options?: PositionOptions
 ): Promise<Position> {
@@ -136,3 +381,82 @@ export async function requestPermissions(
     permissions
   })
 }
+
+/**
+ * Returns the features supported by the positioning backend of the platform.
+ */
+export async function capabilities(): Promise<Capabilities> {
+  return await invoke('plugin:geolocation|capabilities')
+}
+
+/**
+ * Registers a geofence, replacing the one with the same identifier.
+ * Only available on Android and iOS.
+ */
+export async function addGeofence(geofence: Geofence): Promise<void> {
+  await invoke('plugin:geolocation|add_geofence', { geofence })
+}
+
+export async function removeGeofence(id: string): Promise<void> {
+  await invoke('plugin:geolocation|remove_geofence', { id })
+}
+
+/**
+ * Returns the registered geofences.
+ */
+export async function geofences(): Promise<Geofence[]> {
+  return await invoke('plugin:geolocation|geofences')
+}
+
+/**
+ * Listens to the geofence transitions.
+ *
+ * The events that happened before the app listened to them, such as the event that launched the app, are delivered first.
+ *
+ * ```javascript
+ * import { onGeofenceEvent } from "@tauri-apps/plugin-geolocation";
+ * await onGeofenceEvent(({ id, transition }) => console.log(id, transition));
+ * ```
+ */
+export async function onGeofenceEvent(
+  cb: (event: GeofenceEvent) => void
+): Promise<PluginListener> {
+  const listener = await addPluginListener('geolocation', 'geofence', cb)
+  const pending = await invoke<GeofenceEvent[]>(
+    'plugin:geolocation|take_geofence_events'
+  )
+  pending.forEach(cb)
+  return listener
+}
+
+/**
+ * Replaces the positions with a scripted route until `clearMockRoute` is called.
+ * The route is either a {@link MockRoute} or the content of a GPX document, whose times are relative to the first point.
+ *
+ * Only available in debug builds or with the `mock` feature of the plugin.
+ *
+ * ```javascript
+ * import { setMockRoute, watchPosition } from "@tauri-apps/plugin-geolocation";
+ * await setMockRoute({
+ *   waypoints: [
+ *     { latitude: 52.52, longitude: 13.405, time: 0 },
+ *     { latitude: 52.53, longitude: 13.41, time: 60000 }
+ *   ],
+ *   playbackRate: 10
+ * });
+ * ```
+ */
+export async function setMockRoute(route: MockRoute | string): Promise<void> {
+  if (typeof route === 'string') {
+    await invoke('plugin:geolocation|set_mock_gpx', { gpx: route })
+  } else {
+    await invoke('plugin:geolocation|set_mock_route', { route })
+  }
+}
+
+/**
+ * Restores the positions of the device, the watchers started with the mocked route stop.
+ */
+export async function clearMockRoute(): Promise<void> {
+  await invoke('plugin:geolocation|clear_mock_route')
+}
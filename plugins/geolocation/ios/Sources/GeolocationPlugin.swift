@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 import CoreLocation
+import CoreMotion
 import SwiftRs
 import Tauri
 import UIKit
@@ -10,6 +11,7 @@ import WebKit
 
 class GetPositionArgs: Decodable {
   var enableHighAccuracy: Bool?
+  var includeActivity: Bool?
 }
 
 class WatchPositionArgs: Decodable {
@@ -17,20 +19,65 @@ class WatchPositionArgs: Decodable {
   let channel: Channel
 }
 
+class WatchHeadingArgs: Decodable {
+  let channel: Channel
+}
+
 class ClearWatchArgs: Decodable {
   let channelId: UInt32
 }
 
+class RequestPermissionsArgs: Decodable {
+  let permissions: [String]?
+}
+
+struct GeofenceArgs: Codable {
+  let id: String
+  let latitude: Double
+  let longitude: Double
+  let radius: Double
+  let notifyOnEnter: Bool
+  let notifyOnExit: Bool
+  let notifyOnDwell: Bool
+  let loiteringDelay: Double
+}
+
+class RemoveGeofenceArgs: Decodable {
+  let id: String
+}
+
+struct GeofencesResponse: Encodable {
+  let geofences: [GeofenceArgs]
+}
+
+// the registered geofences, the regions themselves are persisted by Core Location
+private let geofencesKey = "app.tauri.geolocation.geofences"
+
 class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
   private let locationManager = CLLocationManager()
   private var isUpdatingLocation: Bool = false
   private var permissionRequests: [Invoke] = []
   private var positionRequests: [Invoke] = []
   private var watcherChannels: [Channel] = []
+  private var headingChannels: [Channel] = []
+  private let motionActivityManager = CMMotionActivityManager()
+  private var isUpdatingActivity: Bool = false
+  private var lastActivity: String?
+  private var geofences: [String: GeofenceArgs] = [:]
+  private var dwellTimers: [String: DispatchWorkItem] = [:]
+  // the geofence events are queued until the app listens to them
+  private var listeningGeofences = false
+  private var pendingGeofenceEvents: [JsonObject] = []
 
   override init() {
     super.init()
     locationManager.delegate = self
+
+    if let data = UserDefaults.standard.data(forKey: geofencesKey),
+      let geofences = try? JSONDecoder().decode([String: GeofenceArgs].self, from: data)
+    {
+      self.geofences = geofences
+    }
   }
 
   //
@@ -43,6 +90,9 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     self.positionRequests.append(invoke)
 
     DispatchQueue.main.async {
+      if args.includeActivity == true {
+        self.startActivityUpdates()
+      }
       if args.enableHighAccuracy == true {
         self.locationManager.desiredAccuracy = kCLLocationAccuracyBest
       } else {
@@ -64,6 +114,9 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     self.watcherChannels.append(args.channel)
 
     DispatchQueue.main.async {
+      if args.options.includeActivity == true {
+        self.startActivityUpdates()
+      }
       if args.options.enableHighAccuracy == true {
         self.locationManager.desiredAccuracy = kCLLocationAccuracyBest
       } else {
@@ -86,48 +139,124 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     let args = try invoke.parseArgs(ClearWatchArgs.self)
 
     self.watcherChannels = self.watcherChannels.filter { $0.id != args.channelId }
+    self.headingChannels = self.headingChannels.filter { $0.id != args.channelId }
 
     // TODO: capacitor plugin calls stopUpdating unconditionally
     if self.watcherChannels.isEmpty {
       self.stopUpdating()
     }
+    if self.headingChannels.isEmpty {
+      DispatchQueue.main.async {
+        self.locationManager.stopUpdatingHeading()
+      }
+    }
+
+    invoke.resolve()
+  }
+
+  @objc public func watchHeading(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(WatchHeadingArgs.self)
+
+    guard CLLocationManager.headingAvailable() else {
+      invoke.reject("Compass unavailable.")
+      return
+    }
+
+    self.headingChannels.append(args.channel)
+
+    DispatchQueue.main.async {
+      self.locationManager.startUpdatingHeading()
+    }
 
     invoke.resolve()
   }
 
   @objc override public func checkPermissions(_ invoke: Invoke) {
     var status: String = ""
+    var backgroundStatus: String = ""
 
     if CLLocationManager.locationServicesEnabled() {
       // TODO: Use the authorizationStatus instance property with locationManagerDidChangeAuthorization(_:) instead.
       switch CLLocationManager.authorizationStatus() {
       case .notDetermined:
         status = "prompt"
+        backgroundStatus = "prompt"
       case .restricted, .denied:
         status = "denied"
-      case .authorizedAlways, .authorizedWhenInUse:
+        backgroundStatus = "denied"
+      case .authorizedAlways:
         status = "granted"
+        backgroundStatus = "granted"
+      case .authorizedWhenInUse:
+        status = "granted"
+        // the "Always" authorization can still be requested once
+        backgroundStatus = "prompt"
       @unknown default:
         status = "prompt"
+        backgroundStatus = "prompt"
       }
     } else {
       invoke.reject("Location services are not enabled.")
       return
     }
 
-    let result = ["location": status, "coarseLocation": status]
+    let motionStatus: String
+    switch CMMotionActivityManager.authorizationStatus() {
+    case .notDetermined:
+      motionStatus = "prompt"
+    case .restricted, .denied:
+      motionStatus = "denied"
+    case .authorized:
+      motionStatus = "granted"
+    @unknown default:
+      motionStatus = "prompt"
+    }
+
+    let result = [
+      "location": status, "coarseLocation": status, "backgroundLocation": backgroundStatus,
+      "motion": CMMotionActivityManager.isActivityAvailable() ? motionStatus : "denied",
+    ]
 
     invoke.resolve(result)
   }
 
   @objc override public func requestPermissions(_ invoke: Invoke) {
+    let args = try? invoke.parseArgs(RequestPermissionsArgs.self)
+    let permissions = args?.permissions
+    let background = permissions?.contains("backgroundLocation") ?? false
+
+    if permissions?.contains("motion") == true && CMMotionActivityManager.isActivityAvailable()
+      && CMMotionActivityManager.authorizationStatus() == .notDetermined
+    {
+      // there is no explicit request, the authorization is prompted on the first query
+      let now = Date()
+      motionActivityManager.queryActivityStarting(from: now, to: now, to: .main) { _, _ in
+        if permissions?.contains(where: { $0 != "motion" }) == true {
+          self.requestLocationPermissions(invoke, background: background)
+        } else {
+          self.checkPermissions(invoke)
+        }
+      }
+    } else if permissions?.allSatisfy({ $0 == "motion" }) == true {
+      checkPermissions(invoke)
+    } else {
+      requestLocationPermissions(invoke, background: background)
+    }
+  }
+
+  private func requestLocationPermissions(_ invoke: Invoke, background: Bool) {
     if CLLocationManager.locationServicesEnabled() {
       // TODO: Use the authorizationStatus instance property with locationManagerDidChangeAuthorization(_:) instead.
-      if CLLocationManager.authorizationStatus() == .notDetermined {
+      let status = CLLocationManager.authorizationStatus()
+      if status == .notDetermined || (background && status == .authorizedWhenInUse) {
         self.permissionRequests.append(invoke)
 
         DispatchQueue.main.async {
-          self.locationManager.requestWhenInUseAuthorization()
+          if background {
+            self.locationManager.requestAlwaysAuthorization()
+          } else {
+            self.locationManager.requestWhenInUseAuthorization()
+          }
         }
       } else {
         checkPermissions(invoke)
@@ -137,6 +266,59 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     }
   }
 
+  @objc public func addGeofence(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(GeofenceArgs.self)
+
+    guard CLLocationManager.isMonitoringAvailable(for: CLCircularRegion.self) else {
+      invoke.reject("Geofencing is not available on this device.")
+      return
+    }
+
+    DispatchQueue.main.async {
+      let region = CLCircularRegion(
+        center: CLLocationCoordinate2D(latitude: args.latitude, longitude: args.longitude),
+        radius: min(args.radius, self.locationManager.maximumRegionMonitoringDistance),
+        identifier: args.id
+      )
+      // the dwell is detected from the entry and exit
+      region.notifyOnEntry = args.notifyOnEnter || args.notifyOnDwell
+      region.notifyOnExit = args.notifyOnExit || args.notifyOnDwell
+      self.locationManager.startMonitoring(for: region)
+
+      self.geofences[args.id] = args
+      self.saveGeofences()
+      invoke.resolve()
+    }
+  }
+
+  @objc public func removeGeofence(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(RemoveGeofenceArgs.self)
+
+    DispatchQueue.main.async {
+      for region in self.locationManager.monitoredRegions where region.identifier == args.id {
+        self.locationManager.stopMonitoring(for: region)
+      }
+      self.dwellTimers.removeValue(forKey: args.id)?.cancel()
+
+      self.geofences.removeValue(forKey: args.id)
+      self.saveGeofences()
+      invoke.resolve()
+    }
+  }
+
+  @objc public func geofences(_ invoke: Invoke) throws {
+    invoke.resolve(GeofencesResponse(geofences: Array(self.geofences.values)))
+  }
+
+  @objc public func takeGeofenceEvents(_ invoke: Invoke) throws {
+    DispatchQueue.main.async {
+      let events = self.pendingGeofenceEvents
+      self.pendingGeofenceEvents.removeAll()
+      self.listeningGeofences = true
+      invoke.resolve(["events": events])
+    }
+  }
+
   //
   // Delegate methods
   //
@@ -216,10 +398,107 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     }
   }
 
+  public func locationManager(_ manager: CLLocationManager, didUpdateHeading newHeading: CLHeading) {
+    let heading = convertHeading(newHeading)
+    for channel in self.headingChannels {
+      do {
+        try channel.send(heading)
+      } catch {
+        Logger.error(error)
+      }
+    }
+  }
+
+  public func locationManager(_ manager: CLLocationManager, didEnterRegion region: CLRegion) {
+    guard let geofence = self.geofences[region.identifier] else { return }
+
+    if geofence.notifyOnEnter {
+      dispatchGeofenceEvent(geofence.id, transition: "enter", location: manager.location)
+    }
+    if geofence.notifyOnDwell {
+      let timer = DispatchWorkItem { [weak self] in
+        self?.dwellTimers.removeValue(forKey: geofence.id)
+        self?.dispatchGeofenceEvent(geofence.id, transition: "dwell", location: manager.location)
+      }
+      self.dwellTimers[geofence.id]?.cancel()
+      self.dwellTimers[geofence.id] = timer
+      DispatchQueue.main.asyncAfter(deadline: .now() + geofence.loiteringDelay / 1000, execute: timer)
+    }
+  }
+
+  public func locationManager(_ manager: CLLocationManager, didExitRegion region: CLRegion) {
+    guard let geofence = self.geofences[region.identifier] else { return }
+
+    self.dwellTimers.removeValue(forKey: geofence.id)?.cancel()
+    if geofence.notifyOnExit {
+      dispatchGeofenceEvent(geofence.id, transition: "exit", location: manager.location)
+    }
+  }
+
+  public func locationManager(
+    _ manager: CLLocationManager, monitoringDidFailFor region: CLRegion?, withError error: Error
+  ) {
+    Logger.error("Failed to monitor the geofence \(region?.identifier ?? ""): \(error)")
+  }
+
   //
   // Internal/Helper methods
   //
 
+  private func startActivityUpdates() {
+    guard !self.isUpdatingActivity && CMMotionActivityManager.isActivityAvailable() else { return }
+    self.isUpdatingActivity = true
+    motionActivityManager.startActivityUpdates(to: .main) { [weak self] activity in
+      guard let activity = activity else { return }
+      if activity.stationary {
+        self?.lastActivity = "stationary"
+      } else if activity.automotive {
+        self?.lastActivity = "automotive"
+      } else if activity.cycling {
+        self?.lastActivity = "cycling"
+      } else if activity.running {
+        self?.lastActivity = "running"
+      } else if activity.walking {
+        self?.lastActivity = "walking"
+      } else {
+        self?.lastActivity = "unknown"
+      }
+    }
+  }
+
+  private func convertHeading(_ heading: CLHeading) -> JsonObject {
+    var ret: JsonObject = [:]
+    ret["timestamp"] = Int((heading.timestamp.timeIntervalSince1970 * 1000))
+    ret["magneticHeading"] = heading.magneticHeading
+    // negative values are invalid
+    if heading.trueHeading >= 0 {
+      ret["trueHeading"] = heading.trueHeading
+    }
+    if heading.headingAccuracy >= 0 {
+      ret["accuracy"] = heading.headingAccuracy
+    }
+    return ret
+  }
+
+  private func saveGeofences() {
+    if let data = try? JSONEncoder().encode(self.geofences) {
+      UserDefaults.standard.set(data, forKey: geofencesKey)
+    }
+  }
+
+  private func dispatchGeofenceEvent(_ id: String, transition: String, location: CLLocation?) {
+    var event: JsonObject = ["id": id, "transition": transition]
+    if let location = location {
+      event["position"] = convertLocation(location)
+    }
+
+    if self.listeningGeofences {
+      try? trigger("geofence", data: event)
+    } else {
+      self.pendingGeofenceEvents.append(event)
+    }
+  }
+
   // TODO: Why is this pub in capacitor
   private func stopUpdating() {
     self.locationManager.stopUpdatingLocation()
@@ -237,8 +516,21 @@ class GeolocationPlugin: Plugin, CLLocationManagerDelegate {
     coords["altitudeAccuracy"] = location.verticalAccuracy
     coords["speed"] = location.speed
     coords["heading"] = location.course
+    // negative values are invalid
+    if location.speedAccuracy >= 0 {
+      coords["speedAccuracy"] = location.speedAccuracy
+    }
+    if #available(iOS 13.4, *), location.courseAccuracy >= 0 {
+      coords["headingAccuracy"] = location.courseAccuracy
+    }
     ret["timestamp"] = Int((location.timestamp.timeIntervalSince1970 * 1000))
     ret["coords"] = coords
+    if !self.headingChannels.isEmpty, let heading = self.locationManager.heading {
+      ret["compass"] = convertHeading(heading)
+    }
+    if let activity = self.lastActivity {
+      ret["activity"] = activity
+    }
 
     return ret
   }
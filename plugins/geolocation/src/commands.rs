@@ -4,7 +4,10 @@
 
 use tauri::{command, ipc::Channel, AppHandle, Runtime};
 
-use crate::{GeolocationExt, PermissionStatus, PermissionType, Position, PositionOptions, Result};
+use crate::{
+    Capabilities, Geofence, GeofenceEvent, GeolocationExt, MockRoute, PermissionStatus,
+    PermissionType, Position, PositionOptions, Result,
+};
 
 #[command]
 #[specta::specta]
@@ -25,6 +28,12 @@ pub(crate) async fn watch_position<R: Runtime>(
     app.geolocation().watch_position_inner(options, channel)
 }
 
+#[command]
+#[specta::specta]
+pub(crate) async fn watch_heading<R: Runtime>(app: AppHandle<R>, channel: Channel) -> Result<()> {
+    app.geolocation().watch_heading_inner(channel)
+}
+
 #[command]
 #[specta::specta]
 pub(crate) async fn clear_watch<R: Runtime>(app: AppHandle<R>, channel_id: u32) -> Result<()> {
@@ -45,3 +54,53 @@ pub(crate) async fn request_permissions<R: Runtime>(
 ) -> Result<PermissionStatus> {
     app.geolocation().request_permissions(permissions)
 }
+
+#[command]
+#[specta::specta]
+pub(crate) async fn capabilities<R: Runtime>(app: AppHandle<R>) -> Result<Capabilities> {
+    app.geolocation().capabilities()
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn add_geofence<R: Runtime>(app: AppHandle<R>, geofence: Geofence) -> Result<()> {
+    app.geolocation().add_geofence(geofence)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn remove_geofence<R: Runtime>(app: AppHandle<R>, id: String) -> Result<()> {
+    app.geolocation().remove_geofence(id)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn geofences<R: Runtime>(app: AppHandle<R>) -> Result<Vec<Geofence>> {
+    app.geolocation().geofences()
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn take_geofence_events<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<GeofenceEvent>> {
+    app.geolocation().take_geofence_events()
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn set_mock_route<R: Runtime>(app: AppHandle<R>, route: MockRoute) -> Result<()> {
+    app.geolocation().set_mock_route(route)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn set_mock_gpx<R: Runtime>(app: AppHandle<R>, gpx: String) -> Result<()> {
+    app.geolocation().set_mock_route(MockRoute::from_gpx(&gpx)?)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn clear_mock_route<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.geolocation().clear_mock_route()
+}
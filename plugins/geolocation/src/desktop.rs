@@ -2,33 +2,64 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::de::DeserializeOwned;
 use tauri::{
     ipc::{Channel, InvokeResponseBody},
-    plugin::PluginApi,
+    plugin::{PermissionState, PluginApi},
     AppHandle, Runtime,
 };
 
-use crate::models::*;
+use crate::{mock::Mock, models::*};
+
+#[cfg(target_os = "windows")]
+#[path = "platform_impl/windows.rs"]
+mod platform_impl;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+#[path = "platform_impl/linux.rs"]
+mod platform_impl;
+#[cfg(target_os = "macos")]
+#[path = "platform_impl/macos.rs"]
+mod platform_impl;
+
+/// Receives the updates of a watcher, called from the thread of the backend.
+pub(crate) type Callback = Box<dyn Fn(WatchEvent) + Send + Sync>;
 
 pub fn init<R: Runtime, C: DeserializeOwned>(
     app: &AppHandle<R>,
     _api: PluginApi<R, C>,
 ) -> crate::Result<Geolocation<R>> {
-    Ok(Geolocation(app.clone()))
+    Ok(Geolocation {
+        app: app.clone(),
+        watchers: Default::default(),
+        mock: Default::default(),
+    })
 }
 
 /// Access to the geolocation APIs.
-pub struct Geolocation<R: Runtime>(AppHandle<R>);
+pub struct Geolocation<R: Runtime> {
+    app: AppHandle<R>,
+    /// The active watchers by channel id, dropping a watcher stops its updates.
+    watchers: Mutex<HashMap<u32, platform_impl::Watcher>>,
+    mock: Mock,
+}
 
 impl<R: Runtime> Geolocation<R> {
     pub fn get_current_position(
         &self,
-        _options: Option<PositionOptions>,
+        options: Option<PositionOptions>,
     ) -> crate::Result<Position> {
-        Ok(Position::default())
+        platform_impl::current_position(&self.app, &options.unwrap_or_default())
     }
 
+    /// Register a position watcher. This method returns an id to use in `clear_watch`.
     pub fn watch_position<F: Fn(WatchEvent) + Send + Sync + 'static>(
         &self,
         options: PositionOptions,
@@ -58,38 +89,146 @@ impl<R: Runtime> Geolocation<R> {
 
     pub(crate) fn watch_position_inner(
         &self,
-        _options: PositionOptions,
-        _callback_channel: Channel,
+        options: PositionOptions,
+        channel: Channel,
     ) -> crate::Result<()> {
+        if self.mock.watch(&channel) {
+            return Ok(());
+        }
+        let id = channel.id();
+        let watcher = platform_impl::watch(
+            &self.app,
+            &options,
+            Box::new(move |event| {
+                let _ = channel.send(event);
+            }),
+        )?;
+        self.watchers.lock().unwrap().insert(id, watcher);
         Ok(())
     }
 
-    pub fn clear_watch(&self, _channel_id: u32) -> crate::Result<()> {
+    /// Register a compass heading watcher. This method returns an id to use in `clear_watch`.
+    ///
+    /// Unsupported on desktop.
+    pub fn watch_heading<F: Fn(HeadingEvent) + Send + Sync + 'static>(
+        &self,
+        callback: F,
+    ) -> crate::Result<u32> {
+        let channel = Channel::new(move |event| {
+            let payload = match event {
+                InvokeResponseBody::Json(payload) => serde_json::from_str::<HeadingEvent>(&payload)
+                    .unwrap_or_else(|error| {
+                        HeadingEvent::Error(format!(
+                            "Couldn't deserialize heading event payload: `{error}`"
+                        ))
+                    }),
+                _ => HeadingEvent::Error("Unexpected heading event payload.".to_string()),
+            };
+
+            callback(payload);
+
+            Ok(())
+        });
+        let id = channel.id();
+
+        self.watch_heading_inner(channel)?;
+
+        Ok(id)
+    }
+
+    pub(crate) fn watch_heading_inner(&self, _channel: Channel) -> crate::Result<()> {
+        Err(crate::Error::Unsupported("compass heading".into()))
+    }
+
+    /// Clears a position watcher.
+    pub fn clear_watch(&self, channel_id: u32) -> crate::Result<()> {
+        if self.mock.clear_watch(channel_id) {
+            return Ok(());
+        }
+        self.watchers.lock().unwrap().remove(&channel_id);
         Ok(())
     }
 
+    /// The `location`, `coarseLocation` and `backgroundLocation` aliases share the state of the backend,
+    /// motion activities are not available on desktop.
     pub fn check_permissions(&self) -> crate::Result<PermissionStatus> {
-        Ok(PermissionStatus::default())
+        platform_impl::check_permissions(&self.app).map(permission_status)
     }
 
     pub fn request_permissions(
         &self,
         _permissions: Option<Vec<PermissionType>>,
     ) -> crate::Result<PermissionStatus> {
-        Ok(PermissionStatus::default())
+        platform_impl::request_permissions(&self.app).map(permission_status)
+    }
+
+    /// Replaces the positions with a scripted route until [`Self::clear_mock_route`] is called.
+    ///
+    /// Only available in debug builds or with the `mock` feature.
+    pub fn set_mock_route(&self, route: MockRoute) -> crate::Result<()> {
+        self.mock.set(route)
+    }
+
+    /// Restores the positions of the device, the watchers started with the mocked route stop.
+    pub fn clear_mock_route(&self) -> crate::Result<()> {
+        self.mock.clear();
+        Ok(())
+    }
+
+    /// Returns the features supported by the positioning backend.
+    pub fn capabilities(&self) -> crate::Result<Capabilities> {
+        Ok(platform_impl::capabilities(&self.app))
+    }
+
+    /// Unsupported on desktop.
+    pub fn add_geofence(&self, _geofence: Geofence) -> crate::Result<()> {
+        Err(crate::Error::Unsupported("geofencing".into()))
+    }
+
+    pub fn remove_geofence(&self, _id: String) -> crate::Result<()> {
+        Ok(())
+    }
+
+    pub fn geofences(&self) -> crate::Result<Vec<Geofence>> {
+        Ok(Vec::new())
+    }
+
+    pub fn take_geofence_events(&self) -> crate::Result<Vec<GeofenceEvent>> {
+        Ok(Vec::new())
     }
 }
 
-#[derive(Serialize)]
-#[allow(unused)] // TODO:
-struct WatchPayload {
-    options: PositionOptions,
-    channel: Channel,
+fn permission_status(state: PermissionState) -> PermissionStatus {
+    PermissionStatus {
+        location: state,
+        coarse_location: state,
+        background_location: state,
+        motion: PermissionState::Denied,
+    }
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-#[allow(unused)] // TODO:
-struct ClearWatchPayload {
-    channel_id: u32,
+/// Starts a watcher and waits for its first update, for the backends without a one-shot request.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn first_position(
+    options: &PositionOptions,
+    watch: impl FnOnce(Callback) -> crate::Result<platform_impl::Watcher>,
+) -> crate::Result<Position> {
+    use std::{sync::mpsc::channel, time::Duration};
+
+    let (tx, rx) = channel();
+    let watcher = watch(Box::new(move |event| {
+        let _ = tx.send(event);
+    }))?;
+    let event = if options.timeout > 0 {
+        rx.recv_timeout(Duration::from_millis(options.timeout.into()))
+            .map_err(|_| crate::Error::Timeout)?
+    } else {
+        rx.recv().map_err(|_| crate::Error::Timeout)?
+    };
+    drop(watcher);
+
+    match event {
+        WatchEvent::Position(position) => Ok(position),
+        WatchEvent::Error(error) => Err(crate::Error::Unavailable(error)),
+    }
 }
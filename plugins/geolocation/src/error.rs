@@ -18,6 +18,49 @@ pub enum Error {
         #[from]
         tauri::plugin::mobile::PluginInvokeError,
     ),
+    #[error("mocking the positions requires a debug build or the `mock` feature")]
+    MockDisabled,
+    #[error("invalid mock route: {0}")]
+    InvalidMockRoute(String),
+    #[cfg(desktop)]
+    #[error("location unavailable: {0}")]
+    Unavailable(String),
+    #[cfg(desktop)]
+    #[error("location permission denied")]
+    PermissionDenied,
+    #[cfg(desktop)]
+    #[error("timed out waiting for a position")]
+    Timeout,
+    #[cfg(desktop)]
+    #[error("{0} is not supported on this platform")]
+    Unsupported(String),
+    #[cfg(desktop)]
+    #[error(transparent)]
+    Tauri(
+        #[serde(skip)]
+        #[from]
+        tauri::Error,
+    ),
+    #[cfg(windows)]
+    #[error(transparent)]
+    Windows(
+        #[serde(skip)]
+        #[from]
+        windows::core::Error,
+    ),
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    #[error(transparent)]
+    Zbus(
+        #[serde(skip)]
+        #[from]
+        zbus::Error,
+    ),
 }
 
 impl Serialize for Error {
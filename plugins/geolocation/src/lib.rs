@@ -18,6 +18,7 @@ mod mobile;
 
 mod commands;
 mod error;
+mod mock;
 mod models;
 
 pub use error::{Error, Result};
@@ -33,9 +34,18 @@ use mobile::Geolocation;
             .commands(collect_commands![
                 commands::get_current_position,
                 commands::watch_position,
+                commands::watch_heading,
                 commands::clear_watch,
                 commands::check_permissions,
-                commands::request_permissions
+                commands::request_permissions,
+                commands::capabilities,
+                commands::add_geofence,
+                commands::remove_geofence,
+                commands::geofences,
+                commands::take_geofence_events,
+                commands::set_mock_route,
+                commands::set_mock_gpx,
+                commands::clear_mock_route
             ])
             .header("// @ts-nocheck")
             .config(
@@ -65,9 +75,18 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
         .invoke_handler(tauri::generate_handler![
             commands::get_current_position,
             commands::watch_position,
+            commands::watch_heading,
             commands::clear_watch,
             commands::check_permissions,
-            commands::request_permissions
+            commands::request_permissions,
+            commands::capabilities,
+            commands::add_geofence,
+            commands::remove_geofence,
+            commands::geofences,
+            commands::take_geofence_events,
+            commands::set_mock_route,
+            commands::set_mock_gpx,
+            commands::clear_mock_route
         ])
         .setup(|app, api| {
             #[cfg(mobile)]
@@ -2,14 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tauri::{
     ipc::{Channel, InvokeResponseBody},
     plugin::{PluginApi, PluginHandle},
     AppHandle, Runtime,
 };
 
-use crate::models::*;
+use crate::{mock::Mock, models::*};
 
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "app.tauri.geolocation";
@@ -26,19 +26,28 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
     let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "GeolocationPlugin")?;
     #[cfg(target_os = "ios")]
     let handle = api.register_ios_plugin(init_plugin_geolocation)?;
-    Ok(Geolocation(handle))
+    Ok(Geolocation {
+        handle,
+        mock: Default::default(),
+    })
 }
 
 /// Access to the geolocation APIs.
-pub struct Geolocation<R: Runtime>(PluginHandle<R>);
+pub struct Geolocation<R: Runtime> {
+    handle: PluginHandle<R>,
+    mock: Mock,
+}
 
 impl<R: Runtime> Geolocation<R> {
     pub fn get_current_position(
         &self,
         options: Option<PositionOptions>,
     ) -> crate::Result<Position> {
+        if let Some(position) = self.mock.current_position() {
+            return Ok(position);
+        }
         // TODO: We may have to send over None if that's better on Android
-        self.0
+        self.handle
             .run_mobile_plugin("getCurrentPosition", options.unwrap_or_default())
             .map_err(Into::into)
     }
@@ -76,19 +85,59 @@ impl<R: Runtime> Geolocation<R> {
         options: PositionOptions,
         channel: Channel,
     ) -> crate::Result<()> {
-        self.0
+        if self.mock.watch(&channel) {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin("watchPosition", WatchPayload { options, channel })
             .map_err(Into::into)
     }
 
+    /// Register a compass heading watcher. This method returns an id to use in `clear_watch`.
+    pub fn watch_heading<F: Fn(HeadingEvent) + Send + Sync + 'static>(
+        &self,
+        callback: F,
+    ) -> crate::Result<u32> {
+        let channel = Channel::new(move |event| {
+            let payload = match event {
+                InvokeResponseBody::Json(payload) => serde_json::from_str::<HeadingEvent>(&payload)
+                    .unwrap_or_else(|error| {
+                        HeadingEvent::Error(format!(
+                            "Couldn't deserialize heading event payload: `{error}`"
+                        ))
+                    }),
+                _ => HeadingEvent::Error("Unexpected heading event payload.".to_string()),
+            };
+
+            callback(payload);
+
+            Ok(())
+        });
+        let id = channel.id();
+
+        self.watch_heading_inner(channel)?;
+
+        Ok(id)
+    }
+
+    pub(crate) fn watch_heading_inner(&self, channel: Channel) -> crate::Result<()> {
+        self.handle
+            .run_mobile_plugin("watchHeading", WatchHeadingPayload { channel })
+            .map_err(Into::into)
+    }
+
+    /// Clears a position or heading watcher.
     pub fn clear_watch(&self, channel_id: u32) -> crate::Result<()> {
-        self.0
+        if self.mock.clear_watch(channel_id) {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin("clearWatch", ClearWatchPayload { channel_id })
             .map_err(Into::into)
     }
 
     pub fn check_permissions(&self) -> crate::Result<PermissionStatus> {
-        self.0
+        self.handle
             .run_mobile_plugin("checkPermissions", ())
             .map_err(Into::into)
     }
@@ -97,13 +146,97 @@ impl<R: Runtime> Geolocation<R> {
         &self,
         permissions: Option<Vec<PermissionType>>,
     ) -> crate::Result<PermissionStatus> {
-        self.0
+        self.handle
             .run_mobile_plugin(
                 "requestPermissions",
                 serde_json::json!({ "permissions": permissions }),
             )
             .map_err(Into::into)
     }
+
+    /// Replaces the positions with a scripted route until [`Self::clear_mock_route`] is called.
+    ///
+    /// Only available in debug builds or with the `mock` feature.
+    pub fn set_mock_route(&self, route: MockRoute) -> crate::Result<()> {
+        self.mock.set(route)
+    }
+
+    /// Restores the positions of the device, the watchers started with the mocked route stop.
+    pub fn clear_mock_route(&self) -> crate::Result<()> {
+        self.mock.clear();
+        Ok(())
+    }
+
+    /// Returns the features supported by the positioning backend.
+    pub fn capabilities(&self) -> crate::Result<Capabilities> {
+        Ok(Capabilities {
+            #[cfg(target_os = "android")]
+            backend: Backend::Android,
+            #[cfg(target_os = "ios")]
+            backend: Backend::Ios,
+            available: true,
+            altitude: true,
+            speed: true,
+            heading: true,
+            compass: true,
+            geofencing: true,
+            motion_activity: true,
+        })
+    }
+
+    /// Registers a geofence, replacing the one with the same identifier.
+    ///
+    /// The transitions are reported to the `geofence` plugin listener, even while the app is in the background
+    /// if the `backgroundLocation` permission is granted.
+    pub fn add_geofence(&self, geofence: Geofence) -> crate::Result<()> {
+        self.handle
+            .run_mobile_plugin("addGeofence", geofence)
+            .map_err(Into::into)
+    }
+
+    pub fn remove_geofence(&self, id: String) -> crate::Result<()> {
+        self.handle
+            .run_mobile_plugin("removeGeofence", RemoveGeofencePayload { id })
+            .map_err(Into::into)
+    }
+
+    /// Returns the registered geofences.
+    pub fn geofences(&self) -> crate::Result<Vec<Geofence>> {
+        self.handle
+            .run_mobile_plugin::<GeofencesResponse>("geofences", ())
+            .map(|response| response.geofences)
+            .map_err(Into::into)
+    }
+
+    /// Returns the geofence events that happened before the app listened to them, such as the event that launched the app.
+    ///
+    /// The following events are sent to the `geofence` plugin listener.
+    pub fn take_geofence_events(&self) -> crate::Result<Vec<GeofenceEvent>> {
+        self.handle
+            .run_mobile_plugin::<GeofenceEventsResponse>("takeGeofenceEvents", ())
+            .map(|response| response.events)
+            .map_err(Into::into)
+    }
+}
+
+#[derive(Serialize)]
+struct RemoveGeofencePayload {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct GeofencesResponse {
+    geofences: Vec<Geofence>,
+}
+
+#[derive(Deserialize)]
+struct GeofenceEventsResponse {
+    events: Vec<GeofenceEvent>,
+}
+
+#[derive(Serialize)]
+struct WatchHeadingPayload {
+    channel: Channel,
 }
 
 #[derive(Serialize)]
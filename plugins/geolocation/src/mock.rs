@@ -0,0 +1,297 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Simulated positions from a scripted route, for testing in CI and on devices without a positioning device.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use tauri::ipc::Channel;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::models::*;
+
+/// Routes can only be mocked in debug builds or with the `mock` feature.
+const ENABLED: bool = cfg!(any(debug_assertions, feature = "mock"));
+
+/// How often the watchers receive a position.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The time between the GPX points without a timestamp, in milliseconds.
+const GPX_STEP: u32 = 1000;
+
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+impl MockRoute {
+    /// Reads the track points of a GPX document, or its route points or waypoints if it has no track.
+    ///
+    /// The times of the points are relative to the first one, points without a time are one second apart.
+    pub fn from_gpx(gpx: &str) -> crate::Result<Self> {
+        let invalid = crate::Error::InvalidMockRoute;
+        let document = roxmltree::Document::parse(gpx).map_err(|e| invalid(e.to_string()))?;
+        let points = ["trkpt", "rtept", "wpt"]
+            .iter()
+            .map(|name| {
+                document
+                    .descendants()
+                    .filter(|node| node.has_tag_name(*name))
+                    .collect::<Vec<_>>()
+            })
+            .find(|points| !points.is_empty())
+            .unwrap_or_default();
+
+        let mut start = None;
+        let mut waypoints = Vec::with_capacity(points.len());
+        for (index, point) in points.iter().enumerate() {
+            let coordinate = |name: &str| {
+                point
+                    .attribute(name)
+                    .and_then(|value| value.trim().parse::<f64>().ok())
+                    .ok_or_else(|| invalid(format!("point {index} has no valid `{name}`")))
+            };
+            let child = |name: &str| {
+                point
+                    .children()
+                    .find(|child| child.has_tag_name(name))
+                    .and_then(|child| child.text())
+                    .map(str::trim)
+            };
+
+            let time =
+                match child("time").and_then(|time| OffsetDateTime::parse(time, &Rfc3339).ok()) {
+                    Some(time) => {
+                        let start = *start.get_or_insert(time);
+                        (time - start)
+                            .whole_milliseconds()
+                            .clamp(0, u32::MAX.into()) as u32
+                    }
+                    None => (index as u32).saturating_mul(GPX_STEP),
+                };
+
+            waypoints.push(MockWaypoint {
+                latitude: coordinate("lat")?,
+                longitude: coordinate("lon")?,
+                altitude: child("ele").and_then(|ele| ele.parse().ok()),
+                accuracy: 5.0,
+                time,
+            });
+        }
+
+        Ok(Self {
+            waypoints,
+            repeat: false,
+            playback_rate: 1.0,
+        })
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        if self.waypoints.is_empty() {
+            return Err(crate::Error::InvalidMockRoute(
+                "the route has no waypoints".into(),
+            ));
+        }
+        if self
+            .waypoints
+            .windows(2)
+            .any(|pair| pair[1].time < pair[0].time)
+        {
+            return Err(crate::Error::InvalidMockRoute(
+                "the waypoints are not ordered by time".into(),
+            ));
+        }
+        if self.playback_rate.is_nan() || self.playback_rate <= 0.0 {
+            return Err(crate::Error::InvalidMockRoute(
+                "the playback rate must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct Playback {
+    route: MockRoute,
+    started: Instant,
+}
+
+impl Playback {
+    /// Returns the time in the route in milliseconds, and whether the route ended.
+    fn elapsed(&self) -> (u64, bool) {
+        let duration = self
+            .route
+            .waypoints
+            .last()
+            .map(|w| w.time)
+            .unwrap_or_default();
+        let elapsed =
+            (self.started.elapsed().as_secs_f64() * 1000.0 * self.route.playback_rate) as u64;
+        if self.route.repeat && duration > 0 {
+            (elapsed % u64::from(duration), false)
+        } else {
+            (elapsed, elapsed >= u64::from(duration))
+        }
+    }
+
+    fn position(&self) -> Position {
+        let (elapsed, _) = self.elapsed();
+        position_at(&self.route.waypoints, elapsed)
+    }
+}
+
+fn distance(from: &MockWaypoint, to: &MockWaypoint) -> f64 {
+    let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (to.longitude - from.longitude).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
+
+fn bearing(from: &MockWaypoint, to: &MockWaypoint) -> f64 {
+    let (lat1, lat2) = (from.latitude.to_radians(), to.latitude.to_radians());
+    let dlon = (to.longitude - from.longitude).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+/// Interpolates the position at the given time of the route, the waypoints must not be empty.
+fn position_at(waypoints: &[MockWaypoint], elapsed: u64) -> Position {
+    let segment = waypoints
+        .windows(2)
+        .find(|pair| u64::from(pair[0].time) <= elapsed && elapsed < u64::from(pair[1].time));
+
+    let coords = match segment {
+        Some([from, to]) => {
+            let duration = f64::from(to.time - from.time);
+            let fraction = (elapsed - u64::from(from.time)) as f64 / duration;
+            let lerp = |a: f64, b: f64| a + (b - a) * fraction;
+            let speed = distance(from, to) / (duration / 1000.0);
+            let altitude = from.altitude.zip(to.altitude).map(|(a, b)| lerp(a, b));
+            let accuracy = lerp(from.accuracy, to.accuracy);
+            Coordinates {
+                latitude: lerp(from.latitude, to.latitude),
+                longitude: lerp(from.longitude, to.longitude),
+                accuracy,
+                altitude,
+                altitude_accuracy: altitude.map(|_| accuracy),
+                speed: Some(speed),
+                heading: (speed > 0.0).then(|| bearing(from, to)),
+                ..Default::default()
+            }
+        }
+        // before the first or after the last waypoint
+        _ => {
+            let waypoint = if elapsed < u64::from(waypoints[0].time) {
+                &waypoints[0]
+            } else {
+                &waypoints[waypoints.len() - 1]
+            };
+            Coordinates {
+                latitude: waypoint.latitude,
+                longitude: waypoint.longitude,
+                accuracy: waypoint.accuracy,
+                altitude: waypoint.altitude,
+                altitude_accuracy: waypoint.altitude.map(|_| waypoint.accuracy),
+                speed: Some(0.0),
+                ..Default::default()
+            }
+        }
+    };
+
+    Position {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default(),
+        coords,
+        ..Default::default()
+    }
+}
+
+/// The mocked route, replacing the positions of the platform while set.
+#[derive(Default)]
+pub(crate) struct Mock {
+    playback: Arc<Mutex<Option<Arc<Playback>>>>,
+    /// The stop flags of the watchers started while a route is mocked, by channel id.
+    watchers: Mutex<HashMap<u32, Arc<AtomicBool>>>,
+}
+
+impl Mock {
+    /// Starts playing the route, replacing the current one.
+    pub(crate) fn set(&self, route: MockRoute) -> crate::Result<()> {
+        if !ENABLED {
+            return Err(crate::Error::MockDisabled);
+        }
+        route.validate()?;
+        self.playback.lock().unwrap().replace(Arc::new(Playback {
+            route,
+            started: Instant::now(),
+        }));
+        Ok(())
+    }
+
+    /// Stops the route and the watchers started while it was set.
+    pub(crate) fn clear(&self) {
+        self.playback.lock().unwrap().take();
+        for (_, stopped) in self.watchers.lock().unwrap().drain() {
+            stopped.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the current position of the route, if a route is mocked.
+    pub(crate) fn current_position(&self) -> Option<Position> {
+        self.playback
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|playback| playback.position())
+    }
+
+    /// Sends the positions of the route to the channel every second, returns `false` if no route is mocked.
+    ///
+    /// The watcher follows the route set later, and stops once the route ends.
+    pub(crate) fn watch(&self, channel: &Channel) -> bool {
+        if self.playback.lock().unwrap().is_none() {
+            return false;
+        }
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        self.watchers
+            .lock()
+            .unwrap()
+            .insert(channel.id(), stopped.clone());
+
+        let playback = self.playback.clone();
+        let channel = channel.clone();
+        std::thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                let Some(playback) = playback.lock().unwrap().clone() else {
+                    break;
+                };
+                let _ = channel.send(WatchEvent::Position(playback.position()));
+                if playback.elapsed().1 {
+                    break;
+                }
+                std::thread::sleep(WATCH_INTERVAL);
+            }
+        });
+        true
+    }
+
+    /// Stops a watcher started while a route was mocked, returns `false` if the watcher is not mocked.
+    pub(crate) fn clear_watch(&self, channel_id: u32) -> bool {
+        match self.watchers.lock().unwrap().remove(&channel_id) {
+            Some(stopped) => {
+                stopped.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -23,6 +23,21 @@ pub struct PermissionStatus {
     ///
     /// On iOS it will have the same value as the `location` alias.
     pub coarse_location: PermissionState,
+    /// Permission state for the backgroundLocation alias, required to receive geofence events while the app is in the background.
+    ///
+    /// On Android it requests/checks ACCESS_BACKGROUND_LOCATION, which must be added to the app manifest.
+    /// It can only be granted after the `location` alias on Android 11+.
+    ///
+    /// On iOS it requests/checks the "Always" location authorization.
+    #[serde(default)]
+    pub background_location: PermissionState,
+    /// Permission state for the motion alias, required for the motion activity of the positions.
+    ///
+    /// On Android it requests/checks ACTIVITY_RECOGNITION, which must be added to the app manifest.
+    ///
+    /// On iOS it checks the motion and fitness authorization, which is requested on the first use.
+    #[serde(default)]
+    pub motion: PermissionState,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
@@ -35,15 +50,21 @@ pub struct PositionOptions {
     /// Default: 10000
     /// On Android the timeout gets ignored for getCurrentPosition.
     /// Ignored on iOS.
+    /// On desktop a timeout of 0 waits indefinitely.
     // TODO: Handle Infinity and default to it.
     // TODO: Should be u64+ but specta doesn't like that?
     pub timeout: u32,
     /// The maximum age in milliseconds of a possible cached position that is acceptable to return.
     /// Default: 0
-    /// Ignored on iOS.
+    /// Ignored on iOS, macOS and Linux.
     // TODO: Handle Infinity.
     // TODO: Should be u64+ but specta doesn't like that?
     pub maximum_age: u32,
+    /// Whether to detect the motion activity of the user, reported in [`Position::activity`].
+    /// Requires the `motion` permission.
+    /// Default: false
+    #[serde(default)]
+    pub include_activity: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -51,6 +72,8 @@ pub struct PositionOptions {
 pub enum PermissionType {
     Location,
     CoarseLocation,
+    BackgroundLocation,
+    Motion,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
@@ -69,8 +92,42 @@ pub struct Coordinates {
     pub altitude: Option<f64>,
     // The speed the user is traveling, if available.
     pub speed: Option<f64>,
+    /// Accuracy level of the speed in meters per second, if available.
+    /// Available on all iOS versions and on Android 8 and above.
+    #[serde(default)]
+    pub speed_accuracy: Option<f64>,
     /// The heading the user is facing, if available.
     pub heading: Option<f64>,
+    /// Accuracy level of the heading in degrees, if available.
+    /// Available on iOS 13.4 and above and on Android 8 and above.
+    #[serde(default)]
+    pub heading_accuracy: Option<f64>,
+}
+
+/// The direction the device is pointing to, measured by the compass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Heading {
+    /// Creation time for this heading.
+    pub timestamp: u64,
+    /// The heading relative to the magnetic north in degrees.
+    pub magnetic_heading: f64,
+    /// The heading relative to the geographic north in degrees, if the position is known.
+    pub true_heading: Option<f64>,
+    /// The maximum deviation of the magnetic heading in degrees, if available.
+    pub accuracy: Option<f64>,
+}
+
+/// The motion activity of the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum MotionActivity {
+    Stationary,
+    Walking,
+    Running,
+    Cycling,
+    Automotive,
+    Unknown,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
@@ -81,6 +138,12 @@ pub struct Position {
     pub timestamp: u64,
     /// The GPS coordinates along with the accuracy of the data.
     pub coords: Coordinates,
+    /// The last compass heading, only available while a heading watcher is active.
+    #[serde(default)]
+    pub compass: Option<Heading>,
+    /// The last detected motion activity, only available with [`PositionOptions::include_activity`].
+    #[serde(default)]
+    pub activity: Option<MotionActivity>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -89,3 +152,145 @@ pub enum WatchEvent {
     Position(Position),
     Error(String),
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(untagged)]
+pub enum HeadingEvent {
+    Heading(Heading),
+    Error(String),
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_loitering_delay() -> u32 {
+    30000
+}
+
+fn default_mock_accuracy() -> f64 {
+    5.0
+}
+
+fn default_playback_rate() -> f64 {
+    1.0
+}
+
+/// A circular region monitored by the system, even while the app is in the background.
+///
+/// The geofences are persisted and registered again when the app starts and after a reboot.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Geofence {
+    /// The identifier of the geofence, a geofence with the same identifier is replaced.
+    pub id: String,
+    /// Latitude of the center in decimal degrees.
+    pub latitude: f64,
+    /// Longitude of the center in decimal degrees.
+    pub longitude: f64,
+    /// The radius in meters.
+    /// The system may not report regions smaller than 100 to 150 meters reliably.
+    pub radius: f64,
+    /// Whether to report entering the geofence. Default: true
+    #[serde(default = "default_true")]
+    pub notify_on_enter: bool,
+    /// Whether to report leaving the geofence. Default: true
+    #[serde(default = "default_true")]
+    pub notify_on_exit: bool,
+    /// Whether to report staying in the geofence for the `loitering_delay`. Default: false
+    ///
+    /// On iOS the dwell is only detected while the app is running.
+    #[serde(default)]
+    pub notify_on_dwell: bool,
+    /// The time in milliseconds to stay in the geofence before reporting a dwell.
+    /// Default: 30000
+    #[serde(default = "default_loitering_delay")]
+    pub loitering_delay: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GeofenceTransition {
+    Enter,
+    Exit,
+    Dwell,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct GeofenceEvent {
+    /// The identifier of the geofence.
+    pub id: String,
+    pub transition: GeofenceTransition,
+    /// The position that triggered the transition, if known.
+    pub position: Option<Position>,
+}
+
+/// The service providing the positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum Backend {
+    Android,
+    Ios,
+    /// The `Windows.Devices.Geolocation` API.
+    Windows,
+    /// The CoreLocation framework on macOS.
+    CoreLocation,
+    /// The GeoClue service on Linux.
+    GeoClue,
+}
+
+/// The features supported by the positioning backend of the platform.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    pub backend: Backend,
+    /// Whether the positioning service is installed and enabled.
+    pub available: bool,
+    /// Whether the positions may include the altitude.
+    pub altitude: bool,
+    /// Whether the positions may include the speed.
+    pub speed: bool,
+    /// Whether the positions may include the direction of travel.
+    pub heading: bool,
+    /// Whether [`watch_heading`](crate::Geolocation::watch_heading) is supported.
+    pub compass: bool,
+    /// Whether geofences are supported.
+    pub geofencing: bool,
+    /// Whether [`PositionOptions::include_activity`] is supported.
+    pub motion_activity: bool,
+}
+
+/// A point of a [`MockRoute`].
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MockWaypoint {
+    /// Latitude in decimal degrees.
+    pub latitude: f64,
+    /// Longitude in decimal degrees.
+    pub longitude: f64,
+    /// The altitude in meters, if known.
+    #[serde(default)]
+    pub altitude: Option<f64>,
+    /// The reported accuracy in meters. Default: 5
+    #[serde(default = "default_mock_accuracy")]
+    pub accuracy: f64,
+    /// The time in milliseconds since the start of the route when the point is reached.
+    pub time: u32,
+}
+
+/// A scripted route replacing the positions of the device, to test location features without a positioning device.
+///
+/// The positions are interpolated between the waypoints, with the speed and heading of the current segment.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MockRoute {
+    /// The waypoints, ordered by time.
+    pub waypoints: Vec<MockWaypoint>,
+    /// Whether to start over once the last waypoint is reached. Default: false
+    #[serde(default)]
+    pub repeat: bool,
+    /// The speed of the playback, 2 plays the route twice as fast. Default: 1
+    #[serde(default = "default_playback_rate")]
+    pub playback_rate: f64,
+}
@@ -0,0 +1,186 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Positions from the GeoClue service.
+//!
+//! The authorization is handled by the GeoClue agent of the desktop when a client starts,
+//! identified by the app identifier which should match the name of its desktop entry.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::{plugin::PermissionState, AppHandle, Runtime};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::OwnedObjectPath,
+};
+
+use crate::{
+    desktop::{first_position, Callback},
+    models::*,
+};
+
+const GEOCLUE: &str = "org.freedesktop.GeoClue2";
+const MANAGER_PATH: &str = "/org/freedesktop/GeoClue2/Manager";
+const MANAGER: &str = "org.freedesktop.GeoClue2.Manager";
+const CLIENT: &str = "org.freedesktop.GeoClue2.Client";
+const LOCATION: &str = "org.freedesktop.GeoClue2.Location";
+
+// GClueAccuracyLevel
+const ACCURACY_NONE: u32 = 0;
+const ACCURACY_NEIGHBORHOOD: u32 = 5;
+const ACCURACY_EXACT: u32 = 8;
+
+/// Stops the GeoClue client when dropped.
+pub(crate) struct Watcher {
+    connection: Connection,
+    client: OwnedObjectPath,
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self
+            .connection
+            .call_method(Some(GEOCLUE), &self.client, Some(CLIENT), "Stop", &());
+        // ends the signal iterator of the watcher thread
+        let _ = self.connection.clone().close();
+    }
+}
+
+fn available_accuracy(connection: &Connection) -> zbus::Result<u32> {
+    Proxy::new(connection, GEOCLUE, MANAGER_PATH, MANAGER)?.get_property("AvailableAccuracyLevel")
+}
+
+/// GeoClue cannot be queried for the decision of its agent, so the state is `prompt` while the service is available.
+pub(crate) fn check_permissions<R: Runtime>(_app: &AppHandle<R>) -> crate::Result<PermissionState> {
+    let connection = Connection::system()?;
+    Ok(match available_accuracy(&connection) {
+        Ok(level) if level > ACCURACY_NONE => PermissionState::Prompt,
+        _ => PermissionState::Denied,
+    })
+}
+
+/// Starts a client so the agent asks the user, its answer is known once the client started.
+pub(crate) fn request_permissions<R: Runtime>(
+    app: &AppHandle<R>,
+) -> crate::Result<PermissionState> {
+    match watch(app, &PositionOptions::default(), Box::new(|_| {})) {
+        Ok(_watcher) => Ok(PermissionState::Granted),
+        Err(crate::Error::PermissionDenied) => Ok(PermissionState::Denied),
+        Err(e) => Err(e),
+    }
+}
+
+fn read_location(connection: &Connection, path: OwnedObjectPath) -> zbus::Result<Position> {
+    let location = Proxy::new(connection, GEOCLUE, path, LOCATION)?;
+    let altitude = location.get_property::<f64>("Altitude")?;
+    let speed = location.get_property::<f64>("Speed")?;
+    let heading = location.get_property::<f64>("Heading")?;
+    let timestamp = location
+        .get_property::<(u64, u64)>("Timestamp")
+        .map(|(seconds, micros)| seconds * 1000 + micros / 1000)
+        .unwrap_or_else(|_| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default()
+        });
+
+    Ok(Position {
+        timestamp,
+        coords: Coordinates {
+            latitude: location.get_property("Latitude")?,
+            longitude: location.get_property("Longitude")?,
+            accuracy: location.get_property("Accuracy")?,
+            // unknown values are -DBL_MAX for the altitude and negative for the speed and heading
+            altitude: (altitude > f64::MIN).then_some(altitude),
+            speed: (speed >= 0.0).then_some(speed),
+            heading: (heading >= 0.0).then_some(heading),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+fn map_start_error(e: zbus::Error) -> crate::Error {
+    match &e {
+        zbus::Error::MethodError(name, ..)
+            if name.as_str() == "org.freedesktop.DBus.Error.AccessDenied" =>
+        {
+            crate::Error::PermissionDenied
+        }
+        _ => e.into(),
+    }
+}
+
+pub(crate) fn current_position<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &PositionOptions,
+) -> crate::Result<Position> {
+    first_position(options, |callback| watch(app, options, callback))
+}
+
+pub(crate) fn watch<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &PositionOptions,
+    callback: Callback,
+) -> crate::Result<Watcher> {
+    let connection = Connection::system()?;
+    let client = connection
+        .call_method(Some(GEOCLUE), MANAGER_PATH, Some(MANAGER), "GetClient", &())?
+        .body()
+        .deserialize::<OwnedObjectPath>()?;
+
+    let proxy = Proxy::new(&connection, GEOCLUE, client.clone(), CLIENT)?;
+    proxy
+        .set_property("DesktopId", app.config().identifier.as_str())
+        .map_err(zbus::Error::from)?;
+    proxy
+        .set_property(
+            "RequestedAccuracyLevel",
+            if options.enable_high_accuracy {
+                ACCURACY_EXACT
+            } else {
+                ACCURACY_NEIGHBORHOOD
+            },
+        )
+        .map_err(zbus::Error::from)?;
+
+    let updates = proxy.receive_signal("LocationUpdated")?;
+    proxy
+        .call::<_, _, ()>("Start", &())
+        .map_err(map_start_error)?;
+
+    let location_connection = connection.clone();
+    std::thread::spawn(move || {
+        for signal in updates {
+            let event = signal
+                .body()
+                .deserialize::<(OwnedObjectPath, OwnedObjectPath)>()
+                .and_then(|(_, location)| read_location(&location_connection, location));
+            callback(match event {
+                Ok(position) => WatchEvent::Position(position),
+                Err(e) => WatchEvent::Error(e.to_string()),
+            });
+        }
+    });
+
+    Ok(Watcher { connection, client })
+}
+
+pub(crate) fn capabilities<R: Runtime>(_app: &AppHandle<R>) -> Capabilities {
+    let available = Connection::system()
+        .and_then(|connection| available_accuracy(&connection))
+        .is_ok_and(|level| level > ACCURACY_NONE);
+
+    Capabilities {
+        backend: Backend::GeoClue,
+        available,
+        altitude: true,
+        speed: true,
+        heading: true,
+        compass: false,
+        geofencing: false,
+        motion_activity: false,
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Positions from the CoreLocation framework.
+//!
+//! The location managers deliver their updates to the run loop of the thread that created them,
+//! so they are created and kept on the main thread.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc::channel,
+    },
+};
+
+use objc2::{
+    declare_class, msg_send_id, mutability, rc::Id, runtime::ProtocolObject, ClassType,
+    DeclaredClass,
+};
+use objc2_core_location::{
+    kCLLocationAccuracyBest, kCLLocationAccuracyHundredMeters, CLAuthorizationStatus, CLLocation,
+    CLLocationManager, CLLocationManagerDelegate,
+};
+use objc2_foundation::{MainThreadMarker, NSArray, NSError, NSObject, NSObjectProtocol};
+use tauri::{plugin::PermissionState, AppHandle, Runtime};
+
+use crate::{
+    desktop::{first_position, Callback},
+    models::*,
+};
+
+// kCLErrorLocationUnknown, the manager keeps trying after reporting it
+const ERROR_LOCATION_UNKNOWN: isize = 0;
+
+enum Update {
+    Position(Position),
+    Error(String),
+    Authorization(PermissionState),
+}
+
+type Handler = Box<dyn Fn(Update) + Send>;
+
+struct Ivars {
+    handler: Handler,
+    /// Requests the updates, called again once the app is authorized.
+    start: fn(&CLLocationManager),
+}
+
+declare_class!(
+    struct Delegate;
+
+    unsafe impl ClassType for Delegate {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "TauriPluginGeolocationDelegate";
+    }
+
+    impl DeclaredClass for Delegate {
+        type Ivars = Ivars;
+    }
+
+    unsafe impl NSObjectProtocol for Delegate {}
+
+    unsafe impl CLLocationManagerDelegate for Delegate {
+        #[method(locationManager:didUpdateLocations:)]
+        fn did_update_locations(&self, _manager: &CLLocationManager, locations: &NSArray<CLLocation>) {
+            if let Some(location) = unsafe { locations.lastObject() } {
+                (self.ivars().handler)(Update::Position(convert(&location)));
+            }
+        }
+
+        #[method(locationManager:didFailWithError:)]
+        fn did_fail_with_error(&self, _manager: &CLLocationManager, error: &NSError) {
+            if error.code() != ERROR_LOCATION_UNKNOWN {
+                (self.ivars().handler)(Update::Error(error.localizedDescription().to_string()));
+            }
+        }
+
+        #[method(locationManagerDidChangeAuthorization:)]
+        fn did_change_authorization(&self, manager: &CLLocationManager) {
+            let state = permission_state(unsafe { manager.authorizationStatus() });
+            if state == PermissionState::Granted {
+                (self.ivars().start)(manager);
+            }
+            (self.ivars().handler)(Update::Authorization(state));
+        }
+    }
+);
+
+impl Delegate {
+    fn new(mtm: MainThreadMarker, ivars: Ivars) -> Id<Self> {
+        let this = mtm.alloc().set_ivars(ivars);
+        unsafe { msg_send_id![super(this), init] }
+    }
+}
+
+thread_local! {
+    /// The running managers by id, only accessed from the main thread.
+    static MANAGERS: RefCell<HashMap<u32, (Id<CLLocationManager>, Id<Delegate>)>> = RefCell::default();
+}
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Stops the location manager when dropped.
+pub(crate) struct Watcher(Option<Box<dyn FnOnce() + Send>>);
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        if let Some(stop) = self.0.take() {
+            stop();
+        }
+    }
+}
+
+fn permission_state(status: CLAuthorizationStatus) -> PermissionState {
+    match status.0 {
+        // kCLAuthorizationStatusNotDetermined
+        0 => PermissionState::Prompt,
+        // kCLAuthorizationStatusAuthorizedAlways and kCLAuthorizationStatusAuthorizedWhenInUse
+        3 | 4 => PermissionState::Granted,
+        // kCLAuthorizationStatusRestricted, kCLAuthorizationStatusDenied or location services disabled
+        _ => PermissionState::Denied,
+    }
+}
+
+fn convert(location: &CLLocation) -> Position {
+    unsafe {
+        let coordinate = location.coordinate();
+        let vertical_accuracy = location.verticalAccuracy();
+        let speed_accuracy = location.speedAccuracy();
+        let course_accuracy = location.courseAccuracy();
+        // negative values are invalid
+        let valid = |value: f64| (value >= 0.0).then_some(value);
+
+        Position {
+            timestamp: (location.timestamp().timeIntervalSince1970() * 1000.0) as u64,
+            coords: Coordinates {
+                latitude: coordinate.latitude,
+                longitude: coordinate.longitude,
+                accuracy: location.horizontalAccuracy(),
+                altitude: valid(vertical_accuracy).map(|_| location.altitude()),
+                altitude_accuracy: valid(vertical_accuracy),
+                speed: valid(speed_accuracy).and(valid(location.speed())),
+                speed_accuracy: valid(speed_accuracy),
+                heading: valid(course_accuracy).and(valid(location.course())),
+                heading_accuracy: valid(course_accuracy),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// Runs the closure on the main thread and waits for its result.
+fn on_main<R: Runtime, T: Send + 'static>(
+    app: &AppHandle<R>,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> crate::Result<T> {
+    let (tx, rx) = channel();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(f());
+    })?;
+    rx.recv()
+        .map_err(|_| crate::Error::Unavailable("the event loop is not running".into()))
+}
+
+/// Creates a location manager on the main thread, requesting the authorization if needed.
+fn start<R: Runtime>(
+    app: &AppHandle<R>,
+    accuracy: f64,
+    start: fn(&CLLocationManager),
+    handler: Handler,
+) -> crate::Result<Watcher> {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    app.run_on_main_thread(move || {
+        let mtm = MainThreadMarker::new().unwrap();
+        let delegate = Delegate::new(mtm, Ivars { handler, start });
+        let manager = unsafe { CLLocationManager::new() };
+        unsafe {
+            manager.setDesiredAccuracy(accuracy);
+            manager.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+            if permission_state(manager.authorizationStatus()) == PermissionState::Prompt {
+                manager.requestWhenInUseAuthorization();
+            } else {
+                start(&manager);
+            }
+        }
+        MANAGERS.with(|managers| managers.borrow_mut().insert(id, (manager, delegate)));
+    })?;
+
+    let app = app.clone();
+    Ok(Watcher(Some(Box::new(move || {
+        let _ = app.run_on_main_thread(move || {
+            let removed = MANAGERS.with(|managers| managers.borrow_mut().remove(&id));
+            if let Some((manager, _delegate)) = removed {
+                unsafe {
+                    manager.stopUpdatingLocation();
+                    manager.setDelegate(None);
+                }
+            }
+        });
+    }))))
+}
+
+fn accuracy(options: &PositionOptions) -> f64 {
+    unsafe {
+        if options.enable_high_accuracy {
+            kCLLocationAccuracyBest
+        } else {
+            kCLLocationAccuracyHundredMeters
+        }
+    }
+}
+
+pub(crate) fn check_permissions<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PermissionState> {
+    on_main(app, || unsafe {
+        permission_state(CLLocationManager::new().authorizationStatus())
+    })
+}
+
+pub(crate) fn request_permissions<R: Runtime>(
+    app: &AppHandle<R>,
+) -> crate::Result<PermissionState> {
+    let state = check_permissions(app)?;
+    if state != PermissionState::Prompt {
+        return Ok(state);
+    }
+
+    let (tx, rx) = channel();
+    let _watcher = start(
+        app,
+        accuracy(&PositionOptions::default()),
+        |_| {},
+        Box::new(move |update| {
+            if let Update::Authorization(state) = update {
+                let _ = tx.send(state);
+            }
+        }),
+    )?;
+    // the delegate is notified of the current state first, wait for the answer of the user
+    Ok(rx
+        .iter()
+        .find(|state| *state != PermissionState::Prompt)
+        .unwrap_or(PermissionState::Prompt))
+}
+
+fn watch_with<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &PositionOptions,
+    callback: Callback,
+    request: fn(&CLLocationManager),
+) -> crate::Result<Watcher> {
+    start(
+        app,
+        accuracy(options),
+        request,
+        Box::new(move |update| match update {
+            Update::Position(position) => callback(WatchEvent::Position(position)),
+            Update::Error(error) => callback(WatchEvent::Error(error)),
+            Update::Authorization(PermissionState::Denied) => callback(WatchEvent::Error(
+                crate::Error::PermissionDenied.to_string(),
+            )),
+            Update::Authorization(_) => {}
+        }),
+    )
+}
+
+pub(crate) fn current_position<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &PositionOptions,
+) -> crate::Result<Position> {
+    first_position(options, |callback| {
+        watch_with(app, options, callback, |manager| unsafe {
+            manager.requestLocation()
+        })
+    })
+}
+
+pub(crate) fn watch<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &PositionOptions,
+    callback: Callback,
+) -> crate::Result<Watcher> {
+    watch_with(app, options, callback, |manager| unsafe {
+        manager.startUpdatingLocation()
+    })
+}
+
+pub(crate) fn capabilities<R: Runtime>(app: &AppHandle<R>) -> Capabilities {
+    Capabilities {
+        backend: Backend::CoreLocation,
+        available: check_permissions(app).is_ok_and(|state| state != PermissionState::Denied),
+        altitude: true,
+        speed: true,
+        heading: true,
+        compass: false,
+        geofencing: false,
+        motion_activity: false,
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Positions from the `Windows.Devices.Geolocation` API.
+
+use tauri::{plugin::PermissionState, AppHandle, Runtime};
+use windows::{
+    Devices::Geolocation::{
+        GeolocationAccessStatus, Geolocator, Geoposition, PositionAccuracy,
+        PositionChangedEventArgs, PositionStatus,
+    },
+    Foundation::{IReference, TimeSpan, TypedEventHandler},
+};
+
+use crate::{desktop::Callback, models::*};
+
+/// The difference between the Windows epoch (1601) and the Unix epoch, in 100 nanoseconds.
+const UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+/// Stops the position updates when dropped.
+pub(crate) struct Watcher {
+    locator: Geolocator,
+    token: i64,
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        let _ = self.locator.RemovePositionChanged(self.token);
+    }
+}
+
+fn permission_state(status: GeolocationAccessStatus) -> PermissionState {
+    match status {
+        GeolocationAccessStatus::Allowed => PermissionState::Granted,
+        GeolocationAccessStatus::Denied => PermissionState::Denied,
+        _ => PermissionState::Prompt,
+    }
+}
+
+/// Windows has no way to read the access status without requesting it, the system only prompts once.
+pub(crate) fn check_permissions<R: Runtime>(app: &AppHandle<R>) -> crate::Result<PermissionState> {
+    request_permissions(app)
+}
+
+pub(crate) fn request_permissions<R: Runtime>(
+    _app: &AppHandle<R>,
+) -> crate::Result<PermissionState> {
+    Ok(permission_state(Geolocator::RequestAccessAsync()?.get()?))
+}
+
+fn locator(options: &PositionOptions) -> crate::Result<Geolocator> {
+    let locator = Geolocator::new()?;
+    locator.SetDesiredAccuracy(if options.enable_high_accuracy {
+        PositionAccuracy::High
+    } else {
+        PositionAccuracy::Default
+    })?;
+    Ok(locator)
+}
+
+fn time_span(millis: u32) -> TimeSpan {
+    TimeSpan {
+        Duration: i64::from(millis) * 10_000,
+    }
+}
+
+fn value(reference: windows::core::Result<IReference<f64>>) -> Option<f64> {
+    reference.and_then(|reference| reference.Value()).ok()
+}
+
+fn convert(position: &Geoposition) -> crate::Result<Position> {
+    let coordinate = position.Coordinate()?;
+    let point = coordinate.Point()?.Position()?;
+    let timestamp = coordinate.Timestamp()?.UniversalTime;
+    let altitude_accuracy = value(coordinate.AltitudeAccuracy());
+
+    Ok(Position {
+        timestamp: ((timestamp - UNIX_EPOCH) / 10_000).max(0) as u64,
+        coords: Coordinates {
+            latitude: point.Latitude,
+            longitude: point.Longitude,
+            accuracy: coordinate.Accuracy()?,
+            // the altitude is 0 when unknown, only trust it with an accuracy
+            altitude: altitude_accuracy.map(|_| point.Altitude),
+            altitude_accuracy,
+            speed: value(coordinate.Speed()).filter(|speed| !speed.is_nan()),
+            heading: value(coordinate.Heading()).filter(|heading| !heading.is_nan()),
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+}
+
+pub(crate) fn current_position<R: Runtime>(
+    _app: &AppHandle<R>,
+    options: &PositionOptions,
+) -> crate::Result<Position> {
+    let locator = locator(options)?;
+    let position = if options.timeout > 0 {
+        locator.GetGeopositionAsyncWithAgeAndTimeout(
+            time_span(options.maximum_age),
+            time_span(options.timeout),
+        )?
+    } else {
+        locator.GetGeopositionAsync()?
+    }
+    .get()
+    .map_err(|e| {
+        if e.code() == windows::Win32::Foundation::E_ACCESSDENIED {
+            crate::Error::PermissionDenied
+        } else {
+            e.into()
+        }
+    })?;
+    convert(&position)
+}
+
+pub(crate) fn watch<R: Runtime>(
+    _app: &AppHandle<R>,
+    options: &PositionOptions,
+    callback: Callback,
+) -> crate::Result<Watcher> {
+    let locator = locator(options)?;
+    let token = locator.PositionChanged(&TypedEventHandler::new(
+        move |_, args: &Option<PositionChangedEventArgs>| {
+            if let Some(args) = args {
+                callback(
+                    match args
+                        .Position()
+                        .map_err(Into::into)
+                        .and_then(|p| convert(&p))
+                    {
+                        Ok(position) => WatchEvent::Position(position),
+                        Err(e) => WatchEvent::Error(e.to_string()),
+                    },
+                );
+            }
+            Ok(())
+        },
+    ))?;
+    Ok(Watcher { locator, token })
+}
+
+pub(crate) fn capabilities<R: Runtime>(_app: &AppHandle<R>) -> Capabilities {
+    let available = Geolocator::new()
+        .and_then(|locator| locator.LocationStatus())
+        .map(|status| {
+            !matches!(
+                status,
+                PositionStatus::Disabled | PositionStatus::NotAvailable
+            )
+        })
+        .unwrap_or(false);
+
+    Capabilities {
+        backend: Backend::Windows,
+        available,
+        altitude: true,
+        speed: true,
+        heading: true,
+        compass: false,
+        geofencing: false,
+        motion_activity: false,
+    }
+}
@@ -8,6 +8,7 @@ import android.app.Activity
 import android.content.Context
 import android.os.Build
 import android.os.VibrationEffect
+import android.os.VibrationEffect.Composition
 import android.os.Vibrator
 import android.os.VibratorManager
 import app.tauri.Logger
@@ -23,16 +24,29 @@ import app.tauri.haptics.patterns.NotificationPatternError
 import app.tauri.haptics.patterns.NotificationPatternSuccess
 import app.tauri.haptics.patterns.NotificationPatternWarning
 import app.tauri.haptics.patterns.Pattern
+import app.tauri.haptics.patterns.Pulse
 import app.tauri.haptics.patterns.SelectionPattern
+import app.tauri.haptics.patterns.amplitude
+import app.tauri.haptics.patterns.pulsesPattern
+import app.tauri.haptics.patterns.transientPulse
+import app.tauri.haptics.patterns.waveformPattern
 import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
+import app.tauri.plugin.JSObject
 import app.tauri.plugin.Plugin
 import com.fasterxml.jackson.annotation.JsonProperty
+import kotlin.math.roundToInt
 
 @InvokeArg
 class HapticsOptions {
   var duration: Long = 300
 }
 
+@InvokeArg
+class IntensityArgs {
+    var intensity: Float = 1f
+}
+
 @InvokeArg
 class NotificationFeedbackArgs {
     val type: NotificationFeedbackType = NotificationFeedbackType.Success
@@ -85,6 +99,90 @@ enum class ImpactFeedbackStyle {
     }
 }
 
+@InvokeArg
+class HapticEventArgs {
+    var kind: String = "transient"
+    var time: Long = 0
+    var duration: Long = 0
+    var intensity: Float = 1f
+}
+
+@InvokeArg
+class HapticPrimitiveArgs {
+    var primitive: HapticPrimitiveKind = HapticPrimitiveKind.Click
+    var scale: Float = 1f
+    var delay: Int = 0
+}
+
+@InvokeArg
+enum class HapticPrimitiveKind {
+    @JsonProperty("click")
+    Click,
+    @JsonProperty("thud")
+    Thud,
+    @JsonProperty("spin")
+    Spin,
+    @JsonProperty("quickRise")
+    QuickRise,
+    @JsonProperty("slowRise")
+    SlowRise,
+    @JsonProperty("quickFall")
+    QuickFall,
+    @JsonProperty("tick")
+    Tick,
+    @JsonProperty("lowTick")
+    LowTick;
+
+    fun key(): String {
+        return when(this) {
+            Click -> "click"
+            Thud -> "thud"
+            Spin -> "spin"
+            QuickRise -> "quickRise"
+            SlowRise -> "slowRise"
+            QuickFall -> "quickFall"
+            Tick -> "tick"
+            LowTick -> "lowTick"
+        }
+    }
+
+    fun id(): Int {
+        return when(this) {
+            Click -> Composition.PRIMITIVE_CLICK
+            Thud -> Composition.PRIMITIVE_THUD
+            Spin -> Composition.PRIMITIVE_SPIN
+            QuickRise -> Composition.PRIMITIVE_QUICK_RISE
+            SlowRise -> Composition.PRIMITIVE_SLOW_RISE
+            QuickFall -> Composition.PRIMITIVE_QUICK_FALL
+            Tick -> Composition.PRIMITIVE_TICK
+            LowTick -> Composition.PRIMITIVE_LOW_TICK
+        }
+    }
+
+    // The duration and strength of the pulse approximating the primitive on older devices
+    fun pulse(): Pair<Long, Float> {
+        return when(this) {
+            Click -> Pair(20, 1f)
+            Thud -> Pair(50, 1f)
+            Spin -> Pair(100, 0.6f)
+            QuickRise -> Pair(80, 0.8f)
+            SlowRise -> Pair(200, 0.8f)
+            QuickFall -> Pair(80, 0.8f)
+            Tick -> Pair(10, 0.6f)
+            LowTick -> Pair(10, 0.3f)
+        }
+    }
+}
+
+@InvokeArg
+class PlayPatternArgs {
+    lateinit var type: String
+    var timings: LongArray = longArrayOf()
+    var amplitudes: IntArray = intArrayOf()
+    var events: Array<HapticEventArgs> = arrayOf()
+    var primitives: Array<HapticPrimitiveArgs> = arrayOf()
+}
+
 @TauriPlugin
 class HapticsPlugin(private val activity: Activity): Plugin(activity) {
     private val vibrator: Vibrator = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
@@ -95,6 +193,9 @@ class HapticsPlugin(private val activity: Activity): Plugin(activity) {
         activity.applicationContext.getSystemService(Context.VIBRATOR_SERVICE) as Vibrator
     }
 
+    // The global intensity multiplier, persisted by the Rust side
+    private var intensity = 1f
+
     //
     // TAURI COMMANDS
     //
@@ -103,7 +204,8 @@ class HapticsPlugin(private val activity: Activity): Plugin(activity) {
     fun vibrate(invoke: Invoke) {
         val args = invoke.parseArgs(HapticsOptions::class.java)
         if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
-            vibrator.vibrate(VibrationEffect.createOneShot(args.duration, VibrationEffect.DEFAULT_AMPLITUDE))
+            val amplitude = if (intensity < 1f) amplitude(intensity) else VibrationEffect.DEFAULT_AMPLITUDE
+            vibrator.vibrate(VibrationEffect.createOneShot(args.duration, amplitude))
         } else {
             vibrator.vibrate(args.duration)
         }
@@ -131,11 +233,82 @@ class HapticsPlugin(private val activity: Activity): Plugin(activity) {
         invoke.resolve()
     }
 
+    @Command
+    fun playPattern(invoke: Invoke) {
+        val args = invoke.parseArgs(PlayPatternArgs::class.java)
+        when (args.type) {
+            "waveform" -> vibratePattern(waveformPattern(args.timings, args.amplitudes))
+            "events" -> vibratePattern(pulsesPattern(args.events.map {
+                if (it.kind == "continuous") {
+                    Pulse(it.time, it.duration, amplitude(it.intensity))
+                } else {
+                    transientPulse(it.time, it.intensity)
+                }
+            }))
+            "composition" -> vibrateComposition(args.primitives)
+            else -> {
+                invoke.reject("AHAP patterns are only supported on iOS")
+                return
+            }
+        }
+        invoke.resolve()
+    }
+
+    @Command
+    fun capabilities(invoke: Invoke) {
+        val primitives = JSArray()
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
+            for (primitive in HapticPrimitiveKind.values()) {
+                if (vibrator.areAllPrimitivesSupported(primitive.id())) {
+                    primitives.put(primitive.key())
+                }
+            }
+        }
+
+        val capabilities = JSObject()
+        capabilities.put("supported", vibrator.hasVibrator())
+        capabilities.put("amplitudeControl", Build.VERSION.SDK_INT >= Build.VERSION_CODES.O && vibrator.hasAmplitudeControl())
+        capabilities.put("primitives", primitives)
+        invoke.resolve(capabilities)
+    }
+
+    @Command
+    fun setIntensity(invoke: Invoke) {
+        val args = invoke.parseArgs(IntensityArgs::class.java)
+        intensity = args.intensity
+        invoke.resolve()
+    }
+
     // INTERNAL FUNCTIONS
 
+    private fun vibrateComposition(primitives: Array<HapticPrimitiveArgs>) {
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+            val ids = primitives.map { it.primitive.id() }.toIntArray()
+            if (vibrator.areAllPrimitivesSupported(*ids)) {
+                val composition = VibrationEffect.startComposition()
+                for (primitive in primitives) {
+                    composition.addPrimitive(primitive.primitive.id(), primitive.scale * intensity, primitive.delay)
+                }
+                vibrator.vibrate(composition.compose())
+                return
+            }
+        }
+
+        var time = 0L
+        val pulses = primitives.map {
+            val (duration, strength) = it.primitive.pulse()
+            time += it.delay
+            val pulse = Pulse(time, duration, amplitude(strength * it.scale))
+            time += duration
+            pulse
+        }
+        vibratePattern(pulsesPattern(pulses))
+    }
+
     private fun vibratePattern(pattern: Pattern) {
         if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
-            vibrator.vibrate(VibrationEffect.createWaveform(pattern.timings, pattern.amplitudes, -1))
+            val amplitudes = pattern.amplitudes.map { (it * intensity).roundToInt() }.toIntArray()
+            vibrator.vibrate(VibrationEffect.createWaveform(pattern.timings, amplitudes, -1))
         } else {
             vibrator.vibrate(pattern.oldSDKPattern, -1)
         }
@@ -0,0 +1,55 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.haptics.patterns
+
+import kotlin.math.roundToInt
+
+// Duration of the pulse played for a transient event
+private const val TRANSIENT_DURATION = 20L
+
+fun amplitude(intensity: Float): Int {
+    return (intensity * 255).roundToInt().coerceIn(0, 255)
+}
+
+// Builds a pattern from consecutive segments and their amplitudes
+fun waveformPattern(timings: LongArray, amplitudes: IntArray): Pattern {
+    // the legacy API only alternates between off and on durations, starting with off
+    val legacy = mutableListOf(0L)
+    var on = false
+    for (i in timings.indices) {
+        val segmentOn = amplitudes[i] > 0
+        if (segmentOn == on) {
+            legacy[legacy.size - 1] += timings[i]
+        } else {
+            legacy.add(timings[i])
+            on = segmentOn
+        }
+    }
+    return Pattern(timings, amplitudes, legacy.toLongArray())
+}
+
+class Pulse(val time: Long, val duration: Long, val amplitude: Int)
+
+// Lays out the pulses on a waveform, delaying the ones overlapping the previous pulse
+fun pulsesPattern(pulses: List<Pulse>): Pattern {
+    val timings = mutableListOf<Long>()
+    val amplitudes = mutableListOf<Int>()
+    var cursor = 0L
+    for (pulse in pulses.sortedBy { it.time }) {
+        val start = maxOf(pulse.time, cursor)
+        if (start > cursor) {
+            timings.add(start - cursor)
+            amplitudes.add(0)
+        }
+        timings.add(pulse.duration)
+        amplitudes.add(pulse.amplitude)
+        cursor = start + pulse.duration
+    }
+    return waveformPattern(timings.toLongArray(), amplitudes.toIntArray())
+}
+
+fun transientPulse(time: Long, intensity: Float): Pulse {
+    return Pulse(time, TRANSIENT_DURATION, amplitude(intensity))
+}
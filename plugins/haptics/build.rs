@@ -7,6 +7,12 @@ const COMMANDS: &[&str] = &[
     "impact_feedback",
     "notification_feedback",
     "selection_feedback",
+    "play_pattern",
+    "devices",
+    "set_device_intensity",
+    "capabilities",
+    "intensity",
+    "set_intensity",
 ];
 
 fn main() {
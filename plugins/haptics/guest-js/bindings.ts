@@ -57,6 +57,78 @@ export const commands = {
       if (e instanceof Error) throw e
       else return { status: 'error', error: e as any }
     }
+  },
+  async playPattern(pattern: HapticPattern): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|play_pattern', { pattern })
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
+  },
+  async devices(): Promise<Result<HapticDevice[], Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|devices')
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
+  },
+  async setDeviceIntensity(
+    id: string,
+    intensity: number
+  ): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|set_device_intensity', {
+          id,
+          intensity
+        })
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
+  },
+  async capabilities(): Promise<Result<HapticCapabilities, Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|capabilities')
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
+  },
+  async intensity(): Promise<Result<number, Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|intensity')
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
+  },
+  async setIntensity(intensity: number): Promise<Result<null, Error>> {
+    try {
+      return {
+        status: 'ok',
+        data: await TAURI_INVOKE('plugin:haptics|set_intensity', { intensity })
+      }
+    } catch (e) {
+      if (e instanceof Error) throw e
+      else return { status: 'error', error: e as any }
+    }
   }
 }
 
@@ -72,7 +144,138 @@ export const commands = {
 
 /** user-defined types **/
 
-export type Error = never
+export type Error =
+  | { InvalidPattern: string }
+  | { InvalidIntensity: number }
+  | { UnknownDevice: string }
+  | { Io: string }
+/**
+ * What the haptics hardware of the device supports.
+ */
+export type HapticCapabilities = {
+  /**
+   * Whether the device can play haptic feedback at all.
+   */
+  supported: boolean
+  /**
+   * Whether the strength of the vibrations can be controlled, otherwise they are either on or off.
+   */
+  amplitudeControl: boolean
+  /**
+   * The longest continuous event or waveform segment that can be played, in milliseconds.
+   */
+  maxEventDuration: number | null
+  /**
+   * The primitives played natively, the others are approximated.
+   */
+  primitives: HapticPrimitiveKind[]
+}
+/**
+ * Modulates a parameter of the events over time.
+ */
+export type HapticCurve = {
+  parameter: HapticCurveParameter
+  points: HapticCurvePoint[]
+}
+export type HapticCurveParameter = 'intensity' | 'sharpness'
+export type HapticCurvePoint = {
+  /**
+   * The time from the start of the pattern, in milliseconds.
+   */
+  time: number
+  /**
+   * The multiplier applied to the parameter, between 0 and 1.
+   */
+  value: number
+}
+/**
+ * A desktop device able to play haptic feedback.
+ */
+export type HapticDevice = {
+  /**
+   * The identifier of the device, only stable while it stays connected.
+   */
+  id: string
+  name: string
+  kind: HapticDeviceKind
+  /**
+   * The intensity between 0 (disabled) and 1 applied to the feedback played on this device.
+   */
+  intensity: number
+}
+export type HapticDeviceKind = 'gamepad' | 'trackpad'
+export type HapticEvent = {
+  kind: HapticEventKind
+  /**
+   * The start of the event from the start of the pattern, in milliseconds.
+   */
+  time?: number
+  /**
+   * The duration of a continuous event, in milliseconds.
+   */
+  duration?: number
+  /**
+   * The strength between 0 and 1.
+   */
+  intensity: number
+  /**
+   * The sharpness between 0 (round) and 1 (crisp), only honored on iOS.
+   */
+  sharpness?: number
+}
+export type HapticEventKind =
+  /**
+   * A brief impulse, its duration is ignored.
+   */
+  | 'transient'
+  /**
+   * A sustained vibration.
+   */
+  | 'continuous'
+/**
+ * A custom haptic pattern.
+ *
+ * Each platform plays the representation it supports natively and approximates the others:
+ * Android turns events into a waveform, iOS turns waveforms and compositions into Core Haptics events.
+ */
+export type HapticPattern =
+  /**
+   * Consecutive segments with their duration in milliseconds and their amplitude between 0 (off) and 255,
+   * like Android's `VibrationEffect.createWaveform`.
+   */
+  | { type: 'waveform'; timings: number[]; amplitudes: number[] }
+  /**
+   * Transient and continuous events with optional parameter curves, like Core Haptics.
+   */
+  | { type: 'events'; events: HapticEvent[]; curves?: HapticCurve[] }
+  /**
+   * A sequence of haptic primitives, like Android's `VibrationEffect.Composition`.
+   */
+  | { type: 'composition'; primitives: HapticPrimitive[] }
+  /**
+   * An Apple Haptic and Audio Pattern (AHAP) JSON document. iOS only.
+   */
+  | { type: 'ahap'; data: string }
+export type HapticPrimitive = {
+  primitive: HapticPrimitiveKind
+  /**
+   * The strength between 0 and 1.
+   */
+  scale?: number
+  /**
+   * The delay after the previous primitive, in milliseconds.
+   */
+  delay?: number
+}
+export type HapticPrimitiveKind =
+  | 'click'
+  | 'thud'
+  | 'spin'
+  | 'quickRise'
+  | 'slowRise'
+  | 'quickFall'
+  | 'tick'
+  | 'lowTick'
 export type ImpactFeedbackStyle =
   | 'light'
   | 'medium'
@@ -10,9 +10,29 @@ export const {
   vibrate,
   impactFeedback,
   notificationFeedback,
-  selectionFeedback
+  selectionFeedback,
+  playPattern,
+  devices,
+  setDeviceIntensity,
+  capabilities,
+  intensity,
+  setIntensity
 } = commands
 
-export { ImpactFeedbackStyle, NotificationFeedbackType } from './bindings'
+export {
+  ImpactFeedbackStyle,
+  NotificationFeedbackType,
+  HapticPattern,
+  HapticCapabilities,
+  HapticDevice,
+  HapticDeviceKind,
+  HapticEvent,
+  HapticEventKind,
+  HapticCurve,
+  HapticCurveParameter,
+  HapticCurvePoint,
+  HapticPrimitive,
+  HapticPrimitiveKind
+} from './bindings'
 
 // export { events };
@@ -51,12 +51,91 @@ enum NotificationFeedbackType: String, Decodable {
   }
 }
 
+class IntensityOptions: Decodable {
+  let intensity: Float
+}
+
 class VibrateOptions: Decodable {
   // TODO: Array
   let duration: Double
 }
 
+enum HapticEventKind: String, Decodable {
+  case transient, continuous
+}
+
+class HapticEventArgs: Decodable {
+  let kind: HapticEventKind
+  let time: Double
+  let duration: Double
+  let intensity: Float
+  let sharpness: Float
+}
+
+enum HapticCurveParameter: String, Decodable {
+  case intensity, sharpness
+}
+
+class HapticCurvePointArgs: Decodable {
+  let time: Double
+  let value: Float
+}
+
+class HapticCurveArgs: Decodable {
+  let parameter: HapticCurveParameter
+  let points: [HapticCurvePointArgs]
+}
+
+enum HapticPrimitiveKind: String, Decodable {
+  case click, thud, spin, quickRise, slowRise, quickFall, tick, lowTick
+
+  // the duration in milliseconds (0 for a transient event), intensity and sharpness approximating the primitive,
+  // and whether its intensity ramps up (1) or down (-1)
+  func parameters() -> (duration: Double, intensity: Float, sharpness: Float, ramp: Float) {
+    switch self {
+    case .click:
+      return (0, 1, 0.9, 0)
+    case .thud:
+      return (0, 1, 0.1, 0)
+    case .tick:
+      return (0, 0.6, 1, 0)
+    case .lowTick:
+      return (0, 0.4, 0.3, 0)
+    case .spin:
+      return (100, 0.6, 0.5, 0)
+    case .quickRise:
+      return (80, 1, 0.5, 1)
+    case .slowRise:
+      return (200, 1, 0.5, 1)
+    case .quickFall:
+      return (80, 1, 0.5, -1)
+    }
+  }
+}
+
+class HapticPrimitiveArgs: Decodable {
+  let primitive: HapticPrimitiveKind
+  let scale: Float
+  let delay: Double
+}
+
+class PlayPatternOptions: Decodable {
+  let type: String
+  let timings: [Double]?
+  let amplitudes: [Int]?
+  let events: [HapticEventArgs]?
+  let curves: [HapticCurveArgs]?
+  let primitives: [HapticPrimitiveArgs]?
+  let data: String?
+}
+
 class HapticsPlugin: Plugin {
+  private var engine: CHHapticEngine?
+  // the global intensity multiplier, persisted by the Rust side
+  private var intensity: Float = 1
+  // the longest continuous event Core Haptics plays, in milliseconds
+  private let maxEventDuration = 30_000
+
   //
   // Tauri commands
   //
@@ -75,7 +154,7 @@ class HapticsPlugin: Plugin {
           }
         }
         // TODO: Make some of this (or all) configurable?
-        let intensity: CHHapticEventParameter = CHHapticEventParameter(parameterID: .hapticIntensity, value: 1.0)
+        let intensity: CHHapticEventParameter = CHHapticEventParameter(parameterID: .hapticIntensity, value: intensity)
         let sharpness: CHHapticEventParameter = CHHapticEventParameter(parameterID: .hapticSharpness, value: 1.0)
         let continuousEvent = CHHapticEvent(
           eventType: .hapticContinuous,
@@ -103,7 +182,7 @@ class HapticsPlugin: Plugin {
     let args = try invoke.parseArgs(ImpactFeedbackOptions.self)
     let generator = UIImpactFeedbackGenerator(style: args.style.into())
     generator.prepare()
-    generator.impactOccurred()
+    generator.impactOccurred(intensity: CGFloat(intensity))
 
     invoke.resolve()
   }
@@ -125,6 +204,159 @@ class HapticsPlugin: Plugin {
 
     invoke.resolve()
   }
+
+  @objc public func playPattern(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(PlayPatternOptions.self)
+    guard CHHapticEngine.capabilitiesForHardware().supportsHaptics else {
+      invoke.reject("The device does not support haptics")
+      return
+    }
+
+    let pattern: CHHapticPattern
+    if args.type == "ahap" {
+      let data = Data((args.data ?? "").utf8)
+      guard let dictionary = try JSONSerialization.jsonObject(with: data) as? [CHHapticPattern.Key: Any]
+      else {
+        invoke.reject("The AHAP document is not a JSON object")
+        return
+      }
+      pattern = try CHHapticPattern(dictionary: dictionary)
+    } else {
+      pattern = try makePattern(args)
+    }
+
+    let player = try hapticEngine().makePlayer(with: pattern)
+    try player.sendParameters(
+      [CHHapticDynamicParameter(parameterID: .hapticIntensityControl, value: intensity, relativeTime: 0)],
+      atTime: CHHapticTimeImmediate)
+    try player.start(atTime: CHHapticTimeImmediate)
+
+    invoke.resolve()
+  }
+
+  @objc public func capabilities(_ invoke: Invoke) throws {
+    let supported = CHHapticEngine.capabilitiesForHardware().supportsHaptics
+    invoke.resolve([
+      "supported": supported,
+      "amplitudeControl": supported,
+      "maxEventDuration": maxEventDuration,
+      // the primitives are approximated with Core Haptics events
+      "primitives": [String](),
+    ])
+  }
+
+  // the notification and selection feedback generators cannot be scaled, they are only muted at 0
+  @objc public func setIntensity(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(IntensityOptions.self)
+    intensity = args.intensity
+    invoke.resolve()
+  }
+
+  //
+  // Internal functions
+  //
+
+  // the engine is kept alive while playing and recreated after the system stops it
+  private func hapticEngine() throws -> CHHapticEngine {
+    if let engine = engine {
+      return engine
+    }
+    let engine = try CHHapticEngine()
+    engine.resetHandler = { [weak engine] in
+      try? engine?.start()
+    }
+    engine.stoppedHandler = { [weak self] _ in
+      self?.engine = nil
+    }
+    try engine.start()
+    self.engine = engine
+    return engine
+  }
+
+  private func hapticEvent(
+    _ type: CHHapticEvent.EventType, time: Double, duration: Double, intensity: Float,
+    sharpness: Float
+  ) -> CHHapticEvent {
+    return CHHapticEvent(
+      eventType: type,
+      parameters: [
+        CHHapticEventParameter(parameterID: .hapticIntensity, value: intensity),
+        CHHapticEventParameter(parameterID: .hapticSharpness, value: sharpness),
+      ],
+      relativeTime: time / 1000,
+      duration: duration / 1000
+    )
+  }
+
+  private func makePattern(_ args: PlayPatternOptions) throws -> CHHapticPattern {
+    var events: [CHHapticEvent] = []
+    var curves: [CHHapticParameterCurve] = []
+
+    switch args.type {
+    case "waveform":
+      var time = 0.0
+      for (duration, amplitude) in zip(args.timings ?? [], args.amplitudes ?? []) {
+        if amplitude > 0 {
+          events.append(
+            hapticEvent(
+              .hapticContinuous, time: time, duration: duration,
+              intensity: Float(amplitude) / 255, sharpness: 0.5))
+        }
+        time += duration
+      }
+    case "composition":
+      var time = 0.0
+      for primitive in args.primitives ?? [] {
+        time += primitive.delay
+        let (duration, intensity, sharpness, ramp) = primitive.primitive.parameters()
+        if duration == 0 {
+          events.append(
+            hapticEvent(
+              .hapticTransient, time: time, duration: 0, intensity: intensity * primitive.scale,
+              sharpness: sharpness))
+        } else {
+          events.append(
+            hapticEvent(
+              .hapticContinuous, time: time, duration: duration,
+              intensity: intensity * primitive.scale, sharpness: sharpness))
+          if ramp != 0 {
+            let from: Float = ramp > 0 ? 0 : 1
+            curves.append(
+              CHHapticParameterCurve(
+                parameterID: .hapticIntensityControl,
+                controlPoints: [
+                  CHHapticParameterCurve.ControlPoint(relativeTime: 0, value: from),
+                  CHHapticParameterCurve.ControlPoint(relativeTime: duration / 1000, value: 1 - from),
+                ],
+                relativeTime: time / 1000
+              ))
+          }
+        }
+        time += duration
+      }
+    default:
+      for event in args.events ?? [] {
+        events.append(
+          hapticEvent(
+            event.kind == .continuous ? .hapticContinuous : .hapticTransient, time: event.time,
+            duration: event.kind == .continuous ? event.duration : 0, intensity: event.intensity,
+            sharpness: event.sharpness))
+      }
+      for curve in args.curves ?? [] {
+        curves.append(
+          CHHapticParameterCurve(
+            parameterID: curve.parameter == .intensity
+              ? .hapticIntensityControl : .hapticSharpnessControl,
+            controlPoints: curve.points.map {
+              CHHapticParameterCurve.ControlPoint(relativeTime: $0.time / 1000, value: $0.value)
+            },
+            relativeTime: 0
+          ))
+      }
+    }
+
+    return try CHHapticPattern(events: events, parameterCurves: curves)
+  }
 }
 
 @_cdecl("init_plugin_haptics")
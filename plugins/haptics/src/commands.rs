@@ -4,7 +4,10 @@
 
 use tauri::{command, AppHandle, Runtime};
 
-use crate::{HapticsExt, ImpactFeedbackStyle, NotificationFeedbackType, Result};
+use crate::{
+    HapticCapabilities, HapticDevice, HapticPattern, HapticsExt, ImpactFeedbackStyle,
+    NotificationFeedbackType, Result,
+};
 
 #[command]
 #[specta::specta]
@@ -35,3 +38,46 @@ pub(crate) async fn notification_feedback<R: Runtime>(
 pub(crate) async fn selection_feedback<R: Runtime>(app: AppHandle<R>) -> Result<()> {
     app.haptics().selection_feedback()
 }
+
+#[command]
+#[specta::specta]
+pub(crate) async fn play_pattern<R: Runtime>(
+    app: AppHandle<R>,
+    pattern: HapticPattern,
+) -> Result<()> {
+    app.haptics().play_pattern(pattern)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn devices<R: Runtime>(app: AppHandle<R>) -> Result<Vec<HapticDevice>> {
+    app.haptics().devices()
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn set_device_intensity<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+    intensity: f32,
+) -> Result<()> {
+    app.haptics().set_device_intensity(&id, intensity)
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn capabilities<R: Runtime>(app: AppHandle<R>) -> Result<HapticCapabilities> {
+    app.haptics().capabilities()
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn intensity<R: Runtime>(app: AppHandle<R>) -> Result<f32> {
+    Ok(app.haptics().intensity())
+}
+
+#[command]
+#[specta::specta]
+pub(crate) async fn set_intensity<R: Runtime>(app: AppHandle<R>, intensity: f32) -> Result<()> {
+    app.haptics().set_intensity(intensity)
+}
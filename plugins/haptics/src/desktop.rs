@@ -2,35 +2,154 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use std::{collections::HashMap, sync::Mutex};
+
 use serde::de::DeserializeOwned;
 use tauri::{plugin::PluginApi, AppHandle, Runtime};
 
-use crate::models::*;
+use crate::{
+    intensity::{self, Intensity},
+    models::*,
+    waveform::{self, Feedback, Pulse},
+};
 
 pub fn init<R: Runtime, C: DeserializeOwned>(
     app: &AppHandle<R>,
     _api: PluginApi<R, C>,
 ) -> crate::Result<Haptics<R>> {
-    Ok(Haptics(app.clone()))
+    Ok(Haptics {
+        app: app.clone(),
+        intensity: Intensity::load(app),
+        intensities: Default::default(),
+        #[cfg(feature = "gamepad")]
+        gamepads: crate::gamepad::Gamepads::new(),
+    })
 }
 
 /// Access to the haptics APIs.
-pub struct Haptics<R: Runtime>(AppHandle<R>);
+///
+/// On desktop, the feedback is played on the connected game controllers with the `gamepad` feature,
+/// and on the Force Touch trackpads on macOS.
+pub struct Haptics<R: Runtime> {
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    app: AppHandle<R>,
+    intensity: Intensity,
+    /// The intensity of each device, 1 if unset.
+    intensities: Mutex<HashMap<String, f32>>,
+    #[cfg(feature = "gamepad")]
+    gamepads: crate::gamepad::Gamepads,
+}
 
 impl<R: Runtime> Haptics<R> {
-    pub fn vibrate(&self, _duration: u32) -> crate::Result<()> {
-        Ok(())
+    pub fn vibrate(&self, duration: u32) -> crate::Result<()> {
+        self.play(waveform::vibrate(duration), Feedback::Generic)
     }
 
-    pub fn impact_feedback(&self, _style: ImpactFeedbackStyle) -> crate::Result<()> {
-        Ok(())
+    pub fn impact_feedback(&self, style: ImpactFeedbackStyle) -> crate::Result<()> {
+        self.play(waveform::impact(&style), Feedback::Generic)
     }
 
-    pub fn notification_feedback(&self, _type: NotificationFeedbackType) -> crate::Result<()> {
-        Ok(())
+    pub fn notification_feedback(&self, r#type: NotificationFeedbackType) -> crate::Result<()> {
+        self.play(waveform::notification(&r#type), Feedback::LevelChange)
     }
 
     pub fn selection_feedback(&self) -> crate::Result<()> {
+        self.play(waveform::selection(), Feedback::Alignment)
+    }
+
+    pub fn play_pattern(&self, pattern: HapticPattern) -> crate::Result<()> {
+        pattern.validate()?;
+        self.play(waveform::pattern(&pattern), Feedback::Generic)
+    }
+
+    /// Returns what the connected devices support.
+    pub fn capabilities(&self) -> crate::Result<HapticCapabilities> {
+        let devices = self.devices()?;
+        Ok(HapticCapabilities {
+            supported: !devices.is_empty(),
+            amplitude_control: devices
+                .iter()
+                .any(|device| device.kind == HapticDeviceKind::Gamepad),
+            max_event_duration: None,
+            primitives: Vec::new(),
+        })
+    }
+
+    /// Returns the global intensity multiplier applied to all the feedback.
+    pub fn intensity(&self) -> f32 {
+        self.intensity.get()
+    }
+
+    /// Sets the global intensity multiplier between 0 (disabled) and 1, persisted across restarts.
+    pub fn set_intensity(&self, intensity: f32) -> crate::Result<()> {
+        self.intensity.set(intensity)
+    }
+
+    /// Lists the connected devices able to play haptic feedback.
+    pub fn devices(&self) -> crate::Result<Vec<HapticDevice>> {
+        #[allow(unused_mut)]
+        let mut devices = Vec::new();
+
+        #[cfg(target_os = "macos")]
+        devices.push((
+            crate::trackpad::DEVICE_ID.to_string(),
+            "Trackpad".to_string(),
+            HapticDeviceKind::Trackpad,
+        ));
+        #[cfg(feature = "gamepad")]
+        devices.extend(
+            self.gamepads
+                .devices()
+                .into_iter()
+                .map(|(id, name)| (id, name, HapticDeviceKind::Gamepad)),
+        );
+
+        let intensities = self.intensities.lock().unwrap();
+        Ok(devices
+            .into_iter()
+            .map(|(id, name, kind)| HapticDevice {
+                intensity: intensities.get(&id).copied().unwrap_or(1.0),
+                id,
+                name,
+                kind,
+            })
+            .collect())
+    }
+
+    /// Sets the intensity of a device between 0 (disabled) and 1.
+    pub fn set_device_intensity(&self, id: &str, intensity: f32) -> crate::Result<()> {
+        intensity::validate(intensity)?;
+        if !self.devices()?.iter().any(|device| device.id == id) {
+            return Err(crate::Error::UnknownDevice(id.into()));
+        }
+        self.intensities
+            .lock()
+            .unwrap()
+            .insert(id.into(), intensity);
+        Ok(())
+    }
+
+    #[allow(unused_variables)]
+    fn play(&self, mut pulses: Vec<Pulse>, feedback: Feedback) -> crate::Result<()> {
+        let global = self.intensity.get();
+        if pulses.is_empty() || global <= 0.0 {
+            return Ok(());
+        }
+        for pulse in &mut pulses {
+            pulse.strength *= global;
+        }
+        let intensities = self.intensities.lock().unwrap().clone();
+
+        #[cfg(target_os = "macos")]
+        if intensities
+            .get(crate::trackpad::DEVICE_ID)
+            .map_or(true, |intensity| *intensity > 0.0)
+        {
+            crate::trackpad::perform(&self.app, &pulses, feedback);
+        }
+        #[cfg(feature = "gamepad")]
+        self.gamepads.play(pulses, intensities);
+
         Ok(())
     }
 }
@@ -11,6 +11,18 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, thiserror::Error, Type)]
 pub enum Error {
+    #[error("invalid haptic pattern: {0}")]
+    InvalidPattern(String),
+    #[error("intensity must be between 0 and 1, got {0}")]
+    InvalidIntensity(f32),
+    #[error("unknown haptic device: {0}")]
+    UnknownDevice(String),
+    #[error(transparent)]
+    Io(
+        #[serde(skip)]
+        #[from]
+        std::io::Error,
+    ),
     #[cfg(mobile)]
     #[error(transparent)]
     PluginInvoke(
@@ -0,0 +1,141 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Rumble of the connected game controllers.
+
+use std::{
+    collections::HashMap,
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
+};
+
+use gilrs::{
+    ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks},
+    GamepadId, Gilrs,
+};
+
+use crate::waveform::{self, Pulse};
+
+/// How often the controller connections are refreshed while idle.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+enum Request {
+    Devices(Sender<Vec<(String, String)>>),
+    Play {
+        pulses: Vec<Pulse>,
+        intensities: HashMap<String, f32>,
+    },
+}
+
+pub(crate) fn device_id(id: GamepadId) -> String {
+    format!("gamepad:{}", usize::from(id))
+}
+
+/// Owns the gilrs context on its own thread.
+pub(crate) struct Gamepads {
+    tx: Sender<Request>,
+}
+
+impl Gamepads {
+    pub(crate) fn new() -> Self {
+        let (tx, rx) = channel();
+
+        std::thread::spawn(move || {
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                // the platform is not supported, a dummy context without gamepads is returned
+                Err(gilrs::Error::NotImplemented(gilrs)) => gilrs,
+                Err(e) => {
+                    log::warn!("failed to initialize the game controllers: {e}");
+                    return;
+                }
+            };
+            // effects stop when dropped, keep them until they are done
+            let mut playing: Vec<(Instant, Effect)> = Vec::new();
+
+            loop {
+                while gilrs.next_event().is_some() {}
+
+                match rx.recv_timeout(POLL_INTERVAL) {
+                    Ok(Request::Devices(reply)) => {
+                        let devices = gilrs
+                            .gamepads()
+                            .filter(|(_, gamepad)| gamepad.is_ff_supported())
+                            .map(|(id, gamepad)| (device_id(id), gamepad.name().to_string()))
+                            .collect();
+                        let _ = reply.send(devices);
+                    }
+                    Ok(Request::Play {
+                        pulses,
+                        intensities,
+                    }) => {
+                        let total = waveform::duration(&pulses);
+                        let targets = gilrs
+                            .gamepads()
+                            .filter(|(_, gamepad)| gamepad.is_ff_supported())
+                            .map(|(id, _)| id)
+                            .collect::<Vec<_>>();
+                        for id in targets {
+                            let intensity = intensities.get(&device_id(id)).copied().unwrap_or(1.0);
+                            if intensity <= 0.0 {
+                                continue;
+                            }
+
+                            let mut builder = EffectBuilder::new();
+                            for pulse in &pulses {
+                                let magnitude =
+                                    (pulse.strength * intensity).clamp(0.0, 1.0) * u16::MAX as f32;
+                                builder.add_effect(BaseEffect {
+                                    kind: BaseEffectType::Strong {
+                                        magnitude: magnitude as u16,
+                                    },
+                                    scheduling: Replay {
+                                        after: Ticks::from_ms(pulse.start),
+                                        play_for: Ticks::from_ms(pulse.duration),
+                                        with_delay: Ticks::from_ms(0),
+                                    },
+                                    envelope: Default::default(),
+                                });
+                            }
+                            let effect = builder
+                                .repeat(Repeat::For(Ticks::from_ms(total)))
+                                .gamepads(&[id])
+                                .finish(&mut gilrs);
+                            match effect.and_then(|effect| effect.play().map(|_| effect)) {
+                                Ok(effect) => playing.push((
+                                    Instant::now() + Duration::from_millis(total.into()),
+                                    effect,
+                                )),
+                                Err(e) => log::warn!("failed to play the rumble effect: {e}"),
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let now = Instant::now();
+                playing.retain(|(end, _)| *end > now);
+            }
+        });
+
+        Self { tx }
+    }
+
+    /// Returns the ids and names of the controllers supporting force feedback.
+    pub(crate) fn devices(&self) -> Vec<(String, String)> {
+        let (tx, rx) = channel();
+        if self.tx.send(Request::Devices(tx)).is_err() {
+            return Vec::new();
+        }
+        rx.recv().unwrap_or_default()
+    }
+
+    pub(crate) fn play(&self, pulses: Vec<Pulse>, intensities: HashMap<String, f32>) {
+        let _ = self.tx.send(Request::Play {
+            pulses,
+            intensities,
+        });
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The global intensity multiplier, persisted across restarts.
+
+use std::{fs::create_dir_all, path::PathBuf, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// The file storing the settings in the app config directory.
+const SETTINGS_FILENAME: &str = ".haptics.json";
+
+#[derive(Serialize, Deserialize)]
+struct Settings {
+    intensity: f32,
+}
+
+pub(crate) fn validate(intensity: f32) -> crate::Result<()> {
+    if (0.0..=1.0).contains(&intensity) {
+        Ok(())
+    } else {
+        Err(crate::Error::InvalidIntensity(intensity))
+    }
+}
+
+pub(crate) struct Intensity {
+    path: Option<PathBuf>,
+    value: Mutex<f32>,
+}
+
+impl Intensity {
+    /// Reads the persisted intensity, 1 if it was never set.
+    pub(crate) fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = app
+            .path()
+            .app_config_dir()
+            .ok()
+            .map(|dir| dir.join(SETTINGS_FILENAME));
+        let value = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice::<Settings>(&bytes).ok())
+            .map(|settings| settings.intensity)
+            .filter(|intensity| validate(*intensity).is_ok())
+            .unwrap_or(1.0);
+
+        Self {
+            path,
+            value: Mutex::new(value),
+        }
+    }
+
+    pub(crate) fn get(&self) -> f32 {
+        *self.value.lock().unwrap()
+    }
+
+    pub(crate) fn set(&self, intensity: f32) -> crate::Result<()> {
+        validate(intensity)?;
+
+        let mut value = self.value.lock().unwrap();
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                create_dir_all(dir)?;
+            }
+            let bytes =
+                serde_json::to_vec(&Settings { intensity }).map_err(std::io::Error::from)?;
+            std::fs::write(path, bytes)?;
+        }
+        *value = intensity;
+        Ok(())
+    }
+}
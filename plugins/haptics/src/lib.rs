@@ -13,11 +13,18 @@ pub use models::*;
 
 #[cfg(desktop)]
 mod desktop;
+#[cfg(all(desktop, feature = "gamepad"))]
+mod gamepad;
 #[cfg(mobile)]
 mod mobile;
+#[cfg(target_os = "macos")]
+mod trackpad;
+#[cfg(desktop)]
+mod waveform;
 
 mod commands;
 mod error;
+mod intensity;
 mod models;
 
 pub use error::{Error, Result};
@@ -34,7 +41,13 @@ use mobile::Haptics;
                 commands::vibrate,
                 commands::impact_feedback,
                 commands::notification_feedback,
-                commands::selection_feedback
+                commands::selection_feedback,
+                commands::play_pattern,
+                commands::devices,
+                commands::set_device_intensity,
+                commands::capabilities,
+                commands::intensity,
+                commands::set_intensity
             ])
             .header("// @ts-nocheck")
             .config(
@@ -65,7 +78,13 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::vibrate,
             commands::impact_feedback,
             commands::notification_feedback,
-            commands::selection_feedback
+            commands::selection_feedback,
+            commands::play_pattern,
+            commands::devices,
+            commands::set_device_intensity,
+            commands::capabilities,
+            commands::intensity,
+            commands::set_intensity
         ])
         .setup(|app, api| {
             #[cfg(mobile)]
@@ -8,7 +8,7 @@ use tauri::{
     AppHandle, Runtime,
 };
 
-use crate::models::*;
+use crate::{intensity::Intensity, models::*};
 
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "app.tauri.haptics";
@@ -18,34 +18,59 @@ tauri::ios_plugin_binding!(init_plugin_haptics);
 
 // initializes the Kotlin or Swift plugin classes
 pub fn init<R: Runtime, C: DeserializeOwned>(
-    _app: &AppHandle<R>,
+    app: &AppHandle<R>,
     api: PluginApi<R, C>,
 ) -> crate::Result<Haptics<R>> {
     #[cfg(target_os = "android")]
     let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "HapticsPlugin")?;
     #[cfg(target_os = "ios")]
     let handle = api.register_ios_plugin(init_plugin_haptics)?;
-    Ok(Haptics(handle))
+
+    let intensity = Intensity::load(app);
+    if intensity.get() != 1.0 {
+        if let Err(e) = handle.run_mobile_plugin::<()>(
+            "setIntensity",
+            IntensityPayload {
+                intensity: intensity.get(),
+            },
+        ) {
+            log::warn!("failed to restore the haptics intensity: {e}");
+        }
+    }
+
+    Ok(Haptics { handle, intensity })
 }
 
 /// Access to the haptics APIs.
-pub struct Haptics<R: Runtime>(PluginHandle<R>);
+pub struct Haptics<R: Runtime> {
+    handle: PluginHandle<R>,
+    intensity: Intensity,
+}
 
 impl<R: Runtime> Haptics<R> {
     pub fn vibrate(&self, duration: u32) -> crate::Result<()> {
-        self.0
+        if self.muted() {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin("vibrate", VibratePayload { duration })
             .map_err(Into::into)
     }
 
     pub fn impact_feedback(&self, style: ImpactFeedbackStyle) -> crate::Result<()> {
-        self.0
+        if self.muted() {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin("impactFeedback", ImpactFeedbackPayload { style })
             .map_err(Into::into)
     }
 
     pub fn notification_feedback(&self, r#type: NotificationFeedbackType) -> crate::Result<()> {
-        self.0
+        if self.muted() {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin(
                 "notificationFeedback",
                 NotificationFeedbackPayload { r#type },
@@ -54,10 +79,56 @@ impl<R: Runtime> Haptics<R> {
     }
 
     pub fn selection_feedback(&self) -> crate::Result<()> {
-        self.0
+        if self.muted() {
+            return Ok(());
+        }
+        self.handle
             .run_mobile_plugin("selectionFeedback", ())
             .map_err(Into::into)
     }
+
+    pub fn play_pattern(&self, pattern: HapticPattern) -> crate::Result<()> {
+        pattern.validate()?;
+        if self.muted() {
+            return Ok(());
+        }
+        self.handle
+            .run_mobile_plugin("playPattern", pattern)
+            .map_err(Into::into)
+    }
+
+    /// Returns what the haptics hardware of the device supports.
+    pub fn capabilities(&self) -> crate::Result<HapticCapabilities> {
+        self.handle
+            .run_mobile_plugin("capabilities", ())
+            .map_err(Into::into)
+    }
+
+    /// Returns the global intensity multiplier applied to all the feedback.
+    pub fn intensity(&self) -> f32 {
+        self.intensity.get()
+    }
+
+    /// Sets the global intensity multiplier between 0 (disabled) and 1, persisted across restarts.
+    pub fn set_intensity(&self, intensity: f32) -> crate::Result<()> {
+        crate::intensity::validate(intensity)?;
+        self.handle
+            .run_mobile_plugin::<()>("setIntensity", IntensityPayload { intensity })?;
+        self.intensity.set(intensity)
+    }
+
+    /// Lists the connected desktop devices, always empty on mobile where the device itself vibrates.
+    pub fn devices(&self) -> crate::Result<Vec<HapticDevice>> {
+        Ok(Vec::new())
+    }
+
+    pub fn set_device_intensity(&self, id: &str, _intensity: f32) -> crate::Result<()> {
+        Err(crate::Error::UnknownDevice(id.into()))
+    }
+
+    fn muted(&self) -> bool {
+        self.intensity.get() <= 0.0
+    }
 }
 
 #[derive(Serialize)]
@@ -74,3 +145,8 @@ struct ImpactFeedbackPayload {
 struct NotificationFeedbackPayload {
     r#type: NotificationFeedbackType,
 }
+
+#[derive(Serialize)]
+struct IntensityPayload {
+    intensity: f32,
+}
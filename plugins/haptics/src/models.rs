@@ -32,3 +32,209 @@ pub enum NotificationFeedbackType {
     Warning,
     Error,
 }
+
+/// A custom haptic pattern.
+///
+/// Each platform plays the representation it supports natively and approximates the others:
+/// Android turns events into a waveform, iOS turns waveforms and compositions into Core Haptics events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HapticPattern {
+    /// Consecutive segments with their duration in milliseconds and their amplitude between 0 (off) and 255,
+    /// like Android's `VibrationEffect.createWaveform`.
+    #[serde(rename_all = "camelCase")]
+    Waveform {
+        timings: Vec<u32>,
+        amplitudes: Vec<u8>,
+    },
+    /// Transient and continuous events with optional parameter curves, like Core Haptics.
+    #[serde(rename_all = "camelCase")]
+    Events {
+        events: Vec<HapticEvent>,
+        #[serde(default)]
+        curves: Vec<HapticCurve>,
+    },
+    /// A sequence of haptic primitives, like Android's `VibrationEffect.Composition`.
+    #[serde(rename_all = "camelCase")]
+    Composition { primitives: Vec<HapticPrimitive> },
+    /// An Apple Haptic and Audio Pattern (AHAP) JSON document. iOS only.
+    #[serde(rename_all = "camelCase")]
+    Ahap { data: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HapticEventKind {
+    /// A brief impulse, its duration is ignored.
+    Transient,
+    /// A sustained vibration.
+    Continuous,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticEvent {
+    pub kind: HapticEventKind,
+    /// The start of the event from the start of the pattern, in milliseconds.
+    #[serde(default)]
+    pub time: u32,
+    /// The duration of a continuous event, in milliseconds.
+    #[serde(default)]
+    pub duration: u32,
+    /// The strength between 0 and 1.
+    pub intensity: f32,
+    /// The sharpness between 0 (round) and 1 (crisp), only honored on iOS.
+    #[serde(default = "default_sharpness")]
+    pub sharpness: f32,
+}
+
+fn default_sharpness() -> f32 {
+    0.5
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HapticCurveParameter {
+    Intensity,
+    Sharpness,
+}
+
+/// Modulates a parameter of the events over time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticCurve {
+    pub parameter: HapticCurveParameter,
+    pub points: Vec<HapticCurvePoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticCurvePoint {
+    /// The time from the start of the pattern, in milliseconds.
+    pub time: u32,
+    /// The multiplier applied to the parameter, between 0 and 1.
+    pub value: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HapticPrimitiveKind {
+    Click,
+    Thud,
+    Spin,
+    QuickRise,
+    SlowRise,
+    QuickFall,
+    Tick,
+    LowTick,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticPrimitive {
+    pub primitive: HapticPrimitiveKind,
+    /// The strength between 0 and 1.
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// The delay after the previous primitive, in milliseconds.
+    #[serde(default)]
+    pub delay: u32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl HapticPattern {
+    /// Checks that the pattern is well formed before sending it to the platform.
+    pub fn validate(&self) -> crate::Result<()> {
+        let unit = |value: f32, name: &str| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(())
+            } else {
+                Err(crate::Error::InvalidPattern(format!(
+                    "{name} must be between 0 and 1, got {value}"
+                )))
+            }
+        };
+
+        match self {
+            Self::Waveform {
+                timings,
+                amplitudes,
+            } => {
+                if timings.is_empty() || timings.len() != amplitudes.len() {
+                    return Err(crate::Error::InvalidPattern(
+                        "a waveform needs as many amplitudes as timings".into(),
+                    ));
+                }
+            }
+            Self::Events { events, curves } => {
+                if events.is_empty() {
+                    return Err(crate::Error::InvalidPattern(
+                        "the pattern has no events".into(),
+                    ));
+                }
+                for event in events {
+                    unit(event.intensity, "intensity")?;
+                    unit(event.sharpness, "sharpness")?;
+                }
+                for point in curves.iter().flat_map(|curve| &curve.points) {
+                    unit(point.value, "curve value")?;
+                }
+            }
+            Self::Composition { primitives } => {
+                if primitives.is_empty() {
+                    return Err(crate::Error::InvalidPattern(
+                        "the composition has no primitives".into(),
+                    ));
+                }
+                for primitive in primitives {
+                    unit(primitive.scale, "scale")?;
+                }
+            }
+            Self::Ahap { data } => {
+                if data.trim().is_empty() {
+                    return Err(crate::Error::InvalidPattern(
+                        "the AHAP data is empty".into(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HapticDeviceKind {
+    Gamepad,
+    Trackpad,
+}
+
+/// A desktop device able to play haptic feedback.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticDevice {
+    /// The identifier of the device, only stable while it stays connected.
+    pub id: String,
+    pub name: String,
+    pub kind: HapticDeviceKind,
+    /// The intensity between 0 (disabled) and 1 applied to the feedback played on this device.
+    pub intensity: f32,
+}
+
+/// What the haptics hardware of the device supports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HapticCapabilities {
+    /// Whether the device can play haptic feedback at all.
+    pub supported: bool,
+    /// Whether the strength of the vibrations can be controlled, otherwise they are either on or off.
+    pub amplitude_control: bool,
+    /// The longest continuous event or waveform segment that can be played, in milliseconds.
+    pub max_event_duration: Option<u32>,
+    /// The primitives played natively, the others are approximated.
+    pub primitives: Vec<HapticPrimitiveKind>,
+}
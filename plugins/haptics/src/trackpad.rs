@@ -0,0 +1,43 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Feedback of the Force Touch trackpads on macOS.
+
+use std::time::Duration;
+
+use objc2_app_kit::{
+    NSHapticFeedbackManager, NSHapticFeedbackPattern, NSHapticFeedbackPerformanceTime,
+};
+use tauri::{AppHandle, Runtime};
+
+use crate::waveform::{Feedback, Pulse};
+
+pub(crate) const DEVICE_ID: &str = "trackpad";
+
+/// Performs the feedback at the start of each pulse, the trackpad cannot vary its strength or duration.
+pub(crate) fn perform<R: Runtime>(app: &AppHandle<R>, pulses: &[Pulse], feedback: Feedback) {
+    let pattern = match feedback {
+        Feedback::Generic => NSHapticFeedbackPattern::Generic,
+        Feedback::Alignment => NSHapticFeedbackPattern::Alignment,
+        Feedback::LevelChange => NSHapticFeedbackPattern::LevelChange,
+    };
+    let mut starts = pulses.iter().map(|pulse| pulse.start).collect::<Vec<_>>();
+    starts.sort_unstable();
+    starts.dedup();
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut elapsed = 0;
+        for start in starts {
+            std::thread::sleep(Duration::from_millis((start - elapsed).into()));
+            elapsed = start;
+            let _ = app.run_on_main_thread(move || unsafe {
+                NSHapticFeedbackManager::defaultPerformer().performFeedbackPattern_performanceTime(
+                    pattern,
+                    NSHapticFeedbackPerformanceTime::Now,
+                );
+            });
+        }
+    });
+}
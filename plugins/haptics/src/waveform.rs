@@ -0,0 +1,140 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Approximates the feedback types and patterns as vibration pulses for the desktop devices.
+
+use crate::models::*;
+
+/// The duration of the pulse played for a transient event.
+const TRANSIENT_DURATION: u32 = 20;
+
+/// A vibration of a desktop device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Pulse {
+    /// The start from the beginning of the feedback, in milliseconds.
+    pub start: u32,
+    pub duration: u32,
+    /// The strength between 0 and 1.
+    pub strength: f32,
+}
+
+impl Pulse {
+    fn new(start: u32, duration: u32, strength: f32) -> Self {
+        Self {
+            start,
+            duration,
+            strength,
+        }
+    }
+}
+
+/// The kind of feedback, for the devices that cannot play arbitrary vibrations such as trackpads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Feedback {
+    Generic,
+    Alignment,
+    LevelChange,
+}
+
+/// Returns the end of the last pulse, in milliseconds.
+pub(crate) fn duration(pulses: &[Pulse]) -> u32 {
+    pulses
+        .iter()
+        .map(|pulse| pulse.start + pulse.duration)
+        .max()
+        .unwrap_or_default()
+}
+
+pub(crate) fn vibrate(duration: u32) -> Vec<Pulse> {
+    vec![Pulse::new(0, duration, 1.0)]
+}
+
+pub(crate) fn impact(style: &ImpactFeedbackStyle) -> Vec<Pulse> {
+    let (duration, strength) = match style {
+        ImpactFeedbackStyle::Light => (40, 0.3),
+        ImpactFeedbackStyle::Medium => (50, 0.5),
+        ImpactFeedbackStyle::Heavy => (60, 0.8),
+        ImpactFeedbackStyle::Soft => (50, 0.3),
+        ImpactFeedbackStyle::Rigid => (40, 0.6),
+    };
+    vec![Pulse::new(0, duration, strength)]
+}
+
+pub(crate) fn notification(r#type: &NotificationFeedbackType) -> Vec<Pulse> {
+    match r#type {
+        NotificationFeedbackType::Success => {
+            vec![Pulse::new(0, 40, 0.5), Pulse::new(140, 40, 0.6)]
+        }
+        NotificationFeedbackType::Warning => {
+            vec![Pulse::new(0, 40, 0.4), Pulse::new(160, 60, 0.6)]
+        }
+        NotificationFeedbackType::Error => vec![
+            Pulse::new(0, 60, 0.5),
+            Pulse::new(160, 40, 0.4),
+            Pulse::new(280, 50, 0.5),
+        ],
+    }
+}
+
+pub(crate) fn selection() -> Vec<Pulse> {
+    vec![Pulse::new(0, TRANSIENT_DURATION, 0.3)]
+}
+
+/// The duration in milliseconds and strength of the pulse approximating a primitive.
+fn primitive(kind: HapticPrimitiveKind) -> (u32, f32) {
+    match kind {
+        HapticPrimitiveKind::Click => (20, 1.0),
+        HapticPrimitiveKind::Thud => (50, 1.0),
+        HapticPrimitiveKind::Spin => (100, 0.6),
+        HapticPrimitiveKind::QuickRise => (80, 0.8),
+        HapticPrimitiveKind::SlowRise => (200, 0.8),
+        HapticPrimitiveKind::QuickFall => (80, 0.8),
+        HapticPrimitiveKind::Tick => (10, 0.6),
+        HapticPrimitiveKind::LowTick => (10, 0.3),
+    }
+}
+
+/// Converts a pattern to pulses, the parameter curves and AHAP documents are not supported.
+pub(crate) fn pattern(pattern: &HapticPattern) -> Vec<Pulse> {
+    match pattern {
+        HapticPattern::Waveform {
+            timings,
+            amplitudes,
+        } => {
+            let mut start = 0;
+            let mut pulses = Vec::new();
+            for (duration, amplitude) in timings.iter().zip(amplitudes) {
+                if *amplitude > 0 {
+                    pulses.push(Pulse::new(start, *duration, *amplitude as f32 / 255.0));
+                }
+                start += duration;
+            }
+            pulses
+        }
+        HapticPattern::Events { events, .. } => events
+            .iter()
+            .map(|event| {
+                let duration = match event.kind {
+                    HapticEventKind::Transient => TRANSIENT_DURATION,
+                    HapticEventKind::Continuous => event.duration,
+                };
+                Pulse::new(event.time, duration, event.intensity)
+            })
+            .collect(),
+        HapticPattern::Composition { primitives } => {
+            let mut start = 0;
+            primitives
+                .iter()
+                .map(|p| {
+                    let (duration, strength) = primitive(p.primitive);
+                    start += p.delay;
+                    let pulse = Pulse::new(start, duration, strength * p.scale);
+                    start += duration;
+                    pulse
+                })
+                .collect()
+        }
+        HapticPattern::Ahap { .. } => Vec::new(),
+    }
+}
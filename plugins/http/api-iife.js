@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_HTTP__=function(e){"use strict";async function t(e,t={},r){return window.__TAURI_INTERNALS__.invoke(e,t,r)}"function"==typeof SuppressedError&&SuppressedError;const r="Request canceled";return e.fetch=async function(e,n){const a=n?.signal;if(a?.aborted)throw new Error(r);const o=n?.maxRedirections,s=n?.connectTimeout,i=n?.proxy;n&&(delete n.maxRedirections,delete n.connectTimeout,delete n.proxy);const d=n?.headers?n.headers instanceof Headers?n.headers:new Headers(n.headers):new Headers,c=new Request(e,n),u=await c.arrayBuffer(),f=0!==u.byteLength?Array.from(new Uint8Array(u)):null;for(const[e,t]of c.headers)d.get(e)||d.set(e,t);const _=(d instanceof Headers?Array.from(d.entries()):Array.isArray(d)?d:Object.entries(d)).map((([e,t])=>[e,"string"==typeof t?t:t.toString()]));if(a?.aborted)throw new Error(r);const h=await t("plugin:http|fetch",{clientConfig:{method:c.method,url:c.url,headers:_,data:f,maxRedirections:o,connectTimeout:s,proxy:i}}),l=()=>t("plugin:http|fetch_cancel",{rid:h});if(a?.aborted)throw l(),new Error(r);a?.addEventListener("abort",(()=>{l()}));const{status:p,statusText:w,url:y,headers:T,rid:A}=await t("plugin:http|fetch_send",{rid:h}),g=await t("plugin:http|fetch_read_body",{rid:A}),R=new Response(g instanceof ArrayBuffer&&0!==g.byteLength?g:g instanceof Array&&g.length>0?new Uint8Array(g):null,{status:p,statusText:w});return Object.defineProperty(R,"url",{value:y}),Object.defineProperty(R,"headers",{value:new Headers(T)}),R},e}({});Object.defineProperty(window.__TAURI__,"http",{value:__TAURI_PLUGIN_HTTP__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_HTTP__=function(t){"use strict";function e(t,e,n,i){if("a"===n&&!i)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof e?t!==e||!i:!e.has(t))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===n?i:"a"===n?i.call(t):i?i.value:e.get(t)}function n(t,e,n,i,o){if("function"==typeof e?t!==e||!o:!e.has(t))throw new TypeError("Cannot write private member to an object whose class did not declare it");return e.set(t,n),n}var i,o,s;"function"==typeof SuppressedError&&SuppressedError;const r="__TAURI_TO_IPC_KEY__";class a{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,i.set(this,(()=>{})),o.set(this,0),s.set(this,{}),this.id=function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}((({message:t,id:r})=>{if(r===e(this,o,"f")){n(this,o,r+1),e(this,i,"f").call(this,t);const a=Object.keys(e(this,s,"f"));if(a.length>0){let t=r+1;for(const n of a.sort()){if(parseInt(n)!==t)break;{const o=e(this,s,"f")[n];delete e(this,s,"f")[n],e(this,i,"f").call(this,o),t+=1}}n(this,o,t)}}else e(this,s,"f")[r.toString()]=t}))}set onmessage(t){n(this,i,t)}get onmessage(){return e(this,i,"f")}[(i=new WeakMap,o=new WeakMap,s=new WeakMap,r)](){return`__CHANNEL__:${this.id}`}toJSON(){return this[r]()}}var d;async function c(t,e={},n){return window.__TAURI_INTERNALS__.invoke(t,e,n)}class h{get rid(){return e(this,d,"f")}constructor(t){d.set(this,void 0),n(this,d,t,"f")}async close(){return c("plugin:resources|close",{rid:this.rid})}}d=new WeakMap;class f extends h{}const p="Request canceled",m=[101,103,204,205,304];async function y(t,e){let n=null,i=null;const o=new a;o.onmessage=t=>{if("string"==typeof t)i?.error(new Error(t));else{const e=t instanceof ArrayBuffer?new Uint8Array(t):Uint8Array.from(t);0===e.byteLength?i?.close():i?.enqueue(e)}n?.(),n=null};const s=await c("plugin:http|fetch_read_body",{rid:t,onChunk:o}),r=new h(s);return e?.addEventListener("abort",(()=>{i?.error(new Error(p)),n?.(),n=null,r.close()})),new ReadableStream({start(t){i=t},async pull(){const t=new Promise((t=>{n=t}));await c("plugin:http|fetch_read_body_pull",{rid:s}),await t},async cancel(){await r.close()}})}function g(t){return"path"in t?{path:t.path instanceof URL?t.path.toString():t.path}:{data:t.data instanceof Uint8Array?Array.from(t.data):t.data}}function w(t){return"path"in t?{...t,path:t.path instanceof URL?t.path.toString():t.path}:"data"in t?{...t,data:t.data instanceof Uint8Array?Array.from(t.data):t.data}:t}function v(t){return{...t,rootCertificates:t.rootCertificates?.map(g),identity:t.identity&&{...t.identity,source:g(t.identity.source)}}}async function b(t){const e=await c("plugin:http|fetch_create_body_stream"),n=new h(e),i=t.getReader();return{rid:e,done:(async()=>{try{for(;;){const{done:t,value:o}=await i.read();if(t)break;await c("plugin:http|fetch_write_body",o,{headers:{rid:e.toString()}})}}catch(t){throw await i.cancel(t).catch((()=>{})),t}finally{await n.close().catch((()=>{}))}})()}}class E extends h{constructor(t){super(t),this.readyState=E.CONNECTING,this.onopen=null,this.onmessage=null,this.onerror=null,this.listeners=new Map}addEventListener(t,e){let n=this.listeners.get(t);n||(n=new Set,this.listeners.set(t,n)),n.add(e)}removeEventListener(t,e){this.listeners.get(t)?.delete(e)}async close(){this.readyState=E.CLOSED,await super.close()}_handle(t){if(this.readyState!==E.CLOSED)switch(t.type){case"open":this.readyState=E.OPEN,this.onopen?.();break;case"message":"message"===t.data.event&&this.onmessage?.(t.data),this.listeners.get(t.data.event)?.forEach((e=>e(t.data)));break;case"error":this.readyState=E.CONNECTING,this.onerror?.(t.data);break;case"closed":this.readyState=E.CLOSED}}}E.CONNECTING=0,E.OPEN=1,E.CLOSED=2;return t.Client=f,t.EventSource=E,t.clearCookies=async function(t){await c("plugin:http|clear_cookies",{jar:t?.jar})},t.clearHttpCache=async function(){await c("plugin:http|clear_cache")},t.createClient=async function(t){const e=await c("plugin:http|create_client",{options:t&&{...t,tls:t.tls&&v(t.tls)}});return new f(e)},t.eventSource=async function(t,e){let n=null;const i=new a;let o=[];i.onmessage=t=>{o?o.push(t):n?._handle(t)};const s=await c("plugin:http|event_source",{url:t.toString(),options:e&&{...e,headers:e.headers?Array.from(new Headers(e.headers).entries()):void 0},onEvent:i});return n=new E(s),setTimeout((()=>{o?.forEach((t=>n?._handle(t))),o=null})),n},t.fetch=async function(t,e){const n=e?.signal;if(n?.aborted)throw new Error(p);const i=e?.maxRedirections,o=e?.connectTimeout,s=e?.proxy,r=e?.bodyFile,l=e?.contentLength,u=e?.cookieJar,_=e?.retry,x=e?.tls,C=e?.multipart,T=e?.onUploadProgress,k=e?.client,A=e?.cache,R=e?.downloadTo instanceof URL?e.downloadTo.toString():e?.downloadTo,S=e?.resume,P=e?.onDownloadProgress;e&&(delete e.downloadTo,delete e.resume,delete e.onDownloadProgress,delete e.maxRedirections,delete e.connectTimeout,delete e.proxy,delete e.bodyFile,delete e.contentLength,delete e.cookieJar,delete e.retry,delete e.tls,delete e.multipart,delete e.onUploadProgress,delete e.client);const U=e?.headers?e.headers instanceof Headers?e.headers:new Headers(e.headers):new Headers,O=e?.body instanceof ReadableStream?e.body:null,D=new Request(t,O?{...e,body:null}:e),I=await D.arrayBuffer(),j=0!==I.byteLength?Array.from(new Uint8Array(I)):null;if(r&&(j||O))throw new TypeError("`bodyFile` cannot be used with `body`");const q=O?await b(O):null;for(const[t,e]of D.headers)U.get(t)||U.set(t,e);const F=(U instanceof Headers?Array.from(U.entries()):Array.isArray(U)?U:Object.entries(U)).map((([t,e])=>[t,"string"==typeof e?e:e.toString()]));if(n?.aborted)throw new Error(p);let L,H;T&&(L=new a,L.onmessage=T);try{H=await c("plugin:http|fetch",{clientConfig:{method:D.method,url:D.url,headers:F,data:j,dataStream:q?.rid,dataFile:r instanceof URL?r.toString():r,contentLength:O?l:void 0,resumeDownload:S?R:void 0,client:k?.rid,cache:A,cookieJar:u,retry:_,tls:x&&v(x),multipart:C?.map(w),maxRedirections:i,connectTimeout:o,proxy:s},onUploadProgress:L})}catch(t){throw q&&(q.done.catch((()=>{})),await new h(q.rid).close()),t}const M=()=>c("plugin:http|fetch_cancel",{rid:H});if(n?.aborted)throw M(),new Error(p);n?.addEventListener("abort",(()=>{M()})),q?.done.catch((()=>{M()}));const{status:N,statusText:W,url:B,headers:V,rid:z}=await c("plugin:http|fetch_send",{rid:H});let G=null,J=null;if(R){const t=new a;P&&(t.onmessage=P),J=await c("plugin:http|fetch_download",{rid:z,path:R,onProgress:t})}else m.includes(N)?await new h(z).close():G=await y(z,n);const K=new Response(G,{status:N,statusText:W});return Object.defineProperty(K,"url",{value:B}),Object.defineProperty(K,"headers",{value:new Headers(V)}),R&&Object.defineProperty(K,"path",{value:J}),K},t.getCookies=async function(t,e){return await c("plugin:http|get_cookies",{url:t.toString(),jar:e?.jar})},t.setCookie=async function(t,e,n){await c("plugin:http|set_cookie",{url:t.toString(),cookie:e,jar:n?.jar})},t}({});Object.defineProperty(window.__TAURI__,"http",{value:__TAURI_PLUGIN_HTTP__})}
\ No newline at end of file
@@ -6,7 +6,22 @@
 #[allow(dead_code)]
 mod scope;
 
-const COMMANDS: &[&str] = &["fetch", "fetch_cancel", "fetch_send", "fetch_read_body"];
+const COMMANDS: &[&str] = &[
+    "fetch",
+    "fetch_cancel",
+    "fetch_create_body_stream",
+    "fetch_write_body",
+    "fetch_send",
+    "fetch_read_body",
+    "fetch_read_body_pull",
+    "fetch_download",
+    "create_client",
+    "event_source",
+    "get_cookies",
+    "set_cookie",
+    "clear_cookies",
+    "clear_cache",
+];
 
 /// HTTP scope entry.
 #[derive(schemars::JsonSchema)]
@@ -26,11 +26,23 @@
  * @module
  */
 
-import { invoke } from '@tauri-apps/api/core'
+import { Channel, invoke, Resource } from '@tauri-apps/api/core'
+
+/**
+ * How requests are proxied without a custom proxy:
+ * - `system`: use the proxy of the system, read from the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`
+ * and `NO_PROXY` environment variables, the Windows registry and the macOS system configuration.
+ * - `none`: connect directly, ignoring the proxy of the system.
+ *
+ * @since 2.3.0
+ */
+export type ProxyMode = 'system' | 'none'
 
 /**
  * Configuration of a proxy that a Client should pass requests to.
  *
+ * SOCKS5 proxies, with `socks5://` or `socks5h://` URLs, require the `socks` feature of the Rust crate.
+ *
  * @since 2.0.0
  */
 export interface Proxy {
@@ -67,6 +79,104 @@ export interface ProxyConfig {
   noProxy?: string
 }
 
+/**
+ * Retry policy of a request, handled on the Rust side.
+ *
+ * Requests with a `ReadableStream` or file body are never retried,
+ * and note that retrying requests that are not idempotent, such as `POST`, may repeat their effects.
+ *
+ * @since 2.3.0
+ */
+export interface RetryOptions {
+  /** Maximum number of attempts, including the first one. Defaults to `3`. */
+  maxAttempts?: number
+  /** Exponential backoff between the attempts. */
+  backoff?: {
+    /** Delay in milliseconds before the second attempt. Defaults to `500`. */
+    initialDelay?: number
+    /** Maximum delay in milliseconds between two attempts. Defaults to `30000`. */
+    maxDelay?: number
+    /** Factor applied to the delay after each attempt. Defaults to `2`. */
+    multiplier?: number
+  }
+  /** Status codes of the responses that are retried. Defaults to `[408, 429, 500, 502, 503, 504]`. */
+  statusCodes?: number[]
+  /** Whether connection errors and timeouts are retried. Defaults to `true`. */
+  networkErrors?: boolean
+  /**
+   * Whether the delay of the `Retry-After` header is used instead of the backoff. Defaults to `true`.
+   * The response is returned without retrying if this delay exceeds `backoff.maxDelay`.
+   */
+  respectRetryAfter?: boolean
+}
+
+/**
+ * A certificate or key, either read from a path allowed on the `fs` plugin scope,
+ * or passed as bytes, for instance read from a Stronghold store.
+ *
+ * @since 2.3.0
+ */
+export type CertificateSource =
+  | { path: string | URL }
+  | { data: Uint8Array | number[] }
+
+/**
+ * A client identity used for mutual TLS:
+ * - `pkcs12`: a DER-encoded PKCS#12 archive, requires a `native-tls` feature of the Rust crate.
+ * - `pem`: a PEM file containing the certificate chain and the private key, requires a `rustls-tls` feature of the Rust crate.
+ *
+ * @since 2.3.0
+ */
+export type ClientIdentity =
+  | { format: 'pkcs12'; source: CertificateSource; password: string }
+  | { format: 'pem'; source: CertificateSource }
+
+/**
+ * TLS configuration of a request, added to the one of the plugin builder.
+ *
+ * @since 2.3.0
+ */
+export interface TlsOptions {
+  /** Additional trusted root certificates, PEM bundles or DER-encoded. */
+  rootCertificates?: CertificateSource[]
+  /** Client identity used for mutual TLS, replacing the one of the plugin builder. */
+  identity?: ClientIdentity
+  /** Whether the built-in root certificates are trusted. Defaults to `true`. */
+  builtInRootCertificates?: boolean
+}
+
+/**
+ * A part of a `multipart/form-data` body:
+ * - a file read from a path allowed on the `fs` plugin scope, streamed from disk,
+ * its `fileName` defaults to the name of the file.
+ * - bytes, sent as a file part.
+ * - a text field.
+ *
+ * @since 2.3.0
+ */
+export type MultipartPart =
+  | { name: string; path: string | URL; fileName?: string; mimeType?: string }
+  | {
+      name: string
+      data: Uint8Array | number[]
+      fileName?: string
+      mimeType?: string
+    }
+  | { name: string; value: string }
+
+/**
+ * Upload progress of a file part of a multipart body.
+ *
+ * @since 2.3.0
+ */
+export interface UploadProgress {
+  /** Index of the part in {@linkcode ClientOptions.multipart}. */
+  part: number
+  name: string
+  bytes: number
+  totalBytes: number
+}
+
 /**
  * Options to configure the Rust client used to make fetch requests
  *
@@ -81,13 +191,362 @@ export interface ClientOptions {
   /** Timeout in milliseconds */
   connectTimeout?: number
   /**
-   * Configuration of a proxy that a Client should pass requests to.
+   * Configuration of a proxy that a Client should pass requests to,
+   * or a {@linkcode ProxyMode}. Defaults to the proxy set on the plugin builder,
+   * which is the proxy of the system unless configured.
+   */
+  proxy?: Proxy | ProxyMode
+  /**
+   * Path of a file to stream as the request body, instead of `body`.
+   * The file is read on the Rust side, so it must be allowed on the `fs` plugin scope.
+   *
+   * @since 2.3.0
+   */
+  bodyFile?: string | URL
+  /**
+   * Length in bytes of a `ReadableStream` request body.
+   * If unknown, the body is sent with chunked transfer encoding.
+   *
+   * @since 2.3.0
+   */
+  contentLength?: number
+  /**
+   * Name of the cookie jar used instead of the default one,
+   * isolating the cookies of this client from the other requests.
+   *
+   * @since 2.3.0
+   */
+  cookieJar?: string
+  /**
+   * Retry the request on network errors and on some response status codes.
+   *
+   * @since 2.3.0
+   */
+  retry?: RetryOptions
+  /**
+   * Client identity and trusted root certificates, for private or enterprise APIs.
+   *
+   * @example
+   * ```typescript
+   * await fetch("https://internal.corp.example", {
+   *   tls: {
+   *     rootCertificates: [{ path: "/etc/corp/ca.pem" }],
+   *     identity: { format: "pem", source: { data: await stronghold.get("client-identity") } }
+   *   }
+   * });
+   * ```
+   *
+   * @since 2.3.0
+   */
+  tls?: TlsOptions
+  /**
+   * Parts of a `multipart/form-data` body, sent instead of `body`.
+   * Unlike a `FormData` body, the file parts are streamed from disk instead of being read by the webview.
+   * Requires the `multipart` feature of the Rust crate.
+   *
+   * @example
+   * ```typescript
+   * await fetch("https://my.upload.host/albums", {
+   *   method: "POST",
+   *   multipart: [
+   *     { name: "title", value: "Holidays" },
+   *     { name: "photo", path: "/home/user/Pictures/beach.jpg", mimeType: "image/jpeg" }
+   *   ],
+   *   onUploadProgress: ({ name, bytes, totalBytes }) => console.log(name, bytes / totalBytes)
+   * });
+   * ```
+   *
+   * @since 2.3.0
+   */
+  multipart?: MultipartPart[]
+  /**
+   * Called with the upload progress of the file parts of {@linkcode ClientOptions.multipart}.
+   *
+   * @since 2.3.0
+   */
+  onUploadProgress?: (progress: UploadProgress) => void
+  /**
+   * Client created with {@linkcode createClient} to send the request with, reusing its connections.
+   * `maxRedirections`, `connectTimeout`, `proxy`, `cookieJar` and `tls` are then set on the client instead.
+   *
+   * @since 2.3.0
+   */
+  client?: Client
+  /**
+   * How the request uses the response cache, which requires the `cache` feature of the Rust crate
+   * and to be enabled on the plugin builder.
+   *
+   * - `default`: fresh responses are served from the cache, stale ones are revalidated with their `ETag` or `Last-Modified` header.
+   * - `no-store`: the cache is neither read nor updated.
+   * - `force-cache`: cached responses are served even when stale, the server is only requested on a miss.
+   *
+   * @since 2.3.0
+   */
+  cache?: 'default' | 'no-store' | 'force-cache'
+}
+
+/**
+ * Connection pool and timeout settings of a {@linkcode Client}, durations are in milliseconds.
+ * The unset settings keep the ones of the plugin builder.
+ *
+ * @since 2.3.0
+ */
+export interface ClientSettings {
+  /** Maximum idle connections kept alive per host. */
+  poolMaxIdlePerHost?: number
+  /** How long an idle connection is kept alive in the pool. */
+  poolIdleTimeout?: number
+  connectTimeout?: number
+  /** Timeout of every read of the response, reset after each successful read. */
+  readTimeout?: number
+  /**
+   * Timeout of the whole request, from connecting until the response body is read.
+   * Writing the request has no timeout of its own, this one covers it.
    */
-  proxy?: Proxy
+  timeout?: number
+  /** Interval of the HTTP/2 keep-alive pings, requires the `http2` feature of the Rust crate. */
+  http2KeepAliveInterval?: number
+  /** How long to wait for the acknowledgement of a keep-alive ping before closing the connection. */
+  http2KeepAliveTimeout?: number
+  /** Whether the keep-alive pings are also sent on idle connections. */
+  http2KeepAliveWhileIdle?: boolean
+  /** Whether `TCP_NODELAY` is set on the sockets, `true` by default. */
+  tcpNodelay?: boolean
+  /** Interval of the TCP keep-alive probes. */
+  tcpKeepalive?: number
+}
+
+/**
+ * Options of {@linkcode createClient}.
+ *
+ * @since 2.3.0
+ */
+export interface CreateClientOptions extends ClientSettings {
+  /** See {@linkcode ClientOptions.maxRedirections}. */
+  maxRedirections?: number
+  /** See {@linkcode ClientOptions.proxy}. */
+  proxy?: Proxy | ProxyMode
+  /** See {@linkcode ClientOptions.cookieJar}. */
+  cookieJar?: string
+  /** See {@linkcode ClientOptions.tls}. */
+  tls?: TlsOptions
+}
+
+/**
+ * A client with its own connection pool, created with {@linkcode createClient}.
+ * Closing it drops its idle connections.
+ *
+ * @since 2.3.0
+ */
+export class Client extends Resource {}
+
+/**
+ * Creates a client whose connections are reused by the requests sent with it,
+ * see {@linkcode ClientOptions.client}.
+ *
+ * @example
+ * ```typescript
+ * const client = await createClient({ poolMaxIdlePerHost: 32, readTimeout: 30_000 });
+ * const response = await fetch("https://my.api.host/items", { client });
+ * // later
+ * await client.close();
+ * ```
+ *
+ * @since 2.3.0
+ */
+export async function createClient(
+  options?: CreateClientOptions
+): Promise<Client> {
+  const rid = await invoke<number>('plugin:http|create_client', {
+    options: options && {
+      ...options,
+      tls: options.tls && toTlsConfig(options.tls)
+    }
+  })
+  return new Client(rid)
+}
+
+/**
+ * Progress of a download started with {@linkcode DownloadOptions.downloadTo}.
+ *
+ * @since 2.3.0
+ */
+export interface DownloadProgress {
+  /** Bytes written to the file, including the ones of a resumed download. */
+  bytes: number
+  /** Complete size of the file, if known. */
+  totalBytes: number | null
+  /** Average download speed in bytes per second. */
+  speed: number
+}
+
+/**
+ * Options to stream the response body straight to a file instead of the webview.
+ *
+ * @since 2.3.0
+ */
+export interface DownloadOptions {
+  /**
+   * Path of the file the response body is written to.
+   * The file is written on the Rust side, so it must be allowed on the `fs` plugin scope.
+   */
+  downloadTo: string | URL
+  /**
+   * Resume the download of a partial file with a `Range` request, starting at its current length.
+   * The file is overwritten if the server doesn't answer with a partial content.
+   */
+  resume?: boolean
+  /** Called with the progress of the download. */
+  onDownloadProgress?: (progress: DownloadProgress) => void
+}
+
+/**
+ * The response of a download, its body is always `null`.
+ *
+ * @since 2.3.0
+ */
+export interface DownloadResponse extends Response {
+  /** The file the body was written to, or `null` if the response was not successful. */
+  path: string | null
 }
 
 const ERROR_REQUEST_CANCELLED = 'Request canceled'
 
+// https://fetch.spec.whatwg.org/#null-body-status
+const NULL_BODY_STATUSES = [101, 103, 204, 205, 304]
+
+/**
+ * Streams the body of the response with the given resource id,
+ * every chunk is only read from the network once the stream pulls it.
+ */
+async function readBody(
+  rid: number,
+  signal?: AbortSignal | null
+): Promise<ReadableStream<Uint8Array>> {
+  // resolves the pending `pull` once its chunk arrived
+  let onPulled: (() => void) | null = null
+  let controller: ReadableStreamDefaultController<Uint8Array> | null = null
+
+  const onChunk = new Channel<ArrayBuffer | number[] | string>()
+  onChunk.onmessage = (chunk) => {
+    if (typeof chunk === 'string') {
+      controller?.error(new Error(chunk))
+    } else {
+      const bytes =
+        chunk instanceof ArrayBuffer
+          ? new Uint8Array(chunk)
+          : Uint8Array.from(chunk)
+      // an empty chunk signals the end of the body
+      if (bytes.byteLength === 0) {
+        controller?.close()
+      } else {
+        controller?.enqueue(bytes)
+      }
+    }
+    onPulled?.()
+    onPulled = null
+  }
+
+  const streamRid = await invoke<number>('plugin:http|fetch_read_body', {
+    rid,
+    onChunk
+  })
+  const stream = new Resource(streamRid)
+
+  signal?.addEventListener('abort', () => {
+    controller?.error(new Error(ERROR_REQUEST_CANCELLED))
+    onPulled?.()
+    onPulled = null
+    void stream.close()
+  })
+
+  return new ReadableStream<Uint8Array>({
+    start(c) {
+      controller = c
+    },
+    async pull() {
+      const pulled = new Promise<void>((resolve) => {
+        onPulled = resolve
+      })
+      await invoke('plugin:http|fetch_read_body_pull', { rid: streamRid })
+      await pulled
+    },
+    async cancel() {
+      await stream.close()
+    }
+  })
+}
+
+function toCertificateSource(source: CertificateSource): CertificateSource {
+  if ('path' in source) {
+    return {
+      path: source.path instanceof URL ? source.path.toString() : source.path
+    }
+  }
+  return {
+    data: source.data instanceof Uint8Array ? Array.from(source.data) : source.data
+  }
+}
+
+function toMultipartPart(part: MultipartPart): MultipartPart {
+  if ('path' in part) {
+    return {
+      ...part,
+      path: part.path instanceof URL ? part.path.toString() : part.path
+    }
+  }
+  if ('data' in part) {
+    return {
+      ...part,
+      data: part.data instanceof Uint8Array ? Array.from(part.data) : part.data
+    }
+  }
+  return part
+}
+
+function toTlsConfig(tls: TlsOptions): TlsOptions {
+  return {
+    ...tls,
+    rootCertificates: tls.rootCertificates?.map(toCertificateSource),
+    identity: tls.identity && {
+      ...tls.identity,
+      source: toCertificateSource(tls.identity.source)
+    }
+  }
+}
+
+/**
+ * Creates a request body stream on the Rust side and starts writing the chunks of `stream` to it,
+ * every chunk waits for the request to send the previous ones.
+ */
+async function writeBody(
+  stream: ReadableStream<Uint8Array>
+): Promise<{ rid: number; done: Promise<void> }> {
+  const rid = await invoke<number>('plugin:http|fetch_create_body_stream')
+  const resource = new Resource(rid)
+  const reader = stream.getReader()
+
+  const done = (async () => {
+    try {
+      for (;;) {
+        const { done, value } = await reader.read()
+        if (done) break
+        await invoke('plugin:http|fetch_write_body', value, {
+          headers: { rid: rid.toString() }
+        })
+      }
+    } catch (e) {
+      await reader.cancel(e).catch(() => {})
+      throw e
+    } finally {
+      // closing the stream ends the request body
+      await resource.close().catch(() => {})
+    }
+  })()
+
+  return { rid, done }
+}
+
 /**
  * Fetch a resource from the network. It returns a `Promise` that resolves to the
  * `Response` to that `Request`, whether it is successful or not.
@@ -100,12 +559,44 @@ const ERROR_REQUEST_CANCELLED = 'Request canceled'
  * const jsonData = await response.json();
  * ```
  *
+ * Large request bodies can be streamed instead of being loaded in memory,
+ * either from a `ReadableStream` or from a file:
+ * ```typescript
+ * await fetch("http://my.upload.host/videos", {
+ *   method: "POST",
+ *   body: file.stream(),
+ *   contentLength: file.size
+ * });
+ * await fetch("http://my.upload.host/videos", {
+ *   method: "POST",
+ *   bodyFile: "/home/user/Videos/holidays.mp4"
+ * });
+ * ```
+ *
+ * The response body can also be written straight to a file, reporting the progress:
+ * ```typescript
+ * const response = await fetch("http://my.download.host/video.mp4", {
+ *   downloadTo: "/home/user/Videos/video.mp4",
+ *   resume: true,
+ *   onDownloadProgress: ({ bytes, totalBytes }) => console.log(bytes, totalBytes)
+ * });
+ * console.log(response.path);
+ * ```
+ *
  * @since 2.0.0
  */
+export async function fetch(
+  input: URL | Request | string,
+  init: RequestInit & ClientOptions & DownloadOptions
+): Promise<DownloadResponse>
 export async function fetch(
   input: URL | Request | string,
   init?: RequestInit & ClientOptions
-): Promise<Response> {
+): Promise<Response>
+export async function fetch(
+  input: URL | Request | string,
+  init?: RequestInit & ClientOptions & Partial<DownloadOptions>
+): Promise<Response | DownloadResponse> {
   // abort early here if needed
   const signal = init?.signal
   if (signal?.aborted) {
@@ -115,12 +606,38 @@ export async function fetch(
   const maxRedirections = init?.maxRedirections
   const connectTimeout = init?.connectTimeout
   const proxy = init?.proxy
+  const bodyFile = init?.bodyFile
+  const contentLength = init?.contentLength
+  const cookieJar = init?.cookieJar
+  const retry = init?.retry
+  const tls = init?.tls
+  const multipart = init?.multipart
+  const onUploadProgress = init?.onUploadProgress
+  const client = init?.client
+  const cache = init?.cache
+  const downloadTo =
+    init?.downloadTo instanceof URL
+      ? init.downloadTo.toString()
+      : init?.downloadTo
+  const resume = init?.resume
+  const onDownloadProgress = init?.onDownloadProgress
 
   // Remove these fields before creating the request
   if (init) {
+    delete init.downloadTo
+    delete init.resume
+    delete init.onDownloadProgress
     delete init.maxRedirections
     delete init.connectTimeout
     delete init.proxy
+    delete init.bodyFile
+    delete init.contentLength
+    delete init.cookieJar
+    delete init.retry
+    delete init.tls
+    delete init.multipart
+    delete init.onUploadProgress
+    delete init.client
   }
 
   const headers = init?.headers
@@ -129,11 +646,24 @@ export async function fetch(
       : new Headers(init.headers)
     : new Headers()
 
-  const req = new Request(input, init)
+  // stream bodies are written to the request after it is created,
+  // so they are never buffered in memory
+  const bodyStream =
+    init?.body instanceof ReadableStream
+      ? (init.body as ReadableStream<Uint8Array>)
+      : null
+
+  const req = new Request(input, bodyStream ? { ...init, body: null } : init)
   const buffer = await req.arrayBuffer()
   const data =
     buffer.byteLength !== 0 ? Array.from(new Uint8Array(buffer)) : null
 
+  if (bodyFile && (data || bodyStream)) {
+    throw new TypeError('`bodyFile` cannot be used with `body`')
+  }
+
+  const upload = bodyStream ? await writeBody(bodyStream) : null
+
   // append new headers created by the browser `Request` implementation,
   // if not already declared by the caller of this function
   for (const [key, value] of req.headers) {
@@ -164,17 +694,44 @@ export async function fetch(
     throw new Error(ERROR_REQUEST_CANCELLED)
   }
 
-  const rid = await invoke<number>('plugin:http|fetch', {
-    clientConfig: {
-      method: req.method,
-      url: req.url,
-      headers: mappedHeaders,
-      data,
-      maxRedirections,
-      connectTimeout,
-      proxy
+  let uploadProgress: Channel<UploadProgress> | undefined
+  if (onUploadProgress) {
+    uploadProgress = new Channel<UploadProgress>()
+    uploadProgress.onmessage = onUploadProgress
+  }
+
+  let rid: number
+  try {
+    rid = await invoke<number>('plugin:http|fetch', {
+      clientConfig: {
+        method: req.method,
+        url: req.url,
+        headers: mappedHeaders,
+        data,
+        dataStream: upload?.rid,
+        dataFile: bodyFile instanceof URL ? bodyFile.toString() : bodyFile,
+        contentLength: bodyStream ? contentLength : undefined,
+        resumeDownload: resume ? downloadTo : undefined,
+        client: client?.rid,
+        cache,
+        cookieJar,
+        retry,
+        tls: tls && toTlsConfig(tls),
+        multipart: multipart?.map(toMultipartPart),
+        maxRedirections,
+        connectTimeout,
+        proxy
+      },
+      onUploadProgress: uploadProgress
+    })
+  } catch (e) {
+    // the body stream was not taken by a request, closing it stops the writes
+    if (upload) {
+      upload.done.catch(() => {})
+      await new Resource(upload.rid).close()
     }
-  })
+    throw e
+  }
 
   const abort = () => invoke('plugin:http|fetch_cancel', { rid })
 
@@ -188,6 +745,9 @@ export async function fetch(
 
   signal?.addEventListener('abort', () => void abort())
 
+  // a failing body stream cancels the request
+  upload?.done.catch(() => void abort())
+
   interface FetchSendResponse {
     status: number
     statusText: string
@@ -206,24 +766,28 @@ export async function fetch(
     rid
   })
 
-  const body = await invoke<ArrayBuffer | number[]>(
-    'plugin:http|fetch_read_body',
-    {
-      rid: responseRid
-    }
-  )
+  let body: ReadableStream<Uint8Array> | null = null
+  let path: string | null = null
+  if (downloadTo) {
+    const onProgress = new Channel<DownloadProgress>()
+    if (onDownloadProgress) onProgress.onmessage = onDownloadProgress
+    path = await invoke<string | null>('plugin:http|fetch_download', {
+      rid: responseRid,
+      path: downloadTo,
+      onProgress
+    })
+  } else if (NULL_BODY_STATUSES.includes(status)) {
+    await new Resource(responseRid).close()
+  } else {
+    body = await readBody(responseRid, signal)
+  }
 
-  const res = new Response(
-    body instanceof ArrayBuffer && body.byteLength !== 0
-      ? body
-      : body instanceof Array && body.length > 0
-        ? new Uint8Array(body)
-        : null,
-    {
-      status,
-      statusText
-    }
-  )
+  // the body is streamed as it is consumed,
+  // so large or never ending responses are not buffered in memory
+  const res = new Response(body, {
+    status,
+    statusText
+  })
 
   // url and headers are read only properties
   // but seems like we can set them like this
@@ -235,6 +799,266 @@ export async function fetch(
   Object.defineProperty(res, 'headers', {
     value: new Headers(responseHeaders)
   })
+  if (downloadTo) {
+    Object.defineProperty(res, 'path', { value: path })
+  }
 
   return res
 }
+
+/**
+ * A cookie stored in a cookie jar.
+ *
+ * @since 2.3.0
+ */
+export interface Cookie {
+  name: string
+  value: string
+  domain: string | null
+  path: string | null
+  /** Expiration date in milliseconds since the Unix epoch, `null` for session cookies. */
+  expires: number | null
+  secure: boolean
+  httpOnly: boolean
+  sameSite: string | null
+}
+
+/**
+ * @since 2.3.0
+ */
+export interface CookieJarOptions {
+  /** Name of the cookie jar, the default jar is used if not set. */
+  jar?: string
+}
+
+/**
+ * Returns the cookies that would be sent with a request to `url`.
+ * The URL must be allowed on the scope of the `get-cookies` permission.
+ *
+ * @example
+ * ```typescript
+ * const cookies = await getCookies("https://my.api.host");
+ * ```
+ *
+ * @since 2.3.0
+ */
+export async function getCookies(
+  url: URL | string,
+  options?: CookieJarOptions
+): Promise<Cookie[]> {
+  return await invoke('plugin:http|get_cookies', {
+    url: url.toString(),
+    jar: options?.jar
+  })
+}
+
+/**
+ * Stores a cookie as if it was received in a `Set-Cookie` header of a response from `url`.
+ * The URL must be allowed on the scope of the `set-cookie` permission.
+ *
+ * @example
+ * ```typescript
+ * await setCookie("https://my.api.host", "session=abc; Path=/; Secure");
+ * ```
+ *
+ * @since 2.3.0
+ */
+export async function setCookie(
+  url: URL | string,
+  cookie: string,
+  options?: CookieJarOptions
+): Promise<void> {
+  await invoke('plugin:http|set_cookie', {
+    url: url.toString(),
+    cookie,
+    jar: options?.jar
+  })
+}
+
+/**
+ * Removes all the cookies of a cookie jar.
+ *
+ * @since 2.3.0
+ */
+export async function clearCookies(options?: CookieJarOptions): Promise<void> {
+  await invoke('plugin:http|clear_cookies', { jar: options?.jar })
+}
+
+/**
+ * Removes all the responses of the cache, from memory and disk.
+ * Requires the `cache` feature of the Rust crate.
+ *
+ * @since 2.3.0
+ */
+export async function clearHttpCache(): Promise<void> {
+  await invoke('plugin:http|clear_cache')
+}
+
+/**
+ * Options of {@linkcode eventSource}.
+ *
+ * @since 2.3.0
+ */
+export interface EventSourceOptions {
+  /** Headers sent with every connection. */
+  headers?: HeadersInit
+  /** Proxy of the connection, see {@linkcode ClientOptions.proxy}. */
+  proxy?: Proxy | ProxyMode
+  /** Name of the cookie jar used instead of the default one. */
+  cookieJar?: string
+  /** Delay in milliseconds before reconnecting, until the server sets one. Defaults to `3000`. */
+  reconnectionTime?: number
+  /** Sent as the `Last-Event-ID` header of the first connection, to resume a previous stream. */
+  lastEventId?: string
+}
+
+/**
+ * An event received from a {@linkcode EventSource}.
+ *
+ * @since 2.3.0
+ */
+export interface ServerSentEvent {
+  /** The event type, `message` unless set by the server. */
+  event: string
+  data: string
+  lastEventId: string
+}
+
+type EventSourceEvent =
+  | { type: 'open' }
+  | { type: 'message'; data: ServerSentEvent }
+  | { type: 'error'; data: string }
+  | { type: 'closed' }
+
+/**
+ * A Server-Sent Events connection maintained on the Rust side,
+ * reconnecting with the `Last-Event-ID` header when it is lost.
+ *
+ * @since 2.3.0
+ */
+export class EventSource extends Resource {
+  static readonly CONNECTING = 0
+  static readonly OPEN = 1
+  static readonly CLOSED = 2
+
+  /** The state of the connection, one of `CONNECTING`, `OPEN` or `CLOSED`. */
+  readyState: number = EventSource.CONNECTING
+  /** Called when the connection is established. */
+  onopen: (() => void) | null = null
+  /** Called with the events of the `message` type. */
+  onmessage: ((event: ServerSentEvent) => void) | null = null
+  /** Called when the connection failed or was lost, check `readyState` to know if it is reconnecting. */
+  onerror: ((error: string) => void) | null = null
+
+  private readonly listeners = new Map<
+    string,
+    Set<(event: ServerSentEvent) => void>
+  >()
+
+  /** @ignore */
+  constructor(rid: number) {
+    super(rid)
+  }
+
+  /** Listens to the events of the given type. */
+  addEventListener(
+    type: string,
+    listener: (event: ServerSentEvent) => void
+  ): void {
+    let listeners = this.listeners.get(type)
+    if (!listeners) {
+      listeners = new Set()
+      this.listeners.set(type, listeners)
+    }
+    listeners.add(listener)
+  }
+
+  removeEventListener(
+    type: string,
+    listener: (event: ServerSentEvent) => void
+  ): void {
+    this.listeners.get(type)?.delete(listener)
+  }
+
+  /** Closes the connection, it won't be reestablished. */
+  async close(): Promise<void> {
+    this.readyState = EventSource.CLOSED
+    await super.close()
+  }
+
+  /** @ignore */
+  _handle(event: EventSourceEvent): void {
+    if (this.readyState === EventSource.CLOSED) return
+
+    switch (event.type) {
+      case 'open':
+        this.readyState = EventSource.OPEN
+        this.onopen?.()
+        break
+      case 'message':
+        if (event.data.event === 'message') this.onmessage?.(event.data)
+        this.listeners.get(event.data.event)?.forEach((l) => l(event.data))
+        break
+      case 'error':
+        this.readyState = EventSource.CONNECTING
+        this.onerror?.(event.data)
+        break
+      case 'closed':
+        this.readyState = EventSource.CLOSED
+        break
+    }
+  }
+}
+
+/**
+ * Connects to a Server-Sent Events stream. The connection is maintained on the Rust side,
+ * parsing the `text/event-stream` body and reconnecting when it is lost.
+ * The URL must be allowed on the scope of the `event-source` permission.
+ *
+ * @example
+ * ```typescript
+ * const source = await eventSource("https://my.api.host/updates");
+ * source.onmessage = (event) => console.log(event.data);
+ * source.addEventListener("status", (event) => console.log(event.data));
+ * // later
+ * await source.close();
+ * ```
+ *
+ * @since 2.3.0
+ */
+export async function eventSource(
+  url: URL | string,
+  options?: EventSourceOptions
+): Promise<EventSource> {
+  // events received before the resource id is known are replayed
+  let source: EventSource | null = null
+
+  const onEvent = new Channel<EventSourceEvent>()
+  let pending: EventSourceEvent[] | null = []
+  onEvent.onmessage = (event) => {
+    if (pending) {
+      pending.push(event)
+    } else {
+      source?._handle(event)
+    }
+  }
+
+  const rid = await invoke<number>('plugin:http|event_source', {
+    url: url.toString(),
+    options: options && {
+      ...options,
+      headers: options.headers
+        ? Array.from(new Headers(options.headers).entries())
+        : undefined
+    },
+    onEvent
+  })
+
+  source = new EventSource(rid)
+  // the handlers are set after this function returns
+  setTimeout(() => {
+    pending?.forEach((event) => source?._handle(event))
+    pending = null
+  })
+  return source
+}
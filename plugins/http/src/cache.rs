@@ -0,0 +1,990 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Cache of the responses kept in memory and optionally on disk,
+//! following the semantics of a private cache of RFC 9111.
+//!
+//! See <https://www.rfc-editor.org/rfc/rfc9111>.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{command, State};
+use url::Url;
+
+use crate::{
+    retry::{send_with_retry, RetryConfig},
+    Http, Result,
+};
+
+/// Status codes whose responses can be stored without explicit freshness, see RFC 9110 section 15.1.
+const HEURISTICALLY_CACHEABLE: &[u16] = &[200, 203, 204, 300, 301, 308, 404, 405, 410, 414, 501];
+
+/// Request headers of a conditional or partial request, which bypasses the cache.
+const CONDITIONAL_HEADERS: &[HeaderName] = &[
+    header::IF_MATCH,
+    header::IF_NONE_MATCH,
+    header::IF_MODIFIED_SINCE,
+    header::IF_UNMODIFIED_SINCE,
+    header::IF_RANGE,
+    header::RANGE,
+];
+
+/// How a request uses the cache, see <https://fetch.spec.whatwg.org/#concept-request-cache-mode>.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheMode {
+    /// Fresh responses are served from the cache, stale ones are revalidated with the server.
+    #[default]
+    Default,
+    /// The cache is neither read nor updated.
+    NoStore,
+    /// Cached responses are served even when stale, the server is only requested on a miss.
+    ForceCache,
+}
+
+/// Settings of the response cache.
+#[derive(Debug, Clone)]
+pub struct CacheSettings {
+    /// Maximum total size in bytes of the response bodies kept in memory.
+    pub memory_capacity: u64,
+    /// Whether the responses are also persisted to disk, so they survive app restarts.
+    pub persist: bool,
+    /// Directory of the persisted responses, this enables [`Self::persist`].
+    ///
+    /// Defaults to `http-cache` in the app cache directory.
+    pub path: Option<PathBuf>,
+    /// Maximum total size in bytes of the persisted responses.
+    pub disk_capacity: u64,
+    /// Responses with a larger body are not cached.
+    pub max_entry_size: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            memory_capacity: 16 * 1024 * 1024,
+            persist: false,
+            path: None,
+            disk_capacity: 256 * 1024 * 1024,
+            max_entry_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// The directives of the `Cache-Control` header that are used by the cache.
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    public: bool,
+    max_age: Option<Duration>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut cache_control = Self::default();
+        for value in headers.get_all(header::CACHE_CONTROL) {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for directive in value.split(',') {
+                let (name, argument) = match directive.split_once('=') {
+                    Some((name, argument)) => {
+                        (name.trim(), Some(argument.trim().trim_matches('"')))
+                    }
+                    None => (directive.trim(), None),
+                };
+                match name.to_ascii_lowercase().as_str() {
+                    "no-store" => cache_control.no_store = true,
+                    // the qualified form is handled as the unqualified one
+                    "no-cache" => cache_control.no_cache = true,
+                    "public" => cache_control.public = true,
+                    // an invalid value makes the response stale
+                    "max-age" => {
+                        let seconds = argument.and_then(|a| a.parse().ok()).unwrap_or(0);
+                        cache_control.max_age.replace(Duration::from_secs(seconds));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        cache_control
+    }
+
+    /// The directives of a request, `Pragma: no-cache` is honored without `Cache-Control`.
+    fn parse_request(headers: &HeaderMap) -> Self {
+        let mut cache_control = Self::parse(headers);
+        if !headers.contains_key(header::CACHE_CONTROL) {
+            cache_control.no_cache = headers
+                .get_all(header::PRAGMA)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .any(|v| v.to_ascii_lowercase().contains("no-cache"));
+        }
+        cache_control
+    }
+}
+
+fn parse_date(headers: &HeaderMap, name: HeaderName) -> Option<SystemTime> {
+    httpdate::parse_http_date(headers.get(name)?.to_str().ok()?).ok()
+}
+
+/// A stored response.
+struct Entry {
+    url: Url,
+    status: StatusCode,
+    headers: HeaderMap,
+    /// The request headers named by `Vary`, with their values when the response was stored.
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    request_time: SystemTime,
+    response_time: SystemTime,
+    body: Vec<u8>,
+}
+
+impl Entry {
+    fn matches(&self, request_headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| request_headers.get(name) == value.as_ref())
+    }
+
+    fn date(&self) -> SystemTime {
+        parse_date(&self.headers, header::DATE).unwrap_or(self.response_time)
+    }
+
+    /// See RFC 9111 section 4.2.1.
+    fn freshness_lifetime(&self) -> Duration {
+        if let Some(max_age) = CacheControl::parse(&self.headers).max_age {
+            return max_age;
+        }
+        if self.headers.contains_key(header::EXPIRES) {
+            // an invalid date means the response is already expired
+            return parse_date(&self.headers, header::EXPIRES)
+                .and_then(|expires| expires.duration_since(self.date()).ok())
+                .unwrap_or_default();
+        }
+        if HEURISTICALLY_CACHEABLE.contains(&self.status.as_u16()) {
+            // 10% of the time since the last modification
+            if let Some(last_modified) = parse_date(&self.headers, header::LAST_MODIFIED) {
+                return self
+                    .date()
+                    .duration_since(last_modified)
+                    .unwrap_or_default()
+                    / 10;
+            }
+        }
+        Duration::ZERO
+    }
+
+    /// See RFC 9111 section 4.2.3.
+    fn current_age(&self) -> Duration {
+        let apparent_age = self
+            .response_time
+            .duration_since(self.date())
+            .unwrap_or_default();
+        let age_value = self
+            .headers
+            .get(header::AGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let response_delay = self
+            .response_time
+            .duration_since(self.request_time)
+            .unwrap_or_default();
+        let resident_time = SystemTime::now()
+            .duration_since(self.response_time)
+            .unwrap_or_default();
+
+        apparent_age.max(age_value + response_delay) + resident_time
+    }
+
+    fn is_fresh(&self, request: &CacheControl) -> bool {
+        if request.no_cache || CacheControl::parse(&self.headers).no_cache {
+            return false;
+        }
+        let age = self.current_age();
+        age < self.freshness_lifetime() && request.max_age.map_or(true, |max_age| age <= max_age)
+    }
+
+    /// Updates the stored headers with the ones of a `304 Not Modified` response, see RFC 9111 section 3.2.
+    fn revalidated(&self, headers: &HeaderMap, request_time: SystemTime) -> Self {
+        let mut updated = self.headers.clone();
+        for name in headers.keys() {
+            if name == header::CONTENT_LENGTH {
+                continue;
+            }
+            updated.remove(name);
+            for value in headers.get_all(name) {
+                updated.append(name, value.clone());
+            }
+        }
+
+        Self {
+            url: self.url.clone(),
+            status: self.status,
+            headers: updated,
+            vary: self.vary.clone(),
+            request_time,
+            response_time: SystemTime::now(),
+            body: self.body.clone(),
+        }
+    }
+
+    fn to_response(&self) -> Result<reqwest::Response> {
+        let mut headers = self.headers.clone();
+        headers.insert(header::AGE, HeaderValue::from(self.current_age().as_secs()));
+
+        let mut builder = http::Response::builder()
+            .status(self.status)
+            .url(self.url.clone());
+        if let Some(h) = builder.headers_mut() {
+            *h = headers;
+        }
+        Ok(builder.body(self.body.clone())?.into())
+    }
+}
+
+/// The serialized metadata of a persisted [`Entry`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    url: String,
+    status: u16,
+    headers: Vec<(String, Vec<u8>)>,
+    vary: Vec<(String, Option<Vec<u8>>)>,
+    /// In milliseconds since the Unix epoch.
+    request_time: u64,
+    /// In milliseconds since the Unix epoch.
+    response_time: u64,
+}
+
+fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn from_millis(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
+
+impl Entry {
+    /// Encodes the entry as the length of the metadata, the metadata and the body.
+    fn encode(&self) -> Result<Vec<u8>> {
+        let metadata = serde_json::to_vec(&Metadata {
+            url: self.url.to_string(),
+            status: self.status.as_u16(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+                .collect(),
+            vary: self
+                .vary
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        value.as_ref().map(|v| v.as_bytes().to_vec()),
+                    )
+                })
+                .collect(),
+            request_time: to_millis(self.request_time),
+            response_time: to_millis(self.response_time),
+        })?;
+
+        let mut data = Vec::with_capacity(4 + metadata.len() + self.body.len());
+        data.extend((metadata.len() as u32).to_le_bytes());
+        data.extend(metadata);
+        data.extend(&self.body);
+        Ok(data)
+    }
+
+    fn decode(mut data: Vec<u8>) -> Option<Self> {
+        let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        let metadata: Metadata = serde_json::from_slice(data.get(4..4 + len)?).ok()?;
+        let body = data.split_off(4 + len);
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in metadata.headers {
+            headers.append(
+                HeaderName::from_str(&name).ok()?,
+                HeaderValue::from_bytes(&value).ok()?,
+            );
+        }
+        let mut vary = Vec::new();
+        for (name, value) in metadata.vary {
+            let value = match value {
+                Some(value) => Some(HeaderValue::from_bytes(&value).ok()?),
+                None => None,
+            };
+            vary.push((HeaderName::from_str(&name).ok()?, value));
+        }
+
+        Some(Self {
+            url: metadata.url.parse().ok()?,
+            status: StatusCode::from_u16(metadata.status).ok()?,
+            headers,
+            vary,
+            request_time: from_millis(metadata.request_time),
+            response_time: from_millis(metadata.response_time),
+            body,
+        })
+    }
+}
+
+/// The entries kept in memory, the least recently used ones are evicted first.
+#[derive(Default)]
+struct Memory {
+    entries: HashMap<String, (Arc<Entry>, u64)>,
+    size: u64,
+    /// Incremented on every access, to order the entries by last use.
+    tick: u64,
+}
+
+impl Memory {
+    fn get(&mut self, key: &str) -> Option<Arc<Entry>> {
+        self.tick += 1;
+        let (entry, last_use) = self.entries.get_mut(key)?;
+        *last_use = self.tick;
+        Some(entry.clone())
+    }
+
+    fn insert(&mut self, key: String, entry: Arc<Entry>, capacity: u64) {
+        self.remove(&key);
+        let size = entry.body.len() as u64;
+        if size > capacity {
+            return;
+        }
+
+        self.tick += 1;
+        self.entries.insert(key, (entry, self.tick));
+        self.size += size;
+
+        while self.size > capacity {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_use))| *last_use)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&key);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((entry, _)) = self.entries.remove(key) {
+            self.size -= entry.body.len() as u64;
+        }
+    }
+}
+
+/// The entries persisted to disk, one file per entry named after the hash of its key.
+/// The least recently used ones are deleted first.
+struct Disk {
+    path: PathBuf,
+    capacity: u64,
+    /// Size and last use of the files.
+    files: Mutex<HashMap<String, (u64, SystemTime)>>,
+}
+
+impl Disk {
+    fn open(path: PathBuf, capacity: u64) -> Result<Self> {
+        std::fs::create_dir_all(&path)?;
+
+        let mut files = HashMap::new();
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // an interrupted write
+            if name.ends_with(".tmp") {
+                let _ = std::fs::remove_file(entry.path());
+                continue;
+            }
+            let last_use = metadata.modified().unwrap_or(UNIX_EPOCH);
+            files.insert(name, (metadata.len(), last_use));
+        }
+
+        Ok(Self {
+            path,
+            capacity,
+            files: Mutex::new(files),
+        })
+    }
+
+    fn file_name(key: &str) -> String {
+        Sha256::digest(key.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    async fn read(&self, key: &str) -> Option<Entry> {
+        let name = Self::file_name(key);
+        let data = tokio::fs::read(self.path.join(&name)).await.ok()?;
+        if let Some((_, last_use)) = self.files.lock().unwrap().get_mut(&name) {
+            *last_use = SystemTime::now();
+        }
+        Entry::decode(data)
+    }
+
+    async fn write(&self, key: &str, entry: &Entry) -> Result<()> {
+        let name = Self::file_name(key);
+        let data = entry.encode()?;
+        let len = data.len() as u64;
+
+        // written to a temporary file first so a reader never sees a partial entry
+        let tmp = self.path.join(format!("{name}.tmp"));
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, self.path.join(&name)).await?;
+
+        let evicted = {
+            let mut files = self.files.lock().unwrap();
+            files.insert(name, (len, SystemTime::now()));
+
+            let mut size: u64 = files.values().map(|(len, _)| len).sum();
+            let mut evicted = Vec::new();
+            while size > self.capacity {
+                let Some(name) = files
+                    .iter()
+                    .min_by_key(|(_, (_, last_use))| *last_use)
+                    .map(|(name, _)| name.clone())
+                else {
+                    break;
+                };
+                if let Some((len, _)) = files.remove(&name) {
+                    size -= len;
+                }
+                evicted.push(name);
+            }
+            evicted
+        };
+        for name in evicted {
+            let _ = tokio::fs::remove_file(self.path.join(name)).await;
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) {
+        let name = Self::file_name(key);
+        let removed = self.files.lock().unwrap().remove(&name).is_some();
+        if removed {
+            let _ = tokio::fs::remove_file(self.path.join(name)).await;
+        }
+    }
+
+    async fn clear(&self) -> Result<()> {
+        let names = std::mem::take(&mut *self.files.lock().unwrap());
+        for name in names.into_keys() {
+            match tokio::fs::remove_file(self.path.join(name)).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A response being read, stored once its body is complete.
+struct PendingEntry {
+    cache: Arc<Cache>,
+    key: String,
+    entry: Entry,
+}
+
+pub(crate) struct Cache {
+    settings: CacheSettings,
+    memory: Mutex<Memory>,
+    disk: Option<Disk>,
+}
+
+impl Cache {
+    /// Creates the cache, loading the index of the persisted responses from `path` if set.
+    pub(crate) fn new(settings: CacheSettings, path: Option<PathBuf>) -> Result<Self> {
+        let disk = match path {
+            Some(path) => Some(Disk::open(path, settings.disk_capacity)?),
+            None => None,
+        };
+        Ok(Self {
+            settings,
+            memory: Default::default(),
+            disk,
+        })
+    }
+
+    async fn get(&self, key: &str) -> Option<Arc<Entry>> {
+        let entry = self.memory.lock().unwrap().get(key);
+        if entry.is_some() {
+            return entry;
+        }
+
+        let entry = Arc::new(self.disk.as_ref()?.read(key).await?);
+        self.memory.lock().unwrap().insert(
+            key.into(),
+            entry.clone(),
+            self.settings.memory_capacity,
+        );
+        Some(entry)
+    }
+
+    async fn put(&self, key: String, entry: Entry) {
+        let entry = Arc::new(entry);
+        self.memory.lock().unwrap().insert(
+            key.clone(),
+            entry.clone(),
+            self.settings.memory_capacity,
+        );
+
+        if let Some(disk) = &self.disk {
+            if let Err(_e) = disk.write(&key, &entry).await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("failed to persist the cached response: {_e}");
+            }
+        }
+    }
+
+    async fn remove(&self, key: &str) {
+        self.memory.lock().unwrap().remove(key);
+        if let Some(disk) = &self.disk {
+            disk.remove(key).await;
+        }
+    }
+
+    pub(crate) async fn clear(&self) -> Result<()> {
+        *self.memory.lock().unwrap() = Memory::default();
+        if let Some(disk) = &self.disk {
+            disk.clear().await?;
+        }
+        Ok(())
+    }
+
+    /// Sends the request through the cache: served from it when possible,
+    /// revalidated when stale and stored once its body is read.
+    pub(crate) async fn send(
+        self: Arc<Self>,
+        request: reqwest::RequestBuilder,
+        mode: CacheMode,
+        retry: Option<&RetryConfig>,
+    ) -> Result<reqwest::Response> {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+
+        let mut key = request.url().clone();
+        key.set_fragment(None);
+        let key = key.to_string();
+
+        let method = request.method().clone();
+        if method != Method::GET {
+            let res = send_with_retry(reqwest::RequestBuilder::from_parts(client, request), retry)
+                .await?;
+            // unsafe methods invalidate the cached response, see RFC 9111 section 4.4
+            if !method.is_safe() && (res.status().is_success() || res.status().is_redirection()) {
+                self.remove(&key).await;
+            }
+            return Ok(res);
+        }
+
+        let request_cache_control = CacheControl::parse_request(request.headers());
+        let bypass = mode == CacheMode::NoStore
+            || request_cache_control.no_store
+            || CONDITIONAL_HEADERS
+                .iter()
+                .any(|name| request.headers().contains_key(name));
+        if bypass {
+            return send_with_retry(reqwest::RequestBuilder::from_parts(client, request), retry)
+                .await;
+        }
+
+        let request_headers = request.headers().clone();
+        let entry = self
+            .get(&key)
+            .await
+            .filter(|entry| entry.matches(&request_headers));
+
+        if let Some(entry) = &entry {
+            if mode == CacheMode::ForceCache || entry.is_fresh(&request_cache_control) {
+                return entry.to_response();
+            }
+
+            // revalidates the stale response with its validators
+            let headers = request.headers_mut();
+            if let Some(etag) = entry.headers.get(header::ETAG) {
+                headers.insert(header::IF_NONE_MATCH, etag.clone());
+            }
+            if let Some(last_modified) = entry.headers.get(header::LAST_MODIFIED) {
+                headers.insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        let request_time = SystemTime::now();
+        let res =
+            send_with_retry(reqwest::RequestBuilder::from_parts(client, request), retry).await?;
+
+        if let Some(entry) = entry.filter(|_| res.status() == StatusCode::NOT_MODIFIED) {
+            let entry = entry.revalidated(res.headers(), request_time);
+            let response = entry.to_response()?;
+            self.put(key, entry).await;
+            return Ok(response);
+        }
+
+        let cache_control = CacheControl::parse(res.headers());
+        if cache_control.no_store {
+            self.remove(&key).await;
+            return Ok(res);
+        }
+
+        let vary_all = res
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .any(|v| v.contains('*'));
+        let storable = !vary_all
+            && res.status() != StatusCode::PARTIAL_CONTENT
+            && (cache_control.max_age.is_some()
+                || cache_control.public
+                || res.headers().contains_key(header::EXPIRES)
+                || HEURISTICALLY_CACHEABLE.contains(&res.status().as_u16()))
+            && res
+                .content_length()
+                .map_or(true, |len| len <= self.settings.max_entry_size);
+        if !storable {
+            return Ok(res);
+        }
+
+        let vary = res
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|name| HeaderName::from_str(name.trim()).ok())
+            .map(|name| {
+                let value = request_headers.get(&name).cloned();
+                (name, value)
+            })
+            .collect();
+
+        let pending = PendingEntry {
+            cache: self,
+            key,
+            entry: Entry {
+                url: res.url().clone(),
+                status: res.status(),
+                headers: res.headers().clone(),
+                vary,
+                request_time,
+                response_time: SystemTime::now(),
+                body: Vec::new(),
+            },
+        };
+
+        tee(res, pending)
+    }
+}
+
+/// Streams the response body while buffering it, the entry is stored once the body is complete.
+fn tee(res: reqwest::Response, pending: PendingEntry) -> Result<reqwest::Response> {
+    let mut builder = http::Response::builder()
+        .status(res.status())
+        .version(res.version())
+        .url(res.url().clone());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = res.headers().clone();
+    }
+
+    let chunks = futures_util::stream::unfold(Some((res, Some(pending))), |state| async move {
+        let (mut res, mut pending) = state?;
+        match res.chunk().await {
+            Ok(Some(chunk)) => {
+                let too_large = pending.as_ref().is_some_and(|p| {
+                    (p.entry.body.len() + chunk.len()) as u64 > p.cache.settings.max_entry_size
+                });
+                if too_large {
+                    pending = None;
+                } else if let Some(p) = &mut pending {
+                    p.entry.body.extend_from_slice(&chunk);
+                }
+                Some((Ok(chunk), Some((res, pending))))
+            }
+            Ok(None) => {
+                if let Some(PendingEntry { cache, key, entry }) = pending {
+                    cache.put(key, entry).await;
+                }
+                None
+            }
+            Err(e) => Some((Err(e), None)),
+        }
+    });
+
+    Ok(builder.body(reqwest::Body::wrap_stream(chunks))?.into())
+}
+
+/// Removes all the cached responses, from memory and disk.
+#[command]
+pub async fn clear_cache(state: State<'_, Http>) -> Result<()> {
+    match &state.cache {
+        Some(cache) => cache.clear().await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `Date` of the responses, rounded since the HTTP dates have a precision of one second.
+    fn date() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    }
+
+    /// An HTTP date the given number of seconds after [`date`].
+    fn http_date(seconds: i64) -> String {
+        let time = if seconds < 0 {
+            date() - Duration::from_secs(seconds.unsigned_abs())
+        } else {
+            date() + Duration::from_secs(seconds as u64)
+        };
+        httpdate::fmt_http_date(time)
+    }
+
+    fn headers(headers: &[(HeaderName, &str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    fn entry(status: u16, response_headers: &[(HeaderName, &str)]) -> Entry {
+        let now = SystemTime::now();
+        Entry {
+            url: "https://tauri.app".parse().unwrap(),
+            status: StatusCode::from_u16(status).unwrap(),
+            headers: headers(response_headers),
+            vary: Vec::new(),
+            request_time: now,
+            response_time: now,
+            body: Vec::new(),
+        }
+    }
+
+    fn fresh(
+        response_headers: &[(HeaderName, &str)],
+        request_headers: &[(HeaderName, &str)],
+    ) -> bool {
+        entry(200, response_headers)
+            .is_fresh(&CacheControl::parse_request(&headers(request_headers)))
+    }
+
+    #[test]
+    fn freshness_lifetime_precedence() {
+        let date = http_date(0);
+        let expires = http_date(600);
+
+        // max-age takes precedence over Expires
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::CACHE_CONTROL, "max-age=60"),
+                (header::EXPIRES, expires.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::from_secs(60));
+
+        // s-maxage only applies to shared caches
+        let response = entry(200, &[(header::CACHE_CONTROL, "max-age=60, s-maxage=600")]);
+        assert_eq!(response.freshness_lifetime(), Duration::from_secs(60));
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::CACHE_CONTROL, "s-maxage=60"),
+                (header::EXPIRES, expires.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::from_secs(600));
+
+        // an invalid max-age makes the response stale
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::CACHE_CONTROL, "max-age=soon"),
+                (header::EXPIRES, expires.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::ZERO);
+    }
+
+    #[test]
+    fn freshness_lifetime_from_expires() {
+        let date = http_date(0);
+        let last_modified = http_date(-1000);
+
+        let expires = http_date(120);
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::EXPIRES, expires.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::from_secs(120));
+
+        let expires = http_date(-120);
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::EXPIRES, expires.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::ZERO);
+
+        // an invalid date means the response is already expired, without heuristic
+        let response = entry(
+            200,
+            &[
+                (header::DATE, date.as_str()),
+                (header::EXPIRES, "0"),
+                (header::LAST_MODIFIED, last_modified.as_str()),
+            ],
+        );
+        assert_eq!(response.freshness_lifetime(), Duration::ZERO);
+    }
+
+    #[test]
+    fn heuristic_freshness_lifetime() {
+        let date = http_date(0);
+        let last_modified = http_date(-1000);
+        let response_headers = [
+            (header::DATE, date.as_str()),
+            (header::LAST_MODIFIED, last_modified.as_str()),
+        ];
+
+        // 10% of the time since the last modification
+        assert_eq!(
+            entry(200, &response_headers).freshness_lifetime(),
+            Duration::from_secs(100)
+        );
+        assert_eq!(
+            entry(404, &response_headers).freshness_lifetime(),
+            Duration::from_secs(100)
+        );
+        assert_eq!(
+            entry(500, &response_headers).freshness_lifetime(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            entry(200, &[(header::DATE, date.as_str())]).freshness_lifetime(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn parses_cache_control() {
+        let cache_control = CacheControl::parse(&headers(&[
+            (header::CACHE_CONTROL, "no-store, must-revalidate"),
+            (
+                header::CACHE_CONTROL,
+                "No-Cache=\"Set-Cookie\", max-age=\"30\"",
+            ),
+        ]));
+        assert!(cache_control.no_store);
+        assert!(cache_control.no_cache);
+        assert!(!cache_control.public);
+        assert_eq!(cache_control.max_age, Some(Duration::from_secs(30)));
+
+        assert!(CacheControl::parse_request(&headers(&[(header::PRAGMA, "no-cache")])).no_cache);
+        // `Pragma` is ignored with `Cache-Control`
+        let cache_control = CacheControl::parse_request(&headers(&[
+            (header::CACHE_CONTROL, "max-age=60"),
+            (header::PRAGMA, "no-cache"),
+        ]));
+        assert!(!cache_control.no_cache);
+    }
+
+    #[test]
+    fn is_fresh() {
+        assert!(fresh(&[(header::CACHE_CONTROL, "max-age=60")], &[]));
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=60"), (header::AGE, "120")],
+            &[]
+        ));
+
+        // no-cache requires a revalidation, from the response or the request
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=60, no-cache")],
+            &[]
+        ));
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=60")],
+            &[(header::CACHE_CONTROL, "no-cache")]
+        ));
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=60")],
+            &[(header::PRAGMA, "no-cache")]
+        ));
+
+        // the max-age of the request limits the age of the response
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=60"), (header::AGE, "10")],
+            &[(header::CACHE_CONTROL, "max-age=5")]
+        ));
+        assert!(fresh(
+            &[(header::CACHE_CONTROL, "max-age=60"), (header::AGE, "10")],
+            &[(header::CACHE_CONTROL, "max-age=30")]
+        ));
+
+        // must-revalidate has no effect on fresh responses, the stale ones are always revalidated
+        assert!(fresh(
+            &[(header::CACHE_CONTROL, "max-age=60, must-revalidate")],
+            &[]
+        ));
+        assert!(!fresh(
+            &[(header::CACHE_CONTROL, "max-age=0, must-revalidate")],
+            &[]
+        ));
+    }
+
+    #[test]
+    fn vary_mismatch() {
+        let mut entry = entry(200, &[(header::VARY, "Accept-Language, Origin")]);
+        entry.vary = vec![
+            (
+                header::ACCEPT_LANGUAGE,
+                Some(HeaderValue::from_static("en")),
+            ),
+            (header::ORIGIN, None),
+        ];
+
+        assert!(entry.matches(&headers(&[(header::ACCEPT_LANGUAGE, "en")])));
+        assert!(!entry.matches(&headers(&[(header::ACCEPT_LANGUAGE, "fr")])));
+        assert!(!entry.matches(&HeaderMap::new()));
+        assert!(!entry.matches(&headers(&[
+            (header::ACCEPT_LANGUAGE, "en"),
+            (header::ORIGIN, "https://tauri.app"),
+        ])));
+    }
+}
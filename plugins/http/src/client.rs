@@ -0,0 +1,146 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Connection pool and timeout settings, and the named clients created from the webview.
+
+use std::time::Duration;
+
+use reqwest::redirect::Policy;
+use serde::{Deserialize, Deserializer};
+use tauri::{command, Manager, ResourceId, Runtime, State, Webview};
+
+use crate::{proxy::ProxySettings, tls::TlsConfig, Http, Result};
+
+/// Deserializes an optional duration from milliseconds.
+fn millis<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Duration>, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+}
+
+/// Connection pool and timeout settings of a client, durations are in milliseconds from the webview.
+///
+/// The unset settings keep the defaults of [`reqwest`].
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientSettings {
+    /// Maximum idle connections kept alive per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept alive in the pool.
+    #[serde(default, deserialize_with = "millis")]
+    pub pool_idle_timeout: Option<Duration>,
+    #[serde(default, deserialize_with = "millis")]
+    pub connect_timeout: Option<Duration>,
+    /// Timeout of every read of the response, reset after each successful read.
+    #[serde(default, deserialize_with = "millis")]
+    pub read_timeout: Option<Duration>,
+    /// Timeout of the whole request, from connecting until the response body is read.
+    ///
+    /// Writing the request has no timeout of its own, this one covers it.
+    #[serde(default, deserialize_with = "millis")]
+    pub timeout: Option<Duration>,
+    /// Interval of the HTTP/2 keep-alive pings, requires the `http2` feature.
+    #[serde(default, deserialize_with = "millis")]
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// How long to wait for the acknowledgement of a keep-alive ping before closing the connection.
+    #[serde(default, deserialize_with = "millis")]
+    pub http2_keep_alive_timeout: Option<Duration>,
+    /// Whether the keep-alive pings are also sent on idle connections.
+    pub http2_keep_alive_while_idle: Option<bool>,
+    /// Whether `TCP_NODELAY` is set on the sockets, `true` by default.
+    pub tcp_nodelay: Option<bool>,
+    /// Interval of the TCP keep-alive probes.
+    #[serde(default, deserialize_with = "millis")]
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl ClientSettings {
+    /// Configures the client builder with the settings that are set.
+    pub(crate) fn apply(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        #[cfg(feature = "http2")]
+        {
+            if let Some(interval) = self.http2_keep_alive_interval {
+                builder = builder.http2_keep_alive_interval(interval);
+            }
+            if let Some(timeout) = self.http2_keep_alive_timeout {
+                builder = builder.http2_keep_alive_timeout(timeout);
+            }
+            if let Some(while_idle) = self.http2_keep_alive_while_idle {
+                builder = builder.http2_keep_alive_while_idle(while_idle);
+            }
+        }
+        if let Some(nodelay) = self.tcp_nodelay {
+            builder = builder.tcp_nodelay(nodelay);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        builder
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateClientOptions {
+    #[serde(flatten)]
+    settings: ClientSettings,
+    max_redirections: Option<usize>,
+    proxy: Option<ProxySettings>,
+    tls: Option<TlsConfig>,
+    #[cfg_attr(not(feature = "cookies"), allow(unused))]
+    cookie_jar: Option<String>,
+}
+
+/// A client created from the webview, its connections are reused by the requests sent with it.
+pub(crate) struct HttpClient(pub(crate) reqwest::Client);
+impl tauri::Resource for HttpClient {}
+
+/// Builds the redirect policy of a client from the maximum number of redirections.
+pub(crate) fn redirect_policy(max_redirections: usize) -> Policy {
+    if max_redirections == 0 {
+        Policy::none()
+    } else {
+        Policy::limited(max_redirections)
+    }
+}
+
+/// Creates a client with its own connection pool, it is closed with its resource.
+#[command]
+pub async fn create_client<R: Runtime>(
+    webview: Webview<R>,
+    state: State<'_, Http>,
+    options: Option<CreateClientOptions>,
+) -> Result<ResourceId> {
+    let options = options.unwrap_or_default();
+
+    let mut builder =
+        state.client_builder(options.proxy.as_ref(), options.cookie_jar.as_deref())?;
+    // the settings of the client override the ones of the plugin
+    builder = state.client_settings.apply(builder);
+    builder = options.settings.apply(builder);
+    if let Some(max_redirections) = options.max_redirections {
+        builder = builder.redirect(redirect_policy(max_redirections));
+    }
+    if let Some(tls) = options.tls {
+        builder = tls.apply(&webview, builder).await?;
+    }
+
+    let client = builder.build()?;
+    Ok(webview.resources_table().add(HttpClient(client)))
+}
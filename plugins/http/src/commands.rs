@@ -2,29 +2,73 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{future::Future, pin::Pin, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    str::FromStr,
+    sync::{Arc, Mutex as StdMutex},
+    time::{Duration, Instant},
+};
 
 use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
-use reqwest::{redirect::Policy, NoProxy};
 use serde::{Deserialize, Serialize};
 use tauri::{
     async_runtime::Mutex,
     command,
-    ipc::{CommandScope, GlobalScope},
+    ipc::{Channel, CommandScope, GlobalScope, InvokeResponseBody},
     Manager, ResourceId, ResourceTable, Runtime, State, Webview,
 };
-use tokio::sync::oneshot::{channel, Receiver, Sender};
+use tauri_plugin_fs::{FsExt, SafeFilePath};
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    sync::{
+        mpsc,
+        oneshot::{channel, Receiver, Sender},
+    },
+};
 
 use crate::{
+    client::{redirect_policy, HttpClient},
+    multipart::{attach_form, MultipartPart, UploadProgress},
+    proxy::ProxySettings,
+    retry::{send_with_retry, RetryConfig},
     scope::{Entry, Scope},
+    tls::TlsConfig,
     Error, Http, Result,
 };
 
 const HTTP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
-struct ReqwestResponse(reqwest::Response);
+struct ReqwestResponse {
+    res: reqwest::Response,
+    /// Still notified by [`fetch_cancel`] while the response is downloaded.
+    abort_rx: AbortRecveiver,
+}
 impl tauri::Resource for ReqwestResponse {}
 
+/// Minimum interval between two download progress events.
+pub(crate) const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Size of the chunks read from a file streamed as the request body.
+const FILE_BODY_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks written to a request body stream that can be buffered
+/// before [`fetch_write_body`] waits for the request to send them.
+const BODY_STREAM_CAPACITY: usize = 4;
+
+/// A request body written in chunks from the webview with [`fetch_write_body`],
+/// closing the resource ends the body.
+struct RequestBodyStream {
+    tx: mpsc::Sender<Vec<u8>>,
+    rx: StdMutex<Option<mpsc::Receiver<Vec<u8>>>>,
+}
+impl tauri::Resource for RequestBodyStream {}
+
+/// Handle of a response body being streamed to the webview, dropping it stops the stream.
+struct ResponseBodyStream(mpsc::UnboundedSender<()>);
+impl tauri::Resource for ResponseBodyStream {}
+
 type CancelableResponseResult = Result<reqwest::Response>;
 type CancelableResponseFuture =
     Pin<Box<dyn Future<Output = CancelableResponseResult> + Send + Sync>>;
@@ -82,87 +126,30 @@ pub struct ClientConfig {
     url: url::Url,
     headers: Vec<(String, String)>,
     data: Option<Vec<u8>>,
+    /// Resource id of a [`RequestBodyStream`] to send as the body.
+    data_stream: Option<ResourceId>,
+    /// Path of a file to send as the body.
+    data_file: Option<SafeFilePath>,
+    /// Parts of a `multipart/form-data` body, file parts are streamed from disk.
+    multipart: Option<Vec<MultipartPart>>,
+    /// Length of the streamed body, it is sent with chunked encoding if unknown.
+    content_length: Option<u64>,
+    /// Path of a partially downloaded file, its length is requested as the start of a `Range`.
+    resume_download: Option<SafeFilePath>,
+    /// Resource id of a [`HttpClient`] to send the request with.
+    client: Option<ResourceId>,
     connect_timeout: Option<u64>,
     max_redirections: Option<usize>,
-    proxy: Option<Proxy>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Proxy {
-    all: Option<UrlOrConfig>,
-    http: Option<UrlOrConfig>,
-    https: Option<UrlOrConfig>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[serde(untagged)]
-pub enum UrlOrConfig {
-    Url(String),
-    Config(ProxyConfig),
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ProxyConfig {
-    url: String,
-    basic_auth: Option<BasicAuth>,
-    no_proxy: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct BasicAuth {
-    username: String,
-    password: String,
-}
-
-#[inline]
-fn proxy_creator(
-    url_or_config: UrlOrConfig,
-    proxy_fn: fn(String) -> reqwest::Result<reqwest::Proxy>,
-) -> reqwest::Result<reqwest::Proxy> {
-    match url_or_config {
-        UrlOrConfig::Url(url) => Ok(proxy_fn(url)?),
-        UrlOrConfig::Config(ProxyConfig {
-            url,
-            basic_auth,
-            no_proxy,
-        }) => {
-            let mut proxy = proxy_fn(url)?;
-            if let Some(basic_auth) = basic_auth {
-                proxy = proxy.basic_auth(&basic_auth.username, &basic_auth.password);
-            }
-            if let Some(no_proxy) = no_proxy {
-                proxy = proxy.no_proxy(NoProxy::from_string(&no_proxy));
-            }
-            Ok(proxy)
-        }
-    }
-}
-
-fn attach_proxy(
-    proxy: Proxy,
-    mut builder: reqwest::ClientBuilder,
-) -> crate::Result<reqwest::ClientBuilder> {
-    let Proxy { all, http, https } = proxy;
-
-    if let Some(all) = all {
-        let proxy = proxy_creator(all, reqwest::Proxy::all)?;
-        builder = builder.proxy(proxy);
-    }
-
-    if let Some(http) = http {
-        let proxy = proxy_creator(http, reqwest::Proxy::http)?;
-        builder = builder.proxy(proxy);
-    }
-
-    if let Some(https) = https {
-        let proxy = proxy_creator(https, reqwest::Proxy::https)?;
-        builder = builder.proxy(proxy);
-    }
-
-    Ok(builder)
+    proxy: Option<ProxySettings>,
+    retry: Option<RetryConfig>,
+    tls: Option<TlsConfig>,
+    /// Name of the cookie jar used instead of the default one.
+    #[cfg_attr(not(feature = "cookies"), allow(unused))]
+    cookie_jar: Option<String>,
+    /// How the request uses the response cache.
+    #[cfg(feature = "cache")]
+    #[serde(default)]
+    cache: crate::CacheMode,
 }
 
 #[command]
@@ -170,6 +157,7 @@ pub async fn fetch<R: Runtime>(
     webview: Webview<R>,
     state: State<'_, Http>,
     client_config: ClientConfig,
+    on_upload_progress: Option<Channel<UploadProgress>>,
     command_scope: CommandScope<Entry>,
     global_scope: GlobalScope<Entry>,
 ) -> crate::Result<ResourceId> {
@@ -178,9 +166,20 @@ pub async fn fetch<R: Runtime>(
         url,
         headers: headers_raw,
         data,
+        data_stream,
+        data_file,
+        multipart,
+        content_length,
+        resume_download,
+        client,
         connect_timeout,
         max_redirections,
         proxy,
+        retry,
+        tls,
+        cookie_jar,
+        #[cfg(feature = "cache")]
+        cache,
     } = client_config;
 
     let scheme = url.scheme();
@@ -218,35 +217,79 @@ pub async fn fetch<R: Runtime>(
             )
             .is_allowed(&url)
             {
-                let mut builder = reqwest::ClientBuilder::new();
-
-                if let Some(timeout) = connect_timeout {
-                    builder = builder.connect_timeout(Duration::from_millis(timeout));
-                }
+                let has_client_options = connect_timeout.is_some()
+                    || max_redirections.is_some()
+                    || proxy.is_some()
+                    || tls.is_some()
+                    || cookie_jar.is_some();
+
+                let client = match client {
+                    Some(_) if has_client_options => return Err(Error::ClientOptionsConflict),
+                    Some(rid) => webview.resources_table().get::<HttpClient>(rid)?.0.clone(),
+                    None if !has_client_options => state.client()?,
+                    None => {
+                        let mut builder =
+                            state.client_builder(proxy.as_ref(), cookie_jar.as_deref())?;
+                        builder = state.client_settings.apply(builder);
+
+                        if let Some(timeout) = connect_timeout {
+                            builder = builder.connect_timeout(Duration::from_millis(timeout));
+                        }
+
+                        if let Some(max_redirections) = max_redirections {
+                            builder = builder.redirect(redirect_policy(max_redirections));
+                        }
+
+                        // the identity of the request replaces the one of the plugin
+                        if let Some(tls) = tls {
+                            builder = tls.apply(&webview, builder).await?;
+                        }
+
+                        builder.build()?
+                    }
+                };
 
-                if let Some(max_redirections) = max_redirections {
-                    builder = builder.redirect(if max_redirections == 0 {
-                        Policy::none()
-                    } else {
-                        Policy::limited(max_redirections)
-                    });
-                }
+                let mut request = client.request(method.clone(), url);
 
-                if let Some(proxy_config) = proxy {
-                    builder = attach_proxy(proxy_config, builder)?;
+                let (body, content_length) = match (data, data_stream, data_file) {
+                    (Some(data), None, None) => (Some(reqwest::Body::from(data)), None),
+                    (None, Some(rid), None) => {
+                        (Some(body_from_stream(&webview, rid)?), content_length)
+                    }
+                    (None, None, Some(path)) => {
+                        let (body, _, len) = body_from_file(&webview, path, |_, _| {}).await?;
+                        (Some(body), Some(len))
+                    }
+                    (None, None, None) => (None, None),
+                    _ => return Err(Error::MultipleBodies),
+                };
+                if multipart.is_some() && body.is_some() {
+                    return Err(Error::MultipleBodies);
                 }
 
-                #[cfg(feature = "cookies")]
+                if let Some(len) = content_length {
+                    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+                } else if body.is_none()
+                    && multipart.is_none()
+                    && matches!(method, Method::POST | Method::PUT)
                 {
-                    builder = builder.cookie_provider(state.cookies_jar.clone());
+                    // POST and PUT requests should always have a 0 length content-length,
+                    // if there is no body. https://fetch.spec.whatwg.org/#http-network-or-cache-fetch
+                    headers.append(header::CONTENT_LENGTH, HeaderValue::from_str("0")?);
                 }
 
-                let mut request = builder.build()?.request(method.clone(), url);
-
-                // POST and PUT requests should always have a 0 length content-length,
-                // if there is no body. https://fetch.spec.whatwg.org/#http-network-or-cache-fetch
-                if data.is_none() && matches!(method, Method::POST | Method::PUT) {
-                    headers.append(header::CONTENT_LENGTH, HeaderValue::from_str("0")?);
+                if let Some(path) = resume_download {
+                    let path = check_fs_scope(&webview, path)?;
+                    let len = tokio::fs::metadata(&path)
+                        .await
+                        .map(|m| m.len())
+                        .unwrap_or_default();
+                    if len > 0 && !headers.contains_key(header::RANGE) {
+                        headers.insert(
+                            header::RANGE,
+                            HeaderValue::from_str(&format!("bytes={len}-"))?,
+                        );
+                    }
                 }
 
                 if headers.contains_key(header::RANGE) {
@@ -277,16 +320,29 @@ pub async fn fetch<R: Runtime>(
                     headers.remove(header::ORIGIN);
                 };
 
-                if let Some(data) = data {
-                    request = request.body(data);
+                if let Some(body) = body {
+                    request = request.body(body);
                 }
 
                 request = request.headers(headers);
 
+                // sets the `Content-Type` with the boundary of the form
+                if let Some(parts) = multipart {
+                    request = attach_form(&webview, request, parts, on_upload_progress).await?;
+                }
+
                 #[cfg(feature = "tracing")]
                 tracing::trace!("{:?}", request);
 
-                let fut = async move { request.send().await.map_err(Into::into) };
+                #[cfg(feature = "cache")]
+                let response_cache = state.cache.clone();
+                let fut = async move {
+                    #[cfg(feature = "cache")]
+                    if let Some(response_cache) = response_cache {
+                        return response_cache.send(request, cache, retry.as_ref()).await;
+                    }
+                    send_with_retry(request, retry.as_ref()).await
+                };
                 let mut resources_table = webview.resources_table();
                 let rid = resources_table.add_request(Box::pin(fut));
 
@@ -319,6 +375,140 @@ pub async fn fetch<R: Runtime>(
     }
 }
 
+/// Takes the receiving end of the [`RequestBodyStream`] to stream it as a request body.
+fn body_from_stream<R: Runtime>(webview: &Webview<R>, rid: ResourceId) -> Result<reqwest::Body> {
+    let stream = webview.resources_table().get::<RequestBodyStream>(rid)?;
+    let rx = stream
+        .rx
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or(Error::RequestBodyInUse)?;
+
+    let chunks = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|chunk| (Ok::<_, std::io::Error>(chunk), rx))
+    });
+    Ok(reqwest::Body::wrap_stream(chunks))
+}
+
+/// Ensures the URL is allowed on the scope of the command.
+pub(crate) fn check_scope(
+    url: &url::Url,
+    command_scope: &CommandScope<Entry>,
+    global_scope: &GlobalScope<Entry>,
+) -> Result<()> {
+    let scope = Scope::new(
+        command_scope
+            .allows()
+            .iter()
+            .chain(global_scope.allows())
+            .collect(),
+        command_scope
+            .denies()
+            .iter()
+            .chain(global_scope.denies())
+            .collect(),
+    );
+    if scope.is_allowed(url) {
+        Ok(())
+    } else {
+        Err(Error::UrlNotAllowed(url.clone()))
+    }
+}
+
+/// Resolves the path and ensures it is allowed on the scope of the fs plugin.
+pub(crate) fn check_fs_scope<R: Runtime>(
+    webview: &Webview<R>,
+    path: SafeFilePath,
+) -> Result<PathBuf> {
+    let path = path.into_path()?;
+    if webview
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path))
+    {
+        Ok(path)
+    } else {
+        Err(Error::PathNotAllowed(path))
+    }
+}
+
+/// Opens the file as a streamed body, `on_read` is called with the bytes read so far
+/// and the length of the file after every chunk.
+///
+/// Returns the body with the resolved path and the length of the file.
+pub(crate) async fn body_from_file<R: Runtime>(
+    webview: &Webview<R>,
+    path: SafeFilePath,
+    on_read: impl FnMut(u64, u64) + Send + Sync + 'static,
+) -> Result<(reqwest::Body, PathBuf, u64)> {
+    let path = check_fs_scope(webview, path)?;
+    let file = tokio::fs::File::open(&path).await?;
+    let len = file.metadata().await?.len();
+
+    let state = Some((file, 0, on_read));
+    let chunks = futures_util::stream::unfold(state, move |state| async move {
+        let (mut file, mut bytes, mut on_read) = state?;
+        let mut buf = vec![0; FILE_BODY_CHUNK_SIZE];
+        match file.read(&mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                bytes += n as u64;
+                on_read(bytes, len);
+                Some((Ok(buf), Some((file, bytes, on_read))))
+            }
+            // end the stream after the error
+            Err(e) => Some((Err(e), None)),
+        }
+    });
+    Ok((reqwest::Body::wrap_stream(chunks), path, len))
+}
+
+/// Creates a request body stream that chunks are written to with [`fetch_write_body`],
+/// its resource id is passed as `dataStream` to [`fetch`].
+#[command]
+pub fn fetch_create_body_stream<R: Runtime>(webview: Webview<R>) -> ResourceId {
+    let (tx, rx) = mpsc::channel(BODY_STREAM_CAPACITY);
+    webview.resources_table().add(RequestBodyStream {
+        tx,
+        rx: StdMutex::new(Some(rx)),
+    })
+}
+
+/// Writes the raw invoke body as the next chunk of the request body stream
+/// identified by the `rid` header, waiting until the request can buffer it.
+#[command]
+pub async fn fetch_write_body<R: Runtime>(
+    webview: Webview<R>,
+    request: tauri::ipc::Request<'_>,
+) -> crate::Result<()> {
+    let data = match request.body() {
+        tauri::ipc::InvokeBody::Raw(data) => data.clone(),
+        tauri::ipc::InvokeBody::Json(serde_json::Value::Array(data)) => data
+            .iter()
+            .flat_map(|v| v.as_u64().map(|v| v as u8))
+            .collect(),
+        _ => return Err(Error::UnexpectedInvokeBody),
+    };
+
+    let rid = request
+        .headers()
+        .get("rid")
+        .and_then(|rid| rid.to_str().ok())
+        .and_then(|rid| rid.parse::<ResourceId>().ok())
+        .ok_or(Error::MissingBodyStreamRid)?;
+
+    // clone the sender so closing the resource while waiting still ends the body
+    let tx = webview
+        .resources_table()
+        .get::<RequestBodyStream>(rid)?
+        .tx
+        .clone();
+    tx.send(data).await.map_err(|_| Error::RequestBodyClosed)
+}
+
 #[command]
 pub fn fetch_cancel<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> crate::Result<()> {
     let mut resources_table = webview.resources_table();
@@ -333,6 +523,7 @@ pub fn fetch_cancel<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> crate::
 #[tauri::command]
 pub async fn fetch_send<R: Runtime>(
     webview: Webview<R>,
+    state: State<'_, Http>,
     rid: ResourceId,
 ) -> crate::Result<FetchResponse> {
     let (req, abort_rx) = {
@@ -342,7 +533,7 @@ pub async fn fetch_send<R: Runtime>(
         (req, abort_rx)
     };
 
-    let Some(abort_rx) = Arc::into_inner(abort_rx) else {
+    let Some(mut abort_rx) = Arc::into_inner(abort_rx) else {
         return Err(Error::RequestCanceled);
     };
 
@@ -350,7 +541,7 @@ pub async fn fetch_send<R: Runtime>(
 
     let res = tokio::select! {
         res = fut.as_mut() => res?,
-        _ = abort_rx.0 => {
+        _ = &mut abort_rx.0 => {
             let mut resources_table = webview.resources_table();
             resources_table.close(rid)?;
             return Err(Error::RequestCanceled);
@@ -360,6 +551,10 @@ pub async fn fetch_send<R: Runtime>(
     #[cfg(feature = "tracing")]
     tracing::trace!("{:?}", res);
 
+    // the response cookies are stored in the jar once the response is received
+    #[cfg(feature = "cookies")]
+    state.cookies.try_save();
+
     let status = res.status();
     let url = res.url().to_string();
     let mut headers = Vec::new();
@@ -371,7 +566,7 @@ pub async fn fetch_send<R: Runtime>(
     }
 
     let mut resources_table = webview.resources_table();
-    let rid = resources_table.add(ReqwestResponse(res));
+    let rid = resources_table.add(ReqwestResponse { res, abort_rx });
 
     Ok(FetchResponse {
         status: status.as_u16(),
@@ -382,22 +577,196 @@ pub async fn fetch_send<R: Runtime>(
     })
 }
 
+/// Streams the response body over the channel, each chunk is only read
+/// once requested with [`fetch_read_body_pull`].
+///
+/// The end of the body is signaled with an empty chunk and errors are sent as a string.
 #[tauri::command]
-pub(crate) async fn fetch_read_body<R: Runtime>(
+pub(crate) fn fetch_read_body<R: Runtime>(
     webview: Webview<R>,
     rid: ResourceId,
-) -> crate::Result<tauri::ipc::Response> {
+    on_chunk: Channel<InvokeResponseBody>,
+) -> crate::Result<ResourceId> {
     let res = {
         let mut resources_table = webview.resources_table();
         resources_table.take::<ReqwestResponse>(rid)?
     };
-    let res = Arc::into_inner(res).unwrap().0;
-    Ok(tauri::ipc::Response::new(res.bytes().await?.to_vec()))
+    let Some(ReqwestResponse { mut res, .. }) = Arc::into_inner(res) else {
+        return Err(Error::ResponseBodyInUse);
+    };
+
+    let (pull_tx, mut pull_rx) = mpsc::unbounded_channel();
+    let rid = webview.resources_table().add(ResponseBodyStream(pull_tx));
+
+    tauri::async_runtime::spawn(async move {
+        'pull: while pull_rx.recv().await.is_some() {
+            // an empty chunk marks the end of the body, so skip the empty frames
+            let chunk = loop {
+                match res.chunk().await {
+                    Ok(Some(chunk)) if chunk.is_empty() => continue,
+                    chunk => break chunk,
+                }
+            };
+
+            match chunk {
+                Ok(Some(chunk)) => {
+                    if on_chunk
+                        .send(InvokeResponseBody::Raw(chunk.to_vec()))
+                        .is_err()
+                    {
+                        break 'pull;
+                    }
+                }
+                Ok(None) => {
+                    let _ = on_chunk.send(InvokeResponseBody::Raw(Vec::new()));
+                    break 'pull;
+                }
+                Err(e) => {
+                    let _ = on_chunk.send(InvokeResponseBody::Json(
+                        serde_json::to_string(&e.to_string()).unwrap_or_default(),
+                    ));
+                    break 'pull;
+                }
+            }
+        }
+        let _ = webview.resources_table().close(rid);
+    });
+
+    Ok(rid)
+}
+
+/// Allows the [`fetch_read_body`] stream to read and send the next chunk.
+#[tauri::command]
+pub(crate) fn fetch_read_body_pull<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+) -> crate::Result<()> {
+    let stream = webview.resources_table().get::<ResponseBodyStream>(rid)?;
+    // the stream already finished if the receiver is gone
+    let _ = stream.0.send(());
+    Ok(())
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    /// Bytes written to the file, including the ones of a resumed download.
+    bytes: u64,
+    total_bytes: Option<u64>,
+    /// Average download speed in bytes per second.
+    speed: f64,
+}
+
+/// Parses the start offset and the complete length of a `Content-Range` header.
+fn parse_content_range(value: &HeaderValue) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes ")?
+        .split_once('/')?;
+    let (start, _) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, total.trim().parse().ok()))
+}
+
+/// Writes the response body to the file at `path`, the file is appended to
+/// if the server answered a resumed download with a partial content.
+///
+/// Nothing is written for unsuccessful responses, in this case `None` is returned.
+#[tauri::command]
+pub(crate) async fn fetch_download<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    path: SafeFilePath,
+    on_progress: Option<Channel<DownloadProgress>>,
+) -> crate::Result<Option<PathBuf>> {
+    let res = {
+        let mut resources_table = webview.resources_table();
+        resources_table.take::<ReqwestResponse>(rid)?
+    };
+    let Some(ReqwestResponse {
+        mut res,
+        mut abort_rx,
+    }) = Arc::into_inner(res)
+    else {
+        return Err(Error::ResponseBodyInUse);
+    };
+
+    if !res.status().is_success() {
+        return Ok(None);
+    }
+    let path = check_fs_scope(&webview, path)?;
+
+    let partial = if res.status() == StatusCode::PARTIAL_CONTENT {
+        res.headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(parse_content_range)
+    } else {
+        None
+    };
+
+    let (mut file, offset, total_bytes) = match partial {
+        Some((start, total)) => {
+            let mut file = tokio::fs::OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .await?;
+            file.set_len(start).await?;
+            file.seek(std::io::SeekFrom::Start(start)).await?;
+            (file, start, total)
+        }
+        None => (
+            tokio::fs::File::create(&path).await?,
+            0,
+            res.content_length(),
+        ),
+    };
+
+    let send_progress = |bytes: u64, started: Instant| {
+        if let Some(on_progress) = &on_progress {
+            let elapsed = started.elapsed().as_secs_f64();
+            let _ = on_progress.send(DownloadProgress {
+                bytes,
+                total_bytes,
+                speed: if elapsed > 0.0 {
+                    (bytes - offset) as f64 / elapsed
+                } else {
+                    0.0
+                },
+            });
+        }
+    };
+
+    let started = Instant::now();
+    let mut last_progress = started;
+    let mut bytes = offset;
+    loop {
+        let chunk = tokio::select! {
+            chunk = res.chunk() => chunk?,
+            _ = &mut abort_rx.0 => return Err(Error::RequestCanceled),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+
+        file.write_all(&chunk).await?;
+        bytes += chunk.len() as u64;
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            send_progress(bytes, started);
+        }
+    }
+    file.flush().await?;
+    send_progress(bytes, started);
+
+    Ok(Some(path))
 }
 
 // forbidden headers per fetch spec https://fetch.spec.whatwg.org/#terminology-headers
 #[cfg(not(feature = "unsafe-headers"))]
-fn is_unsafe_header(header: &HeaderName) -> bool {
+pub(crate) fn is_unsafe_header(header: &HeaderName) -> bool {
     matches!(
         *header,
         header::ACCEPT_CHARSET
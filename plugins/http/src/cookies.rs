@@ -0,0 +1,304 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Cookie jars shared by the requests, optionally persisted to disk.
+
+use std::{
+    collections::HashMap,
+    io::BufReader,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use cookie_store::{CookieStore, RawCookie};
+use http::HeaderValue;
+use serde::Serialize;
+use tauri::{
+    command,
+    ipc::{CommandScope, GlobalScope},
+    State,
+};
+use url::Url;
+
+use crate::{commands::check_scope, scope::Entry, Error, Http, Result};
+
+/// Size of the nonce prepended to the encrypted cookies file.
+const NONCE_SIZE: usize = 12;
+
+/// Where and how the cookie jars are persisted.
+pub(crate) struct Persistence {
+    pub(crate) path: PathBuf,
+    pub(crate) key: Option<[u8; 32]>,
+}
+
+/// A cookie jar used as the cookie provider of the requests.
+#[derive(Default)]
+pub(crate) struct CookieJar {
+    store: RwLock<CookieStore>,
+    /// Whether the jar changed since it was last persisted.
+    dirty: AtomicBool,
+}
+
+impl CookieJar {
+    fn new(store: CookieStore) -> Self {
+        Self {
+            store: RwLock::new(store),
+            dirty: AtomicBool::new(false),
+        }
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies = cookie_headers
+            .filter_map(|header| header.to_str().ok())
+            .filter_map(|cookie| RawCookie::parse(cookie.to_string()).ok());
+        self.store
+            .write()
+            .unwrap()
+            .store_response_cookies(cookies, url);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let cookies = self
+            .store
+            .read()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if cookies.is_empty() {
+            None
+        } else {
+            HeaderValue::from_str(&cookies).ok()
+        }
+    }
+}
+
+/// The cookie jars, identified by name. Requests not using a named jar share the default one.
+pub(crate) struct Cookies {
+    jars: Mutex<HashMap<String, Arc<CookieJar>>>,
+    persistence: Option<Persistence>,
+}
+
+impl Cookies {
+    /// Loads the persisted cookie jars, expired cookies are dropped.
+    ///
+    /// A file that can't be read, e.g. corrupted or encrypted with another key, is moved aside
+    /// to a `.corrupted` copy and the jars start empty, so the app still starts.
+    pub(crate) fn load(persistence: Option<Persistence>) -> Self {
+        let jars = match persistence.as_ref().filter(|p| p.path.exists()) {
+            Some(persistence) => read_jars(persistence).unwrap_or_else(|_e| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    "failed to load the cookies from {}, starting with empty jars: {_e}",
+                    persistence.path.display()
+                );
+                let mut corrupted = persistence.path.clone().into_os_string();
+                corrupted.push(".corrupted");
+                let _ = std::fs::rename(&persistence.path, corrupted);
+                HashMap::new()
+            }),
+            None => HashMap::new(),
+        };
+
+        Self {
+            jars: Mutex::new(jars),
+            persistence,
+        }
+    }
+
+    /// The jar with the given name, or the default jar. It is created if needed.
+    pub(crate) fn jar(&self, name: Option<&str>) -> Arc<CookieJar> {
+        self.jars
+            .lock()
+            .unwrap()
+            .entry(name.unwrap_or_default().to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Writes the cookie jars to disk if persistence is enabled and a jar changed,
+    /// session cookies are kept so they survive app restarts.
+    pub(crate) fn save(&self) -> Result<()> {
+        let Some(persistence) = &self.persistence else {
+            return Ok(());
+        };
+
+        let jars = self.jars.lock().unwrap();
+        // `|` so every dirty flag is reset
+        let dirty = jars.values().fold(false, |dirty, jar| {
+            jar.dirty.swap(false, Ordering::Relaxed) | dirty
+        });
+        if !dirty {
+            return Ok(());
+        }
+
+        let mut stores = HashMap::new();
+        for (name, jar) in jars.iter() {
+            let mut store = Vec::new();
+            cookie_store::serde::json::save_incl_expired_and_nonpersistent(
+                &jar.store.read().unwrap(),
+                &mut store,
+            )
+            .map_err(|e| Error::CookieStore(e.to_string()))?;
+            stores.insert(name, serde_json::from_slice::<serde_json::Value>(&store)?);
+        }
+        drop(jars);
+
+        let mut data = serde_json::to_vec(&stores)?;
+        if let Some(key) = &persistence.key {
+            data = encrypt(key, &data)?;
+        }
+
+        if let Some(parent) = persistence.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&persistence.path, data)?;
+
+        Ok(())
+    }
+
+    /// Saves the cookie jars, only logging the failures.
+    pub(crate) fn try_save(&self) {
+        if let Err(_e) = self.save() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("failed to persist cookies: {_e}");
+        }
+    }
+}
+
+fn read_jars(persistence: &Persistence) -> Result<HashMap<String, Arc<CookieJar>>> {
+    let mut data = std::fs::read(&persistence.path)?;
+    if let Some(key) = &persistence.key {
+        data = decrypt(key, &data)?;
+    }
+
+    let stores: HashMap<String, serde_json::Value> = serde_json::from_slice(&data)?;
+    let mut jars = HashMap::new();
+    for (name, store) in stores {
+        let store = serde_json::to_vec(&store)?;
+        let store = cookie_store::serde::json::load(BufReader::new(store.as_slice()))
+            .map_err(|e| Error::CookieStore(e.to_string()))?;
+        jars.insert(name, Arc::new(CookieJar::new(store)));
+    }
+    Ok(jars)
+}
+
+fn encrypt(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let encrypted = cipher
+        .encrypt(&nonce, data)
+        .map_err(|e| Error::CookieStore(e.to_string()))?;
+
+    let mut out = nonce.to_vec();
+    out.extend(encrypted);
+    Ok(out)
+}
+
+fn decrypt(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < NONCE_SIZE {
+        return Err(Error::CookieStore("invalid encrypted cookies file".into()));
+    }
+    let (nonce, data) = data.split_at(NONCE_SIZE);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), data)
+        .map_err(|_| Error::CookieStore("failed to decrypt the cookies file".into()))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cookie {
+    name: String,
+    value: String,
+    domain: Option<String>,
+    path: Option<String>,
+    /// Expiration date in milliseconds since the Unix epoch, `None` for session cookies.
+    expires: Option<i64>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<String>,
+}
+
+impl From<&cookie_store::Cookie<'_>> for Cookie {
+    fn from(cookie: &cookie_store::Cookie<'_>) -> Self {
+        Self {
+            name: cookie.name().into(),
+            value: cookie.value().into(),
+            domain: cookie.domain().map(Into::into),
+            path: cookie.path().map(Into::into),
+            expires: cookie
+                .expires_datetime()
+                .map(|expires| (expires.unix_timestamp_nanos() / 1_000_000) as i64),
+            secure: cookie.secure().unwrap_or_default(),
+            http_only: cookie.http_only().unwrap_or_default(),
+            same_site: cookie.same_site().map(|s| s.to_string()),
+        }
+    }
+}
+
+/// Returns the cookies of the jar that would be sent with a request to `url`.
+#[command]
+pub async fn get_cookies(
+    state: State<'_, Http>,
+    url: Url,
+    jar: Option<String>,
+    command_scope: CommandScope<Entry>,
+    global_scope: GlobalScope<Entry>,
+) -> Result<Vec<Cookie>> {
+    check_scope(&url, &command_scope, &global_scope)?;
+
+    let jar = state.cookies.jar(jar.as_deref());
+    let store = jar.store.read().unwrap();
+    Ok(store.matches(&url).into_iter().map(Into::into).collect())
+}
+
+/// Stores a cookie in the jar as if it was received in a `Set-Cookie` header of a response from `url`.
+#[command]
+pub async fn set_cookie(
+    state: State<'_, Http>,
+    url: Url,
+    cookie: String,
+    jar: Option<String>,
+    command_scope: CommandScope<Entry>,
+    global_scope: GlobalScope<Entry>,
+) -> Result<()> {
+    check_scope(&url, &command_scope, &global_scope)?;
+
+    let cookie = RawCookie::parse(cookie).map_err(|e| Error::CookieStore(e.to_string()))?;
+    {
+        let jar = state.cookies.jar(jar.as_deref());
+        jar.store
+            .write()
+            .unwrap()
+            .insert_raw(&cookie, &url)
+            .map_err(|e| Error::CookieStore(e.to_string()))?;
+        jar.dirty.store(true, Ordering::Relaxed);
+    }
+
+    state.cookies.save()
+}
+
+/// Removes all the cookies of the jar.
+#[command]
+pub async fn clear_cookies(state: State<'_, Http>, jar: Option<String>) -> Result<()> {
+    {
+        let jar = state.cookies.jar(jar.as_deref());
+        jar.store.write().unwrap().clear();
+        jar.dirty.store(true, Ordering::Relaxed);
+    }
+
+    state.cookies.save()
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use std::path::PathBuf;
+
 use serde::{Serialize, Serializer};
 use url::Url;
 
@@ -31,6 +33,25 @@ pub enum Error {
     SchemeNotSupport(String),
     #[error("Request canceled")]
     RequestCanceled,
+    #[error("response body is already being read")]
+    ResponseBodyInUse,
+    #[error("request body stream is already used by another request")]
+    RequestBodyInUse,
+    #[error("request body stream was closed by the request")]
+    RequestBodyClosed,
+    #[error("only one of `data`, `dataStream`, `dataFile` and `multipart` can be set")]
+    MultipleBodies,
+    #[error("multipart bodies require the `multipart` feature of the http plugin")]
+    MultipartNotEnabled,
+    #[error("`connectTimeout`, `maxRedirections`, `proxy`, `tls` and `cookieJar` can't be set on a request sent with a client")]
+    ClientOptionsConflict,
+    #[error("unexpected invoke body")]
+    UnexpectedInvokeBody,
+    #[error("missing or invalid `rid` header of the request body stream")]
+    MissingBodyStreamRid,
+    /// Path not allowed by the fs plugin scope.
+    #[error("path not allowed on the configured fs scope: {}", .0.display())]
+    PathNotAllowed(PathBuf),
     #[error(transparent)]
     FsError(#[from] tauri_plugin_fs::Error),
     #[error("failed to process data url")]
@@ -39,6 +60,10 @@ pub enum Error {
     DataUrlDecodeError,
     #[error(transparent)]
     Tauri(#[from] tauri::Error),
+    #[error("{0} client identities are not supported by the enabled TLS backend")]
+    TlsIdentityNotSupported(&'static str),
+    #[error("cookie store error: {0}")]
+    CookieStore(String),
     #[error(transparent)]
     Utf8(#[from] std::string::FromUtf8Error),
 }
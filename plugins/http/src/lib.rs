@@ -4,40 +4,319 @@
 
 //! Access the HTTP client written in Rust.
 
+#[cfg(feature = "cookies")]
+use std::path::PathBuf;
+#[cfg(feature = "cache")]
+use std::sync::Arc;
+use std::sync::OnceLock;
+
 pub use reqwest;
 use tauri::{
-    plugin::{Builder, TauriPlugin},
+    plugin::{Builder as PluginBuilder, TauriPlugin},
     Manager, Runtime,
 };
 
+#[cfg(feature = "cache")]
+pub use cache::{CacheMode, CacheSettings};
+pub use client::ClientSettings;
 pub use error::{Error, Result};
+pub use proxy::{BasicAuth, Proxy, ProxyConfig, ProxyMode, ProxySettings, UrlOrConfig};
 
+#[cfg(feature = "cache")]
+mod cache;
+mod client;
 mod commands;
+#[cfg(feature = "cookies")]
+mod cookies;
 mod error;
+mod multipart;
+mod proxy;
+mod retry;
 mod scope;
+mod sse;
+mod tls;
+
+#[cfg(feature = "cookies")]
+const COOKIES_FILE_NAME: &str = "http-cookies.json";
+#[cfg(feature = "cookies")]
+const ENCRYPTED_COOKIES_FILE_NAME: &str = "http-cookies.bin";
+#[cfg(feature = "cache")]
+const CACHE_DIR_NAME: &str = "http-cache";
 
 pub(crate) struct Http {
+    proxy: Option<ProxySettings>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+    client_settings: ClientSettings,
+    /// Client of the requests without client options, shared so their connections are pooled.
+    client: OnceLock<reqwest::Client>,
     #[cfg(feature = "cookies")]
-    cookies_jar: std::sync::Arc<reqwest::cookie::Jar>,
+    cookies: cookies::Cookies,
+    #[cfg(feature = "cache")]
+    cache: Option<Arc<cache::Cache>>,
 }
 
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::<R>::new("http")
-        .setup(|app, _| {
-            let state = Http {
+#[derive(Default)]
+pub struct Builder {
+    proxy: Option<ProxySettings>,
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+    client_settings: ClientSettings,
+    #[cfg(feature = "cookies")]
+    persist_cookies: bool,
+    #[cfg(feature = "cookies")]
+    cookies_path: Option<PathBuf>,
+    #[cfg(feature = "cookies")]
+    cookies_encryption_key: Option<[u8; 32]>,
+    #[cfg(feature = "cache")]
+    cache: Option<CacheSettings>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The proxy settings of the requests that don't set a proxy,
+    /// the proxy of the system is used by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_http::{Proxy, ProxyConfig};
+    ///
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         tauri_plugin_http::Builder::new()
+    ///             .proxy(Proxy {
+    ///                 all: Some(
+    ///                     ProxyConfig {
+    ///                         url: "socks5://proxy.corp.example:1080".into(),
+    ///                         basic_auth: None,
+    ///                         no_proxy: Some("localhost, .corp.example".into()),
+    ///                     }
+    ///                     .into(),
+    ///                 ),
+    ///                 ..Default::default()
+    ///             })
+    ///             .build(),
+    ///     );
+    /// ```
+    pub fn proxy(mut self, proxy: impl Into<ProxySettings>) -> Self {
+        self.proxy.replace(proxy.into());
+        self
+    }
+
+    /// Trusts an additional root certificate in all the requests,
+    /// for instance the certificate authority of private enterprise APIs.
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// The client identity used for mutual TLS by the requests that don't set one.
+    pub fn identity(mut self, identity: reqwest::Identity) -> Self {
+        self.identity.replace(identity);
+        self
+    }
+
+    /// Connection pool and timeout settings of the requests,
+    /// the clients created from the webview start from them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tauri_plugin_http::ClientSettings;
+    ///
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         tauri_plugin_http::Builder::new()
+    ///             .client_settings(ClientSettings {
+    ///                 pool_max_idle_per_host: Some(32),
+    ///                 pool_idle_timeout: Some(Duration::from_secs(90)),
+    ///                 read_timeout: Some(Duration::from_secs(30)),
+    ///                 tcp_nodelay: Some(true),
+    ///                 ..Default::default()
+    ///             })
+    ///             .build(),
+    ///     );
+    /// ```
+    pub fn client_settings(mut self, settings: ClientSettings) -> Self {
+        self.client_settings = settings;
+        self
+    }
+
+    /// Persists the cookies, including the session ones, so they survive app restarts.
+    ///
+    /// They are written to `http-cookies.json` in the app data directory,
+    /// unless another path is set with [`Self::cookies_path`].
+    #[cfg(feature = "cookies")]
+    pub fn persist_cookies(mut self) -> Self {
+        self.persist_cookies = true;
+        self
+    }
+
+    /// Path of the file the cookies are persisted to, this enables [`Self::persist_cookies`].
+    #[cfg(feature = "cookies")]
+    pub fn cookies_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persist_cookies = true;
+        self.cookies_path.replace(path.into());
+        self
+    }
+
+    /// Encrypts the persisted cookies with ChaCha20-Poly1305 using the given key.
+    ///
+    /// The default file name becomes `http-cookies.bin`. Keep the key out of the app binary,
+    /// for instance by storing it in the keychain of the operating system.
+    #[cfg(feature = "cookies")]
+    pub fn cookies_encryption_key(mut self, key: [u8; 32]) -> Self {
+        self.cookies_encryption_key.replace(key);
+        self
+    }
+
+    /// Caches the responses in memory, and on disk if [`CacheSettings::persist`] is set,
+    /// honoring their `Cache-Control`, `Expires`, `ETag` and `Last-Modified` headers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tauri_plugin_http::CacheSettings;
+    ///
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         tauri_plugin_http::Builder::new()
+    ///             .cache(CacheSettings {
+    ///                 persist: true,
+    ///                 ..Default::default()
+    ///             })
+    ///             .build(),
+    ///     );
+    /// ```
+    #[cfg(feature = "cache")]
+    pub fn cache(mut self, settings: CacheSettings) -> Self {
+        self.cache.replace(settings);
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        PluginBuilder::<R>::new("http")
+            .setup(move |app, _| {
                 #[cfg(feature = "cookies")]
-                cookies_jar: std::sync::Arc::new(reqwest::cookie::Jar::default()),
-            };
-
-            app.manage(state);
-
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
-            commands::fetch,
-            commands::fetch_cancel,
-            commands::fetch_send,
-            commands::fetch_read_body,
-        ])
-        .build()
+                let persistence = if self.persist_cookies {
+                    let key = self.cookies_encryption_key;
+                    let path = match self.cookies_path {
+                        Some(path) => path,
+                        None => app.path().app_data_dir()?.join(if key.is_some() {
+                            ENCRYPTED_COOKIES_FILE_NAME
+                        } else {
+                            COOKIES_FILE_NAME
+                        }),
+                    };
+                    Some(cookies::Persistence { path, key })
+                } else {
+                    None
+                };
+
+                #[cfg(feature = "cache")]
+                let cache = match self.cache {
+                    Some(settings) => {
+                        let path = match &settings.path {
+                            Some(path) => Some(path.clone()),
+                            None if settings.persist => {
+                                Some(app.path().app_cache_dir()?.join(CACHE_DIR_NAME))
+                            }
+                            None => None,
+                        };
+                        Some(Arc::new(cache::Cache::new(settings, path)?))
+                    }
+                    None => None,
+                };
+
+                let state = Http {
+                    proxy: self.proxy,
+                    root_certificates: self.root_certificates,
+                    identity: self.identity,
+                    client_settings: self.client_settings,
+                    client: OnceLock::new(),
+                    #[cfg(feature = "cookies")]
+                    cookies: cookies::Cookies::load(persistence),
+                    #[cfg(feature = "cache")]
+                    cache,
+                };
+
+                app.manage(state);
+
+                Ok(())
+            })
+            .invoke_handler(tauri::generate_handler![
+                commands::fetch,
+                commands::fetch_cancel,
+                commands::fetch_create_body_stream,
+                commands::fetch_write_body,
+                commands::fetch_send,
+                commands::fetch_read_body,
+                commands::fetch_read_body_pull,
+                commands::fetch_download,
+                client::create_client,
+                sse::event_source,
+                #[cfg(feature = "cookies")]
+                cookies::get_cookies,
+                #[cfg(feature = "cookies")]
+                cookies::set_cookie,
+                #[cfg(feature = "cookies")]
+                cookies::clear_cookies,
+                #[cfg(feature = "cache")]
+                cache::clear_cache,
+            ])
+            .build()
+    }
+}
+
+impl Http {
+    /// A client builder with the settings of the plugin, except the [`ClientSettings`] that
+    /// would also cut long-lived connections such as event sources.
+    /// The proxy of the request takes precedence over the one of the plugin.
+    pub(crate) fn client_builder(
+        &self,
+        proxy: Option<&ProxySettings>,
+        #[allow(unused)] cookie_jar: Option<&str>,
+    ) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::ClientBuilder::new();
+
+        if let Some(proxy) = proxy.or(self.proxy.as_ref()) {
+            builder = proxy.apply(builder)?;
+        }
+
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(identity.clone());
+        }
+
+        #[cfg(feature = "cookies")]
+        {
+            builder = builder.cookie_provider(self.cookies.jar(cookie_jar));
+        }
+
+        Ok(builder)
+    }
+
+    /// The shared client of the requests without client options.
+    pub(crate) fn client(&self) -> Result<reqwest::Client> {
+        if let Some(client) = self.client.get() {
+            return Ok(client.clone());
+        }
+        let client = self
+            .client_settings
+            .apply(self.client_builder(None, None)?)
+            .build()?;
+        // a concurrent request may have set it first, both clients are equivalent
+        Ok(self.client.get_or_init(|| client).clone())
+    }
+}
+
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new().build()
 }
@@ -0,0 +1,139 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! `multipart/form-data` request bodies with file parts streamed from disk.
+
+use serde::{Deserialize, Serialize};
+use tauri::{ipc::Channel, Runtime, Webview};
+use tauri_plugin_fs::SafeFilePath;
+
+/// A part of a `multipart/form-data` body.
+#[derive(Debug, Deserialize)]
+#[serde(untagged, rename_all_fields = "camelCase")]
+#[cfg_attr(not(feature = "multipart"), allow(dead_code))]
+pub enum MultipartPart {
+    /// A file read from a path allowed on the fs plugin scope, streamed from disk.
+    File {
+        name: String,
+        path: SafeFilePath,
+        /// Defaults to the name of the file.
+        file_name: Option<String>,
+        mime_type: Option<String>,
+    },
+    Bytes {
+        name: String,
+        data: Vec<u8>,
+        file_name: Option<String>,
+        mime_type: Option<String>,
+    },
+    Text {
+        name: String,
+        value: String,
+    },
+}
+
+/// Upload progress of a file part.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(feature = "multipart"), allow(dead_code))]
+pub struct UploadProgress {
+    /// Index of the part in the form.
+    part: usize,
+    name: String,
+    bytes: u64,
+    total_bytes: u64,
+}
+
+/// Sets the parts as the `multipart/form-data` body of the request,
+/// reporting the progress of the file parts on `on_progress`.
+#[cfg(feature = "multipart")]
+pub(crate) async fn attach_form<R: Runtime>(
+    webview: &Webview<R>,
+    request: reqwest::RequestBuilder,
+    parts: Vec<MultipartPart>,
+    on_progress: Option<Channel<UploadProgress>>,
+) -> crate::Result<reqwest::RequestBuilder> {
+    use std::time::Instant;
+
+    use reqwest::multipart::{Form, Part};
+
+    use crate::commands::{body_from_file, PROGRESS_INTERVAL};
+
+    fn with_metadata(
+        mut part: Part,
+        file_name: Option<String>,
+        mime_type: Option<String>,
+    ) -> crate::Result<Part> {
+        if let Some(file_name) = file_name {
+            part = part.file_name(file_name);
+        }
+        if let Some(mime_type) = mime_type {
+            part = part.mime_str(&mime_type)?;
+        }
+        Ok(part)
+    }
+
+    let mut form = Form::new();
+    for (index, part) in parts.into_iter().enumerate() {
+        form = match part {
+            MultipartPart::File {
+                name,
+                path,
+                file_name,
+                mime_type,
+            } => {
+                let on_progress = on_progress.clone();
+                let progress_name = name.clone();
+                let mut last_progress: Option<Instant> = None;
+
+                let (body, path, len) = body_from_file(webview, path, move |bytes, total_bytes| {
+                    let Some(on_progress) = &on_progress else {
+                        return;
+                    };
+                    if bytes == total_bytes
+                        || last_progress.map_or(true, |last| last.elapsed() >= PROGRESS_INTERVAL)
+                    {
+                        last_progress.replace(Instant::now());
+                        let _ = on_progress.send(UploadProgress {
+                            part: index,
+                            name: progress_name.clone(),
+                            bytes,
+                            total_bytes,
+                        });
+                    }
+                })
+                .await?;
+
+                let file_name = file_name.or_else(|| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                });
+                let part = Part::stream_with_length(body, len);
+                form.part(name, with_metadata(part, file_name, mime_type)?)
+            }
+            MultipartPart::Bytes {
+                name,
+                data,
+                file_name,
+                mime_type,
+            } => form.part(
+                name,
+                with_metadata(Part::bytes(data), file_name, mime_type)?,
+            ),
+            MultipartPart::Text { name, value } => form.text(name, value),
+        };
+    }
+
+    Ok(request.multipart(form))
+}
+
+#[cfg(not(feature = "multipart"))]
+pub(crate) async fn attach_form<R: Runtime>(
+    _webview: &Webview<R>,
+    _request: reqwest::RequestBuilder,
+    _parts: Vec<MultipartPart>,
+    _on_progress: Option<Channel<UploadProgress>>,
+) -> crate::Result<reqwest::RequestBuilder> {
+    Err(crate::Error::MultipartNotEnabled)
+}
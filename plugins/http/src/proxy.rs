@@ -0,0 +1,147 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Proxy configuration of the requests.
+
+use reqwest::NoProxy;
+use serde::Deserialize;
+
+/// How the requests are proxied.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ProxySettings {
+    Mode(ProxyMode),
+    Custom(Proxy),
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProxyMode {
+    /// Use the proxy of the system, read from the `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`
+    /// and `NO_PROXY` environment variables, the Windows registry and the macOS system configuration.
+    ///
+    /// This is the default.
+    System,
+    /// Connect directly, ignoring the proxy of the system.
+    None,
+}
+
+/// Configuration of a proxy that a Client should pass requests to.
+///
+/// SOCKS5 proxies, with `socks5://` or `socks5h://` URLs, require the `socks` feature.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proxy {
+    /// Proxy all traffic to the passed URL.
+    pub all: Option<UrlOrConfig>,
+    /// Proxy all HTTP traffic to the passed URL.
+    pub http: Option<UrlOrConfig>,
+    /// Proxy all HTTPS traffic to the passed URL.
+    pub https: Option<UrlOrConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(untagged)]
+pub enum UrlOrConfig {
+    Url(String),
+    Config(ProxyConfig),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    /// The URL of the proxy server.
+    pub url: String,
+    /// Set the `Proxy-Authorization` header using Basic auth.
+    pub basic_auth: Option<BasicAuth>,
+    /// Comma-separated hosts, domains and IP ranges that shouldn't be proxied.
+    pub no_proxy: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+impl From<ProxyMode> for ProxySettings {
+    fn from(mode: ProxyMode) -> Self {
+        Self::Mode(mode)
+    }
+}
+
+impl From<Proxy> for ProxySettings {
+    fn from(proxy: Proxy) -> Self {
+        Self::Custom(proxy)
+    }
+}
+
+impl From<String> for UrlOrConfig {
+    fn from(url: String) -> Self {
+        Self::Url(url)
+    }
+}
+
+impl From<ProxyConfig> for UrlOrConfig {
+    fn from(config: ProxyConfig) -> Self {
+        Self::Config(config)
+    }
+}
+
+#[inline]
+fn proxy_creator(
+    url_or_config: &UrlOrConfig,
+    proxy_fn: fn(String) -> reqwest::Result<reqwest::Proxy>,
+) -> reqwest::Result<reqwest::Proxy> {
+    match url_or_config {
+        UrlOrConfig::Url(url) => Ok(proxy_fn(url.clone())?),
+        UrlOrConfig::Config(ProxyConfig {
+            url,
+            basic_auth,
+            no_proxy,
+        }) => {
+            let mut proxy = proxy_fn(url.clone())?;
+            if let Some(basic_auth) = basic_auth {
+                proxy = proxy.basic_auth(&basic_auth.username, &basic_auth.password);
+            }
+            if let Some(no_proxy) = no_proxy {
+                proxy = proxy.no_proxy(NoProxy::from_string(no_proxy));
+            }
+            Ok(proxy)
+        }
+    }
+}
+
+impl ProxySettings {
+    /// Configures the client builder to use these proxy settings.
+    pub(crate) fn apply(
+        &self,
+        mut builder: reqwest::ClientBuilder,
+    ) -> crate::Result<reqwest::ClientBuilder> {
+        let Proxy { all, http, https } = match self {
+            // reqwest uses the system proxy unless a proxy is set
+            Self::Mode(ProxyMode::System) => return Ok(builder),
+            Self::Mode(ProxyMode::None) => return Ok(builder.no_proxy()),
+            Self::Custom(proxy) => proxy,
+        };
+
+        if let Some(all) = all {
+            let proxy = proxy_creator(all, reqwest::Proxy::all)?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(http) = http {
+            let proxy = proxy_creator(http, reqwest::Proxy::http)?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(https) = https {
+            let proxy = proxy_creator(https, reqwest::Proxy::https)?;
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(builder)
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Retry policy of the requests.
+
+use std::time::{Duration, SystemTime};
+
+use http::header;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// Maximum number of attempts, including the first one.
+    #[serde(default = "default_max_attempts")]
+    max_attempts: u32,
+    #[serde(default)]
+    backoff: Backoff,
+    /// Status codes of the responses that are retried.
+    #[serde(default = "default_status_codes")]
+    status_codes: Vec<u16>,
+    /// Whether connection errors and timeouts are retried.
+    #[serde(default = "default_true")]
+    network_errors: bool,
+    /// Whether the delay of the `Retry-After` header is used instead of the backoff.
+    #[serde(default = "default_true")]
+    respect_retry_after: bool,
+}
+
+/// Exponential backoff between the attempts, in milliseconds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Backoff {
+    #[serde(default = "default_initial_delay")]
+    initial_delay: u64,
+    #[serde(default = "default_max_delay")]
+    max_delay: u64,
+    #[serde(default = "default_multiplier")]
+    multiplier: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: default_initial_delay(),
+            max_delay: default_max_delay(),
+            multiplier: default_multiplier(),
+        }
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_status_codes() -> Vec<u16> {
+    vec![408, 429, 500, 502, 503, 504]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_initial_delay() -> u64 {
+    500
+}
+
+fn default_max_delay() -> u64 {
+    30_000
+}
+
+fn default_multiplier() -> f64 {
+    2.0
+}
+
+impl Backoff {
+    /// Delay before the attempt following the given one, starting at 1.
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial_delay as f64 * self.multiplier.powi(attempt as i32 - 1);
+        Duration::from_millis(delay.min(self.max_delay as f64) as u64)
+    }
+}
+
+/// Parses the `Retry-After` header, either a delay in seconds or an HTTP date.
+fn retry_after(res: &reqwest::Response) -> Option<Duration> {
+    let value = res.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    match value.trim().parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        // a date in the past means no delay
+        Err(_) => Some(
+            httpdate::parse_http_date(value)
+                .ok()?
+                .duration_since(SystemTime::now())
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Sends the request, sending it again according to the retry policy.
+///
+/// Requests with a streamed body can't be sent again, so they are never retried.
+pub(crate) async fn send_with_retry(
+    mut request: reqwest::RequestBuilder,
+    retry: Option<&RetryConfig>,
+) -> crate::Result<reqwest::Response> {
+    let Some(retry) = retry else {
+        return Ok(request.send().await?);
+    };
+
+    let mut attempt = 1;
+    loop {
+        let next_request = if attempt < retry.max_attempts {
+            request.try_clone()
+        } else {
+            None
+        };
+        let Some(next_request) = next_request else {
+            return Ok(request.send().await?);
+        };
+
+        let delay = match request.send().await {
+            Ok(res) if retry.status_codes.contains(&res.status().as_u16()) => {
+                match retry_after(&res).filter(|_| retry.respect_retry_after) {
+                    // the server asks to wait longer than allowed, give up
+                    Some(delay) if delay > Duration::from_millis(retry.backoff.max_delay) => {
+                        return Ok(res)
+                    }
+                    Some(delay) => delay,
+                    None => retry.backoff.delay(attempt),
+                }
+            }
+            Ok(res) => return Ok(res),
+            Err(e) if retry.network_errors && (e.is_connect() || e.is_timeout()) => {
+                retry.backoff.delay(attempt)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!("retrying request after {delay:?}, attempt {attempt} failed");
+
+        tokio::time::sleep(delay).await;
+        request = next_request;
+        attempt += 1;
+    }
+}
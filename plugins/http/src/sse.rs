@@ -0,0 +1,339 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Server-Sent Events connections maintained on the Rust side.
+//!
+//! See <https://html.spec.whatwg.org/multipage/server-sent-events.html>.
+
+use std::{str::FromStr, time::Duration};
+
+use http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    async_runtime::JoinHandle,
+    command,
+    ipc::{Channel, CommandScope, GlobalScope},
+    Manager, ResourceId, Runtime, State, Webview,
+};
+use url::Url;
+
+use crate::{commands::check_scope, proxy::ProxySettings, scope::Entry, Http, Result};
+
+/// Reconnection time used until the server sets one with a `retry` field.
+const DEFAULT_RECONNECTION_TIME: u64 = 3000;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSourceOptions {
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    proxy: Option<ProxySettings>,
+    #[cfg_attr(not(feature = "cookies"), allow(unused))]
+    cookie_jar: Option<String>,
+    /// Reconnection time in milliseconds, until the server sets one.
+    reconnection_time: Option<u64>,
+    /// Sent as the `Last-Event-ID` header of the first connection.
+    last_event_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageEvent {
+    /// The event type, `message` unless set by the server.
+    event: String,
+    data: String,
+    last_event_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum EventSourceEvent {
+    /// The connection was established.
+    Open,
+    Message(MessageEvent),
+    /// The connection failed or was lost, a reconnection follows unless it is closed.
+    Error(String),
+    /// The connection failed permanently and won't be reestablished.
+    Closed,
+}
+
+/// Handle of the connection task, closing the resource aborts it.
+struct EventSource(JoinHandle<()>);
+impl tauri::Resource for EventSource {}
+
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Incremental parser of a `text/event-stream` body.
+#[derive(Debug, Default)]
+struct EventStreamParser {
+    line: Vec<u8>,
+    /// The previous chunk ended with a CR that may be followed by a LF.
+    pending_cr: bool,
+    /// Whether the first line was read, its BOM must be skipped.
+    started: bool,
+    event: String,
+    data: String,
+    last_event_id: String,
+    /// Reconnection time set by the server.
+    retry: Option<u64>,
+}
+
+impl EventStreamParser {
+    fn new(last_event_id: String) -> Self {
+        Self {
+            last_event_id,
+            ..Default::default()
+        }
+    }
+
+    /// Resets the state of a previous connection, keeping the last event id.
+    fn reset(&mut self) {
+        *self = Self {
+            last_event_id: std::mem::take(&mut self.last_event_id),
+            retry: self.retry,
+            ..Default::default()
+        };
+    }
+
+    /// Parses the chunk, returning the events it completed.
+    fn feed(&mut self, chunk: &[u8]) -> Vec<MessageEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let pending_cr = std::mem::take(&mut self.pending_cr);
+            match byte {
+                b'\n' if pending_cr => {}
+                b'\r' | b'\n' => {
+                    self.pending_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<MessageEvent> {
+        let mut line = String::from_utf8_lossy(line);
+        if !self.started {
+            self.started = true;
+            if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                line = stripped.to_string().into();
+            }
+        }
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+        match field {
+            "event" => self.event = value.into(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.into(),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.retry.replace(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<MessageEvent> {
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+
+        Some(MessageEvent {
+            event: if event.is_empty() {
+                "message".into()
+            } else {
+                event
+            },
+            data,
+            last_event_id: self.last_event_id.clone(),
+        })
+    }
+}
+
+/// Connects to the `text/event-stream` at `url`, reconnecting with the last event id when
+/// the connection is lost. The connection is closed with its resource.
+#[command]
+pub async fn event_source<R: Runtime>(
+    webview: Webview<R>,
+    state: State<'_, Http>,
+    url: Url,
+    options: Option<EventSourceOptions>,
+    on_event: Channel<EventSourceEvent>,
+    command_scope: CommandScope<Entry>,
+    global_scope: GlobalScope<Entry>,
+) -> Result<ResourceId> {
+    check_scope(&url, &command_scope, &global_scope)?;
+
+    let options = options.unwrap_or_default();
+
+    let mut headers = HeaderMap::new();
+    for (h, v) in options.headers {
+        let name = HeaderName::from_str(&h)?;
+        #[cfg(not(feature = "unsafe-headers"))]
+        if crate::commands::is_unsafe_header(&name) {
+            continue;
+        }
+        headers.append(name, HeaderValue::from_str(&v)?);
+    }
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_static("text/event-stream"),
+    );
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+
+    let client = state
+        .client_builder(options.proxy.as_ref(), options.cookie_jar.as_deref())?
+        .build()?;
+
+    let mut parser = EventStreamParser::new(options.last_event_id.unwrap_or_default());
+    let default_reconnection_time = options
+        .reconnection_time
+        .unwrap_or(DEFAULT_RECONNECTION_TIME);
+
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            let mut headers = headers.clone();
+            if !parser.last_event_id.is_empty() {
+                if let Ok(id) = HeaderValue::from_str(&parser.last_event_id) {
+                    headers.insert("last-event-id", id);
+                }
+            }
+            parser.reset();
+
+            match client.get(url.clone()).headers(headers).send().await {
+                Ok(mut res) => {
+                    let is_event_stream = res
+                        .headers()
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .is_some_and(|v| v.starts_with("text/event-stream"));
+                    if res.status() != StatusCode::OK || !is_event_stream {
+                        let _ = on_event.send(EventSourceEvent::Error(format!(
+                            "unexpected response with status {} and content type {:?}",
+                            res.status(),
+                            res.headers().get(header::CONTENT_TYPE)
+                        )));
+                        let _ = on_event.send(EventSourceEvent::Closed);
+                        return;
+                    }
+
+                    if on_event.send(EventSourceEvent::Open).is_err() {
+                        return;
+                    }
+
+                    let error = loop {
+                        match res.chunk().await {
+                            Ok(Some(chunk)) => {
+                                for event in parser.feed(&chunk) {
+                                    if on_event.send(EventSourceEvent::Message(event)).is_err() {
+                                        return;
+                                    }
+                                }
+                            }
+                            Ok(None) => {
+                                break "the connection was closed by the server".to_string()
+                            }
+                            Err(e) => break e.to_string(),
+                        }
+                    };
+                    let _ = on_event.send(EventSourceEvent::Error(error));
+                }
+                Err(e) => {
+                    let _ = on_event.send(EventSourceEvent::Error(e.to_string()));
+                }
+            }
+
+            let reconnection_time = parser.retry.unwrap_or(default_reconnection_time);
+            tokio::time::sleep(Duration::from_millis(reconnection_time)).await;
+        }
+    });
+
+    Ok(webview.resources_table().add(EventSource(task)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(event: &str, data: &str, last_event_id: &str) -> MessageEvent {
+        MessageEvent {
+            event: event.into(),
+            data: data.into(),
+            last_event_id: last_event_id.into(),
+        }
+    }
+
+    #[test]
+    fn parses_events() {
+        let mut parser = EventStreamParser::default();
+        let events = parser.feed(
+            b"\xEF\xBB\xBFdata: first\n\n: comment\nevent: update\ndata:a\ndata: b\nid: 7\n\n",
+        );
+        assert_eq!(
+            events,
+            vec![
+                message("message", "first", ""),
+                message("update", "a\nb", "7")
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_split_chunks_and_line_endings() {
+        let mut parser = EventStreamParser::default();
+        assert!(parser.feed(b"data: he").is_empty());
+        assert!(parser.feed(b"llo\r").is_empty());
+        assert_eq!(
+            parser.feed(b"\n\r\ndata: world\r\r"),
+            vec![
+                message("message", "hello", ""),
+                message("message", "world", "")
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_events_without_data() {
+        let mut parser = EventStreamParser::default();
+        assert!(parser.feed(b"event: ping\nid: 3\n\n").is_empty());
+        assert_eq!(parser.last_event_id, "3");
+        assert_eq!(parser.feed(b"data\n\n"), vec![message("message", "", "3")]);
+    }
+
+    #[test]
+    fn keeps_last_event_id_and_retry_on_reset() {
+        let mut parser = EventStreamParser::new("1".into());
+        parser.feed(b"retry: 500\nretry: nope\ndata: partial");
+        parser.reset();
+        assert_eq!(parser.last_event_id, "1");
+        assert_eq!(parser.retry, Some(500));
+        assert!(parser.feed(b"\n\n").is_empty());
+    }
+}
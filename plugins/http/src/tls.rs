@@ -0,0 +1,120 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Client identities and trusted root certificates of the requests.
+
+use reqwest::{Certificate, Identity};
+use serde::Deserialize;
+use tauri::{Runtime, Webview};
+use tauri_plugin_fs::SafeFilePath;
+
+use crate::{commands::check_fs_scope, Error, Result};
+
+/// A certificate or key read from a path allowed on the fs plugin scope, or passed as bytes.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CertificateSource {
+    Path { path: SafeFilePath },
+    Data { data: Vec<u8> },
+}
+
+impl CertificateSource {
+    async fn read<R: Runtime>(self, webview: &Webview<R>) -> Result<Vec<u8>> {
+        match self {
+            Self::Path { path } => {
+                let path = check_fs_scope(webview, path)?;
+                Ok(tokio::fs::read(path).await?)
+            }
+            Self::Data { data } => Ok(data),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum IdentityConfig {
+    /// A DER-encoded PKCS#12 archive, requires a `native-tls` feature.
+    Pkcs12 {
+        source: CertificateSource,
+        password: String,
+    },
+    /// A PEM file containing the certificate chain and the private key, requires a `rustls-tls` feature.
+    Pem { source: CertificateSource },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TlsConfig {
+    /// Additional trusted root certificates, PEM bundles or DER-encoded.
+    #[serde(default)]
+    root_certificates: Vec<CertificateSource>,
+    /// Client identity used for mutual TLS.
+    identity: Option<IdentityConfig>,
+    /// Whether the built-in root certificates are trusted, defaults to `true`.
+    built_in_root_certificates: Option<bool>,
+}
+
+fn parse_certificates(data: &[u8]) -> Result<Vec<Certificate>> {
+    if data.starts_with(b"-----BEGIN") {
+        Ok(Certificate::from_pem_bundle(data)?)
+    } else {
+        Ok(vec![Certificate::from_der(data)?])
+    }
+}
+
+async fn parse_identity<R: Runtime>(
+    webview: &Webview<R>,
+    identity: IdentityConfig,
+) -> Result<Identity> {
+    match identity {
+        #[cfg(feature = "native-tls")]
+        IdentityConfig::Pkcs12 { source, password } => {
+            let der = source.read(webview).await?;
+            Ok(Identity::from_pkcs12_der(&der, &password)?)
+        }
+        #[cfg(any(
+            feature = "rustls-tls",
+            feature = "rustls-tls-manual-roots",
+            feature = "rustls-tls-webpki-roots",
+            feature = "rustls-tls-native-roots"
+        ))]
+        IdentityConfig::Pem { source } => {
+            let pem = source.read(webview).await?;
+            Ok(Identity::from_pem(&pem)?)
+        }
+        #[allow(unreachable_patterns)]
+        identity => {
+            let _ = webview;
+            Err(Error::TlsIdentityNotSupported(match identity {
+                IdentityConfig::Pkcs12 { .. } => "PKCS#12",
+                IdentityConfig::Pem { .. } => "PEM",
+            }))
+        }
+    }
+}
+
+impl TlsConfig {
+    /// Configures the client builder to trust the root certificates and use the identity.
+    pub(crate) async fn apply<R: Runtime>(
+        self,
+        webview: &Webview<R>,
+        mut builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder> {
+        for source in self.root_certificates {
+            for certificate in parse_certificates(&source.read(webview).await?)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(identity) = self.identity {
+            builder = builder.identity(parse_identity(webview, identity).await?);
+        }
+
+        if let Some(built_in) = self.built_in_root_certificates {
+            builder = builder.tls_built_in_root_certs(built_in);
+        }
+
+        Ok(builder)
+    }
+}
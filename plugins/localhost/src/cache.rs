@@ -0,0 +1,137 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Validators and `Cache-Control` of the assets, so reloads only transfer the changed assets.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{
+    header::{IF_MODIFIED_SINCE, IF_NONE_MATCH},
+    HeaderMap, Method,
+};
+use sha2::{Digest, Sha256};
+
+use crate::compression::Encoding;
+
+/// The entity tags and `Cache-Control` rules of the assets.
+pub(crate) struct AssetCache {
+    etags: Mutex<HashMap<String, String>>,
+    /// The assets are embedded in the app, so they are considered modified when the server starts.
+    last_modified: SystemTime,
+    rules: Vec<(glob::Pattern, String)>,
+}
+
+impl AssetCache {
+    /// Compiles the `Cache-Control` rules, the first rule matching a path applies.
+    pub(crate) fn new(rules: &[(String, String)]) -> Result<Self, glob::PatternError> {
+        let rules = rules
+            .iter()
+            .map(|(pattern, value)| Ok((glob::Pattern::new(pattern)?, value.clone())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            etags: Default::default(),
+            last_modified: http_time(SystemTime::now()),
+            rules,
+        })
+    }
+
+    /// Returns the strong entity tag of the asset, hashed once per path as the assets do not change.
+    pub(crate) fn etag(&self, path: &str, body: &[u8]) -> String {
+        if let Some(etag) = self.etags.lock().unwrap().get(path) {
+            return etag.clone();
+        }
+
+        let digest = Sha256::digest(body);
+        let mut etag = String::with_capacity(34);
+        etag.push('"');
+        for byte in &digest[..16] {
+            let _ = write!(etag, "{byte:02x}");
+        }
+        etag.push('"');
+
+        self.etags
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), etag.clone());
+        etag
+    }
+
+    pub(crate) fn last_modified(&self) -> String {
+        httpdate::fmt_http_date(self.last_modified)
+    }
+
+    pub(crate) fn cache_control(&self, path: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the client already has the asset, given the entity tags of its representations.
+    pub(crate) fn is_not_modified(
+        &self,
+        method: &Method,
+        request: &HeaderMap,
+        etags: &[&str],
+    ) -> bool {
+        is_not_modified(method, request, etags, self.last_modified)
+    }
+}
+
+/// Whether the client already has the content, given its entity tags and modification time.
+///
+/// `If-Modified-Since` is ignored when the request has `If-None-Match`.
+pub(crate) fn is_not_modified(
+    method: &Method,
+    request: &HeaderMap,
+    etags: &[&str],
+    last_modified: SystemTime,
+) -> bool {
+    if *method != Method::GET && *method != Method::HEAD {
+        return false;
+    }
+
+    if request.contains_key(IF_NONE_MATCH) {
+        // weak comparison, the entity tags sent by the client may be weak
+        return request
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|tag| {
+                let tag = tag.trim();
+                tag == "*" || etags.contains(&tag.trim_start_matches("W/"))
+            });
+    }
+
+    request
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok())
+        .is_some_and(|since| last_modified <= since)
+}
+
+/// Truncates the time to the second, the precision of the HTTP dates.
+pub(crate) fn http_time(time: SystemTime) -> SystemTime {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// The entity tag of the asset compressed with the encoding, a different representation than the identity.
+pub(crate) fn encoded_etag(etag: &str, encoding: Encoding) -> String {
+    format!(
+        "{}-{}\"",
+        etag.strip_suffix('"').unwrap_or(etag),
+        encoding.name()
+    )
+}
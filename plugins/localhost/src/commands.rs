@@ -0,0 +1,12 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri::State;
+
+use crate::{LocalhostHandle, Metrics};
+
+#[tauri::command]
+pub fn metrics(handle: State<'_, LocalhostHandle>) -> Metrics {
+    handle.metrics()
+}
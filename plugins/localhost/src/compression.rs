@@ -0,0 +1,195 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Compression of the text assets, negotiated with the `Accept-Encoding` header.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use http::{header::ACCEPT_ENCODING, HeaderMap};
+use hyper::body::Bytes;
+
+/// The compression of the responses, see [`crate::Builder::compression`].
+#[derive(Debug, Clone)]
+pub struct CompressionConfig {
+    gzip: bool,
+    brotli: bool,
+    min_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            brotli: true,
+            min_size: 1024,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Enables gzip and brotli for the assets of at least 1 KiB.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to compress with gzip. Default: true
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Whether to compress with brotli, preferred over gzip when the client accepts both. Default: true
+    pub fn brotli(mut self, enabled: bool) -> Self {
+        self.brotli = enabled;
+        self
+    }
+
+    /// The size in bytes under which the assets are sent uncompressed. Default: 1024
+    pub fn min_size(mut self, bytes: usize) -> Self {
+        self.min_size = bytes;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Encoding {
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Brotli => "br",
+            Self::Gzip => "gzip",
+        }
+    }
+
+    fn encode(self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Brotli => {
+                // quality 9 is much faster than the maximum for a similar ratio on scripts
+                let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 9, 22);
+                encoder.write_all(body)?;
+                encoder.flush()?;
+                Ok(encoder.into_inner())
+            }
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(body)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+/// Whether the MIME type is text-like content worth compressing.
+pub(crate) fn is_compressible(mime_type: &str) -> bool {
+    let essence = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence.as_str(),
+            "application/javascript"
+                | "application/json"
+                | "application/manifest+json"
+                | "application/wasm"
+                | "application/xml"
+                | "image/svg+xml"
+        )
+}
+
+/// Compresses the assets, caching the encoded bodies by path as the assets do not change.
+pub(crate) struct Compressor {
+    config: CompressionConfig,
+    cache: Mutex<HashMap<(String, Encoding), Bytes>>,
+}
+
+impl Compressor {
+    pub(crate) fn new(config: CompressionConfig) -> Arc<Self> {
+        Arc::new(Self {
+            config,
+            cache: Default::default(),
+        })
+    }
+
+    /// Picks the supported encoding with the highest quality value for the asset, brotli on ties.
+    ///
+    /// Returns `None` if the asset should be sent uncompressed.
+    pub(crate) fn negotiate(
+        &self,
+        request: &HeaderMap,
+        mime_type: &str,
+        len: usize,
+    ) -> Option<Encoding> {
+        if len < self.config.min_size || !is_compressible(mime_type) {
+            return None;
+        }
+
+        let mut best: Option<(Encoding, f32)> = None;
+        for value in request.get_all(ACCEPT_ENCODING) {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for item in value.split(',') {
+                let mut params = item.split(';');
+                let coding = params.next().unwrap_or_default().trim();
+                let quality = params
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let encoding = match coding.to_ascii_lowercase().as_str() {
+                    "br" if self.config.brotli => Encoding::Brotli,
+                    "gzip" | "x-gzip" if self.config.gzip => Encoding::Gzip,
+                    _ => continue,
+                };
+                if quality <= 0.0 {
+                    continue;
+                }
+                let better = match best {
+                    None => true,
+                    Some((current, q)) => {
+                        quality > q
+                            || (quality == q && encoding == Encoding::Brotli && current != encoding)
+                    }
+                };
+                if better {
+                    best = Some((encoding, quality));
+                }
+            }
+        }
+        best.map(|(encoding, _)| encoding)
+    }
+
+    /// Returns the encoded body, from the cache if the asset was already compressed.
+    pub(crate) fn encode(&self, path: &str, encoding: Encoding, body: &[u8]) -> Option<Bytes> {
+        let key = (path.to_string(), encoding);
+        if let Some(encoded) = self.cache.lock().unwrap().get(&key) {
+            return Some(encoded.clone());
+        }
+
+        match encoding.encode(body) {
+            Ok(encoded) => {
+                let encoded = Bytes::from(encoded);
+                self.cache.lock().unwrap().insert(key, encoded.clone());
+                Some(encoded)
+            }
+            Err(e) => {
+                log::warn!("failed to compress {path}: {e}");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Cross-origin resource sharing, so pages served from other origins can fetch from the server.
+
+use std::time::Duration;
+
+use http::{
+    header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+        ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+        ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD,
+        ORIGIN, VARY,
+    },
+    HeaderMap, HeaderValue, Method,
+};
+
+/// The cross-origin requests allowed by the server, see [`crate::Builder::cors`].
+///
+/// No origin is allowed by default.
+///
+/// ```
+/// use std::time::Duration;
+/// use tauri_plugin_localhost::CorsConfig;
+///
+/// CorsConfig::new()
+///     .allow_origin("http://localhost:5173")
+///     .allow_methods(["GET", "POST"])
+///     .allow_headers(["Content-Type"])
+///     .max_age(Duration::from_secs(600));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// `None` allows any origin.
+    origins: Option<Vec<String>>,
+    methods: Vec<String>,
+    /// Lowercase names, `None` allows any header.
+    headers: Option<Vec<String>>,
+    expose_headers: Vec<String>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            origins: Some(Vec::new()),
+            methods: vec!["GET".into(), "HEAD".into(), "POST".into()],
+            headers: Some(Vec::new()),
+            expose_headers: Vec::new(),
+            credentials: false,
+            max_age: None,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Allows no origin, and the `GET`, `HEAD` and `POST` methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows the origin, such as `http://localhost:5173`.
+    pub fn allow_origin<O: Into<String>>(mut self, origin: O) -> Self {
+        let origin = origin.into();
+        if let Some(origins) = &mut self.origins {
+            origins.push(origin.trim_end_matches('/').to_string());
+        }
+        self
+    }
+
+    /// Allows any origin.
+    pub fn allow_any_origin(mut self) -> Self {
+        self.origins = None;
+        self
+    }
+
+    /// Sets the allowed methods. Default: `GET`, `HEAD` and `POST`
+    pub fn allow_methods<I: IntoIterator<Item = M>, M: Into<String>>(mut self, methods: I) -> Self {
+        self.methods = methods
+            .into_iter()
+            .map(|method| method.into().to_ascii_uppercase())
+            .collect();
+        self
+    }
+
+    /// Allows the request headers, in addition to the headers always allowed by the browsers such as `Accept`.
+    pub fn allow_headers<I: IntoIterator<Item = H>, H: Into<String>>(mut self, headers: I) -> Self {
+        let headers = headers
+            .into_iter()
+            .map(|header| header.into().to_ascii_lowercase());
+        if let Some(allowed) = &mut self.headers {
+            allowed.extend(headers);
+        }
+        self
+    }
+
+    /// Allows any request header.
+    pub fn allow_any_header(mut self) -> Self {
+        self.headers = None;
+        self
+    }
+
+    /// Exposes the response headers to the scripts, in addition to the headers always exposed such as `Content-Type`.
+    pub fn expose_headers<I: IntoIterator<Item = H>, H: Into<String>>(
+        mut self,
+        headers: I,
+    ) -> Self {
+        self.expose_headers
+            .extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether the requests can include the cookies. Default: false
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// How long the browsers can cache the result of the preflight requests.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    fn allows_origin(&self, origin: &str) -> bool {
+        self.origins.as_ref().map_or(true, |origins| {
+            origins.iter().any(|allowed| allowed == origin)
+        })
+    }
+
+    /// Adds the `Access-Control-Allow-Origin` and `Access-Control-Allow-Credentials` headers if the origin is allowed.
+    fn allow_origin_headers(&self, request: &HeaderMap, response: &mut HeaderMap) -> bool {
+        response.append(VARY, HeaderValue::from_static("Origin"));
+        let Some(origin) = request.get(ORIGIN).filter(|origin| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| self.allows_origin(origin))
+        }) else {
+            return false;
+        };
+
+        // the wildcard cannot be used with credentials
+        let allowed = if self.origins.is_none() && !self.credentials {
+            HeaderValue::from_static("*")
+        } else {
+            origin.clone()
+        };
+        response.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allowed);
+        if self.credentials {
+            response.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+        true
+    }
+
+    /// Adds the headers of the response to a preflight request, returns `false` if the request is not allowed.
+    pub(crate) fn preflight(&self, request: &HeaderMap, response: &mut HeaderMap) -> bool {
+        if !self.allow_origin_headers(request, response) {
+            return false;
+        }
+
+        let method = request
+            .get(ACCESS_CONTROL_REQUEST_METHOD)
+            .and_then(|method| method.to_str().ok())
+            .unwrap_or_default();
+        if !self.methods.iter().any(|allowed| allowed == method) {
+            return false;
+        }
+
+        let requested = request
+            .get_all(ACCESS_CONTROL_REQUEST_HEADERS)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|header| header.trim().to_ascii_lowercase())
+            .filter(|header| !header.is_empty())
+            .collect::<Vec<_>>();
+        let headers = match &self.headers {
+            Some(allowed) => {
+                if !requested.iter().all(|header| allowed.contains(header)) {
+                    return false;
+                }
+                allowed.join(", ")
+            }
+            None => requested.join(", "),
+        };
+
+        response.append(
+            VARY,
+            HeaderValue::from_static(
+                "Access-Control-Request-Method, Access-Control-Request-Headers",
+            ),
+        );
+        if let Ok(methods) = HeaderValue::from_str(&self.methods.join(", ")) {
+            response.insert(ACCESS_CONTROL_ALLOW_METHODS, methods);
+        }
+        if let Ok(headers) = HeaderValue::from_str(&headers) {
+            if !headers.is_empty() {
+                response.insert(ACCESS_CONTROL_ALLOW_HEADERS, headers);
+            }
+        }
+        if let Some(max_age) = self.max_age {
+            response.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+        }
+        true
+    }
+
+    /// Adds the headers of the response to an actual request.
+    pub(crate) fn apply(&self, request: &HeaderMap, response: &mut HeaderMap) {
+        if !self.allow_origin_headers(request, response) || self.expose_headers.is_empty() {
+            return;
+        }
+        if let Ok(expose) = HeaderValue::from_str(&self.expose_headers.join(", ")) {
+            response.insert(ACCESS_CONTROL_EXPOSE_HEADERS, expose);
+        }
+    }
+}
+
+/// Whether the request is a CORS preflight request.
+pub(crate) fn is_preflight(method: &Method, request: &HeaderMap) -> bool {
+    *method == Method::OPTIONS
+        && request.contains_key(ORIGIN)
+        && request.contains_key(ACCESS_CONTROL_REQUEST_METHOD)
+}
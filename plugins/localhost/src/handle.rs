@@ -0,0 +1,121 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Shutdown and restart of the running server.
+
+use std::{
+    sync::{
+        mpsc::{self, TryRecvError},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use tokio::sync::watch;
+
+use crate::{metrics::Counters, LocalhostInfo, Metrics};
+
+/// Binds the port and spawns the accept loop.
+pub(crate) type Start = Box<dyn Fn(u16) -> std::io::Result<Running> + Send + Sync>;
+
+/// How long [`LocalhostHandle::shutdown`] waits for the listener to close.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) struct Running {
+    pub(crate) info: LocalhostInfo,
+    /// Stops the accept loop and the connections, also when dropped.
+    pub(crate) shutdown: watch::Sender<bool>,
+    /// Receives once the accept loop ended and the listener is closed.
+    pub(crate) stopped: mpsc::Receiver<()>,
+}
+
+/// Controls the server, managed in the app state.
+///
+/// The server is shut down when the app exits.
+///
+/// ```no_run
+/// use tauri::Manager;
+/// use tauri_plugin_localhost::LocalhostHandle;
+///
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_localhost::Builder::new(0).build())
+///     .setup(|app| {
+///         let handle = app.state::<LocalhostHandle>();
+///         handle.restart(0)?;
+///         assert!(handle.is_running());
+///         Ok(())
+///     });
+/// ```
+pub struct LocalhostHandle {
+    start: Start,
+    running: Mutex<Option<Running>>,
+    counters: Arc<Counters>,
+}
+
+impl LocalhostHandle {
+    pub(crate) fn new(start: Start, counters: Arc<Counters>) -> Self {
+        Self {
+            start,
+            running: Mutex::new(None),
+            counters,
+        }
+    }
+
+    pub(crate) fn start(&self, port: u16) -> std::io::Result<LocalhostInfo> {
+        let running = (self.start)(port)?;
+        let info = running.info.clone();
+        self.running.lock().unwrap().replace(running);
+        Ok(info)
+    }
+
+    /// Stops accepting connections and waits for the port to be released.
+    ///
+    /// The requests in flight are completed before their connection is closed.
+    /// Does nothing if the server is not running.
+    pub fn shutdown(&self) {
+        let Some(running) = self.running.lock().unwrap().take() else {
+            return;
+        };
+        let _ = running.shutdown.send(true);
+        if running.stopped.recv_timeout(SHUTDOWN_TIMEOUT).is_err() {
+            log::warn!("timed out waiting for the localhost server to shut down");
+        }
+    }
+
+    /// Shuts the server down and starts it again on the port, `0` lets the system pick an unused port.
+    ///
+    /// The [`READY_EVENT`](crate::READY_EVENT) is emitted again once the server accepts connections.
+    pub fn restart(&self, port: u16) -> std::io::Result<LocalhostInfo> {
+        self.shutdown();
+        self.start(port)
+    }
+
+    pub fn is_running(&self) -> bool {
+        let mut running = self.running.lock().unwrap();
+        // the accept loop also ends if the listener cannot be registered with the runtime
+        if running
+            .as_ref()
+            .is_some_and(|running| !matches!(running.stopped.try_recv(), Err(TryRecvError::Empty)))
+        {
+            running.take();
+        }
+        running.is_some()
+    }
+
+    /// The address of the running server.
+    ///
+    /// Unlike the [`LocalhostInfo`] state, which is the address the server was first started on, it changes on [`Self::restart`].
+    pub fn info(&self) -> Option<LocalhostInfo> {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|running| running.info.clone())
+    }
+
+    /// A snapshot of the metrics since the app started, kept across restarts.
+    pub fn metrics(&self) -> Metrics {
+        self.counters.snapshot()
+    }
+}
@@ -1,66 +1,262 @@
 use std::collections::HashMap;
-use std::convert::Infallible;
-use std::net::SocketAddr;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
 
-use futures_util::SinkExt;
-use futures_util::StreamExt;
+use http::header::{
+    CACHE_CONTROL, CONTENT_ENCODING, ETAG, LAST_MODIFIED, RANGE, RETRY_AFTER, VARY,
+};
 use http::HeaderName;
 use http::HeaderValue;
 use http_body_util::BodyExt;
 use http_body_util::Full;
-use hyper::body::{Bytes, Incoming};
+use hyper::body::{Body, Bytes, Incoming};
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use hyper::{Request, Response};
-use hyper_tungstenite::{HyperWebsocket, WebSocketStream};
+use hyper_tungstenite::WebSocketStream;
 use hyper_util::rt::TokioIo;
+use serde::Serialize;
 use tauri::AssetResolver;
 use tauri::{
     plugin::{Builder as PluginBuilder, TauriPlugin},
-    Runtime,
+    Emitter, Manager, RunEvent, Runtime,
 };
 use tokio::net::TcpListener;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock, Semaphore};
 use tungstenite::protocol::Message;
 
+mod cache;
+mod commands;
+mod compression;
+mod cors;
+mod handle;
+mod limit;
+mod metrics;
+mod mount;
+mod range;
+mod spa;
+mod tls;
+mod token;
+mod websocket;
+
+use cache::AssetCache;
+pub use compression::CompressionConfig;
+use compression::Compressor;
+pub use cors::CorsConfig;
+pub use handle::LocalhostHandle;
+use handle::Running;
+use limit::RateLimiter;
+use metrics::Counters;
+pub use metrics::Metrics;
+use mount::Mount;
+use spa::SpaFallback;
+use tls::Tls;
+use token::{Access, AccessToken};
+
 type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
-type BoxBody = http_body_util::combinators::BoxBody<Bytes, Infallible>;
+/// A connection, with or without TLS.
+trait Io: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send {}
 
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> Io for T {}
+
+type BoxBody = http_body_util::combinators::BoxBody<Bytes, Error>;
+
+fn boxed(body: Full<Bytes>) -> BoxBody {
+    body.map_err(|never| match never {}).boxed()
+}
+
+fn full<B: Into<Bytes>>(body: B) -> BoxBody {
+    boxed(Full::new(body.into()))
+}
+
+type OnRequest = Arc<dyn Fn(&mut LocalRequest, &mut LocalResponse) + Send + Sync>;
+type OnResponse = Arc<dyn Fn(&LocalRequest, &mut LocalResponse) + Send + Sync>;
+type Route = Box<dyn Fn(&LocalRequest, &mut LocalResponse) + Send + Sync>;
+
+/// A request received by the server, see [`Builder::on_request`].
 pub struct LocalRequest {
+    method: String,
     url: String,
     headers: HashMap<String, String>,
 }
 
 impl LocalRequest {
+    fn new(request: &Request<Incoming>) -> Self {
+        Self {
+            method: request.method().to_string(),
+            url: request.uri().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The path and query of the request.
     pub fn url(&self) -> &str {
         &self.url
     }
 
+    /// Rewrites the path and query of the request before the asset is resolved.
+    pub fn set_url<U: Into<String>>(&mut self, url: U) {
+        self.url = url.into();
+    }
+
+    /// The headers of the request, with lowercase names.
     pub fn headers(&self) -> &HashMap<String, String> {
         &self.headers
     }
+
+    fn path(&self) -> &str {
+        self.url.split('?').next().unwrap_or_default()
+    }
 }
 
+/// The status and headers of a response, see [`Builder::on_request`] and [`Builder::on_response`].
+#[derive(Default)]
 pub struct LocalResponse {
+    status: Option<u16>,
     headers: HashMap<String, String>,
+    body: Vec<u8>,
 }
 
 impl LocalResponse {
     pub fn add_header<H: Into<String>, V: Into<String>>(&mut self, header: H, value: V) {
         self.headers.insert(header.into(), value.into());
     }
+
+    /// The headers added to the response.
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// The status of the response, `None` in [`Builder::on_request`] until it is set.
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// Sets the status of the response.
+    ///
+    /// In [`Builder::on_request`], this responds immediately with the status, the headers and the body
+    /// instead of resolving the asset, to deny the request for instance.
+    pub fn set_status(&mut self, status: u16) {
+        self.status = Some(status);
+    }
+
+    /// Sets the body of the response to a request handled by [`Builder::on_request`], ignored otherwise.
+    pub fn set_body<B: Into<Vec<u8>>>(&mut self, body: B) {
+        self.body = body.into();
+    }
+
+    /// Replaces the headers with the added ones, skipping the invalid names and values.
+    fn write_headers(&self, headers: &mut http::HeaderMap) {
+        for (name, value) in &self.headers {
+            if let Ok(header_name) = name.parse::<HeaderName>() {
+                if let Ok(header_value) = value.parse::<HeaderValue>() {
+                    headers.insert(header_name, header_value);
+                }
+            }
+        }
+    }
+}
+
+/// Emitted with the [`LocalhostInfo`] once the server accepts connections.
+pub const READY_EVENT: &str = "localhost://ready";
+
+/// The address of the running server, managed in the app state.
+///
+/// ```no_run
+/// use tauri::Manager;
+/// use tauri_plugin_localhost::LocalhostInfo;
+///
+/// tauri::Builder::default()
+///     .plugin(tauri_plugin_localhost::Builder::new(0).build())
+///     .setup(|app| {
+///         let port = app.state::<LocalhostInfo>().port();
+///         Ok(())
+///     });
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalhostInfo {
+    port: u16,
+    url: String,
+    #[serde(skip)]
+    access_token: Option<String>,
+}
+
+impl LocalhostInfo {
+    /// The bound port, assigned by the system when the builder was created with port `0`.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The URL of the server, using `localhost` when it listens on a loopback or unspecified address.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The token required by the server, see [`Builder::require_access_token`].
+    pub fn access_token(&self) -> Option<&str> {
+        self.access_token.as_deref()
+    }
+
+    /// Whether the URL is served by the server.
+    fn serves(&self, url: &url::Url) -> bool {
+        url::Url::parse(&self.url).is_ok_and(|server| server.origin() == url.origin())
+    }
 }
 
 pub struct Builder {
     port: u16,
     host: Option<String>,
+    tls: Option<Tls>,
+    compression: Option<CompressionConfig>,
+    cache_control: Vec<(String, String)>,
+    on_request: Option<OnRequest>,
+    on_response: Option<OnResponse>,
+    spa_fallback: Option<String>,
+    spa_exclude: Vec<String>,
+    access_token: bool,
+    cors: Option<CorsConfig>,
+    mounts: Vec<Mount>,
+    routes: HashMap<String, Route>,
+    max_connections: Option<usize>,
+    rate_limit: Option<(u32, u32)>,
+    access_log: bool,
 }
 
 impl Builder {
+    /// Creates the server for the given port, `0` lets the system pick an unused port that can be read from [`LocalhostInfo`].
     pub fn new(port: u16) -> Self {
-        Self { port, host: None }
+        Self {
+            port,
+            host: None,
+            tls: None,
+            compression: None,
+            cache_control: Vec::new(),
+            on_request: None,
+            on_response: None,
+            spa_fallback: None,
+            spa_exclude: Vec::new(),
+            access_token: false,
+            cors: None,
+            mounts: Vec::new(),
+            routes: HashMap::new(),
+            max_connections: None,
+            rate_limit: None,
+            access_log: false,
+        }
     }
 
     pub fn host<H: Into<String>>(mut self, host: H) -> Self {
@@ -68,94 +264,437 @@ impl Builder {
         self
     }
 
+    /// Serves over HTTPS with the given PEM encoded certificate chain and private key,
+    /// for the browser APIs only available in secure contexts.
+    pub fn tls<C: Into<Vec<u8>>, K: Into<Vec<u8>>>(mut self, cert: C, key: K) -> Self {
+        self.tls = Some(Tls::Pem {
+            cert: cert.into(),
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Serves over HTTPS with a self-signed certificate generated on startup for `localhost` and the host.
+    ///
+    /// The webview must be configured to trust the certificate, see [`Self::tls`] to provide a trusted one.
+    pub fn self_signed_tls(mut self) -> Self {
+        self.tls = Some(Tls::SelfSigned);
+        self
+    }
+
+    /// Compresses the text assets such as HTML, JavaScript, CSS and JSON with gzip or brotli when the client accepts it.
+    ///
+    /// The compressed assets are cached for the lifetime of the server.
+    pub fn compression(mut self, config: CompressionConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Sets the `Cache-Control` header of the assets matching the glob pattern, such as `/assets/**`.
+    ///
+    /// The first pattern matching the path of the request applies.
+    /// The assets are always sent with a strong `ETag`, so the clients can revalidate them with `If-None-Match`.
+    ///
+    /// ```no_run
+    /// tauri_plugin_localhost::Builder::new(0)
+    ///     // the bundler adds a hash to the name of these assets
+    ///     .cache_control("/assets/**", "public, max-age=31536000, immutable")
+    ///     .cache_control("*.html", "no-cache")
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn cache_control<G: Into<String>, V: Into<String>>(mut self, glob: G, value: V) -> Self {
+        self.cache_control.push((glob.into(), value.into()));
+        self
+    }
+
+    /// Calls the closure with each request before the asset is resolved.
+    ///
+    /// The closure can rewrite the URL of the request, add headers to the response,
+    /// or set the status of the response to answer without resolving the asset.
+    ///
+    /// ```no_run
+    /// tauri_plugin_localhost::Builder::new(0)
+    ///     .on_request(|request, response| {
+    ///         if request.url().starts_with("/private/") {
+    ///             response.set_status(403);
+    ///         }
+    ///         response.add_header("X-Frame-Options", "DENY");
+    ///     })
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn on_request<F: Fn(&mut LocalRequest, &mut LocalResponse) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.on_request.replace(Arc::new(f));
+        self
+    }
+
+    /// Calls the closure with each response before it is sent, to change its status or headers.
+    ///
+    /// WebSocket upgrades are not passed to the closure.
+    pub fn on_response<F: Fn(&LocalRequest, &mut LocalResponse) + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.on_response.replace(Arc::new(f));
+        self
+    }
+
+    /// Serves the asset, such as `/index.html`, instead of a 404 for the navigations to missing paths,
+    /// so the routes of client-side routers can be reloaded and linked to.
+    ///
+    /// Only the `GET` and `HEAD` requests accepting HTML are served the fallback,
+    /// the requests of missing scripts or images are still not found.
+    pub fn spa_fallback<P: Into<String>>(mut self, path: P) -> Self {
+        self.spa_fallback = Some(path.into());
+        self
+    }
+
+    /// Excludes the paths under the prefix from the [`Self::spa_fallback`], such as `/api` or `/api/*`.
+    pub fn spa_fallback_exclude<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.spa_exclude.push(prefix.into());
+        self
+    }
+
+    /// Rejects the requests without the random token generated on launch with `403 Forbidden`,
+    /// so other processes on the machine cannot read the assets.
+    ///
+    /// The token is added to the navigations of the webviews to the server URL as the `localhost_token` query parameter,
+    /// and stored in a cookie on the first request so the webview sends it with all its requests.
+    /// Other clients can send it in the `X-Localhost-Token` header, see [`LocalhostInfo::access_token`].
+    pub fn require_access_token(mut self) -> Self {
+        self.access_token = true;
+        self
+    }
+
+    /// Allows the cross-origin requests, from a second window served on another port or a local tool for instance.
+    ///
+    /// The preflight `OPTIONS` requests are answered by the server.
+    pub fn cors(mut self, config: CorsConfig) -> Self {
+        self.cors = Some(config);
+        self
+    }
+
+    /// Serves the files of the directory under the path prefix, such as `/docs`, before the assets.
+    ///
+    /// The directories are served their `index.html`, and the request paths cannot lead out of the directory.
+    pub fn mount<P: Into<String>, D: Into<PathBuf>>(mut self, prefix: P, dir: D) -> Self {
+        self.mounts.push(Mount::new(prefix.into(), dir.into()));
+        self
+    }
+
+    /// Answers the requests of the path, such as `/healthz`, before the mounted directories and the assets.
+    ///
+    /// The response has the status `200 OK` unless the handler sets it.
+    ///
+    /// ```no_run
+    /// tauri_plugin_localhost::Builder::new(0)
+    ///     .route("/healthz", |_request, response| {
+    ///         response.add_header("Content-Type", "text/plain");
+    ///         response.set_body("ok");
+    ///     })
+    ///     .build::<tauri::Wry>();
+    /// ```
+    pub fn route<
+        P: Into<String>,
+        F: Fn(&LocalRequest, &mut LocalResponse) + Send + Sync + 'static,
+    >(
+        mut self,
+        path: P,
+        handler: F,
+    ) -> Self {
+        self.routes.insert(path.into(), Box::new(handler));
+        self
+    }
+
+    /// Limits the number of connections open at once, the connections over the limit are answered with `503 Service Unavailable`.
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Limits the requests of each client IP address with a token bucket,
+    /// the requests over the limit are answered with `429 Too Many Requests`.
+    ///
+    /// `burst` is the number of requests allowed at once, refilled at `requests_per_second`.
+    /// All the local processes share the loopback address, so the limit must leave room for the webviews.
+    pub fn rate_limit(mut self, requests_per_second: u32, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// Logs the method, path, status, latency and body size of each request with the [`log`] crate,
+    /// at the info level and with the `tauri_plugin_localhost::access` target.
+    ///
+    /// The latency is the time to the response headers, the size is `-` for the bodies streamed from the dev server.
+    pub fn access_log(mut self) -> Self {
+        self.access_log = true;
+        self
+    }
+
     pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
         let port = self.port;
         let host = self.host.unwrap_or_else(|| "127.0.0.1".to_string());
+        let tls = self.tls;
+        let compression = self.compression;
+        let cache_control = self.cache_control;
+        let on_request = self.on_request;
+        let on_response = self.on_response;
+        let spa_fallback = self
+            .spa_fallback
+            .map(|path| Arc::new(SpaFallback::new(path, self.spa_exclude)));
+        let access_token = self.access_token.then(|| Arc::new(AccessToken::generate()));
+        let cors = self.cors.map(Arc::new);
+        let mut mounts = self.mounts;
+        // the longest prefix matches first
+        mounts.sort_by_key(|mount| std::cmp::Reverse(mount.prefix().len()));
+        let mounts = Arc::new(mounts);
+        let routes = Arc::new(self.routes);
+        let max_connections = self.max_connections;
+        let rate_limiter = self.rate_limit.map(|(requests_per_second, burst)| {
+            Arc::new(RateLimiter::new(requests_per_second, burst))
+        });
+        let access_log = self.access_log;
 
         PluginBuilder::new("localhost")
+            .invoke_handler(tauri::generate_handler![commands::metrics])
             .setup(move |app, _api| {
+                let tls = tls
+                    .as_ref()
+                    .map(|tls| tls.acceptor(&host))
+                    .transpose()
+                    .map_err(|e| e as Box<dyn std::error::Error>)?;
+                let secure = tls.is_some();
+                let scheme = if secure { "https" } else { "http" };
+                let token = access_token
+                    .as_ref()
+                    .map(|token| token.as_str().to_string());
+
+                let app_handle = app.clone();
                 let asset_resolver = app.asset_resolver();
                 let dev_url = app.config().build.dev_url.clone();
                 let is_dev = tauri::is_dev();
 
                 let asset_resolver = Arc::new(RwLock::new(asset_resolver));
+                let compressor = compression.map(Compressor::new);
+                let asset_cache = Arc::new(AssetCache::new(&cache_control)?);
 
-                let server = async move {
-                    let addr: SocketAddr = format!("{}:{}", host, port).parse().unwrap();
-                    log::info!("Listening on http://{}", addr);
-
-                    let listener = TcpListener::bind(addr).await.unwrap();
-
-                    let handle_request_handler = move |req: Request<Incoming>| {
-                        let asset_resolver = asset_resolver.clone();
-                        let dev_url = dev_url.clone();
-
-                        async move {
-                            if hyper_tungstenite::is_upgrade_request(&req) {
-                                let path = req.uri().path().to_string();
-                                let (response, websocket) = hyper_tungstenite::upgrade(req, None)?;
-
-                                tokio::spawn(async move {
-                                    // pipe to devUrl websocket
-                                    // assert dev_url is Some
-                                    let dev_url = dev_url.clone().unwrap();
-                                    let mut proxy_url = dev_url.join(&path).unwrap();
-                                    proxy_url.set_scheme("ws").unwrap();
-                                    let handle_ws = move |ws: HyperWebsocket| async move {
-                                        let websocket = ws.await?;
-                                        let (mut server_write, mut server_read) = websocket.split();
-                                        // connect to dev server
-                                        let (socket, _client_response) =
-                                            tokio_tungstenite::connect_async(proxy_url.as_str())
-                                                .await?;
-                                        let (mut client_write, mut client_read) = socket.split();
-                                        tokio::spawn(async move {
-                                            while let Some(Ok(message)) = client_read.next().await {
-                                                if let Err(e) = server_write.send(message).await {
-                                                    log::error!(
-                                                        "Error sending message to server: {e}"
-                                                    );
-                                                }
-                                            }
-                                        });
-                                        while let Some(Ok(message)) = server_read.next().await {
-                                            if let Err(e) = client_write.send(message).await {
-                                                log::error!("Error sending message to client: {e}");
-                                            }
-                                        }
-                                        Ok::<(), Error>(())
-                                    };
-                                    if let Err(e) = handle_ws(websocket).await {
-                                        eprintln!("Error in websocket connection: {e}");
-                                    }
-                                });
+                let counters = Arc::<Counters>::default();
+                let connections = max_connections.map(|max| Arc::new(Semaphore::new(max)));
+
+                let request_counters = counters.clone();
+                let handle_request_handler = move |req: Request<Incoming>, peer: IpAddr| {
+                    let asset_resolver = asset_resolver.clone();
+                    let dev_url = dev_url.clone();
+                    let compressor = compressor.clone();
+                    let asset_cache = asset_cache.clone();
+                    let on_request = on_request.clone();
+                    let on_response = on_response.clone();
+                    let spa_fallback = spa_fallback.clone();
+                    let access_token = access_token.clone();
+                    let cors = cors.clone();
+                    let mounts = mounts.clone();
+                    let routes = routes.clone();
+                    let rate_limiter = rate_limiter.clone();
+                    let counters = request_counters.clone();
+                    let metrics = request_counters.clone();
+                    let method = req.method().clone();
+                    let path = req.uri().path().to_string();
+                    let started = Instant::now();
 
-                                return Ok::<_, Error>(response);
+                    let response = async move {
+                        if rate_limiter
+                            .as_ref()
+                            .is_some_and(|limiter| !limiter.check(peer))
+                        {
+                            counters
+                                .rate_limited_requests
+                                .fetch_add(1, Ordering::Relaxed);
+                            return Ok(Response::builder()
+                                .status(hyper::StatusCode::TOO_MANY_REQUESTS)
+                                .header(RETRY_AFTER, "1")
+                                .body(full(Bytes::new()))?);
+                        }
+
+                        // the preflight requests do not carry the access token
+                        if let Some(cors) = cors
+                            .as_ref()
+                            .filter(|_| cors::is_preflight(req.method(), req.headers()))
+                        {
+                            let mut response = Response::builder();
+                            let allowed = response
+                                .headers_mut()
+                                .is_some_and(|headers| cors.preflight(req.headers(), headers));
+                            return Ok(response
+                                .status(if allowed {
+                                    hyper::StatusCode::NO_CONTENT
+                                } else {
+                                    hyper::StatusCode::FORBIDDEN
+                                })
+                                .body(full(Bytes::new()))?);
+                        }
+
+                        let mut set_cookie = false;
+                        if let Some(token) = &access_token {
+                            match token.check(req.uri(), req.headers()) {
+                                Access::Granted => {}
+                                Access::GrantedByQuery => set_cookie = true,
+                                Access::Denied => {
+                                    return Ok(Response::builder()
+                                        .status(hyper::StatusCode::FORBIDDEN)
+                                        .body(full(Bytes::new()))?);
+                                }
                             }
-                            let path = req.uri().path().to_string();
+                        }
+
+                        let mut local_request = LocalRequest::new(&req);
+                        let mut local_response = LocalResponse::default();
+                        if let Some(on_request) = &on_request {
+                            on_request(&mut local_request, &mut local_response);
+                        }
+                        let path = local_request.path().to_string();
+
+                        if let Some(status) = local_response.status {
+                            let mut response = Response::builder().status(
+                                hyper::StatusCode::from_u16(status)
+                                    .unwrap_or(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+                            );
+                            if let Some(headers) = response.headers_mut() {
+                                local_response.write_headers(headers);
+                            }
+                            return Ok(response.body(full(local_response.body))?);
+                        }
+
+                        let is_proxied = is_dev && dev_url.is_some();
+                        if is_proxied && hyper_tungstenite::is_upgrade_request(&req) {
+                            let url = dev_url.as_ref().unwrap().join(local_request.url())?;
+                            let response = websocket::proxy(req, url).await?;
+                            return Ok::<_, Error>(response.map(boxed));
+                        }
+
+                        let response: Result<Response<BoxBody>, Error> = async {
+                            if let Some(route) = routes.get(&path) {
+                                let mut route_response = LocalResponse::default();
+                                route(&local_request, &mut route_response);
+                                let mut response = Response::builder().status(
+                                    hyper::StatusCode::from_u16(
+                                        route_response.status.unwrap_or(200),
+                                    )
+                                    .unwrap_or(hyper::StatusCode::INTERNAL_SERVER_ERROR),
+                                );
+                                if let Some(headers) = response.headers_mut() {
+                                    route_response.write_headers(headers);
+                                }
+                                return Ok(response.body(full(route_response.body))?);
+                            }
+
+                            if let Some((mount, relative)) = mounts
+                                .iter()
+                                .find_map(|mount| Some((mount, mount.relative(&path)?)))
+                            {
+                                let mut response = Response::builder();
+                                if let Some(cache_control) = asset_cache.cache_control(&path) {
+                                    response = response.header(CACHE_CONTROL, cache_control);
+                                }
+                                return Ok(mount
+                                    .respond(relative, req.method(), req.headers(), response)
+                                    .await?
+                                    .map(boxed));
+                            }
+
                             let resolver = asset_resolver.read().await;
 
-                            if let Some(asset) = resolver.get(path.clone()) {
-                                let mut local_response = LocalResponse {
-                                    headers: Default::default(),
-                                };
+                            let asset = resolver
+                                .get(path.clone())
+                                .map(|asset| (path.clone(), asset))
+                                .or_else(|| {
+                                    // the dev server has its own fallback
+                                    spa_fallback
+                                        .as_ref()
+                                        .filter(|fallback| {
+                                            !is_proxied
+                                                && fallback.applies(
+                                                    req.method(),
+                                                    req.headers(),
+                                                    &path,
+                                                )
+                                        })
+                                        .and_then(|fallback| {
+                                            let asset = resolver.get(fallback.path.clone())?;
+                                            Some((fallback.path.clone(), asset))
+                                        })
+                                });
+
+                            if let Some((asset_path, asset)) = asset {
+                                let mut asset_response = LocalResponse::default();
 
-                                local_response.add_header("Content-Type", &asset.mime_type);
+                                asset_response.add_header("Content-Type", &asset.mime_type);
                                 if let Some(csp) = asset.csp_header {
-                                    local_response.add_header("Content-Security-Policy", &csp);
+                                    asset_response.add_header("Content-Security-Policy", &csp);
                                 }
 
                                 let mut response = Response::builder();
-                                for (name, value) in local_response.headers {
-                                    if let Ok(header_name) = name.parse::<HeaderName>() {
-                                        if let Ok(header_value) = value.parse::<HeaderValue>() {
-                                            response = response.header(header_name, header_value);
-                                        }
-                                    }
+                                if let Some(headers) = response.headers_mut() {
+                                    asset_response.write_headers(headers);
+                                }
+                                let etag = asset_cache.etag(&asset_path, &asset.bytes);
+                                response =
+                                    response.header(LAST_MODIFIED, asset_cache.last_modified());
+                                if let Some(cache_control) = asset_cache.cache_control(&asset_path)
+                                {
+                                    response = response.header(CACHE_CONTROL, cache_control);
                                 }
-                                let response = response.body(Full::from(asset.bytes))?;
+                                let compressible = compression::is_compressible(&asset.mime_type);
+                                if compressor.is_some() && compressible {
+                                    response = response.header(VARY, "Accept-Encoding");
+                                }
+                                // ranges apply to the encoded content, so they are served uncompressed
+                                let encoding = compressor
+                                    .as_ref()
+                                    .filter(|_| !req.headers().contains_key(RANGE))
+                                    .and_then(|compressor| {
+                                        compressor.negotiate(
+                                            req.headers(),
+                                            &asset.mime_type,
+                                            asset.bytes.len(),
+                                        )
+                                    });
+                                let encoded_etag =
+                                    encoding.map(|encoding| cache::encoded_etag(&etag, encoding));
+
+                                let etags =
+                                    [etag.as_str(), encoded_etag.as_deref().unwrap_or(&etag)];
+                                if asset_cache.is_not_modified(req.method(), req.headers(), &etags)
+                                {
+                                    return Ok(response
+                                        .status(hyper::StatusCode::NOT_MODIFIED)
+                                        .header(ETAG, etags[1])
+                                        .body(full(Bytes::new()))?);
+                                }
+
+                                let encoded = compressor.as_ref().zip(encoding).and_then(
+                                    |(compressor, encoding)| {
+                                        compressor
+                                            .encode(&asset_path, encoding, &asset.bytes)
+                                            .map(|body| (encoding, body))
+                                    },
+                                );
+                                let response = match (encoded, encoded_etag) {
+                                    (Some((encoding, body)), Some(encoded_etag)) => response
+                                        .header(CONTENT_ENCODING, encoding.name())
+                                        .header(ETAG, encoded_etag)
+                                        .body(full(body))?,
+                                    _ => range::respond(
+                                        req.headers(),
+                                        response.header(ETAG, &etag),
+                                        asset.bytes,
+                                        Some(&etag),
+                                    )?
+                                    .map(boxed),
+                                };
                                 Ok(response)
-                            } else if is_dev && dev_url.is_some() {
+                            } else if is_proxied {
                                 // Proxy to dev server
                                 let client = reqwest::Client::new();
                                 let dev_url = dev_url.clone().unwrap();
@@ -178,56 +717,246 @@ impl Builder {
                                             response = response.header(name, value);
                                         }
 
-                                        let body = proxy_res.bytes().await.unwrap_or_default();
-                                        let response = response.body(Full::from(body))?;
+                                        // stream the body so the large source maps and the HMR payloads are sent as they arrive,
+                                        // the trailers are forwarded with the body
+                                        let body = reqwest::Body::from(proxy_res)
+                                            .map_err(Error::from)
+                                            .boxed();
+                                        let response = response.body(body)?;
                                         Ok(response)
                                     }
                                     Err(_) => Ok(Response::builder()
                                         .status(hyper::StatusCode::BAD_GATEWAY)
-                                        .body(Full::default())?),
+                                        .body(full(Bytes::new()))?),
                                 }
                             } else {
                                 Ok(Response::builder()
                                     .status(hyper::StatusCode::NOT_FOUND)
                                     .header("Content-Type", "text/html")
                                     .header("Content-Security-Policy", "default-src 'none'")
-                                    .body(Full::default())?)
+                                    .body(full(Bytes::new()))?)
                             }
                         }
+                        .await;
+
+                        let mut response = response?;
+                        // the headers added by the hook take precedence
+                        local_response.write_headers(response.headers_mut());
+                        if let Some(on_response) = &on_response {
+                            let mut local_response = LocalResponse {
+                                status: Some(response.status().as_u16()),
+                                ..Default::default()
+                            };
+                            on_response(&local_request, &mut local_response);
+                            if let Some(status) = local_response
+                                .status
+                                .and_then(|status| hyper::StatusCode::from_u16(status).ok())
+                            {
+                                *response.status_mut() = status;
+                            }
+                            local_response.write_headers(response.headers_mut());
+                        }
+                        if let Some(token) = access_token.as_ref().filter(|_| set_cookie) {
+                            token.set_cookie(response.headers_mut(), secure);
+                        }
+                        if let Some(cors) = &cors {
+                            cors.apply(req.headers(), response.headers_mut());
+                        }
+                        Ok(response)
                     };
 
-                    loop {
-                        if let Ok((stream, _)) = listener.accept().await {
-                            let mut http = hyper::server::conn::http1::Builder::new();
-                            http.keep_alive(true);
-                            let connection = http
-                                .serve_connection(
-                                    TokioIo::new(stream),
-                                    service_fn(handle_request_handler.clone()),
-                                )
-                                .with_upgrades();
-                            tokio::spawn(connection);
+                    async move {
+                        let response: Result<Response<BoxBody>, Error> = response.await;
+                        let latency = started.elapsed();
+                        if let Ok(response) = &response {
+                            let status = response.status().as_u16();
+                            metrics.record(status, latency);
+                            if access_log {
+                                let bytes = response
+                                    .body()
+                                    .size_hint()
+                                    .exact()
+                                    .map_or_else(|| "-".to_string(), |bytes| bytes.to_string());
+                                log::info!(
+                                    target: "tauri_plugin_localhost::access",
+                                    "{method} {path} {status} {:.1}ms {bytes}",
+                                    latency.as_secs_f64() * 1000.0
+                                );
+                            }
                         }
+                        response
                     }
                 };
-                let handle = tokio::runtime::Handle::try_current();
-                match handle {
-                    Ok(handle) => {
-                        handle.spawn(server);
-                    }
+
+                // the server runs on the current runtime, or on its own runtime outside of one
+                let runtime = match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => handle,
                     Err(_) => {
-                        std::thread::spawn(move || {
-                            let rt = tokio::runtime::Builder::new_multi_thread()
-                                .enable_all()
-                                .build()
-                                .unwrap();
-                            rt.block_on(server);
-                        });
+                        let rt = tokio::runtime::Builder::new_multi_thread()
+                            .enable_all()
+                            .build()?;
+                        let handle = rt.handle().clone();
+                        // keep the runtime alive for the lifetime of the app
+                        std::thread::spawn(move || rt.block_on(std::future::pending::<()>()));
+                        handle
                     }
-                }
+                };
+
+                let handle_counters = counters.clone();
+                let start = move |port: u16| -> std::io::Result<Running> {
+                    let listener = std::net::TcpListener::bind((host.as_str(), port))?;
+                    listener.set_nonblocking(true)?;
+                    let addr = listener.local_addr()?;
+                    let url_addr = if addr.ip().is_loopback() || addr.ip().is_unspecified() {
+                        format!("localhost:{}", addr.port())
+                    } else {
+                        addr.to_string()
+                    };
+                    let info = LocalhostInfo {
+                        port: addr.port(),
+                        url: format!("{scheme}://{url_addr}"),
+                        access_token: token.clone(),
+                    };
+
+                    let (shutdown, shutdown_rx) = watch::channel(false);
+                    let (stopped_tx, stopped) = std::sync::mpsc::channel();
+                    let app_handle = app_handle.clone();
+                    let handle_request_handler = handle_request_handler.clone();
+                    let tls = tls.clone();
+                    let counters = counters.clone();
+                    let connections = connections.clone();
+                    let ready_info = info.clone();
+                    runtime.spawn(async move {
+                        match TcpListener::from_std(listener) {
+                            Ok(listener) => {
+                                log::info!("Listening on {}://{}", scheme, addr);
+                                let _ = app_handle.emit(READY_EVENT, ready_info);
+
+                                let mut stop = shutdown_rx.clone();
+                                loop {
+                                    let (stream, peer) = tokio::select! {
+                                        accepted = listener.accept() => match accepted {
+                                            Ok((stream, peer)) => (stream, peer.ip()),
+                                            Err(_) => continue,
+                                        },
+                                        _ = stop.changed() => break,
+                                    };
+                                    let mut http = hyper::server::conn::http1::Builder::new();
+                                    http.keep_alive(true);
+                                    let handler = handle_request_handler.clone();
+                                    let service = service_fn(move |req| handler(req, peer));
+                                    let acceptor = tls.clone();
+                                    let counters = counters.clone();
+                                    // the permit is released when the connection is closed
+                                    let permit = connections
+                                        .as_ref()
+                                        .map(|connections| connections.clone().try_acquire_owned());
+                                    let mut stop = shutdown_rx.clone();
+                                    // handshake in the task so a slow client does not block the listener
+                                    tokio::spawn(async move {
+                                        let stream: Box<dyn Io> = match acceptor {
+                                            Some(acceptor) => match acceptor.accept(stream).await {
+                                                Ok(stream) => Box::new(stream),
+                                                Err(e) => {
+                                                    log::debug!("TLS handshake failed: {e}");
+                                                    return;
+                                                }
+                                            },
+                                            None => Box::new(stream),
+                                        };
+                                        if matches!(permit, Some(Err(_))) {
+                                            counters
+                                                .rejected_connections
+                                                .fetch_add(1, Ordering::Relaxed);
+                                            http.keep_alive(false);
+                                            let unavailable =
+                                                service_fn(|_: Request<Incoming>| async {
+                                                    Response::builder()
+                                                        .status(
+                                                            hyper::StatusCode::SERVICE_UNAVAILABLE,
+                                                        )
+                                                        .header(RETRY_AFTER, "1")
+                                                        .body(full(Bytes::new()))
+                                                });
+                                            let _ = http
+                                                .serve_connection(TokioIo::new(stream), unavailable)
+                                                .await;
+                                            return;
+                                        }
+
+                                        counters.active_connections.fetch_add(1, Ordering::Relaxed);
+                                        let connection = http
+                                            .serve_connection(TokioIo::new(stream), service)
+                                            .with_upgrades();
+                                        tokio::pin!(connection);
+                                        tokio::select! {
+                                            _ = connection.as_mut() => {}
+                                            // complete the requests in flight before closing
+                                            _ = stop.changed() => {
+                                                connection.as_mut().graceful_shutdown();
+                                                let _ = connection.await;
+                                            }
+                                        }
+                                        counters.active_connections.fetch_sub(1, Ordering::Relaxed);
+                                        drop(permit);
+                                    });
+                                }
+                            }
+                            Err(e) => log::error!("failed to start the localhost server: {e}"),
+                        }
+                        let _ = stopped_tx.send(());
+                    });
+
+                    Ok(Running {
+                        info,
+                        shutdown,
+                        stopped,
+                    })
+                };
+
+                let handle = LocalhostHandle::new(Box::new(start), handle_counters);
+                // bind before the app setup so the port is known by then
+                let info = handle.start(port)?;
+                app.manage(info);
+                app.manage(handle);
 
                 Ok(())
             })
+            .on_navigation(|webview, url| {
+                let Some(info) = webview
+                    .try_state::<LocalhostHandle>()
+                    .and_then(|handle| handle.info())
+                else {
+                    return true;
+                };
+                let Some(token) = info.access_token().filter(|_| info.serves(url)) else {
+                    return true;
+                };
+                let mut url = url.clone();
+                if !token::add_to_url(&mut url, token) {
+                    return true;
+                }
+                // navigate again with the token, outside of the navigation handler
+                let webview = webview.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = webview.navigate(url) {
+                        log::error!("failed to add the access token to the navigation: {e}");
+                    }
+                });
+                false
+            })
+            .on_event(|app, event| {
+                if let RunEvent::Exit = event {
+                    if let Some(handle) = app.try_state::<LocalhostHandle>() {
+                        handle.shutdown();
+                    }
+                }
+            })
+            .on_drop(|app| {
+                if let Some(handle) = app.try_state::<LocalhostHandle>() {
+                    handle.shutdown();
+                }
+            })
             .build()
     }
 }
@@ -0,0 +1,65 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Per-IP rate limiting of the requests, so a misbehaving process cannot flood the server.
+
+use std::{collections::HashMap, net::IpAddr, sync::Mutex, time::Instant};
+
+/// The buckets are pruned once there are more clients than this.
+const MAX_BUCKETS: usize = 1024;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// A token bucket per client IP address, refilled at a constant rate.
+pub(crate) struct RateLimiter {
+    /// The tokens added per second.
+    rate: f64,
+    /// The capacity of the buckets, the number of requests allowed at once.
+    burst: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: u32, burst: u32) -> Self {
+        Self {
+            rate: f64::from(requests_per_second),
+            burst: f64::from(burst.max(1)),
+            buckets: Default::default(),
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+    }
+
+    /// Takes a token from the bucket of the client, returns `false` if the request is over the limit.
+    pub(crate) fn check(&self, ip: IpAddr) -> bool {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_BUCKETS {
+            // the full buckets are the same as new ones
+            buckets.retain(|_, bucket| {
+                self.refill(bucket, now);
+                bucket.tokens < self.burst
+            });
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        self.refill(bucket, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
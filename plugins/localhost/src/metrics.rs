@@ -0,0 +1,83 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Counters of the server, to monitor the connections, the requests and their latency.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use serde::Serialize;
+
+/// How many of the latest request latencies are kept to compute the percentile.
+const LATENCY_SAMPLES: usize = 1024;
+
+/// A snapshot of the server metrics, see [`crate::LocalhostHandle::metrics`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metrics {
+    /// The connections currently open.
+    pub active_connections: usize,
+    /// The connections answered with `503 Service Unavailable` over [`crate::Builder::max_connections`].
+    pub rejected_connections: u64,
+    /// The requests answered with `429 Too Many Requests` by [`crate::Builder::rate_limit`].
+    pub rate_limited_requests: u64,
+    /// The number of requests answered with each status code.
+    pub requests_by_status: BTreeMap<u16, u64>,
+    /// The 95th percentile of the time to the response headers, in milliseconds, over the latest requests.
+    ///
+    /// `None` until a request is answered.
+    pub p95_latency_ms: Option<f64>,
+}
+
+#[derive(Default)]
+struct Requests {
+    by_status: BTreeMap<u16, u64>,
+    latencies: VecDeque<Duration>,
+}
+
+/// The counters shared by the connections, kept across restarts.
+#[derive(Default)]
+pub(crate) struct Counters {
+    pub(crate) active_connections: AtomicUsize,
+    pub(crate) rejected_connections: AtomicU64,
+    pub(crate) rate_limited_requests: AtomicU64,
+    requests: Mutex<Requests>,
+}
+
+impl Counters {
+    /// Records a request answered with the status after the latency.
+    pub(crate) fn record(&self, status: u16, latency: Duration) {
+        let mut requests = self.requests.lock().unwrap();
+        *requests.by_status.entry(status).or_default() += 1;
+        if requests.latencies.len() == LATENCY_SAMPLES {
+            requests.latencies.pop_front();
+        }
+        requests.latencies.push_back(latency);
+    }
+
+    pub(crate) fn snapshot(&self) -> Metrics {
+        let requests = self.requests.lock().unwrap();
+        let mut latencies = requests.latencies.iter().copied().collect::<Vec<_>>();
+        latencies.sort_unstable();
+        // nearest-rank percentile
+        let p95_latency_ms = latencies
+            .len()
+            .checked_sub(1)
+            .map(|last| latencies[(last * 95).div_ceil(100)].as_secs_f64() * 1000.0);
+
+        Metrics {
+            active_connections: self.active_connections.load(Ordering::Relaxed),
+            rejected_connections: self.rejected_connections.load(Ordering::Relaxed),
+            rate_limited_requests: self.rate_limited_requests.load(Ordering::Relaxed),
+            requests_by_status: requests.by_status.clone(),
+            p95_latency_ms,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Directories of the file system served under a path prefix, alongside the assets.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use http::{
+    header::{CONTENT_TYPE, ETAG, LAST_MODIFIED},
+    response::Builder,
+    HeaderMap, Method, Response, StatusCode,
+};
+use http_body_util::Full;
+use hyper::body::Bytes;
+use percent_encoding::percent_decode_str;
+use tauri::utils::mime_type::MimeType;
+
+use crate::{cache, range};
+
+pub(crate) struct Mount {
+    /// Starts with a slash, and has no trailing slash unless it is the root.
+    prefix: String,
+    dir: PathBuf,
+}
+
+impl Mount {
+    pub(crate) fn new(prefix: String, dir: PathBuf) -> Self {
+        let prefix = format!("/{}", prefix.trim_matches('/'));
+        Self { prefix, dir }
+    }
+
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the path relative to the directory if the request path is under the prefix.
+    pub(crate) fn relative<'a>(&self, path: &'a str) -> Option<&'a str> {
+        let rest = path.strip_prefix(self.prefix.trim_end_matches('/'))?;
+        (rest.is_empty() || rest.starts_with('/')).then_some(rest)
+    }
+
+    /// Resolves the file, `index.html` for the directories, without leaving the directory.
+    async fn file(&self, relative: &str) -> Option<PathBuf> {
+        let mut path = self.dir.clone();
+        for segment in relative.split('/').filter(|segment| !segment.is_empty()) {
+            let segment = percent_decode_str(segment).decode_utf8().ok()?;
+            if segment == "." || segment == ".." || segment.contains(['/', '\\', ':', '\0']) {
+                return None;
+            }
+            path.push(segment.as_ref());
+        }
+
+        // the symbolic links must not lead out of the directory either
+        let root = tokio::fs::canonicalize(&self.dir).await.ok()?;
+        let mut path = tokio::fs::canonicalize(&path).await.ok()?;
+        if !path.starts_with(&root) {
+            return None;
+        }
+        if tokio::fs::metadata(&path).await.ok()?.is_dir() {
+            path.push("index.html");
+        }
+        tokio::fs::metadata(&path)
+            .await
+            .ok()?
+            .is_file()
+            .then_some(path)
+    }
+
+    /// Serves the file, with a weak entity tag as the file can change while the server runs.
+    pub(crate) async fn respond(
+        &self,
+        relative: &str,
+        method: &Method,
+        request: &HeaderMap,
+        mut response: Builder,
+    ) -> http::Result<Response<Full<Bytes>>> {
+        let Some(file) = self.file(relative).await else {
+            return not_found();
+        };
+        let (Ok(metadata), Ok(bytes)) = (
+            tokio::fs::metadata(&file).await,
+            tokio::fs::read(&file).await,
+        ) else {
+            return not_found();
+        };
+
+        let modified = metadata.modified().ok().map(cache::http_time);
+        let version = modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs())
+            .unwrap_or_default();
+        let etag = format!("\"{:x}-{:x}\"", bytes.len(), version);
+
+        response = response
+            .header(CONTENT_TYPE, mime_type(&file, &bytes))
+            .header(ETAG, format!("W/{etag}"));
+        if let Some(modified) = modified {
+            response = response.header(LAST_MODIFIED, httpdate::fmt_http_date(modified));
+        }
+        let last_modified = modified.unwrap_or_else(SystemTime::now);
+        if cache::is_not_modified(method, request, &[&etag], last_modified) {
+            return response
+                .status(StatusCode::NOT_MODIFIED)
+                .body(Full::default());
+        }
+
+        // weak entity tags cannot validate `If-Range`
+        range::respond(request, response, bytes, None)
+    }
+}
+
+fn mime_type(file: &Path, bytes: &[u8]) -> String {
+    MimeType::parse(bytes, &file.to_string_lossy())
+}
+
+fn not_found() -> http::Result<Response<Full<Bytes>>> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Full::default())
+}
@@ -0,0 +1,380 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! `Range` requests of the assets, so media elements can seek.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use http::{
+    header::{ACCEPT_RANGES, CONTENT_RANGE, CONTENT_TYPE, IF_RANGE, RANGE},
+    response::Builder,
+    HeaderMap, HeaderValue, Response, StatusCode,
+};
+use http_body_util::Full;
+use hyper::body::Bytes;
+
+/// More ranges than this are served as the full content, to avoid tiny ranges amplifying the response.
+const MAX_RANGES: usize = 32;
+
+enum Ranges {
+    /// The header is absent, invalid or not in bytes.
+    Full,
+    /// The inclusive ranges, sorted and merged.
+    Partial(Vec<(u64, u64)>),
+    Unsatisfiable,
+}
+
+/// Parses the `Range` header for content of the given length, invalid headers are ignored.
+fn parse(header: &str, len: u64) -> Ranges {
+    let Some(specs) = header.trim().strip_prefix("bytes=") else {
+        return Ranges::Full;
+    };
+
+    let mut ranges = Vec::new();
+    for spec in specs.split(',') {
+        let Some((start, end)) = spec.trim().split_once('-') else {
+            return Ranges::Full;
+        };
+        let (start, end) = match (start.trim(), end.trim()) {
+            ("", "") => return Ranges::Full,
+            // the last bytes
+            ("", suffix) => match suffix.parse::<u64>() {
+                Ok(suffix) if suffix > 0 && len > 0 => (len.saturating_sub(suffix), len - 1),
+                Ok(_) => continue,
+                Err(_) => return Ranges::Full,
+            },
+            (start, end) => {
+                let Ok(start) = start.parse::<u64>() else {
+                    return Ranges::Full;
+                };
+                let end = if end.is_empty() {
+                    u64::MAX
+                } else {
+                    match end.parse::<u64>() {
+                        Ok(end) if end >= start => end,
+                        _ => return Ranges::Full,
+                    }
+                };
+                if start >= len {
+                    continue;
+                }
+                (start, end.min(len - 1))
+            }
+        };
+        ranges.push((start, end));
+    }
+
+    if ranges.is_empty() {
+        return Ranges::Unsatisfiable;
+    }
+
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    if merged.len() > MAX_RANGES {
+        return Ranges::Full;
+    }
+    Ranges::Partial(merged)
+}
+
+/// Whether the validator of the `If-Range` header matches the current content.
+///
+/// Only strong entity tags can match, dates are never matched as `Last-Modified` is a weak validator.
+fn if_range_matches(value: &str, etag: Option<&str>) -> bool {
+    let value = value.trim();
+    value.starts_with('"') && etag.is_some_and(|etag| etag == value)
+}
+
+fn random_boundary() -> String {
+    format!("{:016x}", RandomState::new().build_hasher().finish())
+}
+
+/// Builds the response with the requested ranges of the body, or the full body.
+///
+/// `etag` is the strong entity tag of the content, if any, to evaluate `If-Range`.
+pub(crate) fn respond(
+    request: &HeaderMap,
+    mut response: Builder,
+    body: Vec<u8>,
+    etag: Option<&str>,
+) -> http::Result<Response<Full<Bytes>>> {
+    let len = body.len() as u64;
+    response = response.header(ACCEPT_RANGES, "bytes");
+
+    let range = request.get(RANGE).and_then(|value| value.to_str().ok());
+    let if_range = request
+        .get(IF_RANGE)
+        .map(|value| {
+            value
+                .to_str()
+                .is_ok_and(|value| if_range_matches(value, etag))
+        })
+        .unwrap_or(true);
+
+    match range.filter(|_| if_range).map(|range| parse(range, len)) {
+        None | Some(Ranges::Full) => response.body(Full::from(body)),
+        Some(Ranges::Unsatisfiable) => response
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(CONTENT_RANGE, format!("bytes */{len}"))
+            .body(Full::default()),
+        Some(Ranges::Partial(ranges)) => {
+            if let [(start, end)] = ranges[..] {
+                return response
+                    .status(StatusCode::PARTIAL_CONTENT)
+                    .header(CONTENT_RANGE, format!("bytes {start}-{end}/{len}"))
+                    .body(Full::from(body[start as usize..=end as usize].to_vec()));
+            }
+
+            let boundary = random_boundary();
+            let content_type = response
+                .headers_mut()
+                .and_then(|headers| {
+                    headers.insert(
+                        CONTENT_TYPE,
+                        HeaderValue::from_str(&format!(
+                            "multipart/byteranges; boundary={boundary}"
+                        ))
+                        .ok()?,
+                    )
+                })
+                .and_then(|value| value.to_str().map(ToString::to_string).ok());
+
+            let mut multipart = Vec::new();
+            for (start, end) in ranges {
+                multipart.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+                if let Some(content_type) = &content_type {
+                    multipart
+                        .extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+                }
+                multipart.extend_from_slice(
+                    format!("Content-Range: bytes {start}-{end}/{len}\r\n\r\n").as_bytes(),
+                );
+                multipart.extend_from_slice(&body[start as usize..=end as usize]);
+                multipart.extend_from_slice(b"\r\n");
+            }
+            multipart.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+            response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .body(Full::from(multipart))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    use super::*;
+
+    fn ranges(header: &str, len: u64) -> Option<Vec<(u64, u64)>> {
+        match parse(header, len) {
+            Ranges::Partial(ranges) => Some(ranges),
+            _ => None,
+        }
+    }
+
+    fn is_full(header: &str, len: u64) -> bool {
+        matches!(parse(header, len), Ranges::Full)
+    }
+
+    fn is_unsatisfiable(header: &str, len: u64) -> bool {
+        matches!(parse(header, len), Ranges::Unsatisfiable)
+    }
+
+    fn request(headers: &[(http::HeaderName, &str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    fn body(response: Response<Full<Bytes>>) -> Vec<u8> {
+        tauri::async_runtime::block_on(response.into_body().collect())
+            .unwrap()
+            .to_bytes()
+            .to_vec()
+    }
+
+    #[test]
+    fn merges_ranges() {
+        // overlapping
+        assert_eq!(ranges("bytes=0-9,5-19", 100), Some(vec![(0, 19)]));
+        // adjacent
+        assert_eq!(ranges("bytes=0-9,10-19", 100), Some(vec![(0, 19)]));
+        // contained
+        assert_eq!(ranges("bytes=0-49,10-19", 100), Some(vec![(0, 49)]));
+        // unsorted and disjoint
+        assert_eq!(
+            ranges("bytes=50-59, 0-9", 100),
+            Some(vec![(0, 9), (50, 59)])
+        );
+    }
+
+    #[test]
+    fn clamps_ranges() {
+        assert_eq!(ranges("bytes=90-", 100), Some(vec![(90, 99)]));
+        assert_eq!(ranges("bytes=90-200", 100), Some(vec![(90, 99)]));
+        // the ranges starting after the content are skipped
+        assert_eq!(ranges("bytes=0-9,100-109", 100), Some(vec![(0, 9)]));
+        assert!(is_unsatisfiable("bytes=100-", 100));
+    }
+
+    #[test]
+    fn suffix_ranges() {
+        assert_eq!(ranges("bytes=-10", 100), Some(vec![(90, 99)]));
+        assert_eq!(ranges("bytes=-500", 100), Some(vec![(0, 99)]));
+        // a zero suffix selects nothing
+        assert!(is_unsatisfiable("bytes=-0", 100));
+        assert_eq!(ranges("bytes=-0,0-9", 100), Some(vec![(0, 9)]));
+    }
+
+    #[test]
+    fn empty_content() {
+        assert!(is_unsatisfiable("bytes=0-", 0));
+        assert!(is_unsatisfiable("bytes=-10", 0));
+    }
+
+    #[test]
+    fn invalid_headers_are_ignored() {
+        assert!(is_full("items=0-9", 100));
+        assert!(is_full("bytes=", 100));
+        assert!(is_full("bytes=-", 100));
+        assert!(is_full("bytes=abc", 100));
+        assert!(is_full("bytes=9-0", 100));
+        assert!(is_full("bytes=0-9,x-y", 100));
+    }
+
+    #[test]
+    fn too_many_ranges() {
+        let disjoint = (0..=MAX_RANGES)
+            .map(|i| format!("{0}-{0}", i * 2))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(is_full(&format!("bytes={disjoint}"), 1000));
+
+        // counted once merged
+        let overlapping = (0..=MAX_RANGES)
+            .map(|i| format!("{i}-{}", i + 1))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(
+            ranges(&format!("bytes={overlapping}"), 1000),
+            Some(vec![(0, MAX_RANGES as u64 + 1)])
+        );
+    }
+
+    #[test]
+    fn if_range() {
+        let etag = Some("\"abc\"");
+        assert!(if_range_matches("\"abc\"", etag));
+        assert!(!if_range_matches("\"def\"", etag));
+        // weak tags and dates never match
+        assert!(!if_range_matches("W/\"abc\"", Some("W/\"abc\"")));
+        assert!(!if_range_matches("Wed, 21 Oct 2015 07:28:00 GMT", etag));
+        assert!(!if_range_matches("\"abc\"", None));
+    }
+
+    #[test]
+    fn responds_with_a_range() {
+        let content = (0..100u8).collect::<Vec<_>>();
+        let response = respond(
+            &request(&[(RANGE, "bytes=10-19")]),
+            Response::builder(),
+            content.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 10-19/100");
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert_eq!(body(response), content[10..20]);
+    }
+
+    #[test]
+    fn responds_with_the_full_content() {
+        let content = (0..100u8).collect::<Vec<_>>();
+        for headers in [
+            vec![],
+            vec![(RANGE, "bytes=abc")],
+            // the content changed
+            vec![(RANGE, "bytes=10-19"), (IF_RANGE, "\"def\"")],
+            vec![(RANGE, "bytes=10-19"), (IF_RANGE, "W/\"abc\"")],
+        ] {
+            let response = respond(
+                &request(&headers),
+                Response::builder(),
+                content.clone(),
+                Some("\"abc\""),
+            )
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get(CONTENT_RANGE).is_none());
+            assert_eq!(body(response), content);
+        }
+
+        let response = respond(
+            &request(&[(RANGE, "bytes=10-19"), (IF_RANGE, "\"abc\"")]),
+            Response::builder(),
+            content,
+            Some("\"abc\""),
+        )
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    }
+
+    #[test]
+    fn responds_unsatisfiable() {
+        for (content, len) in [(vec![0; 100], 100), (Vec::new(), 0)] {
+            let response = respond(
+                &request(&[(RANGE, "bytes=100-")]),
+                Response::builder(),
+                content,
+                None,
+            )
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+            assert_eq!(
+                response.headers()[CONTENT_RANGE],
+                format!("bytes */{len}").as_str()
+            );
+            assert!(body(response).is_empty());
+        }
+    }
+
+    #[test]
+    fn responds_with_multiple_ranges() {
+        let content = b"0123456789abcdefghij".to_vec();
+        let response = respond(
+            &request(&[(RANGE, "bytes=0-1,10-11")]),
+            Response::builder().header(CONTENT_TYPE, "text/plain"),
+            content,
+            None,
+        )
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+
+        let content_type = response.headers()[CONTENT_TYPE].to_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            String::from_utf8(body(response)).unwrap(),
+            format!(
+                "--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-1/20\r\n\r\n01\r\n\
+                 --{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 10-11/20\r\n\r\nab\r\n\
+                 --{boundary}--\r\n"
+            )
+        );
+    }
+}
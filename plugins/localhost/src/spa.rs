@@ -0,0 +1,53 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Fallback to the entry point of single-page applications, so client-side routes survive reloads.
+
+use http::{header::ACCEPT, HeaderMap, Method};
+
+pub(crate) struct SpaFallback {
+    /// The asset served instead of the missing ones.
+    pub(crate) path: String,
+    /// The path prefixes that are never served the fallback.
+    exclude: Vec<String>,
+}
+
+impl SpaFallback {
+    /// Creates the fallback to the asset, excluding the paths under the prefixes.
+    ///
+    /// `/api` and `/api/*` both exclude `/api` and `/api/users`.
+    pub(crate) fn new(path: String, exclude: Vec<String>) -> Self {
+        let exclude = exclude
+            .iter()
+            .map(|prefix| {
+                prefix
+                    .trim_end_matches('*')
+                    .trim_end_matches('/')
+                    .to_string()
+            })
+            .collect();
+        Self { path, exclude }
+    }
+
+    /// Whether the fallback should be served for a path missing from the assets.
+    ///
+    /// Only navigations are served the fallback, so missing scripts or images are still not found.
+    pub(crate) fn applies(&self, method: &Method, request: &HeaderMap, path: &str) -> bool {
+        if *method != Method::GET && *method != Method::HEAD {
+            return false;
+        }
+
+        let excluded = self.exclude.iter().any(|prefix| {
+            path.strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        });
+        let accepts_html = request
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| value.contains("text/html"));
+
+        !excluded && accepts_html
+    }
+}
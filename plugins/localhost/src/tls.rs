@@ -0,0 +1,60 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use rustls::{
+    pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer},
+    ServerConfig,
+};
+use tokio_rustls::TlsAcceptor;
+
+use crate::Error;
+
+/// The certificate of the HTTPS server.
+pub(crate) enum Tls {
+    /// A PEM encoded certificate chain and private key.
+    Pem { cert: Vec<u8>, key: Vec<u8> },
+    /// A certificate generated on startup for `localhost` and the host of the server.
+    SelfSigned,
+}
+
+impl Tls {
+    pub(crate) fn acceptor(&self, host: &str) -> Result<TlsAcceptor, Error> {
+        let (certs, key) = match self {
+            Self::Pem { cert, key } => {
+                let certs =
+                    rustls_pemfile::certs(&mut cert.as_slice()).collect::<Result<Vec<_>, _>>()?;
+                let key = rustls_pemfile::private_key(&mut key.as_slice())?
+                    .ok_or("no private key found in the PEM data")?;
+                (certs, key)
+            }
+            Self::SelfSigned => {
+                let mut names = vec!["localhost".to_string()];
+                if host != "localhost" {
+                    names.push(host.to_string());
+                }
+                let certified = rcgen::generate_simple_self_signed(names)?;
+                let key = PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der());
+                (
+                    vec![CertificateDer::from(certified.cert.der().to_vec())],
+                    PrivateKeyDer::Pkcs8(key),
+                )
+            }
+        };
+        if certs.is_empty() {
+            return Err("no certificate found in the PEM data".into());
+        }
+
+        // use an explicit provider so the config does not depend on the features enabled by other crates
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_no_client_auth()
+                .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
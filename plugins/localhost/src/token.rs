@@ -0,0 +1,110 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Per-launch access token, so other processes on the machine cannot read the assets from the port.
+
+use std::fmt::Write;
+
+use http::{
+    header::{COOKIE, SET_COOKIE},
+    HeaderMap, HeaderValue, Uri,
+};
+use rand::RngCore;
+use url::Url;
+
+/// The query parameter carrying the token, added to the navigations of the webviews.
+const QUERY_PARAM: &str = "localhost_token";
+
+/// The header carrying the token, for the clients that cannot use the query or the cookie.
+const HEADER: &str = "x-localhost-token";
+
+/// The cookie set once a request is authorized with the query, so the webview sends the token with all its requests.
+const COOKIE_NAME: &str = "localhost_token";
+
+pub(crate) enum Access {
+    Granted,
+    /// Granted with the query parameter, the response should set the cookie.
+    GrantedByQuery,
+    Denied,
+}
+
+pub(crate) struct AccessToken(String);
+
+impl AccessToken {
+    /// Generates a random token of 256 bits.
+    pub(crate) fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let mut token = String::with_capacity(64);
+        for byte in bytes {
+            let _ = write!(token, "{byte:02x}");
+        }
+        Self(token)
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Compares in constant time so the token cannot be guessed from the response times.
+    fn matches(&self, value: &str) -> bool {
+        value.len() == self.0.len()
+            && value
+                .bytes()
+                .zip(self.0.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+
+    /// Checks the token of the query, the header or the cookie of the request.
+    pub(crate) fn check(&self, uri: &Uri, request: &HeaderMap) -> Access {
+        let header = request
+            .get(HEADER)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| self.matches(value));
+        let cookie = request
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .any(|(name, value)| name == COOKIE_NAME && self.matches(value));
+        if header || cookie {
+            return Access::Granted;
+        }
+
+        let query = uri
+            .query()
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .any(|(name, value)| name == QUERY_PARAM && self.matches(&value))
+            })
+            .unwrap_or(false);
+        if query {
+            Access::GrantedByQuery
+        } else {
+            Access::Denied
+        }
+    }
+
+    /// Sets the cookie carrying the token on the response.
+    pub(crate) fn set_cookie(&self, response: &mut HeaderMap, secure: bool) {
+        let secure = if secure { "; Secure" } else { "" };
+        if let Ok(value) = HeaderValue::from_str(&format!(
+            "{COOKIE_NAME}={}; Path=/; HttpOnly; SameSite=Strict{secure}",
+            self.0
+        )) {
+            response.append(SET_COOKIE, value);
+        }
+    }
+}
+
+/// Adds the token to the URL, returns `false` if the URL already has it.
+pub(crate) fn add_to_url(url: &mut Url, token: &str) -> bool {
+    if url.query_pairs().any(|(name, _)| name == QUERY_PARAM) {
+        return false;
+    }
+    url.query_pairs_mut().append_pair(QUERY_PARAM, token);
+    true
+}
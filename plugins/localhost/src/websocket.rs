@@ -0,0 +1,108 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! WebSocket proxy to the dev server, for the hot module replacement of Vite or socket.io.
+
+use std::fmt::Display;
+
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use http::{
+    header::{ACCEPT_LANGUAGE, AUTHORIZATION, COOKIE, ORIGIN, SEC_WEBSOCKET_PROTOCOL, USER_AGENT},
+    HeaderName, Response, StatusCode,
+};
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::Request;
+use tungstenite::{client::IntoClientRequest, protocol::Message};
+use url::Url;
+
+/// The headers of the upgrade request forwarded to the dev server.
+const FORWARDED_HEADERS: [HeaderName; 6] = [
+    ACCEPT_LANGUAGE,
+    AUTHORIZATION,
+    COOKIE,
+    ORIGIN,
+    SEC_WEBSOCKET_PROTOCOL,
+    USER_AGENT,
+];
+
+/// Connects to the dev server, then upgrades the request with the subprotocol it accepted.
+///
+/// `url` is the URL of the request on the dev server, with an `http` or `https` scheme.
+pub(crate) async fn proxy(
+    req: Request<Incoming>,
+    mut url: Url,
+) -> Result<Response<Full<Bytes>>, crate::Error> {
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    let _ = url.set_scheme(scheme);
+
+    let mut upstream_req = url.as_str().into_client_request()?;
+    for name in FORWARDED_HEADERS {
+        for value in req.headers().get_all(&name) {
+            upstream_req.headers_mut().append(&name, value.clone());
+        }
+    }
+
+    let (upstream, upstream_res) = match tokio_tungstenite::connect_async(upstream_req).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            log::error!("failed to connect to the dev server websocket {url}: {e}");
+            return Ok(Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(Full::default())?);
+        }
+    };
+
+    let (mut response, websocket) = hyper_tungstenite::upgrade(req, None)?;
+    if let Some(protocol) = upstream_res.headers().get(SEC_WEBSOCKET_PROTOCOL) {
+        response
+            .headers_mut()
+            .insert(SEC_WEBSOCKET_PROTOCOL, protocol.clone());
+    }
+
+    tokio::spawn(async move {
+        let websocket = match websocket.await {
+            Ok(websocket) => websocket,
+            Err(e) => {
+                log::error!("websocket upgrade failed: {e}");
+                return;
+            }
+        };
+        let (webview_write, webview_read) = websocket.split();
+        let (upstream_write, upstream_read) = upstream.split();
+        tokio::join!(
+            relay(webview_read, upstream_write, "dev server"),
+            relay(upstream_read, webview_write, "webview"),
+        );
+    });
+
+    Ok(response)
+}
+
+/// Forwards the messages, including the ping, pong and close frames, until the connection is closed.
+async fn relay<S, D, E>(mut from: S, mut to: D, target: &str)
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin,
+    D: Sink<Message, Error = E> + Unpin,
+    E: Display,
+{
+    while let Some(message) = from.next().await {
+        let message = match message {
+            Ok(message) => message,
+            Err(e) => {
+                log::debug!("websocket connection closed: {e}");
+                break;
+            }
+        };
+        let close = message.is_close();
+        if let Err(e) = to.send(message).await {
+            log::debug!("failed to forward a websocket message to the {target}: {e}");
+            break;
+        }
+        if close {
+            break;
+        }
+    }
+    let _ = to.close().await;
+}
@@ -0,0 +1,59 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+package app.tauri.nfc
+
+import android.nfc.cardemulation.HostApduService
+import android.os.Bundle
+
+/**
+ * Host card emulation service that forwards the APDUs of the reader to the plugin.
+ *
+ * The AIDs are registered dynamically by [NfcPlugin.startCardEmulation]
+ * and the responses are sent with [respond].
+ */
+class NfcHceService : HostApduService() {
+    companion object {
+        // "conditions of use not satisfied", sent when no handler is registered
+        private val CONDITIONS_NOT_SATISFIED = byteArrayOf(0x69, 0x85.toByte())
+
+        var instance: NfcHceService? = null
+        var onCommand: ((ByteArray) -> Unit)? = null
+        var onDeactivated: ((Int) -> Unit)? = null
+
+        /**
+         * Sends the response to the last command of the reader.
+         *
+         * @return false if no reader is connected.
+         */
+        fun respond(data: ByteArray): Boolean {
+            val service = instance ?: return false
+            service.sendResponseApdu(data)
+            return true
+        }
+    }
+
+    override fun onCreate() {
+        super.onCreate()
+        instance = this
+    }
+
+    override fun onDestroy() {
+        if (instance === this) {
+            instance = null
+        }
+        super.onDestroy()
+    }
+
+    override fun processCommandApdu(commandApdu: ByteArray, extras: Bundle?): ByteArray? {
+        val handler = onCommand ?: return CONDITIONS_NOT_SATISFIED
+        handler(commandApdu)
+        // the response is sent asynchronously by the handler
+        return null
+    }
+
+    override fun onDeactivated(reason: Int) {
+        onDeactivated?.invoke(reason)
+    }
+}
@@ -6,15 +6,28 @@ package app.tauri.nfc
 
 import android.app.Activity
 import android.app.PendingIntent
+import android.content.ComponentName
 import android.content.Intent
 import android.content.IntentFilter
+import android.content.pm.PackageManager
 import android.nfc.NdefMessage
 import android.nfc.NdefRecord
 import android.nfc.NfcAdapter
 import android.nfc.Tag
+import android.nfc.cardemulation.CardEmulation
+import android.nfc.cardemulation.HostApduService
+import android.nfc.tech.IsoDep
+import android.nfc.tech.MifareClassic
+import android.nfc.tech.MifareUltralight
 import android.nfc.tech.Ndef
 import android.nfc.tech.NdefFormatable
+import android.nfc.tech.NfcA
+import android.nfc.tech.NfcB
+import android.nfc.tech.NfcF
+import android.nfc.tech.NfcV
+import android.nfc.tech.TagTechnology
 import android.os.Build
+import android.os.Bundle
 import android.os.Parcelable
 import android.os.PatternMatcher
 import android.webkit.WebView
@@ -22,6 +35,7 @@ import app.tauri.Logger
 import app.tauri.annotation.Command
 import app.tauri.annotation.InvokeArg
 import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Channel
 import app.tauri.plugin.Invoke
 import app.tauri.plugin.JSArray
 import app.tauri.plugin.JSObject
@@ -38,7 +52,7 @@ import kotlin.concurrent.thread
 
 sealed class NfcAction {
     object Read : NfcAction()
-    data class Write(val message: NdefMessage) : NfcAction()
+    data class Write(val message: NdefMessage, val makeReadOnly: Boolean) : NfcAction()
 }
 
 @InvokeArg
@@ -182,10 +196,18 @@ internal class ScanKindDeserializer: JsonDeserializer<ScanKind>() {
     }
 }
 
+@InvokeArg
+class ReaderModeOptions {
+    var skipNdefCheck: Boolean = false
+    var noPlatformSounds: Boolean = false
+    var presenceCheckDelay: Int? = null
+}
+
 @InvokeArg
 class ScanOptions {
     lateinit var kind: ScanKind
     var keepSessionAlive: Boolean = false
+    var readerMode: ReaderModeOptions? = null
 }
 
 @InvokeArg
@@ -200,15 +222,52 @@ class NDEFRecordData {
 class WriteOptions {
     var kind: ScanKind? = null
     lateinit var records: Array<NDEFRecordData>
+    var makeReadOnly: Boolean = false
+    var keepSessionAlive: Boolean = false
+    var readerMode: ReaderModeOptions? = null
+}
+
+@InvokeArg
+class TransceiveOptions {
+    lateinit var data: ByteArray
+    var technology: TechKind? = null
+    var timeout: Int? = null
+}
+
+@InvokeArg
+class CardEmulationOptions {
+    lateinit var aids: Array<String>
+    // "payment" or "other"
+    var category: String = CardEmulation.CATEGORY_OTHER
+    lateinit var channel: Channel
+}
+
+@InvokeArg
+class RespondApduOptions {
+    lateinit var data: ByteArray
 }
 
 class Session(
     val action: NfcAction,
     val invoke: Invoke,
-    val keepAlive: Boolean,
+    var keepAlive: Boolean,
     var tag: Tag? = null,
     val filters: Array<IntentFilter>? = null,
-    val techLists: Array<Array<String>>? = null
+    val techLists: Array<Array<String>>? = null,
+    val readerMode: ReaderModeOptions? = null,
+    // technology connected by transceive(), only one can be connected at a time
+    var technology: TagTechnology? = null
+)
+
+// technologies that support raw commands, in order of preference
+private val TRANSCEIVE_TECHNOLOGIES = arrayOf(
+    TechKind.IsoDep,
+    TechKind.MifareUltralight,
+    TechKind.MifareClassic,
+    TechKind.NfcA,
+    TechKind.NfcB,
+    TechKind.NfcF,
+    TechKind.NfcV
 )
 
 @TauriPlugin
@@ -217,57 +276,102 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
 
     private var nfcAdapter: NfcAdapter? = null
     private var session: Session? = null
+    // tags discovered while there was no scan in progress, e.g. the tag that launched the app
+    private val pendingTags: MutableList<JSObject> = mutableListOf()
+    private var cardEmulation: CardEmulationOptions? = null
 
     override fun load(webView: WebView) {
         super.load(webView)
         this.webView = webView
         this.nfcAdapter = NfcAdapter.getDefaultAdapter(activity.applicationContext)
+
+        // the app was launched by one of the NFC intent filters
+        val intent = activity.intent
+        if (intent != null && isNfcIntent(intent)) {
+            tagFromIntent(intent)?.let { tag ->
+                queueTag(tag, ndefMessagesFromIntent(intent))
+            }
+        }
     }
 
     override fun onNewIntent(intent: Intent) {
         Logger.info("NFC", "onNewIntent")
         super.onNewIntent(intent)
 
-        val extraTag = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+        if (!isNfcIntent(intent)) {
+            return
+        }
+        tagFromIntent(intent)?.let { tag ->
+            handleTag(tag, ndefMessagesFromIntent(intent))
+        }
+    }
+
+    private fun isNfcIntent(intent: Intent): Boolean {
+        return intent.action == NfcAdapter.ACTION_NDEF_DISCOVERED
+            || intent.action == NfcAdapter.ACTION_TECH_DISCOVERED
+            || intent.action == NfcAdapter.ACTION_TAG_DISCOVERED
+    }
+
+    private fun tagFromIntent(intent: Intent): Tag? {
+        return if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
             intent.getParcelableExtra(NfcAdapter.EXTRA_TAG, Tag::class.java)
         } else {
             @Suppress("DEPRECATION")
             intent.getParcelableExtra(NfcAdapter.EXTRA_TAG)
         }
+    }
 
-        extraTag?.let { tag ->
-            session?.let {
-                if (it.keepAlive) {
-                    it.tag = tag
-                }
-            }
+    private fun ndefMessagesFromIntent(intent: Intent): Array<Parcelable>? {
+        return if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+            intent.getParcelableArrayExtra(NfcAdapter.EXTRA_NDEF_MESSAGES, Parcelable::class.java)
+        } else {
+            @Suppress("DEPRECATION")
+            intent.getParcelableArrayExtra(NfcAdapter.EXTRA_NDEF_MESSAGES)
+        }
+    }
 
-            when (session?.action) {
-                is NfcAction.Read -> readTag(tag, intent)
-                is NfcAction.Write -> thread {
-                    if (session?.action is NfcAction.Write) {
-                        try {
-                            writeTag(tag, (session?.action as NfcAction.Write).message)
-                            session?.invoke?.resolve()
-                        } catch (e: Exception) {
-                            session?.invoke?.reject(e.toString())
-                        } finally {
-                            if (this.session?.keepAlive != true) {
-                                this.session = null
-                                disableNFCInForeground()
-                            }
-                        }
+    private fun queueTag(tag: Tag, rawMessages: Array<Parcelable>?) {
+        val data = tagToJson(tag, rawMessages)
+        pendingTags.add(data)
+        trigger("tagDiscovered", data)
+    }
+
+    private fun handleTag(tag: Tag, rawMessages: Array<Parcelable>?) {
+        val session = this.session
+        if (session == null) {
+            queueTag(tag, rawMessages)
+            return
+        }
+
+        if (session.keepAlive) {
+            session.tag = tag
+            // the connected technology belongs to the previous tag
+            session.technology = null
+        }
+
+        when (val action = session.action) {
+            is NfcAction.Read -> readTag(tag, rawMessages)
+            is NfcAction.Write -> thread {
+                try {
+                    writeTag(tag, action.message, action.makeReadOnly)
+                    session.invoke.resolve()
+                } catch (e: Exception) {
+                    session.invoke.reject(e.toString())
+                } finally {
+                    if (!session.keepAlive) {
+                        this.session = null
+                        disableNFCInForeground()
                     }
                 }
-
-                else -> {}
             }
         }
-
     }
 
     override fun onPause() {
         disableNFCInForeground()
+        if (cardEmulation != null) {
+            cardEmulationService()?.unsetPreferredService(activity)
+        }
         super.onPause()
         Logger.info("NFC", "onPause")
     }
@@ -276,7 +380,10 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
         super.onResume()
         Logger.info("NFC", "onResume")
         session?.let {
-            enableNFCInForeground(it.filters, it.techLists)
+            enableNFCInForeground(it)
+        }
+        if (cardEmulation != null) {
+            cardEmulationService()?.setPreferredService(activity, hceComponent())
         }
     }
 
@@ -314,11 +421,17 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
 
         val args = invoke.parseArgs(ScanOptions::class.java)
 
-        val filters = args.kind.filters()
-        val techLists = args.kind.techLists()
-        enableNFCInForeground(filters, techLists)
-
-        session = Session(NfcAction.Read, invoke, args.keepSessionAlive, null, filters, techLists)
+        val session = Session(
+            NfcAction.Read,
+            invoke,
+            args.keepSessionAlive,
+            null,
+            args.kind.filters(),
+            args.kind.techLists(),
+            args.readerMode
+        )
+        this.session = session
+        enableNFCInForeground(session)
     }
 
     @Command
@@ -331,17 +444,22 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
 
         val args = invoke.parseArgs(WriteOptions::class.java)
 
-        val ndefRecords: MutableList<NdefRecord> = ArrayList()
-        for (record in args.records) {
-            ndefRecords.add(NdefRecord(record.format, record.kind, record.id, record.payload))
+        val message = try {
+            val ndefRecords: MutableList<NdefRecord> = ArrayList()
+            for (record in args.records) {
+                ndefRecords.add(NdefRecord(record.format, record.kind, record.id, record.payload))
+            }
+            NdefMessage(ndefRecords.toTypedArray())
+        } catch (e: IllegalArgumentException) {
+            invoke.reject("Invalid NDEF record: ${e.message}")
+            return
         }
 
-        val message = NdefMessage(ndefRecords.toTypedArray())
-
         session?.let { session ->
             session.tag?.let {
                 try {
-                    writeTag(it, message)
+                    session.keepAlive = args.keepSessionAlive
+                    writeTag(it, message, args.makeReadOnly)
                     invoke.resolve()
                 } catch (e: Exception) {
                     invoke.reject(e.toString())
@@ -355,46 +473,252 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
                 invoke.reject("connected tag not found, please wait for it to be available and then call write()")
             }
         } ?: run {
-            args.kind?.let { kind -> {
-                val filters = kind.filters()
-                val techLists = kind.techLists()
-                enableNFCInForeground(filters, techLists)
-                session = Session(NfcAction.Write(message), invoke, true, null, filters, techLists)
+            args.kind?.let { kind ->
+                val session = Session(
+                    NfcAction.Write(message, args.makeReadOnly),
+                    invoke,
+                    args.keepSessionAlive,
+                    null,
+                    kind.filters(),
+                    kind.techLists(),
+                    args.readerMode
+                )
+                this.session = session
+                enableNFCInForeground(session)
                 Logger.warn("NFC", "Write Mode Enabled")
-            }} ?: run {
+            } ?: run {
                 invoke.reject("Missing `kind` for write")
             }
 
         }
     }
 
-    private fun readTag(tag: Tag, intent: Intent) {
+    /**
+     * Ends the current scan or write session, e.g. one kept alive for a multi-step flow.
+     */
+    @Command
+    fun endSession(invoke: Invoke) {
+        session?.technology?.let {
+            try {
+                it.close()
+            } catch (e: IOException) {
+                Logger.error("failed to close tag technology", e)
+            }
+        }
+        session = null
+        disableNFCInForeground()
+        invoke.resolve()
+    }
+
+    /**
+     * The session message is only displayed on iOS.
+     */
+    @Command
+    fun setSessionMessage(invoke: Invoke) {
+        invoke.resolve()
+    }
+
+    /**
+     * Returns and clears the tags discovered while there was no scan in progress.
+     */
+    @Command
+    fun takePendingTags(invoke: Invoke) {
+        val ret = JSObject()
+        ret.put("tags", JSArray.from(pendingTags.toTypedArray()))
+        pendingTags.clear()
+        invoke.resolve(ret)
+    }
+
+    private fun hceComponent(): ComponentName {
+        return ComponentName(activity, NfcHceService::class.java)
+    }
+
+    private fun cardEmulationService(): CardEmulation? {
+        val adapter = nfcAdapter ?: return null
+        if (!activity.packageManager.hasSystemFeature(PackageManager.FEATURE_NFC_HOST_CARD_EMULATION)) {
+            return null
+        }
+        return CardEmulation.getInstance(adapter)
+    }
+
+    /**
+     * Registers the AIDs and forwards the APDUs of readers selecting them to the channel.
+     * Each command must be answered with [respondApdu].
+     */
+    @Command
+    fun startCardEmulation(invoke: Invoke) {
+        val args = invoke.parseArgs(CardEmulationOptions::class.java)
+
+        val service = cardEmulationService()
+        if (service == null) {
+            invoke.reject("host card emulation is not supported on this device")
+            return
+        }
+        if (args.category != CardEmulation.CATEGORY_PAYMENT && args.category != CardEmulation.CATEGORY_OTHER) {
+            invoke.reject("invalid card emulation category ${args.category}")
+            return
+        }
+
+        val component = hceComponent()
         try {
-            val rawMessages = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
-                intent.getParcelableArrayExtra(NfcAdapter.EXTRA_NDEF_MESSAGES, Parcelable::class.java)
-            } else {
-                @Suppress("DEPRECATION")
-                intent.getParcelableArrayExtra(NfcAdapter.EXTRA_NDEF_MESSAGES)
+            if (!service.registerAidsForService(component, args.category, args.aids.map { it.uppercase() })) {
+                invoke.reject("failed to register the AIDs")
+                return
             }
+        } catch (e: IllegalArgumentException) {
+            invoke.reject("invalid AID: ${e.message}", e)
+            return
+        }
+        // the app must be the foreground service to receive AIDs also registered by other apps
+        service.setPreferredService(activity, component)
+
+        NfcHceService.onCommand = { apdu ->
+            val event = JSObject()
+            event.put("type", "command")
+            event.put("data", fromU8Array(apdu))
+            args.channel.send(event)
+        }
+        NfcHceService.onDeactivated = { reason ->
+            val event = JSObject()
+            event.put("type", "deactivated")
+            event.put(
+                "reason",
+                if (reason == HostApduService.DEACTIVATION_LINK_LOSS) "linkLoss" else "deselected"
+            )
+            args.channel.send(event)
+        }
+        cardEmulation = args
 
-            when (intent.action) {
-                NfcAdapter.ACTION_NDEF_DISCOVERED -> {
-                    // For some reason this one never triggers.
-                    Logger.info("NFC", "new NDEF intent")
-                    readTagInner(tag, rawMessages)
-                }
-                NfcAdapter.ACTION_TECH_DISCOVERED -> {
-                    // For some reason this always triggers instead of NDEF_DISCOVERED even though we set ndef filters right now
-                    Logger.info("NFC", "new TECH intent")
-                    // TODO: handle different techs. Don't assume ndef.
-                    readTagInner(tag, rawMessages)
-                }
-                NfcAdapter.ACTION_TAG_DISCOVERED -> {
-                    // This should never trigger when an app handles NDEF and TECH
-                    // TODO: Don't assume ndef.
-                    readTagInner(tag, rawMessages)
-                }
+        invoke.resolve()
+    }
+
+    @Command
+    fun respondApdu(invoke: Invoke) {
+        val args = invoke.parseArgs(RespondApduOptions::class.java)
+        if (NfcHceService.respond(args.data)) {
+            invoke.resolve()
+        } else {
+            invoke.reject("no reader is connected")
+        }
+    }
+
+    @Command
+    fun stopCardEmulation(invoke: Invoke) {
+        val args = cardEmulation
+        if (args != null) {
+            cardEmulationService()?.let { service ->
+                service.unsetPreferredService(activity)
+                service.removeAidsForService(hceComponent(), args.category)
             }
+        }
+        NfcHceService.onCommand = null
+        NfcHceService.onDeactivated = null
+        cardEmulation = null
+        invoke.resolve()
+    }
+
+    @Command
+    fun transceive(invoke: Invoke) {
+        val args = invoke.parseArgs(TransceiveOptions::class.java)
+
+        val session = this.session
+        val tag = session?.tag
+        if (session == null || tag == null) {
+            invoke.reject("connected tag not found, please scan a tag with `keepSessionAlive` set to true first")
+            return
+        }
+
+        thread {
+            try {
+                val technology = connectTechnology(session, tag, args.technology)
+                val response = transceive(technology, args.data, args.timeout)
+                val ret = JSObject()
+                ret.put("data", fromU8Array(response))
+                invoke.resolve(ret)
+            } catch (e: Exception) {
+                invoke.reject(e.message ?: e.toString())
+            }
+        }
+    }
+
+    private fun getTechnology(tag: Tag, kind: TechKind): TagTechnology? {
+        return when (kind) {
+            TechKind.IsoDep -> IsoDep.get(tag)
+            TechKind.MifareClassic -> MifareClassic.get(tag)
+            TechKind.MifareUltralight -> MifareUltralight.get(tag)
+            TechKind.NfcA -> NfcA.get(tag)
+            TechKind.NfcB -> NfcB.get(tag)
+            TechKind.NfcF -> NfcF.get(tag)
+            TechKind.NfcV -> NfcV.get(tag)
+            else -> throw Exception("${kind.value} does not support raw commands")
+        }
+    }
+
+    private fun connectTechnology(session: Session, tag: Tag, kind: TechKind?): TagTechnology {
+        val technology = if (kind != null) {
+            getTechnology(tag, kind) ?: throw Exception("the tag does not support ${kind.value}")
+        } else {
+            TRANSCEIVE_TECHNOLOGIES.firstNotNullOfOrNull { getTechnology(tag, it) }
+                ?: throw Exception("the tag does not support raw commands")
+        }
+
+        val current = session.technology
+        if (current != null && current.javaClass == technology.javaClass && current.isConnected) {
+            return current
+        }
+        try {
+            current?.close()
+        } catch (e: IOException) {
+            Logger.error("failed to close tag technology", e)
+        }
+
+        technology.connect()
+        session.technology = technology
+        return technology
+    }
+
+    private fun transceive(technology: TagTechnology, data: ByteArray, timeout: Int?): ByteArray {
+        val maxLength = when (technology) {
+            is IsoDep -> technology.maxTransceiveLength
+            is MifareClassic -> technology.maxTransceiveLength
+            is MifareUltralight -> technology.maxTransceiveLength
+            is NfcA -> technology.maxTransceiveLength
+            is NfcB -> technology.maxTransceiveLength
+            is NfcF -> technology.maxTransceiveLength
+            is NfcV -> technology.maxTransceiveLength
+            else -> 0
+        }
+        if (data.size > maxLength) {
+            throw Exception("the command is too large for the tag, the maximum length is $maxLength bytes")
+        }
+
+        timeout?.let {
+            when (technology) {
+                is IsoDep -> technology.timeout = it
+                is MifareClassic -> technology.timeout = it
+                is MifareUltralight -> technology.timeout = it
+                is NfcA -> technology.timeout = it
+                is NfcF -> technology.timeout = it
+                // NfcB and NfcV do not support custom timeouts
+                else -> {}
+            }
+        }
+
+        return when (technology) {
+            is IsoDep -> technology.transceive(data)
+            is MifareClassic -> technology.transceive(data)
+            is MifareUltralight -> technology.transceive(data)
+            is NfcA -> technology.transceive(data)
+            is NfcB -> technology.transceive(data)
+            is NfcF -> technology.transceive(data)
+            is NfcV -> technology.transceive(data)
+            else -> throw Exception("the tag does not support raw commands")
+        }
+    }
+
+    private fun readTag(tag: Tag, rawMessages: Array<Parcelable>?) {
+        try {
+            session?.invoke?.resolve(tagToJson(tag, rawMessages))
         } catch (e: Exception) {
             session?.invoke?.reject("failed to read tag", e)
         } finally {
@@ -405,25 +729,22 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
         }
     }
 
-    private fun readTagInner(tag: Tag?, rawMessages: Array<Parcelable>?) {
-        val ndefMessage = rawMessages?.get(0) as NdefMessage?
+    private fun tagToJson(tag: Tag, rawMessages: Array<Parcelable>?): JSObject {
+        val ndefMessage = rawMessages?.getOrNull(0) as NdefMessage?
 
         val records = ndefMessage?.records ?: arrayOf()
 
         val jsonRecords = Array(records.size) { i -> recordToJson(records[i]) }
 
         val ret = JSObject()
-        if (tag !== null) {
-            ret.put("id", fromU8Array(tag.id))
-            // TODO There's also ndef.type which returns the ndef spec type which may be interesting to know too?
-            ret.put("kind", JSArray.from(tag.techList))
-        }
+        ret.put("id", fromU8Array(tag.id))
+        // TODO There's also ndef.type which returns the ndef spec type which may be interesting to know too?
+        ret.put("kind", JSArray.from(tag.techList))
         ret.put("records", JSArray.from(jsonRecords))
-
-        session?.invoke?.resolve(ret)
+        return ret
     }
 
-    private fun writeTag(tag: Tag, message: NdefMessage) {
+    private fun writeTag(tag: Tag, message: NdefMessage, makeReadOnly: Boolean) {
         // This should return tags that are already in ndef format
         val ndefTag = Ndef.get(tag)
         if (ndefTag !== null) {
@@ -438,12 +759,17 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
                 throw Exception("The message is too large for the provided NFC tag")
             } else if (!ndefTag.isWritable) {
                 throw Exception("NFC tag is read-only")
+            } else if (makeReadOnly && !ndefTag.canMakeReadOnly()) {
+                throw Exception("NFC tag cannot be made read-only")
             } else {
                 try {
                     ndefTag.writeNdefMessage(message)
                 } catch (e: Exception) {
                     throw Exception("Couldn't write message to NFC tag", e)
                 }
+                if (makeReadOnly && !ndefTag.makeReadOnly()) {
+                    throw Exception("Couldn't make the NFC tag read-only")
+                }
             }
 
             try {
@@ -460,7 +786,11 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
         if (ndefFormatableTag !== null) {
             try {
                 ndefFormatableTag.connect()
-                ndefFormatableTag.format(message)
+                if (makeReadOnly) {
+                    ndefFormatableTag.formatReadOnly(message)
+                } else {
+                    ndefFormatableTag.format(message)
+                }
             } catch (e: Exception) {
                 throw Exception("Couldn't format tag as Ndef", e)
             }
@@ -478,8 +808,12 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
         throw Exception("Tag doesn't support Ndef format")
     }
 
-    // TODO: Use ReaderMode instead of ForegroundDispatch
-    private fun enableNFCInForeground(filters: Array<IntentFilter>?, techLists: Array<Array<String>>?) {
+    private fun enableNFCInForeground(session: Session) {
+        session.readerMode?.let {
+            enableReaderMode(it)
+            return
+        }
+
         val flag =
             if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_MUTABLE else PendingIntent.FLAG_UPDATE_CURRENT
         val pendingIntent = PendingIntent.getActivity(
@@ -491,12 +825,43 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
             flag
         )
 
-        nfcAdapter?.enableForegroundDispatch(activity, pendingIntent, filters, techLists)
+        nfcAdapter?.enableForegroundDispatch(activity, pendingIntent, session.filters, session.techLists)
+    }
+
+    /**
+     * Reader mode disables the NFC intents and the Android Beam peer-to-peer mode while the activity is in the foreground.
+     * The scan kind filters do not apply in this mode.
+     */
+    private fun enableReaderMode(options: ReaderModeOptions) {
+        var flags = NfcAdapter.FLAG_READER_NFC_A or
+            NfcAdapter.FLAG_READER_NFC_B or
+            NfcAdapter.FLAG_READER_NFC_F or
+            NfcAdapter.FLAG_READER_NFC_V or
+            NfcAdapter.FLAG_READER_NFC_BARCODE
+        if (options.skipNdefCheck) {
+            flags = flags or NfcAdapter.FLAG_READER_SKIP_NDEF_CHECK
+        }
+        if (options.noPlatformSounds) {
+            flags = flags or NfcAdapter.FLAG_READER_NO_PLATFORM_SOUNDS
+        }
+        val extras = Bundle()
+        options.presenceCheckDelay?.let {
+            extras.putInt(NfcAdapter.EXTRA_READER_PRESENCE_CHECK_DELAY, it)
+        }
+
+        nfcAdapter?.enableReaderMode(activity, { tag ->
+            // the NDEF message is read by the system unless the NDEF check is skipped
+            val message = Ndef.get(tag)?.cachedNdefMessage
+            activity.runOnUiThread {
+                handleTag(tag, message?.let { arrayOf<Parcelable>(it) })
+            }
+        }, flags, extras)
     }
 
     private fun disableNFCInForeground() {
         activity.runOnUiThread {
             nfcAdapter?.disableForegroundDispatch(activity)
+            nfcAdapter?.disableReaderMode(activity)
         }
     }
 }
@@ -504,7 +869,7 @@ class NfcPlugin(private val activity: Activity) : Plugin(activity) {
 private fun fromU8Array(byteArray: ByteArray): JSONArray {
     val json = JSONArray()
     for (byte in byteArray) {
-        json.put(byte)
+        json.put(byte.toInt() and 0xff)
     }
     return json
 }
@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_NFC__=function(n){"use strict";async function e(n,e={},t){return window.__TAURI_INTERNALS__.invoke(n,e,t)}"function"==typeof SuppressedError&&SuppressedError;const t=[84],r=[85];var o,c;function a(n,e,t,r){return{format:n,kind:"string"==typeof e?Array.from((new TextEncoder).encode(e)):e,id:"string"==typeof t?Array.from((new TextEncoder).encode(t)):t,payload:"string"==typeof r?Array.from((new TextEncoder).encode(r)):r}}n.TechKind=void 0,(o=n.TechKind||(n.TechKind={}))[o.IsoDep=0]="IsoDep",o[o.MifareClassic=1]="MifareClassic",o[o.MifareUltralight=2]="MifareUltralight",o[o.Ndef=3]="Ndef",o[o.NdefFormatable=4]="NdefFormatable",o[o.NfcA=5]="NfcA",o[o.NfcB=6]="NfcB",o[o.NfcBarcode=7]="NfcBarcode",o[o.NfcF=8]="NfcF",o[o.NfcV=9]="NfcV",n.NFCTypeNameFormat=void 0,(c=n.NFCTypeNameFormat||(n.NFCTypeNameFormat={}))[c.Empty=0]="Empty",c[c.NfcWellKnown=1]="NfcWellKnown",c[c.Media=2]="Media",c[c.AbsoluteURI=3]="AbsoluteURI",c[c.NfcExternal=4]="NfcExternal",c[c.Unknown=5]="Unknown",c[c.Unchanged=6]="Unchanged";const i=["","http://www.","https://www.","http://","https://","tel:","mailto:","ftp://anonymous:anonymous@","ftp://ftp.","ftps://","sftp://","smb://","nfs://","ftp://","dav://","news:","telnet://","imap:","rtsp://","urn:","pop:","sip:","sips:","tftp:","btspp://","btl2cap://","btgoep://","tcpobex://","irdaobex://","file://","urn:epc:id:","urn:epc:tag:","urn:epc:pat:","urn:epc:raw:","urn:epc:","urn:nfc:"];function f(n){const{type:e,...t}=n;return{[e]:t}}return n.RTD_TEXT=t,n.RTD_URI=r,n.isAvailable=async function(){return await e("plugin:nfc|is_available")},n.record=a,n.scan=async function(n,t){return await e("plugin:nfc|scan",{kind:f(n),...t})},n.textRecord=function(e,r,o="en"){const c=Array.from((new TextEncoder).encode(o+e));return c.unshift(o.length),a(n.NFCTypeNameFormat.NfcWellKnown,t,r??[],c)},n.uriRecord=function(e,t){return a(n.NFCTypeNameFormat.NfcWellKnown,r,t??[],function(n){let e="";i.slice(1).forEach((function(t){0!==e.length&&"urn:"!==e||0!==n.indexOf(t)||(e=t)})),0===e.length&&(e="");const t=Array.from((new TextEncoder).encode(n.slice(e.length))),r=i.indexOf(e);return t.unshift(r),t}(e))},n.write=async function(n,t){const{kind:r,...o}=t??{};r&&(o.kind=f(r)),await e("plugin:nfc|write",{records:n,...o})},n}({});Object.defineProperty(window.__TAURI__,"nfc",{value:__TAURI_PLUGIN_NFC__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_NFC__=function(t){"use strict";function e(t,e,n,i){if("a"===n&&!i)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof e?t!==e||!i:!e.has(t))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===n?i:"a"===n?i.call(t):i?i.value:e.get(t)}function n(t,e,n,i,o){if("function"==typeof e?t!==e||!o:!e.has(t))throw new TypeError("Cannot write private member to an object whose class did not declare it");return e.set(t,n),n}var i,o,s;"function"==typeof SuppressedError&&SuppressedError;const r="__TAURI_TO_IPC_KEY__";class a{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,i.set(this,(()=>{})),o.set(this,0),s.set(this,{}),this.id=function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}((({message:t,id:r})=>{if(r===e(this,o,"f")){n(this,o,r+1),e(this,i,"f").call(this,t);const a=Object.keys(e(this,s,"f"));if(a.length>0){let t=r+1;for(const n of a.sort()){if(parseInt(n)!==t)break;{const o=e(this,s,"f")[n];delete e(this,s,"f")[n],e(this,i,"f").call(this,o),t+=1}}n(this,o,t)}}else e(this,s,"f")[r.toString()]=t}))}set onmessage(t){n(this,i,t)}get onmessage(){return e(this,i,"f")}[(i=new WeakMap,o=new WeakMap,s=new WeakMap,r)](){return`__CHANNEL__:${this.id}`}toJSON(){return this[r]()}}class l{constructor(t,e,n){this.plugin=t,this.event=e,this.channelId=n}async unregister(){return c(`plugin:${this.plugin}|remove_listener`,{event:this.event,channelId:this.channelId})}}async function u(t,e,n){const i=new a;return i.onmessage=n,c(`plugin:${t}|registerListener`,{event:e,handler:i}).then((()=>new l(t,e,i.id)))}async function c(t,e={},n){return window.__TAURI_INTERNALS__.invoke(t,e,n)}var h,d,g;const f=[84],m=[85],y=[83,112],v=[97,99,116];function w(t,e,n,i){return{format:t,kind:"string"==typeof e?Array.from((new TextEncoder).encode(e)):e,id:"string"==typeof n?Array.from((new TextEncoder).encode(n)):n,payload:"string"==typeof i?Array.from((new TextEncoder).encode(i)):i}}t.TechKind=void 0,(h=t.TechKind||(t.TechKind={}))[h.IsoDep=0]="IsoDep",h[h.MifareClassic=1]="MifareClassic",h[h.MifareUltralight=2]="MifareUltralight",h[h.Ndef=3]="Ndef",h[h.NdefFormatable=4]="NdefFormatable",h[h.NfcA=5]="NfcA",h[h.NfcB=6]="NfcB",h[h.NfcBarcode=7]="NfcBarcode",h[h.NfcF=8]="NfcF",h[h.NfcV=9]="NfcV",t.NFCTypeNameFormat=void 0,(d=t.NFCTypeNameFormat||(t.NFCTypeNameFormat={}))[d.Empty=0]="Empty",d[d.NfcWellKnown=1]="NfcWellKnown",d[d.Media=2]="Media",d[d.AbsoluteURI=3]="AbsoluteURI",d[d.NfcExternal=4]="NfcExternal",d[d.Unknown=5]="Unknown",d[d.Unchanged=6]="Unchanged";function k(e,n,i="en"){const o=Array.from((new TextEncoder).encode(i+e));return o.unshift(i.length),w(t.NFCTypeNameFormat.NfcWellKnown,f,n??[],o)}const b=["","http://www.","https://www.","http://","https://","tel:","mailto:","ftp://anonymous:anonymous@","ftp://ftp.","ftps://","sftp://","smb://","nfs://","ftp://","dav://","news:","telnet://","imap:","rtsp://","urn:","pop:","sip:","sips:","tftp:","btspp://","btl2cap://","btgoep://","tcpobex://","irdaobex://","file://","urn:epc:id:","urn:epc:tag:","urn:epc:pat:","urn:epc:raw:","urn:epc:","urn:nfc:"];function x(t){let e="";b.slice(1).forEach((function(n){n.length>e.length&&t.startsWith(n)&&(e=n)}));const n=Array.from((new TextEncoder).encode(t.slice(e.length)));return n.unshift(b.indexOf(e)),n}function S(e,n){return w(t.NFCTypeNameFormat.NfcWellKnown,m,n??[],x(e))}function T(e,n,i){return w(t.NFCTypeNameFormat.Media,e.toLowerCase(),i??[],n)}function R(e,n,i,o){return w(t.NFCTypeNameFormat.NfcExternal,`${e}:${n}`.toLowerCase(),o??[],i)}function A(t){const e=[];return t.forEach(((n,i)=>{const{kind:o,id:s,payload:r}=n,a=r.length<256;let l=n.format;0===i&&(l|=128),i===t.length-1&&(l|=64),a&&(l|=16),s.length>0&&(l|=8),e.push(l,o.length),a?e.push(r.length):e.push(r.length>>>24&255,r.length>>>16&255,r.length>>>8&255,255&r.length),s.length>0&&e.push(s.length),e.push(...o,...s,...r)})),e}t.SmartPosterAction=void 0,(g=t.SmartPosterAction||(t.SmartPosterAction={}))[g.Do=0]="Do",g[g.Save=1]="Save",g[g.Edit=2]="Edit";function N(t){const{type:e,...n}=t;return{[e]:n}}return t.RTD_SMART_POSTER=y,t.RTD_TEXT=f,t.RTD_URI=m,t.absoluteUriRecord=function(e,n){return w(t.NFCTypeNameFormat.AbsoluteURI,e,n??[],[])},t.androidApplicationRecord=function(t){return R("android.com","pkg",t)},t.encodeMessage=A,t.endSession=async function(t){await c("plugin:nfc|end_session",{...t})},t.externalRecord=R,t.isAvailable=async function(){return await c("plugin:nfc|is_available")},t.mimeRecord=T,t.onTagDiscovered=async function(t){return await u("nfc","tagDiscovered",t)},t.record=w,t.respondApdu=async function(t){await c("plugin:nfc|respond_apdu",{data:Array.from(t)})},t.scan=async function(t,e){return await c("plugin:nfc|scan",{kind:N(t),...e})},t.setSessionMessage=async function(t){await c("plugin:nfc|set_session_message",{message:t})},t.smartPosterRecord=function(e,n){const i=[S(e.uri)];for(const t of e.titles??[])i.push(k(t.text,void 0,t.language));return void 0!==e.action&&i.push(w(t.NFCTypeNameFormat.NfcWellKnown,v,[],[e.action])),e.icon&&i.push(T(e.icon.mimeType,e.icon.data)),w(t.NFCTypeNameFormat.NfcWellKnown,y,n??[],A(i))},t.startCardEmulation=async function(t,e){const n=new a;n.onmessage=e,await c("plugin:nfc|start_card_emulation",{category:"other",...t,channel:n})},t.stopCardEmulation=async function(){await c("plugin:nfc|stop_card_emulation")},t.takePendingTags=async function(){const{tags:t}=await c("plugin:nfc|take_pending_tags");return t},t.textRecord=k,t.transceive=async function(e,n){const{data:i}=await c("plugin:nfc|transceive",{data:Array.from(e),technology:void 0!==n?.technology?t.TechKind[n.technology]:void 0,timeout:n?.timeout});return Uint8Array.from(i)},t.uriRecord=S,t.write=async function(t,e){const{kind:n,...i}=e??{};n&&(i.kind=N(n)),await c("plugin:nfc|write",{records:t,...i})},t}({});Object.defineProperty(window.__TAURI__,"nfc",{value:__TAURI_PLUGIN_NFC__})}
\ No newline at end of file
@@ -2,7 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["is_available", "write", "scan"];
+const COMMANDS: &[&str] = &[
+    "is_available",
+    "write",
+    "scan",
+    "transceive",
+    "end_session",
+    "set_session_message",
+    "take_pending_tags",
+    "register_listener",
+    "start_card_emulation",
+    "respond_apdu",
+    "stop_card_emulation",
+];
 
 fn main() {
     let result = tauri_plugin::Builder::new(COMMANDS)
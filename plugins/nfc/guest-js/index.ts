@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-import { invoke } from '@tauri-apps/api/core'
+import {
+  addPluginListener,
+  Channel,
+  invoke,
+  type PluginListener
+} from '@tauri-apps/api/core'
 
 export const RTD_TEXT = [0x54] // "T"
 export const RTD_URI = [0x55] // "U"
+export const RTD_SMART_POSTER = [0x53, 0x70] // "Sp"
+const RTD_ACTION = [0x61, 0x63, 0x74] // "act"
 
 export interface UriFilter {
   scheme?: string
@@ -59,12 +66,27 @@ export type ScanKind =
       techLists?: TechKind[][]
     }
 
+/**
+ * Android reader mode configuration, which disables the platform tag handling while the app is scanning.
+ */
+export interface ReaderModeOptions {
+  /** Skips the NDEF check of the platform, required to talk to some tags with raw commands. */
+  skipNdefCheck?: boolean
+  /** Disables the sound played by the platform when a tag is discovered. */
+  noPlatformSounds?: boolean
+  /** Delay in milliseconds between the checks for the presence of the tag. */
+  presenceCheckDelay?: number
+}
+
 export interface ScanOptions {
+  /** Keeps the session alive after scanning so the tag can be written or sent raw commands, until {@link endSession} is called. */
   keepSessionAlive?: boolean
   /** Message displayed in the UI. iOS only. */
   message?: string
   /** Message displayed in the UI when the message has been read. iOS only. */
   successMessage?: string
+  /** Uses reader mode instead of foreground dispatch to scan the tag. Android only. */
+  readerMode?: ReaderModeOptions
 }
 
 export interface WriteOptions {
@@ -75,6 +97,12 @@ export interface WriteOptions {
   successfulReadMessage?: string
   /** Message displayed in the UI when the message has been written. iOS only. */
   successMessage?: string
+  /** Permanently locks the tag after writing the message. This cannot be undone. */
+  makeReadOnly?: boolean
+  /** Keeps the session alive after writing so the tag can be read or written again, until {@link endSession} is called. */
+  keepSessionAlive?: boolean
+  /** Uses reader mode instead of foreground dispatch to scan the tag. Android only. */
+  readerMode?: ReaderModeOptions
 }
 
 export enum NFCTypeNameFormat {
@@ -179,19 +207,13 @@ const protocols = [
 function encodeURI(uri: string): number[] {
   let prefix = ''
 
+  // use the longest matching prefix, e.g. `urn:nfc:` instead of `urn:`
   protocols.slice(1).forEach(function (protocol) {
-    if (
-      (prefix.length === 0 || prefix === 'urn:') &&
-      uri.indexOf(protocol) === 0
-    ) {
+    if (protocol.length > prefix.length && uri.startsWith(protocol)) {
       prefix = protocol
     }
   })
 
-  if (prefix.length === 0) {
-    prefix = ''
-  }
-
   const encoded = Array.from(new TextEncoder().encode(uri.slice(prefix.length)))
   const protocolCode = protocols.indexOf(prefix)
   // prepend protocol code
@@ -209,6 +231,140 @@ export function uriRecord(uri: string, id?: string | number[]): NFCRecord {
   )
 }
 
+/**
+ * A record with an absolute URI (RFC 3986) as type and no payload.
+ */
+export function absoluteUriRecord(
+  uri: string,
+  id?: string | number[]
+): NFCRecord {
+  return record(NFCTypeNameFormat.AbsoluteURI, uri, id ?? [], [])
+}
+
+/**
+ * A record with a media type (RFC 2046) such as `application/json` or `image/png`.
+ */
+export function mimeRecord(
+  mimeType: string,
+  payload: string | number[],
+  id?: string | number[]
+): NFCRecord {
+  return record(
+    NFCTypeNameFormat.Media,
+    mimeType.toLowerCase(),
+    id ?? [],
+    payload
+  )
+}
+
+/**
+ * A NFC Forum external type record, e.g. `example.com:mytype`.
+ */
+export function externalRecord(
+  domain: string,
+  type: string,
+  payload: string | number[],
+  id?: string | number[]
+): NFCRecord {
+  return record(
+    NFCTypeNameFormat.NfcExternal,
+    `${domain}:${type}`.toLowerCase(),
+    id ?? [],
+    payload
+  )
+}
+
+/**
+ * An Android Application Record, which launches the app with the given package name when the tag is scanned on Android.
+ */
+export function androidApplicationRecord(packageName: string): NFCRecord {
+  return externalRecord('android.com', 'pkg', packageName)
+}
+
+/**
+ * Recommended action of a Smart Poster.
+ */
+export enum SmartPosterAction {
+  /** Do the action, e.g. open the URI in the browser. */
+  Do = 0,
+  /** Save the URI for later, e.g. as a bookmark. */
+  Save = 1,
+  /** Open the URI for editing. */
+  Edit = 2
+}
+
+export interface SmartPoster {
+  uri: string
+  /** Titles of the poster, in one or more languages. */
+  titles?: Array<{ text: string; language?: string }>
+  action?: SmartPosterAction
+  icon?: { mimeType: string; data: number[] }
+}
+
+/**
+ * A well-known Smart Poster record: a URI with titles, a recommended action and an icon.
+ *
+ * ```javascript
+ * import { smartPosterRecord, write } from "@tauri-apps/plugin-nfc";
+ * await write([smartPosterRecord({ uri: "https://tauri.app", titles: [{ text: "Tauri" }] })], { kind: { type: "ndef" } });
+ * ```
+ */
+export function smartPosterRecord(
+  poster: SmartPoster,
+  id?: string | number[]
+): NFCRecord {
+  const records = [uriRecord(poster.uri)]
+  for (const title of poster.titles ?? []) {
+    records.push(textRecord(title.text, undefined, title.language))
+  }
+  if (poster.action !== undefined) {
+    records.push(
+      record(NFCTypeNameFormat.NfcWellKnown, RTD_ACTION, [], [poster.action])
+    )
+  }
+  if (poster.icon) {
+    records.push(mimeRecord(poster.icon.mimeType, poster.icon.data))
+  }
+  return record(
+    NFCTypeNameFormat.NfcWellKnown,
+    RTD_SMART_POSTER,
+    id ?? [],
+    encodeMessage(records)
+  )
+}
+
+/**
+ * Serializes the records as a NDEF message, e.g. to embed it in another record.
+ */
+export function encodeMessage(records: NFCRecord[]): number[] {
+  const message: number[] = []
+  records.forEach((r, i) => {
+    const { kind, id, payload } = r
+    const short = payload.length < 256
+
+    let header = r.format
+    if (i === 0) header |= 0x80 // message begin
+    if (i === records.length - 1) header |= 0x40 // message end
+    if (short) header |= 0x10
+    if (id.length > 0) header |= 0x08
+
+    message.push(header, kind.length)
+    if (short) {
+      message.push(payload.length)
+    } else {
+      message.push(
+        (payload.length >>> 24) & 0xff,
+        (payload.length >>> 16) & 0xff,
+        (payload.length >>> 8) & 0xff,
+        payload.length & 0xff
+      )
+    }
+    if (id.length > 0) message.push(id.length)
+    message.push(...kind, ...id, ...payload)
+  })
+  return message
+}
+
 function mapScanKind(kind: ScanKind): Record<string, unknown> {
   const { type: scanKind, ...kindOptions } = kind
   return { [scanKind]: kindOptions }
@@ -249,6 +405,8 @@ export async function scan(
  * If you did not previously call {@link scan} with {@link ScanOptions.keepSessionAlive} set to true,
  * it will first scan the tag then write to it.
  *
+ * All records are written as a single NDEF message.
+ *
  * @param records
  * @param options
  * @returns
@@ -268,6 +426,182 @@ export async function write(
   })
 }
 
+export interface TransceiveOptions {
+  /**
+   * Technology used to talk to the tag.
+   * Defaults to the first technology of the tag that supports raw commands.
+   *
+   * On iOS only {@link TechKind.IsoDep}, {@link TechKind.NfcA}, {@link TechKind.MifareUltralight}
+   * and {@link TechKind.NfcF} are supported.
+   */
+  technology?: TechKind
+  /** Timeout in milliseconds. Android only. */
+  timeout?: number
+}
+
+/**
+ * Sends a raw command to the connected tag, such as an ISO 7816-4 APDU, and returns the tag response.
+ *
+ * The tag must have been previously scanned with {@link ScanOptions.keepSessionAlive} set to true.
+ * On iOS the scan kind must be `tag` and the response of ISO 7816 commands ends with the SW1 and SW2 status bytes.
+ *
+ * ```javascript
+ * import { scan, transceive, TechKind } from "@tauri-apps/plugin-nfc";
+ * await scan({ type: "tag" }, { keepSessionAlive: true });
+ * // SELECT command
+ * const response = await transceive([0x00, 0xa4, 0x04, 0x00, 0x07, 0xf0, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06], { technology: TechKind.IsoDep });
+ * ```
+ *
+ * @param data the command to send.
+ * @param options
+ * @returns the response of the tag.
+ */
+export async function transceive(
+  data: number[] | Uint8Array,
+  options?: TransceiveOptions
+): Promise<Uint8Array> {
+  const { data: response } = await invoke<{ data: number[] }>(
+    'plugin:nfc|transceive',
+    {
+      data: Array.from(data),
+      technology:
+        options?.technology !== undefined
+          ? TechKind[options.technology]
+          : undefined,
+      timeout: options?.timeout
+    }
+  )
+  return Uint8Array.from(response)
+}
+
+export interface EndSessionOptions {
+  /** Message displayed in the UI when the session ends successfully. iOS only. */
+  successMessage?: string
+  /** Ends the session with an error message displayed in the UI. iOS only. */
+  errorMessage?: string
+}
+
+/**
+ * Updates the message displayed in the NFC sheet of the current session, e.g. between the steps of a multi-step flow. iOS only.
+ *
+ * @param message
+ */
+export async function setSessionMessage(message: string): Promise<void> {
+  await invoke('plugin:nfc|set_session_message', { message })
+}
+
+/**
+ * Ends the session kept alive by a previous {@link scan} or {@link write} call.
+ *
+ * ```javascript
+ * import { scan, write, endSession, textRecord } from "@tauri-apps/plugin-nfc";
+ * const tag = await scan({ type: "ndef" }, { keepSessionAlive: true });
+ * await write([textRecord("updated")]);
+ * await endSession({ successMessage: "Tag updated" });
+ * ```
+ *
+ * @param options
+ */
+export async function endSession(options?: EndSessionOptions): Promise<void> {
+  await invoke('plugin:nfc|end_session', { ...options })
+}
+
+/**
+ * Returns and clears the tags read while no scan was in progress, such as the tag that launched the app.
+ *
+ * On Android these are the tags delivered to the activity by the intent filters of the plugin.
+ * On iOS these are the tags read by background tag reading, which only include the NDEF records.
+ */
+export async function takePendingTags(): Promise<Tag[]> {
+  const { tags } = await invoke<{ tags: Tag[] }>(
+    'plugin:nfc|take_pending_tags'
+  )
+  return tags
+}
+
+/**
+ * Listens to tags read while no scan was in progress.
+ * The tags are also queued and can be retrieved with {@link takePendingTags}.
+ *
+ * @param handler
+ */
+export async function onTagDiscovered(
+  handler: (tag: Tag) => void
+): Promise<PluginListener> {
+  return await addPluginListener('nfc', 'tagDiscovered', handler)
+}
+
+export interface CardEmulationOptions {
+  /** Application identifiers (AIDs) selected by the readers, as hexadecimal strings. */
+  aids: string[]
+  /**
+   * Category of the AIDs. Defaults to `other`.
+   *
+   * The user selects the default payment app in the system settings.
+   */
+  category?: 'payment' | 'other'
+}
+
+export type CardEmulationEvent =
+  | {
+      /** A command APDU, which must be answered with {@link respondApdu}. */
+      type: 'command'
+      data: number[]
+    }
+  | {
+      /** The reader is no longer talking to the emulated card. */
+      type: 'deactivated'
+      reason: 'linkLoss' | 'deselected'
+    }
+
+/**
+ * Emulates a card with the given AIDs, forwarding the commands of the readers selecting them to the handler.
+ * Each command must be answered with {@link respondApdu}.
+ *
+ * Only supported on Android devices with host card emulation.
+ *
+ * ```javascript
+ * import { startCardEmulation, respondApdu } from "@tauri-apps/plugin-nfc";
+ * await startCardEmulation({ aids: ["F0010203040506"] }, async (event) => {
+ *   if (event.type === "command") {
+ *     // respond with the card number and the success status word
+ *     await respondApdu([0x12, 0x34, 0x90, 0x00]);
+ *   }
+ * });
+ * ```
+ *
+ * @param options
+ * @param handler
+ */
+export async function startCardEmulation(
+  options: CardEmulationOptions,
+  handler: (event: CardEmulationEvent) => void
+): Promise<void> {
+  const channel = new Channel<CardEmulationEvent>()
+  channel.onmessage = handler
+  await invoke('plugin:nfc|start_card_emulation', {
+    category: 'other',
+    ...options,
+    channel
+  })
+}
+
+/**
+ * Sends the response to the last command of the reader, usually ending with the SW1 and SW2 status bytes.
+ *
+ * @param data
+ */
+export async function respondApdu(data: number[] | Uint8Array): Promise<void> {
+  await invoke('plugin:nfc|respond_apdu', { data: Array.from(data) })
+}
+
+/**
+ * Unregisters the AIDs of {@link startCardEmulation}.
+ */
+export async function stopCardEmulation(): Promise<void> {
+  await invoke('plugin:nfc|stop_card_emulation')
+}
+
 export async function isAvailable(): Promise<boolean> {
   return await invoke('plugin:nfc|is_available')
 }
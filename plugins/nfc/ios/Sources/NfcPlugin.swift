@@ -26,28 +26,51 @@ struct NDEFRecord: Decodable {
   var kind: [UInt8]?
   var identifier: [UInt8]?
   var payload: [UInt8]?
+
+  enum CodingKeys: String, CodingKey {
+    case format, kind, payload
+    case identifier = "id"
+  }
 }
 
 struct WriteOptions: Decodable {
   var kind: ScanKind?
   let records: [NDEFRecord]
+  var makeReadOnly: Bool?
+  var keepSessionAlive: Bool?
   var message: String?
   var successMessage: String?
   var successfulReadMessage: String?
 }
 
+struct SessionMessageOptions: Decodable {
+  let message: String
+}
+
+struct EndSessionOptions: Decodable {
+  var successMessage: String?
+  var errorMessage: String?
+}
+
+struct TransceiveOptions: Decodable {
+  let data: [UInt8]
+  var technology: String?
+}
+
 enum TagProcessMode {
-  case write(message: NFCNDEFMessage)
+  case write(message: NFCNDEFMessage, makeReadOnly: Bool)
   case read
 }
 
 class Session {
   let nfcSession: NFCReaderSession?
-  let invoke: Invoke
+  var invoke: Invoke
   var keepAlive: Bool
   let tagProcessMode: TagProcessMode
   var tagStatus: NFCNDEFStatus?
   var tag: NFCNDEFTag?
+  // tag detected by a NFCTagReaderSession, used to send raw commands
+  var nfcTag: NFCTag?
   let successfulReadMessage: String?
   let successfulWriteAlertMessage: String?
 
@@ -78,11 +101,20 @@ class NfcStatus {
   }
 }
 
+typealias ContinueUserActivity = @convention(c) (
+  AnyObject, Selector, UIApplication, NSUserActivity, AnyObject?
+) -> Bool
+
 class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelegate {
   var session: Session?
   var status: NfcStatus!
+  // tags read in the background by the system, e.g. the tag that launched the app
+  var pendingTags: [JsonObject] = []
+  static var originalContinueUserActivity: IMP?
 
   public override func load(webview: WKWebView) {
+    installUserActivityHandler()
+
     var available = false
     var errorReason: String?
 
@@ -104,6 +136,55 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
     self.status = NfcStatus(available: available, errorReason: errorReason)
   }
 
+  /// Background tag reading delivers the NDEF message of tags with a universal link
+  /// as a user activity. The app delegate is extended to queue these tags,
+  /// calling its original implementation if any.
+  private func installUserActivityHandler() {
+    guard let delegate = UIApplication.shared.delegate else {
+      return
+    }
+    let delegateClass: AnyClass = type(of: delegate)
+    let selector = #selector(
+      UIApplicationDelegate.application(_:continue:restorationHandler:))
+
+    let handler: @convention(block) (AnyObject, UIApplication, NSUserActivity, AnyObject?) -> Bool =
+      { [weak self] delegate, application, userActivity, restorationHandler in
+        let handled = self?.handleUserActivity(userActivity) ?? false
+        if let original = NfcPlugin.originalContinueUserActivity {
+          let continueUserActivity = unsafeBitCast(original, to: ContinueUserActivity.self)
+          return continueUserActivity(
+            delegate, selector, application, userActivity, restorationHandler) || handled
+        }
+        return handled
+      }
+    let implementation = imp_implementationWithBlock(handler)
+
+    if let method = class_getInstanceMethod(delegateClass, selector) {
+      NfcPlugin.originalContinueUserActivity = method_setImplementation(method, implementation)
+    } else {
+      class_addMethod(delegateClass, selector, implementation, "B@:@@@?")
+    }
+  }
+
+  private func handleUserActivity(_ userActivity: NSUserActivity) -> Bool {
+    guard userActivity.activityType == NSUserActivityTypeBrowsingWeb else {
+      return false
+    }
+    let message = userActivity.ndefMessagePayload
+    if message.records.isEmpty {
+      return false
+    }
+
+    let tag: JsonObject = [
+      "id": [],
+      "kind": [],
+      "records": ndefMessageRecords(message),
+    ]
+    pendingTags.append(tag)
+    try? trigger("tagDiscovered", data: tag)
+    return true
+  }
+
   func tagReaderSessionDidBecomeActive(
     _ session: NFCTagReaderSession
   ) {
@@ -138,6 +219,10 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
             return
           }
 
+          if self.session?.keepAlive == true {
+            self.session?.nfcTag = tag
+          }
+
           self.processTag(
             session: session, tag: ndefTag, metadata: tagMetadata(tag),
             mode: self.session!.tagProcessMode)
@@ -250,17 +335,18 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
       if let error = error {
         self.closeSession(session, error: "cannot connect to tag: \(error)")
       } else {
+        if self.session?.keepAlive == true {
+          self.session!.tagStatus = status
+          self.session!.tag = tag
+        }
         switch mode {
-        case .write(let message):
+        case .write(let message, let makeReadOnly):
           self.writeNDEFTag(
             session: session, status: status, tag: tag, message: message,
+            makeReadOnly: makeReadOnly,
             alertMessage: self.session?.successfulWriteAlertMessage)
           break
         case .read:
-          if self.session?.keepAlive == true {
-            self.session!.tagStatus = status
-            self.session!.tag = tag
-          }
           self.readNDEFTag(
             session: session, status: status, tag: tag, metadata: metadata,
             alertMessage: self.session?.successfulReadMessage)
@@ -272,7 +358,7 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
 
   private func writeNDEFTag<T: NFCNDEFTag>(
     session: NFCReaderSession, status: NFCNDEFStatus, tag: T, message: NFCNDEFMessage,
-    alertMessage: String?
+    makeReadOnly: Bool, alertMessage: String?
   ) {
     switch status {
     case .notSupported:
@@ -288,14 +374,20 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
           completionHandler: { (error) in
             if let error = error {
               self.closeSession(session, error: "cannot write to tag: \(error)")
+            } else if makeReadOnly {
+              // permanently locks the tag, this cannot be undone
+              tag.writeLock(completionHandler: { (error) in
+                if let error = error {
+                  currentSession.invoke.reject("cannot make the tag read-only: \(error)")
+                  self.closeSession(session, error: "cannot make the tag read-only: \(error)")
+                } else {
+                  self.finishWrite(
+                    session: session, invoke: currentSession.invoke, alertMessage: alertMessage)
+                }
+              })
             } else {
-              if let message = alertMessage {
-                session.alertMessage = message
-              }
-              currentSession.invoke.resolve()
-
-              self.closeSession(session)
-
+              self.finishWrite(
+                session: session, invoke: currentSession.invoke, alertMessage: alertMessage)
             }
           })
       }
@@ -305,6 +397,17 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
     }
   }
 
+  private func finishWrite(session: NFCReaderSession, invoke: Invoke, alertMessage: String?) {
+    if let message = alertMessage {
+      session.alertMessage = message
+    }
+    invoke.resolve()
+
+    if self.session?.keepAlive != true {
+      self.closeSession(session)
+    }
+  }
+
   private func readNDEFTag<T: NFCNDEFTag>(
     session: NFCReaderSession, status: NFCNDEFStatus, tag: T, metadata m: JsonObject,
     alertMessage: String?
@@ -315,7 +418,10 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
     switch status {
     case .notSupported:
       self.resolveInvoke(message: nil, metadata: metadata)
-      self.closeSession(session)
+      // keep the session alive so raw commands can be sent to tags without NDEF support
+      if self.session?.keepAlive != true {
+        self.closeSession(session)
+      }
       return
     case .readOnly:
       metadata["readOnly"] = true
@@ -424,10 +530,12 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
       if let nfcSession = session.nfcSession, let tagStatus = session.tagStatus,
         let tag = session.tag
       {
-        session.keepAlive = false
+        session.invoke = invoke
+        session.keepAlive = args.keepSessionAlive ?? false
         self.writeNDEFTag(
           session: nfcSession, status: tagStatus, tag: tag,
           message: NFCNDEFMessage(records: ndefPayloads),
+          makeReadOnly: args.makeReadOnly ?? false,
           alertMessage: args.successMessage
         )
       } else {
@@ -438,10 +546,11 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
       self.startScanSession(
         invoke: invoke,
         kind: args.kind ?? .ndef,
-        keepAlive: true,
+        keepAlive: args.keepSessionAlive ?? false,
         invalidateAfterFirstRead: false,
         tagProcessMode: .write(
-          message: NFCNDEFMessage(records: ndefPayloads)
+          message: NFCNDEFMessage(records: ndefPayloads),
+          makeReadOnly: args.makeReadOnly ?? false
         ),
         alertMessage: args.message,
         successfulReadMessage: args.successfulReadMessage,
@@ -470,6 +579,97 @@ class NfcPlugin: Plugin, NFCTagReaderSessionDelegate, NFCNDEFReaderSessionDelega
     )
   }
 
+  /// Updates the message displayed in the system NFC sheet of the current session.
+  @objc public func setSessionMessage(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(SessionMessageOptions.self)
+    self.session?.nfcSession?.alertMessage = args.message
+    invoke.resolve()
+  }
+
+  /// Ends the current session, e.g. one kept alive for a multi-step flow.
+  @objc public func endSession(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(EndSessionOptions.self)
+    if let nfcSession = self.session?.nfcSession {
+      if let errorMessage = args.errorMessage {
+        self.closeSession(nfcSession, error: errorMessage)
+      } else {
+        if let successMessage = args.successMessage {
+          nfcSession.alertMessage = successMessage
+        }
+        self.closeSession(nfcSession)
+      }
+    }
+    invoke.resolve()
+  }
+
+  @objc public func takePendingTags(_ invoke: Invoke) {
+    let tags = pendingTags
+    pendingTags.removeAll()
+    invoke.resolve(["tags": tags])
+  }
+
+  @objc public func startCardEmulation(_ invoke: Invoke) {
+    invoke.reject("host card emulation is not supported on iOS")
+  }
+
+  @objc public func respondApdu(_ invoke: Invoke) {
+    invoke.reject("host card emulation is not supported on iOS")
+  }
+
+  @objc public func stopCardEmulation(_ invoke: Invoke) {
+    invoke.reject("host card emulation is not supported on iOS")
+  }
+
+  @objc public func transceive(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(TransceiveOptions.self)
+
+    guard let tag = self.session?.nfcTag else {
+      invoke.reject(
+        "connected tag not found, please scan a tag with `keepSessionAlive` set to true first")
+      return
+    }
+
+    let data = dataFromByteArray(args.data)
+    let resolve = { [self] (response: Data, error: Error?) in
+      if let error = error {
+        invoke.reject("failed to send command: \(error)")
+      } else {
+        invoke.resolve(["data": self.byteArrayFromData(response)])
+      }
+    }
+    let resolveAPDU = { [self] (response: Data, sw1: UInt8, sw2: UInt8, error: Error?) in
+      if let error = error {
+        invoke.reject("failed to send command: \(error)")
+      } else {
+        invoke.resolve(["data": self.byteArrayFromData(response) + [sw1, sw2]])
+      }
+    }
+
+    switch (tag, args.technology) {
+    case (let .iso7816(tag), .none), (let .iso7816(tag), .some("IsoDep")):
+      guard let apdu = NFCISO7816APDU(data: data) else {
+        invoke.reject("invalid ISO 7816 APDU")
+        return
+      }
+      tag.sendCommand(apdu: apdu, completionHandler: resolveAPDU)
+    case (let .miFare(tag), .some("IsoDep")):
+      guard let apdu = NFCISO7816APDU(data: data) else {
+        invoke.reject("invalid ISO 7816 APDU")
+        return
+      }
+      tag.sendMiFareISO7816Command(apdu, completionHandler: resolveAPDU)
+    case (let .miFare(tag), .none), (let .miFare(tag), .some("NfcA")),
+      (let .miFare(tag), .some("MifareUltralight")):
+      tag.sendMiFareCommand(commandPacket: data, completionHandler: resolve)
+    case (let .feliCa(tag), .none), (let .feliCa(tag), .some("NfcF")):
+      tag.sendFeliCaCommand(commandPacket: data, completionHandler: resolve)
+    case (_, let technology?):
+      invoke.reject("the tag does not support \(technology)")
+    default:
+      invoke.reject("the tag does not support raw commands")
+    }
+  }
+
   private func startScanSession(
     invoke: Invoke,
     kind: ScanKind,
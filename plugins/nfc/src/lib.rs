@@ -6,6 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 use tauri::{
+    ipc::{Channel, InvokeResponseBody},
     plugin::{Builder, PluginHandle, TauriPlugin},
     Manager, Runtime,
 };
@@ -14,6 +15,7 @@ pub use models::*;
 
 mod error;
 mod models;
+pub mod ndef;
 
 pub use error::{Error, Result};
 
@@ -31,9 +33,38 @@ struct IsAvailableResponse {
     available: bool,
 }
 
+#[derive(Deserialize)]
+struct TransceiveResponse {
+    data: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct TakePendingTagsResponse {
+    tags: Vec<NfcTag>,
+}
+
+#[derive(Serialize)]
+struct SessionMessageRequest {
+    message: String,
+}
+
+#[derive(Serialize)]
+struct CardEmulationRequest {
+    #[serde(flatten)]
+    options: CardEmulationOptions,
+    channel: Channel,
+}
+
+#[derive(Serialize)]
+struct RespondApduRequest {
+    data: Vec<u8>,
+}
+
 #[derive(Serialize)]
 struct WriteRequest {
     records: Vec<NfcRecord>,
+    #[serde(flatten)]
+    options: WriteOptions,
 }
 
 impl<R: Runtime> Nfc<R> {
@@ -51,8 +82,101 @@ impl<R: Runtime> Nfc<R> {
     }
 
     pub fn write(&self, records: Vec<NfcRecord>) -> crate::Result<()> {
+        self.write_with_options(records, Default::default())
+    }
+
+    /// Writes the records as a single NDEF message.
+    pub fn write_with_options(
+        &self,
+        records: Vec<NfcRecord>,
+        options: WriteOptions,
+    ) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("write", WriteRequest { records, options })
+            .map_err(Into::into)
+    }
+
+    /// Sends a raw command to the tag connected by a previous [`Self::scan`] with `keep_session_alive` enabled
+    /// and returns the tag response.
+    ///
+    /// On iOS the response of ISO 7816 commands ends with the SW1 and SW2 status bytes.
+    pub fn transceive(&self, request: TransceiveRequest) -> crate::Result<Vec<u8>> {
+        self.0
+            .run_mobile_plugin::<TransceiveResponse>("transceive", request)
+            .map(|r| r.data)
+            .map_err(Into::into)
+    }
+
+    /// Updates the message displayed in the NFC sheet of the current session. iOS only.
+    pub fn set_session_message(&self, message: impl Into<String>) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin(
+                "setSessionMessage",
+                SessionMessageRequest {
+                    message: message.into(),
+                },
+            )
+            .map_err(Into::into)
+    }
+
+    /// Ends the session kept alive by a previous [`Self::scan`] or [`Self::write_with_options`].
+    pub fn end_session(&self, request: EndSessionRequest) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("endSession", request)
+            .map_err(Into::into)
+    }
+
+    /// Returns and clears the tags read while no scan was in progress,
+    /// such as the tag that launched the app.
+    pub fn take_pending_tags(&self) -> crate::Result<Vec<NfcTag>> {
+        self.0
+            .run_mobile_plugin::<TakePendingTagsResponse>("takePendingTags", ())
+            .map(|r| r.tags)
+            .map_err(Into::into)
+    }
+
+    /// Emulates a card with the given AIDs, forwarding the commands of the readers selecting them to the handler.
+    /// Each [`CardEmulationEvent::Command`] must be answered with [`Self::respond_apdu`].
+    ///
+    /// Only supported on Android devices with host card emulation.
+    pub fn start_card_emulation<F: Fn(CardEmulationEvent) + Send + Sync + 'static>(
+        &self,
+        options: CardEmulationOptions,
+        handler: F,
+    ) -> crate::Result<()> {
+        let channel = Channel::new(move |event| {
+            match event {
+                InvokeResponseBody::Json(payload) => {
+                    match serde_json::from_str::<CardEmulationEvent>(&payload) {
+                        Ok(event) => handler(event),
+                        Err(error) => {
+                            log::error!("failed to deserialize card emulation event: {error}")
+                        }
+                    }
+                }
+                _ => log::error!("unexpected card emulation event payload"),
+            }
+            Ok(())
+        });
+        self.0
+            .run_mobile_plugin(
+                "startCardEmulation",
+                CardEmulationRequest { options, channel },
+            )
+            .map_err(Into::into)
+    }
+
+    /// Sends the response to the last command of the reader, usually ending with the SW1 and SW2 status bytes.
+    pub fn respond_apdu(&self, data: Vec<u8>) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("respondApdu", RespondApduRequest { data })
+            .map_err(Into::into)
+    }
+
+    /// Unregisters the AIDs of [`Self::start_card_emulation`].
+    pub fn stop_card_emulation(&self) -> crate::Result<()> {
         self.0
-            .run_mobile_plugin("write", WriteRequest { records })
+            .run_mobile_plugin("stopCardEmulation", ())
             .map_err(Into::into)
     }
 }
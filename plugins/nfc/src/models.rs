@@ -12,7 +12,7 @@ pub struct ScanRequest {
     pub keep_session_alive: bool,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NfcRecord {
     pub format: NFCTypeNameFormat,
@@ -21,7 +21,7 @@ pub struct NfcRecord {
     pub payload: Vec<u8>,
 }
 
-#[derive(serde_repr::Deserialize_repr, serde_repr::Serialize_repr)]
+#[derive(Debug, Clone, Copy, serde_repr::Deserialize_repr, serde_repr::Serialize_repr)]
 #[repr(u8)]
 pub enum NFCTypeNameFormat {
     Empty = 0,
@@ -43,8 +43,12 @@ pub struct NfcTagRecord {
 
 #[derive(Deserialize)]
 pub struct NfcTag {
-    pub id: String,
-    pub kind: String,
+    /// Tag identifier. Empty for tags read in the background on iOS.
+    #[serde(default)]
+    pub id: Vec<u8>,
+    /// Technologies supported by the tag.
+    #[serde(default)]
+    pub kind: Vec<String>,
     pub records: Vec<NfcTagRecord>,
 }
 
@@ -60,7 +64,7 @@ pub struct UriFilter {
     path_prefix: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum TechKind {
     IsoDep,
     MifareClassic,
@@ -117,3 +121,93 @@ pub enum ScanKind {
         uri: Option<UriFilter>,
     },
 }
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteOptions {
+    /// Kind of tag to scan when there is no session kept alive by a previous scan.
+    pub kind: Option<ScanKind>,
+    /// Permanently locks the tag after writing the message. This cannot be undone.
+    pub make_read_only: bool,
+    /// Message displayed in the UI when reading the tag. iOS only.
+    pub message: Option<String>,
+    /// Message displayed in the UI when the tag has been read. iOS only.
+    pub successful_read_message: Option<String>,
+    /// Message displayed in the UI when the message has been written. iOS only.
+    pub success_message: Option<String>,
+    /// Keeps the session alive after writing so the tag can be read or written again,
+    /// until [`crate::Nfc::end_session`] is called.
+    pub keep_session_alive: bool,
+    /// Uses reader mode instead of foreground dispatch to scan the tag. Android only.
+    pub reader_mode: Option<ReaderModeOptions>,
+}
+
+/// Android reader mode configuration, which disables the platform tag handling while the app is scanning.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReaderModeOptions {
+    /// Skips the NDEF check of the platform, required to talk to some tags with raw commands.
+    pub skip_ndef_check: bool,
+    /// Disables the sound played by the platform when a tag is discovered.
+    pub no_platform_sounds: bool,
+    /// Delay in milliseconds between the checks for the presence of the tag.
+    pub presence_check_delay: Option<u32>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndSessionRequest {
+    /// Message displayed in the UI when the session ends successfully. iOS only.
+    pub success_message: Option<String>,
+    /// Ends the session with an error message displayed in the UI. iOS only.
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransceiveRequest {
+    /// Raw command sent to the tag, e.g. an ISO 7816-4 APDU for [`TechKind::IsoDep`].
+    pub data: Vec<u8>,
+    /// Technology used to talk to the tag. Defaults to the first technology of the tag that supports raw commands.
+    pub technology: Option<TechKind>,
+    /// Timeout in milliseconds. Android only.
+    pub timeout: Option<u32>,
+}
+
+/// Category of the AIDs registered for card emulation.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CardEmulationCategory {
+    /// Payment applications, e.g. EMV cards. The user selects the default payment app in the system settings.
+    Payment,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardEmulationOptions {
+    /// Application identifiers (AIDs) selected by the readers, as hexadecimal strings.
+    pub aids: Vec<String>,
+    pub category: CardEmulationCategory,
+}
+
+/// Reason the connection to the reader ended.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeactivationReason {
+    /// The reader left the field.
+    LinkLoss,
+    /// The reader selected another AID.
+    Deselected,
+}
+
+/// Event sent by the reader to the emulated card.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CardEmulationEvent {
+    /// A command APDU, which must be answered with [`crate::Nfc::respond_apdu`].
+    Command { data: Vec<u8> },
+    /// The reader is no longer talking to the emulated card.
+    Deactivated { reason: DeactivationReason },
+}
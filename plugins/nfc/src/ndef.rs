@@ -0,0 +1,236 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! NDEF record construction helpers.
+//!
+//! See the NFC Forum NDEF and RTD specifications for the record layouts.
+
+use crate::{NFCTypeNameFormat, NfcRecord};
+
+/// Record type of the NFC Forum well-known Text record.
+pub const RTD_TEXT: &[u8] = b"T";
+/// Record type of the NFC Forum well-known URI record.
+pub const RTD_URI: &[u8] = b"U";
+/// Record type of the NFC Forum well-known Smart Poster record.
+pub const RTD_SMART_POSTER: &[u8] = b"Sp";
+/// Record type of the Smart Poster recommended action record.
+const RTD_ACTION: &[u8] = b"act";
+
+/// URI identifier codes of the URI record type definition, indexed by their code.
+const URI_PREFIXES: &[&str] = &[
+    "",
+    "http://www.",
+    "https://www.",
+    "http://",
+    "https://",
+    "tel:",
+    "mailto:",
+    "ftp://anonymous:anonymous@",
+    "ftp://ftp.",
+    "ftps://",
+    "sftp://",
+    "smb://",
+    "nfs://",
+    "ftp://",
+    "dav://",
+    "news:",
+    "telnet://",
+    "imap:",
+    "rtsp://",
+    "urn:",
+    "pop:",
+    "sip:",
+    "sips:",
+    "tftp:",
+    "btspp://",
+    "btl2cap://",
+    "btgoep://",
+    "tcpobex://",
+    "irdaobex://",
+    "file://",
+    "urn:epc:id:",
+    "urn:epc:tag:",
+    "urn:epc:pat:",
+    "urn:epc:raw:",
+    "urn:epc:",
+    "urn:nfc:",
+];
+
+/// Recommended action of a Smart Poster.
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum SmartPosterAction {
+    /// Do the action, e.g. open the URI in the browser.
+    Do = 0,
+    /// Save the URI for later, e.g. as a bookmark.
+    Save = 1,
+    /// Open the URI for editing.
+    Edit = 2,
+}
+
+/// A Smart Poster: a URI with titles and an optional icon.
+#[derive(Debug, Default)]
+pub struct SmartPoster {
+    pub uri: String,
+    /// Titles as `(language, text)` pairs.
+    pub titles: Vec<(String, String)>,
+    pub action: Option<SmartPosterAction>,
+    /// Icon as a `(mime type, image data)` pair.
+    pub icon: Option<(String, Vec<u8>)>,
+}
+
+impl NfcRecord {
+    pub fn new(format: NFCTypeNameFormat, kind: Vec<u8>, id: Vec<u8>, payload: Vec<u8>) -> Self {
+        Self {
+            format,
+            kind,
+            id,
+            payload,
+        }
+    }
+
+    /// A record without type or payload.
+    pub fn empty() -> Self {
+        Self::new(NFCTypeNameFormat::Empty, Vec::new(), Vec::new(), Vec::new())
+    }
+
+    /// A well-known Text record with UTF-8 encoded text.
+    pub fn text(text: &str, language: &str) -> Self {
+        let mut payload = Vec::with_capacity(1 + language.len() + text.len());
+        // status byte: UTF-8 encoding and the length of the language code
+        payload.push(language.len() as u8 & 0x3f);
+        payload.extend_from_slice(language.as_bytes());
+        payload.extend_from_slice(text.as_bytes());
+        Self::new(
+            NFCTypeNameFormat::NfcWellKnown,
+            RTD_TEXT.to_vec(),
+            Vec::new(),
+            payload,
+        )
+    }
+
+    /// A well-known URI record, abbreviating the longest known URI prefix.
+    pub fn uri(uri: &str) -> Self {
+        let (code, prefix) = URI_PREFIXES
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, prefix)| uri.starts_with(*prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .unwrap_or((0, &""));
+        let mut payload = Vec::with_capacity(1 + uri.len() - prefix.len());
+        payload.push(code as u8);
+        payload.extend_from_slice(uri[prefix.len()..].as_bytes());
+        Self::new(
+            NFCTypeNameFormat::NfcWellKnown,
+            RTD_URI.to_vec(),
+            Vec::new(),
+            payload,
+        )
+    }
+
+    /// A record with an absolute URI as type and no payload.
+    pub fn absolute_uri(uri: &str) -> Self {
+        Self::new(
+            NFCTypeNameFormat::AbsoluteURI,
+            uri.as_bytes().to_vec(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    /// A record with a RFC 2046 media type, e.g. `application/json`.
+    pub fn mime(mime_type: &str, payload: Vec<u8>) -> Self {
+        Self::new(
+            NFCTypeNameFormat::Media,
+            mime_type.to_ascii_lowercase().into_bytes(),
+            Vec::new(),
+            payload,
+        )
+    }
+
+    /// A NFC Forum external type record, e.g. `example.com:mytype`.
+    pub fn external(domain: &str, kind: &str, payload: Vec<u8>) -> Self {
+        Self::new(
+            NFCTypeNameFormat::NfcExternal,
+            format!("{domain}:{kind}").to_ascii_lowercase().into_bytes(),
+            Vec::new(),
+            payload,
+        )
+    }
+
+    /// An Android Application Record, which launches the app with the given package name when the tag is scanned.
+    pub fn android_application(package_name: &str) -> Self {
+        Self::external("android.com", "pkg", package_name.as_bytes().to_vec())
+    }
+
+    /// A well-known Smart Poster record.
+    pub fn smart_poster(poster: &SmartPoster) -> Self {
+        let mut records = vec![Self::uri(&poster.uri)];
+        for (language, text) in &poster.titles {
+            records.push(Self::text(text, language));
+        }
+        if let Some(action) = poster.action {
+            records.push(Self::new(
+                NFCTypeNameFormat::NfcWellKnown,
+                RTD_ACTION.to_vec(),
+                Vec::new(),
+                vec![action as u8],
+            ));
+        }
+        if let Some((mime_type, data)) = &poster.icon {
+            records.push(Self::mime(mime_type, data.clone()));
+        }
+        Self::new(
+            NFCTypeNameFormat::NfcWellKnown,
+            RTD_SMART_POSTER.to_vec(),
+            Vec::new(),
+            encode_message(&records),
+        )
+    }
+
+    /// Sets the record identifier.
+    pub fn with_id(mut self, id: impl Into<Vec<u8>>) -> Self {
+        self.id = id.into();
+        self
+    }
+}
+
+/// Serializes the records as a NDEF message.
+pub fn encode_message(records: &[NfcRecord]) -> Vec<u8> {
+    let mut message = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        let short = record.payload.len() < 256;
+        let mut header = record.format as u8;
+        if i == 0 {
+            // message begin
+            header |= 0x80;
+        }
+        if i == records.len() - 1 {
+            // message end
+            header |= 0x40;
+        }
+        if short {
+            header |= 0x10;
+        }
+        if !record.id.is_empty() {
+            header |= 0x08;
+        }
+
+        message.push(header);
+        message.push(record.kind.len() as u8);
+        if short {
+            message.push(record.payload.len() as u8);
+        } else {
+            message.extend_from_slice(&(record.payload.len() as u32).to_be_bytes());
+        }
+        if !record.id.is_empty() {
+            message.push(record.id.len() as u8);
+        }
+        message.extend_from_slice(&record.kind);
+        message.extend_from_slice(&record.id);
+        message.extend_from_slice(&record.payload);
+    }
+    message
+}
@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_OPENER__=function(n){"use strict";async function e(n,e={},_){return window.__TAURI_INTERNALS__.invoke(n,e,_)}return"function"==typeof SuppressedError&&SuppressedError,n.openPath=async function(n,_){await e("plugin:opener|open_path",{path:n,with:_})},n.openUrl=async function(n,_){await e("plugin:opener|open_url",{url:n,with:_})},n.revealItemInDir=async function(n){return e("plugin:opener|reveal_item_in_dir",{path:n})},n}({});Object.defineProperty(window.__TAURI__,"opener",{value:__TAURI_PLUGIN_OPENER__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_OPENER__=function(n){"use strict";async function e(n,e={},_){return window.__TAURI_INTERNALS__.invoke(n,e,_)}return"function"==typeof SuppressedError&&SuppressedError,n.getApps=async function(n){return await e("plugin:opener|get_apps",{target:n})},n.getDefaultApp=async function(n){return await e("plugin:opener|get_default_app",{extensionOrScheme:n})},n.openPath=async function(n,_,t){return await e("plugin:opener|open_path",{path:n,with:_,options:t})},n.openUrl=async function(n,_,t){return await e("plugin:opener|open_url",{url:n,with:_,options:t})},n.openWith=async function(n,_){await e("plugin:opener|open_with",{path:n,appId:_})},n.revealItemInDir=async function(n){return e("plugin:opener|reveal_item_in_dir",{path:n})},n}({});Object.defineProperty(window.__TAURI__,"opener",{value:__TAURI_PLUGIN_OPENER__})}
@@ -110,7 +110,14 @@ fn _f() {
     };
 }
 
-const COMMANDS: &[&str] = &["open_url", "open_path", "reveal_item_in_dir"];
+const COMMANDS: &[&str] = &[
+    "open_url",
+    "open_path",
+    "open_with",
+    "get_apps",
+    "get_default_app",
+    "reveal_item_in_dir",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -20,6 +20,45 @@
 
 import { invoke } from '@tauri-apps/api/core'
 
+/**
+ * Options of {@linkcode openUrl} and {@linkcode openPath}.
+ *
+ * @since 2.3.0
+ */
+export interface OpenOptions {
+  /**
+   * Wait for the launched program to exit, e.g. to reimport a file edited externally.
+   *
+   * #### Platform-specific:
+   *
+   * - **Linux:** Without `openWith`, only waits for the launcher such as `xdg-open`, which may exit before the program.
+   * - **Android / iOS:** Unsupported.
+   */
+  wait?: boolean
+}
+
+/**
+ * The result of {@linkcode openUrl} and {@linkcode openPath}.
+ *
+ * @since 2.3.0
+ */
+export interface LaunchResult {
+  /**
+   * The ID of the launched process, when it can be obtained.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows:** Not available if the file is opened in an already running instance of the program.
+   * - **macOS:** Never available.
+   * - **Linux:** Only available with `openWith`.
+   */
+  pid: number | null
+  /** Whether the launch succeeded and, when waiting, the process exited successfully. */
+  success: boolean
+  /** The exit code of the process, when waiting and it can be obtained. */
+  exitCode: number | null
+}
+
 /**
  * Opens a url with the system's default app, or the one specified with {@linkcode openWith}.
  *
@@ -35,13 +74,20 @@ import { invoke } from '@tauri-apps/api/core'
  *
  * @param url The URL to open.
  * @param openWith The app to open the URL with. If not specified, defaults to the system default application for the specified url type.
+ * @param options Since 2.3.0.
+ * @returns The launched process, since 2.3.0.
  *
  * @since 2.0.0
  */
-export async function openUrl(url: string, openWith?: string): Promise<void> {
-  await invoke('plugin:opener|open_url', {
+export async function openUrl(
+  url: string,
+  openWith?: string,
+  options?: OpenOptions
+): Promise<LaunchResult> {
+  return await invoke('plugin:opener|open_url', {
     url,
-    with: openWith
+    with: openWith,
+    options
   })
 }
 
@@ -56,37 +102,180 @@ export async function openUrl(url: string, openWith?: string): Promise<void> {
  * await openPath('/path/to/file');
  * // opens a file using `vlc` command on Windows.
  * await openPath('C:/path/to/file', 'vlc');
+ * // waits for the editor to be closed
+ * const { success } = await openPath('/path/to/file', 'gedit', { wait: true });
  * ```
  *
  * @param path The path to open.
  * @param openWith The app to open the path with. If not specified, defaults to the system default application for the specified path type.
+ * @param options Since 2.3.0.
+ * @returns The launched process, since 2.3.0.
  *
  * @since 2.0.0
  */
-export async function openPath(path: string, openWith?: string): Promise<void> {
-  await invoke('plugin:opener|open_path', {
+export async function openPath(
+  path: string,
+  openWith?: string,
+  options?: OpenOptions
+): Promise<LaunchResult> {
+  return await invoke('plugin:opener|open_path', {
     path,
-    with: openWith
+    with: openWith,
+    options
   })
 }
 
 /**
- * Reveal a path with the system's default explorer.
+ * An application capable of opening a path or URL, see {@linkcode getApps}.
+ *
+ * @since 2.3.0
+ */
+export interface App {
+  /**
+   * The identifier to use with {@linkcode openWith}.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows:** The name of the association handler, usually the path of its executable.
+   * - **macOS:** The bundle identifier, or the path of the bundle if it has none.
+   * - **Linux:** The desktop file ID, e.g. `org.gnome.TextEditor.desktop`.
+   */
+  id: string
+  /** The display name of the application. */
+  name: string
+  /**
+   * The icon of the application.
+   *
+   * #### Platform-specific:
+   *
+   * - **Windows:** The icon location, the path of a file followed by a comma and the resource index.
+   * - **macOS:** Always `null`.
+   * - **Linux:** Either a theme icon name or an absolute path.
+   */
+  icon: string | null
+}
+
+/**
+ * Lists the installed applications capable of opening the path or URL, the default one first.
+ *
+ * #### Platform-specific:
+ *
+ * - **macOS:** Requires macOS 12 or later.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { getApps, openWith } from '@tauri-apps/plugin-opener';
+ *
+ * // build an "Open with…" menu
+ * const apps = await getApps('/path/to/file.txt');
+ * await openWith('/path/to/file.txt', apps[0].id);
+ * ```
+ *
+ * @param pathOrUrl The path or URL to open.
+ *
+ * @since 2.3.0
+ */
+export async function getApps(pathOrUrl: string): Promise<App[]> {
+  return await invoke('plugin:opener|get_apps', { target: pathOrUrl })
+}
+
+/**
+ * Returns the system default application for a file extension or URL scheme,
+ * or `null` if there is none.
+ *
+ * Extensions start with a dot, e.g. `.txt`, everything else is a scheme, e.g. `https`.
+ * Compare the {@linkcode App.id} with the identifier of your app to know if it is the default application:
+ * the path of the executable on Windows, the bundle identifier on macOS and the desktop file ID on Linux.
+ *
+ * #### Platform-specific:
+ *
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { getDefaultApp } from '@tauri-apps/plugin-opener';
+ *
+ * const app = await getDefaultApp('.md');
+ * if (app) {
+ *   console.log(`Opens in ${app.name}`);
+ * }
+ * ```
+ *
+ * @param extensionOrScheme The file extension, starting with a dot, or the URL scheme.
+ *
+ * @since 2.3.0
+ */
+export async function getDefaultApp(
+  extensionOrScheme: string
+): Promise<App | null> {
+  return await invoke('plugin:opener|get_default_app', { extensionOrScheme })
+}
+
+/**
+ * Opens a path with an application returned by {@linkcode getApps}.
+ *
+ * The path and application are checked against the `open_with` command scope,
+ * where the application is matched against the {@linkcode App.id}.
+ *
+ * #### Platform-specific:
+ *
+ * - **Android / iOS:** Unsupported.
+ *
+ * @param path The path to open.
+ * @param appId The {@linkcode App.id} of the application to open the path with.
+ *
+ * @since 2.3.0
+ */
+export async function openWith(path: string, appId: string): Promise<void> {
+  await invoke('plugin:opener|open_with', { path, appId })
+}
+
+/**
+ * A path revealed by {@linkcode revealItemInDir}.
+ *
+ * @since 2.3.0
+ */
+export interface RevealedItem {
+  /** The requested path. */
+  path: string
+  /**
+   * The path selected in the file manager,
+   * which is the nearest existing parent when the requested path does not exist.
+   */
+  revealed: string
+  /** Whether the requested path exists. */
+  exists: boolean
+}
+
+/**
+ * Reveal one or more paths with the system's default explorer.
+ *
+ * Multiple paths are selected in one window where the file manager supports it,
+ * and paths that do not exist are replaced by their nearest existing parent.
  *
  * #### Platform-specific:
  *
+ * - **Windows:** Opens one window per parent directory.
  * - **Android / iOS:** Unsupported.
  *
  * @example
  * ```typescript
  * import { revealItemInDir } from '@tauri-apps/plugin-opener';
  * await revealItemInDir('/path/to/file');
+ * const [item] = await revealItemInDir(['/path/to/deleted/file']);
+ * if (!item.exists) {
+ *   // `item.revealed` is the nearest existing parent
+ * }
  * ```
  *
- * @param path The path to reveal.
+ * @param path The path or paths to reveal.
+ * @returns What was revealed for each path, since 2.3.0.
  *
  * @since 2.0.0
  */
-export async function revealItemInDir(path: string) {
+export async function revealItemInDir(
+  path: string | string[]
+): Promise<RevealedItem[]> {
   return invoke('plugin:opener|reveal_item_in_dir', { path })
 }
@@ -9,7 +9,20 @@ use tauri::{
     AppHandle, Runtime,
 };
 
-use crate::{scope::Scope, Error};
+use serde::Deserialize;
+
+use crate::{scope::Scope, App, Error, LaunchResult, RevealedItem};
+
+/// Launches on a blocking thread, since waiting blocks until the program exits.
+async fn launch(target: String, with: Option<String>, wait: bool) -> crate::Result<LaunchResult> {
+    tauri::async_runtime::spawn_blocking(move || crate::launch(target, with, wait)).await?
+}
+
+#[derive(Default, Deserialize)]
+pub struct OpenOptions {
+    #[serde(default)]
+    wait: bool,
+}
 
 #[tauri::command]
 pub async fn open_url<R: Runtime>(
@@ -18,7 +31,8 @@ pub async fn open_url<R: Runtime>(
     global_scope: GlobalScope<crate::scope::Entry>,
     url: String,
     with: Option<String>,
-) -> crate::Result<()> {
+    options: Option<OpenOptions>,
+) -> crate::Result<LaunchResult> {
     let scope = Scope::new(
         &app,
         command_scope
@@ -34,7 +48,7 @@ pub async fn open_url<R: Runtime>(
     );
 
     if scope.is_url_allowed(&url, with.as_deref()) {
-        crate::open_url(url, with)
+        launch(url, with, options.unwrap_or_default().wait).await
     } else {
         Err(Error::ForbiddenUrl { url, with })
     }
@@ -47,7 +61,8 @@ pub async fn open_path<R: Runtime>(
     global_scope: GlobalScope<crate::scope::Entry>,
     path: String,
     with: Option<String>,
-) -> crate::Result<()> {
+    options: Option<OpenOptions>,
+) -> crate::Result<LaunchResult> {
     let scope = Scope::new(
         &app,
         command_scope
@@ -63,13 +78,65 @@ pub async fn open_path<R: Runtime>(
     );
 
     if scope.is_path_allowed(Path::new(&path), with.as_deref())? {
-        crate::open_path(path, with)
+        launch(path, with, options.unwrap_or_default().wait).await
     } else {
         Err(Error::ForbiddenPath { path, with })
     }
 }
 
 #[tauri::command]
-pub async fn reveal_item_in_dir(path: PathBuf) -> crate::Result<()> {
-    crate::reveal_item_in_dir(path)
+pub async fn open_with<R: Runtime>(
+    app: AppHandle<R>,
+    command_scope: CommandScope<crate::scope::Entry>,
+    global_scope: GlobalScope<crate::scope::Entry>,
+    path: String,
+    app_id: String,
+) -> crate::Result<()> {
+    let scope = Scope::new(
+        &app,
+        command_scope
+            .allows()
+            .iter()
+            .chain(global_scope.allows())
+            .collect(),
+        command_scope
+            .denies()
+            .iter()
+            .chain(global_scope.denies())
+            .collect(),
+    );
+
+    if scope.is_path_allowed(Path::new(&path), Some(&app_id))? {
+        crate::open_with(path, app_id)
+    } else {
+        Err(Error::ForbiddenPath {
+            path,
+            with: Some(app_id),
+        })
+    }
+}
+
+#[tauri::command]
+pub async fn get_apps(target: String) -> crate::Result<Vec<App>> {
+    crate::get_apps(target)
+}
+
+#[tauri::command]
+pub async fn get_default_app(extension_or_scheme: String) -> crate::Result<Option<App>> {
+    crate::get_default_app(extension_or_scheme)
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum RevealPaths {
+    One(PathBuf),
+    Many(Vec<PathBuf>),
+}
+
+#[tauri::command]
+pub async fn reveal_item_in_dir(path: RevealPaths) -> crate::Result<Vec<RevealedItem>> {
+    match path {
+        RevealPaths::One(path) => crate::reveal_items_in_dir([path]),
+        RevealPaths::Many(paths) => crate::reveal_items_in_dir(paths),
+    }
 }
@@ -20,6 +20,8 @@ pub enum Error {
     Json(#[from] serde_json::Error),
     #[error("unknown program {0}")]
     UnknownProgramName(String),
+    #[error("unknown application {0}")]
+    UnknownApplication(String),
     #[error("Not allowed to open path {}{}", .path, .with.as_ref().map(|w| format!(" with {w}")).unwrap_or_default())]
     ForbiddenPath { path: String, with: Option<String> },
     #[error("Not allowed to open url {}{}", .url, .with.as_ref().map(|w| format!(" with {w}")).unwrap_or_default())]
@@ -16,6 +16,7 @@ tauri::ios_plugin_binding!(init_plugin_opener);
 mod commands;
 mod error;
 mod open;
+mod open_with;
 mod reveal_item_in_dir;
 mod scope;
 mod scope_entry;
@@ -23,8 +24,9 @@ mod scope_entry;
 pub use error::Error;
 type Result<T> = std::result::Result<T, Error>;
 
-pub use open::{open_path, open_url};
-pub use reveal_item_in_dir::reveal_item_in_dir;
+pub use open::{launch, open_path, open_url, LaunchResult};
+pub use open_with::{get_apps, get_default_app, open_with, App};
+pub use reveal_item_in_dir::{reveal_item_in_dir, reveal_items_in_dir, RevealedItem};
 
 pub struct Opener<R: Runtime> {
     // we use `fn() -> R` to slicence the unused generic error
@@ -140,9 +142,44 @@ impl<R: Runtime> Opener<R> {
             .map_err(Into::into)
     }
 
+    /// Open a url or path with a default or specific program,
+    /// returning the launched process and optionally waiting for it to exit, see [`launch`].
+    pub fn launch(
+        &self,
+        target: impl AsRef<std::ffi::OsStr>,
+        with: Option<impl AsRef<str>>,
+        wait: bool,
+    ) -> Result<LaunchResult> {
+        crate::open::launch(target, with, wait)
+    }
+
+    /// Lists the installed applications capable of opening the path or URL, see [`get_apps`].
+    pub fn get_apps(&self, target: impl AsRef<str>) -> Result<Vec<App>> {
+        crate::open_with::get_apps(target)
+    }
+
+    /// Returns the system default application for a file extension or URL scheme, see [`get_default_app`].
+    pub fn get_default_app(&self, extension_or_scheme: impl AsRef<str>) -> Result<Option<App>> {
+        crate::open_with::get_default_app(extension_or_scheme)
+    }
+
+    /// Opens the path or URL with an application returned by [`Self::get_apps`], see [`open_with`].
+    pub fn open_with(&self, path: impl AsRef<Path>, app_id: impl AsRef<str>) -> Result<()> {
+        crate::open_with::open_with(path, app_id)
+    }
+
     pub fn reveal_item_in_dir<P: AsRef<Path>>(&self, p: P) -> Result<()> {
         crate::reveal_item_in_dir::reveal_item_in_dir(p)
     }
+
+    /// Reveal multiple paths in the system's default explorer, see [`reveal_items_in_dir`].
+    pub fn reveal_items_in_dir<I, P>(&self, paths: I) -> Result<Vec<RevealedItem>>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        crate::reveal_item_in_dir::reveal_items_in_dir(paths)
+    }
 }
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the opener APIs.
@@ -204,6 +241,9 @@ impl Builder {
             .invoke_handler(tauri::generate_handler![
                 commands::open_url,
                 commands::open_path,
+                commands::open_with,
+                commands::get_apps,
+                commands::get_default_app,
                 commands::reveal_item_in_dir
             ]);
 
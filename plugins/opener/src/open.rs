@@ -6,6 +6,8 @@
 
 use std::{ffi::OsStr, path::Path};
 
+use serde::Serialize;
+
 pub(crate) fn open<P: AsRef<OsStr>, S: AsRef<str>>(path: P, with: Option<S>) -> crate::Result<()> {
     match with {
         Some(program) => ::open::with_detached(path, program.as_ref()),
@@ -55,3 +57,257 @@ pub fn open_path<P: AsRef<Path>, S: AsRef<str>>(path: P, with: Option<S>) -> cra
     let path = path.as_ref();
     open(path, with)
 }
+
+/// The result of [`launch`].
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchResult {
+    /// The ID of the launched process, when it can be obtained.
+    pub pid: Option<u32>,
+    /// Whether the launch succeeded and, when waiting, the process exited successfully.
+    pub success: bool,
+    /// The exit code of the process, when waiting and it can be obtained.
+    pub exit_code: Option<i32>,
+}
+
+/// Opens a URL or path with the program specified in `with`, or system default if `None`,
+/// returning the launched process and optionally waiting for it to exit.
+///
+/// Waiting is useful to reimport a file edited in an external program.
+///
+/// ## Platform-specific:
+///
+/// - **Windows:** The process can't be obtained if the file is opened in an already running instance of the program.
+/// - **macOS:** The process ID and exit code are never available since programs are launched by `open`,
+///   waiting waits for the program to exit.
+/// - **Linux:** The process ID and exit code are only available with `with`,
+///   otherwise waiting only waits for the launcher, such as `xdg-open`, which may exit before the program.
+/// - **Android / iOS**: Always opens using default program, waiting is unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     // wait for the user to close the editor
+///     let result = tauri_plugin_opener::launch("/path/to/file.txt", Some("gedit"), true)?;
+///     if result.success {
+///       // reimport the file
+///     }
+///     Ok(())
+///   });
+/// ```
+pub fn launch<P: AsRef<OsStr>, S: AsRef<str>>(
+    target: P,
+    with: Option<S>,
+    wait: bool,
+) -> crate::Result<LaunchResult> {
+    #[cfg(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    return imp::launch(target.as_ref(), with.as_ref().map(AsRef::as_ref), wait);
+
+    #[cfg(not(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    {
+        if wait {
+            return Err(crate::Error::UnsupportedPlatform);
+        }
+        open(target, with)?;
+        Ok(LaunchResult {
+            success: true,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::*;
+
+    use windows::{
+        core::{HSTRING, PCWSTR},
+        Win32::{
+            Foundation::CloseHandle,
+            System::Threading::{GetExitCodeProcess, GetProcessId, WaitForSingleObject, INFINITE},
+            UI::{
+                Shell::{
+                    ShellExecuteExW, SEE_MASK_FLAG_NO_UI, SEE_MASK_NOCLOSEPROCESS,
+                    SHELLEXECUTEINFOW,
+                },
+                WindowsAndMessaging::SW_SHOWNORMAL,
+            },
+        },
+    };
+
+    pub fn launch(target: &OsStr, with: Option<&str>, wait: bool) -> crate::Result<LaunchResult> {
+        let (file, parameters) = match with {
+            Some(program) => {
+                let mut parameters = std::ffi::OsString::from("\"");
+                parameters.push(target);
+                parameters.push("\"");
+                (HSTRING::from(program), Some(HSTRING::from(parameters)))
+            }
+            None => (HSTRING::from(target), None),
+        };
+
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as _,
+            fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_FLAG_NO_UI,
+            nShow: SW_SHOWNORMAL.0,
+            lpFile: PCWSTR(file.as_ptr()),
+            lpParameters: parameters
+                .as_ref()
+                .map(|p| PCWSTR(p.as_ptr()))
+                .unwrap_or(PCWSTR::null()),
+            ..unsafe { std::mem::zeroed() }
+        };
+
+        unsafe { ShellExecuteExW(&mut info)? };
+
+        // no process is returned when the file is opened by an already running instance
+        let process = info.hProcess;
+        if process.is_invalid() {
+            return Ok(LaunchResult {
+                success: true,
+                ..Default::default()
+            });
+        }
+
+        let pid = unsafe { GetProcessId(process) };
+        let mut exit_code = None;
+        if wait {
+            let mut code = 0;
+            unsafe {
+                WaitForSingleObject(process, INFINITE);
+                if GetExitCodeProcess(process, &mut code).is_ok() {
+                    exit_code = Some(code as i32);
+                }
+            }
+        }
+        let _ = unsafe { CloseHandle(process) };
+
+        Ok(LaunchResult {
+            pid: (pid != 0).then_some(pid),
+            success: exit_code.map_or(true, |code| code == 0),
+            exit_code,
+        })
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod imp {
+    use std::{
+        os::unix::process::CommandExt,
+        process::{Command, Stdio},
+    };
+
+    use super::*;
+
+    fn spawn(mut command: Command, wait: bool) -> std::io::Result<std::process::Child> {
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if !wait {
+            // detach the program like `open::that_detached`, so it isn't killed with the app's process group
+            command.process_group(0);
+        }
+        command.spawn()
+    }
+
+    pub fn launch(target: &OsStr, with: Option<&str>, wait: bool) -> crate::Result<LaunchResult> {
+        let (mut child, is_launcher) = match with {
+            Some(program) => {
+                let mut command = Command::new(program);
+                command.arg(target);
+                (spawn(command, wait)?, false)
+            }
+            None => {
+                // try the launchers in order, like `open::that`
+                let mut last_error = None;
+                let mut child = None;
+                for command in ::open::commands(target) {
+                    match spawn(command, wait) {
+                        Ok(c) => {
+                            child = Some(c);
+                            break;
+                        }
+                        Err(e) => last_error = Some(e),
+                    }
+                }
+                match child {
+                    Some(child) => (child, true),
+                    None => {
+                        return Err(last_error
+                            .unwrap_or_else(|| std::io::ErrorKind::NotFound.into())
+                            .into())
+                    }
+                }
+            }
+        };
+
+        if wait {
+            let status = child.wait()?;
+            return Ok(LaunchResult {
+                pid: (!is_launcher).then(|| child.id()),
+                success: status.success(),
+                exit_code: if is_launcher { None } else { status.code() },
+            });
+        }
+
+        let pid = child.id();
+        // reap the process once it exits
+        std::thread::spawn(move || child.wait());
+
+        Ok(LaunchResult {
+            pid: (!is_launcher).then_some(pid),
+            success: true,
+            exit_code: None,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::process::Command;
+
+    use super::*;
+
+    pub fn launch(target: &OsStr, with: Option<&str>, wait: bool) -> crate::Result<LaunchResult> {
+        let mut command = Command::new("/usr/bin/open");
+        if wait {
+            // waits for the program to exit
+            command.arg("-W");
+        }
+        if let Some(program) = with {
+            command.arg("-a").arg(program);
+        }
+        // without waiting, `open` exits once the program is launched
+        let status = command.arg(target).status()?;
+        Ok(LaunchResult {
+            pid: None,
+            success: status.success(),
+            exit_code: None,
+        })
+    }
+}
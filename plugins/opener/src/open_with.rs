@@ -0,0 +1,766 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Applications capable of opening a path or URL.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+/// An application capable of opening a path or URL, see [`get_apps`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct App {
+    /// The identifier of the application, to use with [`open_with`].
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows:** The name of the association handler, usually the path of its executable.
+    /// - **macOS:** The bundle identifier, or the path of the bundle if it has none.
+    /// - **Linux:** The desktop file ID, e.g. `org.gnome.TextEditor.desktop`.
+    pub id: String,
+    /// The display name of the application.
+    pub name: String,
+    /// The icon of the application.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows:** The icon location, the path of a file followed by a comma and the resource index.
+    /// - **macOS:** Always `None`.
+    /// - **Linux:** The icon name of the desktop entry, either a theme icon name or an absolute path.
+    pub icon: Option<String>,
+}
+
+/// The URL scheme of the target, or `None` if it is a path.
+///
+/// Single letter schemes are Windows drive letters.
+#[cfg(desktop)]
+fn url_scheme(target: &str) -> Option<&str> {
+    let (scheme, _) = target.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = scheme.len() > 1
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then_some(scheme)
+}
+
+/// What [`get_default_app`] queries the handler of.
+#[cfg(desktop)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandlerKind<'a> {
+    /// A file extension, without the leading dot.
+    Extension(&'a str),
+    /// A URL scheme, without the trailing colon.
+    Scheme(&'a str),
+}
+
+#[cfg(desktop)]
+impl<'a> HandlerKind<'a> {
+    /// Extensions start with a dot, everything else is a scheme.
+    fn parse(extension_or_scheme: &'a str) -> Self {
+        match extension_or_scheme.strip_prefix('.') {
+            Some(extension) => Self::Extension(extension),
+            None => Self::Scheme(
+                extension_or_scheme
+                    .trim_end_matches('/')
+                    .trim_end_matches(':'),
+            ),
+        }
+    }
+}
+
+/// Lists the installed applications capable of opening the path or URL, the default one first.
+///
+/// ## Platform-specific:
+///
+/// - **macOS:** Requires macOS 12 or later.
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     for app in tauri_plugin_opener::get_apps("/path/to/file.txt")? {
+///       println!("{} ({})", app.name, app.id);
+///     }
+///     Ok(())
+///   });
+/// ```
+pub fn get_apps<S: AsRef<str>>(target: S) -> crate::Result<Vec<App>> {
+    #[cfg(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    return imp::get_apps(target.as_ref());
+
+    #[cfg(not(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    {
+        let _ = target;
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
+
+/// Returns the system default application for a file extension, starting with a dot, or a URL scheme, e.g. `.txt` or `https`.
+///
+/// Returns `None` if there is no default application.
+/// Compare its [`App::id`] with the identifier of the app to know if it is the default application:
+/// the path of the executable on Windows, the bundle identifier on macOS and the desktop file ID on Linux.
+///
+/// ## Platform-specific:
+///
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     if let Some(app) = tauri_plugin_opener::get_default_app(".md")? {
+///       println!("Markdown files open in {}", app.name);
+///     }
+///     Ok(())
+///   });
+/// ```
+pub fn get_default_app<S: AsRef<str>>(extension_or_scheme: S) -> crate::Result<Option<App>> {
+    #[cfg(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    return imp::get_default_app(HandlerKind::parse(extension_or_scheme.as_ref()));
+
+    #[cfg(not(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    {
+        let _ = extension_or_scheme;
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
+
+/// Opens the path or URL with the application, identified by the [`App::id`] returned by [`get_apps`].
+///
+/// ## Platform-specific:
+///
+/// - **Android / iOS:** Unsupported.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// tauri::Builder::default()
+///   .setup(|app| {
+///     let apps = tauri_plugin_opener::get_apps("/path/to/file.txt")?;
+///     if let Some(app) = apps.first() {
+///       tauri_plugin_opener::open_with("/path/to/file.txt", &app.id)?;
+///     }
+///     Ok(())
+///   });
+/// ```
+pub fn open_with<P: AsRef<Path>, S: AsRef<str>>(path: P, app_id: S) -> crate::Result<()> {
+    #[cfg(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    return imp::open_with(path.as_ref(), app_id.as_ref());
+
+    #[cfg(not(any(
+        windows,
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    {
+        let _ = (path, app_id);
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::*;
+
+    use windows::{
+        core::{HSTRING, PCWSTR, PWSTR},
+        Win32::{
+            System::Com::{CoInitialize, CoTaskMemFree, IDataObject},
+            UI::Shell::{
+                AssocQueryStringW, BHID_DataObject, IAssocHandler, IEnumAssocHandlers, IShellItem,
+                SHAssocEnumHandlers, SHAssocEnumHandlersForProtocolByApplication,
+                SHCreateItemFromParsingName, ASSOCF, ASSOCF_INIT_IGNOREUNKNOWN, ASSOCF_IS_PROTOCOL,
+                ASSOCSTR, ASSOCSTR_EXECUTABLE, ASSOCSTR_FRIENDLYAPPNAME, ASSOC_FILTER_RECOMMENDED,
+            },
+        },
+    };
+
+    /// Converts a string allocated by the shell, freeing it.
+    unsafe fn take_string(s: PWSTR) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let value = s.to_string().ok();
+        CoTaskMemFree(Some(s.0 as _));
+        value
+    }
+
+    fn handlers(target: &str) -> crate::Result<Vec<IAssocHandler>> {
+        let _ = unsafe { CoInitialize(None) };
+
+        let enumerator: IEnumAssocHandlers = match url_scheme(target) {
+            Some(scheme) => unsafe {
+                SHAssocEnumHandlersForProtocolByApplication(&HSTRING::from(scheme))?
+            },
+            None => {
+                let extension = Path::new(target)
+                    .extension()
+                    .map(|e| format!(".{}", e.to_string_lossy()))
+                    .unwrap_or_else(|| "*".into());
+                unsafe { SHAssocEnumHandlers(&HSTRING::from(extension), ASSOC_FILTER_RECOMMENDED)? }
+            }
+        };
+
+        let mut handlers = Vec::new();
+        loop {
+            let mut handler = [None];
+            let mut fetched = 0;
+            unsafe {
+                // `S_FALSE` once the enumeration is done
+                enumerator.Next(&mut handler, Some(&mut fetched)).ok()?;
+            }
+            match handler {
+                [Some(handler)] if fetched == 1 => handlers.push(handler),
+                _ => break,
+            }
+        }
+        Ok(handlers)
+    }
+
+    pub fn get_apps(target: &str) -> crate::Result<Vec<App>> {
+        let apps = handlers(target)?
+            .into_iter()
+            .filter_map(|handler| unsafe {
+                let id = take_string(handler.GetName().ok()?)?;
+                let name = handler
+                    .GetUIName()
+                    .ok()
+                    .and_then(|name| take_string(name))
+                    .unwrap_or_else(|| id.clone());
+
+                let mut icon_path = PWSTR::null();
+                let mut icon_index = 0;
+                let icon = handler
+                    .GetIconLocation(&mut icon_path, &mut icon_index)
+                    .ok()
+                    .and_then(|_| take_string(icon_path))
+                    .map(|path| format!("{path},{icon_index}"));
+
+                Some(App { id, name, icon })
+            })
+            .collect();
+        Ok(apps)
+    }
+
+    fn assoc_query_string(flags: ASSOCF, kind: ASSOCSTR, assoc: &HSTRING) -> Option<String> {
+        let mut len = 0;
+        unsafe {
+            AssocQueryStringW(flags, kind, assoc, PCWSTR::null(), PWSTR::null(), &mut len)
+                .ok()
+                .ok()?;
+            let mut buf = vec![0u16; len as usize];
+            AssocQueryStringW(
+                flags,
+                kind,
+                assoc,
+                PCWSTR::null(),
+                PWSTR(buf.as_mut_ptr()),
+                &mut len,
+            )
+            .ok()
+            .ok()?;
+            // `len` includes the null terminator
+            buf.truncate(len.saturating_sub(1) as usize);
+            String::from_utf16(&buf).ok()
+        }
+    }
+
+    pub fn get_default_app(kind: HandlerKind) -> crate::Result<Option<App>> {
+        let (flags, assoc) = match kind {
+            HandlerKind::Extension(extension) => (
+                ASSOCF_INIT_IGNOREUNKNOWN,
+                HSTRING::from(format!(".{extension}")),
+            ),
+            HandlerKind::Scheme(scheme) => (
+                ASSOCF_INIT_IGNOREUNKNOWN | ASSOCF_IS_PROTOCOL,
+                HSTRING::from(scheme),
+            ),
+        };
+
+        let Some(id) = assoc_query_string(flags, ASSOCSTR_EXECUTABLE, &assoc) else {
+            return Ok(None);
+        };
+        let name = assoc_query_string(flags, ASSOCSTR_FRIENDLYAPPNAME, &assoc)
+            .unwrap_or_else(|| id.clone());
+        Ok(Some(App {
+            icon: Some(format!("{id},0")),
+            id,
+            name,
+        }))
+    }
+
+    pub fn open_with(path: &Path, app_id: &str) -> crate::Result<()> {
+        let target = path.to_string_lossy();
+        let handler = handlers(&target)?
+            .into_iter()
+            .find(|handler| unsafe {
+                handler
+                    .GetName()
+                    .ok()
+                    .and_then(|name| take_string(name))
+                    .is_some_and(|name| name == app_id)
+            })
+            .ok_or_else(|| crate::Error::UnknownApplication(app_id.into()))?;
+
+        unsafe {
+            let item: IShellItem = SHCreateItemFromParsingName(&HSTRING::from(path), None)?;
+            let data: IDataObject = item.BindToHandler(None, &BHID_DataObject)?;
+            handler.Invoke(&data)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod imp {
+    use std::{
+        collections::HashMap,
+        path::PathBuf,
+        process::{Command, Stdio},
+    };
+
+    use super::*;
+
+    /// The `[Desktop Entry]` group of a desktop file.
+    struct DesktopEntry {
+        id: String,
+        keys: HashMap<String, String>,
+    }
+
+    impl DesktopEntry {
+        fn parse(id: String, content: &str) -> Option<Self> {
+            let mut keys = HashMap::new();
+            let mut in_entry = false;
+            for line in content.lines().map(str::trim) {
+                if line.starts_with('[') {
+                    in_entry = line == "[Desktop Entry]";
+                } else if in_entry && !line.starts_with('#') {
+                    if let Some((key, value)) = line.split_once('=') {
+                        keys.insert(key.trim().to_string(), value.trim().to_string());
+                    }
+                }
+            }
+
+            let entry = Self { id, keys };
+            let is_app = entry.get("Type") == Some("Application");
+            let hidden = entry.get("Hidden") == Some("true");
+            (is_app && !hidden && entry.get("Exec").is_some()).then_some(entry)
+        }
+
+        fn get(&self, key: &str) -> Option<&str> {
+            self.keys.get(key).map(String::as_str)
+        }
+
+        fn mime_types(&self) -> impl Iterator<Item = &str> {
+            self.get("MimeType")
+                .unwrap_or_default()
+                .split(';')
+                .filter(|m| !m.is_empty())
+        }
+
+        fn to_app(&self) -> App {
+            App {
+                id: self.id.clone(),
+                name: self.get("Name").unwrap_or(&self.id).to_string(),
+                icon: self.get("Icon").map(Into::into),
+            }
+        }
+    }
+
+    /// The XDG data directories, by precedence.
+    fn data_dirs() -> Vec<PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")));
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+
+        data_home
+            .into_iter()
+            .chain(data_dirs.split(':').map(PathBuf::from))
+            .collect()
+    }
+
+    /// The `applications` directories of the XDG data directories, by precedence.
+    fn application_dirs() -> Vec<PathBuf> {
+        data_dirs()
+            .into_iter()
+            .map(|dir| dir.join("applications"))
+            .collect()
+    }
+
+    /// Looks up the MIME type of the extension in the `mime/globs2` files of the shared MIME-info database.
+    fn extension_mime_type(extension: &str) -> Option<String> {
+        let glob = format!("*.{}", extension.to_lowercase());
+        let mut best: Option<(u32, String)> = None;
+        for dir in data_dirs() {
+            let Ok(globs) = std::fs::read_to_string(dir.join("mime/globs2")) else {
+                continue;
+            };
+            // lines are `weight:mime:glob[:flags]`
+            for line in globs.lines().filter(|line| !line.starts_with('#')) {
+                let mut fields = line.split(':');
+                let (Some(weight), Some(mime), Some(pattern)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                let weight = weight.parse().unwrap_or(50);
+                if pattern.to_lowercase() == glob
+                    && best.as_ref().map_or(true, |(best, _)| weight > *best)
+                {
+                    best = Some((weight, mime.to_string()));
+                }
+            }
+        }
+        best.map(|(_, mime)| mime)
+    }
+
+    /// Collects the desktop files of the directory, their ID is the path relative to the `applications` directory with `/` replaced by `-`.
+    fn collect_entries(dir: &Path, prefix: &str, entries: &mut Vec<DesktopEntry>) {
+        let Ok(read_dir) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for file in read_dir.flatten() {
+            let path = file.path();
+            let name = file.file_name().to_string_lossy().into_owned();
+            if path.is_dir() {
+                collect_entries(&path, &format!("{prefix}{name}-"), entries);
+            } else if name.ends_with(".desktop") {
+                let id = format!("{prefix}{name}");
+                // entries of the directories with a higher precedence shadow the others
+                if entries.iter().any(|e| e.id == id) {
+                    continue;
+                }
+                if let Some(entry) = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| DesktopEntry::parse(id, &content))
+                {
+                    entries.push(entry);
+                }
+            }
+        }
+    }
+
+    fn desktop_entries() -> Vec<DesktopEntry> {
+        let mut entries = Vec::new();
+        for dir in application_dirs() {
+            collect_entries(&dir, "", &mut entries);
+        }
+        entries
+    }
+
+    fn xdg_mime(args: &[&str]) -> Option<String> {
+        let output = Command::new("xdg-mime").args(args).output().ok()?;
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    }
+
+    fn mime_type(target: &str) -> String {
+        match url_scheme(target) {
+            Some(scheme) => format!("x-scheme-handler/{}", scheme.to_ascii_lowercase()),
+            None => xdg_mime(&["query", "filetype", target])
+                .unwrap_or_else(|| "application/octet-stream".into()),
+        }
+    }
+
+    pub fn get_apps(target: &str) -> crate::Result<Vec<App>> {
+        let mime = mime_type(target);
+        let default = xdg_mime(&["query", "default", &mime]);
+
+        let mut apps: Vec<App> = desktop_entries()
+            .iter()
+            .filter(|entry| entry.mime_types().any(|m| m == mime))
+            .map(DesktopEntry::to_app)
+            .collect();
+        if let Some(default) = default {
+            apps.sort_by_key(|app| app.id != default);
+        }
+        Ok(apps)
+    }
+
+    pub fn get_default_app(kind: HandlerKind) -> crate::Result<Option<App>> {
+        let mime = match kind {
+            HandlerKind::Extension(extension) => match extension_mime_type(extension) {
+                Some(mime) => mime,
+                None => return Ok(None),
+            },
+            HandlerKind::Scheme(scheme) => {
+                format!("x-scheme-handler/{}", scheme.to_ascii_lowercase())
+            }
+        };
+        let Some(default) = xdg_mime(&["query", "default", &mime]) else {
+            return Ok(None);
+        };
+
+        let app = desktop_entries()
+            .iter()
+            .find(|entry| entry.id == default)
+            .map(DesktopEntry::to_app);
+        Ok(app)
+    }
+
+    /// Splits the `Exec` key of a desktop entry into arguments, following its quoting rules.
+    fn split_exec(exec: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut current: Option<String> = None;
+        let mut quoted = false;
+        let mut chars = exec.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    quoted = !quoted;
+                    current.get_or_insert_with(String::new);
+                }
+                '\\' if quoted => {
+                    if let Some(c) = chars.next() {
+                        current.get_or_insert_with(String::new).push(c);
+                    }
+                }
+                c if c.is_whitespace() && !quoted => {
+                    if let Some(arg) = current.take() {
+                        args.push(arg);
+                    }
+                }
+                c => current.get_or_insert_with(String::new).push(c),
+            }
+        }
+        args.extend(current);
+        args
+    }
+
+    /// Expands the field codes of the `Exec` key for a single file.
+    fn expand_exec(entry: &DesktopEntry, exec: &str, path: &Path) -> crate::Result<Vec<String>> {
+        let target = path.to_string_lossy().into_owned();
+        let uri = if url_scheme(&target).is_some() {
+            target.clone()
+        } else {
+            let absolute = std::env::current_dir()?.join(path);
+            url::Url::from_file_path(&absolute)
+                .map_err(|_| crate::Error::FailedToConvertPathToFileUrl)?
+                .to_string()
+        };
+
+        let mut args = Vec::new();
+        let mut has_target = false;
+        for arg in split_exec(exec) {
+            match arg.as_str() {
+                "%f" | "%F" => {
+                    args.push(target.clone());
+                    has_target = true;
+                }
+                "%u" | "%U" => {
+                    args.push(uri.clone());
+                    has_target = true;
+                }
+                "%i" => {
+                    if let Some(icon) = entry.get("Icon") {
+                        args.push("--icon".into());
+                        args.push(icon.into());
+                    }
+                }
+                _ => {
+                    let mut expanded = String::new();
+                    let mut chars = arg.chars();
+                    while let Some(c) = chars.next() {
+                        match (c, chars.clone().next()) {
+                            ('%', Some(code)) => {
+                                chars.next();
+                                match code {
+                                    '%' => expanded.push('%'),
+                                    'c' => expanded.push_str(entry.get("Name").unwrap_or_default()),
+                                    // deprecated and unknown field codes are removed
+                                    _ => {}
+                                }
+                            }
+                            (c, _) => expanded.push(c),
+                        }
+                    }
+                    if !expanded.is_empty() {
+                        args.push(expanded);
+                    }
+                }
+            }
+        }
+
+        // apps not declaring a field code still get the target
+        if !has_target {
+            args.push(target);
+        }
+        Ok(args)
+    }
+
+    pub fn open_with(path: &Path, app_id: &str) -> crate::Result<()> {
+        let entries = desktop_entries();
+        let entry = entries
+            .iter()
+            .find(|entry| entry.id == app_id)
+            .ok_or_else(|| crate::Error::UnknownApplication(app_id.into()))?;
+
+        let args = expand_exec(entry, entry.get("Exec").unwrap_or_default(), path)?;
+        let (program, args) = args
+            .split_first()
+            .ok_or_else(|| crate::Error::UnknownApplication(app_id.into()))?;
+
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(dir) = entry.get("Path").filter(|dir| !dir.is_empty()) {
+            command.current_dir(dir);
+        }
+        let mut child = command.spawn()?;
+        // reap the process once it exits
+        std::thread::spawn(move || child.wait());
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::process::Command;
+
+    use super::*;
+    use objc2_app_kit::NSWorkspace;
+    use objc2_foundation::{NSBundle, NSFileManager, NSObjectProtocol, NSString, NSURL};
+
+    pub fn get_apps(target: &str) -> crate::Result<Vec<App>> {
+        unsafe {
+            let url = match url_scheme(target) {
+                Some(_) => NSURL::URLWithString(&NSString::from_str(target)),
+                None => Some(NSURL::fileURLWithPath(&NSString::from_str(target))),
+            }
+            .ok_or(crate::Error::FailedToConvertPathToFileUrl)?;
+
+            let workspace = NSWorkspace::sharedWorkspace();
+            let default = workspace.URLForApplicationToOpenURL(&url);
+            let mut urls = workspace
+                .URLsForApplicationsToOpenURL(&url)
+                .to_vec_retained();
+            if let Some(default) = default {
+                urls.sort_by_key(|url| !url.isEqual(&default));
+            }
+
+            Ok(urls.iter().filter_map(|url| app(url)).collect())
+        }
+    }
+
+    /// The application of the bundle URL.
+    fn app(url: &NSURL) -> Option<App> {
+        unsafe {
+            let path = url.path()?;
+            let id = NSBundle::bundleWithURL(url)
+                .and_then(|bundle| bundle.bundleIdentifier())
+                .unwrap_or_else(|| path.clone());
+            let name = NSFileManager::defaultManager()
+                .displayNameAtPath(&path)
+                .to_string();
+            Some(App {
+                id: id.to_string(),
+                name: name.trim_end_matches(".app").to_string(),
+                icon: None,
+            })
+        }
+    }
+
+    pub fn get_default_app(kind: HandlerKind) -> crate::Result<Option<App>> {
+        let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+        match kind {
+            HandlerKind::Extension(extension) => {
+                // Launch Services needs an existing file to resolve its type
+                let path = std::env::temp_dir().join(format!("tauri-plugin-opener.{extension}"));
+                std::fs::write(&path, [])?;
+                let url =
+                    unsafe { NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy())) };
+                let app = unsafe { workspace.URLForApplicationToOpenURL(&url) };
+                let _ = std::fs::remove_file(&path);
+                Ok(app.and_then(|app| self::app(&app)))
+            }
+            HandlerKind::Scheme(scheme) => {
+                let url =
+                    unsafe { NSURL::URLWithString(&NSString::from_str(&format!("{scheme}:"))) }
+                        .ok_or(crate::Error::FailedToConvertPathToFileUrl)?;
+                let app = unsafe { workspace.URLForApplicationToOpenURL(&url) };
+                Ok(app.and_then(|app| self::app(&app)))
+            }
+        }
+    }
+
+    pub fn open_with(path: &Path, app_id: &str) -> crate::Result<()> {
+        // the ID is the path of the bundle when it has no identifier
+        let flag = if app_id.starts_with('/') { "-a" } else { "-b" };
+        let status = Command::new("open")
+            .arg(flag)
+            .arg(app_id)
+            .arg(path)
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(crate::Error::UnknownApplication(app_id.into()))
+        }
+    }
+}
@@ -2,15 +2,64 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// A path revealed by [`reveal_items_in_dir`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevealedItem {
+    /// The path that was requested.
+    pub path: PathBuf,
+    /// The path selected in the file manager, which is the nearest existing parent if [`Self::exists`] is `false`.
+    pub revealed: PathBuf,
+    /// Whether the requested path exists.
+    pub exists: bool,
+}
 
 /// Reveal a path the system's default explorer.
 ///
+/// If the path does not exist, its nearest existing parent is revealed instead.
+///
 /// ## Platform-specific:
 ///
 /// - **Android / iOS:** Unsupported.
 pub fn reveal_item_in_dir<P: AsRef<Path>>(path: P) -> crate::Result<()> {
-    let path = path.as_ref().canonicalize()?;
+    reveal_items_in_dir([path]).map(|_| ())
+}
+
+/// Reveal multiple paths in the system's default explorer,
+/// selecting them all in one window where the file manager supports it.
+///
+/// Paths that do not exist are replaced by their nearest existing parent,
+/// the returned items describe what was revealed for each requested path.
+///
+/// ## Platform-specific:
+///
+/// - **Windows:** Opens one window per parent directory.
+/// - **Linux:** Opens one window per path if the file manager does not implement `org.freedesktop.FileManager1`.
+/// - **Android / iOS:** Unsupported.
+pub fn reveal_items_in_dir<I, P>(paths: I) -> crate::Result<Vec<RevealedItem>>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    let items = paths
+        .into_iter()
+        .map(|path| resolve(path.as_ref()))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let mut revealed: Vec<PathBuf> = Vec::with_capacity(items.len());
+    for item in &items {
+        if !revealed.contains(&item.revealed) {
+            revealed.push(item.revealed.clone());
+        }
+    }
+
+    if revealed.is_empty() {
+        return Ok(items);
+    }
 
     #[cfg(any(
         windows,
@@ -21,7 +70,7 @@ pub fn reveal_item_in_dir<P: AsRef<Path>>(path: P) -> crate::Result<()> {
         target_os = "netbsd",
         target_os = "openbsd"
     ))]
-    return imp::reveal_item_in_dir(&path);
+    return imp::reveal_items_in_dir(&revealed).map(|_| items);
 
     #[cfg(not(any(
         windows,
@@ -35,6 +84,42 @@ pub fn reveal_item_in_dir<P: AsRef<Path>>(path: P) -> crate::Result<()> {
     Err(crate::Error::UnsupportedPlatform)
 }
 
+/// Canonicalizes the path, or its nearest existing parent if it does not exist.
+fn resolve(path: &Path) -> crate::Result<RevealedItem> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut error = match absolute.canonicalize() {
+        Ok(revealed) => {
+            return Ok(RevealedItem {
+                path: path.to_path_buf(),
+                revealed,
+                exists: true,
+            })
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => e,
+        Err(e) => return Err(e.into()),
+    };
+
+    for parent in absolute.ancestors().skip(1) {
+        match parent.canonicalize() {
+            Ok(revealed) => {
+                return Ok(RevealedItem {
+                    path: path.to_path_buf(),
+                    revealed,
+                    exists: false,
+                })
+            }
+            Err(e) => error = e,
+        }
+    }
+
+    Err(error.into())
+}
+
 #[cfg(windows)]
 mod imp {
     use super::*;
@@ -46,41 +131,68 @@ mod imp {
             System::Com::CoInitialize,
             UI::{
                 Shell::{
-                    ILCreateFromPathW, ILFree, SHOpenFolderAndSelectItems, ShellExecuteExW,
-                    SHELLEXECUTEINFOW,
+                    Common::ITEMIDLIST, ILCreateFromPathW, ILFree, SHOpenFolderAndSelectItems,
+                    ShellExecuteExW, SHELLEXECUTEINFOW,
                 },
                 WindowsAndMessaging::SW_SHOWNORMAL,
             },
         },
     };
 
-    pub fn reveal_item_in_dir(path: &Path) -> crate::Result<()> {
-        let file = dunce::simplified(path);
-
+    pub fn reveal_items_in_dir(paths: &[PathBuf]) -> crate::Result<()> {
         let _ = unsafe { CoInitialize(None) };
 
-        let dir = file
-            .parent()
-            .ok_or_else(|| crate::Error::NoParent(file.to_path_buf()))?;
+        // `SHOpenFolderAndSelectItems` selects items of a single folder
+        let mut dirs: Vec<(&Path, Vec<&Path>)> = Vec::new();
+        for path in paths {
+            let file = dunce::simplified(path);
+            let dir = file
+                .parent()
+                .ok_or_else(|| crate::Error::NoParent(file.to_path_buf()))?;
+            match dirs.iter_mut().find(|(d, _)| *d == dir) {
+                Some((_, files)) => files.push(file),
+                None => dirs.push((dir, vec![file])),
+            }
+        }
+
+        for (dir, files) in dirs {
+            reveal_in_dir(dir, &files)?;
+        }
+
+        Ok(())
+    }
+
+    fn reveal_in_dir(dir: &Path, files: &[&Path]) -> crate::Result<()> {
+        let dir_h = HSTRING::from(dir);
+        let dir_item = unsafe { ILCreateFromPathW(&dir_h) };
 
-        let dir = HSTRING::from(dir);
-        let dir_item = unsafe { ILCreateFromPathW(&dir) };
+        let file_items: Vec<*const ITEMIDLIST> = files
+            .iter()
+            .map(|file| {
+                let file_h = HSTRING::from(*file);
+                unsafe { ILCreateFromPathW(&file_h) as *const _ }
+            })
+            .collect();
 
-        let file_h = HSTRING::from(file);
-        let file_item = unsafe { ILCreateFromPathW(&file_h) };
+        let free = || unsafe {
+            ILFree(Some(dir_item));
+            for item in &file_items {
+                ILFree(Some(*item));
+            }
+        };
 
         unsafe {
-            if let Err(e) = SHOpenFolderAndSelectItems(dir_item, Some(&[file_item]), 0) {
+            if let Err(e) = SHOpenFolderAndSelectItems(dir_item, Some(&file_items), 0) {
                 // from https://github.com/electron/electron/blob/10d967028af2e72382d16b7e2025d243b9e204ae/shell/common/platform_util_win.cc#L302
                 // On some systems, the above call mysteriously fails with "file not
                 // found" even though the file is there.  In these cases, ShellExecute()
                 // seems to work as a fallback (although it won't select the file).
                 if e.code().0 == ERROR_FILE_NOT_FOUND.0 as i32 {
-                    let is_dir = file.is_dir();
+                    let is_dir = files.len() == 1 && files[0].is_dir();
                     let mut info = SHELLEXECUTEINFOW {
                         cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as _,
                         nShow: SW_SHOWNORMAL.0,
-                        lpFile: PCWSTR(dir.as_ptr()),
+                        lpFile: PCWSTR(dir_h.as_ptr()),
                         lpClass: if is_dir { w!("folder") } else { PCWSTR::null() },
                         lpVerb: if is_dir {
                             w!("explore")
@@ -90,18 +202,12 @@ mod imp {
                         ..std::mem::zeroed()
                     };
 
-                    ShellExecuteExW(&mut info).inspect_err(|_| {
-                        ILFree(Some(dir_item));
-                        ILFree(Some(file_item));
-                    })?;
+                    ShellExecuteExW(&mut info).inspect_err(|_| free())?;
                 }
             }
         }
 
-        unsafe {
-            ILFree(Some(dir_item));
-            ILFree(Some(file_item));
-        }
+        free();
 
         Ok(())
     }
@@ -120,19 +226,27 @@ mod imp {
 
     use super::*;
 
-    pub fn reveal_item_in_dir(path: &Path) -> crate::Result<()> {
+    pub fn reveal_items_in_dir(paths: &[PathBuf]) -> crate::Result<()> {
         let connection = zbus::blocking::Connection::session()?;
 
-        reveal_with_filemanager1(path, &connection)
-            .or_else(|_| reveal_with_open_uri_portal(path, &connection))
+        reveal_with_filemanager1(paths, &connection).or_else(|_| {
+            paths
+                .iter()
+                .try_for_each(|path| reveal_with_open_uri_portal(path, &connection))
+        })
     }
 
     fn reveal_with_filemanager1(
-        path: &Path,
+        paths: &[PathBuf],
         connection: &zbus::blocking::Connection,
     ) -> crate::Result<()> {
-        let uri = url::Url::from_file_path(path)
-            .map_err(|_| crate::Error::FailedToConvertPathToFileUrl)?;
+        let uris = paths
+            .iter()
+            .map(|path| {
+                url::Url::from_file_path(path)
+                    .map_err(|_| crate::Error::FailedToConvertPathToFileUrl)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
 
         #[zbus::proxy(
             interface = "org.freedesktop.FileManager1",
@@ -145,7 +259,7 @@ mod imp {
 
         let proxy = FileManager1ProxyBlocking::new(connection)?;
 
-        proxy.ShowItems(vec![uri.as_str()], "")
+        proxy.ShowItems(uris.iter().map(|uri| uri.as_str()).collect(), "")
     }
 
     fn reveal_with_open_uri_portal(
@@ -180,11 +294,15 @@ mod imp {
     use super::*;
     use objc2_app_kit::NSWorkspace;
     use objc2_foundation::{NSArray, NSString, NSURL};
-    pub fn reveal_item_in_dir(path: &Path) -> crate::Result<()> {
+    pub fn reveal_items_in_dir(paths: &[PathBuf]) -> crate::Result<()> {
         unsafe {
-            let path = path.to_string_lossy();
-            let path = NSString::from_str(&path);
-            let urls = vec![NSURL::fileURLWithPath(&path)];
+            let urls = paths
+                .iter()
+                .map(|path| {
+                    let path = NSString::from_str(&path.to_string_lossy());
+                    NSURL::fileURLWithPath(&path)
+                })
+                .collect::<Vec<_>>();
             let urls = NSArray::from_vec(urls);
 
             let workspace = NSWorkspace::new();
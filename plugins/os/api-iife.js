@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_OS__=function(_){"use strict";async function n(_,n={},o){return window.__TAURI_INTERNALS__.invoke(_,n,o)}return"function"==typeof SuppressedError&&SuppressedError,_.arch=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.arch},_.eol=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.eol},_.exeExtension=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.exe_extension},_.family=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.family},_.hostname=async function(){return await n("plugin:os|hostname")},_.locale=async function(){return await n("plugin:os|locale")},_.platform=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.platform},_.type=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.os_type},_.version=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.version},_}({});Object.defineProperty(window.__TAURI__,"os",{value:__TAURI_PLUGIN_OS__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_OS__=function(_){"use strict";function e(_,n=!1){return window.__TAURI_INTERNALS__.transformCallback(_,n)}async function n(_,n={},o){return window.__TAURI_INTERNALS__.invoke(_,n,o)}async function t(_,o){const r={kind:"Any"};return n("plugin:event|listen",{event:_,target:r,handler:e(o)}).then((o=>async()=>async function(_,o){await n("plugin:event|unlisten",{event:_,eventId:o})}(_,o)))}"function"==typeof SuppressedError&&SuppressedError;class r{constructor(_){this.rid=_}async close(){return n("plugin:resources|close",{rid:this.rid})}}class i extends r{}return _.SleepInhibitor=i,_.appearance=async function(){return await n("plugin:os|appearance")},_.arch=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.arch},_.connectivity=async function(){return await n("plugin:os|connectivity")},_.eol=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.eol},_.exeExtension=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.exe_extension},_.family=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.family},_.hostname=async function(){return await n("plugin:os|hostname")},_.idleTime=async function(){return await n("plugin:os|idle_time")},_.inhibitSleep=async function(_,o){const r=await n("plugin:os|inhibit_sleep",{reason:_,options:o});return new i(r)},_.locale=async function(){return await n("plugin:os|locale")},_.networkInterfaces=async function(){return await n("plugin:os|network_interfaces")},_.onAppearanceChanged=async function(_){return await t("os://appearance-changed",(n=>{_(n.payload)}))},_.onConnectivityChanged=async function(_){return await t("os://connectivity-changed",(n=>{_(n.payload)}))},_.onPowerChanged=async function(_){return await t("os://power-changed",(n=>{_(n.payload)}))},_.onSessionEvent=async function(_){return await t("os://session",(n=>{_(n.payload)}))},_.platform=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.platform},_.powerStatus=async function(){return await n("plugin:os|power_status")},_.type=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.os_type},_.version=function(){return window.__TAURI_OS_PLUGIN_INTERNALS__.version},_}({});Object.defineProperty(window.__TAURI__,"os",{value:__TAURI_PLUGIN_OS__})}
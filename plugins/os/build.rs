@@ -11,6 +11,12 @@ const COMMANDS: &[&str] = &[
     "exe_extension",
     "locale",
     "hostname",
+    "appearance",
+    "network_interfaces",
+    "connectivity",
+    "power_status",
+    "inhibit_sleep",
+    "idle_time",
 ];
 
 fn main() {
@@ -8,7 +8,8 @@
  * @module
  */
 
-import { invoke } from '@tauri-apps/api/core'
+import { invoke, Resource } from '@tauri-apps/api/core'
+import { type UnlistenFn, listen } from '@tauri-apps/api/event'
 
 /** @ignore */
 declare global {
@@ -182,6 +183,309 @@ async function hostname(): Promise<string | null> {
   return await invoke('plugin:os|hostname')
 }
 
+/**
+ * The system appearance and accessibility preferences.
+ *
+ * @since 2.3.0
+ */
+interface Appearance {
+  theme: 'light' | 'dark'
+  /** The accent color as a `#rrggbb` hex string, if the system has one. */
+  accentColor: string | null
+  /** Whether the user prefers reduced motion. */
+  reducedMotion: boolean
+  /** Whether the user prefers high contrast. */
+  highContrast: boolean
+}
+
+/**
+ * Returns the system theme, accent color, reduced motion and high contrast preferences.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Read from the XDG desktop portal, the reduced motion preference is read from the GNOME settings.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { appearance } from '@tauri-apps/plugin-os';
+ * const { theme, accentColor } = await appearance();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function appearance(): Promise<Appearance> {
+  return await invoke('plugin:os|appearance')
+}
+
+/**
+ * Listens to the changes of the system {@link appearance}.
+ *
+ * @since 2.3.0
+ */
+async function onAppearanceChanged(
+  handler: (appearance: Appearance) => void
+): Promise<UnlistenFn> {
+  return await listen<Appearance>('os://appearance-changed', (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * A network interface of the system.
+ *
+ * @since 2.3.0
+ */
+interface NetworkInterface {
+  /** The interface name, or its friendly name on Windows. */
+  name: string
+  /** The IPv4 and IPv6 addresses assigned to the interface. */
+  addresses: string[]
+  /** Whether the interface is up and running. */
+  up: boolean
+  /** Whether this is a loopback interface. */
+  loopback: boolean
+  /** The SSID of the Wi-Fi network the interface is connected to, if known and permitted. */
+  wifiSsid: string | null
+}
+
+/**
+ * The network connectivity of the system.
+ *
+ * @since 2.3.0
+ */
+interface Connectivity {
+  /** Whether the system can reach the internet. */
+  online: boolean
+  /** Whether the connection is metered, i.e. the user may be charged for the data usage. */
+  metered: boolean
+}
+
+/**
+ * Returns the network interfaces of the system.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** The Wi-Fi SSID is read from NetworkManager.
+ * - **macOS:** The Wi-Fi SSID requires the location permission.
+ * - **Windows:** The Wi-Fi SSID requires the location permission on Windows 11 24H2 and later.
+ * - **Android / iOS:** The Wi-Fi SSID is not available.
+ *
+ * @example
+ * ```typescript
+ * import { networkInterfaces } from '@tauri-apps/plugin-os';
+ * const interfaces = await networkInterfaces();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function networkInterfaces(): Promise<NetworkInterface[]> {
+  return await invoke('plugin:os|network_interfaces')
+}
+
+/**
+ * Returns the network connectivity of the system,
+ * a more reliable alternative to `navigator.onLine` in the webview.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Read from the network monitor of the XDG desktop portal,
+ *   falling back to the network interfaces when it is unavailable.
+ * - **macOS / Android / iOS:** Guessed from the network interfaces, `metered` is always `false`.
+ *
+ * @example
+ * ```typescript
+ * import { connectivity } from '@tauri-apps/plugin-os';
+ * const { online, metered } = await connectivity();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function connectivity(): Promise<Connectivity> {
+  return await invoke('plugin:os|connectivity')
+}
+
+/**
+ * Listens to the changes of the network {@link connectivity}.
+ *
+ * @since 2.3.0
+ */
+async function onConnectivityChanged(
+  handler: (connectivity: Connectivity) => void
+): Promise<UnlistenFn> {
+  return await listen<Connectivity>('os://connectivity-changed', (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * The source the system draws its power from.
+ *
+ * @since 2.3.0
+ */
+type PowerSource = 'ac' | 'battery' | 'unknown'
+
+/**
+ * The state of the system battery.
+ *
+ * @since 2.3.0
+ */
+interface Battery {
+  /** The charge level, in percent. */
+  level: number
+  /** Whether the battery is charging. */
+  charging: boolean
+}
+
+/**
+ * The power status of the system.
+ *
+ * @since 2.3.0
+ */
+interface PowerStatus {
+  source: PowerSource
+  /** The battery state, `null` if the system has no battery. */
+  battery: Battery | null
+}
+
+/**
+ * Returns the power source and battery state of the system.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Read from `/sys/class/power_supply`, the levels of multiple batteries are averaged.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { powerStatus } from '@tauri-apps/plugin-os';
+ * const { source, battery } = await powerStatus();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function powerStatus(): Promise<PowerStatus> {
+  return await invoke('plugin:os|power_status')
+}
+
+/**
+ * Listens to the changes of the {@link powerStatus}.
+ *
+ * @since 2.3.0
+ */
+async function onPowerChanged(
+  handler: (status: PowerStatus) => void
+): Promise<UnlistenFn> {
+  return await listen<PowerStatus>('os://power-changed', (event) => {
+    handler(event.payload)
+  })
+}
+
+/**
+ * @since 2.3.0
+ */
+interface InhibitSleepOptions {
+  /** Keeps the display from turning off as well, for media playback. */
+  keepDisplayOn?: boolean
+}
+
+/**
+ * Keeps the system from sleeping until it is closed, see {@link inhibitSleep}.
+ *
+ * @since 2.3.0
+ */
+class SleepInhibitor extends Resource {}
+
+/**
+ * Prevents the system from sleeping when idle until the returned inhibitor is closed,
+ * or until the webview that created it is destroyed.
+ *
+ * This does not prevent the user from putting the system to sleep explicitly.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Uses the `idle` inhibitor of systemd-logind,
+ *   and the `org.freedesktop.ScreenSaver` interface to keep the display on.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { inhibitSleep } from '@tauri-apps/plugin-os';
+ * const inhibitor = await inhibitSleep('Exporting the video');
+ * await exportVideo();
+ * await inhibitor.close();
+ * ```
+ *
+ * @param reason The reason displayed to the user by the system, if any.
+ *
+ * @since 2.3.0
+ */
+async function inhibitSleep(
+  reason: string,
+  options?: InhibitSleepOptions
+): Promise<SleepInhibitor> {
+  const rid = await invoke<number>('plugin:os|inhibit_sleep', {
+    reason,
+    options
+  })
+  return new SleepInhibitor(rid)
+}
+
+/**
+ * Returns the seconds since the last user input.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Read from the idle monitor of GNOME, or from the `org.freedesktop.ScreenSaver` interface.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { idleTime } from '@tauri-apps/plugin-os';
+ * const away = (await idleTime()) > 5 * 60;
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function idleTime(): Promise<number> {
+  return await invoke('plugin:os|idle_time')
+}
+
+/**
+ * A change of the user session or of the system sleep state.
+ *
+ * @since 2.3.0
+ */
+type SessionEvent = 'lock' | 'unlock' | 'suspend' | 'resume'
+
+/**
+ * Listens to the session being locked or unlocked, and to the system suspending or resuming.
+ *
+ * #### Platform-specific
+ *
+ * - **Linux:** Requires systemd-logind, the lock state is reported by the desktop environment.
+ * - **Android / iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { onSessionEvent } from '@tauri-apps/plugin-os';
+ * await onSessionEvent((event) => {
+ *   if (event === 'lock' || event === 'suspend') {
+ *     setPresence('away');
+ *   }
+ * });
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function onSessionEvent(
+  handler: (event: SessionEvent) => void
+): Promise<UnlistenFn> {
+  return await listen<SessionEvent>('os://session', (event) => {
+    handler(event.payload)
+  })
+}
+
 export {
   eol,
   platform,
@@ -191,6 +495,30 @@ export {
   arch,
   locale,
   exeExtension,
-  hostname
+  hostname,
+  appearance,
+  onAppearanceChanged,
+  networkInterfaces,
+  connectivity,
+  onConnectivityChanged,
+  powerStatus,
+  onPowerChanged,
+  inhibitSleep,
+  SleepInhibitor,
+  idleTime,
+  onSessionEvent
+}
+export type {
+  Platform,
+  OsType,
+  Arch,
+  Family,
+  Appearance,
+  NetworkInterface,
+  Connectivity,
+  PowerSource,
+  Battery,
+  PowerStatus,
+  InhibitSleepOptions,
+  SessionEvent
 }
-export type { Platform, OsType, Arch, Family }
@@ -0,0 +1,302 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+/// Emitted with the [`Appearance`] when it changes.
+pub const APPEARANCE_CHANGED_EVENT: &str = "os://appearance-changed";
+
+/// The system color scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+/// The system appearance and accessibility preferences.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Appearance {
+    pub theme: Theme,
+    /// The accent color as a `#rrggbb` hex string, if the system has one.
+    pub accent_color: Option<String>,
+    /// Whether the user prefers reduced motion.
+    pub reduced_motion: bool,
+    /// Whether the user prefers high contrast.
+    pub high_contrast: bool,
+}
+
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn hex(r: u8, g: u8, b: u8) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Returns the system appearance and accessibility preferences.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Read from the `org.freedesktop.appearance` settings of the XDG desktop portal,
+///   and from the GNOME settings for the reduced motion preference.
+/// - **Android / iOS:** Unsupported.
+pub fn appearance() -> crate::Result<Appearance> {
+    imp::appearance()
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::{ffi::c_void, mem::size_of};
+
+    use windows_sys::Win32::{
+        System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD},
+        UI::{
+            Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+            WindowsAndMessaging::{
+                SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+            },
+        },
+    };
+
+    use super::*;
+
+    fn wide(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    fn read_dword(key: &str, value: &str) -> Option<u32> {
+        let mut data = 0u32;
+        let mut size = size_of::<u32>() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                wide(key).as_ptr(),
+                wide(value).as_ptr(),
+                RRF_RT_REG_DWORD,
+                std::ptr::null_mut(),
+                &mut data as *mut u32 as *mut c_void,
+                &mut size,
+            )
+        };
+        (status == 0).then_some(data)
+    }
+
+    pub fn appearance() -> crate::Result<Appearance> {
+        let light = read_dword(
+            r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+            "AppsUseLightTheme",
+        );
+        let theme = match light {
+            Some(0) => Theme::Dark,
+            _ => Theme::Light,
+        };
+
+        // stored as 0xAABBGGRR
+        let accent_color = read_dword(r"Software\Microsoft\Windows\DWM", "AccentColor")
+            .map(|color| hex(color as u8, (color >> 8) as u8, (color >> 16) as u8));
+
+        let mut high_contrast = HIGHCONTRASTW {
+            cbSize: size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: std::ptr::null_mut(),
+        };
+        let mut animations = 1i32;
+        unsafe {
+            if SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                &mut high_contrast as *mut HIGHCONTRASTW as *mut c_void,
+                0,
+            ) == 0
+            {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            if SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                &mut animations as *mut i32 as *mut c_void,
+                0,
+            ) == 0
+            {
+                return Err(std::io::Error::last_os_error().into());
+            }
+        }
+
+        Ok(Appearance {
+            theme,
+            accent_color,
+            reduced_motion: animations == 0,
+            high_contrast: high_contrast.dwFlags & HCF_HIGHCONTRASTON != 0,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use objc2_app_kit::NSWorkspace;
+    use objc2_foundation::{NSString, NSUserDefaults};
+
+    use super::*;
+
+    pub fn appearance() -> crate::Result<Appearance> {
+        unsafe {
+            let defaults = NSUserDefaults::standardUserDefaults();
+            let style = defaults.stringForKey(&NSString::from_str("AppleInterfaceStyle"));
+            let theme = match style {
+                Some(style) if style.to_string() == "Dark" => Theme::Dark,
+                _ => Theme::Light,
+            };
+
+            // the key is absent for the default multicolor accent, which is blue
+            let accent_key = NSString::from_str("AppleAccentColor");
+            let accent = if defaults.objectForKey(&accent_key).is_some() {
+                defaults.integerForKey(&accent_key)
+            } else {
+                4
+            };
+            let accent_color = match accent {
+                -1 => Some(hex(0x8e, 0x8e, 0x93)),
+                0 => Some(hex(0xff, 0x3b, 0x30)),
+                1 => Some(hex(0xff, 0x95, 0x00)),
+                2 => Some(hex(0xff, 0xcc, 0x00)),
+                3 => Some(hex(0x28, 0xcd, 0x41)),
+                4 => Some(hex(0x00, 0x7a, 0xff)),
+                5 => Some(hex(0xaf, 0x52, 0xde)),
+                6 => Some(hex(0xff, 0x2d, 0x55)),
+                _ => None,
+            };
+
+            let workspace = NSWorkspace::sharedWorkspace();
+            Ok(Appearance {
+                theme,
+                accent_color,
+                reduced_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+                high_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+            })
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod imp {
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use super::*;
+
+    /// Reads a setting of the XDG desktop portal.
+    fn read(
+        connection: &zbus::blocking::Connection,
+        namespace: &str,
+        key: &str,
+    ) -> Option<OwnedValue> {
+        let call = |method: &str| {
+            connection
+                .call_method(
+                    Some("org.freedesktop.portal.Desktop"),
+                    "/org/freedesktop/portal/desktop",
+                    Some("org.freedesktop.portal.Settings"),
+                    method,
+                    &(namespace, key),
+                )
+                .and_then(|reply| reply.body().deserialize::<OwnedValue>())
+        };
+        // `ReadOne` was introduced in version 2 of the interface, `Read` wraps the value in another variant
+        call("ReadOne").or_else(|_| call("Read")).ok()
+    }
+
+    /// Unwraps the nested variants.
+    fn inner<'a>(value: &'a Value<'a>) -> &'a Value<'a> {
+        match value {
+            Value::Value(value) => inner(value),
+            value => value,
+        }
+    }
+
+    fn as_u32(value: &OwnedValue) -> Option<u32> {
+        match inner(value) {
+            Value::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn as_bool(value: &OwnedValue) -> Option<bool> {
+        match inner(value) {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Reads the `(ddd)` accent color, whose components are between 0 and 1, or out of range if unset.
+    fn as_color(value: &OwnedValue) -> Option<String> {
+        let Value::Structure(color) = inner(value) else {
+            return None;
+        };
+        let components = color
+            .fields()
+            .iter()
+            .map(|field| match inner(field) {
+                Value::F64(c) if (0.0..=1.0).contains(c) => Some((c * 255.0).round() as u8),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        match components[..] {
+            [r, g, b] => Some(hex(r, g, b)),
+            _ => None,
+        }
+    }
+
+    pub fn appearance() -> crate::Result<Appearance> {
+        let connection = zbus::blocking::Connection::session()?;
+
+        // 0: no preference, 1: prefer dark, 2: prefer light
+        let theme = match read(&connection, "org.freedesktop.appearance", "color-scheme")
+            .and_then(|v| as_u32(&v))
+        {
+            Some(1) => Theme::Dark,
+            _ => Theme::Light,
+        };
+        let accent_color = read(&connection, "org.freedesktop.appearance", "accent-color")
+            .and_then(|v| as_color(&v));
+        // 0: no preference, 1: higher contrast
+        let high_contrast = read(&connection, "org.freedesktop.appearance", "contrast")
+            .and_then(|v| as_u32(&v))
+            == Some(1);
+        let reduced_motion = read(
+            &connection,
+            "org.gnome.desktop.interface",
+            "enable-animations",
+        )
+        .and_then(|v| as_bool(&v))
+            == Some(false);
+
+        Ok(Appearance {
+            theme,
+            accent_color,
+            reduced_motion,
+            high_contrast,
+        })
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod imp {
+    use super::*;
+
+    pub fn appearance() -> crate::Result<Appearance> {
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
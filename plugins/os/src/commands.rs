@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use serde::Deserialize;
+use tauri::{ResourceId, Runtime, Webview};
+
 #[tauri::command]
 pub fn locale() -> Option<String> {
     crate::locale()
@@ -11,3 +14,47 @@ pub fn locale() -> Option<String> {
 pub fn hostname() -> String {
     crate::hostname()
 }
+
+#[tauri::command]
+pub fn appearance() -> crate::Result<crate::Appearance> {
+    crate::appearance()
+}
+
+#[tauri::command]
+pub fn network_interfaces() -> crate::Result<Vec<crate::NetworkInterface>> {
+    crate::network_interfaces()
+}
+
+#[tauri::command]
+pub fn connectivity() -> crate::Result<crate::Connectivity> {
+    crate::connectivity()
+}
+
+#[tauri::command]
+pub fn power_status() -> crate::Result<crate::PowerStatus> {
+    crate::power_status()
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InhibitSleepOptions {
+    #[serde(default)]
+    keep_display_on: bool,
+}
+
+#[tauri::command]
+pub fn inhibit_sleep<R: Runtime>(
+    webview: Webview<R>,
+    reason: String,
+    options: Option<InhibitSleepOptions>,
+) -> crate::Result<ResourceId> {
+    let options = options.unwrap_or_default();
+    let inhibitor = crate::inhibit_sleep(&reason, options.keep_display_on)?;
+    Ok(webview.resources_table().add(inhibitor))
+}
+
+/// Returns the seconds since the last user input.
+#[tauri::command]
+pub fn idle_time() -> crate::Result<f64> {
+    crate::idle_time().map(|idle| idle.as_secs_f64())
+}
@@ -5,7 +5,21 @@
 use serde::{Serialize, Serializer};
 
 #[derive(Debug, thiserror::Error)]
-pub enum Error {}
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    #[error(transparent)]
+    Zbus(#[from] zbus::Error),
+    #[error("API not supported on the current platform")]
+    UnsupportedPlatform,
+}
 
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -15,3 +29,5 @@ impl Serialize for Error {
         serializer.serialize_str(self.to_string().as_ref())
     }
 }
+
+pub type Result<T> = std::result::Result<T, Error>;
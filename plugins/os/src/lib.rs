@@ -18,10 +18,25 @@ use tauri::{
     Runtime,
 };
 
+mod appearance;
 mod commands;
 mod error;
-
-pub use error::Error;
+mod network;
+mod power;
+mod session;
+#[cfg(desktop)]
+mod watcher;
+
+pub use appearance::{appearance, Appearance, Theme, APPEARANCE_CHANGED_EVENT};
+pub use error::{Error, Result};
+pub use network::{
+    connectivity, network_interfaces, Connectivity, NetworkInterface, CONNECTIVITY_CHANGED_EVENT,
+};
+pub use power::{
+    inhibit_sleep, power_status, Battery, PowerSource, PowerStatus, SleepInhibitor,
+    POWER_CHANGED_EVENT,
+};
+pub use session::{idle_time, SessionEvent, SESSION_EVENT};
 
 pub enum OsType {
     Linux,
@@ -137,7 +152,21 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
         .js_init_script(init_js.to_string())
         .invoke_handler(tauri::generate_handler![
             commands::locale,
-            commands::hostname
+            commands::hostname,
+            commands::appearance,
+            commands::network_interfaces,
+            commands::connectivity,
+            commands::power_status,
+            commands::inhibit_sleep,
+            commands::idle_time
         ])
+        .setup(|_app, _api| {
+            #[cfg(desktop)]
+            {
+                watcher::spawn(_app.clone());
+                session::listen(_app.clone());
+            }
+            Ok(())
+        })
         .build()
 }
@@ -0,0 +1,482 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::net::IpAddr;
+
+use serde::Serialize;
+
+/// Emitted with the [`Connectivity`] when it changes.
+pub const CONNECTIVITY_CHANGED_EVENT: &str = "os://connectivity-changed";
+
+/// A network interface of the system.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterface {
+    /// The interface name, or its friendly name on Windows.
+    pub name: String,
+    /// The IPv4 and IPv6 addresses assigned to the interface.
+    pub addresses: Vec<IpAddr>,
+    /// Whether the interface is up and running.
+    pub up: bool,
+    /// Whether this is a loopback interface.
+    pub loopback: bool,
+    /// The SSID of the Wi-Fi network the interface is connected to, if known and permitted.
+    pub wifi_ssid: Option<String>,
+}
+
+/// The network connectivity of the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connectivity {
+    /// Whether the system can reach the internet.
+    pub online: bool,
+    /// Whether the connection is metered, i.e. the user may be charged for the data usage.
+    pub metered: bool,
+}
+
+/// Returns the network interfaces of the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** The Wi-Fi SSID is read from NetworkManager.
+/// - **macOS:** The Wi-Fi SSID requires the location permission.
+/// - **Windows:** The Wi-Fi SSID requires the location permission on Windows 11 24H2 and later.
+/// - **Android / iOS:** The Wi-Fi SSID is not available.
+pub fn network_interfaces() -> crate::Result<Vec<NetworkInterface>> {
+    imp::network_interfaces()
+}
+
+/// Returns the network connectivity of the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Read from the network monitor of the XDG desktop portal,
+///   falling back to the network interfaces when it is unavailable.
+/// - **macOS / Android / iOS:** Guessed from the network interfaces, `metered` is always `false`.
+pub fn connectivity() -> crate::Result<Connectivity> {
+    imp::connectivity()
+}
+
+/// Guesses the connectivity from the interfaces: the system is online if an interface
+/// other than the loopback one is up with a globally routable address.
+#[cfg(not(windows))]
+fn guess_connectivity() -> crate::Result<Connectivity> {
+    let online = network_interfaces()?.iter().any(|interface| {
+        interface.up
+            && !interface.loopback
+            && interface.addresses.iter().any(|address| match address {
+                IpAddr::V4(address) => !address.is_link_local() && !address.is_loopback(),
+                // fe80::/10
+                IpAddr::V6(address) => {
+                    address.segments()[0] & 0xffc0 != 0xfe80 && !address.is_loopback()
+                }
+            })
+    });
+    Ok(Connectivity {
+        online,
+        metered: false,
+    })
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::{
+        collections::HashMap,
+        ffi::{c_void, CStr},
+        mem::zeroed,
+        net::{Ipv4Addr, Ipv6Addr},
+    };
+
+    use windows_sys::{
+        core::GUID,
+        Win32::{
+            Foundation::{ERROR_BUFFER_OVERFLOW, ERROR_SUCCESS},
+            NetworkManagement::{
+                IpHelper::{
+                    GetAdaptersAddresses, GetNetworkConnectivityHint, GAA_FLAG_SKIP_ANYCAST,
+                    GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, IF_TYPE_SOFTWARE_LOOPBACK,
+                    IP_ADAPTER_ADDRESSES_LH,
+                },
+                Ndis::IfOperStatusUp,
+                WiFi::{
+                    wlan_interface_state_connected, wlan_intf_opcode_current_connection,
+                    WlanCloseHandle, WlanEnumInterfaces, WlanFreeMemory, WlanOpenHandle,
+                    WlanQueryInterface, WLAN_CONNECTION_ATTRIBUTES, WLAN_INTERFACE_INFO_LIST,
+                },
+            },
+            Networking::WinSock::{
+                NetworkConnectivityCostHintFixed, NetworkConnectivityCostHintVariable,
+                NetworkConnectivityLevelHintConstrainedInternetAccess,
+                NetworkConnectivityLevelHintInternetAccess, AF_INET, AF_INET6, AF_UNSPEC,
+                NL_NETWORK_CONNECTIVITY_HINT, SOCKADDR, SOCKADDR_IN, SOCKADDR_IN6,
+            },
+        },
+    };
+
+    use super::*;
+
+    /// Formats a GUID the way `IP_ADAPTER_ADDRESSES::AdapterName` does.
+    fn guid_string(guid: &GUID) -> String {
+        let d = guid.data4;
+        format!(
+            "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+            guid.data1, guid.data2, guid.data3, d[0], d[1], d[2], d[3], d[4], d[5], d[6], d[7]
+        )
+    }
+
+    /// Returns the SSIDs of the connected Wi-Fi interfaces, by adapter GUID.
+    fn wifi_ssids() -> HashMap<String, String> {
+        let mut ssids = HashMap::new();
+        unsafe {
+            let mut version = 0;
+            let mut handle = std::ptr::null_mut();
+            if WlanOpenHandle(2, std::ptr::null(), &mut version, &mut handle) != ERROR_SUCCESS {
+                return ssids;
+            }
+
+            let mut list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+            if WlanEnumInterfaces(handle, std::ptr::null(), &mut list) == ERROR_SUCCESS {
+                let infos = std::slice::from_raw_parts(
+                    (*list).InterfaceInfo.as_ptr(),
+                    (*list).dwNumberOfItems as usize,
+                );
+                for info in infos {
+                    if info.isState != wlan_interface_state_connected {
+                        continue;
+                    }
+                    let mut size = 0;
+                    let mut data: *mut c_void = std::ptr::null_mut();
+                    if WlanQueryInterface(
+                        handle,
+                        &info.InterfaceGuid,
+                        wlan_intf_opcode_current_connection,
+                        std::ptr::null(),
+                        &mut size,
+                        &mut data,
+                        std::ptr::null_mut(),
+                    ) == ERROR_SUCCESS
+                    {
+                        let attributes = &*(data as *const WLAN_CONNECTION_ATTRIBUTES);
+                        let ssid = &attributes.wlanAssociationAttributes.dot11Ssid;
+                        let len = (ssid.uSSIDLength as usize).min(ssid.ucSSID.len());
+                        ssids.insert(
+                            guid_string(&info.InterfaceGuid),
+                            String::from_utf8_lossy(&ssid.ucSSID[..len]).into_owned(),
+                        );
+                        WlanFreeMemory(data);
+                    }
+                }
+                WlanFreeMemory(list as *const c_void);
+            }
+            WlanCloseHandle(handle, std::ptr::null());
+        }
+        ssids
+    }
+
+    unsafe fn ip_addr(addr: *const SOCKADDR) -> Option<IpAddr> {
+        let addr = addr.as_ref()?;
+        match addr.sa_family {
+            AF_INET => {
+                let addr = &*(addr as *const SOCKADDR as *const SOCKADDR_IN);
+                Some(Ipv4Addr::from(u32::from_be(addr.sin_addr.S_un.S_addr)).into())
+            }
+            AF_INET6 => {
+                let addr = &*(addr as *const SOCKADDR as *const SOCKADDR_IN6);
+                Some(Ipv6Addr::from(addr.sin6_addr.u.Byte).into())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn network_interfaces() -> crate::Result<Vec<NetworkInterface>> {
+        let flags = GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST | GAA_FLAG_SKIP_DNS_SERVER;
+        // the recommended initial size, grown on ERROR_BUFFER_OVERFLOW
+        let mut size = 15_000u32;
+        let mut buffer: Vec<u64>;
+        loop {
+            buffer = vec![0; (size as usize).div_ceil(8)];
+            let status = unsafe {
+                GetAdaptersAddresses(
+                    AF_UNSPEC as u32,
+                    flags,
+                    std::ptr::null(),
+                    buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH,
+                    &mut size,
+                )
+            };
+            match status {
+                ERROR_SUCCESS => break,
+                ERROR_BUFFER_OVERFLOW => continue,
+                status => return Err(std::io::Error::from_raw_os_error(status as i32).into()),
+            }
+        }
+
+        let ssids = wifi_ssids();
+        let mut interfaces = Vec::new();
+        let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+        while let Some(current) = unsafe { adapter.as_ref() } {
+            adapter = current.Next;
+
+            let name = unsafe {
+                let len = (0..)
+                    .take_while(|&i| *current.FriendlyName.add(i) != 0)
+                    .count();
+                String::from_utf16_lossy(std::slice::from_raw_parts(current.FriendlyName, len))
+            };
+            let guid = unsafe { CStr::from_ptr(current.AdapterName as *const _) }
+                .to_string_lossy()
+                .to_uppercase();
+
+            let mut addresses = Vec::new();
+            let mut unicast = current.FirstUnicastAddress;
+            while let Some(address) = unsafe { unicast.as_ref() } {
+                unicast = address.Next;
+                if let Some(address) = unsafe { ip_addr(address.Address.lpSockaddr) } {
+                    addresses.push(address);
+                }
+            }
+
+            interfaces.push(NetworkInterface {
+                name,
+                addresses,
+                up: current.OperStatus == IfOperStatusUp,
+                loopback: current.IfType == IF_TYPE_SOFTWARE_LOOPBACK,
+                wifi_ssid: ssids.get(&guid).cloned(),
+            });
+        }
+
+        Ok(interfaces)
+    }
+
+    pub fn connectivity() -> crate::Result<Connectivity> {
+        let mut hint: NL_NETWORK_CONNECTIVITY_HINT = unsafe { zeroed() };
+        let status = unsafe { GetNetworkConnectivityHint(&mut hint) };
+        if status != ERROR_SUCCESS {
+            return Err(std::io::Error::from_raw_os_error(status as i32).into());
+        }
+
+        Ok(Connectivity {
+            online: matches!(
+                hint.ConnectivityLevel,
+                NetworkConnectivityLevelHintInternetAccess
+                    | NetworkConnectivityLevelHintConstrainedInternetAccess
+            ),
+            metered: matches!(
+                hint.ConnectivityCost,
+                NetworkConnectivityCostHintFixed | NetworkConnectivityCostHintVariable
+            ) || hint.Roaming != 0,
+        })
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::{
+        collections::HashMap,
+        ffi::CStr,
+        net::{Ipv4Addr, Ipv6Addr},
+    };
+
+    use super::*;
+
+    unsafe fn ip_addr(addr: *const libc::sockaddr) -> Option<IpAddr> {
+        let addr = addr.as_ref()?;
+        match addr.sa_family as libc::c_int {
+            libc::AF_INET => {
+                let addr = &*(addr as *const libc::sockaddr as *const libc::sockaddr_in);
+                Some(Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)).into())
+            }
+            libc::AF_INET6 => {
+                let addr = &*(addr as *const libc::sockaddr as *const libc::sockaddr_in6);
+                Some(Ipv6Addr::from(addr.sin6_addr.s6_addr).into())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn network_interfaces() -> crate::Result<Vec<NetworkInterface>> {
+        let mut addrs = std::ptr::null_mut();
+        if unsafe { libc::getifaddrs(&mut addrs) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        // getifaddrs returns an entry per address, group them by interface
+        let mut interfaces: Vec<NetworkInterface> = Vec::new();
+        let mut cursor = addrs;
+        while let Some(ifaddr) = unsafe { cursor.as_ref() } {
+            cursor = ifaddr.ifa_next;
+
+            let name = unsafe { CStr::from_ptr(ifaddr.ifa_name) }
+                .to_string_lossy()
+                .into_owned();
+            let index = match interfaces.iter().position(|i| i.name == name) {
+                Some(index) => index,
+                None => {
+                    let flags = ifaddr.ifa_flags as libc::c_int;
+                    interfaces.push(NetworkInterface {
+                        name,
+                        addresses: Vec::new(),
+                        up: flags & libc::IFF_UP != 0 && flags & libc::IFF_RUNNING != 0,
+                        loopback: flags & libc::IFF_LOOPBACK != 0,
+                        wifi_ssid: None,
+                    });
+                    interfaces.len() - 1
+                }
+            };
+            if let Some(address) = unsafe { ip_addr(ifaddr.ifa_addr) } {
+                interfaces[index].addresses.push(address);
+            }
+        }
+        unsafe { libc::freeifaddrs(addrs) };
+
+        let mut ssids = wifi_ssids();
+        for interface in &mut interfaces {
+            interface.wifi_ssid = ssids.remove(&interface.name);
+        }
+
+        Ok(interfaces)
+    }
+
+    /// Returns the SSIDs of the connected Wi-Fi interfaces, by interface name.
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    fn wifi_ssids() -> HashMap<String, String> {
+        use zbus::{blocking::Proxy, zvariant::OwnedObjectPath};
+
+        const NM: &str = "org.freedesktop.NetworkManager";
+        // NM_DEVICE_TYPE_WIFI
+        const DEVICE_TYPE_WIFI: u32 = 2;
+
+        let device_ssid = |connection: &zbus::blocking::Connection,
+                           device: &OwnedObjectPath|
+         -> Option<(String, String)> {
+            let proxy = Proxy::new(
+                connection,
+                NM,
+                device,
+                "org.freedesktop.NetworkManager.Device",
+            )
+            .ok()?;
+            if proxy.get_property::<u32>("DeviceType").ok()? != DEVICE_TYPE_WIFI {
+                return None;
+            }
+            let name = proxy.get_property::<String>("Interface").ok()?;
+
+            let wireless = Proxy::new(
+                connection,
+                NM,
+                device,
+                "org.freedesktop.NetworkManager.Device.Wireless",
+            )
+            .ok()?;
+            let access_point = wireless
+                .get_property::<OwnedObjectPath>("ActiveAccessPoint")
+                .ok()?;
+            if access_point.as_str() == "/" {
+                return None;
+            }
+            let access_point = Proxy::new(
+                connection,
+                NM,
+                &access_point,
+                "org.freedesktop.NetworkManager.AccessPoint",
+            )
+            .ok()?;
+            let ssid = access_point.get_property::<Vec<u8>>("Ssid").ok()?;
+            Some((name, String::from_utf8_lossy(&ssid).into_owned()))
+        };
+
+        let Ok(connection) = zbus::blocking::Connection::system() else {
+            return HashMap::new();
+        };
+        connection
+            .call_method(
+                Some(NM),
+                "/org/freedesktop/NetworkManager",
+                Some(NM),
+                "GetDevices",
+                &(),
+            )
+            .and_then(|reply| reply.body().deserialize::<Vec<OwnedObjectPath>>())
+            .map(|devices| {
+                devices
+                    .iter()
+                    .filter_map(|device| device_ssid(&connection, device))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the SSIDs of the connected Wi-Fi interfaces, by interface name.
+    #[cfg(target_os = "macos")]
+    fn wifi_ssids() -> HashMap<String, String> {
+        use objc2_core_wlan::CWWiFiClient;
+
+        let mut ssids = HashMap::new();
+        unsafe {
+            let client = CWWiFiClient::sharedWiFiClient();
+            if let Some(interfaces) = client.interfaces() {
+                for interface in interfaces.iter() {
+                    // the SSID is nil without the location permission
+                    if let (Some(name), Some(ssid)) = (interface.interfaceName(), interface.ssid())
+                    {
+                        ssids.insert(name.to_string(), ssid.to_string());
+                    }
+                }
+            }
+        }
+        ssids
+    }
+
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    fn wifi_ssids() -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn connectivity() -> crate::Result<Connectivity> {
+        let portal = || -> zbus::Result<Connectivity> {
+            let connection = zbus::blocking::Connection::session()?;
+            let call = |method: &str| {
+                connection.call_method(
+                    Some("org.freedesktop.portal.Desktop"),
+                    "/org/freedesktop/portal/desktop",
+                    Some("org.freedesktop.portal.NetworkMonitor"),
+                    method,
+                    &(),
+                )
+            };
+            // 1: local only, 2: limited, 3: captive portal, 4: full network
+            let connectivity = call("GetConnectivity")?.body().deserialize::<u32>()?;
+            let metered = call("GetMetered")?.body().deserialize::<bool>()?;
+            Ok(Connectivity {
+                online: connectivity == 4,
+                metered,
+            })
+        };
+        portal().or_else(|_| guess_connectivity())
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    pub fn connectivity() -> crate::Result<Connectivity> {
+        guess_connectivity()
+    }
+}
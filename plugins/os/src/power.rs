@@ -0,0 +1,443 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+
+/// Emitted with the [`PowerStatus`] when it changes.
+pub const POWER_CHANGED_EVENT: &str = "os://power-changed";
+
+/// The source the system draws its power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    Unknown,
+}
+
+/// The state of the system battery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Battery {
+    /// The charge level, in percent.
+    pub level: u8,
+    /// Whether the battery is charging.
+    pub charging: bool,
+}
+
+/// The power status of the system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub source: PowerSource,
+    /// The battery state, `None` if the system has no battery.
+    pub battery: Option<Battery>,
+}
+
+/// Returns the power source and battery state of the system.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Read from `/sys/class/power_supply`, the levels of multiple batteries are averaged.
+/// - **Android / iOS / BSD:** Unsupported.
+pub fn power_status() -> crate::Result<PowerStatus> {
+    imp::power_status()
+}
+
+/// Keeps the system from sleeping while it is alive, see [`inhibit_sleep`].
+pub struct SleepInhibitor {
+    _inhibitor: imp::Inhibitor,
+}
+
+impl tauri::Resource for SleepInhibitor {}
+
+/// Prevents the system from sleeping when idle until the returned [`SleepInhibitor`] is dropped.
+///
+/// This does not prevent the user from putting the system to sleep explicitly.
+/// The display may still turn off unless `keep_display_on` is set.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Uses the `idle` inhibitor of systemd-logind,
+///   and the `org.freedesktop.ScreenSaver` interface to keep the display on.
+/// - **Android / iOS / BSD:** Unsupported.
+pub fn inhibit_sleep(reason: &str, keep_display_on: bool) -> crate::Result<SleepInhibitor> {
+    imp::Inhibitor::new(reason, keep_display_on).map(|inhibitor| SleepInhibitor {
+        _inhibitor: inhibitor,
+    })
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::mem::zeroed;
+
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, HANDLE, INVALID_HANDLE_VALUE},
+        System::{
+            Power::{
+                GetSystemPowerStatus, PowerClearRequest, PowerCreateRequest,
+                PowerRequestDisplayRequired, PowerRequestSystemRequired, PowerSetRequest,
+                POWER_REQUEST_TYPE, SYSTEM_POWER_STATUS,
+            },
+            Threading::{POWER_REQUEST_CONTEXT_SIMPLE_STRING, REASON_CONTEXT, REASON_CONTEXT_0},
+        },
+    };
+
+    use super::*;
+
+    // https://learn.microsoft.com/en-us/windows/win32/api/winbase/ns-winbase-system_power_status
+    const BATTERY_FLAG_CHARGING: u8 = 8;
+    const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+    const UNKNOWN: u8 = 255;
+
+    pub fn power_status() -> crate::Result<PowerStatus> {
+        let mut status: SYSTEM_POWER_STATUS = unsafe { zeroed() };
+        if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        let source = match status.ACLineStatus {
+            0 => PowerSource::Battery,
+            1 => PowerSource::Ac,
+            _ => PowerSource::Unknown,
+        };
+        let battery = (status.BatteryFlag != UNKNOWN
+            && status.BatteryFlag & BATTERY_FLAG_NO_BATTERY == 0
+            && status.BatteryLifePercent != UNKNOWN)
+            .then(|| Battery {
+                level: status.BatteryLifePercent,
+                charging: status.BatteryFlag & BATTERY_FLAG_CHARGING != 0,
+            });
+
+        Ok(PowerStatus { source, battery })
+    }
+
+    pub struct Inhibitor {
+        handle: HANDLE,
+        requests: Vec<POWER_REQUEST_TYPE>,
+    }
+
+    // the power request handle is not tied to the thread that created it
+    unsafe impl Send for Inhibitor {}
+    unsafe impl Sync for Inhibitor {}
+
+    impl Inhibitor {
+        pub fn new(reason: &str, keep_display_on: bool) -> crate::Result<Self> {
+            let mut reason = reason
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect::<Vec<u16>>();
+            let context = REASON_CONTEXT {
+                // POWER_REQUEST_CONTEXT_VERSION
+                Version: 0,
+                Flags: POWER_REQUEST_CONTEXT_SIMPLE_STRING,
+                Reason: REASON_CONTEXT_0 {
+                    SimpleReasonString: reason.as_mut_ptr(),
+                },
+            };
+            let handle = unsafe { PowerCreateRequest(&context) };
+            if handle == INVALID_HANDLE_VALUE {
+                return Err(std::io::Error::last_os_error().into());
+            }
+
+            let mut inhibitor = Self {
+                handle,
+                requests: Vec::new(),
+            };
+            let mut requests = vec![PowerRequestSystemRequired];
+            if keep_display_on {
+                requests.push(PowerRequestDisplayRequired);
+            }
+            for request in requests {
+                if unsafe { PowerSetRequest(handle, request) } == 0 {
+                    return Err(std::io::Error::last_os_error().into());
+                }
+                inhibitor.requests.push(request);
+            }
+
+            Ok(inhibitor)
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            unsafe {
+                for request in &self.requests {
+                    PowerClearRequest(self.handle, *request);
+                }
+                CloseHandle(self.handle);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::ffi::c_void;
+
+    use objc2_foundation::{NSArray, NSDictionary, NSNumber, NSObject, NSString};
+
+    use super::*;
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPSCopyPowerSourcesInfo() -> *const c_void;
+        fn IOPSCopyPowerSourcesList(blob: *const c_void) -> *const c_void;
+        fn IOPSGetPowerSourceDescription(blob: *const c_void, ps: *const c_void) -> *const c_void;
+        fn IOPSGetProvidingPowerSourceType(snapshot: *const c_void) -> *const c_void;
+        fn IOPMAssertionCreateWithName(
+            assertion_type: *const c_void,
+            level: u32,
+            name: *const c_void,
+            id: *mut u32,
+        ) -> i32;
+        fn IOPMAssertionRelease(id: u32) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFRelease(cf: *const c_void);
+    }
+
+    const IOPM_ASSERTION_LEVEL_ON: u32 = 255;
+
+    /// Toll-free bridges a `NSString` to a `CFStringRef`.
+    fn cf_string(string: &NSString) -> *const c_void {
+        string as *const NSString as *const c_void
+    }
+
+    pub fn power_status() -> crate::Result<PowerStatus> {
+        unsafe {
+            let blob = IOPSCopyPowerSourcesInfo();
+            if blob.is_null() {
+                return Err(crate::Error::UnsupportedPlatform);
+            }
+
+            let source = IOPSGetProvidingPowerSourceType(blob);
+            let source = match (source as *const NSString).as_ref().map(|s| s.to_string()) {
+                Some(s) if s == "AC Power" => PowerSource::Ac,
+                Some(s) if s == "Battery Power" => PowerSource::Battery,
+                _ => PowerSource::Unknown,
+            };
+
+            let mut battery = None;
+            let list = IOPSCopyPowerSourcesList(blob);
+            if let Some(sources) = (list as *const NSArray<NSObject>).as_ref() {
+                for ps in sources.iter() {
+                    let description = IOPSGetPowerSourceDescription(
+                        blob,
+                        &*ps as *const NSObject as *const c_void,
+                    )
+                        as *const NSDictionary<NSString, NSObject>;
+                    let Some(description) = description.as_ref() else {
+                        continue;
+                    };
+                    let get = |key: &str| {
+                        description
+                            .get(&NSString::from_str(key))
+                            .map(|value| &*value as *const NSObject)
+                    };
+                    let is_internal = get("Type")
+                        .map(|t| (*(t as *const NSString)).to_string() == "InternalBattery")
+                        .unwrap_or(false);
+                    if !is_internal {
+                        continue;
+                    }
+                    let number = |key: &str| get(key).map(|n| &*(n as *const NSNumber));
+                    let (Some(current), Some(max)) =
+                        (number("Current Capacity"), number("Max Capacity"))
+                    else {
+                        continue;
+                    };
+                    let max = max.as_i64().max(1);
+                    battery = Some(Battery {
+                        level: (current.as_i64() * 100 / max).clamp(0, 100) as u8,
+                        charging: number("Is Charging").map(|c| c.as_bool()).unwrap_or(false),
+                    });
+                    break;
+                }
+                CFRelease(list);
+            }
+            CFRelease(blob);
+
+            Ok(PowerStatus { source, battery })
+        }
+    }
+
+    pub struct Inhibitor {
+        assertions: Vec<u32>,
+    }
+
+    impl Inhibitor {
+        pub fn new(reason: &str, keep_display_on: bool) -> crate::Result<Self> {
+            let mut inhibitor = Self {
+                assertions: Vec::new(),
+            };
+            let mut types = vec!["PreventUserIdleSystemSleep"];
+            if keep_display_on {
+                types.push("PreventUserIdleDisplaySleep");
+            }
+
+            let name = NSString::from_str(reason);
+            for assertion_type in types {
+                let assertion_type = NSString::from_str(assertion_type);
+                let mut id = 0;
+                let status = unsafe {
+                    IOPMAssertionCreateWithName(
+                        cf_string(&assertion_type),
+                        IOPM_ASSERTION_LEVEL_ON,
+                        cf_string(&name),
+                        &mut id,
+                    )
+                };
+                if status != 0 {
+                    return Err(std::io::Error::other(format!(
+                        "failed to create the power assertion: {status:#x}"
+                    ))
+                    .into());
+                }
+                inhibitor.assertions.push(id);
+            }
+
+            Ok(inhibitor)
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            for id in &self.assertions {
+                unsafe { IOPMAssertionRelease(*id) };
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::fs;
+
+    use zbus::zvariant::OwnedFd;
+
+    use super::*;
+
+    pub fn power_status() -> crate::Result<PowerStatus> {
+        let mut source = PowerSource::Unknown;
+        let mut levels = Vec::new();
+        let mut charging = false;
+
+        for entry in fs::read_dir("/sys/class/power_supply")? {
+            let path = entry?.path();
+            let read = |name: &str| {
+                fs::read_to_string(path.join(name))
+                    .map(|value| value.trim().to_string())
+                    .ok()
+            };
+            match read("type").as_deref() {
+                Some("Mains") | Some("USB") => {
+                    if read("online").as_deref() == Some("1") {
+                        source = PowerSource::Ac;
+                    }
+                }
+                // peripherals such as wireless mice have a `Device` scope
+                Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                    if let Some(level) = read("capacity").and_then(|c| c.parse::<u32>().ok()) {
+                        levels.push(level.min(100));
+                    }
+                    charging |= read("status").as_deref() == Some("Charging");
+                }
+                _ => {}
+            }
+        }
+
+        let battery = (!levels.is_empty()).then(|| Battery {
+            level: (levels.iter().sum::<u32>() / levels.len() as u32) as u8,
+            charging,
+        });
+        if source == PowerSource::Unknown && battery.is_some() {
+            source = PowerSource::Battery;
+        }
+
+        Ok(PowerStatus { source, battery })
+    }
+
+    pub struct Inhibitor {
+        /// The logind inhibitor is released when its file descriptor is closed.
+        _fd: OwnedFd,
+        /// The screensaver inhibitor is released when its connection is closed.
+        screensaver: Option<(zbus::blocking::Connection, u32)>,
+    }
+
+    impl Inhibitor {
+        pub fn new(reason: &str, keep_display_on: bool) -> crate::Result<Self> {
+            let who = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+                .unwrap_or_default();
+
+            let fd = zbus::blocking::Connection::system()?
+                .call_method(
+                    Some("org.freedesktop.login1"),
+                    "/org/freedesktop/login1",
+                    Some("org.freedesktop.login1.Manager"),
+                    "Inhibit",
+                    &("idle", who.as_str(), reason, "block"),
+                )?
+                .body()
+                .deserialize::<OwnedFd>()?;
+
+            let screensaver = if keep_display_on {
+                let connection = zbus::blocking::Connection::session()?;
+                let cookie = connection
+                    .call_method(
+                        Some("org.freedesktop.ScreenSaver"),
+                        "/org/freedesktop/ScreenSaver",
+                        Some("org.freedesktop.ScreenSaver"),
+                        "Inhibit",
+                        &(who.as_str(), reason),
+                    )?
+                    .body()
+                    .deserialize::<u32>()?;
+                Some((connection, cookie))
+            } else {
+                None
+            };
+
+            Ok(Self {
+                _fd: fd,
+                screensaver,
+            })
+        }
+    }
+
+    impl Drop for Inhibitor {
+        fn drop(&mut self) {
+            if let Some((connection, cookie)) = self.screensaver.take() {
+                let _ = connection.call_method(
+                    Some("org.freedesktop.ScreenSaver"),
+                    "/org/freedesktop/ScreenSaver",
+                    Some("org.freedesktop.ScreenSaver"),
+                    "UnInhibit",
+                    &(cookie,),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod imp {
+    use super::*;
+
+    pub fn power_status() -> crate::Result<PowerStatus> {
+        Err(crate::Error::UnsupportedPlatform)
+    }
+
+    pub struct Inhibitor;
+
+    impl Inhibitor {
+        pub fn new(_reason: &str, _keep_display_on: bool) -> crate::Result<Self> {
+            Err(crate::Error::UnsupportedPlatform)
+        }
+    }
+}
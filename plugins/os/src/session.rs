@@ -0,0 +1,343 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::time::Duration;
+
+use serde::Serialize;
+#[cfg(desktop)]
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Emitted with a [`SessionEvent`] when the session is locked or unlocked, and when the system suspends or resumes.
+pub const SESSION_EVENT: &str = "os://session";
+
+/// A change of the user session or of the system sleep state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionEvent {
+    Lock,
+    Unlock,
+    Suspend,
+    Resume,
+}
+
+/// Returns the time since the last user input.
+///
+/// ## Platform-specific
+///
+/// - **Linux:** Read from the idle monitor of GNOME, or from the `org.freedesktop.ScreenSaver` interface.
+/// - **Android / iOS:** Unsupported.
+pub fn idle_time() -> crate::Result<Duration> {
+    imp::idle_time()
+}
+
+#[cfg(desktop)]
+fn emit<R: Runtime>(app: &AppHandle<R>, event: SessionEvent) {
+    let _ = app.emit(SESSION_EVENT, event);
+}
+
+/// Emits the [`SESSION_EVENT`] for the lifetime of the app.
+#[cfg(desktop)]
+pub(crate) fn listen<R: Runtime>(app: AppHandle<R>) {
+    imp::listen(app)
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use std::{mem::zeroed, sync::OnceLock};
+
+    use windows_sys::Win32::{
+        Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+        System::{
+            LibraryLoader::GetModuleHandleW,
+            RemoteDesktop::{WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION},
+            SystemInformation::GetTickCount,
+        },
+        UI::{
+            Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
+            WindowsAndMessaging::{
+                CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+                TranslateMessage, MSG, PBT_APMRESUMEAUTOMATIC, PBT_APMSUSPEND, WM_POWERBROADCAST,
+                WM_WTSSESSION_CHANGE, WNDCLASSW, WS_OVERLAPPED, WTS_SESSION_LOCK,
+                WTS_SESSION_UNLOCK,
+            },
+        },
+    };
+
+    use super::*;
+
+    type Callback = Box<dyn Fn(SessionEvent) + Send + Sync>;
+
+    static CALLBACK: OnceLock<Callback> = OnceLock::new();
+
+    pub fn idle_time() -> crate::Result<Duration> {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if unsafe { GetLastInputInfo(&mut info) } == 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        // both tick counts wrap around every 49.7 days
+        let idle = unsafe { GetTickCount() }.wrapping_sub(info.dwTime);
+        Ok(Duration::from_millis(idle.into()))
+    }
+
+    unsafe extern "system" fn wnd_proc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        let event = match (msg, wparam as u32) {
+            (WM_WTSSESSION_CHANGE, WTS_SESSION_LOCK) => Some(SessionEvent::Lock),
+            (WM_WTSSESSION_CHANGE, WTS_SESSION_UNLOCK) => Some(SessionEvent::Unlock),
+            (WM_POWERBROADCAST, PBT_APMSUSPEND) => Some(SessionEvent::Suspend),
+            (WM_POWERBROADCAST, PBT_APMRESUMEAUTOMATIC) => Some(SessionEvent::Resume),
+            _ => None,
+        };
+        if let (Some(event), Some(callback)) = (event, CALLBACK.get()) {
+            callback(event);
+        }
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+
+    pub fn listen<R: Runtime>(app: AppHandle<R>) {
+        if CALLBACK
+            .set(Box::new(move |event| emit(&app, event)))
+            .is_err()
+        {
+            return;
+        }
+
+        // power broadcasts are not sent to message-only windows, so use a hidden top-level one
+        std::thread::spawn(|| unsafe {
+            let class_name = "TauriPluginOsSession"
+                .encode_utf16()
+                .chain(std::iter::once(0))
+                .collect::<Vec<u16>>();
+            let instance = GetModuleHandleW(std::ptr::null());
+
+            let mut class: WNDCLASSW = zeroed();
+            class.lpfnWndProc = Some(wnd_proc);
+            class.hInstance = instance;
+            class.lpszClassName = class_name.as_ptr();
+            RegisterClassW(&class);
+
+            let hwnd = CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                class_name.as_ptr(),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                instance,
+                std::ptr::null(),
+            );
+            if hwnd.is_null() {
+                log::warn!(
+                    "failed to create the session events window: {}",
+                    std::io::Error::last_os_error()
+                );
+                return;
+            }
+            WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION);
+
+            let mut msg: MSG = zeroed();
+            while GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) > 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        });
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::ptr::NonNull;
+
+    use block2::RcBlock;
+    use objc2_app_kit::{
+        NSWorkspace, NSWorkspaceDidWakeNotification, NSWorkspaceWillSleepNotification,
+    };
+    use objc2_foundation::{
+        NSDistributedNotificationCenter, NSNotification, NSNotificationCenter, NSString,
+    };
+
+    use super::*;
+
+    // kCGEventSourceStateHIDSystemState
+    const HID_SYSTEM_STATE: i32 = 1;
+    // kCGAnyInputEventType
+    const ANY_INPUT_EVENT_TYPE: u32 = u32::MAX;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+    }
+
+    pub fn idle_time() -> crate::Result<Duration> {
+        let seconds = unsafe {
+            CGEventSourceSecondsSinceLastEventType(HID_SYSTEM_STATE, ANY_INPUT_EVENT_TYPE)
+        };
+        Ok(Duration::from_secs_f64(seconds.max(0.0)))
+    }
+
+    pub fn listen<R: Runtime>(app: AppHandle<R>) {
+        let observe = |center: &NSNotificationCenter, name: &NSString, event: SessionEvent| {
+            let app = app.clone();
+            let block = RcBlock::new(move |_: NonNull<NSNotification>| emit(&app, event));
+            let observer = unsafe {
+                center.addObserverForName_object_queue_usingBlock(Some(name), None, None, &block)
+            };
+            // observe for the lifetime of the app
+            std::mem::forget(observer);
+        };
+
+        unsafe {
+            let workspace = NSWorkspace::sharedWorkspace().notificationCenter();
+            observe(
+                &workspace,
+                NSWorkspaceWillSleepNotification,
+                SessionEvent::Suspend,
+            );
+            observe(
+                &workspace,
+                NSWorkspaceDidWakeNotification,
+                SessionEvent::Resume,
+            );
+
+            let distributed = NSDistributedNotificationCenter::defaultCenter();
+            observe(
+                &distributed,
+                &NSString::from_str("com.apple.screenIsLocked"),
+                SessionEvent::Lock,
+            );
+            observe(
+                &distributed,
+                &NSString::from_str("com.apple.screenIsUnlocked"),
+                SessionEvent::Unlock,
+            );
+        }
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod imp {
+    use std::collections::HashMap;
+
+    use zbus::{
+        blocking::{Connection, MessageIterator},
+        zvariant::{OwnedObjectPath, OwnedValue, Value},
+        MatchRule,
+    };
+
+    use super::*;
+
+    const LOGIND: &str = "org.freedesktop.login1";
+
+    pub fn idle_time() -> crate::Result<Duration> {
+        let connection = Connection::session()?;
+
+        let mutter = connection
+            .call_method(
+                Some("org.gnome.Mutter.IdleMonitor"),
+                "/org/gnome/Mutter/IdleMonitor/Core",
+                Some("org.gnome.Mutter.IdleMonitor"),
+                "GetIdletime",
+                &(),
+            )
+            .and_then(|reply| reply.body().deserialize::<u64>());
+        if let Ok(millis) = mutter {
+            return Ok(Duration::from_millis(millis));
+        }
+
+        let seconds = connection
+            .call_method(
+                Some("org.freedesktop.ScreenSaver"),
+                "/org/freedesktop/ScreenSaver",
+                Some("org.freedesktop.ScreenSaver"),
+                "GetSessionIdleTime",
+                &(),
+            )?
+            .body()
+            .deserialize::<u32>()?;
+        Ok(Duration::from_secs(seconds.into()))
+    }
+
+    /// Listens to the logind signals, the lock state is read from the `LockedHint` of the session set by the desktop.
+    fn run<R: Runtime>(app: &AppHandle<R>) -> zbus::Result<()> {
+        let connection = Connection::system()?;
+        let session = connection
+            .call_method(
+                Some(LOGIND),
+                "/org/freedesktop/login1",
+                Some("org.freedesktop.login1.Manager"),
+                "GetSession",
+                &("auto",),
+            )?
+            .body()
+            .deserialize::<OwnedObjectPath>()?;
+
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(LOGIND)?
+            .build();
+        for message in MessageIterator::for_match_rule(rule, &connection, None)? {
+            let message = message?;
+            let header = message.header();
+            let event = match header.member().map(|member| member.as_str()) {
+                Some("PrepareForSleep") => Some(if message.body().deserialize::<bool>()? {
+                    SessionEvent::Suspend
+                } else {
+                    SessionEvent::Resume
+                }),
+                Some("PropertiesChanged")
+                    if header.path().map(|path| path.as_str()) == Some(session.as_str()) =>
+                {
+                    let (_, changed, _) =
+                        message
+                            .body()
+                            .deserialize::<(String, HashMap<String, OwnedValue>, Vec<String>)>()?;
+                    match changed.get("LockedHint").map(|value| &**value) {
+                        Some(Value::Bool(true)) => Some(SessionEvent::Lock),
+                        Some(Value::Bool(false)) => Some(SessionEvent::Unlock),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(event) = event {
+                emit(app, event);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn listen<R: Runtime>(app: AppHandle<R>) {
+        std::thread::spawn(move || {
+            if let Err(e) = run(&app) {
+                log::warn!("failed to listen to the session events: {e}");
+            }
+        });
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "ios"))]
+mod imp {
+    use super::*;
+
+    pub fn idle_time() -> crate::Result<Duration> {
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Polls the system state and emits the change events.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// How often the system state is polled.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A polled value, emitted when it changes.
+struct Watch<T> {
+    event: &'static str,
+    read: fn() -> crate::Result<T>,
+    last: Option<T>,
+}
+
+impl<T: PartialEq + Clone + Serialize> Watch<T> {
+    fn new(event: &'static str, read: fn() -> crate::Result<T>) -> Self {
+        Self {
+            event,
+            last: read().ok(),
+            read,
+        }
+    }
+
+    fn poll<R: Runtime>(&mut self, app: &AppHandle<R>) {
+        let Ok(value) = (self.read)() else {
+            return;
+        };
+        if self.last.as_ref() != Some(&value) {
+            let _ = app.emit(self.event, value.clone());
+            self.last = Some(value);
+        }
+    }
+}
+
+pub(crate) fn spawn<R: Runtime>(app: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let mut appearance = Watch::new(crate::APPEARANCE_CHANGED_EVENT, crate::appearance);
+        let mut connectivity = Watch::new(crate::CONNECTIVITY_CHANGED_EVENT, crate::connectivity);
+        let mut power = Watch::new(crate::POWER_CHANGED_EVENT, crate::power_status);
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            appearance.poll(&app);
+            connectivity.poll(&app);
+            power.poll(&app);
+        }
+    });
+}
@@ -0,0 +1,148 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invoke } from '@tauri-apps/api/core'
+import { type UnlistenFn, listen } from '@tauri-apps/api/event'
+
+/**
+ * The persisted scopes: the `fs` plugin scope and,
+ * with the `protocol-asset` feature, the asset protocol scope.
+ */
+export type ScopeKind = 'fs' | 'asset'
+
+/**
+ * The kind of path a scope entry applies to.
+ *
+ * - `file`: a single file.
+ * - `directory`: the direct children of a directory.
+ * - `recursiveDirectory`: a directory and all of its descendants.
+ */
+export type TargetType = 'file' | 'directory' | 'recursiveDirectory'
+
+/**
+ * How an entry was granted.
+ *
+ * Entries dropped on a window are detected automatically,
+ * other origins are set when adding the entry.
+ */
+export type Origin = 'unknown' | 'dialog' | 'dragDrop' | 'deepLink'
+
+/**
+ * How long an entry is granted for.
+ *
+ * - `session`: the entry is not persisted and is only granted until the app is restarted.
+ *   Entries that are already persisted are kept.
+ * - `days`: the entry is granted for the given number of days.
+ *   Expired entries are not restored when the app is reopened, see {@link prune} to remove them at runtime.
+ */
+export type Expiry = 'session' | { days: number }
+
+/**
+ * An allowed or forbidden path of a scope.
+ */
+export interface ScopeEntry {
+  /** The glob pattern of the entry, used to remove it. */
+  pattern: string
+  path: string
+  target: TargetType
+  /** Whether the entry allows or forbids access to the path. */
+  allowed: boolean
+  /** Whether the entry is only granted for the current session. */
+  temporary: boolean
+  origin: Origin
+  /** When the entry was granted, in seconds since the Unix epoch. */
+  grantedAt: number | null
+  /** When the entry expires, in seconds since the Unix epoch. */
+  expiresAt: number | null
+}
+
+export interface AddOptions {
+  /** Defaults to `file`. */
+  target?: TargetType
+  /**
+   * How the entry was granted. The origin of an existing entry is kept when unknown.
+   * Defaults to `unknown`.
+   */
+  origin?: Origin
+  /** The entry is persisted without expiry by default. */
+  expiry?: Expiry
+}
+
+/**
+ * Lists the allowed and forbidden entries of the scope,
+ * including the ones defined in the app configuration.
+ *
+ * @example
+ * ```typescript
+ * import { list } from '@tauri-apps/plugin-persisted-scope';
+ * const folders = (await list('fs')).filter((entry) => entry.allowed);
+ * ```
+ */
+async function list(scope: ScopeKind): Promise<ScopeEntry[]> {
+  return await invoke('plugin:persisted-scope|list', { scope })
+}
+
+/**
+ * Allows access to the path.
+ *
+ * Adding an existing entry updates its metadata.
+ * Fails if the path is forbidden, e.g. when it was removed in the current session,
+ * or if it is not allowed by the scope of the `persisted-scope:allow-add` permission.
+ *
+ * @example
+ * ```typescript
+ * import { open } from '@tauri-apps/plugin-dialog';
+ * import { add } from '@tauri-apps/plugin-persisted-scope';
+ * const folder = await open({ directory: true, recursive: true });
+ * if (folder) {
+ *   await add('fs', folder, {
+ *     target: 'recursiveDirectory',
+ *     origin: 'dialog',
+ *     expiry: { days: 30 }
+ *   });
+ * }
+ * ```
+ */
+async function add(
+  scope: ScopeKind,
+  path: string,
+  options?: AddOptions
+): Promise<void> {
+  await invoke('plugin:persisted-scope|add', { scope, path, options })
+}
+
+/**
+ * Removes the allowed entry with the given pattern.
+ *
+ * Removed entries stay forbidden until the app is restarted.
+ * Fails for forbidden entries, which can only be removed from Rust.
+ */
+async function remove(scope: ScopeKind, pattern: string): Promise<void> {
+  await invoke('plugin:persisted-scope|remove', { scope, pattern })
+}
+
+/**
+ * Removes the allowed entries that expired.
+ *
+ * @returns The removed entries.
+ */
+async function prune(scope: ScopeKind): Promise<ScopeEntry[]> {
+  return await invoke('plugin:persisted-scope|prune', { scope })
+}
+
+/**
+ * Listens to the scope changes, including the paths allowed by other plugins such as the dialog.
+ */
+async function onScopeChange(
+  handler: (scope: ScopeKind) => void
+): Promise<UnlistenFn> {
+  return await listen<{ scope: ScopeKind }>(
+    'persisted-scope://changed',
+    (event) => {
+      handler(event.payload.scope)
+    }
+  )
+}
+
+export { list, add, remove, prune, onScopeChange }
@@ -0,0 +1,69 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use tauri::{command, ipc::CommandScope, utils::config::FsScope, AppHandle, Runtime, State};
+use tauri_plugin_fs::Entry;
+
+use crate::{AddOptions, Error, PersistedScope, Result, ScopeEntry, ScopeKind};
+
+#[command]
+pub(crate) async fn list<R: Runtime>(
+    persisted_scope: State<'_, PersistedScope<R>>,
+    scope: ScopeKind,
+) -> Result<Vec<ScopeEntry>> {
+    persisted_scope.entries(scope)
+}
+
+/// Allows access to the path, which must be allowed by the scope of the permission
+/// so the webview can't grant itself access to arbitrary paths.
+#[command]
+pub(crate) async fn add<R: Runtime>(
+    app: AppHandle<R>,
+    persisted_scope: State<'_, PersistedScope<R>>,
+    command_scope: CommandScope<Entry>,
+    scope: ScopeKind,
+    path: PathBuf,
+    options: Option<AddOptions>,
+) -> Result<()> {
+    let allows = paths(command_scope.allows());
+    if allows.is_empty() {
+        return Err(Error::Forbidden(path));
+    }
+    let grantable = tauri::fs::Scope::new(
+        &app,
+        &FsScope::Scope {
+            allow: allows,
+            deny: paths(command_scope.denies()),
+            require_literal_leading_dot: None,
+        },
+    )?;
+    if !grantable.is_allowed(&path) {
+        return Err(Error::Forbidden(path));
+    }
+
+    persisted_scope.add(scope, path, options.unwrap_or_default())
+}
+
+#[command]
+pub(crate) async fn remove<R: Runtime>(
+    persisted_scope: State<'_, PersistedScope<R>>,
+    scope: ScopeKind,
+    pattern: String,
+) -> Result<()> {
+    persisted_scope.remove_allowed(scope, &pattern)
+}
+
+#[command]
+pub(crate) async fn prune<R: Runtime>(
+    persisted_scope: State<'_, PersistedScope<R>>,
+    scope: ScopeKind,
+) -> Result<Vec<ScopeEntry>> {
+    persisted_scope.prune(scope)
+}
+
+fn paths(entries: &[Entry]) -> Vec<PathBuf> {
+    entries.iter().filter_map(|e| e.path.clone()).collect()
+}
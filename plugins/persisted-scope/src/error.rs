@@ -0,0 +1,45 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use serde::{ser::Serializer, Serialize};
+
+use crate::ScopeKind;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
+    #[error(transparent)]
+    Bincode(#[from] Box<bincode::ErrorKind>),
+    /// The scope is not available, e.g. the `fs` plugin is not registered.
+    #[error("the {0} scope is not available")]
+    ScopeNotAvailable(ScopeKind),
+    /// The scope file header is truncated.
+    #[error("the scope file is corrupted")]
+    Corrupted,
+    /// The scope file was written by a newer version of the plugin.
+    #[error("unsupported scope file version: {0}")]
+    UnsupportedVersion(u32),
+    /// The path is forbidden, e.g. it was removed in the current session.
+    #[error("path is forbidden: {0}")]
+    Forbidden(PathBuf),
+    /// The pattern is not an allowed entry, the forbidden entries can only be removed from Rust.
+    #[error("not an allowed scope entry: {0}")]
+    NotAllowed(String),
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
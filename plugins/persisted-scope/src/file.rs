@@ -0,0 +1,130 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The scope file format.
+//!
+//! Files start with [`MAGIC`] and the little endian schema version, followed by the bincode encoded scope.
+//! Files written before the schema version was introduced have no header and are considered version 0.
+
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{Error, Result, Scope};
+
+/// The scope of versions 0 and 1, without the entry metadata.
+#[derive(Deserialize)]
+struct ScopeV1 {
+    allowed_paths: Vec<String>,
+    forbidden_patterns: Vec<String>,
+}
+
+impl From<ScopeV1> for Scope {
+    fn from(scope: ScopeV1) -> Self {
+        Self {
+            allowed_paths: scope.allowed_paths,
+            forbidden_patterns: scope.forbidden_patterns,
+            metadata: Default::default(),
+        }
+    }
+}
+
+const MAGIC: &[u8; 4] = b"TPSC";
+const VERSION: u32 = 2;
+
+/// The last scope file that was read successfully, refreshed on every launch.
+const BACKUP_EXTENSION: &str = "bak";
+/// The unreadable scope file, kept around for inspection.
+const CORRUPTED_EXTENSION: &str = "corrupted";
+/// The scope file being written, renamed once complete so a crash cannot leave a truncated file behind.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Appends the extension to the file name, `Path::with_extension` would replace the one of `.persisted-scope-asset`.
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(extension);
+    name.into()
+}
+
+/// The files written next to the scope file, which must not be accessible from the scope.
+pub(crate) fn siblings(path: &Path) -> [PathBuf; 3] {
+    [
+        sibling(path, BACKUP_EXTENSION),
+        sibling(path, CORRUPTED_EXTENSION),
+        sibling(path, TMP_EXTENSION),
+    ]
+}
+
+fn decode(data: &[u8]) -> Result<Scope> {
+    let (version, body) = match data.strip_prefix(MAGIC) {
+        Some(rest) if rest.len() >= 4 => {
+            let (version, body) = rest.split_at(4);
+            (u32::from_le_bytes(version.try_into().unwrap()), body)
+        }
+        Some(_) => return Err(Error::Corrupted),
+        None => (0, data),
+    };
+
+    match version {
+        // version 1 only introduced the header
+        0 | 1 => Ok(bincode::deserialize::<ScopeV1>(body)?.into()),
+        2 => Ok(bincode::deserialize(body)?),
+        _ => Err(Error::UnsupportedVersion(version)),
+    }
+}
+
+fn read(path: &Path) -> Result<Option<Scope>> {
+    match fs::read(path) {
+        Ok(data) => decode(&data).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Reads the scope file, recovering from the backup if it cannot be read.
+pub(crate) fn load(path: &Path) -> Option<Scope> {
+    match read(path) {
+        Ok(Some(scope)) => {
+            let _ = fs::copy(path, sibling(path, BACKUP_EXTENSION));
+            Some(scope)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("failed to read the scope file {}: {e}", path.display());
+            let _ = fs::rename(path, sibling(path, CORRUPTED_EXTENSION));
+
+            match read(&sibling(path, BACKUP_EXTENSION)) {
+                Ok(Some(scope)) => {
+                    log::warn!("restored the scope file {} from its backup", path.display());
+                    Some(scope)
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+pub(crate) fn save(path: &Path, scope: &Scope) -> Result<()> {
+    let mut data = MAGIC.to_vec();
+    data.extend(VERSION.to_le_bytes());
+    bincode::serialize_into(&mut data, scope)?;
+
+    write(path, &data)
+}
+
+/// Writes the file next to it first, renaming it once complete.
+pub(crate) fn write(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = sibling(path, TMP_EXTENSION);
+    fs::write(&tmp, data)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
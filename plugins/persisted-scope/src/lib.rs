@@ -13,21 +13,36 @@ use aho_corasick::AhoCorasick;
 use serde::{Deserialize, Serialize};
 
 use tauri::{
-    plugin::{Builder, TauriPlugin},
-    Manager, Runtime,
+    plugin::{Builder as PluginBuilder, TauriPlugin},
+    DragDropEvent, Manager, RunEvent, Runtime, WindowEvent,
 };
 use tauri_plugin_fs::FsExt;
 
+mod commands;
+mod error;
+mod file;
+mod scope;
+#[cfg(feature = "shell")]
+mod shell;
+
+pub use error::{Error, Result};
+pub use scope::{
+    AddOptions, Expiry, Origin, PersistedScope, ScopeChangedEvent, ScopeEntry, ScopeKind,
+    SCOPE_CHANGED_EVENT,
+};
+
 use std::{
-    fs::{create_dir_all, File},
-    io::Write,
-    path::Path,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 // Using 2 separate files so that we don't have to think about write conflicts and not break backwards compat.
 const SCOPE_STATE_FILENAME: &str = ".persisted-scope";
 #[cfg(feature = "protocol-asset")]
 const ASSET_SCOPE_STATE_FILENAME: &str = ".persisted-scope-asset";
+#[cfg(feature = "shell")]
+const SHELL_SCOPE_STATE_FILENAME: &str = ".persisted-scope-shell";
 
 // Most of these patterns are just added to try to fix broken files in the wild.
 // After a while we can hopefully reduce it to something like [r"[?]", r"[*]", r"\\?\\\?\"]
@@ -42,21 +57,16 @@ const PATTERNS: &[&str] = &[
 ];
 const REPLACE_WITH: &[&str] = &[r"[", r"]", r"?", r"*", r"\?", r"\\?\", r"\\?\"];
 
-#[derive(Debug, thiserror::Error)]
-enum Error {
-    #[error(transparent)]
-    Io(#[from] std::io::Error),
-    #[error(transparent)]
-    Tauri(#[from] tauri::Error),
-    #[error(transparent)]
-    Bincode(#[from] Box<bincode::ErrorKind>),
-}
-
-#[derive(Debug, Default, Deserialize, Serialize, Eq, PartialEq, Hash)]
-enum TargetType {
+/// The kind of path a scope entry applies to.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum TargetType {
+    /// A single file.
     #[default]
     File,
+    /// The direct children of a directory.
     Directory,
+    /// A directory and all of its descendants.
     RecursiveDirectory,
 }
 
@@ -64,6 +74,26 @@ enum TargetType {
 struct Scope {
     allowed_paths: Vec<String>,
     forbidden_patterns: Vec<String>,
+    /// The metadata of the allowed entries, keyed by their unescaped pattern.
+    metadata: HashMap<String, EntryMetadata>,
+}
+
+/// How and until when an allowed entry was granted.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+struct EntryMetadata {
+    origin: Origin,
+    /// Seconds since the Unix epoch.
+    granted_at: Option<u64>,
+    /// Seconds since the Unix epoch.
+    expires_at: Option<u64>,
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
 }
 
 fn fix_pattern(ac: &AhoCorasick, s: &str) -> String {
@@ -136,121 +166,260 @@ fn forbid_path(scope: &tauri::fs::Scope, path: &str) {
     }
 }
 
-fn save_scopes(scope: &tauri::fs::Scope, app_dir: &Path, scope_state_path: &Path) {
+/// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the persisted scopes.
+pub trait PersistedScopeExt<R: Runtime> {
+    fn persisted_scope(&self) -> &PersistedScope<R>;
+}
+
+impl<R: Runtime, T: Manager<R>> crate::PersistedScopeExt<R> for T {
+    fn persisted_scope(&self) -> &PersistedScope<R> {
+        self.state::<PersistedScope<R>>().inner()
+    }
+}
+
+/// Saves the scope to disk, except the patterns matching `excluded`.
+fn save_scopes(
+    scope: &tauri::fs::Scope,
+    scope_state_path: &Path,
+    metadata: &HashMap<String, EntryMetadata>,
+    excluded: impl Fn(&str) -> bool,
+) {
+    let allowed_paths: Vec<String> = scope
+        .allowed_patterns()
+        .into_iter()
+        .map(|p| p.to_string())
+        .filter(|p| !excluded(p))
+        .collect();
+
+    let ac = AhoCorasick::new(PATTERNS).unwrap();
+    let saved: HashSet<String> = allowed_paths.iter().map(|p| fix_pattern(&ac, p)).collect();
+
     let scope = Scope {
-        allowed_paths: scope
-            .allowed_patterns()
-            .into_iter()
-            .map(|p| p.to_string())
-            .collect(),
         forbidden_patterns: scope
             .forbidden_patterns()
             .into_iter()
             .map(|p| p.to_string())
+            .filter(|p| !excluded(p))
             .collect(),
+        metadata: metadata
+            .iter()
+            .filter(|(pattern, _)| saved.contains(*pattern))
+            .map(|(pattern, metadata)| (pattern.clone(), metadata.clone()))
+            .collect(),
+        allowed_paths,
     };
 
-    let _ = create_dir_all(app_dir)
-        .and_then(|_| File::create(scope_state_path))
-        .map_err(Error::Io)
-        .and_then(|mut f| {
-            f.write_all(&bincode::serialize(&scope).map_err(Error::from)?)
-                .map_err(Into::into)
-        });
+    if let Err(e) = file::save(scope_state_path, &scope) {
+        log::error!(
+            "failed to save the scope file {}: {e}",
+            scope_state_path.display()
+        );
+    }
 }
 
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("persisted-scope")
-        .setup(|app, _api| {
-            let fs_scope = app.try_fs_scope();
-            #[cfg(feature = "protocol-asset")]
-            let asset_protocol_scope = app.asset_protocol_scope();
-            let app = app.clone();
-            let app_dir = app.path().app_data_dir();
-
-            if let Ok(app_dir) = app_dir {
-                let fs_scope_state_path = app_dir.join(SCOPE_STATE_FILENAME);
-                #[cfg(feature = "protocol-asset")]
-                let asset_scope_state_path = app_dir.join(ASSET_SCOPE_STATE_FILENAME);
+type MigratePathFn = dyn Fn(&Path) -> Option<PathBuf> + Send + Sync;
+
+/// Restores the scope from its state file and migrates the persisted paths.
+///
+/// Expired entries are not restored. Returns the metadata of the restored entries.
+fn restore_scope(
+    scope: &tauri::fs::Scope,
+    scope_state_path: &Path,
+    migrate_path: Option<&MigratePathFn>,
+) -> HashMap<String, EntryMetadata> {
+    // Forbid the state file and the files written next to it so they can't be tampered with.
+    let _ = scope.forbid_file(scope_state_path);
+    for path in file::siblings(scope_state_path) {
+        let _ = scope.forbid_file(path);
+    }
 
-                if let Some(fs_scope) = &fs_scope {
-                     let _ = fs_scope.forbid_file(&fs_scope_state_path);
-                } else {
-                    #[cfg(debug_assertions)]
-                    eprintln!("Please make sure to register the `fs` plugin before the `persisted-scope` plugin!");
-                }
-                #[cfg(feature = "protocol-asset")]
-                let _ = asset_protocol_scope.forbid_file(&asset_scope_state_path);
+    let Some(persisted) = file::load(scope_state_path) else {
+        return HashMap::new();
+    };
 
-                // We're trying to fix broken .persisted-scope files seamlessly, so we'll be running this on the values read on the saved file.
-                // We will still save some semi-broken values because the scope events are quite spammy and we don't want to reduce runtime performance any further.
-                let ac = AhoCorasick::new(PATTERNS).unwrap(/* This should be impossible to fail since we're using a small static input */);
+    // We're trying to fix broken .persisted-scope files seamlessly, so we'll be running this on the values read on the saved file.
+    // We will still save some semi-broken values because the scope events are quite spammy and we don't want to reduce runtime performance any further.
+    let ac = AhoCorasick::new(PATTERNS).unwrap(/* This should be impossible to fail since we're using a small static input */);
+    let migrate = |pattern: &str| {
+        let pattern = fix_pattern(&ac, pattern);
+        let Some(path) = migrate_path.and_then(|migrate| migrate(fix_directory(&pattern))) else {
+            return pattern;
+        };
+        let path = match detect_scope_type(&pattern) {
+            TargetType::File => path,
+            TargetType::Directory => path.join(DIRECTORY_SUFFIX),
+            TargetType::RecursiveDirectory => path.join(RESURSIVE_DIRECTORY_SUFFIX),
+        };
+        path.to_string_lossy().into_owned()
+    };
 
-                if let Some(fs_scope) = &fs_scope {
-                    if fs_scope_state_path.exists() {
-                    let scope: Scope = std::fs::read(&fs_scope_state_path)
-                        .map_err(Error::from)
-                        .and_then(|scope| bincode::deserialize(&scope).map_err(Into::into))
-                        .unwrap_or_default();
-
-                    for allowed in &scope.allowed_paths {
-                        let allowed = fix_pattern(&ac, allowed);
-                        allow_path(fs_scope, &allowed);
-                    }
-                    for forbidden in &scope.forbidden_patterns {
-                        let forbidden = fix_pattern(&ac, forbidden);
-                        forbid_path(fs_scope, &forbidden);
-                    }
+    let now = now();
+    let mut metadata = HashMap::new();
+    for allowed in &persisted.allowed_paths {
+        let entry = persisted.metadata.get(&fix_pattern(&ac, allowed));
+        if entry.and_then(|m| m.expires_at).is_some_and(|t| t <= now) {
+            continue;
+        }
 
-                    // Manually save the fixed scopes to disk once.
-                    // This is needed to fix broken .peristed-scope files in case the app doesn't update the scope itself.
-                    save_scopes(fs_scope, &app_dir, &fs_scope_state_path);
-                }
-            }
+        let allowed = migrate(allowed);
+        allow_path(scope, &allowed);
+        if let Some(entry) = entry {
+            metadata.insert(allowed, entry.clone());
+        }
+    }
+    for forbidden in &persisted.forbidden_patterns {
+        forbid_path(scope, &migrate(forbidden));
+    }
 
-                #[cfg(feature = "protocol-asset")]
-                if asset_scope_state_path.exists() {
-                    let scope: Scope = std::fs::read(&asset_scope_state_path)
-                        .map_err(Error::from)
-                        .and_then(|scope| bincode::deserialize(&scope).map_err(Into::into))
-                        .unwrap_or_default();
-
-                    for allowed in &scope.allowed_paths {
-                        let allowed = fix_pattern(&ac, allowed);
-                        allow_path(&asset_protocol_scope, &allowed);
-                    }
-                    for forbidden in &scope.forbidden_patterns {
-                        let forbidden = fix_pattern(&ac, forbidden);
-                        forbid_path(&asset_protocol_scope, &forbidden);
-                    }
+    // Manually save the fixed scopes to disk once.
+    // This is needed to fix broken .peristed-scope files in case the app doesn't update the scope itself,
+    // and upgrades the file to the current schema version.
+    save_scopes(scope, scope_state_path, &metadata, |_| false);
 
-                    // Manually save the fixed scopes to disk once.
-                    save_scopes(&asset_protocol_scope, &app_dir, &asset_scope_state_path);
-                }
+    metadata
+}
 
+/// The persisted-scope plugin Builder.
+#[derive(Default)]
+pub struct Builder {
+    migrate_path: Option<Box<MigratePathFn>>,
+}
+
+impl Builder {
+    /// Create a new persisted-scope plugin Builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rewrites the persisted paths when the scopes are restored,
+    /// e.g. when a base directory was renamed between releases.
+    ///
+    /// The function receives the path of each entry, without the directory glob,
+    /// and returns `None` to keep it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let old = PathBuf::from("/home/user/Old Name");
+    /// let new = PathBuf::from("/home/user/New Name");
+    /// tauri_plugin_persisted_scope::Builder::new().migrate_path(move |path: &Path| {
+    ///     path.strip_prefix(&old).ok().map(|rest| new.join(rest))
+    /// });
+    /// ```
+    pub fn migrate_path<F>(mut self, migrate: F) -> Self
+    where
+        F: Fn(&Path) -> Option<PathBuf> + Send + Sync + 'static,
+    {
+        self.migrate_path = Some(Box::new(migrate));
+        self
+    }
+
+    /// Build and Initializes the plugin.
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        PluginBuilder::new("persisted-scope")
+            .invoke_handler(tauri::generate_handler![
+                commands::list,
+                commands::add,
+                commands::remove,
+                commands::prune
+            ])
+            .setup(move |app, _api| {
+                let fs_scope = app.try_fs_scope();
                 #[cfg(feature = "protocol-asset")]
-                let app_dir_ = app_dir.clone();
+                let asset_protocol_scope = app.asset_protocol_scope();
+                let app = app.clone();
+                let app_dir = app.path().app_data_dir();
+                app.manage(PersistedScope::new(app.clone(), app_dir.clone().ok()));
+
+                let Ok(app_dir) = app_dir else {
+                    return Ok(());
+                };
+                let migrate_path = self.migrate_path.as_deref();
 
                 if let Some(fs_scope) = &fs_scope {
+                    // the allowed programs could be changed from the webview otherwise
+                    #[cfg(feature = "shell")]
+                    {
+                        let shell_scope_state_path = app_dir.join(SHELL_SCOPE_STATE_FILENAME);
+                        let _ = fs_scope.forbid_file(&shell_scope_state_path);
+                        for path in file::siblings(&shell_scope_state_path) {
+                            let _ = fs_scope.forbid_file(path);
+                        }
+                    }
+
+                    let metadata =
+                        restore_scope(fs_scope, &app_dir.join(SCOPE_STATE_FILENAME), migrate_path);
+                    app.persisted_scope().set_metadata(ScopeKind::Fs, metadata);
+
                     let app_ = app.clone();
                     fs_scope.listen(move |event| {
-                        if let tauri::fs::Event::PathAllowed(_) = event {
-                            save_scopes(&app_.fs_scope(), &app_dir, &fs_scope_state_path);
+                        if let tauri::fs::Event::PathAllowed(_) | tauri::fs::Event::PathForbidden(_) =
+                            event
+                        {
+                            app_.persisted_scope().changed(ScopeKind::Fs);
                         }
                     });
+                } else {
+                    #[cfg(debug_assertions)]
+                    eprintln!("Please make sure to register the `fs` plugin before the `persisted-scope` plugin!");
                 }
 
                 #[cfg(feature = "protocol-asset")]
                 {
-                    let asset_protocol_scope_ = asset_protocol_scope.clone();
+                    let metadata = restore_scope(
+                        &asset_protocol_scope,
+                        &app_dir.join(ASSET_SCOPE_STATE_FILENAME),
+                        migrate_path,
+                    );
+                    app.persisted_scope()
+                        .set_metadata(ScopeKind::Asset, metadata);
+
+                    let app_ = app.clone();
                     asset_protocol_scope.listen(move |event| {
-                        if let tauri::scope::fs::Event::PathAllowed(_) = event {
-                            save_scopes(&asset_protocol_scope_, &app_dir_, &asset_scope_state_path);
+                        if let tauri::scope::fs::Event::PathAllowed(_)
+                        | tauri::scope::fs::Event::PathForbidden(_) = event
+                        {
+                            app_.persisted_scope().changed(ScopeKind::Asset);
+                        }
+                    });
+                }
+
+                #[cfg(feature = "shell")]
+                if let Some(shell) = app.try_state::<tauri_plugin_shell::Shell<R>>() {
+                    let shell_scope = shell.scope().clone();
+                    let path = app_dir.join(SHELL_SCOPE_STATE_FILENAME);
+                    shell::restore(&shell_scope, &path);
+
+                    let scope = shell_scope.clone();
+                    shell_scope.listen(move |_event| {
+                        if let Err(e) = shell::save(&scope, &path) {
+                            log::warn!("failed to save the shell scope: {e}");
                         }
                     });
+                } else {
+                    #[cfg(debug_assertions)]
+                    eprintln!("Please make sure to register the `shell` plugin before the `persisted-scope` plugin!");
+                }
+
+                Ok(())
+            })
+            .on_event(|app, event| {
+                // the fs plugin allows the dropped paths first since it must be registered before this plugin
+                if let RunEvent::WindowEvent {
+                    event: WindowEvent::DragDrop(DragDropEvent::Drop { paths, .. }),
+                    ..
+                } = event
+                {
+                    app.persisted_scope().record_drop(paths);
                 }
-            }
-            Ok(())
-        })
-        .build()
+            })
+            .build()
+    }
+}
+
+/// Initializes the plugin.
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::default().build()
 }
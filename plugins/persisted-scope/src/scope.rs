@@ -0,0 +1,453 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use aho_corasick::AhoCorasick;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_fs::FsExt;
+
+use crate::{
+    detect_scope_type, fix_directory, fix_pattern, forbid_path, now, save_scopes, EntryMetadata,
+    Error, Result, TargetType, PATTERNS, SCOPE_STATE_FILENAME,
+};
+#[cfg(feature = "protocol-asset")]
+use {crate::ASSET_SCOPE_STATE_FILENAME, tauri::Manager};
+
+/// Event emitted when a scope changes, with a [`ScopeChangedEvent`] payload.
+pub const SCOPE_CHANGED_EVENT: &str = "persisted-scope://changed";
+
+/// The scopes persisted by the plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScopeKind {
+    /// The scope of the `fs` plugin.
+    Fs,
+    /// The scope of the asset protocol.
+    #[cfg(feature = "protocol-asset")]
+    Asset,
+}
+
+impl fmt::Display for ScopeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fs => write!(f, "fs"),
+            #[cfg(feature = "protocol-asset")]
+            Self::Asset => write!(f, "asset"),
+        }
+    }
+}
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How an entry was granted.
+///
+/// Entries dropped on a window are detected automatically,
+/// other origins are set by the app when adding the entry, see [`AddOptions::origin`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Origin {
+    #[default]
+    Unknown,
+    /// A file or folder picked in a dialog.
+    Dialog,
+    /// A file or folder dropped on a window.
+    DragDrop,
+    /// A path opened with a deep link.
+    DeepLink,
+}
+
+/// How long an entry is granted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Expiry {
+    /// The entry is not persisted and is only granted until the app is restarted.
+    ///
+    /// Entries that are already persisted are kept.
+    Session,
+    /// The entry is granted for the given number of days.
+    ///
+    /// Expired entries are not restored when the app is reopened, see [`PersistedScope::prune`] to remove them at runtime.
+    Days(u32),
+}
+
+/// Options to add an entry, see [`PersistedScope::add`].
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddOptions {
+    #[serde(default)]
+    pub target: TargetType,
+    /// How the entry was granted. The origin of an existing entry is kept when unknown.
+    #[serde(default)]
+    pub origin: Origin,
+    /// The entry is persisted without expiry by default.
+    pub expiry: Option<Expiry>,
+}
+
+impl AddOptions {
+    pub fn new(target: TargetType) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn expiry(mut self, expiry: Expiry) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+}
+
+/// An allowed or forbidden path of a scope.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeEntry {
+    /// The glob pattern of the entry, used to remove it.
+    pub pattern: String,
+    pub path: PathBuf,
+    pub target: TargetType,
+    /// Whether the entry allows or forbids access to the path.
+    pub allowed: bool,
+    /// Whether the entry is only granted for the current session.
+    pub temporary: bool,
+    pub origin: Origin,
+    /// When the entry was granted, in seconds since the Unix epoch. Unknown for entries not added by this plugin.
+    pub granted_at: Option<u64>,
+    /// When the entry expires, in seconds since the Unix epoch.
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeChangedEvent {
+    pub scope: ScopeKind,
+}
+
+#[derive(Default)]
+struct SessionState {
+    /// Patterns only granted for the current session, which are not persisted.
+    temporary: HashSet<String>,
+    /// Patterns removed in the current session: the allowed patterns
+    /// and the patterns forbidden to revoke them, which are not persisted.
+    removed: HashSet<String>,
+    /// The metadata of the allowed entries, keyed by their unescaped pattern.
+    metadata: HashMap<String, EntryMetadata>,
+}
+
+/// Access to the persisted scopes.
+pub struct PersistedScope<R: Runtime> {
+    app: AppHandle<R>,
+    app_dir: Option<PathBuf>,
+    fs: Mutex<SessionState>,
+    #[cfg(feature = "protocol-asset")]
+    asset: Mutex<SessionState>,
+    /// Set while this API updates a scope so the scope listeners do not persist the intermediate state.
+    updating: AtomicBool,
+}
+
+/// Whether the unescaped pattern applies to the path, ignoring the directory glob.
+fn matches(pattern: &str, path: &Path, canonical: Option<&Path>) -> bool {
+    let pattern_path = fix_directory(pattern);
+    pattern_path == path || Some(pattern_path) == canonical
+}
+
+fn patterns<P: ToString>(patterns: HashSet<P>) -> HashSet<String> {
+    patterns.into_iter().map(|p| p.to_string()).collect()
+}
+
+impl<R: Runtime> PersistedScope<R> {
+    pub(crate) fn new(app: AppHandle<R>, app_dir: Option<PathBuf>) -> Self {
+        Self {
+            app,
+            app_dir,
+            fs: Default::default(),
+            #[cfg(feature = "protocol-asset")]
+            asset: Default::default(),
+            updating: AtomicBool::new(false),
+        }
+    }
+
+    fn scope(&self, kind: ScopeKind) -> Result<tauri::fs::Scope> {
+        match kind {
+            ScopeKind::Fs => self
+                .app
+                .try_fs_scope()
+                .ok_or(Error::ScopeNotAvailable(kind)),
+            #[cfg(feature = "protocol-asset")]
+            ScopeKind::Asset => Ok(self.app.asset_protocol_scope()),
+        }
+    }
+
+    fn state(&self, kind: ScopeKind) -> &Mutex<SessionState> {
+        match kind {
+            ScopeKind::Fs => &self.fs,
+            #[cfg(feature = "protocol-asset")]
+            ScopeKind::Asset => &self.asset,
+        }
+    }
+
+    pub(crate) fn set_metadata(&self, kind: ScopeKind, metadata: HashMap<String, EntryMetadata>) {
+        self.state(kind).lock().unwrap().metadata = metadata;
+    }
+
+    pub(crate) fn state_path(&self, kind: ScopeKind) -> Option<PathBuf> {
+        let filename = match kind {
+            ScopeKind::Fs => SCOPE_STATE_FILENAME,
+            #[cfg(feature = "protocol-asset")]
+            ScopeKind::Asset => ASSET_SCOPE_STATE_FILENAME,
+        };
+        self.app_dir.as_ref().map(|dir| dir.join(filename))
+    }
+
+    /// Lists the allowed and forbidden entries of the scope, including the ones defined in the app configuration.
+    pub fn entries(&self, kind: ScopeKind) -> Result<Vec<ScopeEntry>> {
+        let scope = self.scope(kind)?;
+        let state = self.state(kind).lock().unwrap();
+        let ac = AhoCorasick::new(PATTERNS).unwrap();
+
+        let entries = |patterns: HashSet<String>, allowed: bool| {
+            // directories are also added without their `*` suffix, list them once
+            let directories: HashSet<PathBuf> = patterns
+                .iter()
+                .filter(|p| detect_scope_type(p) != TargetType::File)
+                .map(|p| fix_directory(p).to_path_buf())
+                .collect();
+
+            patterns
+                .into_iter()
+                .filter(|p| !state.removed.contains(p))
+                .filter(|p| {
+                    detect_scope_type(p) != TargetType::File || !directories.contains(Path::new(p))
+                })
+                .map(|pattern| {
+                    let path = fix_pattern(&ac, &pattern);
+                    let metadata = state.metadata.get(&path).cloned().unwrap_or_default();
+                    ScopeEntry {
+                        path: fix_directory(&path).to_path_buf(),
+                        target: detect_scope_type(&path),
+                        allowed,
+                        temporary: state.temporary.contains(&pattern),
+                        origin: metadata.origin,
+                        granted_at: metadata.granted_at,
+                        expires_at: metadata.expires_at,
+                        pattern,
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut list = entries(patterns(scope.allowed_patterns()), true);
+        list.extend(entries(patterns(scope.forbidden_patterns()), false));
+        Ok(list)
+    }
+
+    /// Allows access to the path.
+    ///
+    /// Adding an existing entry updates its metadata.
+    pub fn add<P: AsRef<Path>>(&self, kind: ScopeKind, path: P, options: AddOptions) -> Result<()> {
+        let path = path.as_ref();
+        let scope = self.scope(kind)?;
+        let before = patterns(scope.allowed_patterns());
+
+        self.updating.store(true, Ordering::SeqCst);
+        let result = match options.target {
+            TargetType::File => scope.allow_file(path),
+            TargetType::Directory => scope.allow_directory(path, false),
+            TargetType::RecursiveDirectory => scope.allow_directory(path, true),
+        };
+        self.updating.store(false, Ordering::SeqCst);
+        result?;
+
+        let now = now();
+        let expires_at = match options.expiry {
+            Some(Expiry::Days(days)) => Some(now + u64::from(days) * SECONDS_PER_DAY),
+            _ => None,
+        };
+        // the scope stores the canonical path when it exists
+        let canonical = path.canonicalize().ok();
+        let ac = AhoCorasick::new(PATTERNS).unwrap();
+
+        {
+            let mut state = self.state(kind).lock().unwrap();
+            for pattern in patterns(scope.allowed_patterns()) {
+                let added = !before.contains(&pattern);
+                let key = fix_pattern(&ac, &pattern);
+                if !added && !matches(&key, path, canonical.as_deref()) {
+                    continue;
+                }
+
+                if added && options.expiry == Some(Expiry::Session) {
+                    state.temporary.insert(pattern);
+                }
+                let metadata = state.metadata.entry(key).or_default();
+                if options.origin != Origin::Unknown {
+                    metadata.origin = options.origin;
+                }
+                metadata.granted_at = Some(now);
+                if expires_at.is_some() {
+                    metadata.expires_at = expires_at;
+                }
+            }
+        }
+
+        self.changed(kind);
+
+        // forbidden patterns take precedence, including the ones removed in this session
+        if !scope.is_allowed(path) {
+            return Err(Error::Forbidden(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Removes the entry with the given pattern, see [`ScopeEntry::pattern`].
+    ///
+    /// Allowed entries are revoked by forbidding the path for the rest of the session,
+    /// so they cannot be added again until the app is restarted.
+    /// Removed forbidden entries are only allowed again once the app is restarted.
+    pub fn remove(&self, kind: ScopeKind, pattern: &str) -> Result<()> {
+        let scope = self.scope(kind)?;
+        let allowed = patterns(scope.allowed_patterns());
+
+        if allowed.contains(pattern) {
+            let ac = AhoCorasick::new(PATTERNS).unwrap();
+            let key = fix_pattern(&ac, pattern);
+            let before = patterns(scope.forbidden_patterns());
+
+            self.updating.store(true, Ordering::SeqCst);
+            forbid_path(&scope, &key);
+            self.updating.store(false, Ordering::SeqCst);
+
+            let forbidden = patterns(scope.forbidden_patterns());
+            let mut state = self.state(kind).lock().unwrap();
+            state
+                .removed
+                .extend(forbidden.into_iter().filter(|p| !before.contains(p)));
+
+            // directories are also allowed without their `*` suffix, remove them together
+            let path = fix_directory(&key);
+            for pattern in allowed {
+                let key = fix_pattern(&ac, &pattern);
+                if fix_directory(&key) == path {
+                    state.metadata.remove(&key);
+                    state.temporary.remove(&pattern);
+                    state.removed.insert(pattern);
+                }
+            }
+        } else {
+            self.state(kind)
+                .lock()
+                .unwrap()
+                .removed
+                .insert(pattern.into());
+        }
+
+        self.changed(kind);
+        Ok(())
+    }
+
+    /// Removes an allowed entry like [`Self::remove`], failing for the forbidden ones
+    /// so the webview can't lift the restrictions of the app.
+    pub(crate) fn remove_allowed(&self, kind: ScopeKind, pattern: &str) -> Result<()> {
+        let scope = self.scope(kind)?;
+        if !patterns(scope.allowed_patterns()).contains(pattern) {
+            return Err(Error::NotAllowed(pattern.into()));
+        }
+        self.remove(kind, pattern)
+    }
+
+    /// Removes the allowed entries that expired, see [`Expiry::Days`].
+    ///
+    /// Returns the removed entries.
+    pub fn prune(&self, kind: ScopeKind) -> Result<Vec<ScopeEntry>> {
+        let now = now();
+        let expired: Vec<ScopeEntry> = self
+            .entries(kind)?
+            .into_iter()
+            .filter(|entry| entry.allowed && entry.expires_at.is_some_and(|t| t <= now))
+            .collect();
+
+        for entry in &expired {
+            self.remove(kind, &entry.pattern)?;
+        }
+        Ok(expired)
+    }
+
+    /// Records the origin of the entries allowed for the paths dropped on a window.
+    pub(crate) fn record_drop(&self, paths: &[PathBuf]) {
+        let kinds = [
+            ScopeKind::Fs,
+            #[cfg(feature = "protocol-asset")]
+            ScopeKind::Asset,
+        ];
+        let ac = AhoCorasick::new(PATTERNS).unwrap();
+        let now = now();
+
+        for kind in kinds {
+            let Ok(scope) = self.scope(kind) else {
+                continue;
+            };
+
+            let mut recorded = false;
+            {
+                let mut state = self.state(kind).lock().unwrap();
+                for pattern in patterns(scope.allowed_patterns()) {
+                    let key = fix_pattern(&ac, &pattern);
+                    if state.metadata.contains_key(&key)
+                        || !paths
+                            .iter()
+                            .any(|path| matches(&key, path, path.canonicalize().ok().as_deref()))
+                    {
+                        continue;
+                    }
+
+                    state.metadata.insert(
+                        key,
+                        EntryMetadata {
+                            origin: Origin::DragDrop,
+                            granted_at: Some(now),
+                            expires_at: None,
+                        },
+                    );
+                    recorded = true;
+                }
+            }
+
+            if recorded {
+                self.changed(kind);
+            }
+        }
+    }
+
+    /// Persists the scope and emits the [`SCOPE_CHANGED_EVENT`].
+    pub(crate) fn changed(&self, kind: ScopeKind) {
+        if self.updating.load(Ordering::SeqCst) {
+            return;
+        }
+
+        if let (Ok(scope), Some(path)) = (self.scope(kind), self.state_path(kind)) {
+            let state = self.state(kind).lock().unwrap();
+            save_scopes(&scope, &path, &state.metadata, |pattern| {
+                state.temporary.contains(pattern) || state.removed.contains(pattern)
+            });
+        }
+
+        let _ = self
+            .app
+            .emit(SCOPE_CHANGED_EVENT, ScopeChangedEvent { scope: kind });
+    }
+}
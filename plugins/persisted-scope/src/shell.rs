@@ -0,0 +1,99 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The programs allowed at runtime with the `DynamicScope` of the shell plugin.
+
+use std::{fs, path::Path, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri_plugin_shell::{DynamicScope, ScopeAllowedArg};
+
+use crate::{file, Result};
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ShellScope {
+    programs: Vec<Program>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Program {
+    path: PathBuf,
+    /// Any arguments are allowed if `None`.
+    args: Option<Vec<Arg>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+enum Arg {
+    Fixed(String),
+    /// The regex the value must match, already anchored if needed.
+    Validator(String),
+}
+
+/// Allows the saved programs again, skipping the ones whose validators are invalid.
+pub(crate) fn restore(scope: &DynamicScope, path: &Path) {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            log::warn!(
+                "failed to read the shell scope file {}: {e}",
+                path.display()
+            );
+            return;
+        }
+    };
+    let persisted: ShellScope = match bincode::deserialize(&data) {
+        Ok(persisted) => persisted,
+        Err(e) => {
+            log::warn!(
+                "failed to read the shell scope file {}: {e}",
+                path.display()
+            );
+            return;
+        }
+    };
+
+    for program in persisted.programs {
+        let args = program
+            .args
+            .map(|args| {
+                args.into_iter()
+                    .map(|arg| match arg {
+                        Arg::Fixed(fixed) => Ok(ScopeAllowedArg::Fixed(fixed)),
+                        Arg::Validator(validator) => ScopeAllowedArg::raw_var(&validator),
+                    })
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .transpose();
+        match args {
+            Ok(args) => scope.allow_program(program.path, args),
+            Err(e) => log::warn!(
+                "failed to restore the allowed program {}: {e}",
+                program.path.display()
+            ),
+        }
+    }
+}
+
+pub(crate) fn save(scope: &DynamicScope, path: &Path) -> Result<()> {
+    let programs = scope
+        .allowed_programs()
+        .into_iter()
+        .map(|command| Program {
+            path: command.command,
+            args: command.args.map(|args| {
+                args.into_iter()
+                    .map(|arg| match arg {
+                        ScopeAllowedArg::Fixed(fixed) => Arg::Fixed(fixed),
+                        ScopeAllowedArg::Var { validator } => {
+                            Arg::Validator(validator.as_str().into())
+                        }
+                    })
+                    .collect()
+            }),
+        })
+        .collect();
+
+    file::write(path, &bincode::serialize(&ShellScope { programs })?)
+}
@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_POSITIONER__=function(t){"use strict";async function o(t,o={},e){return window.__TAURI_INTERNALS__.invoke(t,o,e)}var e;return"function"==typeof SuppressedError&&SuppressedError,t.Position=void 0,(e=t.Position||(t.Position={}))[e.TopLeft=0]="TopLeft",e[e.TopRight=1]="TopRight",e[e.BottomLeft=2]="BottomLeft",e[e.BottomRight=3]="BottomRight",e[e.TopCenter=4]="TopCenter",e[e.BottomCenter=5]="BottomCenter",e[e.LeftCenter=6]="LeftCenter",e[e.RightCenter=7]="RightCenter",e[e.Center=8]="Center",e[e.TrayLeft=9]="TrayLeft",e[e.TrayBottomLeft=10]="TrayBottomLeft",e[e.TrayRight=11]="TrayRight",e[e.TrayBottomRight=12]="TrayBottomRight",e[e.TrayCenter=13]="TrayCenter",e[e.TrayBottomCenter=14]="TrayBottomCenter",t.handleIconState=async function(t){await o("plugin:positioner|set_tray_icon_state",{position:t.rect.position,size:t.rect.size})},t.moveWindow=async function(t){await o("plugin:positioner|move_window",{position:t})},t.moveWindowConstrained=async function(t){await o("plugin:positioner|move_window_constrained",{position:t})},t}({});Object.defineProperty(window.__TAURI__,"positioner",{value:__TAURI_PLUGIN_POSITIONER__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_POSITIONER__=function(t){"use strict";async function o(t,o={},e){return window.__TAURI_INTERNALS__.invoke(t,o,e)}var e,n,i;function r(t){return"number"==typeof t?{pixels:t}:t}function c(t){return{offset:t?.offset?{x:r(t.offset.x),y:r(t.offset.y)}:void 0,anchor:t?.anchor,clamp:t?.clamp,rememberMonitor:t?.rememberMonitor}}return"function"==typeof SuppressedError&&SuppressedError,t.Anchor=void 0,t.Position=void 0,t.RelativePosition=void 0,(n=t.Anchor||(t.Anchor={}))[n.TopLeft=0]="TopLeft",n[n.TopCenter=1]="TopCenter",n[n.TopRight=2]="TopRight",n[n.LeftCenter=3]="LeftCenter",n[n.Center=4]="Center",n[n.RightCenter=5]="RightCenter",n[n.BottomLeft=6]="BottomLeft",n[n.BottomCenter=7]="BottomCenter",n[n.BottomRight=8]="BottomRight",(e=t.Position||(t.Position={}))[e.TopLeft=0]="TopLeft",e[e.TopRight=1]="TopRight",e[e.BottomLeft=2]="BottomLeft",e[e.BottomRight=3]="BottomRight",e[e.TopCenter=4]="TopCenter",e[e.BottomCenter=5]="BottomCenter",e[e.LeftCenter=6]="LeftCenter",e[e.RightCenter=7]="RightCenter",e[e.Center=8]="Center",e[e.TrayLeft=9]="TrayLeft",e[e.TrayBottomLeft=10]="TrayBottomLeft",e[e.TrayRight=11]="TrayRight",e[e.TrayBottomRight=12]="TrayBottomRight",e[e.TrayCenter=13]="TrayCenter",e[e.TrayBottomCenter=14]="TrayBottomCenter",(i=t.RelativePosition||(t.RelativePosition={}))[i.Center=0]="Center",i[i.Top=1]="Top",i[i.Bottom=2]="Bottom",i[i.Left=3]="Left",i[i.Right=4]="Right",i[i.Cascade=5]="Cascade",t.handleIconState=async function(t){await o("plugin:positioner|set_tray_icon_state",{position:t.rect.position,size:t.rect.size})},t.moveWindow=async function(t,e){await o("plugin:positioner|move_window",{position:t,...c(e)})},t.moveWindowConstrained=async function(t,e){await o("plugin:positioner|move_window_constrained",{position:t,...c(e)})},t.moveWindowRelative=async function(t,e,n){await o("plugin:positioner|move_window_relative",{parent:t,position:e,...c(n)})},t}({});Object.defineProperty(window.__TAURI__,"positioner",{value:__TAURI_PLUGIN_POSITIONER__})}
@@ -4,6 +4,7 @@
 
 const COMMANDS: &[&str] = &[
     "move_window",
+    "move_window_relative",
     "move_window_constrained",
     "set_tray_icon_state",
 ];
@@ -27,15 +27,89 @@ export enum Position {
   TrayBottomCenter
 }
 
+/**
+ * The point of the window placed at the target point of a {@link Position}.
+ */
+export enum Anchor {
+  TopLeft = 0,
+  TopCenter,
+  TopRight,
+  LeftCenter,
+  Center,
+  RightCenter,
+  BottomLeft,
+  BottomCenter,
+  BottomRight
+}
+
+/**
+ * A distance along one axis: logical pixels, scaled by the scale factor of the target monitor,
+ * or a percentage of the target monitor width or height.
+ */
+export type Distance = number | { percent: number }
+
+export interface MoveOptions {
+  /** Offset added to the target point of the position, e.g. `{ x: 0, y: -8 }`. */
+  offset?: { x: Distance; y: Distance }
+  /**
+   * The point of the window placed at the target point.
+   * Defaults to the anchor that keeps the window inside the screen, e.g. {@link Anchor.TopRight} for {@link Position.TopRight}.
+   */
+  anchor?: Anchor
+  /**
+   * Keeps the window fully within the work area of the target monitor,
+   * the area not covered by the taskbar, dock or menubar.
+   */
+  clamp?: boolean
+  /**
+   * Places screen positions on the monitor previously used for this window, if still connected,
+   * instead of the current monitor. The monitor is remembered across sessions.
+   *
+   * Not supported by {@link moveWindowRelative}.
+   */
+  rememberMonitor?: boolean
+}
+
+function mapDistance(
+  distance: Distance
+): { pixels: number } | { percent: number } {
+  return typeof distance === 'number' ? { pixels: distance } : distance
+}
+
+function mapOptions(options?: MoveOptions): Record<string, unknown> {
+  return {
+    offset: options?.offset
+      ? {
+          x: mapDistance(options.offset.x),
+          y: mapDistance(options.offset.y)
+        }
+      : undefined,
+    anchor: options?.anchor,
+    clamp: options?.clamp,
+    rememberMonitor: options?.rememberMonitor
+  }
+}
+
 /**
  * Moves the `Window` to the given {@link Position} using `WindowExt.move_window()`
  * All positions are relative to the **current** screen.
  *
+ * ```javascript
+ * import { moveWindow, Position, Anchor } from '@tauri-apps/plugin-positioner';
+ * // 8 pixels above the tray icon, anchored at the window bottom-center
+ * await moveWindow(Position.TrayCenter, { offset: { x: 0, y: -8 }, anchor: Anchor.BottomCenter });
+ * ```
+ *
  * @param to The {@link Position} to move to.
+ * @param options Offset and anchor of the window.
  */
-export async function moveWindow(to: Position): Promise<void> {
+export async function moveWindow(
+  to: Position,
+  options?: MoveOptions
+): Promise<void> {
   await invoke('plugin:positioner|move_window', {
-    position: to
+    position: to,
+    ...mapOptions(options)
   })
 }
 
@@ -45,10 +119,61 @@ export async function moveWindow(to: Position): Promise<void> {
  * This move operation constrains the window to the screen dimensions in case of
  * tray-icon positions.
  * @param to The (tray) {@link Position} to move to.
+ * @param options Offset and anchor of the window.
  */
-export async function moveWindowConstrained(to: Position): Promise<void> {
+export async function moveWindowConstrained(
+  to: Position,
+  options?: MoveOptions
+): Promise<void> {
   await invoke('plugin:positioner|move_window_constrained', {
-    position: to
+    position: to,
+    ...mapOptions(options)
+  })
+}
+
+/**
+ * Window positions relative to another window.
+ */
+export enum RelativePosition {
+  /** Centered over the parent window. */
+  Center = 0,
+  /** Snapped above the parent window, centered horizontally. */
+  Top,
+  /** Snapped below the parent window, centered horizontally. */
+  Bottom,
+  /** Snapped to the left of the parent window, centered vertically. */
+  Left,
+  /** Snapped to the right of the parent window, centered vertically. */
+  Right,
+  /**
+   * Offset from the parent window top-left corner, so cascaded windows stay visible.
+   * Use the previously cascaded window as parent to cascade several windows.
+   */
+  Cascade
+}
+
+/**
+ * Moves the `Window` relative to the window with the given label using `WindowExt.move_window_relative()`,
+ * e.g. to center a dialog over its parent or snap a tool palette to its edge.
+ *
+ * ```javascript
+ * import { moveWindowRelative, RelativePosition } from '@tauri-apps/plugin-positioner';
+ * await moveWindowRelative('main', RelativePosition.Center);
+ * ```
+ *
+ * @param parent The label of the parent window.
+ * @param to The {@link RelativePosition} to move to.
+ * @param options Offset and anchor of the window.
+ */
+export async function moveWindowRelative(
+  parent: string,
+  to: RelativePosition,
+  options?: MoveOptions
+): Promise<void> {
+  await invoke('plugin:positioner|move_window_relative', {
+    parent,
+    position: to,
+    ...mapOptions(options)
   })
 }
 
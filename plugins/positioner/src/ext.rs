@@ -3,17 +3,22 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use crate::work_area::{work_area, Rect};
+use crate::Monitors;
 #[cfg(feature = "tray-icon")]
 use crate::Tray;
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
-#[cfg(feature = "tray-icon")]
 use tauri::Manager;
-#[cfg(feature = "tray-icon")]
-use tauri::Monitor;
-use tauri::{PhysicalPosition, PhysicalSize, Result, Runtime, WebviewWindow, Window};
+use tauri::{Monitor, PhysicalPosition, PhysicalSize, Result, Runtime, WebviewWindow, Window};
 
 /// Well known window positions.
-#[derive(Debug, Deserialize_repr)]
+///
+/// Tray positions place the window next to the tray icon, on the side of the taskbar or menubar facing
+/// the inside of the monitor. The `Left`, `Center` and `Right` variants align the window along the taskbar,
+/// from its top or left end for vertical and horizontal taskbars respectively.
+/// The `Bottom` variants of horizontal taskbars align the top of the window with the top of the icon.
+#[derive(Debug, Clone, Copy, Deserialize_repr)]
 #[repr(u16)]
 pub enum Position {
     TopLeft = 0,
@@ -39,12 +44,213 @@ pub enum Position {
     TrayBottomCenter,
 }
 
+/// Offset of [`RelativePosition::Cascade`] from the parent window in logical pixels.
+const CASCADE_STEP: f64 = 32.0;
+
+/// Window positions relative to another window.
+#[derive(Debug, Clone, Copy, Deserialize_repr)]
+#[repr(u16)]
+pub enum RelativePosition {
+    /// Centered over the parent window.
+    Center = 0,
+    /// Snapped above the parent window, centered horizontally.
+    Top,
+    /// Snapped below the parent window, centered horizontally.
+    Bottom,
+    /// Snapped to the left of the parent window, centered vertically.
+    Left,
+    /// Snapped to the right of the parent window, centered vertically.
+    Right,
+    /// Offset from the parent window top-left corner, so cascaded windows stay visible.
+    /// Use the previously cascaded window as parent to cascade several windows.
+    Cascade,
+}
+
+/// The point of the window placed at the target point of a [`Position`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize_repr)]
+#[repr(u16)]
+pub enum Anchor {
+    TopLeft = 0,
+    TopCenter,
+    TopRight,
+    LeftCenter,
+    Center,
+    RightCenter,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+/// A distance along one axis.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Distance {
+    /// Logical pixels, scaled by the scale factor of the target monitor.
+    Pixels(f64),
+    /// Percentage of the target monitor width or height.
+    Percent(f64),
+}
+
+/// Offset added to the target point of a [`Position`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Offset {
+    pub x: Distance,
+    pub y: Distance,
+}
+
+impl Offset {
+    /// An offset in logical pixels.
+    pub fn pixels(x: f64, y: f64) -> Self {
+        Self {
+            x: Distance::Pixels(x),
+            y: Distance::Pixels(y),
+        }
+    }
+
+    /// An offset in percentage of the target monitor size.
+    pub fn percent(x: f64, y: f64) -> Self {
+        Self {
+            x: Distance::Percent(x),
+            y: Distance::Percent(y),
+        }
+    }
+}
+
+/// A [`Position`] with an optional offset and anchor.
+///
+/// Each position has a target point, e.g. the top-right corner of the screen for [`Position::TopRight`]
+/// or the center of the tray icon edge for [`Position::TrayCenter`], and a default anchor
+/// that keeps the window inside the screen, e.g. the window top-right corner.
+///
+/// ```
+/// use tauri_plugin_positioner::{Anchor, Offset, Position, PositionOptions};
+///
+/// // 8 pixels above the tray icon, anchored at the window bottom-center
+/// # #[cfg(feature = "tray-icon")]
+/// let options = PositionOptions::new(Position::TrayCenter)
+///     .offset(Offset::pixels(0.0, -8.0))
+///     .anchor(Anchor::BottomCenter);
+/// ```
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionOptions {
+    pub position: Position,
+    pub offset: Option<Offset>,
+    pub anchor: Option<Anchor>,
+    /// Keeps the window fully within the work area of the target monitor,
+    /// the area not covered by the taskbar, dock or menubar.
+    #[serde(default)]
+    pub clamp: bool,
+    /// Places screen positions on the monitor previously used for this window, if still connected,
+    /// instead of the current monitor. The monitor is remembered across sessions.
+    ///
+    /// Requires registering the plugin.
+    #[serde(default)]
+    pub remember_monitor: bool,
+}
+
+impl PositionOptions {
+    pub fn new(position: Position) -> Self {
+        Self {
+            position,
+            offset: None,
+            anchor: None,
+            clamp: false,
+            remember_monitor: false,
+        }
+    }
+
+    /// Keeps the window fully within the work area of the target monitor.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Places screen positions on the monitor remembered for this window.
+    pub fn remember_monitor(mut self, remember_monitor: bool) -> Self {
+        self.remember_monitor = remember_monitor;
+        self
+    }
+
+    /// Adds an offset to the target point.
+    pub fn offset(mut self, offset: Offset) -> Self {
+        self.offset.replace(offset);
+        self
+    }
+
+    /// Sets the point of the window placed at the target point.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor.replace(anchor);
+        self
+    }
+}
+
+impl From<Position> for PositionOptions {
+    fn from(position: Position) -> Self {
+        Self::new(position)
+    }
+}
+
+/// A [`RelativePosition`] with an optional offset and anchor, see [`PositionOptions`].
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RelativePositionOptions {
+    pub position: RelativePosition,
+    pub offset: Option<Offset>,
+    pub anchor: Option<Anchor>,
+    /// Keeps the window fully within the work area of the parent window monitor.
+    #[serde(default)]
+    pub clamp: bool,
+}
+
+impl RelativePositionOptions {
+    pub fn new(position: RelativePosition) -> Self {
+        Self {
+            position,
+            offset: None,
+            anchor: None,
+            clamp: false,
+        }
+    }
+
+    /// Keeps the window fully within the work area of the parent window monitor.
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Adds an offset to the target point.
+    pub fn offset(mut self, offset: Offset) -> Self {
+        self.offset.replace(offset);
+        self
+    }
+
+    /// Sets the point of the window placed at the target point.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor.replace(anchor);
+        self
+    }
+}
+
+impl From<RelativePosition> for RelativePositionOptions {
+    fn from(position: RelativePosition) -> Self {
+        Self::new(position)
+    }
+}
+
 /// A [`Window`] extension that provides extra methods related to positioning.
 pub trait WindowExt {
     /// Moves the [`Window`] to the given [`Position`]
     ///
     /// All (non-tray) positions are relative to the **current** screen.
+    /// Tray positions are relative to the screen the tray icon is located on.
     fn move_window(&self, position: Position) -> Result<()>;
+    /// Moves the [`Window`] to the given [`Position`] with a custom offset and anchor.
+    ///
+    /// See [`PositionOptions`].
+    fn move_window_with(&self, options: PositionOptions) -> Result<()>;
+    /// Moves the [`Window`] relative to the window with the given label,
+    /// e.g. to center a dialog over its parent or snap a tool palette to its edge.
+    fn move_window_relative(&self, parent: &str, options: RelativePositionOptions) -> Result<()>;
     #[cfg(feature = "tray-icon")]
     /// Moves the [`Window`] to the given [`Position`] while constraining Tray Positions to the dimensions of the screen.
     ///
@@ -53,6 +259,9 @@ pub trait WindowExt {
     /// This method allows you to position your Tray Windows without having them
     /// cut off on the screen borders.
     fn move_window_constrained(&self, position: Position) -> Result<()>;
+    #[cfg(feature = "tray-icon")]
+    /// Same as [`Self::move_window_constrained`] with a custom offset and anchor.
+    fn move_window_constrained_with(&self, options: PositionOptions) -> Result<()>;
 }
 
 impl<R: Runtime> WindowExt for WebviewWindow<R> {
@@ -60,248 +269,479 @@ impl<R: Runtime> WindowExt for WebviewWindow<R> {
         self.as_ref().window().move_window(pos)
     }
 
+    fn move_window_with(&self, options: PositionOptions) -> Result<()> {
+        self.as_ref().window().move_window_with(options)
+    }
+
+    fn move_window_relative(&self, parent: &str, options: RelativePositionOptions) -> Result<()> {
+        self.as_ref().window().move_window_relative(parent, options)
+    }
+
     #[cfg(feature = "tray-icon")]
     fn move_window_constrained(&self, position: Position) -> Result<()> {
         self.as_ref().window().move_window_constrained(position)
     }
+
+    #[cfg(feature = "tray-icon")]
+    fn move_window_constrained_with(&self, options: PositionOptions) -> Result<()> {
+        self.as_ref().window().move_window_constrained_with(options)
+    }
 }
 
 impl<R: Runtime> WindowExt for Window<R> {
     #[cfg(feature = "tray-icon")]
     fn move_window_constrained(&self, position: Position) -> Result<()> {
+        self.move_window_constrained_with(position.into())
+    }
+
+    #[cfg(feature = "tray-icon")]
+    fn move_window_constrained_with(&self, options: PositionOptions) -> Result<()> {
         // Diverge to basic move_window, if the position is not a tray position
-        if !matches!(
-            position,
-            Position::TrayLeft
-                | Position::TrayBottomLeft
-                | Position::TrayRight
-                | Position::TrayBottomRight
-                | Position::TrayCenter
-                | Position::TrayBottomCenter
-        ) {
-            return self.move_window(position);
+        if !options.position.is_tray() {
+            return self.move_window_with(options);
         }
 
-        let window_position = calculate_position(self, position)?;
-        let monitor = get_monitor_for_tray_icon(self)?;
-        if let Some(monitor) = monitor {
-            let monitor_size = monitor.size();
-            let monitor_position = monitor.position();
-            let window_size = self.outer_size()?;
-
-            let right_border_monitor = monitor_position.x as f64 + monitor_size.width as f64;
-            let left_border_monitor = monitor_position.x as f64;
-            let right_border_window = window_position.x as f64 + window_size.width as f64;
-            let left_border_window = window_position.x as f64;
-
-            let constrained_x = if left_border_window < left_border_monitor {
-                left_border_monitor
-            } else if right_border_window > right_border_monitor {
-                right_border_monitor - window_size.width as f64
-            } else {
-                window_position.x as f64
-            };
+        let placement = calculate_position(self, &options)?;
 
-            let bottom_border_monitor = monitor_position.y as f64 + monitor_size.height as f64;
-            let top_border_monitor = monitor_position.y as f64;
-            let bottom_border_window = window_position.y as f64 + window_size.height as f64;
-            let top_border_window = window_position.y as f64;
-
-            let constrained_y = if top_border_window < top_border_monitor {
-                top_border_monitor
-            } else if bottom_border_window > bottom_border_monitor {
-                bottom_border_monitor - window_size.height as f64
+        if let Some(monitor) = &placement.monitor {
+            let area = if options.clamp {
+                work_area(self, monitor)
             } else {
-                window_position.y as f64
+                Rect::of(monitor)
             };
-
-            self.set_position(PhysicalPosition::new(constrained_x, constrained_y))?;
+            self.set_position(constrain(placement.position, placement.window_size, area))?;
         } else {
             // Fallback on non constrained positioning
-            self.set_position(window_position)?;
+            self.set_position(placement.position)?;
         }
 
         Ok(())
     }
 
     fn move_window(&self, pos: Position) -> Result<()> {
-        let position = calculate_position(self, pos)?;
-        self.set_position(position)
+        self.move_window_with(pos.into())
+    }
+
+    fn move_window_with(&self, options: PositionOptions) -> Result<()> {
+        let placement = calculate_position(self, &options)?;
+        set_placement(self, placement, options.clamp)
+    }
+
+    fn move_window_relative(&self, parent: &str, options: RelativePositionOptions) -> Result<()> {
+        let parent = self
+            .get_window(parent)
+            .ok_or(tauri::Error::WindowNotFound)?;
+        let target = relative_target(&parent, options.position)?;
+        let placement = place(self, target, options.offset, options.anchor)?;
+        set_placement(self, placement, options.clamp)
     }
 }
 
 #[cfg(feature = "tray-icon")]
-/// Retrieve the monitor, where the tray icon is located on.
-fn get_monitor_for_tray_icon<R: Runtime>(window: &Window<R>) -> Result<Option<Monitor>> {
-    let tray_position = window
-        .state::<Tray>()
-        .0
-        .lock()
-        .unwrap()
-        .map(|(pos, _)| pos)
-        .unwrap_or_default();
+impl Position {
+    fn is_tray(&self) -> bool {
+        matches!(
+            self,
+            Position::TrayLeft
+                | Position::TrayBottomLeft
+                | Position::TrayRight
+                | Position::TrayBottomRight
+                | Position::TrayCenter
+                | Position::TrayBottomCenter
+        )
+    }
+}
 
-    window.monitor_from_point(tray_position.x, tray_position.y)
+/// Edge of the monitor the taskbar or menubar holding the tray icon is docked to.
+#[cfg(feature = "tray-icon")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrayEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
 }
 
-/// Calculate the top-left position of the window based on the given
-/// [`Position`].
-fn calculate_position<R: Runtime>(
+#[cfg(feature = "tray-icon")]
+struct TrayIcon {
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<i32>,
+    /// The monitor the tray icon is located on.
+    monitor: Option<Monitor>,
+    edge: TrayEdge,
+}
+
+#[cfg(feature = "tray-icon")]
+/// Retrieve the tray icon rect in physical pixels of the monitor it is located on.
+fn tray_icon<R: Runtime>(window: &Window<R>) -> Result<TrayIcon> {
+    let Some((position, size)) = *window.state::<Tray>().0.lock().unwrap() else {
+        panic!("Tray position not set");
+    };
+
+    let (position, size, monitor) = match position {
+        tauri::Position::Physical(position) => {
+            let position = position.cast::<f64>();
+            let monitor = window.monitor_from_point(position.x, position.y)?;
+            let scale_factor = monitor.as_ref().map_or(1.0, |m| m.scale_factor());
+            (position, size.to_physical::<f64>(scale_factor), monitor)
+        }
+        tauri::Position::Logical(position) => {
+            // find the monitor that contains the icon once scaled with its own scale factor
+            let monitor = window.available_monitors()?.into_iter().find(|monitor| {
+                let position = position.to_physical::<f64>(monitor.scale_factor());
+                contains(monitor, position)
+            });
+            let scale_factor = monitor.as_ref().map_or(1.0, |m| m.scale_factor());
+            (
+                position.to_physical::<f64>(scale_factor),
+                size.to_physical::<f64>(scale_factor),
+                monitor,
+            )
+        }
+    };
+
+    let position = PhysicalPosition::new(position.x as i32, position.y as i32);
+    let size = PhysicalSize::new(size.width as i32, size.height as i32);
+    let edge = match &monitor {
+        Some(monitor) => tray_edge(monitor, position, size),
+        None if cfg!(target_os = "macos") => TrayEdge::Top,
+        None => TrayEdge::Bottom,
+    };
+
+    Ok(TrayIcon {
+        position,
+        size,
+        monitor,
+        edge,
+    })
+}
+
+#[cfg(feature = "tray-icon")]
+fn contains(monitor: &Monitor, position: PhysicalPosition<f64>) -> bool {
+    let monitor_position = monitor.position();
+    let monitor_size = monitor.size();
+    position.x >= monitor_position.x as f64
+        && position.y >= monitor_position.y as f64
+        && position.x < monitor_position.x as f64 + monitor_size.width as f64
+        && position.y < monitor_position.y as f64 + monitor_size.height as f64
+}
+
+#[cfg(feature = "tray-icon")]
+/// The taskbar is docked to the monitor edge closest to the tray icon.
+fn tray_edge(
+    monitor: &Monitor,
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<i32>,
+) -> TrayEdge {
+    let monitor_position = monitor.position();
+    let monitor_size = monitor.size();
+
+    [
+        (TrayEdge::Top, position.y - monitor_position.y),
+        (
+            TrayEdge::Bottom,
+            monitor_position.y + monitor_size.height as i32 - (position.y + size.height),
+        ),
+        (TrayEdge::Left, position.x - monitor_position.x),
+        (
+            TrayEdge::Right,
+            monitor_position.x + monitor_size.width as i32 - (position.x + size.width),
+        ),
+    ]
+    .into_iter()
+    .min_by_key(|(_, distance)| *distance)
+    .map(|(edge, _)| edge)
+    .unwrap()
+}
+
+/// The outer size of the window once moved to the given monitor,
+/// which differs from its current size when the monitors have different scale factors.
+fn window_size_on<R: Runtime>(
     window: &Window<R>,
-    pos: Position,
-) -> Result<PhysicalPosition<i32>> {
+    monitor: Option<&Monitor>,
+) -> Result<PhysicalSize<i32>> {
+    let size = window.outer_size()?;
+    let scale = match monitor {
+        Some(monitor) => monitor.scale_factor() / window.scale_factor()?,
+        None => 1.0,
+    };
+    Ok(PhysicalSize {
+        width: (size.width as f64 * scale).round() as i32,
+        height: (size.height as f64 * scale).round() as i32,
+    })
+}
+
+impl Anchor {
+    /// Position of the anchor relative to the window top-left corner.
+    fn offset_in(self, size: PhysicalSize<i32>) -> PhysicalPosition<i32> {
+        use Anchor::*;
+
+        let x = match self {
+            TopLeft | LeftCenter | BottomLeft => 0,
+            TopCenter | Center | BottomCenter => size.width / 2,
+            TopRight | RightCenter | BottomRight => size.width,
+        };
+        let y = match self {
+            TopLeft | TopCenter | TopRight => 0,
+            LeftCenter | Center | RightCenter => size.height / 2,
+            BottomLeft | BottomCenter | BottomRight => size.height,
+        };
+        PhysicalPosition { x, y }
+    }
+}
+
+/// Where a window is placed: the target point of a [`Position`] and the window point placed there.
+struct Target {
+    point: PhysicalPosition<i32>,
+    anchor: Anchor,
+    /// The monitor the position is relative to.
+    monitor: Option<Monitor>,
+}
+
+/// The calculated window position.
+struct Placement {
+    position: PhysicalPosition<i32>,
+    /// The window size on the target monitor.
+    window_size: PhysicalSize<i32>,
+    monitor: Option<Monitor>,
+}
+
+#[cfg(feature = "tray-icon")]
+/// Calculate the target point of the given tray [`Position`].
+fn tray_target(tray: TrayIcon, pos: Position) -> Target {
     use Position::*;
 
-    let screen = window.current_monitor()?.unwrap();
-    // Only use the screen_position for the Tray independent positioning,
-    // because a tray event may not be called on the currently active monitor.
-    let screen_position = screen.position();
+    let PhysicalPosition {
+        x: tray_x,
+        y: tray_y,
+    } = tray.position;
+    let PhysicalSize {
+        width: tray_width,
+        height: tray_height,
+    } = tray.size;
+
+    let (point, anchor) = match tray.edge {
+        TrayEdge::Top | TrayEdge::Bottom => {
+            let (x, centered) = match pos {
+                TrayLeft | TrayBottomLeft => (tray_x, false),
+                TrayRight | TrayBottomRight => (tray_x + tray_width, false),
+                _ => (tray_x + tray_width / 2, true),
+            };
+            let (y, below) = match (pos, tray.edge) {
+                (TrayBottomLeft | TrayBottomRight | TrayBottomCenter, _) => (tray_y, true),
+                (_, TrayEdge::Bottom) => (tray_y, false),
+                _ => (tray_y + tray_height, true),
+            };
+            let anchor = match (below, centered) {
+                (true, false) => Anchor::TopLeft,
+                (true, true) => Anchor::TopCenter,
+                (false, false) => Anchor::BottomLeft,
+                (false, true) => Anchor::BottomCenter,
+            };
+            (PhysicalPosition { x, y }, anchor)
+        }
+        TrayEdge::Left | TrayEdge::Right => {
+            let left = tray.edge == TrayEdge::Left;
+            let x = if left { tray_x + tray_width } else { tray_x };
+            let (y, anchor) = match (pos, left) {
+                (TrayLeft | TrayBottomLeft, true) => (tray_y, Anchor::TopLeft),
+                (TrayLeft | TrayBottomLeft, false) => (tray_y, Anchor::TopRight),
+                (TrayRight | TrayBottomRight, true) => (tray_y + tray_height, Anchor::BottomLeft),
+                (TrayRight | TrayBottomRight, false) => (tray_y + tray_height, Anchor::BottomRight),
+                (_, true) => (tray_y + tray_height / 2, Anchor::LeftCenter),
+                (_, false) => (tray_y + tray_height / 2, Anchor::RightCenter),
+            };
+            (PhysicalPosition { x, y }, anchor)
+        }
+    };
+
+    Target {
+        point,
+        anchor,
+        monitor: tray.monitor,
+    }
+}
+
+/// Calculate the target point of the given screen [`Position`] on the **current** screen.
+fn screen_target(screen: Monitor, pos: Position) -> Target {
+    use Position::*;
+
+    let screen_position = *screen.position();
     let screen_size = PhysicalSize::<i32> {
         width: screen.size().width as i32,
         height: screen.size().height as i32,
     };
-    let window_size = PhysicalSize::<i32> {
-        width: window.outer_size()?.width as i32,
-        height: window.outer_size()?.height as i32,
-    };
-    #[cfg(feature = "tray-icon")]
-    let (tray_position, tray_size) = window
-        .state::<Tray>()
-        .0
-        .lock()
-        .unwrap()
-        .map(|(pos, size)| {
-            (
-                Some((pos.x as i32, pos.y as i32)),
-                Some((size.width as i32, size.height as i32)),
-            )
-        })
-        .unwrap_or_default();
-
-    let physical_pos = match pos {
-        TopLeft => *screen_position,
-        TopRight => PhysicalPosition {
-            x: screen_position.x + (screen_size.width - window_size.width),
-            y: screen_position.y,
-        },
-        BottomLeft => PhysicalPosition {
-            x: screen_position.x,
-            y: screen_size.height - (window_size.height - screen_position.y),
-        },
-        BottomRight => PhysicalPosition {
-            x: screen_position.x + (screen_size.width - window_size.width),
-            y: screen_size.height - (window_size.height - screen_position.y),
-        },
-        TopCenter => PhysicalPosition {
-            x: screen_position.x + ((screen_size.width / 2) - (window_size.width / 2)),
-            y: screen_position.y,
-        },
-        BottomCenter => PhysicalPosition {
-            x: screen_position.x + ((screen_size.width / 2) - (window_size.width / 2)),
-            y: screen_size.height - (window_size.height - screen_position.y),
-        },
-        LeftCenter => PhysicalPosition {
-            x: screen_position.x,
-            y: screen_position.y + (screen_size.height / 2) - (window_size.height / 2),
-        },
-        RightCenter => PhysicalPosition {
-            x: screen_position.x + (screen_size.width - window_size.width),
-            y: screen_position.y + (screen_size.height / 2) - (window_size.height / 2),
-        },
-        Center => PhysicalPosition {
-            x: screen_position.x + ((screen_size.width / 2) - (window_size.width / 2)),
-            y: screen_position.y + (screen_size.height / 2) - (window_size.height / 2),
-        },
-        #[cfg(feature = "tray-icon")]
-        TrayLeft => {
-            if let (Some((tray_x, tray_y)), Some((_, _tray_height))) = (tray_position, tray_size) {
-                let y = tray_y - window_size.height;
-                // Choose y value based on the target OS
-                #[cfg(target_os = "windows")]
-                let y = if y < 0 { tray_y + _tray_height } else { y };
 
-                #[cfg(target_os = "macos")]
-                let y = if y < 0 { tray_y } else { y };
+    let left = screen_position.x;
+    let center = screen_position.x + screen_size.width / 2;
+    let right = screen_position.x + screen_size.width;
+    let top = screen_position.y;
+    let middle = screen_position.y + screen_size.height / 2;
+    let bottom = screen_position.y + screen_size.height;
 
-                PhysicalPosition { x: tray_x, y }
-            } else {
-                panic!("Tray position not set");
-            }
-        }
-        #[cfg(feature = "tray-icon")]
-        TrayBottomLeft => {
-            if let Some((tray_x, tray_y)) = tray_position {
-                PhysicalPosition {
-                    x: tray_x,
-                    y: tray_y,
-                }
-            } else {
-                panic!("Tray position not set");
-            }
-        }
+    let (x, y, anchor) = match pos {
+        TopLeft => (left, top, Anchor::TopLeft),
+        TopRight => (right, top, Anchor::TopRight),
+        BottomLeft => (left, bottom, Anchor::BottomLeft),
+        BottomRight => (right, bottom, Anchor::BottomRight),
+        TopCenter => (center, top, Anchor::TopCenter),
+        BottomCenter => (center, bottom, Anchor::BottomCenter),
+        LeftCenter => (left, middle, Anchor::LeftCenter),
+        RightCenter => (right, middle, Anchor::RightCenter),
+        Center => (center, middle, Anchor::Center),
         #[cfg(feature = "tray-icon")]
-        TrayRight => {
-            if let (Some((tray_x, tray_y)), Some((tray_width, _tray_height))) =
-                (tray_position, tray_size)
-            {
-                let y = tray_y - window_size.height;
-                // Choose y value based on the target OS
-                #[cfg(target_os = "windows")]
-                let y = if y < 0 { tray_y + _tray_height } else { y };
-
-                #[cfg(target_os = "macos")]
-                let y = if y < 0 { tray_y } else { y };
-
-                PhysicalPosition {
-                    x: tray_x + tray_width,
-                    y,
-                }
-            } else {
-                panic!("Tray position not set");
-            }
-        }
-        #[cfg(feature = "tray-icon")]
-        TrayBottomRight => {
-            if let (Some((tray_x, tray_y)), Some((tray_width, _))) = (tray_position, tray_size) {
-                PhysicalPosition {
-                    x: tray_x + tray_width,
-                    y: tray_y,
-                }
-            } else {
-                panic!("Tray position not set");
-            }
-        }
-        #[cfg(feature = "tray-icon")]
-        TrayCenter => {
-            if let (Some((tray_x, tray_y)), Some((tray_width, _tray_height))) =
-                (tray_position, tray_size)
-            {
-                let x = tray_x + tray_width / 2 - window_size.width / 2;
-                let y = tray_y - window_size.height;
-                // Choose y value based on the target OS
-                #[cfg(target_os = "windows")]
-                let y = if y < 0 { tray_y + _tray_height } else { y };
-
-                #[cfg(target_os = "macos")]
-                let y = if y < 0 { tray_y } else { y };
-
-                PhysicalPosition { x, y }
-            } else {
-                panic!("Tray position not set");
-            }
+        TrayLeft | TrayBottomLeft | TrayRight | TrayBottomRight | TrayCenter | TrayBottomCenter => {
+            unreachable!()
         }
-        #[cfg(feature = "tray-icon")]
-        TrayBottomCenter => {
-            if let (Some((tray_x, tray_y)), Some((tray_width, _))) = (tray_position, tray_size) {
-                PhysicalPosition {
-                    x: tray_x + (tray_width / 2) - (window_size.width / 2),
-                    y: tray_y,
-                }
-            } else {
-                panic!("Tray position not set");
-            }
+    };
+
+    Target {
+        point: PhysicalPosition { x, y },
+        anchor,
+        monitor: Some(screen),
+    }
+}
+
+/// Calculate the target point of the given [`RelativePosition`] around the parent window.
+fn relative_target<R: Runtime>(parent: &Window<R>, pos: RelativePosition) -> Result<Target> {
+    use RelativePosition::*;
+
+    let parent_position = parent.outer_position()?;
+    let parent_size = parent.outer_size()?;
+
+    let left = parent_position.x;
+    let center = parent_position.x + parent_size.width as i32 / 2;
+    let right = parent_position.x + parent_size.width as i32;
+    let top = parent_position.y;
+    let middle = parent_position.y + parent_size.height as i32 / 2;
+    let bottom = parent_position.y + parent_size.height as i32;
+
+    let (x, y, anchor) = match pos {
+        Center => (center, middle, Anchor::Center),
+        Top => (center, top, Anchor::BottomCenter),
+        Bottom => (center, bottom, Anchor::TopCenter),
+        Left => (left, middle, Anchor::RightCenter),
+        Right => (right, middle, Anchor::LeftCenter),
+        Cascade => {
+            let step = (CASCADE_STEP * parent.scale_factor()?).round() as i32;
+            (left + step, top + step, Anchor::TopLeft)
         }
     };
 
-    Ok(physical_pos)
+    Ok(Target {
+        point: PhysicalPosition { x, y },
+        anchor,
+        monitor: parent.current_monitor()?,
+    })
+}
+
+/// Calculate the top-left position of the window based on the given
+/// [`PositionOptions`].
+fn calculate_position<R: Runtime>(
+    window: &Window<R>,
+    options: &PositionOptions,
+) -> Result<Placement> {
+    #[cfg(feature = "tray-icon")]
+    if options.position.is_tray() {
+        let target = tray_target(tray_icon(window)?, options.position);
+        return place(window, target, options.offset, options.anchor);
+    }
+
+    let screen = screen_monitor(window, options.remember_monitor)?;
+    let target = screen_target(screen, options.position);
+    place(window, target, options.offset, options.anchor)
+}
+
+/// The monitor screen positions are relative to: the **current** monitor,
+/// or the monitor remembered for the window if it is still connected.
+fn screen_monitor<R: Runtime>(window: &Window<R>, remember: bool) -> Result<Monitor> {
+    let monitors = window.try_state::<Monitors>().filter(|_| remember);
+    let Some(monitors) = monitors else {
+        return Ok(window.current_monitor()?.unwrap());
+    };
+
+    let remembered = match monitors.get(window.label()) {
+        Some(name) => window
+            .available_monitors()?
+            .into_iter()
+            .find(|monitor| monitor.name() == Some(&name)),
+        None => None,
+    };
+    let monitor = match remembered {
+        Some(monitor) => monitor,
+        None => window.current_monitor()?.unwrap(),
+    };
+    if let Some(name) = monitor.name() {
+        monitors.set(window.label(), name);
+    }
+    Ok(monitor)
+}
+
+/// Moves the window to the placement, keeping it within the monitor work area if `clamp` is set.
+fn set_placement<R: Runtime>(window: &Window<R>, placement: Placement, clamp: bool) -> Result<()> {
+    let position = match &placement.monitor {
+        Some(monitor) if clamp => constrain(
+            placement.position,
+            placement.window_size,
+            work_area(window, monitor),
+        ),
+        _ => placement.position,
+    };
+    window.set_position(position)
+}
+
+/// Moves the position so the window fits in the area, preferring its top-left corner
+/// if the window is larger than the area.
+fn constrain(
+    position: PhysicalPosition<i32>,
+    window_size: PhysicalSize<i32>,
+    area: Rect,
+) -> PhysicalPosition<i32> {
+    let right = area.position.x + area.size.width;
+    let bottom = area.position.y + area.size.height;
+    PhysicalPosition {
+        x: position
+            .x
+            .min(right - window_size.width)
+            .max(area.position.x),
+        y: position
+            .y
+            .min(bottom - window_size.height)
+            .max(area.position.y),
+    }
+}
+
+/// Calculate the top-left position of the window placing its anchor at the target point.
+fn place<R: Runtime>(
+    window: &Window<R>,
+    target: Target,
+    offset: Option<Offset>,
+    anchor: Option<Anchor>,
+) -> Result<Placement> {
+    let window_size = window_size_on(window, target.monitor.as_ref())?;
+
+    let mut point = target.point;
+    if let Some(offset) = offset {
+        let (scale_factor, monitor_size) = match &target.monitor {
+            Some(monitor) => (monitor.scale_factor(), *monitor.size()),
+            None => (1.0, PhysicalSize::default()),
+        };
+        let distance = |distance: Distance, monitor_length: u32| match distance {
+            Distance::Pixels(pixels) => (pixels * scale_factor).round() as i32,
+            Distance::Percent(percent) => (monitor_length as f64 * percent / 100.0).round() as i32,
+        };
+        point.x += distance(offset.x, monitor_size.width);
+        point.y += distance(offset.y, monitor_size.height);
+    }
+
+    let anchor = anchor.unwrap_or(target.anchor).offset_in(window_size);
+
+    Ok(Placement {
+        position: PhysicalPosition {
+            x: point.x - anchor.x,
+            y: point.y - anchor.y,
+        },
+        window_size,
+        monitor: target.monitor,
+    })
 }
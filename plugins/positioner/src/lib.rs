@@ -18,18 +18,68 @@
 #![cfg(not(any(target_os = "android", target_os = "ios")))]
 
 mod ext;
+mod work_area;
 
 pub use ext::*;
+use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex};
 use tauri::{
     plugin::{self, TauriPlugin},
-    Result, Runtime,
+    Manager, Result, Runtime,
 };
 
+const MONITORS_FILENAME: &str = ".positioner-monitors.json";
+
+/// The monitor names remembered for each window label, see [`PositionOptions::remember_monitor`].
+struct Monitors {
+    path: Option<PathBuf>,
+    names: Mutex<HashMap<String, String>>,
+}
+
+impl Monitors {
+    fn load(path: Option<PathBuf>) -> Self {
+        let names = path
+            .as_ref()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            names: Mutex::new(names),
+        }
+    }
+
+    fn get(&self, label: &str) -> Option<String> {
+        self.names.lock().unwrap().get(label).cloned()
+    }
+
+    fn set(&self, label: &str, name: &str) {
+        let mut names = self.names.lock().unwrap();
+        if names.get(label).map(String::as_str) == Some(name) {
+            return;
+        }
+        names.insert(label.into(), name.into());
+
+        if let Some(path) = &self.path {
+            let result = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(path, serde_json::to_vec(&*names)?));
+            if let Err(e) = result {
+                log::error!("failed to save the positioner monitors: {e}");
+            }
+        }
+    }
+}
+
 #[cfg(feature = "tray-icon")]
-use tauri::{tray::TrayIconEvent, AppHandle, Manager, PhysicalPosition, PhysicalSize};
+use tauri::{tray::TrayIconEvent, AppHandle, PhysicalPosition, PhysicalSize};
 
+/// The tray icon rect as reported by the tray event.
+///
+/// It is converted to physical pixels using the scale factor of the monitor the icon is on,
+/// which is not known until a window is moved.
 #[cfg(feature = "tray-icon")]
-struct Tray(std::sync::Mutex<Option<(PhysicalPosition<f64>, PhysicalSize<f64>)>>);
+struct Tray(std::sync::Mutex<Option<(tauri::Position, tauri::Size)>>);
 
 #[cfg(feature = "tray-icon")]
 pub fn on_tray_event<R: Runtime>(app: &AppHandle<R>, event: &TrayIconEvent) {
@@ -38,12 +88,7 @@ pub fn on_tray_event<R: Runtime>(app: &AppHandle<R>, event: &TrayIconEvent) {
             TrayIconEvent::Click { rect, .. }
             | TrayIconEvent::Enter { rect, .. }
             | TrayIconEvent::Leave { rect, .. }
-            | TrayIconEvent::Move { rect, .. } => {
-                // tray-icon emits PhysicalSize so the scale factor should not matter.
-                let size = rect.size.to_physical(1.0);
-                let position = rect.position.to_physical(1.0);
-                (position, size)
-            }
+            | TrayIconEvent::Move { rect, .. } => (rect.position, rect.size),
 
             _ => return,
         }
@@ -57,8 +102,41 @@ pub fn on_tray_event<R: Runtime>(app: &AppHandle<R>, event: &TrayIconEvent) {
 }
 
 #[tauri::command]
-async fn move_window<R: Runtime>(window: tauri::Window<R>, position: Position) -> Result<()> {
-    window.move_window(position)
+async fn move_window<R: Runtime>(
+    window: tauri::Window<R>,
+    position: Position,
+    offset: Option<Offset>,
+    anchor: Option<Anchor>,
+    clamp: Option<bool>,
+    remember_monitor: Option<bool>,
+) -> Result<()> {
+    window.move_window_with(PositionOptions {
+        position,
+        offset,
+        anchor,
+        clamp: clamp.unwrap_or_default(),
+        remember_monitor: remember_monitor.unwrap_or_default(),
+    })
+}
+
+#[tauri::command]
+async fn move_window_relative<R: Runtime>(
+    window: tauri::Window<R>,
+    parent: String,
+    position: RelativePosition,
+    offset: Option<Offset>,
+    anchor: Option<Anchor>,
+    clamp: Option<bool>,
+) -> Result<()> {
+    window.move_window_relative(
+        &parent,
+        RelativePositionOptions {
+            position,
+            offset,
+            anchor,
+            clamp: clamp.unwrap_or_default(),
+        },
+    )
 }
 
 #[cfg(feature = "tray-icon")]
@@ -66,8 +144,18 @@ async fn move_window<R: Runtime>(window: tauri::Window<R>, position: Position) -
 async fn move_window_constrained<R: Runtime>(
     window: tauri::Window<R>,
     position: Position,
+    offset: Option<Offset>,
+    anchor: Option<Anchor>,
+    clamp: Option<bool>,
+    remember_monitor: Option<bool>,
 ) -> Result<()> {
-    window.move_window_constrained(position)
+    window.move_window_constrained_with(PositionOptions {
+        position,
+        offset,
+        anchor,
+        clamp: clamp.unwrap_or_default(),
+        remember_monitor: remember_monitor.unwrap_or_default(),
+    })
 }
 
 #[cfg(feature = "tray-icon")]
@@ -81,24 +169,31 @@ fn set_tray_icon_state<R: Runtime>(
         .0
         .lock()
         .unwrap()
-        .replace((position, size));
+        .replace((position.into(), size.into()));
 }
 
 /// The Tauri plugin that exposes [`WindowExt::move_window`] to the webview.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
     let plugin = plugin::Builder::new("positioner").invoke_handler(tauri::generate_handler![
         move_window,
+        move_window_relative,
         #[cfg(feature = "tray-icon")]
         move_window_constrained,
         #[cfg(feature = "tray-icon")]
         set_tray_icon_state
     ]);
 
-    #[cfg(feature = "tray-icon")]
-    let plugin = plugin.setup(|app_handle, _api| {
-        app_handle.manage(Tray(std::sync::Mutex::new(None)));
-        Ok(())
-    });
-
-    plugin.build()
+    plugin
+        .setup(|app_handle, _api| {
+            let path = app_handle
+                .path()
+                .app_config_dir()
+                .ok()
+                .map(|dir| dir.join(MONITORS_FILENAME));
+            app_handle.manage(Monitors::load(path));
+            #[cfg(feature = "tray-icon")]
+            app_handle.manage(Tray(std::sync::Mutex::new(None)));
+            Ok(())
+        })
+        .build()
 }
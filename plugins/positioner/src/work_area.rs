@@ -0,0 +1,134 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use tauri::{Monitor, PhysicalPosition, PhysicalSize, Runtime, Window};
+
+/// A rectangle in physical pixels.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rect {
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<i32>,
+}
+
+impl Rect {
+    /// The monitor bounds.
+    pub fn of(monitor: &Monitor) -> Self {
+        Self {
+            position: *monitor.position(),
+            size: PhysicalSize::new(monitor.size().width as i32, monitor.size().height as i32),
+        }
+    }
+}
+
+/// The area of the monitor not covered by the taskbar, dock or menubar.
+///
+/// Falls back to the monitor bounds on Linux and when the platform does not report it.
+#[allow(unused_variables)]
+pub(crate) fn work_area<R: Runtime>(window: &Window<R>, monitor: &Monitor) -> Rect {
+    #[cfg(windows)]
+    let work_area = windows::work_area(monitor);
+    #[cfg(target_os = "macos")]
+    let work_area = macos::work_area(window, monitor);
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let work_area = None;
+
+    work_area.unwrap_or_else(|| Rect::of(monitor))
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::Rect;
+    use tauri::{Monitor, PhysicalPosition, PhysicalSize};
+    use windows_sys::Win32::{
+        Foundation::POINT,
+        Graphics::Gdi::{GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITOR_DEFAULTTONULL},
+    };
+
+    pub fn work_area(monitor: &Monitor) -> Option<Rect> {
+        let position = monitor.position();
+        let point = POINT {
+            x: position.x,
+            y: position.y,
+        };
+
+        unsafe {
+            let hmonitor = MonitorFromPoint(point, MONITOR_DEFAULTTONULL);
+            if hmonitor.is_null() {
+                return None;
+            }
+
+            let mut info: MONITORINFO = std::mem::zeroed();
+            info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+            if GetMonitorInfoW(hmonitor, &mut info) == 0 {
+                return None;
+            }
+
+            let work = info.rcWork;
+            Some(Rect {
+                position: PhysicalPosition::new(work.left, work.top),
+                size: PhysicalSize::new(work.right - work.left, work.bottom - work.top),
+            })
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::Rect;
+    use objc2_app_kit::NSScreen;
+    use objc2_foundation::MainThreadMarker;
+    use tauri::{Monitor, PhysicalPosition, PhysicalSize, Runtime, Window};
+
+    pub fn work_area<R: Runtime>(window: &Window<R>, monitor: &Monitor) -> Option<Rect> {
+        // AppKit screens can only be accessed on the main thread
+        let position = *monitor.position();
+        let scale_factor = monitor.scale_factor();
+        match MainThreadMarker::new() {
+            Some(mtm) => visible_frame(mtm, position, scale_factor),
+            None => {
+                let (tx, rx) = std::sync::mpsc::channel();
+                window
+                    .run_on_main_thread(move || {
+                        let mtm = MainThreadMarker::new().unwrap();
+                        let _ = tx.send(visible_frame(mtm, position, scale_factor));
+                    })
+                    .ok()?;
+                rx.recv().ok()?
+            }
+        }
+    }
+
+    fn visible_frame(
+        mtm: MainThreadMarker,
+        position: PhysicalPosition<i32>,
+        scale_factor: f64,
+    ) -> Option<Rect> {
+        let screens = NSScreen::screens(mtm);
+        // AppKit coordinates start at the bottom-left corner of the primary screen
+        let primary_height = screens.iter().next()?.frame().size.height;
+        let flip = |y: f64, height: f64| primary_height - (y + height);
+
+        screens.iter().find_map(|screen| {
+            let frame = screen.frame();
+            let top_left = (frame.origin.x, flip(frame.origin.y, frame.size.height));
+            if (top_left.0 * scale_factor - position.x as f64).abs() > 1.0
+                || (top_left.1 * scale_factor - position.y as f64).abs() > 1.0
+            {
+                return None;
+            }
+
+            let visible = screen.visibleFrame();
+            Some(Rect {
+                position: PhysicalPosition::new(
+                    (visible.origin.x * scale_factor).round() as i32,
+                    (flip(visible.origin.y, visible.size.height) * scale_factor).round() as i32,
+                ),
+                size: PhysicalSize::new(
+                    (visible.size.width * scale_factor).round() as i32,
+                    (visible.size.height * scale_factor).round() as i32,
+                ),
+            })
+        })
+    }
+}
@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_PROCESS__=function(_){"use strict";async function n(_,n={},e){return window.__TAURI_INTERNALS__.invoke(_,n,e)}return"function"==typeof SuppressedError&&SuppressedError,_.exit=async function(_=0){await n("plugin:process|exit",{code:_})},_.relaunch=async function(){await n("plugin:process|restart")},_}({});Object.defineProperty(window.__TAURI__,"process",{value:__TAURI_PLUGIN_PROCESS__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_PROCESS__=function(e){"use strict";function n(e,n=!1){return window.__TAURI_INTERNALS__.transformCallback(e,n)}async function r(e,n={},r){return window.__TAURI_INTERNALS__.invoke(e,n,r)}async function t(e,t){const i={kind:"Any"};return r("plugin:event|listen",{event:e,target:i,handler:n(t)}).then((n=>async()=>async function(e,n){await r("plugin:event|unlisten",{event:e,eventId:n})}(e,n)))}return"function"==typeof SuppressedError&&SuppressedError,e.exit=async function(e=0){await r("plugin:process|exit",{code:e})},e.getMetrics=async function(){return await r("plugin:process|metrics")},e.onBeforeExit=async function(e){const n=await t("process://before-exit",(async n=>{const{id:t,code:i,restart:s}=n.payload;let o=!1;try{await e({code:i,restart:s,preventDefault:()=>{o=!0}})}finally{await r("plugin:process|before_exit_response",{id:t,prevent:o})}}));return await r("plugin:process|listen_before_exit"),()=>{n(),r("plugin:process|unlisten_before_exit")}},e.onMetrics=async function(e){return await t("process://metrics",(n=>{e(n.payload)}))},e.relaunch=async function(){await r("plugin:process|restart")},e}({});Object.defineProperty(window.__TAURI__,"process",{value:__TAURI_PLUGIN_PROCESS__})}
@@ -2,7 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["exit", "restart"];
+const COMMANDS: &[&str] = &[
+    "exit",
+    "restart",
+    "listen_before_exit",
+    "unlisten_before_exit",
+    "before_exit_response",
+    "metrics",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -8,9 +8,51 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import { type UnlistenFn, listen } from '@tauri-apps/api/event'
 
 /**
- * Exits immediately with the given `exitCode`.
+ * An exit request, see {@link onBeforeExit}.
+ *
+ * @since 2.3.0
+ */
+interface BeforeExitEvent {
+  /** The exit code. */
+  code: number
+  /** Whether the app restarts. */
+  restart: boolean
+  /** Prevents the exit. */
+  preventDefault: () => void
+}
+
+/**
+ * The resource usage of the current process, see {@link getMetrics}.
+ *
+ * @since 2.3.0
+ */
+interface ProcessMetrics {
+  /**
+   * The CPU usage since the previous sample, or since the process started for the first one,
+   * in percent of a single core: a process using two cores fully reports 200.
+   */
+  cpuUsage: number
+  /** The resident set size, or working set on Windows, in bytes. */
+  memory: number
+  /** The number of open file descriptors, or handles on Windows. */
+  handleCount: number
+  /** The time since the process started, in milliseconds. */
+  uptime: number
+}
+
+interface BeforeExitPayload {
+  id: number
+  code: number
+  restart: boolean
+}
+
+/**
+ * Exits with the given `exitCode`.
+ *
+ * The {@link onBeforeExit} listeners and the exit hooks registered from Rust run first.
  * @example
  * ```typescript
  * import { exit } from '@tauri-apps/plugin-process';
@@ -42,4 +84,97 @@ async function relaunch(): Promise<void> {
   await invoke('plugin:process|restart')
 }
 
-export { exit, relaunch }
+/**
+ * Listens to the exit requests, including {@link exit}, {@link relaunch} and closing the last window.
+ *
+ * The exit waits for the handler to resolve, up to the exit timeout configured in Rust,
+ * and is cancelled if the handler calls `preventDefault`.
+ * The handler is called once for each exit request.
+ *
+ * @example
+ * ```typescript
+ * import { exit, onBeforeExit } from '@tauri-apps/plugin-process';
+ *
+ * let confirmed = false;
+ * await onBeforeExit(async (event) => {
+ *   if (hasUnsavedChanges() && !confirmed) {
+ *     event.preventDefault();
+ *     if (await confirm('Discard the unsaved changes?')) {
+ *       confirmed = true;
+ *       await exit(event.code);
+ *     }
+ *   } else {
+ *     await flush();
+ *   }
+ * });
+ * ```
+ *
+ * @returns A function to stop listening.
+ *
+ * @since 2.3.0
+ */
+async function onBeforeExit(
+  handler: (event: BeforeExitEvent) => void | Promise<void>
+): Promise<UnlistenFn> {
+  const unlisten = await listen<BeforeExitPayload>(
+    'process://before-exit',
+    async (event) => {
+      const { id, code, restart } = event.payload
+      let prevent = false
+      try {
+        await handler({
+          code,
+          restart,
+          preventDefault: () => {
+            prevent = true
+          }
+        })
+      } finally {
+        await invoke('plugin:process|before_exit_response', { id, prevent })
+      }
+    }
+  )
+  await invoke('plugin:process|listen_before_exit')
+
+  return () => {
+    unlisten()
+    void invoke('plugin:process|unlisten_before_exit')
+  }
+}
+
+/**
+ * Returns the resource usage of the current process.
+ *
+ * #### Platform-specific:
+ *
+ * - **iOS:** Unsupported.
+ *
+ * @example
+ * ```typescript
+ * import { getMetrics } from '@tauri-apps/plugin-process';
+ * const { cpuUsage, memory } = await getMetrics();
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function getMetrics(): Promise<ProcessMetrics> {
+  return await invoke('plugin:process|metrics')
+}
+
+/**
+ * Listens to the resource usage of the current process,
+ * emitted at the interval configured with `Builder::metrics_interval` in Rust.
+ *
+ * @since 2.3.0
+ */
+async function onMetrics(
+  handler: (metrics: ProcessMetrics) => void
+): Promise<UnlistenFn> {
+  return await listen<ProcessMetrics>('process://metrics', (event) => {
+    handler(event.payload)
+  })
+}
+
+export type { BeforeExitEvent, ProcessMetrics }
+
+export { exit, relaunch, onBeforeExit, getMetrics, onMetrics }
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use tauri::{AppHandle, Runtime};
+use tauri::{AppHandle, Runtime, Webview};
+
+use crate::{Metrics, ProcessExt};
 
 #[tauri::command]
 pub fn exit<R: Runtime>(app: AppHandle<R>, code: i32) {
@@ -11,5 +13,31 @@ pub fn exit<R: Runtime>(app: AppHandle<R>, code: i32) {
 
 #[tauri::command]
 pub fn restart<R: Runtime>(app: AppHandle<R>) {
-    app.restart()
+    app.process().restart()
+}
+
+#[tauri::command]
+pub fn listen_before_exit<R: Runtime>(webview: Webview<R>) {
+    let process = webview.process();
+    process.state.lock().unwrap().add_listener(webview.label());
+}
+
+#[tauri::command]
+pub fn unlisten_before_exit<R: Runtime>(webview: Webview<R>) {
+    let process = webview.process();
+    process
+        .state
+        .lock()
+        .unwrap()
+        .remove_listener(webview.label());
+}
+
+#[tauri::command]
+pub fn before_exit_response<R: Runtime>(app: AppHandle<R>, id: u32, prevent: bool) {
+    app.process().state.lock().unwrap().respond(id, prevent);
+}
+
+#[tauri::command]
+pub fn metrics<R: Runtime>(app: AppHandle<R>) -> crate::Result<Metrics> {
+    app.process().metrics()
 }
@@ -0,0 +1,145 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The graceful exit pipeline.
+//!
+//! Exiting first notifies the frontend listeners, which respond once to each exit request to delay or prevent it,
+//! then runs the exit hooks in their registration order before exiting for real.
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use tauri::AppHandle;
+
+/// The event emitted to the frontend before exiting.
+pub const BEFORE_EXIT_EVENT: &str = "process://before-exit";
+
+pub(crate) type ExitHook<R> =
+    dyn Fn(AppHandle<R>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync;
+pub(crate) type ExitHooks<R> = Vec<Arc<ExitHook<R>>>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BeforeExitEvent {
+    pub id: u32,
+    pub code: i32,
+    pub restart: bool,
+}
+
+/// An exit waiting for the responses of the frontend listeners.
+struct Pending {
+    id: u32,
+    remaining: usize,
+    prevented: bool,
+    tx: Option<oneshot::Sender<bool>>,
+}
+
+#[derive(Default)]
+pub(crate) struct ExitState {
+    /// The pipeline is running.
+    running: bool,
+    /// The pipeline completed, the next exit request goes through.
+    finished: bool,
+    next_id: u32,
+    /// The number of listeners registered by each webview.
+    listeners: HashMap<String, usize>,
+    pending: Option<Pending>,
+}
+
+impl ExitState {
+    pub(crate) fn listeners(&self) -> usize {
+        self.listeners.values().sum()
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// Marks the pipeline as running, returns `false` if it already is.
+    pub(crate) fn start(&mut self) -> bool {
+        !std::mem::replace(&mut self.running, true)
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.running = false;
+        self.finished = true;
+    }
+
+    /// Stops the pipeline after the frontend prevented the exit.
+    pub(crate) fn cancel(&mut self) {
+        self.running = false;
+    }
+
+    pub(crate) fn add_listener(&mut self, webview: &str) {
+        *self.listeners.entry(webview.into()).or_default() += 1;
+    }
+
+    pub(crate) fn remove_listener(&mut self, webview: &str) {
+        if let Some(count) = self.listeners.get_mut(webview) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.listeners.remove(webview);
+            }
+        }
+    }
+
+    /// Forgets the listeners of a webview that navigated or was destroyed.
+    pub(crate) fn remove_webview(&mut self, webview: &str) {
+        if let Some(count) = self.listeners.remove(webview) {
+            if let Some(pending) = &mut self.pending {
+                pending.remaining = pending.remaining.saturating_sub(count);
+                pending.complete();
+            }
+        }
+    }
+
+    /// Waits for the responses of the current listeners.
+    pub(crate) fn notify(
+        &mut self,
+        code: i32,
+        restart: bool,
+    ) -> (BeforeExitEvent, oneshot::Receiver<bool>) {
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let mut pending = Pending {
+            id,
+            remaining: self.listeners(),
+            prevented: false,
+            tx: Some(tx),
+        };
+        pending.complete();
+        self.pending = Some(pending);
+
+        (BeforeExitEvent { id, code, restart }, rx)
+    }
+
+    pub(crate) fn respond(&mut self, id: u32, prevent: bool) {
+        if let Some(pending) = self.pending.as_mut().filter(|p| p.id == id) {
+            pending.remaining = pending.remaining.saturating_sub(1);
+            pending.prevented |= prevent;
+            pending.complete();
+        }
+    }
+
+    pub(crate) fn clear_pending(&mut self) {
+        self.pending = None;
+    }
+}
+
+impl Pending {
+    fn complete(&mut self) {
+        if self.remaining == 0 {
+            if let Some(tx) = self.tx.take() {
+                let _ = tx.send(self.prevented);
+            }
+        }
+    }
+}
@@ -9,15 +9,286 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
 use tauri::{
-    plugin::{Builder, TauriPlugin},
-    Runtime,
+    plugin::{Builder as PluginBuilder, TauriPlugin},
+    webview::PageLoadEvent,
+    AppHandle, Emitter, Manager, RunEvent, Runtime, WindowEvent,
 };
 
 mod commands;
+mod error;
+mod exit;
+mod metrics;
+
+pub use error::{Error, Result};
+pub use exit::BEFORE_EXIT_EVENT;
+use exit::{ExitHooks, ExitState};
+use metrics::Sampler;
+pub use metrics::{Metrics, METRICS_EVENT};
+
+/// The default timeout of the frontend listeners and of the exit hooks.
+const DEFAULT_EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Access to the process APIs.
+pub struct Process<R: Runtime> {
+    app: AppHandle<R>,
+    exit_timeout: Duration,
+    hooks: Mutex<ExitHooks<R>>,
+    state: Mutex<ExitState>,
+    sampler: Mutex<Sampler>,
+}
+
+impl<R: Runtime> Process<R> {
+    /// Returns the resource usage of the current process.
+    ///
+    /// The CPU usage is computed since the previous sample,
+    /// including the ones emitted with [`Builder::metrics_interval`].
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **iOS / BSD:** Unsupported.
+    pub fn metrics(&self) -> Result<Metrics> {
+        self.sampler.lock().unwrap().sample()
+    }
+
+    /// Registers an async hook to run before the app exits, e.g. to flush stores or stop sidecars.
+    ///
+    /// Hooks run in their registration order, once the frontend listeners are notified,
+    /// and are abandoned after the exit timeout, see [`Builder::exit_timeout`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_process::ProcessExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     app.process().on_exit(|app| async move {
+    ///       // flush the stores, stop the sidecars...
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn on_exit<F, Fut>(&self, hook: F)
+    where
+        F: Fn(AppHandle<R>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.hooks
+            .lock()
+            .unwrap()
+            .push(Arc::new(move |app| Box::pin(hook(app))));
+    }
+
+    /// Exits the app with the given code once the exit pipeline completes.
+    ///
+    /// [`AppHandle::exit`] and closing the last window also go through the pipeline.
+    pub fn exit(&self, code: i32) {
+        self.start(code, false);
+    }
+
+    /// Restarts the app once the exit pipeline completes.
+    pub fn restart(&self) {
+        self.start(0, true);
+    }
+
+    /// Whether the exit request must wait for the pipeline.
+    fn intercepts_exit(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        !state.is_finished()
+            && (state.is_running()
+                || state.listeners() > 0
+                || !self.hooks.lock().unwrap().is_empty())
+    }
+
+    fn start(&self, code: i32, restart: bool) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if !state.is_finished() && !state.start() {
+                return;
+            }
+        }
+
+        let app = self.app.clone();
+        tauri::async_runtime::spawn(async move {
+            app.state::<Process<R>>().run(code, restart).await;
+        });
+    }
+
+    async fn run(&self, code: i32, restart: bool) {
+        let finished = self.state.lock().unwrap().is_finished();
+        if !finished {
+            if self.notify_listeners(code, restart).await {
+                self.state.lock().unwrap().cancel();
+                return;
+            }
+
+            let hooks = self.hooks.lock().unwrap().clone();
+            let app = self.app.clone();
+            let _ = tokio::time::timeout(self.exit_timeout, async move {
+                for hook in hooks {
+                    hook(app.clone()).await;
+                }
+            })
+            .await;
+
+            self.state.lock().unwrap().finish();
+        }
+
+        if restart {
+            self.app.restart();
+        } else {
+            self.app.exit(code);
+        }
+    }
+
+    /// Notifies the frontend listeners, returns whether they prevented the exit.
+    async fn notify_listeners(&self, code: i32, restart: bool) -> bool {
+        let (event, rx) = {
+            let mut state = self.state.lock().unwrap();
+            if state.listeners() == 0 {
+                return false;
+            }
+            state.notify(code, restart)
+        };
+
+        if self.app.emit(BEFORE_EXIT_EVENT, &event).is_err() {
+            self.state.lock().unwrap().clear_pending();
+            return false;
+        }
+
+        let prevented = tokio::time::timeout(self.exit_timeout, rx)
+            .await
+            .ok()
+            .and_then(Result::ok)
+            .unwrap_or(false);
+        self.state.lock().unwrap().clear_pending();
+
+        prevented
+    }
+}
+
+/// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the process APIs.
+pub trait ProcessExt<R: Runtime> {
+    fn process(&self) -> &Process<R>;
+}
+
+impl<R: Runtime, T: Manager<R>> ProcessExt<R> for T {
+    fn process(&self) -> &Process<R> {
+        self.state::<Process<R>>().inner()
+    }
+}
+
+/// The process plugin Builder.
+pub struct Builder {
+    exit_timeout: Duration,
+    metrics_interval: Option<Duration>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            exit_timeout: DEFAULT_EXIT_TIMEOUT,
+            metrics_interval: None,
+        }
+    }
+}
+
+impl Builder {
+    /// Create a new process plugin Builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long to wait for the frontend listeners, and then for the exit hooks, before exiting anyway.
+    ///
+    /// Defaults to 5 seconds.
+    pub fn exit_timeout(mut self, timeout: Duration) -> Self {
+        self.exit_timeout = timeout;
+        self
+    }
+
+    /// Emits the resource usage of the process as the [`METRICS_EVENT`] at the given interval.
+    pub fn metrics_interval(mut self, interval: Duration) -> Self {
+        self.metrics_interval.replace(interval);
+        self
+    }
+
+    /// Build and Initializes the plugin.
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R> {
+        let exit_timeout = self.exit_timeout;
+        let metrics_interval = self.metrics_interval;
+        PluginBuilder::new("process")
+            .invoke_handler(tauri::generate_handler![
+                commands::exit,
+                commands::restart,
+                commands::listen_before_exit,
+                commands::unlisten_before_exit,
+                commands::before_exit_response,
+                commands::metrics
+            ])
+            .setup(move |app, _api| {
+                app.manage(Process {
+                    app: app.clone(),
+                    exit_timeout,
+                    hooks: Default::default(),
+                    state: Default::default(),
+                    sampler: Default::default(),
+                });
+
+                if let Some(interval) = metrics_interval {
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        loop {
+                            tokio::time::sleep(interval).await;
+                            if let Ok(metrics) = app.process().metrics() {
+                                let _ = app.emit(METRICS_EVENT, metrics);
+                            }
+                        }
+                    });
+                }
+
+                Ok(())
+            })
+            .on_page_load(|webview, payload| {
+                // the listeners of the previous page are gone
+                if payload.event() == PageLoadEvent::Started {
+                    let process = webview.process();
+                    process
+                        .state
+                        .lock()
+                        .unwrap()
+                        .remove_webview(webview.label());
+                }
+            })
+            .on_event(|app, event| match event {
+                RunEvent::ExitRequested { code, api, .. } => {
+                    let process = app.process();
+                    if process.intercepts_exit() {
+                        api.prevent_exit();
+                        process.exit(code.unwrap_or(0));
+                    }
+                }
+                RunEvent::WindowEvent {
+                    label,
+                    event: WindowEvent::Destroyed,
+                    ..
+                } => {
+                    app.process().state.lock().unwrap().remove_webview(label);
+                }
+                _ => {}
+            })
+            .build()
+    }
+}
 
+/// Initializes the plugin.
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("process")
-        .invoke_handler(tauri::generate_handler![commands::exit, commands::restart])
-        .build()
+    Builder::default().build()
 }
@@ -0,0 +1,306 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Resource usage of the current process.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+
+/// The event emitted with the [`Metrics`] when a metrics interval is set, see [`crate::Builder::metrics_interval`].
+pub const METRICS_EVENT: &str = "process://metrics";
+
+/// The resource usage of the current process.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metrics {
+    /// The CPU usage since the previous sample, or since the process started for the first one,
+    /// in percent of a single core: a process using two cores fully reports 200.
+    pub cpu_usage: f64,
+    /// The resident set size, or working set on Windows, in bytes.
+    pub memory: u64,
+    /// The number of open file descriptors, or handles on Windows.
+    pub handle_count: u64,
+    /// The time since the process started, in milliseconds.
+    pub uptime: u64,
+}
+
+/// The raw counters of the process.
+struct Sample {
+    cpu_time: Duration,
+    memory: u64,
+    handle_count: u64,
+    started_at: SystemTime,
+}
+
+/// Computes the CPU usage between samples.
+#[derive(Default)]
+pub(crate) struct Sampler {
+    previous: Option<(Instant, Duration)>,
+}
+
+impl Sampler {
+    pub(crate) fn sample(&mut self) -> crate::Result<Metrics> {
+        let sample = imp::sample()?;
+        let now = Instant::now();
+        let uptime = SystemTime::now()
+            .duration_since(sample.started_at)
+            .unwrap_or_default();
+
+        let (elapsed, cpu_time) = match self.previous {
+            Some((at, cpu_time)) => (
+                now.duration_since(at),
+                sample.cpu_time.saturating_sub(cpu_time),
+            ),
+            None => (uptime, sample.cpu_time),
+        };
+        self.previous = Some((now, sample.cpu_time));
+
+        let cpu_usage = if elapsed.is_zero() {
+            0.0
+        } else {
+            cpu_time.as_secs_f64() / elapsed.as_secs_f64() * 100.0
+        };
+
+        Ok(Metrics {
+            cpu_usage,
+            memory: sample.memory,
+            handle_count: sample.handle_count,
+            uptime: uptime.as_millis() as u64,
+        })
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod imp {
+    use std::fs;
+
+    use super::*;
+
+    fn sysconf(name: libc::c_int) -> u64 {
+        match unsafe { libc::sysconf(name) } {
+            value if value > 0 => value as u64,
+            _ => 0,
+        }
+    }
+
+    pub(super) fn sample() -> crate::Result<Sample> {
+        let invalid =
+            || std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid /proc/self/stat");
+
+        let stat = fs::read_to_string("/proc/self/stat")?;
+        // the fields following the executable name, which can contain spaces, starting with the state
+        let fields: Vec<&str> = stat
+            .rfind(')')
+            .map(|end| stat[end + 1..].split_whitespace().collect())
+            .ok_or_else(invalid)?;
+        let field = |n: usize| -> crate::Result<u64> {
+            // `n` is the 1-based field number of proc(5), the state is the 3rd
+            fields
+                .get(n - 3)
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| invalid().into())
+        };
+
+        let ticks = sysconf(libc::_SC_CLK_TCK).max(1);
+        let from_ticks = |value: u64| Duration::from_secs_f64(value as f64 / ticks as f64);
+
+        let cpu_time = from_ticks(field(14)? + field(15)?);
+        let memory = field(24)? * sysconf(libc::_SC_PAGESIZE);
+
+        let system_uptime: f64 = fs::read_to_string("/proc/uptime")?
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(invalid)?;
+        let uptime = Duration::from_secs_f64(system_uptime).saturating_sub(from_ticks(field(22)?));
+
+        // excluding the descriptor used to read the directory
+        let handle_count = fs::read_dir("/proc/self/fd")?.count().saturating_sub(1) as u64;
+
+        Ok(Sample {
+            cpu_time,
+            memory,
+            handle_count,
+            started_at: SystemTime::now() - uptime,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::mem::{size_of, MaybeUninit};
+
+    use super::*;
+
+    fn pid_info<T>(flavor: libc::c_int) -> std::io::Result<T> {
+        let mut info = MaybeUninit::<T>::zeroed();
+        let size = unsafe {
+            libc::proc_pidinfo(
+                libc::getpid(),
+                flavor,
+                0,
+                info.as_mut_ptr().cast(),
+                size_of::<T>() as libc::c_int,
+            )
+        };
+        if size as usize == size_of::<T>() {
+            Ok(unsafe { info.assume_init() })
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
+    fn from_timeval(time: libc::timeval) -> Duration {
+        Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000)
+    }
+
+    fn handle_count() -> std::io::Result<u64> {
+        // without a buffer, returns an estimate of the size needed to list the descriptors,
+        // with room for the descriptors opened meanwhile
+        let estimate = unsafe {
+            libc::proc_pidinfo(
+                libc::getpid(),
+                libc::PROC_PIDLISTFDS,
+                0,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if estimate <= 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut fds = Vec::<libc::proc_fdinfo>::with_capacity(
+            estimate as usize / size_of::<libc::proc_fdinfo>(),
+        );
+        let size = unsafe {
+            libc::proc_pidinfo(
+                libc::getpid(),
+                libc::PROC_PIDLISTFDS,
+                0,
+                fds.as_mut_ptr().cast(),
+                (fds.capacity() * size_of::<libc::proc_fdinfo>()) as libc::c_int,
+            )
+        };
+        if size < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(size as u64 / size_of::<libc::proc_fdinfo>() as u64)
+    }
+
+    pub(super) fn sample() -> crate::Result<Sample> {
+        let mut usage = MaybeUninit::<libc::rusage>::zeroed();
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let usage = unsafe { usage.assume_init() };
+        let cpu_time = from_timeval(usage.ru_utime) + from_timeval(usage.ru_stime);
+
+        let task: libc::proc_taskinfo = pid_info(libc::PROC_PIDTASKINFO)?;
+        let bsd: libc::proc_bsdinfo = pid_info(libc::PROC_PIDTBSDINFO)?;
+
+        let handle_count = handle_count()?;
+
+        Ok(Sample {
+            cpu_time,
+            memory: task.pti_resident_size,
+            handle_count,
+            started_at: SystemTime::UNIX_EPOCH
+                + Duration::new(bsd.pbi_start_tvsec, bsd.pbi_start_tvusec as u32 * 1000),
+        })
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::mem::{size_of, MaybeUninit};
+
+    use windows_sys::Win32::{
+        Foundation::FILETIME,
+        System::{
+            ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+            Threading::{GetCurrentProcess, GetProcessHandleCount, GetProcessTimes},
+        },
+    };
+
+    use super::*;
+
+    /// The number of 100 nanoseconds intervals between 1601-01-01, the `FILETIME` epoch, and the Unix epoch.
+    const UNIX_EPOCH_INTERVALS: u64 = 116_444_736_000_000_000;
+
+    fn intervals(time: FILETIME) -> u64 {
+        ((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64
+    }
+
+    fn from_intervals(intervals: u64) -> Duration {
+        Duration::from_nanos(intervals * 100)
+    }
+
+    pub(super) fn sample() -> crate::Result<Sample> {
+        let last_error = || crate::Error::from(std::io::Error::last_os_error());
+
+        unsafe {
+            let process = GetCurrentProcess();
+
+            let mut creation = MaybeUninit::<FILETIME>::zeroed();
+            let mut exit = MaybeUninit::<FILETIME>::zeroed();
+            let mut kernel = MaybeUninit::<FILETIME>::zeroed();
+            let mut user = MaybeUninit::<FILETIME>::zeroed();
+            if GetProcessTimes(
+                process,
+                creation.as_mut_ptr(),
+                exit.as_mut_ptr(),
+                kernel.as_mut_ptr(),
+                user.as_mut_ptr(),
+            ) == 0
+            {
+                return Err(last_error());
+            }
+            let cpu_time = from_intervals(intervals(kernel.assume_init()))
+                + from_intervals(intervals(user.assume_init()));
+            let started_at = SystemTime::UNIX_EPOCH
+                + from_intervals(
+                    intervals(creation.assume_init()).saturating_sub(UNIX_EPOCH_INTERVALS),
+                );
+
+            let mut counters = MaybeUninit::<PROCESS_MEMORY_COUNTERS>::zeroed();
+            if K32GetProcessMemoryInfo(
+                process,
+                counters.as_mut_ptr(),
+                size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            ) == 0
+            {
+                return Err(last_error());
+            }
+            let memory = counters.assume_init().WorkingSetSize as u64;
+
+            let mut handle_count = 0;
+            if GetProcessHandleCount(process, &mut handle_count) == 0 {
+                return Err(last_error());
+            }
+
+            Ok(Sample {
+                cpu_time,
+                memory,
+                handle_count: handle_count as u64,
+                started_at,
+            })
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod imp {
+    use super::*;
+
+    pub(super) fn sample() -> crate::Result<Sample> {
+        Err(crate::Error::UnsupportedPlatform)
+    }
+}
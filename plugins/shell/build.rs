@@ -86,6 +86,10 @@ pub(crate) enum ShellScopeEntry {
         /// The allowed arguments for the command execution.
         #[serde(default)]
         args: ShellScopeEntryAllowedArgs,
+        /// Whether the command can be executed with administrator privileges
+        /// using the `elevated` option of the webview API.
+        #[serde(default)]
+        elevated: bool,
     },
     Sidecar {
         /// The name for this allowed shell command configuration.
@@ -98,6 +102,10 @@ pub(crate) enum ShellScopeEntry {
         args: ShellScopeEntryAllowedArgs,
         /// If this command is a sidecar command.
         sidecar: bool,
+        /// Whether the command can be executed with administrator privileges
+        /// using the `elevated` option of the webview API.
+        #[serde(default)]
+        elevated: bool,
     },
 }
 
@@ -111,11 +119,13 @@ fn _f() {
         name: String::new(),
         args: ShellScopeEntryAllowedArgs::Flag(false),
         sidecar: true,
+        elevated: false,
     }) {
         ShellScopeEntry::Command {
             name,
             command,
             args,
+            elevated,
         } => scope_entry::EntryRaw {
             name,
             command: Some(command),
@@ -135,11 +145,13 @@ fn _f() {
                 ),
             },
             sidecar: false,
+            elevated,
         },
         ShellScopeEntry::Sidecar {
             name,
             args,
             sidecar,
+            elevated,
         } => scope_entry::EntryRaw {
             name,
             command: None,
@@ -159,11 +171,28 @@ fn _f() {
                 ),
             },
             sidecar,
+            elevated,
         },
     };
 }
 
-const COMMANDS: &[&str] = &["execute", "spawn", "stdin_write", "kill", "open"];
+const COMMANDS: &[&str] = &[
+    "execute",
+    "spawn",
+    "stdin_write",
+    "stdin_write_chunk",
+    "close_stdin",
+    "resize",
+    "kill",
+    "kill_tree",
+    "terminate",
+    "stats",
+    "spawn_supervised",
+    "supervisor_write",
+    "supervisor_pid",
+    "supervisor_stop",
+    "open",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
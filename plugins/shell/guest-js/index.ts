@@ -73,12 +73,215 @@ interface SpawnOptions {
   cwd?: string
   /** Environment variables. set to `null` to clear the process env. */
   env?: Record<string, string>
+  /**
+   * Clears the process env before setting {@linkcode SpawnOptions.env}, so the parent environment isn't inherited.
+   * The default environment of sidecars is still set.
+   *
+   * @since 2.3.0
+   */
+  clearEnv?: boolean
+  /**
+   * Environment variables removed after setting {@linkcode SpawnOptions.env}.
+   *
+   * @since 2.3.0
+   */
+  removeEnv?: string[]
+  /**
+   * Paths prepended to the `PATH` environment variable after setting {@linkcode SpawnOptions.env}.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const command = Command.create('node', ['build.js'], {
+   *   clearEnv: true,
+   *   env: { PATH: '/usr/bin:/bin' },
+   *   prependPath: ['/opt/node/bin']
+   * });
+   * ```
+   *
+   * @since 2.3.0
+   */
+  prependPath?: string[]
   /**
    * Character encoding for stdout/stderr
    *
    * @since 2.0.0
    *  */
   encoding?: string
+  /**
+   * Character encoding for stdout, overriding {@linkcode SpawnOptions.encoding}. `raw` emits the bytes as received.
+   *
+   * With a per-stream encoding, the output is decoded as it's read so multi-byte encodings such as `utf-16le` are split in lines correctly,
+   * and invalid characters are replaced instead of emitting an error.
+   *
+   * @since 2.3.0
+   */
+  stdoutEncoding?: string
+  /**
+   * Character encoding for stderr, overriding {@linkcode SpawnOptions.encoding}, see {@linkcode SpawnOptions.stdoutEncoding}.
+   *
+   * @since 2.3.0
+   */
+  stderrEncoding?: string
+  /**
+   * Emits stdout and stderr in the order they are read on the {@linkcode Command.output} emitter, tagged with their stream.
+   *
+   * {@linkcode Command.execute} returns the stderr in `stdout` instead, decoded with its encoding.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const command = Command.create('build.bat', [], {
+   *   mergeStreams: true,
+   *   stderrEncoding: 'utf-16le'
+   * });
+   * command.output.on('data', ({ stream, data }) => console.log(`[${stream}] ${data}`));
+   * await command.spawn();
+   * ```
+   *
+   * @since 2.3.0
+   */
+  mergeStreams?: boolean
+  /**
+   * Attaches the child process to a pseudo-terminal, so interactive programs keep their colors
+   * and don't buffer their output. `true` uses a 80x24 terminal.
+   *
+   * The output is emitted on `stdout` as soon as it is read, stderr included.
+   * Only supported by {@linkcode Command.spawn} on desktop.
+   *
+   * @since 2.3.0
+   */
+  pty?: boolean | PtySize
+  /**
+   * Kills the child process and its descendants once it ran for longer than this many milliseconds.
+   *
+   * {@linkcode Command.execute} then rejects with a `command timed out` error,
+   * a spawned child emits it as an `error` event right before `close`.
+   *
+   * @since 2.3.0
+   */
+  timeoutMs?: number
+  /**
+   * Kills the child process and its descendants once it wrote more than this many bytes to stdout and stderr combined.
+   * The output past the limit is dropped.
+   *
+   * {@linkcode Command.execute} then rejects with a `command output exceeded` error,
+   * a spawned child emits it as an `error` event right before `close`.
+   *
+   * @since 2.3.0
+   */
+  maxOutputBytes?: number
+  /**
+   * Asks the user for administrator privileges and runs the command with them,
+   * with `runas` on Windows, an authentication dialog on macOS and `pkexec` on Linux.
+   * The command must be allowed with `elevated: true` in its shell scope entry.
+   *
+   * Only supported by {@linkcode Command.execute} on desktop, see {@linkcode ChildProcess.elevationGranted}.
+   * The output isn't captured on Windows, and the limits are not enforced.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const output = await Command.create('install-driver', [], { elevated: true }).execute();
+   * if (!output.elevationGranted) {
+   *   console.log('the installation was cancelled');
+   * }
+   * ```
+   *
+   * @since 2.3.0
+   */
+  elevated?: boolean
+}
+
+/**
+ * Size of a pseudo-terminal, in characters.
+ *
+ * @since 2.3.0
+ */
+interface PtySize {
+  cols: number
+  rows: number
+}
+
+/**
+ * Signal asking a child process to exit. A `CTRL_BREAK` event is sent instead on Windows.
+ *
+ * @since 2.3.0
+ */
+type Signal = 'SIGTERM' | 'SIGINT' | 'SIGHUP' | 'SIGQUIT'
+
+/**
+ * @since 2.3.0
+ */
+interface TerminateOptions {
+  /** The signal sent first. Defaults to `SIGTERM`. */
+  signal?: Signal
+  /** Milliseconds to wait for the child to exit before killing it. Defaults to 5000. */
+  timeout?: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface StdinStreamOptions {
+  /**
+   * Bytes queued by the stream before {@linkcode WritableStreamDefaultWriter.desiredSize} reports backpressure.
+   * Defaults to 1 MiB.
+   */
+  highWaterMark?: number
+}
+
+/**
+ * Delay before restarting a crashed process, multiplied after each restart.
+ *
+ * @since 2.3.0
+ */
+interface BackoffOptions {
+  /** Milliseconds before the first restart. Defaults to 500. */
+  initialMs?: number
+  /** Maximum milliseconds between two restarts. Defaults to 30000. */
+  maxMs?: number
+  /** Factor the delay is multiplied by after each restart. Defaults to 2. */
+  multiplier?: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface SupervisorOptions {
+  /** Restarts the process when it exits successfully too, it's only restarted when it crashes by default. */
+  restartOnExit?: boolean
+  /** Maximum number of restarts. The process is restarted indefinitely by default. */
+  maxRestarts?: number
+  /** Delay before restarting the process. */
+  backoff?: BackoffOptions
+}
+
+/**
+ * Resource usage of a child process, see {@linkcode Child.stats}.
+ *
+ * @since 2.3.0
+ */
+interface ChildStats {
+  /**
+   * CPU usage since the previous call, or since the process was spawned on the first call,
+   * in percent of a single core so it exceeds 100 when several cores are used.
+   */
+  cpuPercent: number
+  /** Resident set size in bytes, the working set on Windows. */
+  rssBytes: number
+  /** Milliseconds elapsed since the process was spawned. */
+  runtimeMs: number
+  /** Number of processes the usage was summed over. */
+  processes: number
+}
+
+/**
+ * @since 2.3.0
+ */
+interface ChildStatsOptions {
+  /** Sums the usage of the descendants of the process too. Defaults to `false`. */
+  tree?: boolean
 }
 
 /** @ignore */
@@ -98,6 +301,13 @@ interface ChildProcess<O extends IOPayload> {
   stdout: O
   /** The data that the process wrote to `stderr`. */
   stderr: O
+  /**
+   * Whether the user granted the administrator privileges, only set for {@linkcode SpawnOptions.elevated} commands.
+   * The command didn't run if `false`.
+   *
+   * @since 2.3.0
+   */
+  elevationGranted?: boolean
 }
 
 /**
@@ -326,6 +536,83 @@ class Child {
     })
   }
 
+  /**
+   * Writes the binary `data` to the `stdin` without serializing it to JSON.
+   *
+   * The promise resolves once the child process read enough of its `stdin` to accept the whole chunk,
+   * so awaiting each write keeps a large input from being buffered in memory.
+   *
+   * @since 2.3.0
+   */
+  async writeChunk(data: Uint8Array | ArrayBuffer): Promise<void> {
+    await invoke('plugin:shell|stdin_write_chunk', data, {
+      headers: {
+        pid: this.pid.toString()
+      }
+    })
+  }
+
+  /**
+   * Closes the `stdin` so the child process reads the end of file.
+   * Writing to it afterwards fails.
+   *
+   * @since 2.3.0
+   */
+  async closeStdin(): Promise<void> {
+    await invoke('plugin:shell|close_stdin', {
+      pid: this.pid
+    })
+  }
+
+  /**
+   * Returns a stream writing to the `stdin` with {@linkcode Child.writeChunk}, closing the stream closes the `stdin`.
+   *
+   * The writer's `desiredSize` and `ready` report the backpressure of the child process.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const child = await Command.create('ffmpeg', ['-i', 'pipe:0', 'out.mp4']).spawn();
+   * const response = await fetch('https://example.com/input.mkv');
+   * await response.body!.pipeTo(child.stdinStream());
+   * ```
+   *
+   * @since 2.3.0
+   */
+  stdinStream(options: StdinStreamOptions = {}): WritableStream<Uint8Array> {
+    return new WritableStream<Uint8Array>(
+      {
+        write: (chunk) => this.writeChunk(chunk),
+        close: () => this.closeStdin(),
+        abort: () => this.closeStdin()
+      },
+      new ByteLengthQueuingStrategy({
+        highWaterMark: options.highWaterMark ?? 1024 * 1024
+      })
+    )
+  }
+
+  /**
+   * Resizes the pseudo-terminal of a child spawned with the {@linkcode SpawnOptions.pty} option.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const command = Command.create('bash', [], { pty: true, encoding: 'raw' });
+   * const child = await command.spawn();
+   * await child.resize(120, 40);
+   * ```
+   *
+   * @since 2.3.0
+   */
+  async resize(cols: number, rows: number): Promise<void> {
+    await invoke('plugin:shell|resize', {
+      pid: this.pid,
+      cols,
+      rows
+    })
+  }
+
   /**
    * Kills the child process.
    *
@@ -339,6 +626,113 @@ class Child {
       pid: this.pid
     })
   }
+
+  /**
+   * Kills the child process and all its descendants.
+   *
+   * @returns A promise indicating the success or failure of the operation.
+   *
+   * @since 2.3.0
+   */
+  async killTree(): Promise<void> {
+    await invoke('plugin:shell|kill_tree', {
+      pid: this.pid
+    })
+  }
+
+  /**
+   * Asks the child process and its descendants to exit,
+   * then kills all of them once the child exited or after the timeout.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const child = await Command.create('npm', ['run', 'dev']).spawn();
+   * await child.terminate({ signal: 'SIGINT', timeout: 3000 });
+   * ```
+   *
+   * @returns A promise resolved once the processes are killed.
+   *
+   * @since 2.3.0
+   */
+  async terminate(options: TerminateOptions = {}): Promise<void> {
+    await invoke('plugin:shell|terminate', {
+      pid: this.pid,
+      signal: options.signal,
+      timeout: options.timeout
+    })
+  }
+
+  /**
+   * Returns the CPU usage, memory and runtime of the child process, polled from the operating system.
+   * The CPU usage is measured since the previous call, so it should be polled at an interval.
+   *
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const child = await Command.sidecar('ffmpeg', ['-i', 'input.mp4', 'output.webm']).spawn();
+   * setInterval(async () => {
+   *   const stats = await child.stats({ tree: true });
+   *   if (stats && stats.rssBytes > 2 * 1024 ** 3) {
+   *     await child.killTree();
+   *   }
+   * }, 1000);
+   * ```
+   *
+   * @returns A promise resolving to the usage, `null` once the child exited.
+   *
+   * @since 2.3.0
+   */
+  async stats(options: ChildStatsOptions = {}): Promise<ChildStats | null> {
+    return await invoke<ChildStats | null>('plugin:shell|stats', {
+      pid: this.pid,
+      tree: options.tree
+    })
+  }
+}
+
+/**
+ * Handle to a supervised process, which stays the same when the process is restarted.
+ *
+ * @since 2.3.0
+ */
+class Supervisor {
+  /** The supervisor identifier. */
+  id: number
+
+  constructor(id: number) {
+    this.id = id
+  }
+
+  /**
+   * Returns the `pid` of the current process, `null` while it's restarted or once the supervision ended.
+   */
+  async pid(): Promise<number | null> {
+    return await invoke<number | null>('plugin:shell|supervisor_pid', {
+      id: this.id
+    })
+  }
+
+  /**
+   * Writes `data` to the `stdin` of the current process.
+   *
+   * @param data The message to write, either a string or a byte array.
+   */
+  async write(data: IOPayload | number[]): Promise<void> {
+    await invoke('plugin:shell|supervisor_write', {
+      id: this.id,
+      buffer: data
+    })
+  }
+
+  /**
+   * Stops the supervision and kills the current process with its descendants.
+   */
+  async stop(): Promise<void> {
+    await invoke('plugin:shell|supervisor_stop', {
+      id: this.id
+    })
+  }
 }
 
 interface CommandEvents {
@@ -346,7 +740,33 @@ interface CommandEvents {
   error: string
 }
 
-interface OutputEvents<O extends IOPayload> {
+/**
+ * Lifecycle events of a supervised process, see {@linkcode Command.spawnSupervised}.
+ *
+ * @since 2.3.0
+ */
+interface SupervisorEvents {
+  /** The process was spawned. */
+  started: { pid: number }
+  /** The process exited with a failure status or was killed by a signal. */
+  crashed: TerminatedPayload
+  /** The process was spawned again after it exited. */
+  restarted: { pid: number; restarts: number }
+  /** The process won't be restarted anymore, after too many restarts or if it failed to spawn. */
+  'gave-up': { restarts: number; error: string | null }
+}
+
+interface OutputEvents<O> {
+  data: O
+}
+
+/**
+ * Output line tagged with its stream, see {@linkcode SpawnOptions.mergeStreams}.
+ *
+ * @since 2.3.0
+ */
+interface TaggedOutput<O extends IOPayload> {
+  stream: 'stdout' | 'stderr'
   data: O
 }
 
@@ -382,6 +802,19 @@ class Command<O extends IOPayload> extends EventEmitter<CommandEvents> {
   readonly stdout = new EventEmitter<OutputEvents<O>>()
   /** Event emitter for the `stderr`. Emits the `data` event. */
   readonly stderr = new EventEmitter<OutputEvents<O>>()
+  /**
+   * Event emitter for the merged `stdout` and `stderr`, used with {@linkcode SpawnOptions.mergeStreams}. Emits the `data` event.
+   *
+   * @since 2.3.0
+   */
+  readonly output = new EventEmitter<OutputEvents<TaggedOutput<O>>>()
+  /**
+   * Event emitter for the lifecycle of a process spawned with {@linkcode Command.spawnSupervised}.
+   * Emits the `started`, `crashed`, `restarted` and `gave-up` events.
+   *
+   * @since 2.3.0
+   */
+  readonly lifecycle = new EventEmitter<SupervisorEvents>()
 
   /**
    * @ignore
@@ -485,6 +918,52 @@ class Command<O extends IOPayload> extends EventEmitter<CommandEvents> {
       Object.freeze(args)
     }
 
+    return await invoke<number>('plugin:shell|spawn', {
+      program,
+      args,
+      options,
+      onEvent: this.eventChannel()
+    }).then((pid) => new Child(pid))
+  }
+
+  /**
+   * Executes the command as a child process and restarts it when it crashes.
+   * The output of every process is emitted by the `stdout` and `stderr` event emitters,
+   * and their lifecycle by {@linkcode Command.lifecycle}.
+   * @example
+   * ```typescript
+   * import { Command } from '@tauri-apps/plugin-shell';
+   * const command = Command.sidecar('server');
+   * command.lifecycle.on('crashed', ({ code }) => console.error(`server crashed with code ${code}`));
+   * command.lifecycle.on('gave-up', () => console.error('server stopped'));
+   * const supervisor = await command.spawnSupervised({ maxRestarts: 5 });
+   * // later
+   * await supervisor.stop();
+   * ```
+   *
+   * @returns A promise resolving to the supervisor handle.
+   *
+   * @since 2.3.0
+   */
+  async spawnSupervised(options: SupervisorOptions = {}): Promise<Supervisor> {
+    const program = this.program
+    const args = this.args
+
+    if (typeof args === 'object') {
+      Object.freeze(args)
+    }
+
+    return await invoke<number>('plugin:shell|spawn_supervised', {
+      program,
+      args,
+      options: this.options,
+      supervisor: options,
+      onEvent: this.eventChannel()
+    }).then((id) => new Supervisor(id))
+  }
+
+  /** @ignore */
+  private eventChannel(): Channel<CommandEvent<O>> {
     const onEvent = new Channel<CommandEvent<O>>()
     onEvent.onmessage = (event) => {
       switch (event.event) {
@@ -500,15 +979,24 @@ class Command<O extends IOPayload> extends EventEmitter<CommandEvents> {
         case 'Stderr':
           this.stderr.emit('data', event.payload)
           break
+        case 'Output':
+          this.output.emit('data', event.payload)
+          break
+        case 'Started':
+          this.lifecycle.emit('started', event.payload)
+          break
+        case 'Crashed':
+          this.lifecycle.emit('crashed', event.payload)
+          break
+        case 'Restarted':
+          this.lifecycle.emit('restarted', event.payload)
+          break
+        case 'GaveUp':
+          this.lifecycle.emit('gave-up', event.payload)
+          break
       }
     }
-
-    return await invoke<number>('plugin:shell|spawn', {
-      program,
-      args,
-      options,
-      onEvent
-    }).then((pid) => new Child(pid))
+    return onEvent
   }
 
   /**
@@ -571,6 +1059,11 @@ type CommandEvent<O extends IOPayload> =
   | Event<'Stderr', O>
   | Event<'Terminated', TerminatedPayload>
   | Event<'Error', string>
+  | Event<'Output', TaggedOutput<O>>
+  | Event<'Started', SupervisorEvents['started']>
+  | Event<'Crashed', TerminatedPayload>
+  | Event<'Restarted', SupervisorEvents['restarted']>
+  | Event<'GaveUp', SupervisorEvents['gave-up']>
 
 /**
  * Opens a path or URL with the system's default app,
@@ -605,12 +1098,22 @@ async function open(path: string, openWith?: string): Promise<void> {
   })
 }
 
-export { Command, Child, EventEmitter, open }
+export { Command, Child, Supervisor, EventEmitter, open }
 export type {
   IOPayload,
   CommandEvents,
   TerminatedPayload,
   OutputEvents,
   ChildProcess,
-  SpawnOptions
+  SpawnOptions,
+  PtySize,
+  Signal,
+  TerminateOptions,
+  StdinStreamOptions,
+  TaggedOutput,
+  SupervisorOptions,
+  BackoffOptions,
+  SupervisorEvents,
+  ChildStats,
+  ChildStatsOptions
 }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{collections::HashMap, future::Future, path::PathBuf, pin::Pin, string::FromUtf8Error};
+use std::{
+    collections::HashMap, future::Future, path::PathBuf, pin::Pin, string::FromUtf8Error,
+    sync::atomic::Ordering, time::Duration,
+};
 
 use encoding_rs::Encoding;
 use serde::{Deserialize, Serialize};
@@ -14,12 +17,19 @@ use tauri::{
 #[allow(deprecated)]
 use crate::open::Program;
 use crate::{
-    process::{CommandEvent, TerminatedPayload},
+    process::{
+        Backoff, ChildStdin, CommandChild, CommandEvent, PtySize, Signal, SupervisorEvent,
+        SupervisorOptions, TerminatedPayload,
+    },
     scope::ExecuteArgs,
     Shell,
 };
 
 type ChildId = u32;
+type SupervisorId = u32;
+
+/// Time given to a child to exit after it was asked to, when not set by the webview.
+const DEFAULT_TERMINATE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event", content = "payload")]
@@ -33,6 +43,33 @@ pub enum JSCommandEvent {
     Error(String),
     /// Command process terminated.
     Terminated(TerminatedPayload),
+    /// Stdout or stderr line tagged with its stream, when the streams are merged.
+    Output(TaggedOutput),
+    /// Supervised process spawned.
+    Started { pid: u32 },
+    /// Supervised process exited with a failure status or was killed by a signal.
+    Crashed(TerminatedPayload),
+    /// Supervised process spawned again after it exited.
+    Restarted { pid: u32, restarts: u32 },
+    /// Supervised process won't be restarted anymore.
+    GaveUp {
+        restarts: u32,
+        error: Option<String>,
+    },
+}
+
+/// The stream of a [`JSCommandEvent::Output`] event.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaggedOutput {
+    stream: OutputStream,
+    data: Buffer,
 }
 
 fn get_event_buffer(line: Vec<u8>, encoding: EncodingWrapper) -> Result<Buffer, FromUtf8Error> {
@@ -76,6 +113,148 @@ pub enum EncodingWrapper {
     Text(Option<&'static Encoding>),
 }
 
+impl EncodingWrapper {
+    fn from_label(label: String) -> crate::Result<Self> {
+        if label == "raw" {
+            return Ok(Self::Raw);
+        }
+        match Encoding::for_label(label.as_bytes()) {
+            Some(encoding) => Ok(Self::Text(Some(encoding))),
+            None => Err(crate::Error::UnknownEncoding(label)),
+        }
+    }
+}
+
+/// The encodings of the output streams of a command.
+#[derive(Debug, Copy, Clone)]
+pub struct OutputEncodings {
+    stdout: EncodingWrapper,
+    stderr: EncodingWrapper,
+    merge_streams: bool,
+    /// Whether the output is read as raw bytes and decoded with a [`LiveDecoder`].
+    live: bool,
+}
+
+impl OutputEncodings {
+    fn live_decoder(&self) -> Option<LiveDecoder> {
+        self.live.then(|| LiveDecoder {
+            stdout: LineDecoder::new(self.stdout),
+            stderr: LineDecoder::new(self.stderr),
+            merge_streams: self.merge_streams,
+        })
+    }
+}
+
+/// Decodes a stream as it's read and splits it into lines, so multi-byte encodings such as UTF-16 are supported.
+enum LineDecoder {
+    Raw,
+    Text {
+        decoder: encoding_rs::Decoder,
+        pending: String,
+    },
+}
+
+impl LineDecoder {
+    fn new(encoding: EncodingWrapper) -> Self {
+        match encoding {
+            EncodingWrapper::Raw => Self::Raw,
+            EncodingWrapper::Text(encoding) => Self::Text {
+                decoder: encoding
+                    .unwrap_or(encoding_rs::UTF_8)
+                    .new_decoder_with_bom_removal(),
+                pending: String::new(),
+            },
+        }
+    }
+
+    /// Returns the lines completed by the bytes, without their newline (\n) or carriage return (\r),
+    /// and the remaining text if it's the `last` bytes of the stream.
+    fn decode(&mut self, bytes: &[u8], last: bool) -> Vec<Buffer> {
+        let (decoder, pending) = match self {
+            Self::Raw if bytes.is_empty() => return Vec::new(),
+            Self::Raw => return vec![Buffer::Raw(bytes.to_vec())],
+            Self::Text { decoder, pending } => (decoder, pending),
+        };
+
+        if let Some(len) = decoder.max_utf8_buffer_length(bytes.len()) {
+            pending.reserve(len);
+        }
+        let _ = decoder.decode_to_string(bytes, pending, last);
+
+        let text = pending.as_bytes();
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+        while i < text.len() {
+            match text[i] {
+                b'\n' => {
+                    lines.push(Buffer::Text(pending[start..i].to_string()));
+                    start = i + 1;
+                }
+                b'\r' => {
+                    // the carriage return may be followed by a newline in the next bytes
+                    if i + 1 == text.len() && !last {
+                        break;
+                    }
+                    lines.push(Buffer::Text(pending[start..i].to_string()));
+                    if text.get(i + 1) == Some(&b'\n') {
+                        i += 1;
+                    }
+                    start = i + 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        pending.drain(..start);
+
+        if last && !pending.is_empty() {
+            lines.push(Buffer::Text(std::mem::take(pending)));
+        }
+        lines
+    }
+}
+
+/// Decodes the output events of a child whose streams have their own encoding or are merged.
+struct LiveDecoder {
+    stdout: LineDecoder,
+    stderr: LineDecoder,
+    merge_streams: bool,
+}
+
+impl LiveDecoder {
+    fn events(&mut self, event: CommandEvent) -> Vec<JSCommandEvent> {
+        match event {
+            CommandEvent::Stdout(bytes) => self.output(OutputStream::Stdout, &bytes, false),
+            CommandEvent::Stderr(bytes) => self.output(OutputStream::Stderr, &bytes, false),
+            CommandEvent::Error(error) => vec![JSCommandEvent::Error(error)],
+            CommandEvent::Terminated(payload) => {
+                let mut events = self.output(OutputStream::Stdout, &[], true);
+                events.extend(self.output(OutputStream::Stderr, &[], true));
+                events.push(JSCommandEvent::Terminated(payload));
+                events
+            }
+        }
+    }
+
+    fn output(&mut self, stream: OutputStream, bytes: &[u8], last: bool) -> Vec<JSCommandEvent> {
+        let merge_streams = self.merge_streams;
+        let decoder = match stream {
+            OutputStream::Stdout => &mut self.stdout,
+            OutputStream::Stderr => &mut self.stderr,
+        };
+        decoder
+            .decode(bytes, last)
+            .into_iter()
+            .map(|data| match stream {
+                _ if merge_streams => JSCommandEvent::Output(TaggedOutput { stream, data }),
+                OutputStream::Stdout => JSCommandEvent::Stdout(data),
+                OutputStream::Stderr => JSCommandEvent::Stderr(data),
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommandOptions {
@@ -86,8 +265,49 @@ pub struct CommandOptions {
     // but the env is an `Option` so when it's `None` we clear the env.
     #[serde(default = "default_env")]
     env: Option<HashMap<String, String>>,
+    // Clears the env before setting `env`, same as a `None` env
+    #[serde(default)]
+    clear_env: bool,
+    // Env variables removed after setting `env`
+    #[serde(default)]
+    remove_env: Vec<String>,
+    // Paths prepended to the PATH env variable after setting `env`
+    #[serde(default)]
+    prepend_path: Vec<PathBuf>,
     // Character encoding for stdout/stderr
     encoding: Option<String>,
+    // Character encodings overriding `encoding` for a single stream
+    stdout_encoding: Option<String>,
+    stderr_encoding: Option<String>,
+    // Sends stdout and stderr tagged in a single stream
+    #[serde(default)]
+    merge_streams: bool,
+    // Attaches the child to a pseudo-terminal, only supported by `spawn`
+    pty: Option<PtyOption>,
+    // Kills the child once it ran for longer
+    timeout_ms: Option<u64>,
+    // Kills the child once its stdout and stderr are larger
+    max_output_bytes: Option<usize>,
+    // Runs the command with administrator privileges, only supported by `execute`
+    #[serde(default)]
+    elevated: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PtyOption {
+    Flag(bool),
+    Size(PtySize),
+}
+
+impl PtyOption {
+    fn size(&self) -> Option<PtySize> {
+        match self {
+            Self::Flag(false) => None,
+            Self::Flag(true) => Some(PtySize::default()),
+            Self::Size(size) => Some(*size),
+        }
+    }
 }
 
 #[allow(clippy::unnecessary_wraps)]
@@ -103,15 +323,21 @@ fn prepare_cmd<R: Runtime>(
     options: CommandOptions,
     command_scope: CommandScope<crate::scope::ScopeAllowedCommand>,
     global_scope: GlobalScope<crate::scope::ScopeAllowedCommand>,
-) -> crate::Result<(crate::process::Command, EncodingWrapper)> {
+) -> crate::Result<(crate::process::Command, OutputEncodings)> {
+    let dynamic_scope = window.state::<Shell<R>>().scope().commands();
     let scope = crate::scope::ShellScope {
         scopes: command_scope
             .allows()
             .iter()
             .chain(global_scope.allows())
+            .chain(&dynamic_scope)
             .collect(),
     };
 
+    if options.elevated {
+        scope.ensure_elevation_allowed(&program)?;
+    }
+
     let mut command = if options.sidecar {
         let program = PathBuf::from(program);
         let program_as_string = program.display().to_string();
@@ -144,30 +370,61 @@ fn prepare_cmd<R: Runtime>(
     if let Some(cwd) = options.cwd {
         command = command.current_dir(cwd);
     }
+    if let Some(size) = options.pty.as_ref().and_then(PtyOption::size) {
+        command = command.pty(size);
+    }
+    if let Some(timeout) = options.timeout_ms {
+        command = command.timeout(Duration::from_millis(timeout));
+    }
+    if let Some(max) = options.max_output_bytes {
+        command = command.max_output_bytes(max);
+    }
+    if options.elevated {
+        command = command.elevated(true);
+    }
+    if options.clear_env || options.env.is_none() {
+        command = command.env_clear();
+    }
+    if options.sidecar {
+        command = command.envs(&window.state::<Shell<R>>().sidecar_env);
+    }
     if let Some(env) = options.env {
         command = command.envs(env);
-    } else {
-        command = command.env_clear();
+    }
+    for key in options.remove_env {
+        command = command.env_remove(key);
+    }
+    if !options.prepend_path.is_empty() {
+        command = command.prepend_path(options.prepend_path)?;
     }
 
-    let encoding = match options.encoding {
-        Option::None => EncodingWrapper::Text(None),
-        Some(encoding) => match encoding.as_str() {
-            "raw" => {
-                command = command.set_raw_out(true);
-                EncodingWrapper::Raw
-            }
-            _ => {
-                if let Some(text_encoding) = Encoding::for_label(encoding.as_bytes()) {
-                    EncodingWrapper::Text(Some(text_encoding))
-                } else {
-                    return Err(crate::Error::UnknownEncoding(encoding));
-                }
-            }
-        },
+    let encoding = options
+        .encoding
+        .map(EncodingWrapper::from_label)
+        .transpose()?
+        .unwrap_or(EncodingWrapper::Text(None));
+    let live = options.stdout_encoding.is_some()
+        || options.stderr_encoding.is_some()
+        || options.merge_streams;
+    let encodings = OutputEncodings {
+        stdout: options
+            .stdout_encoding
+            .map(EncodingWrapper::from_label)
+            .transpose()?
+            .unwrap_or(encoding),
+        stderr: options
+            .stderr_encoding
+            .map(EncodingWrapper::from_label)
+            .transpose()?
+            .unwrap_or(encoding),
+        merge_streams: options.merge_streams,
+        live,
     };
+    if live || matches!(encoding, EncodingWrapper::Raw) {
+        command = command.set_raw_out(true);
+    }
 
-    Ok((command, encoding))
+    Ok((command, encodings))
 }
 
 #[derive(Serialize)]
@@ -178,11 +435,89 @@ enum Output {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ChildProcessReturn {
     code: Option<i32>,
     signal: Option<i32>,
     stdout: Output,
     stderr: Output,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elevation_granted: Option<bool>,
+}
+
+fn decode_output(bytes: Vec<u8>, encoding: EncodingWrapper) -> crate::Result<Output> {
+    Ok(match encoding {
+        EncodingWrapper::Text(Some(encoding)) => {
+            Output::String(encoding.decode_with_bom_removal(&bytes).0.into())
+        }
+        EncodingWrapper::Text(None) => Output::String(String::from_utf8(bytes)?),
+        EncodingWrapper::Raw => Output::Raw(bytes),
+    })
+}
+
+struct RawOutput {
+    status: TerminatedPayload,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    elevation_granted: Option<bool>,
+}
+
+/// Runs the command, returning its output before it's decoded.
+///
+/// The stderr is written to the stdout if `merge_streams` is set.
+async fn raw_output(
+    command: crate::process::Command,
+    merge_streams: bool,
+) -> crate::Result<RawOutput> {
+    if command.is_elevated() {
+        let mut output = command.output().await?;
+        if merge_streams {
+            let stderr = std::mem::take(&mut output.stderr);
+            output.stdout.extend(stderr);
+        }
+        return Ok(RawOutput {
+            status: TerminatedPayload {
+                code: output.status.code(),
+                signal: None,
+            },
+            elevation_granted: output.status.elevation_granted(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        });
+    }
+
+    let (mut rx, mut child) = command.set_raw_out(true).spawn()?;
+    let exceeded_limit = child.exceeded_limit();
+    // the child can't wait on its stdin
+    child.close_stdin();
+    drop(child);
+
+    let mut status = None;
+    let mut error = None;
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    while let Some(event) = rx.recv().await {
+        match event {
+            CommandEvent::Stdout(bytes) => stdout.extend(bytes),
+            CommandEvent::Stderr(bytes) if merge_streams => stdout.extend(bytes),
+            CommandEvent::Stderr(bytes) => stderr.extend(bytes),
+            CommandEvent::Error(e) => error = Some(e),
+            CommandEvent::Terminated(payload) => status = Some(payload),
+        }
+    }
+    if let Some(error) = exceeded_limit.error() {
+        return Err(error);
+    }
+    let status = status.ok_or_else(|| {
+        std::io::Error::other(error.unwrap_or_else(|| "the command did not terminate".into()))
+    })?;
+
+    Ok(RawOutput {
+        status,
+        stdout,
+        stderr,
+        elevation_granted: None,
+    })
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -195,35 +530,27 @@ pub async fn execute<R: Runtime>(
     command_scope: CommandScope<crate::scope::ScopeAllowedCommand>,
     global_scope: GlobalScope<crate::scope::ScopeAllowedCommand>,
 ) -> crate::Result<ChildProcessReturn> {
-    let (command, encoding) =
-        prepare_cmd(window, program, args, options, command_scope, global_scope)?;
-
-    let mut command: std::process::Command = command.into();
-    let output = command.output()?;
-
-    let (stdout, stderr) = match encoding {
-        EncodingWrapper::Text(Some(encoding)) => (
-            Output::String(encoding.decode_with_bom_removal(&output.stdout).0.into()),
-            Output::String(encoding.decode_with_bom_removal(&output.stderr).0.into()),
-        ),
-        EncodingWrapper::Text(None) => (
-            Output::String(String::from_utf8(output.stdout)?),
-            Output::String(String::from_utf8(output.stderr)?),
-        ),
-        EncodingWrapper::Raw => (Output::Raw(output.stdout), Output::Raw(output.stderr)),
-    };
+    if options.pty.as_ref().and_then(PtyOption::size).is_some() {
+        return Err(crate::Error::PtyUnsupported);
+    }
 
-    #[cfg(unix)]
-    use std::os::unix::process::ExitStatusExt;
+    let (command, encodings) =
+        prepare_cmd(window, program, args, options, command_scope, global_scope)?;
 
-    Ok(ChildProcessReturn {
-        code: output.status.code(),
-        #[cfg(windows)]
-        signal: None,
-        #[cfg(unix)]
-        signal: output.status.signal(),
+    // the output is decoded once complete
+    let RawOutput {
+        status,
         stdout,
         stderr,
+        elevation_granted,
+    } = raw_output(command, encodings.merge_streams).await?;
+
+    Ok(ChildProcessReturn {
+        code: status.code,
+        signal: status.signal,
+        stdout: decode_output(stdout, encodings.stdout)?,
+        stderr: decode_output(stderr, encodings.stderr)?,
+        elevation_granted,
     })
 }
 
@@ -239,10 +566,11 @@ pub fn spawn<R: Runtime>(
     command_scope: CommandScope<crate::scope::ScopeAllowedCommand>,
     global_scope: GlobalScope<crate::scope::ScopeAllowedCommand>,
 ) -> crate::Result<ChildId> {
-    let (command, encoding) =
+    let (command, encodings) =
         prepare_cmd(window, program, args, options, command_scope, global_scope)?;
 
     let (mut rx, child) = command.spawn()?;
+    let mut live_decoder = encodings.live_decoder();
 
     let pid = child.pid();
     shell.children.lock().unwrap().insert(pid, child);
@@ -253,21 +581,13 @@ pub fn spawn<R: Runtime>(
             if matches!(event, crate::process::CommandEvent::Terminated(_)) {
                 children.lock().unwrap().remove(&pid);
             };
-            let js_event = JSCommandEvent::new(event, encoding);
-
-            if on_event.send(js_event.clone()).is_err() {
-                fn send<'a>(
-                    on_event: &'a Channel<JSCommandEvent>,
-                    js_event: &'a JSCommandEvent,
-                ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
-                    Box::pin(async move {
-                        tokio::time::sleep(std::time::Duration::from_millis(15)).await;
-                        if on_event.send(js_event.clone()).is_err() {
-                            send(on_event, js_event).await;
-                        }
-                    })
-                }
-                send(&on_event, &js_event).await;
+            let js_events = match &mut live_decoder {
+                Some(decoder) => decoder.events(event),
+                None => vec![JSCommandEvent::new(event, encodings.stdout)],
+            };
+
+            for js_event in js_events {
+                send_event(&on_event, &js_event).await;
             }
         }
     });
@@ -275,18 +595,196 @@ pub fn spawn<R: Runtime>(
     Ok(pid)
 }
 
+/// Sends the event to the webview, retrying until the channel accepts it.
+async fn send_event(on_event: &Channel<JSCommandEvent>, js_event: &JSCommandEvent) {
+    if on_event.send(js_event.clone()).is_err() {
+        fn send<'a>(
+            on_event: &'a Channel<JSCommandEvent>,
+            js_event: &'a JSCommandEvent,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+            Box::pin(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(15)).await;
+                if on_event.send(js_event.clone()).is_err() {
+                    send(on_event, js_event).await;
+                }
+            })
+        }
+        send(on_event, js_event).await;
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisedOptions {
+    // Restarts the child when it exits successfully too
+    #[serde(default)]
+    restart_on_exit: bool,
+    max_restarts: Option<u32>,
+    backoff: Option<BackoffOptions>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackoffOptions {
+    initial_ms: Option<u64>,
+    max_ms: Option<u64>,
+    multiplier: Option<f64>,
+}
+
+impl From<SupervisedOptions> for SupervisorOptions {
+    fn from(options: SupervisedOptions) -> Self {
+        let mut backoff = Backoff::default();
+        if let Some(options) = options.backoff {
+            if let Some(initial) = options.initial_ms {
+                backoff.initial = Duration::from_millis(initial);
+            }
+            if let Some(max) = options.max_ms {
+                backoff.max = Duration::from_millis(max);
+            }
+            if let Some(multiplier) = options.multiplier {
+                backoff.multiplier = multiplier;
+            }
+        }
+        SupervisorOptions {
+            restart_on_exit: options.restart_on_exit,
+            max_restarts: options.max_restarts,
+            backoff,
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub fn stdin_write<R: Runtime>(
+pub fn spawn_supervised<R: Runtime>(
+    window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    program: String,
+    args: ExecuteArgs,
+    on_event: Channel<JSCommandEvent>,
+    options: CommandOptions,
+    supervisor: SupervisedOptions,
+    command_scope: CommandScope<crate::scope::ScopeAllowedCommand>,
+    global_scope: GlobalScope<crate::scope::ScopeAllowedCommand>,
+) -> crate::Result<SupervisorId> {
+    let (command, encodings) =
+        prepare_cmd(window, program, args, options, command_scope, global_scope)?;
+
+    let (mut rx, supervisor) = command.spawn_supervised(supervisor.into())?;
+
+    let id = shell.next_supervisor_id.fetch_add(1, Ordering::Relaxed);
+    shell.supervisors.lock().unwrap().insert(id, supervisor);
+    let supervisors = shell.supervisors.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut live_decoder = encodings.live_decoder();
+        while let Some(event) = rx.recv().await {
+            let js_events = match event {
+                SupervisorEvent::Child(event) => match &mut live_decoder {
+                    Some(decoder) => decoder.events(event),
+                    None => vec![JSCommandEvent::new(event, encodings.stdout)],
+                },
+                SupervisorEvent::Started { pid } => vec![JSCommandEvent::Started { pid }],
+                SupervisorEvent::Crashed(payload) => vec![JSCommandEvent::Crashed(payload)],
+                SupervisorEvent::Restarted { pid, restarts } => {
+                    // the decoders of the previous child were flushed when it terminated
+                    live_decoder = encodings.live_decoder();
+                    vec![JSCommandEvent::Restarted { pid, restarts }]
+                }
+                SupervisorEvent::GaveUp { restarts, error } => {
+                    vec![JSCommandEvent::GaveUp { restarts, error }]
+                }
+            };
+
+            for js_event in js_events {
+                send_event(&on_event, &js_event).await;
+            }
+        }
+        supervisors.lock().unwrap().remove(&id);
+    });
+
+    Ok(id)
+}
+
+fn child_stdin<R: Runtime>(shell: &Shell<R>, pid: ChildId) -> Option<ChildStdin> {
+    shell
+        .children
+        .lock()
+        .unwrap()
+        .get(&pid)
+        .map(CommandChild::stdin)
+}
+
+/// Writes on a blocking thread, resolving once the child read enough of its stdin.
+async fn write_stdin(stdin: ChildStdin, buf: Vec<u8>) -> crate::Result<()> {
+    tauri::async_runtime::spawn_blocking(move || stdin.write(&buf)).await?
+}
+
+#[tauri::command]
+pub async fn stdin_write<R: Runtime>(
     _window: Window<R>,
     shell: State<'_, Shell<R>>,
     pid: ChildId,
     buffer: Buffer,
+) -> crate::Result<()> {
+    if let Some(stdin) = child_stdin(&shell, pid) {
+        let buf = match buffer {
+            Buffer::Text(t) => t.into_bytes(),
+            Buffer::Raw(r) => r,
+        };
+        write_stdin(stdin, buf).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stdin_write_chunk<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    request: tauri::ipc::Request<'_>,
+) -> crate::Result<()> {
+    let pid: ChildId = request
+        .headers()
+        .get("pid")
+        .and_then(|pid| pid.to_str().ok())
+        .and_then(|pid| pid.parse().ok())
+        .ok_or(crate::Error::InvalidStdinChunk("missing child pid"))?;
+    let buf = match request.body() {
+        tauri::ipc::InvokeBody::Raw(data) => data.clone(),
+        tauri::ipc::InvokeBody::Json(serde_json::Value::Array(data)) => data
+            .iter()
+            .filter_map(|v| v.as_u64().map(|v| v as u8))
+            .collect(),
+        _ => return Err(crate::Error::InvalidStdinChunk("unexpected invoke body")),
+    };
+
+    if let Some(stdin) = child_stdin(&shell, pid) {
+        write_stdin(stdin, buf).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn close_stdin<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    pid: ChildId,
 ) -> crate::Result<()> {
     if let Some(child) = shell.children.lock().unwrap().get_mut(&pid) {
-        match buffer {
-            Buffer::Text(t) => child.write(t.as_bytes())?,
-            Buffer::Raw(r) => child.write(&r)?,
-        }
+        child.close_stdin();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resize<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    pid: ChildId,
+    cols: u16,
+    rows: u16,
+) -> crate::Result<()> {
+    if let Some(child) = shell.children.lock().unwrap().get(&pid) {
+        child.resize(PtySize { rows, cols })?;
     }
     Ok(())
 }
@@ -303,6 +801,115 @@ pub fn kill<R: Runtime>(
     Ok(())
 }
 
+#[tauri::command]
+pub fn kill_tree<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    pid: ChildId,
+) -> crate::Result<()> {
+    if let Some(child) = shell.children.lock().unwrap().remove(&pid) {
+        child.kill_tree()?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildStatsReturn {
+    cpu_percent: f64,
+    rss_bytes: u64,
+    runtime_ms: u64,
+    processes: usize,
+}
+
+#[tauri::command]
+pub fn stats<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    pid: ChildId,
+    tree: Option<bool>,
+) -> crate::Result<Option<ChildStatsReturn>> {
+    let children = shell.children.lock().unwrap();
+    let Some(child) = children.get(&pid) else {
+        return Ok(None);
+    };
+    let stats = child.stats(tree.unwrap_or_default())?;
+    Ok(Some(ChildStatsReturn {
+        cpu_percent: stats.cpu_percent,
+        rss_bytes: stats.rss_bytes,
+        runtime_ms: stats.runtime.as_millis() as u64,
+        processes: stats.processes,
+    }))
+}
+
+#[tauri::command]
+pub async fn terminate<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    pid: ChildId,
+    signal: Option<Signal>,
+    timeout: Option<u64>,
+) -> crate::Result<()> {
+    let child = shell.children.lock().unwrap().remove(&pid);
+    if let Some(child) = child {
+        let timeout = timeout
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TERMINATE_TIMEOUT);
+        child.terminate(signal.unwrap_or_default(), timeout).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn supervisor_write<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    id: SupervisorId,
+    buffer: Buffer,
+) -> crate::Result<()> {
+    let stdin = shell
+        .supervisors
+        .lock()
+        .unwrap()
+        .get(&id)
+        .and_then(|supervisor| supervisor.stdin());
+    if let Some(stdin) = stdin {
+        let buf = match buffer {
+            Buffer::Text(t) => t.into_bytes(),
+            Buffer::Raw(r) => r,
+        };
+        write_stdin(stdin, buf).await?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn supervisor_pid<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    id: SupervisorId,
+) -> Option<ChildId> {
+    shell
+        .supervisors
+        .lock()
+        .unwrap()
+        .get(&id)
+        .and_then(|supervisor| supervisor.pid())
+}
+
+#[tauri::command]
+pub fn supervisor_stop<R: Runtime>(
+    _window: Window<R>,
+    shell: State<'_, Shell<R>>,
+    id: SupervisorId,
+) -> crate::Result<()> {
+    let supervisor = shell.supervisors.lock().unwrap().remove(&id);
+    if let Some(supervisor) = supervisor {
+        supervisor.stop()?;
+    }
+    Ok(())
+}
+
 #[allow(deprecated)]
 #[tauri::command]
 pub async fn open<R: Runtime>(
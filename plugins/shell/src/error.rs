@@ -12,6 +12,8 @@ pub enum Error {
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
     #[error(transparent)]
+    Tauri(#[from] tauri::Error),
+    #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error("current executable path has no parent")]
     CurrentExeHasNoParent,
@@ -25,6 +27,29 @@ pub enum Error {
     /// Program not allowed by the scope.
     #[error("program not allowed on the configured shell scope: {0}")]
     ProgramNotAllowed(PathBuf),
+    #[cfg(desktop)]
+    #[error("pseudo-terminal error: {0}")]
+    Pty(String),
+    #[error("pseudo-terminals are only supported when spawning a command on desktop")]
+    PtyUnsupported,
+    #[error("the child process is not attached to a pseudo-terminal")]
+    NotPty,
+    #[error("the stdin of the child process was closed")]
+    StdinClosed,
+    #[error("the resource usage of processes is not supported on this platform")]
+    StatsUnsupported,
+    #[error("the supervised process was stopped")]
+    SupervisorStopped,
+    #[error("elevated commands can only be executed on desktop, not spawned")]
+    ElevationUnsupported,
+    #[error("invalid stdin chunk: {0}")]
+    InvalidStdinChunk(&'static str),
+    /// The command ran for longer than its timeout.
+    #[error("command timed out after {}ms", .0.as_millis())]
+    Timeout(std::time::Duration),
+    /// The command wrote more than its maximum output size.
+    #[error("command output exceeded {0} bytes")]
+    OutputLimitExceeded(usize),
     #[error("unknown encoding {0}")]
     UnknownEncoding(String),
     /// JSON error.
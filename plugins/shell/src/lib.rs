@@ -11,15 +11,15 @@
 
 use std::{
     collections::HashMap,
-    ffi::OsStr,
+    ffi::{OsStr, OsString},
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{atomic::AtomicU32, Arc, Mutex},
 };
 
-use process::{Command, CommandChild};
+use process::{Command, CommandChild, Supervisor};
 use regex::Regex;
 use tauri::{
-    plugin::{Builder, TauriPlugin},
+    plugin::{Builder as PluginBuilder, TauriPlugin},
     AppHandle, Manager, RunEvent, Runtime,
 };
 
@@ -34,6 +34,7 @@ mod scope;
 mod scope_entry;
 
 pub use error::Error;
+pub use scope::{DynamicScope, ScopeAllowedArg, ScopeAllowedCommand, ScopeEvent};
 type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(mobile)]
@@ -44,6 +45,7 @@ const PLUGIN_IDENTIFIER: &str = "app.tauri.shell";
 tauri::ios_plugin_binding!(init_plugin_shell);
 
 type ChildStore = Arc<Mutex<HashMap<u32, CommandChild>>>;
+type SupervisorStore = Arc<Mutex<HashMap<u32, Supervisor>>>;
 
 pub struct Shell<R: Runtime> {
     #[allow(dead_code)]
@@ -51,7 +53,11 @@ pub struct Shell<R: Runtime> {
     #[cfg(mobile)]
     mobile_plugin_handle: PluginHandle<R>,
     open_scope: scope::OpenScope,
+    scope: DynamicScope,
     children: ChildStore,
+    supervisors: SupervisorStore,
+    next_supervisor_id: AtomicU32,
+    sidecar_env: HashMap<OsString, OsString>,
 }
 
 impl<R: Runtime> Shell<R> {
@@ -64,8 +70,15 @@ impl<R: Runtime> Shell<R> {
     ///
     /// A sidecar program is a embedded external binary in order to make your application work
     /// or to prevent users having to install additional dependencies (e.g. Node.js, Python, etc).
+    ///
+    /// The environment variables set with [`Builder::sidecar_env`] are set on the command.
     pub fn sidecar(&self, program: impl AsRef<Path>) -> Result<Command> {
-        Command::new_sidecar(program)
+        Command::new_sidecar(program).map(|command| command.envs(&self.sidecar_env))
+    }
+
+    /// The programs allowed to be executed by the webview API at runtime, in addition to the capabilities.
+    pub fn scope(&self) -> &DynamicScope {
+        &self.scope
     }
 
     /// Open a (url) path with a default or specific browser opening program.
@@ -100,48 +113,114 @@ impl<R: Runtime, T: Manager<R>> ShellExt<R> for T {
     }
 }
 
-pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
-    Builder::<R, Option<config::Config>>::new("shell")
-        .js_init_script(include_str!("init-iife.js").to_string())
-        .invoke_handler(tauri::generate_handler![
-            commands::execute,
-            commands::spawn,
-            commands::stdin_write,
-            commands::kill,
-            commands::open
-        ])
-        .setup(|app, api| {
-            let default_config = config::Config::default();
-            let config = api.config().as_ref().unwrap_or(&default_config);
-
-            #[cfg(target_os = "android")]
-            let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "ShellPlugin")?;
-            #[cfg(target_os = "ios")]
-            let handle = api.register_ios_plugin(init_plugin_shell)?;
-
-            app.manage(Shell {
-                app: app.clone(),
-                children: Default::default(),
-                open_scope: open_scope(&config.open),
-
-                #[cfg(mobile)]
-                mobile_plugin_handle: handle,
-            });
-            Ok(())
-        })
-        .on_event(|app, event| {
-            if let RunEvent::Exit = event {
-                let shell = app.state::<Shell<R>>();
-                let children = {
-                    let mut lock = shell.children.lock().unwrap();
-                    std::mem::take(&mut *lock)
-                };
-                for child in children.into_values() {
-                    let _ = child.kill();
+#[derive(Default)]
+pub struct Builder {
+    sidecar_env: HashMap<OsString, OsString>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an environment variable on all the sidecars, spawned from Rust or from the webview,
+    /// before the variables of the command itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// tauri::Builder::default()
+    ///     .plugin(
+    ///         tauri_plugin_shell::Builder::new()
+    ///             .sidecar_env("NODE_ENV", "production")
+    ///             .build(),
+    ///     );
+    /// ```
+    pub fn sidecar_env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.sidecar_env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets multiple environment variables on all the sidecars, see [`Self::sidecar_env`].
+    pub fn sidecar_envs<I, K, V>(mut self, envs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<OsString>,
+        V: Into<OsString>,
+    {
+        self.sidecar_env
+            .extend(envs.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R, Option<config::Config>> {
+        PluginBuilder::<R, Option<config::Config>>::new("shell")
+            .js_init_script(include_str!("init-iife.js").to_string())
+            .invoke_handler(tauri::generate_handler![
+                commands::execute,
+                commands::spawn,
+                commands::stdin_write,
+                commands::stdin_write_chunk,
+                commands::close_stdin,
+                commands::resize,
+                commands::kill,
+                commands::kill_tree,
+                commands::terminate,
+                commands::stats,
+                commands::spawn_supervised,
+                commands::supervisor_write,
+                commands::supervisor_pid,
+                commands::supervisor_stop,
+                commands::open
+            ])
+            .setup(move |app, api| {
+                let default_config = config::Config::default();
+                let config = api.config().as_ref().unwrap_or(&default_config);
+
+                #[cfg(target_os = "android")]
+                let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "ShellPlugin")?;
+                #[cfg(target_os = "ios")]
+                let handle = api.register_ios_plugin(init_plugin_shell)?;
+
+                app.manage(Shell {
+                    app: app.clone(),
+                    children: Default::default(),
+                    supervisors: Default::default(),
+                    next_supervisor_id: Default::default(),
+                    open_scope: open_scope(&config.open),
+                    scope: Default::default(),
+                    sidecar_env: self.sidecar_env,
+
+                    #[cfg(mobile)]
+                    mobile_plugin_handle: handle,
+                });
+                Ok(())
+            })
+            .on_event(|app, event| {
+                if let RunEvent::Exit = event {
+                    let shell = app.state::<Shell<R>>();
+                    let supervisors = {
+                        let mut lock = shell.supervisors.lock().unwrap();
+                        std::mem::take(&mut *lock)
+                    };
+                    for supervisor in supervisors.into_values() {
+                        let _ = supervisor.stop();
+                    }
+                    let children = {
+                        let mut lock = shell.children.lock().unwrap();
+                        std::mem::take(&mut *lock)
+                    };
+                    for child in children.into_values() {
+                        let _ = child.kill();
+                    }
                 }
-            }
-        })
-        .build()
+            })
+            .build()
+    }
+}
+
+pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
+    Builder::new().build()
 }
 
 fn open_scope(open: &config::ShellAllowlistOpen) -> scope::OpenScope {
@@ -0,0 +1,316 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Execution of a command with administrator privileges, asking the user to grant them.
+//!
+//! - Windows: `ShellExecuteEx` with the `runas` verb, the output can't be captured.
+//! - macOS: `do shell script` with administrator privileges through `osascript`.
+//! - Linux and BSDs: `pkexec`.
+
+use std::process::Command as StdCommand;
+
+/// The result of an elevated command.
+pub(super) struct ElevatedOutput {
+    /// Whether the user granted the privileges, the command didn't run otherwise.
+    pub(super) granted: bool,
+    pub(super) code: Option<i32>,
+    pub(super) stdout: Vec<u8>,
+    pub(super) stderr: Vec<u8>,
+}
+
+impl ElevatedOutput {
+    #[cfg_attr(any(target_os = "ios", target_os = "android"), allow(dead_code))]
+    fn denied() -> Self {
+        Self {
+            granted: false,
+            code: None,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{
+        ffi::OsStr,
+        io,
+        iter::{once, repeat},
+        os::windows::ffi::OsStrExt,
+        process::Command as StdCommand,
+    };
+
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, ERROR_CANCELLED},
+        System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE},
+        UI::{
+            Shell::{
+                ShellExecuteExW, SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+            },
+            WindowsAndMessaging::SW_HIDE,
+        },
+    };
+
+    use super::ElevatedOutput;
+
+    fn wide(s: &OsStr) -> Vec<u16> {
+        s.encode_wide().chain(once(0)).collect()
+    }
+
+    /// Quotes the argument following the rules of `CommandLineToArgvW`.
+    fn quote(arg: &str) -> String {
+        if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\u{b}', '"']) {
+            return arg.into();
+        }
+
+        let mut quoted = String::from('"');
+        let mut backslashes = 0;
+        for c in arg.chars() {
+            if c == '\\' {
+                backslashes += 1;
+                continue;
+            }
+            if c == '"' {
+                backslashes = backslashes * 2 + 1;
+            }
+            quoted.extend(repeat('\\').take(backslashes));
+            quoted.push(c);
+            backslashes = 0;
+        }
+        quoted.extend(repeat('\\').take(backslashes * 2));
+        quoted.push('"');
+        quoted
+    }
+
+    pub(super) fn run(command: &StdCommand, _env_cleared: bool) -> crate::Result<ElevatedOutput> {
+        let verb = wide(OsStr::new("runas"));
+        let file = wide(command.get_program());
+        let parameters = command
+            .get_args()
+            .map(|arg| quote(&arg.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parameters = wide(OsStr::new(&parameters));
+        let directory = command.get_current_dir().map(|dir| wide(dir.as_os_str()));
+
+        // SAFETY: the structure is valid zeroed, and the strings outlive the call
+        let mut info: SHELLEXECUTEINFOW = unsafe { std::mem::zeroed() };
+        info.cbSize = std::mem::size_of::<SHELLEXECUTEINFOW>() as u32;
+        info.fMask = SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC;
+        info.lpVerb = verb.as_ptr();
+        info.lpFile = file.as_ptr();
+        info.lpParameters = parameters.as_ptr();
+        info.lpDirectory = directory
+            .as_ref()
+            .map_or(std::ptr::null(), |dir| dir.as_ptr());
+        info.nShow = SW_HIDE;
+
+        if unsafe { ShellExecuteExW(&mut info) } == 0 {
+            let error = io::Error::last_os_error();
+            // the user declined the UAC prompt
+            if error.raw_os_error() == Some(ERROR_CANCELLED as i32) {
+                return Ok(ElevatedOutput::denied());
+            }
+            return Err(error.into());
+        }
+
+        let mut code = None;
+        // no process is returned if an existing one handled the request
+        if !info.hProcess.is_null() {
+            let mut exit_code = 0;
+            unsafe {
+                WaitForSingleObject(info.hProcess, INFINITE);
+                if GetExitCodeProcess(info.hProcess, &mut exit_code) != 0 {
+                    code.replace(exit_code as i32);
+                }
+                CloseHandle(info.hProcess);
+            }
+        }
+
+        Ok(ElevatedOutput {
+            granted: true,
+            code,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{
+        ffi::OsStr,
+        fs::{read, read_to_string, remove_dir_all, DirBuilder},
+        io,
+        os::unix::fs::DirBuilderExt,
+        path::Path,
+        process::{Command as StdCommand, Stdio},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::ElevatedOutput;
+
+    /// Error number of `osascript` when the user cancels the authentication dialog.
+    const USER_CANCELED: &str = "(-128)";
+
+    static RUN_ID: AtomicUsize = AtomicUsize::new(0);
+
+    /// Quotes the string for `sh`.
+    fn quote(s: impl AsRef<OsStr>) -> String {
+        format!("'{}'", s.as_ref().to_string_lossy().replace('\'', r"'\''"))
+    }
+
+    fn shell_script(command: &StdCommand, env_cleared: bool, dir: &Path) -> String {
+        let mut script = String::new();
+        if let Some(cwd) = command.get_current_dir() {
+            script.push_str(&format!("cd {} && ", quote(cwd)));
+        }
+        script.push_str("/usr/bin/env");
+        if env_cleared {
+            script.push_str(" -i");
+        }
+        for (key, value) in command.get_envs() {
+            if value.is_none() {
+                script.push_str(&format!(" -u {}", quote(key)));
+            }
+        }
+        for (key, value) in command.get_envs() {
+            if let Some(value) = value {
+                let mut assignment = key.to_os_string();
+                assignment.push("=");
+                assignment.push(value);
+                script.push_str(&format!(" {}", quote(assignment)));
+            }
+        }
+        script.push_str(&format!(" {}", quote(command.get_program())));
+        for arg in command.get_args() {
+            script.push_str(&format!(" {}", quote(arg)));
+        }
+        // only stdout is returned by `do shell script`, and not on failures
+        script.push_str(&format!(
+            " < /dev/null > {} 2> {}; echo $? > {}",
+            quote(dir.join("stdout")),
+            quote(dir.join("stderr")),
+            quote(dir.join("status")),
+        ));
+        script
+    }
+
+    pub(super) fn run(command: &StdCommand, env_cleared: bool) -> crate::Result<ElevatedOutput> {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-shell-elevated-{}-{}",
+            std::process::id(),
+            RUN_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        DirBuilder::new().mode(0o700).create(&dir)?;
+
+        let result = run_in(command, env_cleared, &dir);
+        let _ = remove_dir_all(&dir);
+        result
+    }
+
+    fn run_in(
+        command: &StdCommand,
+        env_cleared: bool,
+        dir: &Path,
+    ) -> crate::Result<ElevatedOutput> {
+        let script = shell_script(command, env_cleared, dir)
+            .replace('\\', r"\\")
+            .replace('"', "\\\"");
+        let output = StdCommand::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "do shell script \"{script}\" with administrator privileges"
+            ))
+            .stdin(Stdio::null())
+            .output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            if error.contains(USER_CANCELED) {
+                return Ok(ElevatedOutput::denied());
+            }
+            return Err(io::Error::other(error.trim().to_string()).into());
+        }
+
+        Ok(ElevatedOutput {
+            granted: true,
+            code: read_to_string(dir.join("status"))?.trim().parse().ok(),
+            stdout: read(dir.join("stdout"))?,
+            stderr: read(dir.join("stderr"))?,
+        })
+    }
+}
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+mod platform {
+    use std::process::{Command as StdCommand, Stdio};
+
+    use super::ElevatedOutput;
+
+    /// Exit code of `pkexec` when the user dismissed the authentication dialog.
+    const DISMISSED: i32 = 126;
+    /// Exit code of `pkexec` when the user isn't authorized or failed to authenticate.
+    const NOT_AUTHORIZED: i32 = 127;
+
+    pub(super) fn run(command: &StdCommand, env_cleared: bool) -> crate::Result<ElevatedOutput> {
+        // `pkexec` resets the environment and the working directory, `env` sets them back
+        let mut pkexec = StdCommand::new("pkexec");
+        pkexec.arg("/usr/bin/env");
+        if env_cleared {
+            pkexec.arg("-i");
+        }
+        if let Some(cwd) = command.get_current_dir() {
+            pkexec.arg("-C").arg(cwd);
+        }
+        for (key, value) in command.get_envs() {
+            if value.is_none() {
+                pkexec.arg("-u").arg(key);
+            }
+        }
+        for (key, value) in command.get_envs() {
+            if let Some(value) = value {
+                let mut assignment = key.to_os_string();
+                assignment.push("=");
+                assignment.push(value);
+                pkexec.arg(assignment);
+            }
+        }
+        pkexec.arg(command.get_program()).args(command.get_args());
+
+        let output = pkexec.stdin(Stdio::null()).output()?;
+        if matches!(output.status.code(), Some(DISMISSED | NOT_AUTHORIZED)) {
+            return Ok(ElevatedOutput {
+                stderr: output.stderr,
+                ..ElevatedOutput::denied()
+            });
+        }
+
+        Ok(ElevatedOutput {
+            granted: true,
+            code: output.status.code(),
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+mod platform {
+    use std::process::Command as StdCommand;
+
+    use super::ElevatedOutput;
+
+    pub(super) fn run(_command: &StdCommand, _env_cleared: bool) -> crate::Result<ElevatedOutput> {
+        Err(crate::Error::ElevationUnsupported)
+    }
+}
+
+/// Runs the command with administrator privileges, blocking until it exits.
+pub(super) fn run(command: &StdCommand, env_cleared: bool) -> crate::Result<ElevatedOutput> {
+    platform::run(command, env_cleared)
+}
@@ -0,0 +1,258 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The process group (Unix) or job object (Windows) containing a child and its descendants.
+
+use std::io;
+
+use serde::Deserialize;
+
+#[cfg(windows)]
+use windows_sys::Win32::{
+    Foundation::{CloseHandle, HANDLE},
+    System::{
+        Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT},
+        JobObjects::{
+            AssignProcessToJobObject, CreateJobObjectW, JobObjectBasicProcessIdList,
+            QueryInformationJobObject, TerminateJobObject,
+        },
+        Threading::{OpenProcess, PROCESS_SET_QUOTA, PROCESS_TERMINATE},
+    },
+};
+
+/// Signal asking a process to exit, see [`super::CommandChild::terminate`].
+///
+/// On Windows, a `CTRL_BREAK` event is sent instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Signal {
+    #[default]
+    #[serde(rename = "SIGTERM")]
+    Term,
+    #[serde(rename = "SIGINT")]
+    Int,
+    #[serde(rename = "SIGHUP")]
+    Hup,
+    #[serde(rename = "SIGQUIT")]
+    Quit,
+}
+
+#[cfg(unix)]
+impl Signal {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Self::Term => libc::SIGTERM,
+            Self::Int => libc::SIGINT,
+            Self::Hup => libc::SIGHUP,
+            Self::Quit => libc::SIGQUIT,
+        }
+    }
+}
+
+/// Job object handle, closed on drop.
+#[cfg(windows)]
+#[derive(Debug)]
+struct Job(HANDLE);
+
+// SAFETY: job object handles can be used from any thread
+#[cfg(windows)]
+unsafe impl Send for Job {}
+#[cfg(windows)]
+unsafe impl Sync for Job {}
+
+#[cfg(windows)]
+impl Drop for Job {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+/// Maximum number of processes listed by [`ProcessGroup::pids`] on Windows.
+#[cfg(windows)]
+const MAX_JOB_PROCESSES: usize = 1024;
+
+/// `JOBOBJECT_BASIC_PROCESS_ID_LIST` with room for [`MAX_JOB_PROCESSES`] ids.
+#[cfg(windows)]
+#[repr(C)]
+struct JobProcessIdList {
+    assigned: u32,
+    listed: u32,
+    ids: [usize; MAX_JOB_PROCESSES],
+}
+
+#[derive(Debug)]
+pub(crate) struct ProcessGroup {
+    /// The child is the leader of its process group, spawned with `process_group(0)` or `setsid`.
+    #[cfg(unix)]
+    pgid: u32,
+    #[cfg(windows)]
+    job: Job,
+    /// The child is the root of its console process group, spawned with `CREATE_NEW_PROCESS_GROUP`.
+    #[cfg(windows)]
+    pid: u32,
+}
+
+impl ProcessGroup {
+    /// The group of the child with the given pid, the descendants it spawns join it.
+    pub(crate) fn new(pid: u32) -> io::Result<Self> {
+        #[cfg(unix)]
+        {
+            Ok(Self { pgid: pid })
+        }
+
+        #[cfg(windows)]
+        unsafe {
+            let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+            if job.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let job = Job(job);
+
+            let process = OpenProcess(PROCESS_SET_QUOTA | PROCESS_TERMINATE, 0, pid);
+            if process.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let assigned = AssignProcessToJobObject(job.0, process);
+            CloseHandle(process);
+            if assigned == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self { job, pid })
+        }
+    }
+
+    #[cfg(unix)]
+    fn kill_group(&self, signal: libc::c_int) -> io::Result<()> {
+        if unsafe { libc::killpg(self.pgid as libc::pid_t, signal) } == 0 {
+            return Ok(());
+        }
+        let error = io::Error::last_os_error();
+        // every process of the group already exited
+        if error.raw_os_error() == Some(libc::ESRCH) {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Sends the signal to every process of the group.
+    pub(crate) fn signal(&self, signal: Signal) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            self.kill_group(signal.as_raw())
+        }
+
+        #[cfg(windows)]
+        {
+            let _ = signal;
+            if unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, self.pid) } == 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Lists the pids of the processes in the group, starting with the child.
+    pub(crate) fn pids(&self) -> io::Result<Vec<u32>> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            /// Index of the process group id in [`super::stats::proc_stat`].
+            const PGRP: usize = 2;
+
+            let mut pids = vec![self.pgid];
+            for entry in std::fs::read_dir("/proc")? {
+                let Some(pid) = entry?
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.parse::<u32>().ok())
+                else {
+                    continue;
+                };
+                // the process may have exited since the directory was read
+                let Ok(stat) = super::stats::proc_stat(pid) else {
+                    continue;
+                };
+                if pid != self.pgid && stat.get(PGRP) == Some(&self.pgid.to_string()) {
+                    pids.push(pid);
+                }
+            }
+            Ok(pids)
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let mut pids = vec![0 as libc::pid_t; 1024];
+            let count = unsafe {
+                libc::proc_listpgrppids(
+                    self.pgid as libc::pid_t,
+                    pids.as_mut_ptr() as *mut libc::c_void,
+                    (pids.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int,
+                )
+            };
+            if count < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            pids.truncate(count as usize);
+
+            let mut group = vec![self.pgid];
+            group.extend(
+                pids.into_iter()
+                    .map(|pid| pid as u32)
+                    .filter(|pid| *pid != self.pgid),
+            );
+            Ok(group)
+        }
+
+        // the descendants can't be listed, only the child is
+        #[cfg(all(
+            unix,
+            not(any(target_os = "linux", target_os = "android", target_os = "macos"))
+        ))]
+        {
+            Ok(vec![self.pgid])
+        }
+
+        #[cfg(windows)]
+        unsafe {
+            let mut list: JobProcessIdList = std::mem::zeroed();
+            if QueryInformationJobObject(
+                self.job.0,
+                JobObjectBasicProcessIdList,
+                &mut list as *mut _ as *mut std::ffi::c_void,
+                std::mem::size_of::<JobProcessIdList>() as u32,
+                std::ptr::null_mut(),
+            ) == 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut pids = vec![self.pid];
+            pids.extend(
+                list.ids[..(list.listed as usize).min(MAX_JOB_PROCESSES)]
+                    .iter()
+                    .map(|pid| *pid as u32)
+                    .filter(|pid| *pid != self.pid),
+            );
+            Ok(pids)
+        }
+    }
+
+    /// Kills every process of the group.
+    pub(crate) fn kill(&self) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            self.kill_group(libc::SIGKILL)
+        }
+
+        #[cfg(windows)]
+        {
+            if unsafe { TerminateJobObject(self.job.0, 1) } == 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
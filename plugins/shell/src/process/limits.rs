@@ -0,0 +1,137 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Execution time and output size limits of a child process.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tauri::async_runtime::{channel, spawn, Receiver};
+use tokio::time::{timeout_at, Instant};
+
+use super::CommandEvent;
+
+/// Kills the child and its descendants.
+pub(crate) type Kill = Box<dyn FnMut() + Send>;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Limits {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) max_output_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Limit {
+    Timeout(Duration),
+    OutputSize(usize),
+}
+
+impl From<Limit> for crate::Error {
+    fn from(limit: Limit) -> Self {
+        match limit {
+            Limit::Timeout(timeout) => Self::Timeout(timeout),
+            Limit::OutputSize(max) => Self::OutputLimitExceeded(max),
+        }
+    }
+}
+
+/// The limit a child exceeded, shared with the task enforcing them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExceededLimit(Arc<Mutex<Option<Limit>>>);
+
+impl ExceededLimit {
+    /// The error of the exceeded limit, if any.
+    pub(crate) fn error(&self) -> Option<crate::Error> {
+        self.0.lock().unwrap().map(Into::into)
+    }
+
+    fn is_set(&self) -> bool {
+        self.0.lock().unwrap().is_some()
+    }
+
+    /// Records the limit, returns `false` if one was already exceeded.
+    fn set(&self, limit: Limit) -> bool {
+        let mut exceeded = self.0.lock().unwrap();
+        if exceeded.is_some() {
+            return false;
+        }
+        exceeded.replace(limit);
+        true
+    }
+}
+
+impl Limits {
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.max_output_bytes.is_none()
+    }
+
+    /// Forwards the events of the child, killing it once a limit is exceeded.
+    ///
+    /// The output read after that is dropped and the error is sent right before [`CommandEvent::Terminated`].
+    pub(crate) fn enforce(
+        self,
+        mut rx: Receiver<CommandEvent>,
+        mut kill: Kill,
+        exceeded: ExceededLimit,
+    ) -> Receiver<CommandEvent> {
+        let (tx, limited_rx) = channel(1);
+
+        spawn(async move {
+            let deadline = self
+                .timeout
+                .map(|timeout| (timeout, Instant::now() + timeout));
+            let mut output_bytes = 0usize;
+
+            loop {
+                let event = match deadline {
+                    Some((timeout, deadline)) if !exceeded.is_set() => {
+                        match timeout_at(deadline, rx.recv()).await {
+                            Ok(event) => event,
+                            Err(_) => {
+                                if exceeded.set(Limit::Timeout(timeout)) {
+                                    kill();
+                                }
+                                continue;
+                            }
+                        }
+                    }
+                    _ => rx.recv().await,
+                };
+                let Some(event) = event else {
+                    break;
+                };
+
+                match &event {
+                    CommandEvent::Stdout(bytes) | CommandEvent::Stderr(bytes) => {
+                        if exceeded.is_set() {
+                            continue;
+                        }
+                        output_bytes += bytes.len();
+                        if let Some(max) = self.max_output_bytes {
+                            if output_bytes > max {
+                                if exceeded.set(Limit::OutputSize(max)) {
+                                    kill();
+                                }
+                                continue;
+                            }
+                        }
+                    }
+                    CommandEvent::Terminated(_) => {
+                        if let Some(error) = exceeded.error() {
+                            let _ = tx.send(CommandEvent::Error(error.to_string())).await;
+                        }
+                    }
+                    CommandEvent::Error(_) => {}
+                }
+
+                // keeps reading so the child isn't blocked if the receiver was dropped
+                let _ = tx.send(event).await;
+            }
+        });
+
+        limited_rx
+    }
+}
@@ -4,30 +4,54 @@
 
 use std::{
     ffi::OsStr,
-    io::{BufRead, BufReader, Write},
+    io::{BufRead, BufReader},
     path::{Path, PathBuf},
     process::{Command as StdCommand, Stdio},
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
     thread::spawn,
+    time::{Duration, Instant},
 };
 
 #[cfg(unix)]
-use std::os::unix::process::ExitStatusExt;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+#[cfg(windows)]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
 const NEWLINE_BYTE: u8 = b'\n';
+/// Interval between two checks of the exit of a child being terminated.
+const TERMINATE_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 use tauri::async_runtime::{block_on as block_on_task, channel, Receiver, Sender};
 
 pub use encoding_rs::Encoding;
-use os_pipe::{pipe, PipeReader, PipeWriter};
-use serde::Serialize;
+use os_pipe::{pipe, PipeReader};
+use serde::{Deserialize, Serialize};
 use shared_child::SharedChild;
 use tauri::utils::platform;
 
+mod elevated;
+mod group;
+mod limits;
+#[cfg(desktop)]
+mod pty;
+mod stats;
+mod stdin;
+mod supervisor;
+
+use group::ProcessGroup;
+pub use group::Signal;
+use limits::{ExceededLimit, Kill, Limits};
+pub use stats::ProcessStats;
+pub use stdin::ChildStdin;
+pub use supervisor::{Backoff, Supervisor, SupervisorEvent, SupervisorOptions};
+
 /// Payload for the [`CommandEvent::Terminated`] command event.
 #[derive(Debug, Clone, Serialize)]
 pub struct TerminatedPayload {
@@ -53,36 +77,185 @@ pub enum CommandEvent {
     Terminated(TerminatedPayload),
 }
 
+/// Size of a pseudo-terminal, in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct PtySize {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+impl Default for PtySize {
+    fn default() -> Self {
+        Self { rows: 24, cols: 80 }
+    }
+}
+
 /// The type to spawn commands.
 #[derive(Debug)]
 pub struct Command {
     cmd: StdCommand,
     raw_out: bool,
+    /// Whether [`Command::env_clear`] was called, it can't be read from the [`StdCommand`].
+    env_cleared: bool,
+    pty: Option<PtySize>,
+    limits: Limits,
+    elevated: bool,
 }
 
 /// Spawned child process.
 #[derive(Debug)]
 pub struct CommandChild {
-    inner: Arc<SharedChild>,
-    stdin_writer: PipeWriter,
+    inner: ChildInner,
+    stdin: ChildStdin,
+    /// `None` if the child couldn't be assigned to a job object on Windows.
+    group: Option<Arc<ProcessGroup>>,
+    /// Set once the child exited.
+    exited: Arc<AtomicBool>,
+    exceeded: ExceededLimit,
+    stats: stats::Sampler,
+}
+
+#[derive(Debug)]
+enum ChildInner {
+    Pipe {
+        child: Arc<SharedChild>,
+    },
+    #[cfg(desktop)]
+    Pty(pty::PtyChild),
 }
 
 impl CommandChild {
     /// Writes to process stdin.
     pub fn write(&mut self, buf: &[u8]) -> crate::Result<()> {
-        self.stdin_writer.write_all(buf)?;
-        Ok(())
+        self.stdin.write(buf)
+    }
+
+    /// Closes the process stdin so it reads the end of file, see [`ChildStdin::close`].
+    pub fn close_stdin(&mut self) {
+        self.stdin.close();
+    }
+
+    /// Returns a handle to the process stdin, to write to it without borrowing the child.
+    pub fn stdin(&self) -> ChildStdin {
+        self.stdin.clone()
     }
 
     /// Sends a kill signal to the child.
     pub fn kill(self) -> crate::Result<()> {
-        self.inner.kill()?;
+        match self.inner {
+            ChildInner::Pipe { child } => child.kill()?,
+            #[cfg(desktop)]
+            ChildInner::Pty(mut pty) => pty.kill()?,
+        }
+        Ok(())
+    }
+
+    /// Kills the child and all its descendants, which are in its process group on Unix
+    /// and in its job object on Windows.
+    ///
+    /// Descendants that moved to another process group or session on Unix are not killed.
+    pub fn kill_tree(self) -> crate::Result<()> {
+        match &self.group {
+            Some(group) => group.kill()?,
+            None => return self.kill(),
+        }
+        Ok(())
+    }
+
+    /// Asks the child and its descendants to exit with `signal`, a `CTRL_BREAK` event on Windows,
+    /// then kills all of them once the child exited or after `timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use tauri_plugin_shell::{process::Signal, ShellExt};
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     let (_rx, child) = app.shell().command("npm").args(["run", "dev"]).spawn()?;
+    ///     tauri::async_runtime::block_on(child.terminate(Signal::Term, Duration::from_secs(5)))?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub async fn terminate(self, signal: Signal, timeout: Duration) -> crate::Result<()> {
+        let Some(group) = &self.group else {
+            return self.kill();
+        };
+
+        // the child is killed right away if it can't be signaled
+        if group.signal(signal).is_ok() {
+            let deadline = Instant::now() + timeout;
+            while !self.exited.load(Ordering::Relaxed) && Instant::now() < deadline {
+                tokio::time::sleep(TERMINATE_POLL_INTERVAL).await;
+            }
+        }
+
+        group.kill()?;
         Ok(())
     }
 
     /// Returns the process pid.
     pub fn pid(&self) -> u32 {
-        self.inner.id()
+        match &self.inner {
+            ChildInner::Pipe { child } => child.id(),
+            #[cfg(desktop)]
+            ChildInner::Pty(pty) => pty.pid(),
+        }
+    }
+
+    /// Returns the CPU usage, memory and runtime of the child, summed with its descendants if `tree` is set.
+    ///
+    /// The descendants are the processes of its process group on Unix and of its job object on Windows,
+    /// only the child is included on other Unix systems than Linux and macOS.
+    /// The CPU usage is measured since the previous call with the same `tree`, so it should be polled at an interval.
+    pub fn stats(&self, tree: bool) -> crate::Result<ProcessStats> {
+        let pid = self.pid();
+        let pids = match &self.group {
+            Some(group) if tree => group.pids()?,
+            _ => vec![pid],
+        };
+        self.stats.sample(&pids, tree)
+    }
+
+    /// The limit set with [`Command::timeout`] or [`Command::max_output_bytes`] the child exceeded.
+    pub(crate) fn exceeded_limit(&self) -> ExceededLimit {
+        self.exceeded.clone()
+    }
+
+    fn killer(&self) -> Kill {
+        if let Some(group) = &self.group {
+            let group = group.clone();
+            return Box::new(move || {
+                let _ = group.kill();
+            });
+        }
+        match &self.inner {
+            ChildInner::Pipe { child } => {
+                let child = child.clone();
+                Box::new(move || {
+                    let _ = child.kill();
+                })
+            }
+            #[cfg(desktop)]
+            ChildInner::Pty(pty) => {
+                let mut killer = pty.clone_killer();
+                Box::new(move || {
+                    let _ = killer.kill();
+                })
+            }
+        }
+    }
+
+    /// Resizes the pseudo-terminal of a child spawned with [`Command::pty`].
+    pub fn resize(&self, size: PtySize) -> crate::Result<()> {
+        match &self.inner {
+            #[cfg(desktop)]
+            ChildInner::Pty(pty) => pty.resize(size),
+            _ => {
+                let _ = size;
+                Err(crate::Error::NotPty)
+            }
+        }
     }
 }
 
@@ -90,6 +263,7 @@ impl CommandChild {
 #[derive(Debug)]
 pub struct ExitStatus {
     code: Option<i32>,
+    elevation_granted: Option<bool>,
 }
 
 impl ExitStatus {
@@ -98,6 +272,12 @@ impl ExitStatus {
         self.code
     }
 
+    /// Returns whether the user granted the privileges to a command executed with [`Command::elevated`],
+    /// `None` if it wasn't elevated.
+    pub fn elevation_granted(&self) -> Option<bool> {
+        self.elevation_granted
+    }
+
     /// Returns true if exit status is zero. Signal termination is not considered a success, and success is defined as a zero exit status.
     pub fn success(&self) -> bool {
         self.code == Some(0)
@@ -115,6 +295,14 @@ pub struct Output {
     pub stderr: Vec<u8>,
 }
 
+/// Environment variable names are case-insensitive on Windows.
+fn is_path_key(key: &OsStr) -> bool {
+    #[cfg(windows)]
+    return key.eq_ignore_ascii_case("PATH");
+    #[cfg(not(windows))]
+    return key == "PATH";
+}
+
 fn relative_command_path(command: &Path) -> crate::Result<PathBuf> {
     match platform::current_exe()?.parent() {
         #[cfg(windows)]
@@ -138,12 +326,19 @@ impl Command {
         command.stdout(Stdio::piped());
         command.stdin(Stdio::piped());
         command.stderr(Stdio::piped());
+        // the child leads a new process group so it can be killed with its descendants
+        #[cfg(unix)]
+        command.process_group(0);
         #[cfg(windows)]
-        command.creation_flags(CREATE_NO_WINDOW);
+        command.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
 
         Self {
             cmd: command,
             raw_out: false,
+            env_cleared: false,
+            pty: None,
+            limits: Limits::default(),
+            elevated: false,
         }
     }
 
@@ -173,6 +368,7 @@ impl Command {
     #[must_use]
     pub fn env_clear(mut self) -> Self {
         self.cmd.env_clear();
+        self.env_cleared = true;
         self
     }
 
@@ -199,6 +395,38 @@ impl Command {
         self
     }
 
+    /// Removes an explicitly set or inherited environment variable.
+    #[must_use]
+    pub fn env_remove<K: AsRef<OsStr>>(mut self, key: K) -> Self {
+        self.cmd.env_remove(key);
+        self
+    }
+
+    /// Prepends the paths to the `PATH` environment variable as set when it's called,
+    /// or the inherited one if it wasn't set and the environment wasn't cleared.
+    ///
+    /// Fails if a path contains the `PATH` separator.
+    pub fn prepend_path<I, P>(mut self, paths: I) -> crate::Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let current = match self.cmd.get_envs().find(|(key, _)| is_path_key(key)) {
+            Some((_, value)) => value.map(OsStr::to_os_string),
+            None if self.env_cleared => None,
+            None => std::env::var_os("PATH"),
+        };
+        let path = std::env::join_paths(
+            paths
+                .into_iter()
+                .map(|path| path.as_ref().to_path_buf())
+                .chain(current.iter().flat_map(std::env::split_paths)),
+        )
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.cmd.env("PATH", path);
+        Ok(self)
+    }
+
     /// Sets the working directory for the child process.
     #[must_use]
     pub fn current_dir<P: AsRef<Path>>(mut self, current_dir: P) -> Self {
@@ -212,6 +440,80 @@ impl Command {
         self
     }
 
+    /// Attaches the child process to a pseudo-terminal of the given size, so interactive programs
+    /// keep their colors and don't buffer their output. Only supported on desktop.
+    ///
+    /// The output is sent as [`CommandEvent::Stdout`] chunks as soon as it is read, stderr included.
+    /// Unless configured for raw output, a chunk never ends with an incomplete UTF-8 character.
+    #[must_use]
+    pub fn pty(mut self, size: PtySize) -> Self {
+        self.pty.replace(size);
+        self
+    }
+
+    /// Kills the child process and its descendants once it ran for longer than `timeout`.
+    ///
+    /// [`Command::output`] and [`Command::status`] then fail with [`crate::Error::Timeout`],
+    /// a spawned child sends it as a [`CommandEvent::Error`] right before [`CommandEvent::Terminated`].
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.limits.timeout.replace(timeout);
+        self
+    }
+
+    /// Kills the child process and its descendants once it wrote more than `max` bytes to stdout and stderr combined,
+    /// the output past the limit is dropped.
+    ///
+    /// [`Command::output`] and [`Command::status`] then fail with [`crate::Error::OutputLimitExceeded`],
+    /// a spawned child sends it as a [`CommandEvent::Error`] right before [`CommandEvent::Terminated`].
+    #[must_use]
+    pub fn max_output_bytes(mut self, max: usize) -> Self {
+        self.limits.max_output_bytes.replace(max);
+        self
+    }
+
+    /// Asks the user for administrator privileges and runs the command with them, with `runas` on Windows,
+    /// an `osascript` authentication dialog on macOS and `pkexec` on Linux.
+    ///
+    /// Elevated commands can only be run with [`Command::output`] and [`Command::status`] on desktop,
+    /// whose [`ExitStatus::elevation_granted`] tells whether the user granted the privileges.
+    /// Their stdin is closed, and the limits are not enforced.
+    /// On Windows, the output isn't captured and the environment variables are not set.
+    #[must_use]
+    pub fn elevated(mut self, elevated: bool) -> Self {
+        self.elevated = elevated;
+        self
+    }
+
+    pub(crate) fn is_elevated(&self) -> bool {
+        self.elevated
+    }
+
+    /// Builds a new command with the same program, arguments, environment and options,
+    /// the [`StdCommand`] can't be cloned.
+    fn duplicate(&self) -> Self {
+        let mut command = Self::new(self.cmd.get_program());
+        command.cmd.args(self.cmd.get_args());
+        if self.env_cleared {
+            command.cmd.env_clear();
+        }
+        for (key, value) in self.cmd.get_envs() {
+            match value {
+                Some(value) => command.cmd.env(key, value),
+                None => command.cmd.env_remove(key),
+            };
+        }
+        if let Some(cwd) = self.cmd.get_current_dir() {
+            command.cmd.current_dir(cwd);
+        }
+        command.raw_out = self.raw_out;
+        command.env_cleared = self.env_cleared;
+        command.pty = self.pty;
+        command.limits = self.limits;
+        command.elevated = self.elevated;
+        command
+    }
+
     /// Spawns the command.
     ///
     /// # Examples
@@ -243,6 +545,27 @@ impl Command {
     /// });
     /// ```
     pub fn spawn(self) -> crate::Result<(Receiver<CommandEvent>, CommandChild)> {
+        if self.elevated {
+            return Err(crate::Error::ElevationUnsupported);
+        }
+
+        let limits = self.limits;
+        let (rx, child) = match self.pty {
+            #[cfg(desktop)]
+            Some(size) => pty::spawn(self, size)?,
+            #[cfg(not(desktop))]
+            Some(_) => return Err(crate::Error::PtyUnsupported),
+            None => self.spawn_piped()?,
+        };
+
+        if limits.is_unlimited() {
+            return Ok((rx, child));
+        }
+        let rx = limits.enforce(rx, child.killer(), child.exceeded_limit());
+        Ok((rx, child))
+    }
+
+    fn spawn_piped(self) -> crate::Result<(Receiver<CommandEvent>, CommandChild)> {
         let raw = self.raw_out;
         let mut command: StdCommand = self.into();
         let (stdout_reader, stdout_writer) = pipe()?;
@@ -255,6 +578,9 @@ impl Command {
         let shared_child = SharedChild::spawn(&mut command)?;
         let child = Arc::new(shared_child);
         let child_ = child.clone();
+        let group = ProcessGroup::new(child.id()).ok().map(Arc::new);
+        let exited = Arc::new(AtomicBool::new(false));
+        let exited_ = exited.clone();
         let guard = Arc::new(RwLock::new(()));
 
         let (tx, rx) = channel(1);
@@ -275,7 +601,9 @@ impl Command {
         );
 
         spawn(move || {
-            let _ = match child_.wait() {
+            let status = child_.wait();
+            exited_.store(true, Ordering::Relaxed);
+            let _ = match status {
                 Ok(status) => {
                     let _l = guard.write().unwrap();
                     block_on_task(async move {
@@ -299,8 +627,12 @@ impl Command {
         Ok((
             rx,
             CommandChild {
-                inner: child,
-                stdin_writer,
+                inner: ChildInner::Pipe { child },
+                stdin: ChildStdin::new(stdin_writer),
+                group,
+                exited,
+                exceeded: Default::default(),
+                stats: Default::default(),
             },
         ))
     }
@@ -319,7 +651,11 @@ impl Command {
     ///   });
     /// ```
     pub async fn status(self) -> crate::Result<ExitStatus> {
-        let (mut rx, _child) = self.spawn()?;
+        if self.elevated {
+            return self.output_elevated().await.map(|output| output.status);
+        }
+
+        let (mut rx, child) = self.spawn()?;
         let mut code = None;
         #[allow(clippy::collapsible_match)]
         while let Some(event) = rx.recv().await {
@@ -327,7 +663,13 @@ impl Command {
                 code = payload.code;
             }
         }
-        Ok(ExitStatus { code })
+        if let Some(error) = child.exceeded.error() {
+            return Err(error);
+        }
+        Ok(ExitStatus {
+            code,
+            elevation_granted: None,
+        })
     }
 
     /// Executes the command as a child process, waiting for it to finish and collecting all of its output.
@@ -346,7 +688,11 @@ impl Command {
     ///   });
     /// ```
     pub async fn output(self) -> crate::Result<Output> {
-        let (mut rx, _child) = self.spawn()?;
+        if self.elevated {
+            return self.output_elevated().await;
+        }
+
+        let (mut rx, child) = self.spawn()?;
 
         let mut code = None;
         let mut stdout = Vec::new();
@@ -368,12 +714,34 @@ impl Command {
                 CommandEvent::Error(_) => {}
             }
         }
+        if let Some(error) = child.exceeded.error() {
+            return Err(error);
+        }
         Ok(Output {
-            status: ExitStatus { code },
+            status: ExitStatus {
+                code,
+                elevation_granted: None,
+            },
             stdout,
             stderr,
         })
     }
+
+    async fn output_elevated(self) -> crate::Result<Output> {
+        let env_cleared = self.env_cleared;
+        let command = self.cmd;
+        let output =
+            tauri::async_runtime::spawn_blocking(move || elevated::run(&command, env_cleared))
+                .await??;
+        Ok(Output {
+            status: ExitStatus {
+                code: output.code,
+                elevation_granted: Some(output.granted),
+            },
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
 }
 
 fn read_raw_bytes<F: Fn(Vec<u8>) -> CommandEvent + Send + Copy + 'static>(
@@ -0,0 +1,214 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Child processes attached to a pseudo-terminal.
+
+use std::{
+    io::{ErrorKind, Read},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    thread::spawn as spawn_thread,
+};
+
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty};
+use tauri::async_runtime::{block_on as block_on_task, channel, Receiver, Sender};
+
+use super::{
+    ChildInner, ChildStdin, Command, CommandChild, CommandEvent, ProcessGroup, PtySize,
+    TerminatedPayload,
+};
+
+/// Size of the buffer the output is read into.
+const READ_BUFFER_SIZE: usize = 4096;
+
+fn pty_error(error: impl std::fmt::Display) -> crate::Error {
+    crate::Error::Pty(error.to_string())
+}
+
+impl From<PtySize> for portable_pty::PtySize {
+    fn from(size: PtySize) -> Self {
+        Self {
+            rows: size.rows,
+            cols: size.cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        }
+    }
+}
+
+/// A child process attached to a pseudo-terminal.
+pub(crate) struct PtyChild {
+    master: Box<dyn MasterPty + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    pid: u32,
+}
+
+impl std::fmt::Debug for PtyChild {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PtyChild")
+            .field("pid", &self.pid)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PtyChild {
+    pub(crate) fn kill(&mut self) -> crate::Result<()> {
+        self.killer.kill()?;
+        Ok(())
+    }
+
+    pub(crate) fn clone_killer(&self) -> Box<dyn ChildKiller + Send + Sync> {
+        self.killer.clone_killer()
+    }
+
+    pub(crate) fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub(crate) fn resize(&self, size: PtySize) -> crate::Result<()> {
+        self.master.resize(size.into()).map_err(pty_error)
+    }
+}
+
+/// Converts the command, the environment is inherited unless it was cleared.
+fn command_builder(command: &Command) -> CommandBuilder {
+    let cmd = &command.cmd;
+    let mut builder = CommandBuilder::new(cmd.get_program());
+    builder.args(cmd.get_args());
+    if command.env_cleared {
+        builder.env_clear();
+    }
+    for (key, value) in cmd.get_envs() {
+        match value {
+            Some(value) => builder.env(key, value),
+            None => builder.env_remove(key),
+        }
+    }
+    // lets the programs know they can use colors and cursor movements
+    if builder.get_env("TERM").is_none() {
+        builder.env("TERM", "xterm-256color");
+    }
+    if let Some(cwd) = cmd.get_current_dir() {
+        builder.cwd(cwd.as_os_str());
+    }
+    builder
+}
+
+/// Length of the bytes ending on a complete UTF-8 character, an incomplete one is read again with the next bytes.
+fn utf8_boundary(bytes: &[u8]) -> usize {
+    match std::str::from_utf8(bytes) {
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        _ => bytes.len(),
+    }
+}
+
+fn send(tx: &Sender<CommandEvent>, event: CommandEvent) {
+    let tx = tx.clone();
+    let _ = block_on_task(async move { tx.send(event).await });
+}
+
+/// Reads the output of the pseudo-terminal, stdout and stderr are merged so it's all sent as stdout.
+fn spawn_reader(
+    tx: Sender<CommandEvent>,
+    guard: Arc<RwLock<()>>,
+    mut reader: Box<dyn Read + Send>,
+    raw_out: bool,
+) {
+    spawn_thread(move || {
+        let _lock = guard.read().unwrap();
+        let mut buf = [0; READ_BUFFER_SIZE];
+        let mut pending = Vec::new();
+
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => pending.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                // reading fails with EIO once the child exited on Linux
+                Err(_) => break,
+            }
+
+            let len = if raw_out {
+                pending.len()
+            } else {
+                utf8_boundary(&pending)
+            };
+            if len > 0 {
+                send(&tx, CommandEvent::Stdout(pending.drain(..len).collect()));
+            }
+        }
+
+        if !pending.is_empty() {
+            send(&tx, CommandEvent::Stdout(pending));
+        }
+    });
+}
+
+/// Spawns the command attached to a new pseudo-terminal.
+pub(super) fn spawn(
+    command: Command,
+    size: PtySize,
+) -> crate::Result<(Receiver<CommandEvent>, CommandChild)> {
+    let builder = command_builder(&command);
+
+    let pair = native_pty_system()
+        .openpty(size.into())
+        .map_err(pty_error)?;
+    let mut child = pair.slave.spawn_command(builder).map_err(pty_error)?;
+    // the output is only closed when the child exits if its side is the only one left open
+    drop(pair.slave);
+
+    let reader = pair.master.try_clone_reader().map_err(pty_error)?;
+    let writer = pair.master.take_writer().map_err(pty_error)?;
+    let killer = child.clone_killer();
+    let pid = child.process_id().unwrap_or_default();
+    // the child leads a new session on Unix
+    let group = ProcessGroup::new(pid).ok().map(Arc::new);
+    let exited = Arc::new(AtomicBool::new(false));
+    let exited_ = exited.clone();
+
+    let guard = Arc::new(RwLock::new(()));
+    let (tx, rx) = channel(1);
+
+    spawn_reader(tx.clone(), guard.clone(), reader, command.raw_out);
+
+    spawn_thread(move || {
+        let status = child.wait();
+        exited_.store(true, Ordering::Relaxed);
+        let event = match status {
+            Ok(status) => CommandEvent::Terminated(TerminatedPayload {
+                code: Some(status.exit_code() as i32),
+                signal: None,
+            }),
+            Err(e) => CommandEvent::Error(e.to_string()),
+        };
+
+        // ConPTY only closes the output once the pseudo-terminal is closed,
+        // so the end of the output can't be awaited on Windows
+        #[cfg(not(windows))]
+        let _l = guard.write().unwrap();
+        #[cfg(windows)]
+        drop(guard);
+
+        send(&tx, event);
+    });
+
+    Ok((
+        rx,
+        CommandChild {
+            inner: ChildInner::Pty(PtyChild {
+                master: pair.master,
+                killer,
+                pid,
+            }),
+            stdin: ChildStdin::new(writer),
+            group,
+            exited,
+            exceeded: Default::default(),
+            stats: Default::default(),
+        },
+    ))
+}
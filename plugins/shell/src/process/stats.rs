@@ -0,0 +1,243 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Resource usage of a child process, read from the operating system when requested.
+//!
+//! - Linux and Android: `/proc/<pid>/stat`.
+//! - macOS: `proc_pidinfo`.
+//! - Windows: `GetProcessTimes` and `GetProcessMemoryInfo`.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Resource usage of a child process, see [`super::CommandChild::stats`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessStats {
+    /// CPU usage since the previous call, or since the child was spawned on the first call,
+    /// in percent of a single core so it exceeds 100 when several cores are used.
+    pub cpu_percent: f64,
+    /// Resident set size in bytes, the working set on Windows.
+    pub rss_bytes: u64,
+    /// Time elapsed since the child was spawned.
+    pub runtime: Duration,
+    /// Number of processes the usage was summed over.
+    pub processes: usize,
+}
+
+/// CPU time and memory of a single process.
+#[derive(Debug, Default, Clone, Copy)]
+struct Usage {
+    cpu_time: Duration,
+    rss_bytes: u64,
+}
+
+/// The CPU time of the previous call, to compute the CPU usage between two calls.
+#[derive(Debug)]
+pub(super) struct Sampler {
+    started: Instant,
+    /// The previous sample of the child alone, and of the child with its descendants.
+    last: Mutex<[Option<(Instant, Duration)>; 2]>,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            last: Default::default(),
+        }
+    }
+}
+
+impl Sampler {
+    /// Sums the usage of the processes, skipping the ones that exited in the meantime.
+    pub(super) fn sample(&self, pids: &[u32], tree: bool) -> crate::Result<ProcessStats> {
+        let mut total = Usage::default();
+        let mut processes = 0;
+        let mut error = None;
+        for pid in pids {
+            match platform::usage(*pid) {
+                Ok(usage) => {
+                    total.cpu_time += usage.cpu_time;
+                    total.rss_bytes += usage.rss_bytes;
+                    processes += 1;
+                }
+                Err(e) => {
+                    error.replace(e);
+                }
+            }
+        }
+        if processes == 0 {
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+
+        let now = Instant::now();
+        let (since, cpu_time) = self.last.lock().unwrap()[tree as usize]
+            .replace((now, total.cpu_time))
+            .unwrap_or((self.started, Duration::ZERO));
+        let elapsed = now.duration_since(since).as_secs_f64();
+        // descendants that exited took their CPU time with them
+        let cpu_percent = match total.cpu_time.checked_sub(cpu_time) {
+            Some(cpu_time) if elapsed > 0.0 => cpu_time.as_secs_f64() / elapsed * 100.0,
+            _ => 0.0,
+        };
+
+        Ok(ProcessStats {
+            cpu_percent,
+            rss_bytes: total.rss_bytes,
+            runtime: now.duration_since(self.started),
+            processes,
+        })
+    }
+}
+
+/// Fields of `/proc/<pid>/stat` after the command name, starting with the state.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(super) fn proc_stat(pid: u32) -> std::io::Result<Vec<String>> {
+    use std::io;
+
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))?;
+    // the command name is in parentheses and can contain spaces and parentheses
+    let fields = stat
+        .rfind(')')
+        .map(|end| &stat[end + 1..])
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid process stat"))?;
+    Ok(fields.split_whitespace().map(Into::into).collect())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod platform {
+    use std::{io, time::Duration};
+
+    use super::{proc_stat, Usage};
+
+    const UTIME: usize = 11;
+    const STIME: usize = 12;
+    const RSS: usize = 21;
+
+    pub(super) fn usage(pid: u32) -> crate::Result<Usage> {
+        let fields = proc_stat(pid)?;
+        let field = |index: usize| {
+            fields
+                .get(index)
+                .and_then(|field| field.parse::<u64>().ok())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid process stat"))
+        };
+
+        let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(0) as u64;
+        let ticks = field(UTIME)? + field(STIME)?;
+
+        Ok(Usage {
+            cpu_time: Duration::from_secs_f64(ticks as f64 / ticks_per_second as f64),
+            rss_bytes: field(RSS)? * page_size,
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::{io, mem, time::Duration};
+
+    use super::Usage;
+
+    pub(super) fn usage(pid: u32) -> crate::Result<Usage> {
+        let mut info: libc::proc_taskinfo = unsafe { mem::zeroed() };
+        let size = mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+        let written = unsafe {
+            libc::proc_pidinfo(
+                pid as libc::c_int,
+                libc::PROC_PIDTASKINFO,
+                0,
+                &mut info as *mut _ as *mut libc::c_void,
+                size,
+            )
+        };
+        if written != size {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // the CPU times are in mach absolute time units, which are not nanoseconds on Apple silicon
+        let mut timebase = libc::mach_timebase_info_data_t { numer: 0, denom: 0 };
+        #[allow(deprecated)]
+        unsafe {
+            libc::mach_timebase_info(&mut timebase)
+        };
+        let ticks = (info.pti_total_user + info.pti_total_system) as u128;
+        let nanos = ticks * timebase.numer.max(1) as u128 / timebase.denom.max(1) as u128;
+
+        Ok(Usage {
+            cpu_time: Duration::from_nanos(nanos as u64),
+            rss_bytes: info.pti_resident_size,
+        })
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::{io, mem, time::Duration};
+
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, FILETIME},
+        System::{
+            ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+            Threading::{GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+        },
+    };
+
+    use super::Usage;
+
+    /// Converts a duration in 100 nanoseconds intervals.
+    fn duration(time: FILETIME) -> Duration {
+        let intervals = (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64;
+        Duration::from_nanos(intervals * 100)
+    }
+
+    pub(super) fn usage(pid: u32) -> crate::Result<Usage> {
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if process.is_null() {
+                return Err(io::Error::last_os_error().into());
+            }
+
+            let mut creation: FILETIME = mem::zeroed();
+            let mut exit: FILETIME = mem::zeroed();
+            let mut kernel: FILETIME = mem::zeroed();
+            let mut user: FILETIME = mem::zeroed();
+            let mut counters: PROCESS_MEMORY_COUNTERS = mem::zeroed();
+            counters.cb = mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+
+            let result =
+                if GetProcessTimes(process, &mut creation, &mut exit, &mut kernel, &mut user) == 0
+                    || GetProcessMemoryInfo(process, &mut counters, counters.cb) == 0
+                {
+                    Err(io::Error::last_os_error().into())
+                } else {
+                    Ok(Usage {
+                        cpu_time: duration(kernel) + duration(user),
+                        rss_bytes: counters.WorkingSetSize as u64,
+                    })
+                };
+            CloseHandle(process);
+            result
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod platform {
+    use super::Usage;
+
+    pub(super) fn usage(_pid: u32) -> crate::Result<Usage> {
+        Err(crate::Error::StatsUnsupported)
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The stdin of a child process.
+
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+/// Handle to the stdin of a [`super::CommandChild`], it can be cloned to write from another thread
+/// without borrowing the child.
+///
+/// Writes block until the child read enough of the previous ones, so writing a large input
+/// in chunks doesn't buffer all of it in memory.
+#[derive(Clone)]
+pub struct ChildStdin(Arc<Mutex<Option<Box<dyn Write + Send>>>>);
+
+impl std::fmt::Debug for ChildStdin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChildStdin")
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl ChildStdin {
+    pub(crate) fn new(writer: impl Write + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Some(Box::new(writer)))))
+    }
+
+    /// Writes the whole buffer, fails with [`crate::Error::StdinClosed`] once [`Self::close`] was called.
+    pub fn write(&self, buf: &[u8]) -> crate::Result<()> {
+        let mut stdin = self.0.lock().unwrap();
+        let stdin = stdin.as_mut().ok_or(crate::Error::StdinClosed)?;
+        stdin.write_all(buf)?;
+        stdin.flush()?;
+        Ok(())
+    }
+
+    /// Closes the stdin so the child reads the end of file, closing it again does nothing.
+    /// On a pseudo-terminal, the end of file character is sent instead, which isn't supported on Windows.
+    ///
+    /// It's closed for every clone of the handle, after the write in progress if any.
+    pub fn close(&self) {
+        self.0.lock().unwrap().take();
+    }
+
+    /// Whether [`Self::close`] was called.
+    pub fn is_closed(&self) -> bool {
+        self.0.lock().unwrap().is_none()
+    }
+}
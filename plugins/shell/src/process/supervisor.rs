@@ -0,0 +1,270 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Supervision of a long-running child process, restarting it when it crashes.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tauri::async_runtime::{channel, spawn, Receiver, Sender};
+
+use super::{ChildStdin, Command, CommandChild, CommandEvent, TerminatedPayload};
+
+/// Delay before restarting a crashed child, growing with the number of restarts.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    /// Delay before the first restart.
+    pub initial: Duration,
+    /// Maximum delay between two restarts.
+    pub max: Duration,
+    /// Factor the delay is multiplied by after each restart.
+    pub multiplier: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl Backoff {
+    fn delay(&self, restarts: u32) -> Duration {
+        let delay = self.initial.as_secs_f64() * self.multiplier.powi(restarts as i32);
+        Duration::try_from_secs_f64(delay)
+            .unwrap_or(self.max)
+            .min(self.max)
+    }
+}
+
+/// Options of [`Command::spawn_supervised`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SupervisorOptions {
+    /// Restarts the child when it exits successfully too, it's only restarted when it crashes by default.
+    pub restart_on_exit: bool,
+    /// Maximum number of restarts, the child is restarted indefinitely if `None`.
+    pub max_restarts: Option<u32>,
+    /// Delay before restarting the child.
+    pub backoff: Backoff,
+}
+
+/// A event sent by a supervisor.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum SupervisorEvent {
+    /// The child was spawned.
+    Started {
+        /// Pid of the child.
+        pid: u32,
+    },
+    /// A event of the current child, its output and termination.
+    Child(CommandEvent),
+    /// The child exited with a failure status or was killed by a signal.
+    Crashed(TerminatedPayload),
+    /// The child was spawned again after it exited.
+    Restarted {
+        /// Pid of the new child.
+        pid: u32,
+        /// Number of restarts so far.
+        restarts: u32,
+    },
+    /// The child won't be restarted anymore, after too many restarts or if it failed to spawn.
+    GaveUp {
+        /// Number of restarts so far.
+        restarts: u32,
+        /// The error spawning the child, if any.
+        error: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+struct SupervisorState {
+    child: Option<CommandChild>,
+    stopped: bool,
+}
+
+/// Handle to a supervised child, which stays the same across restarts.
+#[derive(Debug, Clone)]
+pub struct Supervisor(Arc<Mutex<SupervisorState>>);
+
+impl Supervisor {
+    /// Returns the pid of the current child, `None` while it's restarted and once the supervisor was stopped or gave up.
+    pub fn pid(&self) -> Option<u32> {
+        self.0.lock().unwrap().child.as_ref().map(CommandChild::pid)
+    }
+
+    /// Returns a handle to the stdin of the current child.
+    pub fn stdin(&self) -> Option<ChildStdin> {
+        self.0
+            .lock()
+            .unwrap()
+            .child
+            .as_ref()
+            .map(CommandChild::stdin)
+    }
+
+    /// Writes to the stdin of the current child.
+    pub fn write(&self, buf: &[u8]) -> crate::Result<()> {
+        self.stdin()
+            .ok_or(crate::Error::SupervisorStopped)?
+            .write(buf)
+    }
+
+    /// Stops the supervision and kills the current child with its descendants.
+    pub fn stop(&self) -> crate::Result<()> {
+        let child = {
+            let mut state = self.0.lock().unwrap();
+            state.stopped = true;
+            state.child.take()
+        };
+        if let Some(child) = child {
+            child.kill_tree()?;
+        }
+        Ok(())
+    }
+
+    /// Whether [`Self::stop`] was called.
+    pub fn is_stopped(&self) -> bool {
+        self.0.lock().unwrap().stopped
+    }
+
+    /// Sets the current child, it's returned if the supervisor was stopped.
+    fn replace(&self, child: CommandChild) -> Option<CommandChild> {
+        let mut state = self.0.lock().unwrap();
+        if state.stopped {
+            return Some(child);
+        }
+        state.child.replace(child);
+        None
+    }
+
+    fn clear(&self) {
+        self.0.lock().unwrap().child.take();
+    }
+}
+
+impl Command {
+    /// Spawns the command and supervises it, restarting it when it crashes.
+    ///
+    /// The [`Supervisor`] handle stays the same across restarts, and [`Supervisor::stop`] ends the supervision.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_shell::{
+    ///   process::{SupervisorEvent, SupervisorOptions},
+    ///   ShellExt,
+    /// };
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     let (mut rx, _supervisor) = app.shell().sidecar("server")?.spawn_supervised(SupervisorOptions {
+    ///       max_restarts: Some(5),
+    ///       ..Default::default()
+    ///     })?;
+    ///     tauri::async_runtime::spawn(async move {
+    ///       while let Some(event) = rx.recv().await {
+    ///         if let SupervisorEvent::Crashed(payload) = event {
+    ///           println!("server crashed with code {:?}", payload.code);
+    ///         }
+    ///       }
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn spawn_supervised(
+        self,
+        options: SupervisorOptions,
+    ) -> crate::Result<(Receiver<SupervisorEvent>, Supervisor)> {
+        let (rx, child) = self.duplicate().spawn()?;
+        let pid = child.pid();
+        let supervisor = Supervisor(Arc::new(Mutex::new(SupervisorState {
+            child: Some(child),
+            stopped: false,
+        })));
+        let (tx, events) = channel(1);
+
+        spawn(supervise(self, options, rx, pid, supervisor.clone(), tx));
+
+        Ok((events, supervisor))
+    }
+}
+
+async fn supervise(
+    command: Command,
+    options: SupervisorOptions,
+    mut rx: Receiver<CommandEvent>,
+    pid: u32,
+    supervisor: Supervisor,
+    tx: Sender<SupervisorEvent>,
+) {
+    let _ = tx.send(SupervisorEvent::Started { pid }).await;
+    let mut restarts = 0;
+
+    loop {
+        let mut terminated = None;
+        while let Some(event) = rx.recv().await {
+            if let CommandEvent::Terminated(payload) = &event {
+                terminated.replace(payload.clone());
+            }
+            let _ = tx.send(SupervisorEvent::Child(event)).await;
+        }
+        supervisor.clear();
+        if supervisor.is_stopped() {
+            break;
+        }
+
+        let crashed = !matches!(terminated, Some(TerminatedPayload { code: Some(0), .. }));
+        if crashed {
+            let payload = terminated.unwrap_or(TerminatedPayload {
+                code: None,
+                signal: None,
+            });
+            let _ = tx.send(SupervisorEvent::Crashed(payload)).await;
+        } else if !options.restart_on_exit {
+            break;
+        }
+
+        if options.max_restarts.is_some_and(|max| restarts >= max) {
+            let _ = tx
+                .send(SupervisorEvent::GaveUp {
+                    restarts,
+                    error: None,
+                })
+                .await;
+            break;
+        }
+
+        tokio::time::sleep(options.backoff.delay(restarts)).await;
+        if supervisor.is_stopped() {
+            break;
+        }
+
+        match command.duplicate().spawn() {
+            Ok((child_rx, child)) => {
+                let pid = child.pid();
+                if let Some(child) = supervisor.replace(child) {
+                    let _ = child.kill_tree();
+                    break;
+                }
+                rx = child_rx;
+                restarts += 1;
+                let _ = tx.send(SupervisorEvent::Restarted { pid, restarts }).await;
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(SupervisorEvent::GaveUp {
+                        restarts,
+                        error: Some(e.to_string()),
+                    })
+                    .await;
+                break;
+            }
+        }
+    }
+}
@@ -2,7 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
 
 #[allow(deprecated)]
 use crate::open::Program;
@@ -71,6 +78,9 @@ pub struct ScopeAllowedCommand {
 
     /// If this command is a sidecar command.
     pub sidecar: bool,
+
+    /// If this command can be executed with administrator privileges.
+    pub elevated: bool,
 }
 
 impl ScopeObject for ScopeAllowedCommand {
@@ -115,6 +125,7 @@ impl ScopeObject for ScopeAllowedCommand {
             command,
             args,
             sidecar: scope.sidecar,
+            elevated: scope.elevated,
         })
     }
 }
@@ -137,6 +148,141 @@ impl ScopeAllowedArg {
     pub fn is_fixed(&self) -> bool {
         matches!(self, Self::Fixed(_))
     }
+
+    /// A variable argument whose value must match the whole `validator` regex, like the `validator` of the capabilities.
+    pub fn var(validator: &str) -> Result<Self, regex::Error> {
+        Self::raw_var(&format!("^{validator}$"))
+    }
+
+    /// A variable argument whose value must match the `validator` regex, which isn't anchored.
+    pub fn raw_var(validator: &str) -> Result<Self, regex::Error> {
+        Regex::new(validator).map(|validator| Self::Var { validator })
+    }
+}
+
+/// An event emitted by the [`DynamicScope`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ScopeEvent {
+    /// The program with the given path was allowed.
+    ProgramAllowed(PathBuf),
+    /// The program with the given path was removed.
+    ProgramRemoved(PathBuf),
+}
+
+type EventListener = Arc<dyn Fn(&ScopeEvent) + Send + Sync>;
+
+/// Programs allowed to be executed by the webview API at runtime, in addition to the capabilities.
+///
+/// A program allowed with [`DynamicScope::allow_program`] is called by its path,
+/// e.g. `Command.create('/usr/local/bin/ffmpeg')`, and still requires the `execute` or `spawn` permission.
+#[derive(Clone, Default)]
+pub struct DynamicScope {
+    commands: Arc<Mutex<Vec<Arc<ScopeAllowedCommand>>>>,
+    listeners: Arc<Mutex<HashMap<u32, EventListener>>>,
+    next_listener_id: Arc<AtomicU32>,
+}
+
+impl std::fmt::Debug for DynamicScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicScope")
+            .field("commands", &self.commands)
+            .finish()
+    }
+}
+
+impl DynamicScope {
+    /// Allows the program to be executed with arguments validated by `args`, any arguments are allowed if `None`.
+    ///
+    /// Allowing a program again replaces its arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_shell::{ScopeAllowedArg, ShellExt};
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     // e.g. the path configured by the user in the settings
+    ///     app.shell().scope().allow_program(
+    ///       "/usr/local/bin/ffmpeg",
+    ///       Some(vec![
+    ///         ScopeAllowedArg::Fixed("-i".into()),
+    ///         ScopeAllowedArg::var(r"[^-].*")?,
+    ///       ]),
+    ///     );
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn allow_program(&self, path: impl Into<PathBuf>, args: Option<Vec<ScopeAllowedArg>>) {
+        let path = path.into();
+        {
+            let mut commands = self.commands.lock().unwrap();
+            commands.retain(|command| command.command != path);
+            commands.push(Arc::new(ScopeAllowedCommand {
+                name: path.to_string_lossy().into_owned(),
+                command: path.clone(),
+                args,
+                sidecar: false,
+                elevated: false,
+            }));
+        }
+        self.emit(ScopeEvent::ProgramAllowed(path));
+    }
+
+    /// Removes a program allowed with [`Self::allow_program`], returning whether it was allowed.
+    pub fn remove_program(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        let removed = {
+            let mut commands = self.commands.lock().unwrap();
+            let len = commands.len();
+            commands.retain(|command| command.command != path);
+            commands.len() != len
+        };
+        if removed {
+            self.emit(ScopeEvent::ProgramRemoved(path.to_path_buf()));
+        }
+        removed
+    }
+
+    /// The programs allowed with [`Self::allow_program`].
+    pub fn allowed_programs(&self) -> Vec<ScopeAllowedCommand> {
+        self.commands
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|command| ScopeAllowedCommand::clone(command))
+            .collect()
+    }
+
+    pub(crate) fn commands(&self) -> Vec<Arc<ScopeAllowedCommand>> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    /// Listens to the changes of the scope, returning the id to pass to [`Self::unlisten`].
+    pub fn listen<F: Fn(&ScopeEvent) + Send + Sync + 'static>(&self, f: F) -> u32 {
+        let id = self.next_listener_id.fetch_add(1, Ordering::Relaxed);
+        self.listeners.lock().unwrap().insert(id, Arc::new(f));
+        id
+    }
+
+    /// Removes a listener added with [`Self::listen`].
+    pub fn unlisten(&self, id: u32) {
+        self.listeners.lock().unwrap().remove(&id);
+    }
+
+    fn emit(&self, event: ScopeEvent) {
+        // the listeners are called without the lock so they can update the scope and its listeners
+        let listeners = self
+            .listeners
+            .lock()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for listener in listeners {
+            listener(&event);
+        }
+    }
 }
 
 /// Scope for the open command
@@ -165,6 +311,10 @@ pub enum Error {
   )]
     Sidecar(String),
 
+    /// The scoped command was executed with administrator privileges but it isn't allowed to.
+    #[error("Scoped command {0} is not allowed to be executed with administrator privileges")]
+    ElevationNotAllowed(String),
+
     /// The named command was not found in the scoped config.
     #[error("Scoped command {0} not found")]
     NotFound(String),
@@ -235,6 +385,15 @@ impl ShellScope<'_> {
         self._prepare(command_name, args, Some(command_script))
     }
 
+    /// Ensures the named command can be executed with administrator privileges.
+    pub fn ensure_elevation_allowed(&self, command_name: &str) -> Result<(), Error> {
+        match self.scopes.iter().find(|s| s.name == command_name) {
+            Some(command) if command.elevated => Ok(()),
+            Some(_) => Err(Error::ElevationNotAllowed(command_name.into())),
+            None => Err(Error::NotFound(command_name.into())),
+        }
+    }
+
     /// Validates argument inputs and creates a Tauri [`Command`].
     pub fn prepare(&self, command_name: &str, args: ExecuteArgs) -> Result<Command, Error> {
         self._prepare(command_name, args, None)
@@ -13,6 +13,7 @@ pub(crate) struct Entry {
     pub(crate) command: PathBuf,
     pub(crate) args: ShellAllowedArgs,
     pub(crate) sidecar: bool,
+    pub(crate) elevated: bool,
 }
 
 #[derive(Deserialize)]
@@ -24,6 +25,8 @@ pub(crate) struct EntryRaw {
     pub(crate) args: ShellAllowedArgs,
     #[serde(default)]
     pub(crate) sidecar: bool,
+    #[serde(default)]
+    pub(crate) elevated: bool,
 }
 
 impl<'de> Deserialize<'de> for Entry {
@@ -44,6 +47,7 @@ impl<'de> Deserialize<'de> for Entry {
             command: config.command.unwrap_or_default(),
             args: config.args,
             sidecar: config.sidecar,
+            elevated: config.elevated,
         })
     }
 }
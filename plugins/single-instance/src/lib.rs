@@ -36,6 +36,10 @@ pub fn init<R: Runtime, F: FnMut(&AppHandle<R>, Vec<String>, String) + Send + Sy
         if let Some(deep_link) = app.try_state::<tauri_plugin_deep_link::DeepLink<R>>() {
             deep_link.handle_cli_arguments(args.iter());
         }
+        #[cfg(feature = "cli")]
+        if let Some(cli) = app.try_state::<tauri_plugin_cli::Cli<R>>() {
+            let _ = cli.handle_second_instance(args.iter(), &cwd);
+        }
         f(app, args, cwd)
     }))
 }
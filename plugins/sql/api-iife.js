@@ -1 +1 @@
-if("__TAURI__"in window){var __TAURI_PLUGIN_SQL__=function(){"use strict";async function e(e,t={},s){return window.__TAURI_INTERNALS__.invoke(e,t,s)}"function"==typeof SuppressedError&&SuppressedError;class t{constructor(e){this.path=e}static async load(s){const n=await e("plugin:sql|load",{db:s});return new t(n)}static get(e){return new t(e)}async execute(t,s){const[n,r]=await e("plugin:sql|execute",{db:this.path,query:t,values:s??[]});return{lastInsertId:r,rowsAffected:n}}async select(t,s){return await e("plugin:sql|select",{db:this.path,query:t,values:s??[]})}async close(t){return await e("plugin:sql|close",{db:t})}}return t}();Object.defineProperty(window.__TAURI__,"sql",{value:__TAURI_PLUGIN_SQL__})}
+if("__TAURI__"in window){var __TAURI_PLUGIN_SQL__=function(){"use strict";function e(t,e,n,i){if("a"===n&&!i)throw new TypeError("Private accessor was defined without a getter");if("function"==typeof e?t!==e||!i:!e.has(t))throw new TypeError("Cannot read private member from an object whose class did not declare it");return"m"===n?i:"a"===n?i.call(t):i?i.value:e.get(t)}function n(t,e,n,i,o){if("function"==typeof e?t!==e||!o:!e.has(t))throw new TypeError("Cannot write private member to an object whose class did not declare it");return e.set(t,n),n}var i,o,s;"function"==typeof SuppressedError&&SuppressedError;const r="__TAURI_TO_IPC_KEY__";class a{constructor(){this.__TAURI_CHANNEL_MARKER__=!0,i.set(this,(()=>{})),o.set(this,0),s.set(this,{}),this.id=function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}((({message:t,id:r})=>{if(r===e(this,o,"f")){n(this,o,r+1),e(this,i,"f").call(this,t);const a=Object.keys(e(this,s,"f"));if(a.length>0){let t=r+1;for(const n of a.sort()){if(parseInt(n)!==t)break;{const o=e(this,s,"f")[n];delete e(this,s,"f")[n],e(this,i,"f").call(this,o),t+=1}}n(this,o,t)}}else e(this,s,"f")[r.toString()]=t}))}set onmessage(t){n(this,i,t)}get onmessage(){return e(this,i,"f")}[(i=new WeakMap,o=new WeakMap,s=new WeakMap,r)](){return`__CHANNEL__:${this.id}`}toJSON(){return this[r]()}}var d,p,m,y;async function c(t,e={},n){return window.__TAURI_INTERNALS__.invoke(t,e,n)}async function h(t,e){const n={kind:"Any"};return c("plugin:event|listen",{event:t,target:n,handler:function(t,e=!1){return window.__TAURI_INTERNALS__.transformCallback(t,e)}(e)}).then((e=>async()=>async function(t,e){await c("plugin:event|unlisten",{event:t,eventId:e})}(t,e)))}function f(t){return"string"==typeof t?t:Array.from(t)}const g={blob:t=>({$type:"blob",value:Array.from(t)}),decimal:t=>({$type:"decimal",value:t}),uuid:t=>({$type:"uuid",value:t}),timestamptz:t=>({$type:"timestamptz",value:"string"==typeof t?t:t.toISOString()}),array:(t,e)=>({$type:`${t}[]`,value:e})};function v(t){return(t??[]).map((t=>t instanceof Uint8Array?g.blob(t):t))}function b(t){return function(t){return"object"==typeof t&&null!==t&&2===Object.keys(t).length&&"blob"===t.$type&&Array.isArray(t.value)}(t)?new Uint8Array(t.value):Array.isArray(t)?t.map(b):t}function k(t){for(const e of t)for(const n in e)e[n]=b(e[n]);return t}class x{constructor(){this.id=-1,this.channel=new a,d.set(this,[]),p.set(this,null),m.set(this,!1),y.set(this,null),this.channel.onmessage=t=>{if(!e(this,m,"f")){switch(t.event){case"Batch":e(this,d,"f").push(k(t.payload));break;case"Error":n(this,p,t.payload,"f"),n(this,m,!0,"f");break;case"End":n(this,m,!0,"f")}e(this,y,"f")?.call(this)}}}async next(){for(;0===e(this,d,"f").length&&!e(this,m,"f");)await new Promise((t=>{n(this,y,t,"f")})),n(this,y,null,"f");const t=e(this,d,"f").shift();if(t)return c("plugin:sql|select_stream_ack",{stream:this.id}),{value:t,done:!1};if(null!==e(this,p,"f")){const t=e(this,p,"f");throw n(this,p,null,"f"),new Error(t)}return{value:void 0,done:!0}}async return(){return await this.cancel(),{value:void 0,done:!0}}async cancel(){e(this,m,"f")||(n(this,m,!0,"f"),n(this,d,[],"f"),e(this,y,"f")?.call(this),await c("plugin:sql|select_stream_cancel",{stream:this.id}))}[(d=new WeakMap,p=new WeakMap,m=new WeakMap,y=new WeakMap,Symbol.asyncIterator)](){return this}}class S{constructor(t){this.id=t}async execute(t,e){const[n,i]=await c("plugin:sql|transaction_execute",{transaction:this.id,query:t,values:v(e)});return{lastInsertId:i,rowsAffected:n}}async select(t,e){return k(await c("plugin:sql|transaction_select",{transaction:this.id,query:t,values:v(e)}))}async commit(){await c("plugin:sql|commit",{transaction:this.id})}async rollback(){await c("plugin:sql|rollback",{transaction:this.id})}async savepoint(t){await c("plugin:sql|savepoint",{transaction:this.id,name:t})}async releaseSavepoint(t){await c("plugin:sql|release_savepoint",{transaction:this.id,name:t})}async rollbackToSavepoint(t){await c("plugin:sql|rollback_to_savepoint",{transaction:this.id,name:t})}}class D{constructor(t){this.path=t}static async load(t,e){const n=await c("plugin:sql|load",{db:t,encryptionKey:e?.encryptionKey?f(e.encryptionKey):void 0,pool:e?.pool});return new D(n)}static get(t){return new D(t)}async execute(t,e){const[n,i]=await c("plugin:sql|execute",{db:this.path,query:t,values:v(e)});return{lastInsertId:i,rowsAffected:n}}async select(t,e){return k(await c("plugin:sql|select",{db:this.path,query:t,values:v(e)}))}async prepare(t,e){await c("plugin:sql|prepare",{db:this.path,name:t,query:e})}async unprepare(t){return await c("plugin:sql|unprepare",{db:this.path,name:t})}async executePrepared(t,e){const[n,i]=await c("plugin:sql|execute_prepared",{db:this.path,name:t,values:v(e)});return{lastInsertId:i,rowsAffected:n}}async selectPrepared(t,e){return k(await c("plugin:sql|select_prepared",{db:this.path,name:t,values:v(e)}))}async selectStream(t,e,n){const i=new x;return i.id=await c("plugin:sql|select_stream",{db:this.path,query:t,values:v(e),batchSize:n?.batchSize,maxPendingBatches:n?.maxPendingBatches,onEvent:i.channel}),i}async begin(){const t=await c("plugin:sql|begin",{db:this.path});return new S(t)}async poolStatus(){return await c("plugin:sql|pool_status",{db:this.path})}async onConnectionChange(t){return await h("sql://connection",(e=>{e.payload.db===this.path&&t(e.payload.connected,e.payload.error??void 0)}))}async listen(t,e,n){const i=new a;i.onmessage=t=>{switch(t.event){case"Notification":e(t.payload);break;case"ConnectionLost":n?.onConnectionLost?.();break;case"Error":n?.onError?.(t.payload)}};const o=await c("plugin:sql|listen",{db:this.path,channels:"string"==typeof t?[t]:t,onEvent:i});return()=>{c("plugin:sql|unlisten",{listener:o})}}async getMigrationStatus(){return await c("plugin:sql|get_migration_status",{db:this.path})}async rekey(t){await c("plugin:sql|rekey",{db:this.path,key:f(t)})}async backup(t,e){const n=new a;e&&(n.onmessage=e),await c("plugin:sql|backup",{db:this.path,destPath:t instanceof URL?t.toString():t,onProgress:n})}async vacuumInto(t){await c("plugin:sql|vacuum_into",{db:this.path,destPath:t instanceof URL?t.toString():t})}async attach(t,e){await c("plugin:sql|attach",{db:this.path,path:t instanceof URL?t.toString():t,alias:e})}async detach(t){return await c("plugin:sql|detach",{db:this.path,alias:t})}async close(t){return await c("plugin:sql|close",{db:t})}}return D.SqlValue=g,D}();Object.defineProperty(window.__TAURI__,"sql",{value:__TAURI_PLUGIN_SQL__})}
\ No newline at end of file
@@ -2,7 +2,36 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-const COMMANDS: &[&str] = &["load", "execute", "select", "close"];
+const COMMANDS: &[&str] = &[
+    "load",
+    "execute",
+    "select",
+    "close",
+    "get_migration_status",
+    "pool_status",
+    "prepare",
+    "unprepare",
+    "execute_prepared",
+    "select_prepared",
+    "listen",
+    "unlisten",
+    "rekey",
+    "backup",
+    "vacuum_into",
+    "attach",
+    "detach",
+    "select_stream",
+    "select_stream_ack",
+    "select_stream_cancel",
+    "begin",
+    "transaction_execute",
+    "transaction_select",
+    "commit",
+    "rollback",
+    "savepoint",
+    "release_savepoint",
+    "rollback_to_savepoint",
+];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS)
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-import { invoke } from '@tauri-apps/api/core'
+import { Channel, invoke } from '@tauri-apps/api/core'
+import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
 export interface QueryResult {
   /** The number of rows affected by the query. */
@@ -18,6 +19,470 @@ export interface QueryResult {
   lastInsertId?: number
 }
 
+/**
+ * The state of a migration, see {@link Database.getMigrationStatus}.
+ */
+export interface MigrationStatus {
+  version: number
+  /** `null` if the migration was applied but isn't defined anymore, e.g. by a newer version of the app. */
+  description: string | null
+  applied: boolean
+  /** Whether the migration has a down migration, so it can be reverted. */
+  reversible: boolean
+  /** SHA-384 checksum of the defined migration, hex encoded. */
+  checksum: string | null
+  /** SHA-384 checksum of the migration when it was applied, which differs from `checksum` if it was modified since. */
+  appliedChecksum: string | null
+}
+
+/**
+ * The key of a SQLite database encrypted with SQLCipher, either a passphrase
+ * or a raw 32 bytes key, e.g. read from a Stronghold store.
+ */
+export type EncryptionKey = string | Uint8Array | number[]
+
+/**
+ * Options of the connection pool of a database, the sqlx defaults are used for the ones not set.
+ */
+export interface PoolOptions {
+  /** The maximum number of connections. */
+  maxConnections?: number
+  /** The number of connections kept open even when they're idle. */
+  minConnections?: number
+  /** The time to wait for a connection before failing, in milliseconds. */
+  acquireTimeoutMs?: number
+  /** The time after which an idle connection is closed, in milliseconds. */
+  idleTimeoutMs?: number
+  /** The time after which a connection is closed once it's released, in milliseconds. */
+  maxLifetimeMs?: number
+  /** Whether a connection is pinged before it's acquired, so broken connections are replaced. */
+  testBeforeAcquire?: boolean
+  /** The number of prepared statements cached by each connection, 100 by default. */
+  statementCacheCapacity?: number
+  /**
+   * The interval of the health check of MySQL and Postgres servers, in milliseconds,
+   * 30 seconds by default and disabled with `0`. See {@link Database.onConnectionChange}.
+   */
+  healthCheckIntervalMs?: number
+}
+
+/**
+ * The connections of the pool of a database, see {@link Database.poolStatus}.
+ */
+export interface PoolStatus {
+  /** The number of open connections. */
+  size: number
+  /** The number of open connections not in use. */
+  idle: number
+  /** The number of connections in use. */
+  active: number
+  maxConnections: number
+  closed: boolean
+}
+
+/**
+ * A notification sent with `NOTIFY` to a channel of a Postgres database, see {@link Database.listen}.
+ */
+export interface PgNotification {
+  channel: string
+  payload: string
+  /** The process id of the server backend which sent the notification. */
+  processId: number
+}
+
+/**
+ * Options of {@link Database.listen}.
+ */
+export interface ListenOptions {
+  /**
+   * Called when the connection of the listener was lost, the notifications sent until it's
+   * reopened are missed so the data they're about should be fetched again.
+   */
+  onConnectionLost?: () => void
+  /** Called when reopening the connection failed, it's retried after a delay. */
+  onError?: (error: string) => void
+}
+
+type ListenEvent =
+  | { event: 'Notification'; payload: PgNotification }
+  | { event: 'ConnectionLost' }
+  | { event: 'Error'; payload: string }
+
+interface ConnectionEvent {
+  db: string
+  connected: boolean
+  error: string | null
+}
+
+/**
+ * Options of {@link Database.load}.
+ */
+export interface LoadOptions {
+  /**
+   * The options of the connection pool.
+   * Defaults to the options set on the plugin builder, if any.
+   */
+  pool?: PoolOptions
+  /**
+   * The key of the database, requires the `sqlcipher` feature.
+   * Defaults to the key added to the plugin builder, if any.
+   */
+  encryptionKey?: EncryptionKey
+}
+
+function keyArg(key: EncryptionKey): string | number[] {
+  return typeof key === 'string' ? key : Array.from(key)
+}
+
+/**
+ * A value bound with an explicit type, for the types JSON can't represent losslessly.
+ * Created with the {@link Database.SqlValue} helpers.
+ */
+export interface TypedValue {
+  $type: string
+  value: unknown
+}
+
+/**
+ * The element types of the Postgres arrays bound with `SqlValue.array`.
+ */
+export type PgArrayType =
+  | 'int8'
+  | 'float8'
+  | 'numeric'
+  | 'text'
+  | 'bool'
+  | 'uuid'
+  | 'jsonb'
+
+/**
+ * Helpers binding values with an explicit type, exposed as {@link Database.SqlValue}.
+ *
+ * @example
+ * ```ts
+ * const { SqlValue } = Database;
+ * await db.execute(
+ *   "INSERT INTO payments (id, amount, paid_at) VALUES ($1, $2, $3)",
+ *   [ SqlValue.uuid(id), SqlValue.decimal("1234.5678"), SqlValue.timestamptz(new Date()) ]
+ * );
+ * const result = await db.select(
+ *   "SELECT * from todos WHERE id = ANY($1)", [ SqlValue.array("int8", ids) ]
+ * );
+ * ```
+ */
+const SqlValue = {
+  /** A BLOB, `Uint8Array`s are also bound as BLOBs when passed directly. */
+  blob(bytes: Uint8Array | number[]): TypedValue {
+    return { $type: 'blob', value: Array.from(bytes) }
+  },
+  /** A DECIMAL or NUMERIC, passed as a string to keep all its digits. */
+  decimal(value: string): TypedValue {
+    return { $type: 'decimal', value }
+  },
+  /** A UUID, bound as text on SQLite and MySQL. */
+  uuid(value: string): TypedValue {
+    return { $type: 'uuid', value }
+  },
+  /** A timestamp with its offset, a RFC 3339 string or a `Date`. */
+  timestamptz(value: Date | string): TypedValue {
+    return {
+      $type: 'timestamptz',
+      value: typeof value === 'string' ? value : value.toISOString()
+    }
+  },
+  /** A Postgres array, its elements can be `null`. */
+  array(elementType: PgArrayType, values: unknown[]): TypedValue {
+    return { $type: `${elementType}[]`, value: values }
+  }
+}
+
+function encodeValues(values?: unknown[]): unknown[] {
+  return (values ?? []).map((value) =>
+    value instanceof Uint8Array ? SqlValue.blob(value) : value
+  )
+}
+
+function isBlob(value: unknown): value is { $type: 'blob'; value: number[] } {
+  return (
+    typeof value === 'object' &&
+    value !== null &&
+    Object.keys(value).length === 2 &&
+    (value as TypedValue).$type === 'blob' &&
+    Array.isArray((value as TypedValue).value)
+  )
+}
+
+function decodeValue(value: unknown): unknown {
+  if (isBlob(value)) {
+    return new Uint8Array(value.value)
+  }
+  // the Postgres arrays
+  return Array.isArray(value) ? value.map(decodeValue) : value
+}
+
+/** Turns the BLOBs of the rows, sent tagged, into `Uint8Array`s. */
+function decodeRows<T>(rows: T): T {
+  for (const row of rows as Array<Record<string, unknown>>) {
+    for (const column in row) {
+      row[column] = decodeValue(row[column])
+    }
+  }
+  return rows
+}
+
+/**
+ * The progress of a backup, see {@link Database.backup}.
+ */
+export interface BackupProgress {
+  /** The number of pages left to copy. */
+  remaining: number
+  /** The number of pages of the database. */
+  total: number
+}
+
+/**
+ * Options of {@link Database.selectStream}.
+ */
+export interface SelectStreamOptions {
+  /** The number of rows per batch, 100 by default. */
+  batchSize?: number
+  /**
+   * The number of batches read from the database ahead of the ones consumed, 2 by default.
+   * The query is paused until a batch is consumed once this many are pending.
+   */
+  maxPendingBatches?: number
+}
+
+type StreamEvent<T> =
+  | { event: 'Batch'; payload: T[] }
+  | { event: 'Error'; payload: string }
+  | { event: 'End' }
+
+/**
+ * **SelectStream**
+ *
+ * The rows of a query started with {@link Database.selectStream}, iterated in batches.
+ * The next rows are only read from the database as the batches are consumed.
+ */
+class SelectStream<T> implements AsyncIterableIterator<T[]> {
+  id = -1
+  /** @ignore */
+  readonly channel = new Channel<StreamEvent<T>>()
+  #batches: T[][] = []
+  #error: string | null = null
+  #done = false
+  #wake: (() => void) | null = null
+
+  /** @ignore */
+  constructor() {
+    this.channel.onmessage = (message) => {
+      if (this.#done) {
+        return
+      }
+      switch (message.event) {
+        case 'Batch':
+          this.#batches.push(decodeRows(message.payload))
+          break
+        case 'Error':
+          this.#error = message.payload
+          this.#done = true
+          break
+        case 'End':
+          this.#done = true
+          break
+      }
+      this.#wake?.()
+    }
+  }
+
+  /**
+   * **next**
+   *
+   * Waits for the next batch of rows, allowing the query to read one more batch.
+   * Rejects if the query failed.
+   */
+  async next(): Promise<IteratorResult<T[]>> {
+    while (this.#batches.length === 0 && !this.#done) {
+      await new Promise<void>((resolve) => {
+        this.#wake = resolve
+      })
+      this.#wake = null
+    }
+
+    const batch = this.#batches.shift()
+    if (batch) {
+      void invoke('plugin:sql|select_stream_ack', { stream: this.id })
+      return { value: batch, done: false }
+    }
+    if (this.#error !== null) {
+      const error = this.#error
+      this.#error = null
+      throw new Error(error)
+    }
+    return { value: undefined, done: true }
+  }
+
+  /**
+   * Cancels the stream when a `for await` loop is exited early.
+   */
+  async return(): Promise<IteratorResult<T[]>> {
+    await this.cancel()
+    return { value: undefined, done: true }
+  }
+
+  /**
+   * **cancel**
+   *
+   * Stops the query and discards the pending batches, releasing the connection.
+   *
+   * @example
+   * ```ts
+   * const stream = await db.selectStream("SELECT * from logs");
+   * const { value } = await stream.next();
+   * await stream.cancel();
+   * ```
+   */
+  async cancel(): Promise<void> {
+    if (this.#done) {
+      return
+    }
+    this.#done = true
+    this.#batches = []
+    this.#wake?.()
+    await invoke('plugin:sql|select_stream_cancel', { stream: this.id })
+  }
+
+  [Symbol.asyncIterator](): AsyncIterableIterator<T[]> {
+    return this
+  }
+}
+
+/**
+ * **Transaction**
+ *
+ * A transaction started with {@link Database.begin}, whose statements all run
+ * on the same connection of the pool until it's committed or rolled back.
+ */
+class Transaction {
+  id: number
+  constructor(id: number) {
+    this.id = id
+  }
+
+  /**
+   * **execute**
+   *
+   * Passes a SQL expression to the database for execution within the transaction.
+   *
+   * @example
+   * ```ts
+   * const tx = await db.begin();
+   * await tx.execute("UPDATE accounts SET balance = balance - $1 WHERE id = $2", [ amount, from ]);
+   * await tx.execute("UPDATE accounts SET balance = balance + $1 WHERE id = $2", [ amount, to ]);
+   * await tx.commit();
+   * ```
+   */
+  async execute(query: string, bindValues?: unknown[]): Promise<QueryResult> {
+    const [rowsAffected, lastInsertId] = await invoke<[number, number]>(
+      'plugin:sql|transaction_execute',
+      {
+        transaction: this.id,
+        query,
+        values: encodeValues(bindValues)
+      }
+    )
+    return {
+      lastInsertId,
+      rowsAffected
+    }
+  }
+
+  /**
+   * **select**
+   *
+   * Passes in a SELECT query to the database for execution within the transaction.
+   *
+   * @example
+   * ```ts
+   * const result = await tx.select(
+   *    "SELECT * from todos WHERE id = $1", [ id ]
+   * );
+   * ```
+   */
+  async select<T>(query: string, bindValues?: unknown[]): Promise<T> {
+    const result = await invoke<T>('plugin:sql|transaction_select', {
+      transaction: this.id,
+      query,
+      values: encodeValues(bindValues)
+    })
+
+    return decodeRows(result)
+  }
+
+  /**
+   * **commit**
+   *
+   * Commits the transaction, releasing its connection.
+   */
+  async commit(): Promise<void> {
+    await invoke('plugin:sql|commit', { transaction: this.id })
+  }
+
+  /**
+   * **rollback**
+   *
+   * Rolls back the transaction, releasing its connection.
+   */
+  async rollback(): Promise<void> {
+    await invoke('plugin:sql|rollback', { transaction: this.id })
+  }
+
+  /**
+   * **savepoint**
+   *
+   * Creates a savepoint, to roll back part of the transaction with {@link Transaction.rollbackToSavepoint}.
+   *
+   * @example
+   * ```ts
+   * await tx.savepoint("before_import");
+   * try {
+   *   await tx.execute("INSERT INTO items (name) VALUES ($1)", [ name ]);
+   *   await tx.releaseSavepoint("before_import");
+   * } catch {
+   *   await tx.rollbackToSavepoint("before_import");
+   * }
+   * ```
+   * @param name - The savepoint name, made of ASCII letters, digits and underscores.
+   */
+  async savepoint(name: string): Promise<void> {
+    await invoke('plugin:sql|savepoint', { transaction: this.id, name })
+  }
+
+  /**
+   * **releaseSavepoint**
+   *
+   * Releases a savepoint, keeping the changes made since it was created.
+   */
+  async releaseSavepoint(name: string): Promise<void> {
+    await invoke('plugin:sql|release_savepoint', {
+      transaction: this.id,
+      name
+    })
+  }
+
+  /**
+   * **rollbackToSavepoint**
+   *
+   * Rolls back the changes made since the savepoint was created, the transaction stays open.
+   */
+  async rollbackToSavepoint(name: string): Promise<void> {
+    await invoke('plugin:sql|rollback_to_savepoint', {
+      transaction: this.id,
+      name
+    })
+  }
+}
+
 /**
  * **Database**
  *
@@ -25,6 +490,17 @@ export interface QueryResult {
  * communicating with the rust side of the sql plugin.
  */
 export default class Database {
+  /**
+   * The helpers binding values with an explicit type, for the types JSON can't represent losslessly.
+   *
+   * @example
+   * ```ts
+   * const { SqlValue } = Database;
+   * await db.execute("INSERT INTO payments (amount) VALUES ($1)", [ SqlValue.decimal("1234.5678") ]);
+   * ```
+   */
+  static readonly SqlValue = SqlValue
+
   path: string
   constructor(path: string) {
     this.path = path
@@ -40,14 +516,22 @@ export default class Database {
    *
    * The path is relative to `tauri::path::BaseDirectory::App` and must start with `sqlite:`.
    *
+   * A SQLite database encrypted with SQLCipher is opened with the `encryptionKey` option,
+   * which rejects with a wrong encryption key error if it doesn't match.
+   *
    * @example
    * ```ts
    * const db = await Database.load("sqlite:test.db");
+   * const secrets = await Database.load("sqlite:secrets.db", { encryptionKey: passphrase });
    * ```
    */
-  static async load(path: string): Promise<Database> {
+  static async load(path: string, options?: LoadOptions): Promise<Database> {
     const _path = await invoke<string>('plugin:sql|load', {
-      db: path
+      db: path,
+      encryptionKey: options?.encryptionKey
+        ? keyArg(options.encryptionKey)
+        : undefined,
+      pool: options?.pool
     })
 
     return new Database(_path)
@@ -111,7 +595,7 @@ export default class Database {
       {
         db: this.path,
         query,
-        values: bindValues ?? []
+        values: encodeValues(bindValues)
       }
     )
     return {
@@ -142,10 +626,360 @@ export default class Database {
     const result = await invoke<T>('plugin:sql|select', {
       db: this.path,
       query,
-      values: bindValues ?? []
+      values: encodeValues(bindValues)
+    })
+
+    return decodeRows(result)
+  }
+
+  /**
+   * **prepare**
+   *
+   * Prepares a statement by name, so a hot query is only sent once and parsed once per
+   * connection. Rejects if the statement is invalid, and replaces the statement prepared
+   * with the same name if any.
+   *
+   * @example
+   * ```ts
+   * await db.prepare("insert_todo", "INSERT INTO todos (title) VALUES ($1)");
+   * await db.executePrepared("insert_todo", [ title ]);
+   * ```
+   */
+  async prepare(name: string, query: string): Promise<void> {
+    await invoke('plugin:sql|prepare', {
+      db: this.path,
+      name,
+      query
+    })
+  }
+
+  /**
+   * **unprepare**
+   *
+   * Removes a prepared statement.
+   *
+   * @returns Whether the statement was prepared.
+   */
+  async unprepare(name: string): Promise<boolean> {
+    return await invoke<boolean>('plugin:sql|unprepare', {
+      db: this.path,
+      name
+    })
+  }
+
+  /**
+   * **executePrepared**
+   *
+   * Executes a statement prepared with {@link Database.prepare}.
+   *
+   * @example
+   * ```ts
+   * const result = await db.executePrepared("insert_todo", [ title ]);
+   * ```
+   */
+  async executePrepared(
+    name: string,
+    bindValues?: unknown[]
+  ): Promise<QueryResult> {
+    const [rowsAffected, lastInsertId] = await invoke<[number, number]>(
+      'plugin:sql|execute_prepared',
+      {
+        db: this.path,
+        name,
+        values: encodeValues(bindValues)
+      }
+    )
+    return {
+      lastInsertId,
+      rowsAffected
+    }
+  }
+
+  /**
+   * **selectPrepared**
+   *
+   * Runs a SELECT query prepared with {@link Database.prepare}.
+   *
+   * @example
+   * ```ts
+   * await db.prepare("todo_by_id", "SELECT * from todos WHERE id = $1");
+   * const result = await db.selectPrepared("todo_by_id", [ id ]);
+   * ```
+   */
+  async selectPrepared<T>(name: string, bindValues?: unknown[]): Promise<T> {
+    const result = await invoke<T>('plugin:sql|select_prepared', {
+      db: this.path,
+      name,
+      values: encodeValues(bindValues)
+    })
+
+    return decodeRows(result)
+  }
+
+  /**
+   * **selectStream**
+   *
+   * Passes in a SELECT query to the database, reading the rows in batches as they're consumed
+   * instead of loading them all in memory. The query holds a connection of the pool until
+   * all the rows were read or the stream is cancelled.
+   *
+   * @example
+   * ```ts
+   * const stream = await db.selectStream<Log>(
+   *    "SELECT * from logs WHERE level = $1", [ level ], { batchSize: 500 }
+   * );
+   * for await (const logs of stream) {
+   *   render(logs);
+   * }
+   * ```
+   */
+  async selectStream<T>(
+    query: string,
+    bindValues?: unknown[],
+    options?: SelectStreamOptions
+  ): Promise<SelectStream<T>> {
+    const stream = new SelectStream<T>()
+    stream.id = await invoke<number>('plugin:sql|select_stream', {
+      db: this.path,
+      query,
+      values: encodeValues(bindValues),
+      batchSize: options?.batchSize,
+      maxPendingBatches: options?.maxPendingBatches,
+      onEvent: stream.channel
     })
+    return stream
+  }
 
-    return result
+  /**
+   * **begin**
+   *
+   * Starts a transaction on one of the connections of the pool. Unlike a `BEGIN` statement
+   * passed to {@link Database.execute}, its statements are guaranteed to run on the same connection.
+   * The open transactions of a database are rolled back when it's closed.
+   *
+   * @example
+   * ```ts
+   * const tx = await db.begin();
+   * try {
+   *   await tx.execute("INSERT INTO todos (title) VALUES ($1)", [ title ]);
+   *   await tx.commit();
+   * } catch (e) {
+   *   await tx.rollback();
+   *   throw e;
+   * }
+   * ```
+   */
+  async begin(): Promise<Transaction> {
+    const id = await invoke<number>('plugin:sql|begin', {
+      db: this.path
+    })
+    return new Transaction(id)
+  }
+
+  /**
+   * **poolStatus**
+   *
+   * Reports the open and idle connections of the connection pool.
+   *
+   * @example
+   * ```ts
+   * const { active, maxConnections } = await db.poolStatus();
+   * ```
+   */
+  async poolStatus(): Promise<PoolStatus> {
+    return await invoke<PoolStatus>('plugin:sql|pool_status', {
+      db: this.path
+    })
+  }
+
+  /**
+   * **onConnectionChange**
+   *
+   * Listens to the health check of a MySQL or Postgres database, called with `false` when
+   * the server can't be reached and with `true` once the pool reconnected.
+   *
+   * @example
+   * ```ts
+   * const unlisten = await db.onConnectionChange((connected, error) => {
+   *   setOffline(!connected);
+   * });
+   * ```
+   */
+  async onConnectionChange(
+    cb: (connected: boolean, error?: string) => void
+  ): Promise<UnlistenFn> {
+    return await listen<ConnectionEvent>('sql://connection', (event) => {
+      if (event.payload.db === this.path) {
+        cb(event.payload.connected, event.payload.error ?? undefined)
+      }
+    })
+  }
+
+  /**
+   * **listen**
+   *
+   * Listens to one or several channels of a Postgres database on a dedicated connection,
+   * which is reopened and listens to the channels again if it's lost.
+   *
+   * @example
+   * ```ts
+   * const unlisten = await db.listen("todos_changed", (notification) => {
+   *   refresh(JSON.parse(notification.payload));
+   * });
+   * // from any connection
+   * await db.execute("NOTIFY todos_changed, '{\"id\": 1}'");
+   * ```
+   * @returns A promise resolving to a function stopping the listener and closing its connection.
+   */
+  async listen(
+    channels: string | string[],
+    handler: (notification: PgNotification) => void,
+    options?: ListenOptions
+  ): Promise<UnlistenFn> {
+    const onEvent = new Channel<ListenEvent>()
+    onEvent.onmessage = (message) => {
+      switch (message.event) {
+        case 'Notification':
+          handler(message.payload)
+          break
+        case 'ConnectionLost':
+          options?.onConnectionLost?.()
+          break
+        case 'Error':
+          options?.onError?.(message.payload)
+          break
+      }
+    }
+
+    const listener = await invoke<number>('plugin:sql|listen', {
+      db: this.path,
+      channels: typeof channels === 'string' ? [channels] : channels,
+      onEvent
+    })
+    return () => {
+      void invoke('plugin:sql|unlisten', { listener })
+    }
+  }
+
+  /**
+   * **getMigrationStatus**
+   *
+   * Lists the defined and applied migrations of the database, sorted by version.
+   *
+   * @example
+   * ```ts
+   * const status = await db.getMigrationStatus();
+   * const pending = status.filter((migration) => !migration.applied);
+   * ```
+   */
+  async getMigrationStatus(): Promise<MigrationStatus[]> {
+    return await invoke<MigrationStatus[]>('plugin:sql|get_migration_status', {
+      db: this.path
+    })
+  }
+
+  /**
+   * **rekey**
+   *
+   * Changes the key of a SQLite database encrypted with SQLCipher, re-encrypting it.
+   * Its open transactions and streams are ended first.
+   *
+   * @example
+   * ```ts
+   * await db.rekey(newPassphrase);
+   * ```
+   */
+  async rekey(key: EncryptionKey): Promise<void> {
+    await invoke('plugin:sql|rekey', {
+      db: this.path,
+      key: keyArg(key)
+    })
+  }
+
+  /**
+   * **backup**
+   *
+   * Copies a SQLite database to a file while it's in use, with the online backup API.
+   * The file is overwritten, and the copy of an encrypted database is encrypted with the same key.
+   * The path must be allowed on the scope of the fs plugin.
+   *
+   * @example
+   * ```ts
+   * import { appDataDir, join } from '@tauri-apps/api/path';
+   * const dest = await join(await appDataDir(), 'backup.db');
+   * await db.backup(dest, ({ remaining, total }) => {
+   *   setProgress((total - remaining) / total);
+   * });
+   * ```
+   */
+  async backup(
+    destPath: string | URL,
+    onProgress?: (progress: BackupProgress) => void
+  ): Promise<void> {
+    const channel = new Channel<BackupProgress>()
+    if (onProgress) {
+      channel.onmessage = onProgress
+    }
+    await invoke('plugin:sql|backup', {
+      db: this.path,
+      destPath: destPath instanceof URL ? destPath.toString() : destPath,
+      onProgress: channel
+    })
+  }
+
+  /**
+   * **vacuumInto**
+   *
+   * Writes a vacuumed copy of a SQLite database to a file with `VACUUM INTO`, which is smaller
+   * than a backup. Rejects if the file exists. The path must be allowed on the scope of the fs plugin.
+   *
+   * @example
+   * ```ts
+   * await db.vacuumInto(dest);
+   * ```
+   */
+  async vacuumInto(destPath: string | URL): Promise<void> {
+    await invoke('plugin:sql|vacuum_into', {
+      db: this.path,
+      destPath: destPath instanceof URL ? destPath.toString() : destPath
+    })
+  }
+
+  /**
+   * **attach**
+   *
+   * Attaches a SQLite database file under an alias, so a query can join its tables, as
+   * `alias.table`, with the ones of this database. The connections of the database are reopened,
+   * ending its open transactions and streams. The path must be allowed on the scope of the fs plugin.
+   *
+   * @example
+   * ```ts
+   * await db.attach(archivePath, "archive");
+   * const result = await db.select(
+   *    "SELECT * FROM todos UNION ALL SELECT * FROM archive.todos"
+   * );
+   * ```
+   */
+  async attach(path: string | URL, alias: string): Promise<void> {
+    await invoke('plugin:sql|attach', {
+      db: this.path,
+      path: path instanceof URL ? path.toString() : path,
+      alias
+    })
+  }
+
+  /**
+   * **detach**
+   *
+   * Detaches a database attached with {@link Database.attach}, reopening the connections of the database.
+   *
+   * @returns Whether the alias was attached.
+   */
+  async detach(alias: string): Promise<boolean> {
+    return await invoke<boolean>('plugin:sql|detach', {
+      db: this.path,
+      alias
+    })
   }
 
   /**
@@ -166,3 +1000,5 @@ export default class Database {
     return success
   }
 }
+
+export type { SelectStream, Transaction }
@@ -0,0 +1,202 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Databases attached to SQLite databases with `ATTACH`, so a query can join across several files.
+//!
+//! `ATTACH` only applies to the connection it runs on, so the attachments are kept and run on
+//! every connection of the pool when it's opened.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use tauri::{Manager, Runtime};
+
+#[cfg(feature = "sqlite")]
+use sqlx::{Executor, SqliteConnection};
+
+use crate::{
+    DbInstances, DbPool, DbPoolOptions, DbStreams, DbTransactions, EncryptionKeys, Error, Sql,
+};
+
+/// A database attached to another one.
+#[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub(crate) struct Attachment {
+    alias: String,
+    path: PathBuf,
+}
+
+impl Attachment {
+    /// The alias must be a plain identifier since it can't be bound.
+    fn new(path: PathBuf, alias: &str) -> Result<Self, Error> {
+        let valid = alias
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && alias.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !["main", "temp"].contains(&alias.to_ascii_lowercase().as_str());
+        if !valid {
+            return Err(Error::InvalidAlias(alias.into()));
+        }
+        Ok(Self {
+            alias: alias.into(),
+            path,
+        })
+    }
+
+    #[cfg(feature = "sqlite")]
+    pub(crate) async fn attach(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        let statement = format!("ATTACH DATABASE ? AS {}", self.alias);
+        sqlx::query(&statement)
+            .bind(self.path.to_string_lossy().into_owned())
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn detach(&self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        conn.execute(format!("DETACH DATABASE {}", self.alias).as_str())
+            .await?;
+        Ok(())
+    }
+}
+
+impl DbPool {
+    /// Attaches and detaches the database on one of the connections, failing if it can't be attached.
+    async fn check_attachment(&self, _db: &str, _attachment: &Attachment) -> Result<(), Error> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                let mut conn = pool.acquire().await?;
+                _attachment.attach(&mut conn).await?;
+                _attachment.detach(&mut conn).await?;
+                Ok(())
+            }
+            _ => Err(Error::AttachUnsupported(_db.into())),
+        }
+    }
+}
+
+/// The databases attached to each database, attached again when it's loaded until they're detached.
+#[derive(Default)]
+pub(crate) struct DbAttachments(Mutex<HashMap<String, Vec<Attachment>>>);
+
+impl DbAttachments {
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(crate) fn get(&self, db: &str) -> Vec<Attachment> {
+        self.0.lock().unwrap().get(db).cloned().unwrap_or_default()
+    }
+
+    fn insert(&self, db: &str, attachment: Attachment) -> Result<(), Error> {
+        let mut attachments = self.0.lock().unwrap();
+        let attachments = attachments.entry(db.into()).or_default();
+        if attachments
+            .iter()
+            .any(|a| a.alias.eq_ignore_ascii_case(&attachment.alias))
+        {
+            return Err(Error::AliasInUse(attachment.alias));
+        }
+        attachments.push(attachment);
+        Ok(())
+    }
+
+    /// Removes the attachment, returning it if the alias was attached.
+    fn remove(&self, db: &str, alias: &str) -> Option<Attachment> {
+        let mut attachments = self.0.lock().unwrap();
+        let attachments = attachments.get_mut(db)?;
+        let index = attachments
+            .iter()
+            .position(|a| a.alias.eq_ignore_ascii_case(alias))?;
+        Some(attachments.remove(index))
+    }
+}
+
+impl<R: Runtime> Sql<R> {
+    /// Reopens the connections of a database, so they use its current attachments.
+    ///
+    /// The current connections are kept if the new ones fail to open.
+    async fn reopen(&self, db: &str) -> Result<(), Error> {
+        let instances = self.app.state::<DbInstances>();
+        let mut instances = instances.0.write().await;
+        if !instances.contains_key(db) {
+            return Err(Error::DatabaseNotLoaded(db.into()));
+        }
+        let key = self.app.state::<EncryptionKeys>().get(db);
+        let pool_options = self.app.state::<DbPoolOptions>().get(db);
+        let pool = DbPool::connect(db, &self.app, key.as_ref(), &pool_options).await?;
+
+        // the pool waits for the connections held by the transactions and streams to be released
+        self.app.state::<DbStreams>().cancel_all(Some(db));
+        self.app
+            .state::<DbTransactions>()
+            .rollback_all(Some(db))
+            .await;
+        if let Some(pool) = instances.insert(db.into(), pool) {
+            pool.close().await;
+        }
+        Ok(())
+    }
+
+    /// Attaches a SQLite database file to a loaded SQLite database under an alias, so its tables
+    /// can be queried as `alias.table`. The connections of the database are reopened, ending its
+    /// open transactions and streams, and an encrypted database attaches the file with its key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri::Manager;
+    /// use tauri_plugin_sql::SqlExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     let handle = app.handle().clone();
+    ///     let archive = app.path().app_data_dir()?.join("archive.db");
+    ///     tauri::async_runtime::spawn(async move {
+    ///       handle.sql().attach("sqlite:app.db", &archive, "archive").await
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub async fn attach(&self, db: &str, path: impl AsRef<Path>, alias: &str) -> Result<(), Error> {
+        let attachment = Attachment::new(path.as_ref().to_path_buf(), alias)?;
+        {
+            let instances = self.app.state::<DbInstances>();
+            let instances = instances.0.read().await;
+            let pool = instances
+                .get(db)
+                .ok_or_else(|| Error::DatabaseNotLoaded(db.into()))?;
+            pool.check_attachment(db, &attachment).await?;
+        }
+
+        let alias = attachment.alias.clone();
+        let attachments = self.app.state::<DbAttachments>();
+        attachments.insert(db, attachment)?;
+        if let Err(e) = self.reopen(db).await {
+            attachments.remove(db, &alias);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Detaches a database attached with [`Self::attach`], reopening the connections of the database.
+    ///
+    /// Returns whether the alias was attached.
+    pub async fn detach(&self, db: &str, alias: &str) -> Result<bool, Error> {
+        let attachments = self.app.state::<DbAttachments>();
+        let Some(attachment) = attachments.remove(db, alias) else {
+            return Ok(false);
+        };
+        if let Err(e) = self.reopen(db).await {
+            // only fails if the alias was attached again in the meantime
+            let _ = attachments.insert(db, attachment);
+            return Err(e);
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Copies of SQLite databases taken while they're in use, with the
+//! [online backup API](https://www.sqlite.org/backup.html) or `VACUUM INTO`.
+
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{Manager, Runtime};
+
+#[cfg(feature = "sqlite")]
+use std::{ffi::CStr, ptr};
+
+#[cfg(feature = "sqlite")]
+use libsqlite3_sys as ffi;
+
+use crate::{DbInstances, DbPool, EncryptionKey, EncryptionKeys, Error, Sql};
+
+/// Number of pages copied by each step of a backup, between which the database can be written.
+#[cfg(feature = "sqlite")]
+const PAGES_PER_STEP: i32 = 256;
+
+/// Time to wait before retrying a step when the database is locked, in milliseconds.
+#[cfg(feature = "sqlite")]
+const BUSY_RETRY_DELAY_MS: i32 = 50;
+
+/// The progress of a backup, reported after each step.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupProgress {
+    /// Number of pages left to copy.
+    pub remaining: u32,
+    /// Number of pages of the database.
+    pub total: u32,
+}
+
+/// A connection to the destination of a backup, closed when dropped.
+#[cfg(feature = "sqlite")]
+struct Destination(*mut ffi::sqlite3);
+
+#[cfg(feature = "sqlite")]
+impl Drop for Destination {
+    fn drop(&mut self) {
+        // a no-op for a null handle
+        unsafe { ffi::sqlite3_close(self.0) };
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Destination {
+    fn open(path: &Path) -> Result<Self, Error> {
+        let c_path = std::ffi::CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|_| Error::Backup(format!("invalid path {}", path.display())))?;
+        let mut handle = ptr::null_mut();
+        let rc = unsafe {
+            ffi::sqlite3_open_v2(
+                c_path.as_ptr(),
+                &mut handle,
+                ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE,
+                ptr::null(),
+            )
+        };
+        // the handle is allocated even when opening fails, so it must be closed
+        let destination = Self(handle);
+        if rc != ffi::SQLITE_OK {
+            return Err(destination.error());
+        }
+        Ok(destination)
+    }
+
+    fn error(&self) -> Error {
+        let message = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(self.0)) };
+        Error::Backup(message.to_string_lossy().into_owned())
+    }
+
+    /// Encrypts the destination, before anything is written to it.
+    #[cfg(feature = "sqlcipher")]
+    fn key(&self, db: &str, key: &EncryptionKey) -> Result<(), Error> {
+        let statement = std::ffi::CString::new(format!("PRAGMA key = {}", key.pragma()))
+            .map_err(|_| Error::InvalidEncryptionKey(db.into()))?;
+        let rc = unsafe {
+            ffi::sqlite3_exec(
+                self.0,
+                statement.as_ptr(),
+                None,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        if rc != ffi::SQLITE_OK {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+
+    /// Copies the database of the source connection, which must not be used meanwhile.
+    fn copy_from(
+        &self,
+        source: *mut ffi::sqlite3,
+        mut on_progress: impl FnMut(BackupProgress),
+    ) -> Result<(), Error> {
+        let main = c"main";
+        let backup =
+            unsafe { ffi::sqlite3_backup_init(self.0, main.as_ptr(), source, main.as_ptr()) };
+        if backup.is_null() {
+            return Err(self.error());
+        }
+
+        let progress = || BackupProgress {
+            remaining: unsafe { ffi::sqlite3_backup_remaining(backup) } as u32,
+            total: unsafe { ffi::sqlite3_backup_pagecount(backup) } as u32,
+        };
+        loop {
+            match unsafe { ffi::sqlite3_backup_step(backup, PAGES_PER_STEP) } {
+                ffi::SQLITE_OK => on_progress(progress()),
+                ffi::SQLITE_DONE => {
+                    on_progress(progress());
+                    break;
+                }
+                // written by another connection, or the destination is in use
+                ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => unsafe {
+                    ffi::sqlite3_sleep(BUSY_RETRY_DELAY_MS);
+                },
+                _ => break,
+            }
+        }
+
+        // returns the error of the failed step, if any
+        if unsafe { ffi::sqlite3_backup_finish(backup) } != ffi::SQLITE_OK {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+}
+
+impl DbPool {
+    /// Copies the database to the file with the online backup API, keying the copy
+    /// with the key of the database if it's encrypted.
+    async fn backup(
+        &self,
+        _db: &str,
+        _dest: &Path,
+        _key: Option<&EncryptionKey>,
+        _on_progress: impl FnMut(BackupProgress) + Send,
+    ) -> Result<(), Error> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                let mut conn = pool.acquire().await?;
+                let mut handle = conn.lock_handle().await?;
+                let source = handle.as_raw_handle().as_ptr();
+                // the steps block, so the other tasks are moved off this thread meanwhile
+                tokio::task::block_in_place(|| {
+                    let destination = Destination::open(_dest)?;
+                    #[cfg(feature = "sqlcipher")]
+                    if let Some(key) = _key {
+                        destination.key(_db, key)?;
+                    }
+                    destination.copy_from(source, _on_progress)
+                })
+            }
+            _ => Err(Error::BackupUnsupported(_db.into())),
+        }
+    }
+
+    /// Writes a vacuumed copy of the database to the file, which must not exist.
+    async fn vacuum_into(&self, _db: &str, _dest: &Path) -> Result<(), Error> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                sqlx::query("VACUUM INTO ?")
+                    .bind(_dest.to_string_lossy().into_owned())
+                    .execute(pool)
+                    .await?;
+                Ok(())
+            }
+            _ => Err(Error::BackupUnsupported(_db.into())),
+        }
+    }
+}
+
+impl<R: Runtime> Sql<R> {
+    /// Copies a loaded SQLite database to a file while it's in use, overwriting the file.
+    /// The copy of an encrypted database is encrypted with the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri::Manager;
+    /// use tauri_plugin_sql::SqlExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     let handle = app.handle().clone();
+    ///     let dest = app.path().app_data_dir()?.join("backup.db");
+    ///     tauri::async_runtime::spawn(async move {
+    ///       handle
+    ///         .sql()
+    ///         .backup("sqlite:app.db", &dest, |progress| {
+    ///           println!("{} pages left", progress.remaining);
+    ///         })
+    ///         .await
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub async fn backup(
+        &self,
+        db: &str,
+        dest: impl AsRef<Path>,
+        on_progress: impl FnMut(BackupProgress) + Send,
+    ) -> Result<(), Error> {
+        let key = self.app.state::<EncryptionKeys>().get(db);
+
+        let instances = self.app.state::<DbInstances>();
+        let instances = instances.0.read().await;
+        let pool = instances
+            .get(db)
+            .ok_or_else(|| Error::DatabaseNotLoaded(db.into()))?;
+        pool.backup(db, dest.as_ref(), key.as_ref(), on_progress)
+            .await
+    }
+
+    /// Writes a vacuumed copy of a loaded SQLite database to a file with `VACUUM INTO`,
+    /// which is smaller than a backup but fails if the file exists.
+    pub async fn vacuum_into(&self, db: &str, dest: impl AsRef<Path>) -> Result<(), Error> {
+        let instances = self.app.state::<DbInstances>();
+        let instances = instances.0.read().await;
+        let pool = instances
+            .get(db)
+            .ok_or_else(|| Error::DatabaseNotLoaded(db.into()))?;
+        pool.vacuum_into(db, dest.as_ref()).await
+    }
+}
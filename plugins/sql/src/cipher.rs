@@ -0,0 +1,82 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Encryption of SQLite databases at rest with [SQLCipher](https://www.zetetic.net/sqlcipher/),
+//! enabled by the `sqlcipher` feature.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::Deserialize;
+#[cfg(feature = "sqlcipher")]
+use sqlx::Executor;
+
+use crate::{DbPool, Error};
+
+/// The key of an encrypted SQLite database.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EncryptionKey {
+    /// A passphrase the encryption key is derived from.
+    Passphrase(String),
+    /// A raw 256 bits key used as is, skipping the key derivation,
+    /// or 384 bits including the database salt.
+    Raw(Vec<u8>),
+}
+
+impl EncryptionKey {
+    /// The value of the `key` and `rekey` pragmas.
+    #[cfg(feature = "sqlcipher")]
+    pub(crate) fn pragma(&self) -> String {
+        match self {
+            Self::Passphrase(passphrase) => format!("'{}'", passphrase.replace('\'', "''")),
+            Self::Raw(key) => {
+                let hex: String = key.iter().map(|byte| format!("{byte:02X}")).collect();
+                format!("\"x'{hex}'\"")
+            }
+        }
+    }
+}
+
+impl DbPool {
+    /// Changes the key of the database on one of the connections,
+    /// the other ones must be reopened with the new key.
+    pub(crate) async fn rekey(&self, _db: &str, _key: &EncryptionKey) -> Result<(), Error> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "sqlcipher")]
+            DbPool::Sqlite(pool) => {
+                let statement = format!("PRAGMA rekey = {}", _key.pragma());
+                pool.execute(statement.as_str())
+                    .await
+                    .map_err(|e| map_key_error(_db, e))?;
+                Ok(())
+            }
+            _ => Err(Error::EncryptionUnsupported(_db.into())),
+        }
+    }
+}
+
+/// The keys of the databases, added to the plugin builder or changed with `rekey`.
+#[derive(Default)]
+pub(crate) struct EncryptionKeys(pub(crate) Mutex<HashMap<String, EncryptionKey>>);
+
+impl EncryptionKeys {
+    pub(crate) fn get(&self, db: &str) -> Option<EncryptionKey> {
+        self.0.lock().unwrap().get(db).cloned()
+    }
+}
+
+/// Maps the error of a statement on a database opened with a wrong key,
+/// SQLCipher only failing once the first page is read.
+#[cfg(feature = "sqlcipher")]
+pub(crate) fn map_key_error(db: &str, error: sqlx::Error) -> Error {
+    const SQLITE_NOTADB: &str = "26";
+
+    match &error {
+        sqlx::Error::Database(e) if e.code().as_deref() == Some(SQLITE_NOTADB) => {
+            Error::InvalidEncryptionKey(db.into())
+        }
+        _ => error.into(),
+    }
+}
@@ -2,23 +2,50 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use std::path::PathBuf;
+
 use indexmap::IndexMap;
 use serde_json::Value as JsonValue;
 use sqlx::migrate::Migrator;
-use tauri::{command, AppHandle, Runtime, State};
+use tauri::{command, ipc::Channel, AppHandle, Runtime, State};
+use tauri_plugin_fs::{FsExt, SafeFilePath};
 
-use crate::{DbInstances, DbPool, Error, LastInsertId, Migrations};
+use crate::{
+    backup::BackupProgress,
+    cipher::{EncryptionKey, EncryptionKeys},
+    notify::{DbListeners, ListenEvent, ListenerId},
+    pool::{DbPoolOptions, PoolOptions, PoolStatus},
+    prepared::PreparedStatements,
+    stream::{DbStreams, StreamEvent, StreamId, StreamOptions},
+    transaction::{DbTransactions, SavepointAction, TransactionId},
+    DbInstances, DbPool, Error, LastInsertId, MigrationStatus, Migrations, SqlExt,
+};
 
+#[allow(clippy::too_many_arguments)]
 #[command]
 pub(crate) async fn load<R: Runtime>(
     app: AppHandle<R>,
     db_instances: State<'_, DbInstances>,
     migrations: State<'_, Migrations>,
+    encryption_keys: State<'_, EncryptionKeys>,
+    pool_options: State<'_, DbPoolOptions>,
     db: String,
+    encryption_key: Option<EncryptionKey>,
+    pool: Option<PoolOptions>,
 ) -> Result<String, crate::Error> {
-    let pool = DbPool::connect(&db, &app).await?;
+    let key = encryption_key.or_else(|| encryption_keys.get(&db));
+    // kept for the connections reopened with a new key
+    if let Some(pool) = pool {
+        pool_options.0.lock().unwrap().insert(db.clone(), pool);
+    }
+    let pool = DbPool::connect(&db, &app, key.as_ref(), &pool_options.get(&db)).await?;
+    // kept for the backups, encrypted with the same key
+    if let Some(key) = key {
+        encryption_keys.0.lock().unwrap().insert(db.clone(), key);
+    }
 
-    if let Some(migrations) = migrations.0.lock().await.remove(&db) {
+    let migrations = migrations.0.lock().await.get(&db).cloned();
+    if let Some(migrations) = migrations {
         let migrator = Migrator::new(migrations).await?;
         pool.migrate(&migrator).await?;
     }
@@ -34,8 +61,16 @@ pub(crate) async fn load<R: Runtime>(
 #[command]
 pub(crate) async fn close(
     db_instances: State<'_, DbInstances>,
+    transactions: State<'_, DbTransactions>,
+    streams: State<'_, DbStreams>,
+    listeners: State<'_, DbListeners>,
     db: Option<String>,
 ) -> Result<bool, crate::Error> {
+    // the pools wait for the connections held by the transactions and streams to be released
+    streams.cancel_all(db.as_deref());
+    listeners.unlisten_all(db.as_deref());
+    transactions.rollback_all(db.as_deref()).await;
+
     let instances = db_instances.0.read().await;
 
     let pools = if let Some(db) = db {
@@ -52,6 +87,144 @@ pub(crate) async fn close(
     Ok(true)
 }
 
+/// Listens to the channels of a Postgres database, forwarding the notifications through the channel
+/// until [`unlisten`] is called or the webview is reloaded.
+#[command]
+pub(crate) async fn listen(
+    db_instances: State<'_, DbInstances>,
+    listeners: State<'_, DbListeners>,
+    db: String,
+    channels: Vec<String>,
+    on_event: Channel<ListenEvent>,
+) -> Result<ListenerId, crate::Error> {
+    let instances = db_instances.0.read().await;
+
+    let pool = instances
+        .get(&db)
+        .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+    listeners.listen(db, pool, channels, on_event).await
+}
+
+#[command]
+pub(crate) fn unlisten(listeners: State<'_, DbListeners>, listener: ListenerId) {
+    listeners.unlisten(listener)
+}
+
+/// Lists the defined and applied migrations of the database.
+#[command]
+pub(crate) async fn get_migration_status<R: Runtime>(
+    app: AppHandle<R>,
+    db: String,
+) -> Result<Vec<MigrationStatus>, crate::Error> {
+    app.sql().migration_status(&db).await
+}
+
+/// Reports the open and idle connections of the pool of the database.
+#[command]
+pub(crate) async fn pool_status(
+    db_instances: State<'_, DbInstances>,
+    db: String,
+) -> Result<PoolStatus, crate::Error> {
+    let instances = db_instances.0.read().await;
+
+    let db = instances.get(&db).ok_or(Error::DatabaseNotLoaded(db))?;
+    Ok(db.status())
+}
+
+/// Changes the key of an encrypted SQLite database, reopening its connections with the new key.
+#[allow(clippy::too_many_arguments)]
+#[command]
+pub(crate) async fn rekey<R: Runtime>(
+    app: AppHandle<R>,
+    db_instances: State<'_, DbInstances>,
+    transactions: State<'_, DbTransactions>,
+    streams: State<'_, DbStreams>,
+    encryption_keys: State<'_, EncryptionKeys>,
+    pool_options: State<'_, DbPoolOptions>,
+    db: String,
+    key: EncryptionKey,
+) -> Result<(), crate::Error> {
+    let mut instances = db_instances.0.write().await;
+
+    let pool = instances
+        .get(&db)
+        .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+    // the pool waits for the connections held by the transactions and streams to be released
+    streams.cancel_all(Some(&db));
+    transactions.rollback_all(Some(&db)).await;
+
+    pool.rekey(&db, &key).await?;
+    // the other connections still use the previous key
+    pool.close().await;
+    let pool = DbPool::connect(&db, &app, Some(&key), &pool_options.get(&db)).await?;
+    instances.insert(db.clone(), pool);
+    encryption_keys.0.lock().unwrap().insert(db, key);
+
+    Ok(())
+}
+
+/// Resolves the path and ensures it is allowed on the scope of the fs plugin.
+fn check_fs_scope<R: Runtime>(app: &AppHandle<R>, path: SafeFilePath) -> Result<PathBuf, Error> {
+    let path = path.into_path()?;
+    if app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path))
+    {
+        Ok(path)
+    } else {
+        Err(Error::PathNotAllowed(path))
+    }
+}
+
+/// Copies a SQLite database to a file with the online backup API, reporting the progress after each step.
+#[command]
+pub(crate) async fn backup<R: Runtime>(
+    app: AppHandle<R>,
+    db: String,
+    dest_path: SafeFilePath,
+    on_progress: Channel<BackupProgress>,
+) -> Result<(), crate::Error> {
+    let dest_path = check_fs_scope(&app, dest_path)?;
+    app.sql()
+        .backup(&db, dest_path, |progress| {
+            let _ = on_progress.send(progress);
+        })
+        .await
+}
+
+/// Writes a vacuumed copy of a SQLite database to a file, which must not exist.
+#[command]
+pub(crate) async fn vacuum_into<R: Runtime>(
+    app: AppHandle<R>,
+    db: String,
+    dest_path: SafeFilePath,
+) -> Result<(), crate::Error> {
+    let dest_path = check_fs_scope(&app, dest_path)?;
+    app.sql().vacuum_into(&db, dest_path).await
+}
+
+/// Attaches a SQLite database file to a SQLite database under an alias, reopening its connections.
+#[command]
+pub(crate) async fn attach<R: Runtime>(
+    app: AppHandle<R>,
+    db: String,
+    path: SafeFilePath,
+    alias: String,
+) -> Result<(), crate::Error> {
+    let path = check_fs_scope(&app, path)?;
+    app.sql().attach(&db, path, &alias).await
+}
+
+/// Detaches an attached database, returning whether the alias was attached.
+#[command]
+pub(crate) async fn detach<R: Runtime>(
+    app: AppHandle<R>,
+    db: String,
+    alias: String,
+) -> Result<bool, crate::Error> {
+    app.sql().detach(&db, &alias).await
+}
+
 /// Execute a command against the database
 #[command]
 pub(crate) async fn execute(
@@ -78,3 +251,216 @@ pub(crate) async fn select(
     let db = instances.get(&db).ok_or(Error::DatabaseNotLoaded(db))?;
     db.select(query, values).await
 }
+
+/// Prepares a statement by name, failing if it's invalid.
+/// Preparing a statement again with the same name replaces it.
+#[command]
+pub(crate) async fn prepare(
+    db_instances: State<'_, DbInstances>,
+    statements: State<'_, PreparedStatements>,
+    db: String,
+    name: String,
+    query: String,
+) -> Result<(), crate::Error> {
+    let instances = db_instances.0.read().await;
+
+    let pool = instances
+        .get(&db)
+        .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+    pool.prepare(&query).await?;
+    statements.insert(db, name, query);
+    Ok(())
+}
+
+/// Removes a prepared statement, returning whether it existed.
+#[command]
+pub(crate) fn unprepare(
+    statements: State<'_, PreparedStatements>,
+    db: String,
+    name: String,
+) -> bool {
+    statements.remove(&db, &name)
+}
+
+#[command]
+pub(crate) async fn execute_prepared(
+    db_instances: State<'_, DbInstances>,
+    statements: State<'_, PreparedStatements>,
+    db: String,
+    name: String,
+    values: Vec<JsonValue>,
+) -> Result<(u64, LastInsertId), crate::Error> {
+    let query = statements.get(&db, &name)?;
+    let instances = db_instances.0.read().await;
+
+    let db = instances.get(&db).ok_or(Error::DatabaseNotLoaded(db))?;
+    db.execute(query, values).await
+}
+
+#[command]
+pub(crate) async fn select_prepared(
+    db_instances: State<'_, DbInstances>,
+    statements: State<'_, PreparedStatements>,
+    db: String,
+    name: String,
+    values: Vec<JsonValue>,
+) -> Result<Vec<IndexMap<String, JsonValue>>, crate::Error> {
+    let query = statements.get(&db, &name)?;
+    let instances = db_instances.0.read().await;
+
+    let db = instances.get(&db).ok_or(Error::DatabaseNotLoaded(db))?;
+    db.select(query, values).await
+}
+
+/// Runs a SELECT query in the background, sending the rows in batches through the channel.
+/// A batch is only sent once one of the pending ones is acknowledged with [`select_stream_ack`].
+#[allow(clippy::too_many_arguments)]
+#[command]
+pub(crate) async fn select_stream(
+    db_instances: State<'_, DbInstances>,
+    streams: State<'_, DbStreams>,
+    db: String,
+    query: String,
+    values: Vec<JsonValue>,
+    batch_size: Option<usize>,
+    max_pending_batches: Option<usize>,
+    on_event: Channel<StreamEvent>,
+) -> Result<StreamId, crate::Error> {
+    let instances = db_instances.0.read().await;
+
+    let pool = instances
+        .get(&db)
+        .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?
+        .clone();
+    Ok(streams.start(
+        db,
+        pool,
+        query,
+        values,
+        StreamOptions {
+            batch_size,
+            max_pending_batches,
+        },
+        on_event,
+    ))
+}
+
+#[command]
+pub(crate) fn select_stream_ack(streams: State<'_, DbStreams>, stream: StreamId) {
+    streams.ack(stream)
+}
+
+#[command]
+pub(crate) fn select_stream_cancel(streams: State<'_, DbStreams>, stream: StreamId) {
+    streams.cancel(stream)
+}
+
+/// Starts a transaction on one of the connections of the pool,
+/// which is used by all its statements until it's committed or rolled back.
+#[command]
+pub(crate) async fn begin(
+    db_instances: State<'_, DbInstances>,
+    transactions: State<'_, DbTransactions>,
+    db: String,
+) -> Result<TransactionId, crate::Error> {
+    let instances = db_instances.0.read().await;
+
+    let pool = instances
+        .get(&db)
+        .ok_or_else(|| Error::DatabaseNotLoaded(db.clone()))?;
+    let transaction = pool.begin().await?;
+    Ok(transactions.insert(db, transaction))
+}
+
+#[command]
+pub(crate) async fn transaction_execute(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+    query: String,
+    values: Vec<JsonValue>,
+) -> Result<(u64, LastInsertId), crate::Error> {
+    let shared = transactions.get(transaction)?;
+    let mut lock = shared.lock().await;
+    let tx = lock
+        .as_mut()
+        .ok_or(Error::TransactionNotFound(transaction))?;
+    tx.execute(query, values).await
+}
+
+#[command]
+pub(crate) async fn transaction_select(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+    query: String,
+    values: Vec<JsonValue>,
+) -> Result<Vec<IndexMap<String, JsonValue>>, crate::Error> {
+    let shared = transactions.get(transaction)?;
+    let mut lock = shared.lock().await;
+    let tx = lock
+        .as_mut()
+        .ok_or(Error::TransactionNotFound(transaction))?;
+    tx.select(query, values).await
+}
+
+#[command]
+pub(crate) async fn commit(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+) -> Result<(), crate::Error> {
+    transactions.take(transaction).await?.commit().await
+}
+
+#[command]
+pub(crate) async fn rollback(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+) -> Result<(), crate::Error> {
+    transactions.take(transaction).await?.rollback().await
+}
+
+async fn run_savepoint(
+    transactions: &DbTransactions,
+    transaction: TransactionId,
+    action: SavepointAction,
+    name: &str,
+) -> Result<(), crate::Error> {
+    let shared = transactions.get(transaction)?;
+    let mut lock = shared.lock().await;
+    let tx = lock
+        .as_mut()
+        .ok_or(Error::TransactionNotFound(transaction))?;
+    tx.savepoint(action, name).await
+}
+
+#[command]
+pub(crate) async fn savepoint(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+    name: String,
+) -> Result<(), crate::Error> {
+    run_savepoint(&transactions, transaction, SavepointAction::Create, &name).await
+}
+
+#[command]
+pub(crate) async fn release_savepoint(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+    name: String,
+) -> Result<(), crate::Error> {
+    run_savepoint(&transactions, transaction, SavepointAction::Release, &name).await
+}
+
+#[command]
+pub(crate) async fn rollback_to_savepoint(
+    transactions: State<'_, DbTransactions>,
+    transaction: TransactionId,
+    name: String,
+) -> Result<(), crate::Error> {
+    run_savepoint(
+        &transactions,
+        transaction,
+        SavepointAction::RollbackTo,
+        &name,
+    )
+    .await
+}
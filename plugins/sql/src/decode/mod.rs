@@ -8,3 +8,21 @@ pub(crate) mod mysql;
 pub(crate) mod postgres;
 #[cfg(feature = "sqlite")]
 pub(crate) mod sqlite;
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use serde_json::{json, Value as JsonValue};
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+/// A BLOB, tagged so the JS API turns it into a `Uint8Array`.
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+pub(crate) fn blob(bytes: Vec<u8>) -> JsonValue {
+    json!({ "$type": "blob", "value": bytes })
+}
+
+/// A timestamp with its offset, formatted as RFC 3339.
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+pub(crate) fn timestamp(v: OffsetDateTime) -> JsonValue {
+    // years outside 0-9999 can't be formatted as RFC 3339
+    JsonValue::String(v.format(&Rfc3339).unwrap_or_else(|_| v.to_string()))
+}
@@ -3,7 +3,11 @@
 // SPDX-License-Identifier: MIT
 
 use serde_json::Value as JsonValue;
-use sqlx::{postgres::PgValueRef, TypeInfo, Value, ValueRef};
+use sqlx::{
+    postgres::{PgValueRef, Postgres},
+    types::{BigDecimal, Uuid},
+    Decode, Type, TypeInfo, Value, ValueRef,
+};
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 
 use crate::Error;
@@ -14,13 +18,28 @@ pub(crate) fn to_json(v: PgValueRef) -> Result<JsonValue, Error> {
     }
 
     let res = match v.type_info().name() {
-        "CHAR" | "VARCHAR" | "TEXT" | "NAME" | "UUID" => {
+        "CHAR" | "VARCHAR" | "TEXT" | "NAME" => {
             if let Ok(v) = ValueRef::to_owned(&v).try_decode() {
                 JsonValue::String(v)
             } else {
                 JsonValue::Null
             }
         }
+        "UUID" => {
+            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<Uuid>() {
+                JsonValue::String(v.hyphenated().to_string())
+            } else {
+                JsonValue::Null
+            }
+        }
+        // as a string, a float can't hold all the digits
+        "NUMERIC" => {
+            if let Ok(v) = ValueRef::to_owned(&v).try_decode::<BigDecimal>() {
+                JsonValue::String(v.to_string())
+            } else {
+                JsonValue::Null
+            }
+        }
         "FLOAT4" => {
             if let Ok(v) = ValueRef::to_owned(&v).try_decode::<f32>() {
                 JsonValue::from(v)
@@ -86,7 +105,7 @@ pub(crate) fn to_json(v: PgValueRef) -> Result<JsonValue, Error> {
         }
         "TIMESTAMPTZ" => {
             if let Ok(v) = ValueRef::to_owned(&v).try_decode::<OffsetDateTime>() {
-                JsonValue::String(v.to_string())
+                super::timestamp(v)
             } else {
                 JsonValue::Null
             }
@@ -94,14 +113,42 @@ pub(crate) fn to_json(v: PgValueRef) -> Result<JsonValue, Error> {
         "JSON" | "JSONB" => ValueRef::to_owned(&v).try_decode().unwrap_or_default(),
         "BYTEA" => {
             if let Ok(v) = ValueRef::to_owned(&v).try_decode::<Vec<u8>>() {
-                JsonValue::Array(v.into_iter().map(|n| JsonValue::Number(n.into())).collect())
+                super::blob(v)
             } else {
                 JsonValue::Null
             }
         }
+        "CHAR[]" | "VARCHAR[]" | "TEXT[]" | "NAME[]" => array(&v, JsonValue::String),
+        "FLOAT4[]" => array(&v, |v: f32| JsonValue::from(v)),
+        "FLOAT8[]" => array(&v, |v: f64| JsonValue::from(v)),
+        "INT2[]" => array(&v, |v: i16| JsonValue::from(v)),
+        "INT4[]" => array(&v, |v: i32| JsonValue::from(v)),
+        "INT8[]" => array(&v, |v: i64| JsonValue::from(v)),
+        "BOOL[]" => array(&v, JsonValue::Bool),
+        "NUMERIC[]" => array(&v, |v: BigDecimal| JsonValue::String(v.to_string())),
+        "UUID[]" => array(&v, |v: Uuid| JsonValue::String(v.hyphenated().to_string())),
+        "TIMESTAMPTZ[]" => array(&v, super::timestamp),
+        "JSON[]" | "JSONB[]" => array(&v, |v: JsonValue| v),
+        "BYTEA[]" => array(&v, super::blob),
         "VOID" => JsonValue::Null,
         _ => return Err(Error::UnsupportedDatatype(v.type_info().name().to_string())),
     };
 
     Ok(res)
 }
+
+/// Decodes an array, its elements can be `NULL`.
+fn array<T>(v: &PgValueRef, to_json: impl Fn(T) -> JsonValue) -> JsonValue
+where
+    Vec<Option<T>>: for<'r> Decode<'r, Postgres> + Type<Postgres>,
+{
+    if let Ok(v) = ValueRef::to_owned(v).try_decode::<Vec<Option<T>>>() {
+        JsonValue::Array(
+            v.into_iter()
+                .map(|v| v.map(&to_json).unwrap_or(JsonValue::Null))
+                .collect(),
+        )
+    } else {
+        JsonValue::Null
+    }
+}
@@ -28,13 +28,23 @@ pub(crate) fn to_json(v: SqliteValueRef) -> Result<JsonValue, Error> {
                 JsonValue::Null
             }
         }
-        "INTEGER" | "NUMERIC" => {
+        "INTEGER" => {
             if let Ok(v) = v.to_owned().try_decode::<i64>() {
                 JsonValue::Number(v.into())
             } else {
                 JsonValue::Null
             }
         }
+        // stored as an integer when it has no fractional part, as a float otherwise
+        "NUMERIC" => {
+            if let Ok(v) = v.to_owned().try_decode::<i64>() {
+                JsonValue::Number(v.into())
+            } else if let Ok(v) = v.to_owned().try_decode::<f64>() {
+                JsonValue::from(v)
+            } else {
+                JsonValue::Null
+            }
+        }
         "BOOLEAN" => {
             if let Ok(v) = v.to_owned().try_decode() {
                 JsonValue::Bool(v)
@@ -65,7 +75,7 @@ pub(crate) fn to_json(v: SqliteValueRef) -> Result<JsonValue, Error> {
         }
         "BLOB" => {
             if let Ok(v) = v.to_owned().try_decode::<Vec<u8>>() {
-                JsonValue::Array(v.into_iter().map(|n| JsonValue::Number(n.into())).collect())
+                super::blob(v)
             } else {
                 JsonValue::Null
             }
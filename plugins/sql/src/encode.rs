@@ -0,0 +1,162 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Values bound with an explicit type, for the types JSON can't represent losslessly.
+//! They're sent as `{ "$type": "<type>", "value": <value> }`.
+
+use std::str::FromStr;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use sqlx::{
+    query::Query,
+    types::{BigDecimal, Uuid},
+    Database,
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::Error;
+
+#[cfg(feature = "mysql")]
+use sqlx::MySql;
+#[cfg(feature = "postgres")]
+use sqlx::Postgres;
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
+
+/// A value bound with an explicit type.
+pub(crate) enum TypedValue {
+    Blob(Vec<u8>),
+    Decimal(BigDecimal),
+    Uuid(Uuid),
+    Timestamptz(OffsetDateTime),
+    /// A Postgres array.
+    Array(ArrayValue),
+}
+
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub(crate) enum ArrayValue {
+    Int8(Vec<Option<i64>>),
+    Float8(Vec<Option<f64>>),
+    Numeric(Vec<Option<BigDecimal>>),
+    Text(Vec<Option<String>>),
+    Bool(Vec<Option<bool>>),
+    Uuid(Vec<Option<Uuid>>),
+    Jsonb(Vec<JsonValue>),
+}
+
+impl TypedValue {
+    /// Parses a typed value, `None` if it's a plain JSON value.
+    pub(crate) fn parse(value: &JsonValue) -> Result<Option<Self>, Error> {
+        let Some(object) = value.as_object() else {
+            return Ok(None);
+        };
+        let (Some(ty), Some(value), 2) = (
+            object.get("$type").and_then(JsonValue::as_str),
+            object.get("value"),
+            object.len(),
+        ) else {
+            return Ok(None);
+        };
+
+        let invalid = || Error::InvalidValue(ty.to_string());
+        let decode = |value: &JsonValue| -> Result<_, Error> { deserialize(value, ty) };
+        let decimal = |value: &str| BigDecimal::from_str(value).map_err(|_| invalid());
+        let uuid = |value: &str| Uuid::parse_str(value).map_err(|_| invalid());
+
+        Ok(Some(match ty {
+            "blob" => Self::Blob(decode(value)?),
+            "decimal" => Self::Decimal(decimal(&decode::<String>(value)?)?),
+            "uuid" => Self::Uuid(uuid(&decode::<String>(value)?)?),
+            "timestamptz" => Self::Timestamptz(
+                OffsetDateTime::parse(&decode::<String>(value)?, &Rfc3339)
+                    .map_err(|_| invalid())?,
+            ),
+            "int8[]" => Self::Array(ArrayValue::Int8(decode(value)?)),
+            "float8[]" => Self::Array(ArrayValue::Float8(decode(value)?)),
+            "numeric[]" => Self::Array(ArrayValue::Numeric(
+                decode::<Vec<Option<String>>>(value)?
+                    .iter()
+                    .map(|value| value.as_deref().map(decimal).transpose())
+                    .collect::<Result<_, _>>()?,
+            )),
+            "text[]" => Self::Array(ArrayValue::Text(decode(value)?)),
+            "bool[]" => Self::Array(ArrayValue::Bool(decode(value)?)),
+            "uuid[]" => Self::Array(ArrayValue::Uuid(
+                decode::<Vec<Option<String>>>(value)?
+                    .iter()
+                    .map(|value| value.as_deref().map(uuid).transpose())
+                    .collect::<Result<_, _>>()?,
+            )),
+            "jsonb[]" => Self::Array(ArrayValue::Jsonb(decode(value)?)),
+            _ => return Err(Error::UnsupportedDatatype(ty.to_string())),
+        }))
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: &JsonValue, ty: &str) -> Result<T, Error> {
+    T::deserialize(value).map_err(|_| Error::InvalidValue(ty.to_string()))
+}
+
+/// Binding of the typed values, the types without an equivalent are bound as text.
+pub(crate) trait BindTyped: Database {
+    fn bind_typed<'q>(
+        query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+        value: TypedValue,
+    ) -> Result<Query<'q, Self, <Self as Database>::Arguments<'q>>, Error>;
+}
+
+#[cfg(feature = "sqlite")]
+impl BindTyped for Sqlite {
+    fn bind_typed<'q>(
+        query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+        value: TypedValue,
+    ) -> Result<Query<'q, Self, <Self as Database>::Arguments<'q>>, Error> {
+        Ok(match value {
+            TypedValue::Blob(bytes) => query.bind(bytes),
+            TypedValue::Decimal(decimal) => query.bind(decimal.to_string()),
+            TypedValue::Uuid(uuid) => query.bind(uuid.hyphenated().to_string()),
+            TypedValue::Timestamptz(timestamp) => query.bind(timestamp),
+            TypedValue::Array(_) => return Err(Error::UnsupportedDatatype("array".into())),
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl BindTyped for MySql {
+    fn bind_typed<'q>(
+        query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+        value: TypedValue,
+    ) -> Result<Query<'q, Self, <Self as Database>::Arguments<'q>>, Error> {
+        Ok(match value {
+            TypedValue::Blob(bytes) => query.bind(bytes),
+            TypedValue::Decimal(decimal) => query.bind(decimal),
+            TypedValue::Uuid(uuid) => query.bind(uuid.hyphenated().to_string()),
+            TypedValue::Timestamptz(timestamp) => query.bind(timestamp),
+            TypedValue::Array(_) => return Err(Error::UnsupportedDatatype("array".into())),
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl BindTyped for Postgres {
+    fn bind_typed<'q>(
+        query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+        value: TypedValue,
+    ) -> Result<Query<'q, Self, <Self as Database>::Arguments<'q>>, Error> {
+        Ok(match value {
+            TypedValue::Blob(bytes) => query.bind(bytes),
+            TypedValue::Decimal(decimal) => query.bind(decimal),
+            TypedValue::Uuid(uuid) => query.bind(uuid),
+            TypedValue::Timestamptz(timestamp) => query.bind(timestamp),
+            TypedValue::Array(ArrayValue::Int8(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Float8(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Numeric(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Text(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Bool(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Uuid(values)) => query.bind(values),
+            TypedValue::Array(ArrayValue::Jsonb(values)) => query.bind(values),
+        })
+    }
+}
@@ -14,8 +14,42 @@ pub enum Error {
     InvalidDbUrl(String),
     #[error("database {0} not loaded")]
     DatabaseNotLoaded(String),
+    #[error("migration {0} can't be reverted, it has no down migration")]
+    IrreversibleMigration(i64),
     #[error("unsupported datatype: {0}")]
     UnsupportedDatatype(String),
+    #[error("invalid value of type {0}")]
+    InvalidValue(String),
+    #[error("transaction {0} not found, it was committed or rolled back")]
+    TransactionNotFound(u32),
+    #[error("invalid savepoint name: {0}")]
+    InvalidSavepointName(String),
+    #[error("stream {0} not found, it ended or was cancelled")]
+    StreamNotFound(u32),
+    #[error("wrong encryption key for database {0}")]
+    InvalidEncryptionKey(String),
+    #[error("encryption of database {0} is not supported, it requires a sqlite database and the `sqlcipher` feature")]
+    EncryptionUnsupported(String),
+    #[error("prepared statement {0} not found")]
+    StatementNotFound(String),
+    #[error("database {0} doesn't support LISTEN, it requires a postgres database")]
+    ListenUnsupported(String),
+    #[error("database {0} doesn't support backups, it requires a sqlite database")]
+    BackupUnsupported(String),
+    #[error("backup failed: {0}")]
+    Backup(String),
+    #[error("database {0} doesn't support ATTACH, it requires a sqlite database")]
+    AttachUnsupported(String),
+    #[error("invalid database alias: {0}")]
+    InvalidAlias(String),
+    #[error("database alias {0} already in use")]
+    AliasInUse(String),
+    #[error("path not allowed on the configured fs scope: {0}")]
+    PathNotAllowed(std::path::PathBuf),
+    #[error(transparent)]
+    Fs(#[from] tauri_plugin_fs::Error),
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
 }
 
 impl Serialize for Error {
@@ -0,0 +1,187 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use sqlx::migrate::Migrate;
+use sqlx::migrate::{AppliedMigration, Migrator};
+use tauri::{Manager, Runtime};
+
+use crate::{DbInstances, DbPool, Error, MigrationList, Migrations, Sql};
+
+/// The state of a migration of a database, see [`Sql::migration_status`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationStatus {
+    pub version: i64,
+    /// `None` if the migration was applied but isn't defined anymore, e.g. by a newer version of the app.
+    pub description: Option<String>,
+    pub applied: bool,
+    /// Whether the migration has a down migration, so it can be reverted.
+    pub reversible: bool,
+    /// SHA-384 checksum of the defined migration, hex encoded.
+    pub checksum: Option<String>,
+    /// SHA-384 checksum of the migration when it was applied, which differs from
+    /// [`Self::checksum`] if the migration was modified since.
+    pub applied_checksum: Option<String>,
+}
+
+fn hex(checksum: &[u8]) -> String {
+    checksum.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+impl DbPool {
+    /// Lists the applied migrations, creating the migrations table if needed.
+    pub(crate) async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, Error> {
+        Ok(match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                let mut conn = pool.acquire().await?;
+                conn.ensure_migrations_table().await?;
+                conn.list_applied_migrations().await?
+            }
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => {
+                let mut conn = pool.acquire().await?;
+                conn.ensure_migrations_table().await?;
+                conn.list_applied_migrations().await?
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                let mut conn = pool.acquire().await?;
+                conn.ensure_migrations_table().await?;
+                conn.list_applied_migrations().await?
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => Vec::new(),
+        })
+    }
+
+    /// Reverts the applied migrations above the target version.
+    pub(crate) async fn undo(&self, _migrator: &Migrator, _target: i64) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => _migrator.undo(pool, _target).await?,
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => _migrator.undo(pool, _target).await?,
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => _migrator.undo(pool, _target).await?,
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => (),
+        }
+        Ok(())
+    }
+}
+
+impl<R: Runtime> Sql<R> {
+    async fn migrations(&self, db: &str) -> MigrationList {
+        let migrations = self.app.state::<Migrations>();
+        let migrations = migrations.0.lock().await;
+        migrations.get(db).cloned().unwrap_or_default()
+    }
+
+    /// Applies or reverts the migrations of a loaded database so its schema matches the version,
+    /// the migrations to revert must have a down migration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use tauri_plugin_sql::SqlExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .setup(|app| {
+    ///     let handle = app.handle().clone();
+    ///     tauri::async_runtime::spawn(async move {
+    ///       handle.sql().migrate_to("sqlite:app.db", 2).await
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub async fn migrate_to(&self, db: &str, version: i64) -> Result<(), Error> {
+        let definitions = self.migrations(db).await;
+        let migrator = Migrator::new(definitions.clone()).await?;
+
+        let instances = self.app.state::<DbInstances>();
+        let instances = instances.0.read().await;
+        let pool = instances
+            .get(db)
+            .ok_or_else(|| Error::DatabaseNotLoaded(db.into()))?;
+
+        // sqlx skips the applied migrations without a down migration instead of failing
+        let applied = pool.applied_migrations().await?;
+        if let Some(migration) = applied.iter().find(|applied| {
+            applied.version > version
+                && !migrator.iter().any(|migration| {
+                    migration.version == applied.version
+                        && migration.migration_type.is_down_migration()
+                })
+        }) {
+            return Err(Error::IrreversibleMigration(migration.version));
+        }
+        pool.undo(&migrator, version).await?;
+
+        let mut migrator = Migrator::new(MigrationList(
+            definitions
+                .0
+                .into_iter()
+                .filter(|migration| migration.version <= version)
+                .collect(),
+        ))
+        .await?;
+        // the migrations above the version were reverted but may still be applied
+        // if they're not defined anymore
+        migrator.set_ignore_missing(true);
+        pool.migrate(&migrator).await
+    }
+
+    /// Lists the defined and applied migrations of a loaded database, sorted by version.
+    pub async fn migration_status(&self, db: &str) -> Result<Vec<MigrationStatus>, Error> {
+        let migrator = Migrator::new(self.migrations(db).await).await?;
+
+        let instances = self.app.state::<DbInstances>();
+        let instances = instances.0.read().await;
+        let pool = instances
+            .get(db)
+            .ok_or_else(|| Error::DatabaseNotLoaded(db.into()))?;
+        let applied = pool.applied_migrations().await?;
+
+        let mut statuses: Vec<MigrationStatus> = migrator
+            .iter()
+            .filter(|migration| migration.migration_type.is_up_migration())
+            .map(|migration| {
+                let applied = applied
+                    .iter()
+                    .find(|applied| applied.version == migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    description: Some(migration.description.to_string()),
+                    applied: applied.is_some(),
+                    reversible: migrator.iter().any(|down| {
+                        down.version == migration.version && down.migration_type.is_down_migration()
+                    }),
+                    checksum: Some(hex(&migration.checksum)),
+                    applied_checksum: applied.map(|applied| hex(&applied.checksum)),
+                }
+            })
+            .collect();
+        for applied in applied {
+            if !statuses
+                .iter()
+                .any(|status| status.version == applied.version)
+            {
+                statuses.push(MigrationStatus {
+                    version: applied.version,
+                    description: None,
+                    applied: true,
+                    reversible: false,
+                    checksum: None,
+                    applied_checksum: Some(hex(&applied.checksum)),
+                });
+            }
+        }
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+}
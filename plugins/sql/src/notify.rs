@@ -0,0 +1,164 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Forwarding of the Postgres notifications sent with `NOTIFY` to the webview.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use futures_core::future::BoxFuture;
+use serde::Serialize;
+use tauri::{
+    async_runtime::{spawn, JoinHandle},
+    ipc::Channel,
+};
+
+#[cfg(feature = "postgres")]
+use std::time::Duration;
+
+#[cfg(feature = "postgres")]
+use sqlx::postgres::PgListener;
+
+use crate::{DbPool, Error};
+
+pub(crate) type ListenerId = u32;
+
+/// Maximum delay before reconnecting a listener after a failure.
+#[cfg(feature = "postgres")]
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// A event of a listener, sent through its channel.
+#[derive(Serialize, Clone)]
+#[serde(tag = "event", content = "payload")]
+pub(crate) enum ListenEvent {
+    #[serde(rename_all = "camelCase")]
+    Notification {
+        channel: String,
+        payload: String,
+        process_id: u32,
+    },
+    /// The connection was lost, the notifications sent until it's reopened are missed.
+    ConnectionLost,
+    /// Reconnecting failed, it's retried after a delay.
+    Error(String),
+}
+
+/// Receives the notifications, the listener reconnecting and listening to its channels again
+/// when the connection is lost.
+#[cfg(feature = "postgres")]
+async fn forward(mut listener: PgListener, channel: Channel<ListenEvent>) {
+    let mut failures = 0;
+    loop {
+        let event = match listener.try_recv().await {
+            Ok(Some(notification)) => {
+                failures = 0;
+                ListenEvent::Notification {
+                    channel: notification.channel().into(),
+                    payload: notification.payload().into(),
+                    process_id: notification.process_id(),
+                }
+            }
+            // reconnected on the next call
+            Ok(None) => ListenEvent::ConnectionLost,
+            Err(e) => {
+                failures += 1;
+                if channel.send(ListenEvent::Error(e.to_string())).is_err() {
+                    break;
+                }
+                let delay = Duration::from_secs(1 << failures.min(5));
+                tokio::time::sleep(delay.min(MAX_RETRY_DELAY)).await;
+                continue;
+            }
+        };
+        // the webview was reloaded
+        if channel.send(event).is_err() {
+            break;
+        }
+    }
+}
+
+impl DbPool {
+    /// Listens to the channels, returning the future forwarding the notifications.
+    async fn listen(
+        &self,
+        _db: &str,
+        _channels: &[String],
+        _channel: Channel<ListenEvent>,
+    ) -> Result<BoxFuture<'static, ()>, Error> {
+        #[allow(unreachable_patterns)]
+        match self {
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                // a dedicated connection, opened with the options of the pool
+                let mut listener = PgListener::connect_with(pool).await?;
+                listener
+                    .listen_all(_channels.iter().map(String::as_str))
+                    .await?;
+                Ok(Box::pin(forward(listener, _channel)))
+            }
+            _ => Err(Error::ListenUnsupported(_db.into())),
+        }
+    }
+}
+
+struct OpenListener {
+    db: String,
+    task: JoinHandle<()>,
+}
+
+/// The listeners started from the webview, until they're stopped or the webview is reloaded.
+#[derive(Default)]
+pub(crate) struct DbListeners {
+    next_id: AtomicU32,
+    listeners: Arc<Mutex<HashMap<ListenerId, OpenListener>>>,
+}
+
+impl DbListeners {
+    /// Listens to the channels of a Postgres database.
+    pub(crate) async fn listen(
+        &self,
+        db: String,
+        pool: &DbPool,
+        channels: Vec<String>,
+        channel: Channel<ListenEvent>,
+    ) -> Result<ListenerId, Error> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let forward = pool.listen(&db, &channels, channel).await?;
+
+        let open_listeners = self.listeners.clone();
+        // locked until the listener is inserted, so the task can't remove it before
+        let mut listeners = self.listeners.lock().unwrap();
+        let task = spawn(async move {
+            forward.await;
+            // the webview is gone
+            open_listeners.lock().unwrap().remove(&id);
+        });
+        listeners.insert(id, OpenListener { db, task });
+
+        Ok(id)
+    }
+
+    /// Stops the listener and closes its connection, ignored if it already stopped.
+    pub(crate) fn unlisten(&self, id: ListenerId) {
+        if let Some(listener) = self.listeners.lock().unwrap().remove(&id) {
+            listener.task.abort();
+        }
+    }
+
+    /// Stops the listeners of the database, or of all the databases if `None`.
+    pub(crate) fn unlisten_all(&self, db: Option<&str>) {
+        self.listeners.lock().unwrap().retain(|_, listener| {
+            let stop = db.is_none() || db == Some(listener.db.as_str());
+            if stop {
+                listener.task.abort();
+            }
+            !stop
+        });
+    }
+}
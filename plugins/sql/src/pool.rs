@@ -0,0 +1,192 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{collections::HashMap, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use std::time::Duration;
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use sqlx::{pool::PoolOptions as SqlxPoolOptions, Database};
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use sqlx::{Connection, Pool};
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::DbPool;
+
+/// Interval of the health check of the MySQL and Postgres pools if not specified.
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+const DEFAULT_HEALTH_CHECK_INTERVAL_MS: u64 = 30_000;
+
+/// Options of the connection pool of a database, the sqlx defaults are used for the ones not set.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolOptions {
+    /// Maximum number of connections.
+    pub max_connections: Option<u32>,
+    /// Number of connections kept open even when they're idle.
+    pub min_connections: Option<u32>,
+    /// Time to wait for a connection before failing, in milliseconds.
+    pub acquire_timeout_ms: Option<u64>,
+    /// Time after which an idle connection is closed, in milliseconds.
+    pub idle_timeout_ms: Option<u64>,
+    /// Time after which a connection is closed once it's released, in milliseconds.
+    pub max_lifetime_ms: Option<u64>,
+    /// Whether a connection is pinged before it's acquired, so broken connections are replaced.
+    pub test_before_acquire: Option<bool>,
+    /// Number of prepared statements cached by each connection, 100 by default.
+    pub statement_cache_capacity: Option<usize>,
+    /// Interval of the health check of MySQL and Postgres servers, in milliseconds,
+    /// 30 seconds by default and disabled with `0`.
+    pub health_check_interval_ms: Option<u64>,
+}
+
+impl PoolOptions {
+    #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+    pub(crate) fn to_sqlx<DB: Database>(&self) -> SqlxPoolOptions<DB> {
+        let mut options = SqlxPoolOptions::<DB>::new();
+        if let Some(max_connections) = self.max_connections {
+            options = options.max_connections(max_connections);
+        }
+        if let Some(min_connections) = self.min_connections {
+            options = options.min_connections(min_connections);
+        }
+        if let Some(timeout) = self.acquire_timeout_ms {
+            options = options.acquire_timeout(Duration::from_millis(timeout));
+        }
+        if let Some(timeout) = self.idle_timeout_ms {
+            options = options.idle_timeout(Duration::from_millis(timeout));
+        }
+        if let Some(lifetime) = self.max_lifetime_ms {
+            options = options.max_lifetime(Duration::from_millis(lifetime));
+        }
+        if let Some(test) = self.test_before_acquire {
+            options = options.test_before_acquire(test);
+        }
+        options
+    }
+
+    /// Pings the server periodically until the pool is closed, emitting a `sql://connection` event
+    /// when it can't be reached and once the pool reconnected.
+    #[cfg(any(feature = "mysql", feature = "postgres"))]
+    pub(crate) fn spawn_health_check<R: Runtime, DB: Database>(
+        &self,
+        app: &AppHandle<R>,
+        db: &str,
+        pool: Pool<DB>,
+    ) {
+        let interval = self
+            .health_check_interval_ms
+            .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_MS);
+        if interval == 0 {
+            return;
+        }
+        let interval = Duration::from_millis(interval);
+        let app = app.clone();
+        let db = db.to_string();
+
+        tauri::async_runtime::spawn(async move {
+            let mut connected = true;
+            loop {
+                tokio::time::sleep(interval).await;
+                if pool.is_closed() {
+                    break;
+                }
+
+                // the pool opens new connections when acquiring, replacing the dropped ones
+                let result = match pool.acquire().await {
+                    Ok(mut conn) => conn.ping().await,
+                    Err(e) => Err(e),
+                };
+                let error = match result {
+                    Ok(()) if !connected => None,
+                    Err(e) if connected => Some(e.to_string()),
+                    _ => continue,
+                };
+                connected = error.is_none();
+                let _ = app.emit(
+                    "sql://connection",
+                    ConnectionEvent {
+                        db: &db,
+                        connected,
+                        error,
+                    },
+                );
+            }
+        });
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectionEvent<'a> {
+    db: &'a str,
+    connected: bool,
+    error: Option<String>,
+}
+
+/// The connections of the pool of a database.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStatus {
+    /// Number of open connections.
+    pub size: u32,
+    /// Number of open connections not in use.
+    pub idle: u32,
+    /// Number of connections in use.
+    pub active: u32,
+    pub max_connections: u32,
+    pub closed: bool,
+}
+
+impl DbPool {
+    pub(crate) fn status(&self) -> PoolStatus {
+        let (size, idle, max_connections, closed) = match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => (
+                pool.size(),
+                pool.num_idle() as u32,
+                pool.options().get_max_connections(),
+                pool.is_closed(),
+            ),
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => (
+                pool.size(),
+                pool.num_idle() as u32,
+                pool.options().get_max_connections(),
+                pool.is_closed(),
+            ),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => (
+                pool.size(),
+                pool.num_idle() as u32,
+                pool.options().get_max_connections(),
+                pool.is_closed(),
+            ),
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => (0, 0, 0, true),
+        };
+        PoolStatus {
+            size,
+            idle,
+            active: size.saturating_sub(idle),
+            max_connections,
+            closed,
+        }
+    }
+}
+
+/// The pool options of the databases, added to the plugin builder or passed when loading them.
+#[derive(Default)]
+pub(crate) struct DbPoolOptions(pub(crate) Mutex<HashMap<String, PoolOptions>>);
+
+impl DbPoolOptions {
+    pub(crate) fn get(&self, db: &str) -> PoolOptions {
+        self.0.lock().unwrap().get(db).cloned().unwrap_or_default()
+    }
+}
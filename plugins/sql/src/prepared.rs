@@ -0,0 +1,73 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Statements prepared by name, so hot queries are sent once and parsed once per connection.
+//!
+//! sqlx caches the prepared statements of each connection, up to
+//! [`PoolOptions::statement_cache_capacity`](crate::PoolOptions::statement_cache_capacity).
+
+use std::{collections::HashMap, sync::Mutex};
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use sqlx::Executor;
+
+use crate::{DbPool, Error};
+
+impl DbPool {
+    /// Prepares the statement on one of the connections, failing if it's invalid.
+    pub(crate) async fn prepare(&self, _query: &str) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                pool.prepare(_query).await?;
+            }
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => {
+                pool.prepare(_query).await?;
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                pool.prepare(_query).await?;
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => (),
+        }
+        Ok(())
+    }
+}
+
+/// The SQL of the prepared statements of each database, by name.
+#[derive(Default)]
+pub(crate) struct PreparedStatements(Mutex<HashMap<String, HashMap<String, String>>>);
+
+impl PreparedStatements {
+    pub(crate) fn insert(&self, db: String, name: String, query: String) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(db)
+            .or_default()
+            .insert(name, query);
+    }
+
+    pub(crate) fn get(&self, db: &str, name: &str) -> Result<String, Error> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(db)
+            .and_then(|statements| statements.get(name))
+            .cloned()
+            .ok_or_else(|| Error::StatementNotFound(name.into()))
+    }
+
+    /// Removes the statement, returning whether it was prepared.
+    pub(crate) fn remove(&self, db: &str, name: &str) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .get_mut(db)
+            .and_then(|statements| statements.remove(name))
+            .is_some()
+    }
+}
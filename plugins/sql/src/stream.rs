@@ -0,0 +1,240 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streaming of the rows of a query, sent to the webview in batches as it consumes them
+//! instead of being loaded in memory all at once.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use tauri::{
+    async_runtime::{spawn, JoinHandle},
+    ipc::Channel,
+};
+use tokio::sync::Semaphore;
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use std::{future::poll_fn, pin::Pin};
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use futures_core::{stream::BoxStream, Stream};
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use sqlx::{Database, Row};
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use crate::wrapper::{bind_values, rows_to_json};
+use crate::{DbPool, Error};
+
+pub(crate) type StreamId = u32;
+
+/// Number of rows per batch if not specified.
+const DEFAULT_BATCH_SIZE: usize = 100;
+/// Number of batches sent but not acknowledged yet if not specified.
+const DEFAULT_MAX_PENDING_BATCHES: usize = 2;
+
+/// A event of a stream, sent through its channel.
+#[derive(Serialize, Clone)]
+#[serde(tag = "event", content = "payload")]
+pub(crate) enum StreamEvent {
+    /// The next rows.
+    Batch(Vec<IndexMap<String, JsonValue>>),
+    /// The query failed, no more events are sent.
+    Error(String),
+    /// All the rows were sent.
+    End,
+}
+
+/// Sends the batches through the channel, each one using a credit given back by [`DbStreams::ack`]
+/// once the webview consumed it.
+struct BatchSink {
+    id: StreamId,
+    channel: Channel<StreamEvent>,
+    credits: Arc<Semaphore>,
+}
+
+impl BatchSink {
+    #[cfg_attr(
+        not(any(feature = "sqlite", feature = "mysql", feature = "postgres")),
+        allow(dead_code)
+    )]
+    async fn send(&self, rows: Vec<IndexMap<String, JsonValue>>) -> Result<(), Error> {
+        self.credits
+            .acquire()
+            .await
+            .map_err(|_| Error::StreamNotFound(self.id))?
+            .forget();
+        self.channel.send(StreamEvent::Batch(rows))?;
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+/// Reads the rows from the database cursor as the batches are sent, so at most one batch is in memory.
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+async fn send_rows<R: Row>(
+    mut rows: BoxStream<'_, Result<R, sqlx::Error>>,
+    to_json: impl for<'r> Fn(<R::Database as Database>::ValueRef<'r>) -> Result<JsonValue, Error>,
+    batch_size: usize,
+    sink: &BatchSink,
+) -> Result<(), Error> {
+    let mut batch = Vec::with_capacity(batch_size);
+    while let Some(row) = next(&mut rows).await {
+        batch.push(row?);
+        if batch.len() >= batch_size {
+            let rows = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+            sink.send(rows_to_json(rows, &to_json)?).await?;
+        }
+    }
+    if !batch.is_empty() {
+        sink.send(rows_to_json(batch, &to_json)?).await?;
+    }
+    Ok(())
+}
+
+impl DbPool {
+    async fn stream(
+        &self,
+        _query: String,
+        _values: Vec<JsonValue>,
+        _batch_size: usize,
+        _sink: &BatchSink,
+    ) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                send_rows(
+                    query.fetch(pool),
+                    crate::decode::sqlite::to_json,
+                    _batch_size,
+                    _sink,
+                )
+                .await?
+            }
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                send_rows(
+                    query.fetch(pool),
+                    crate::decode::mysql::to_json,
+                    _batch_size,
+                    _sink,
+                )
+                .await?
+            }
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                send_rows(
+                    query.fetch(pool),
+                    crate::decode::postgres::to_json,
+                    _batch_size,
+                    _sink,
+                )
+                .await?
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => (),
+        }
+        Ok(())
+    }
+}
+
+/// Options of [`DbStreams::start`].
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct StreamOptions {
+    pub(crate) batch_size: Option<usize>,
+    pub(crate) max_pending_batches: Option<usize>,
+}
+
+struct OpenStream {
+    db: String,
+    credits: Arc<Semaphore>,
+    task: JoinHandle<()>,
+}
+
+/// The streams started from the webview, until all their rows were sent or they're cancelled.
+#[derive(Default)]
+pub(crate) struct DbStreams {
+    next_id: AtomicU32,
+    streams: Arc<std::sync::Mutex<HashMap<StreamId, OpenStream>>>,
+}
+
+impl DbStreams {
+    /// Runs the query in the background, holding a connection of the pool until the stream ends.
+    pub(crate) fn start(
+        &self,
+        db: String,
+        pool: DbPool,
+        query: String,
+        values: Vec<JsonValue>,
+        options: StreamOptions,
+        channel: Channel<StreamEvent>,
+    ) -> StreamId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+        let max_pending_batches = options
+            .max_pending_batches
+            .unwrap_or(DEFAULT_MAX_PENDING_BATCHES)
+            .max(1);
+        let credits = Arc::new(Semaphore::new(max_pending_batches));
+        let sink = BatchSink {
+            id,
+            channel,
+            credits: credits.clone(),
+        };
+
+        let open_streams = self.streams.clone();
+        // locked until the stream is inserted, so the task can't remove it before
+        let mut streams = self.streams.lock().unwrap();
+        let task = spawn(async move {
+            let event = match pool.stream(query, values, batch_size, &sink).await {
+                Ok(()) => StreamEvent::End,
+                Err(e) => StreamEvent::Error(e.to_string()),
+            };
+            let _ = sink.channel.send(event);
+            open_streams.lock().unwrap().remove(&id);
+        });
+        streams.insert(id, OpenStream { db, credits, task });
+
+        id
+    }
+
+    /// Allows the stream to send one more batch, ignored if it ended.
+    pub(crate) fn ack(&self, id: StreamId) {
+        if let Some(stream) = self.streams.lock().unwrap().get(&id) {
+            stream.credits.add_permits(1);
+        }
+    }
+
+    /// Stops reading the rows and releases the connection, ignored if the stream ended.
+    pub(crate) fn cancel(&self, id: StreamId) {
+        if let Some(stream) = self.streams.lock().unwrap().remove(&id) {
+            stream.task.abort();
+        }
+    }
+
+    /// Cancels the streams of the database, or of all the databases if `None`.
+    pub(crate) fn cancel_all(&self, db: Option<&str>) {
+        self.streams.lock().unwrap().retain(|_, stream| {
+            let cancel = db.is_none() || db == Some(stream.db.as_str());
+            if cancel {
+                stream.task.abort();
+            }
+            !cancel
+        });
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use sqlx::{Executor, Transaction};
+use tokio::sync::Mutex;
+
+#[cfg(feature = "mysql")]
+use sqlx::MySql;
+#[cfg(feature = "postgres")]
+use sqlx::Postgres;
+#[cfg(feature = "sqlite")]
+use sqlx::Sqlite;
+
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use crate::wrapper::{bind_values, rows_to_json};
+use crate::{DbPool, Error, LastInsertId};
+
+pub(crate) type TransactionId = u32;
+
+/// A transaction holding one of the connections of the pool until it's committed or rolled back,
+/// so all its statements run on the same connection.
+pub(crate) enum DbTransaction {
+    #[cfg(feature = "sqlite")]
+    Sqlite(Transaction<'static, Sqlite>),
+    #[cfg(feature = "mysql")]
+    MySql(Transaction<'static, MySql>),
+    #[cfg(feature = "postgres")]
+    Postgres(Transaction<'static, Postgres>),
+    #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+    None,
+}
+
+/// A statement managing a savepoint of a transaction.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SavepointAction {
+    Create,
+    Release,
+    RollbackTo,
+}
+
+impl SavepointAction {
+    /// The statement for the savepoint, whose name must be a plain identifier since it can't be bound.
+    fn statement(self, name: &str) -> Result<String, Error> {
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(Error::InvalidSavepointName(name.into()));
+        }
+
+        Ok(match self {
+            Self::Create => format!("SAVEPOINT {name}"),
+            Self::Release => format!("RELEASE SAVEPOINT {name}"),
+            Self::RollbackTo => format!("ROLLBACK TO SAVEPOINT {name}"),
+        })
+    }
+}
+
+impl DbPool {
+    pub(crate) async fn begin(&self) -> Result<DbTransaction, Error> {
+        Ok(match self {
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => DbTransaction::Sqlite(pool.begin().await?),
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => DbTransaction::MySql(pool.begin().await?),
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => DbTransaction::Postgres(pool.begin().await?),
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbPool::None => DbTransaction::None,
+        })
+    }
+}
+
+impl DbTransaction {
+    pub(crate) async fn execute(
+        &mut self,
+        _query: String,
+        _values: Vec<JsonValue>,
+    ) -> Result<(u64, LastInsertId), Error> {
+        Ok(match self {
+            #[cfg(feature = "sqlite")]
+            DbTransaction::Sqlite(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let result = query.execute(&mut **tx).await?;
+                (
+                    result.rows_affected(),
+                    LastInsertId::Sqlite(result.last_insert_rowid()),
+                )
+            }
+            #[cfg(feature = "mysql")]
+            DbTransaction::MySql(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let result = query.execute(&mut **tx).await?;
+                (
+                    result.rows_affected(),
+                    LastInsertId::MySql(result.last_insert_id()),
+                )
+            }
+            #[cfg(feature = "postgres")]
+            DbTransaction::Postgres(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let result = query.execute(&mut **tx).await?;
+                (result.rows_affected(), LastInsertId::Postgres(()))
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbTransaction::None => (0, LastInsertId::None),
+        })
+    }
+
+    pub(crate) async fn select(
+        &mut self,
+        _query: String,
+        _values: Vec<JsonValue>,
+    ) -> Result<Vec<IndexMap<String, JsonValue>>, Error> {
+        Ok(match self {
+            #[cfg(feature = "sqlite")]
+            DbTransaction::Sqlite(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let rows = query.fetch_all(&mut **tx).await?;
+                rows_to_json(rows, crate::decode::sqlite::to_json)?
+            }
+            #[cfg(feature = "mysql")]
+            DbTransaction::MySql(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let rows = query.fetch_all(&mut **tx).await?;
+                rows_to_json(rows, crate::decode::mysql::to_json)?
+            }
+            #[cfg(feature = "postgres")]
+            DbTransaction::Postgres(tx) => {
+                let query = bind_values(sqlx::query(&_query), _values)?;
+                let rows = query.fetch_all(&mut **tx).await?;
+                rows_to_json(rows, crate::decode::postgres::to_json)?
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbTransaction::None => Vec::new(),
+        })
+    }
+
+    pub(crate) async fn savepoint(
+        &mut self,
+        action: SavepointAction,
+        name: &str,
+    ) -> Result<(), Error> {
+        let _statement = action.statement(name)?;
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbTransaction::Sqlite(tx) => {
+                (&mut **tx).execute(_statement.as_str()).await?;
+            }
+            #[cfg(feature = "mysql")]
+            DbTransaction::MySql(tx) => {
+                (&mut **tx).execute(_statement.as_str()).await?;
+            }
+            #[cfg(feature = "postgres")]
+            DbTransaction::Postgres(tx) => {
+                (&mut **tx).execute(_statement.as_str()).await?;
+            }
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbTransaction::None => (),
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn commit(self) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbTransaction::Sqlite(tx) => tx.commit().await?,
+            #[cfg(feature = "mysql")]
+            DbTransaction::MySql(tx) => tx.commit().await?,
+            #[cfg(feature = "postgres")]
+            DbTransaction::Postgres(tx) => tx.commit().await?,
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbTransaction::None => (),
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn rollback(self) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            DbTransaction::Sqlite(tx) => tx.rollback().await?,
+            #[cfg(feature = "mysql")]
+            DbTransaction::MySql(tx) => tx.rollback().await?,
+            #[cfg(feature = "postgres")]
+            DbTransaction::Postgres(tx) => tx.rollback().await?,
+            #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
+            DbTransaction::None => (),
+        }
+        Ok(())
+    }
+}
+
+/// A transaction, `None` once it was committed or rolled back.
+pub(crate) type SharedTransaction = Arc<Mutex<Option<DbTransaction>>>;
+
+struct OpenTransaction {
+    db: String,
+    transaction: SharedTransaction,
+}
+
+/// The transactions started from the webview, until they're committed or rolled back.
+#[derive(Default)]
+pub(crate) struct DbTransactions {
+    next_id: AtomicU32,
+    transactions: std::sync::Mutex<HashMap<TransactionId, OpenTransaction>>,
+}
+
+impl DbTransactions {
+    pub(crate) fn insert(&self, db: String, transaction: DbTransaction) -> TransactionId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.transactions.lock().unwrap().insert(
+            id,
+            OpenTransaction {
+                db,
+                transaction: Arc::new(Mutex::new(Some(transaction))),
+            },
+        );
+        id
+    }
+
+    pub(crate) fn get(&self, id: TransactionId) -> Result<SharedTransaction, Error> {
+        self.transactions
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|open| open.transaction.clone())
+            .ok_or(Error::TransactionNotFound(id))
+    }
+
+    /// Removes the transaction, waiting for the statement in progress if any.
+    pub(crate) async fn take(&self, id: TransactionId) -> Result<DbTransaction, Error> {
+        let open = self
+            .transactions
+            .lock()
+            .unwrap()
+            .remove(&id)
+            .ok_or(Error::TransactionNotFound(id))?;
+        let transaction = open.transaction.lock().await.take();
+        transaction.ok_or(Error::TransactionNotFound(id))
+    }
+
+    /// Rolls back the transactions of the database, or of all the databases if `None`.
+    pub(crate) async fn rollback_all(&self, db: Option<&str>) {
+        let transactions: Vec<OpenTransaction> = {
+            let mut transactions = self.transactions.lock().unwrap();
+            let ids: Vec<TransactionId> = transactions
+                .iter()
+                .filter(|(_, open)| db.is_none() || db == Some(open.db.as_str()))
+                .map(|(id, _)| *id)
+                .collect();
+            ids.into_iter()
+                .filter_map(|id| transactions.remove(&id))
+                .collect()
+        };
+        for open in transactions {
+            if let Some(transaction) = open.transaction.lock().await.take() {
+                let _ = transaction.rollback().await;
+            }
+        }
+    }
+}
@@ -4,24 +4,33 @@
 
 #[cfg(feature = "sqlite")]
 use std::fs::create_dir_all;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use std::str::FromStr;
 
 use indexmap::IndexMap;
 use serde_json::Value as JsonValue;
 #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
-use sqlx::{migrate::MigrateDatabase, Column, Executor, Pool, Row};
+use sqlx::{
+    migrate::MigrateDatabase, query::Query, Column, Database, Encode, Executor, Pool, Row, Type,
+};
 #[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
 use tauri::Manager;
 use tauri::{AppHandle, Runtime};
 
 #[cfg(feature = "mysql")]
-use sqlx::MySql;
+use sqlx::{mysql::MySqlConnectOptions, MySql};
 #[cfg(feature = "postgres")]
-use sqlx::Postgres;
+use sqlx::{postgres::PgConnectOptions, Postgres};
 #[cfg(feature = "sqlite")]
-use sqlx::Sqlite;
+use sqlx::{sqlite::SqliteConnectOptions, Sqlite};
 
-use crate::LastInsertId;
+#[cfg(feature = "sqlite")]
+use crate::attach::DbAttachments;
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+use crate::encode::{BindTyped, TypedValue};
+use crate::{EncryptionKey, LastInsertId, PoolOptions};
 
+#[derive(Clone)]
 pub enum DbPool {
     #[cfg(feature = "sqlite")]
     Sqlite(Pool<Sqlite>),
@@ -65,15 +74,22 @@ pub enum DbPool {
 
 // private methods
 impl DbPool {
+    /// Connects to the database, decrypting it with the key if it's an encrypted SQLite database.
     pub(crate) async fn connect<R: Runtime>(
         conn_url: &str,
         _app: &AppHandle<R>,
+        _key: Option<&EncryptionKey>,
+        _pool_options: &PoolOptions,
     ) -> Result<Self, crate::Error> {
-        match conn_url
+        let scheme = conn_url
             .split_once(':')
             .ok_or_else(|| crate::Error::InvalidDbUrl(conn_url.to_string()))?
-            .0
-        {
+            .0;
+        if _key.is_some() && (!cfg!(feature = "sqlcipher") || scheme != "sqlite") {
+            return Err(crate::Error::EncryptionUnsupported(conn_url.to_string()));
+        }
+
+        match scheme {
             #[cfg(feature = "sqlite")]
             "sqlite" => {
                 let app_path = _app
@@ -83,26 +99,74 @@ impl DbPool {
 
                 create_dir_all(&app_path).expect("Couldn't create app config dir");
 
-                let conn_url = &path_mapper(app_path, conn_url);
+                let mapped_url = &path_mapper(app_path, conn_url);
 
-                if !Sqlite::database_exists(conn_url).await.unwrap_or(false) {
-                    Sqlite::create_database(conn_url).await?;
+                if !Sqlite::database_exists(mapped_url).await.unwrap_or(false) {
+                    Sqlite::create_database(mapped_url).await?;
                 }
-                Ok(Self::Sqlite(Pool::connect(conn_url).await?))
+
+                let mut options = SqliteConnectOptions::from_str(mapped_url)?;
+                if let Some(capacity) = _pool_options.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+                let mut pool_options = _pool_options.to_sqlx::<Sqlite>();
+                let attachments = _app
+                    .try_state::<DbAttachments>()
+                    .map(|attachments| attachments.get(conn_url))
+                    .unwrap_or_default();
+                if !attachments.is_empty() {
+                    pool_options = pool_options.after_connect(move |conn, _| {
+                        let attachments = attachments.clone();
+                        Box::pin(async move {
+                            for attachment in &attachments {
+                                attachment.attach(conn).await?;
+                            }
+                            Ok(())
+                        })
+                    });
+                }
+                #[cfg(feature = "sqlcipher")]
+                if let Some(key) = _key {
+                    // the key must be set before any other statement, which sqlx ensures
+                    options = options.pragma("key", key.pragma());
+                    let pool = pool_options
+                        .connect_with(options)
+                        .await
+                        .map_err(|e| crate::cipher::map_key_error(conn_url, e))?;
+                    // SQLCipher only checks the key once a page is read
+                    sqlx::query("SELECT count(*) FROM sqlite_master")
+                        .execute(&pool)
+                        .await
+                        .map_err(|e| crate::cipher::map_key_error(conn_url, e))?;
+                    return Ok(Self::Sqlite(pool));
+                }
+                Ok(Self::Sqlite(pool_options.connect_with(options).await?))
             }
             #[cfg(feature = "mysql")]
             "mysql" => {
                 if !MySql::database_exists(conn_url).await.unwrap_or(false) {
                     MySql::create_database(conn_url).await?;
                 }
-                Ok(Self::MySql(Pool::connect(conn_url).await?))
+                let mut options = MySqlConnectOptions::from_str(conn_url)?;
+                if let Some(capacity) = _pool_options.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+                let pool = _pool_options.to_sqlx().connect_with(options).await?;
+                _pool_options.spawn_health_check(_app, conn_url, pool.clone());
+                Ok(Self::MySql(pool))
             }
             #[cfg(feature = "postgres")]
             "postgres" => {
                 if !Postgres::database_exists(conn_url).await.unwrap_or(false) {
                     Postgres::create_database(conn_url).await?;
                 }
-                Ok(Self::Postgres(Pool::connect(conn_url).await?))
+                let mut options = PgConnectOptions::from_str(conn_url)?;
+                if let Some(capacity) = _pool_options.statement_cache_capacity {
+                    options = options.statement_cache_capacity(capacity);
+                }
+                let pool = _pool_options.to_sqlx().connect_with(options).await?;
+                _pool_options.spawn_health_check(_app, conn_url, pool.clone());
+                Ok(Self::Postgres(pool))
             }
             #[cfg(not(any(feature = "sqlite", feature = "postgres", feature = "mysql")))]
             _ => Err(crate::Error::InvalidDbUrl(format!(
@@ -151,18 +215,7 @@ impl DbPool {
         Ok(match self {
             #[cfg(feature = "sqlite")]
             DbPool::Sqlite(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let result = pool.execute(query).await?;
                 (
                     result.rows_affected(),
@@ -171,18 +224,7 @@ impl DbPool {
             }
             #[cfg(feature = "mysql")]
             DbPool::MySql(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let result = pool.execute(query).await?;
                 (
                     result.rows_affected(),
@@ -191,18 +233,7 @@ impl DbPool {
             }
             #[cfg(feature = "postgres")]
             DbPool::Postgres(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let result = pool.execute(query).await?;
                 (result.rows_affected(), LastInsertId::Postgres(()))
             }
@@ -219,93 +250,21 @@ impl DbPool {
         Ok(match self {
             #[cfg(feature = "sqlite")]
             DbPool::Sqlite(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let rows = pool.fetch_all(query).await?;
-                let mut values = Vec::new();
-                for row in rows {
-                    let mut value = IndexMap::default();
-                    for (i, column) in row.columns().iter().enumerate() {
-                        let v = row.try_get_raw(i)?;
-
-                        let v = crate::decode::sqlite::to_json(v)?;
-
-                        value.insert(column.name().to_string(), v);
-                    }
-
-                    values.push(value);
-                }
-                values
+                rows_to_json(rows, crate::decode::sqlite::to_json)?
             }
             #[cfg(feature = "mysql")]
             DbPool::MySql(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let rows = pool.fetch_all(query).await?;
-                let mut values = Vec::new();
-                for row in rows {
-                    let mut value = IndexMap::default();
-                    for (i, column) in row.columns().iter().enumerate() {
-                        let v = row.try_get_raw(i)?;
-
-                        let v = crate::decode::mysql::to_json(v)?;
-
-                        value.insert(column.name().to_string(), v);
-                    }
-
-                    values.push(value);
-                }
-                values
+                rows_to_json(rows, crate::decode::mysql::to_json)?
             }
             #[cfg(feature = "postgres")]
             DbPool::Postgres(pool) => {
-                let mut query = sqlx::query(&_query);
-                for value in _values {
-                    if value.is_null() {
-                        query = query.bind(None::<JsonValue>);
-                    } else if value.is_string() {
-                        query = query.bind(value.as_str().unwrap().to_owned())
-                    } else if let Some(number) = value.as_number() {
-                        query = query.bind(number.as_f64().unwrap_or_default())
-                    } else {
-                        query = query.bind(value);
-                    }
-                }
+                let query = bind_values(sqlx::query(&_query), _values)?;
                 let rows = pool.fetch_all(query).await?;
-                let mut values = Vec::new();
-                for row in rows {
-                    let mut value = IndexMap::default();
-                    for (i, column) in row.columns().iter().enumerate() {
-                        let v = row.try_get_raw(i)?;
-
-                        let v = crate::decode::postgres::to_json(v)?;
-
-                        value.insert(column.name().to_string(), v);
-                    }
-
-                    values.push(value);
-                }
-                values
+                rows_to_json(rows, crate::decode::postgres::to_json)?
             }
             #[cfg(not(any(feature = "sqlite", feature = "mysql", feature = "postgres")))]
             DbPool::None => Vec::new(),
@@ -313,6 +272,57 @@ impl DbPool {
     }
 }
 
+/// Binds the values to the query, numbers are bound as floats and the typed values
+/// (see [`TypedValue`]) with their type.
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+pub(crate) fn bind_values<'q, DB: BindTyped>(
+    mut query: Query<'q, DB, <DB as Database>::Arguments<'q>>,
+    values: Vec<JsonValue>,
+) -> Result<Query<'q, DB, <DB as Database>::Arguments<'q>>, crate::Error>
+where
+    Option<JsonValue>: Encode<'q, DB> + Type<DB>,
+    String: Encode<'q, DB> + Type<DB>,
+    f64: Encode<'q, DB> + Type<DB>,
+    JsonValue: Encode<'q, DB> + Type<DB>,
+{
+    for value in values {
+        if let Some(typed) = TypedValue::parse(&value)? {
+            query = DB::bind_typed(query, typed)?;
+        } else if value.is_null() {
+            query = query.bind(None::<JsonValue>);
+        } else if value.is_string() {
+            query = query.bind(value.as_str().unwrap().to_owned())
+        } else if let Some(number) = value.as_number() {
+            query = query.bind(number.as_f64().unwrap_or_default())
+        } else {
+            query = query.bind(value);
+        }
+    }
+    Ok(query)
+}
+
+/// Decodes the rows to JSON objects keyed by column name, in column order.
+#[cfg(any(feature = "sqlite", feature = "mysql", feature = "postgres"))]
+pub(crate) fn rows_to_json<R: Row>(
+    rows: Vec<R>,
+    to_json: impl for<'r> Fn(<R::Database as Database>::ValueRef<'r>) -> Result<JsonValue, crate::Error>,
+) -> Result<Vec<IndexMap<String, JsonValue>>, crate::Error> {
+    let mut values = Vec::new();
+    for row in rows {
+        let mut value = IndexMap::default();
+        for (i, column) in row.columns().iter().enumerate() {
+            let v = row.try_get_raw(i)?;
+
+            let v = to_json(v)?;
+
+            value.insert(column.name().to_string(), v);
+        }
+
+        values.push(value);
+    }
+    Ok(values)
+}
+
 #[cfg(feature = "sqlite")]
 /// Maps the user supplied DB connection string to a connection string
 /// with a fully qualified file path to the App's designed "app_path"
@@ -17,6 +17,8 @@ const COMMANDS: &[&str] = &[
     "length",
     "reload",
     "save",
+    "expires_at",
+    "batch",
 ];
 
 fn main() {
@@ -14,6 +14,36 @@ interface ChangePayload<T> {
   exists: boolean
 }
 
+interface BatchChangePayload<T> {
+  path: string
+  resourceId?: number
+  changes: Array<{ key: string; value: T; exists: boolean }>
+}
+
+interface ExpiredPayload {
+  path: string
+  resourceId?: number
+  key: string
+}
+
+/**
+ * Options to set an entry
+ */
+export type SetOptions = {
+  /**
+   * Time after which the entry is removed, in milliseconds.
+   * Expired entries are removed when the store is read and in the background.
+   */
+  ttlMs?: number
+}
+
+/**
+ * An operation of a batch, see {@linkcode Store.batch}
+ */
+export type BatchOperation =
+  | { type: 'set'; key: string; value: unknown; ttlMs?: number }
+  | { type: 'delete'; key: string }
+
 /**
  * Options to create a store
  */
@@ -105,8 +135,16 @@ export class LazyStore implements IStore {
     await this.store
   }
 
-  async set(key: string, value: unknown): Promise<void> {
-    return (await this.store).set(key, value)
+  async set(key: string, value: unknown, options?: SetOptions): Promise<void> {
+    return (await this.store).set(key, value, options)
+  }
+
+  async expiresAt(key: string): Promise<Date | undefined> {
+    return (await this.store).expiresAt(key)
+  }
+
+  async batch(operations: BatchOperation[]): Promise<void> {
+    return (await this.store).batch(operations)
   }
 
   async get<T>(key: string): Promise<T | undefined> {
@@ -166,6 +204,16 @@ export class LazyStore implements IStore {
     return (await this.store).onChange<T>(cb)
   }
 
+  async onBatchChange<T>(
+    cb: (changes: Array<[key: string, value: T | undefined]>) => void
+  ): Promise<UnlistenFn> {
+    return (await this.store).onBatchChange<T>(cb)
+  }
+
+  async onExpired(cb: (key: string) => void): Promise<UnlistenFn> {
+    return (await this.store).onExpired(cb)
+  }
+
   async close(): Promise<void> {
     if (this._store) {
       await (await this._store).close()
@@ -226,14 +274,27 @@ export class Store extends Resource implements IStore {
     )
   }
 
-  async set(key: string, value: unknown): Promise<void> {
+  async set(key: string, value: unknown, options?: SetOptions): Promise<void> {
     await invoke('plugin:store|set', {
       rid: this.rid,
       key,
-      value
+      value,
+      options
     })
   }
 
+  async expiresAt(key: string): Promise<Date | undefined> {
+    const expiresAt = await invoke<number | null>('plugin:store|expires_at', {
+      rid: this.rid,
+      key
+    })
+    return expiresAt === null ? undefined : new Date(expiresAt)
+  }
+
+  async batch(operations: BatchOperation[]): Promise<void> {
+    await invoke('plugin:store|batch', { rid: this.rid, operations })
+  }
+
   async get<T>(key: string): Promise<T | undefined> {
     const [value, exists] = await invoke<[T, boolean]>('plugin:store|get', {
       rid: this.rid,
@@ -292,9 +353,9 @@ export class Store extends Resource implements IStore {
     key: string,
     cb: (value: T | undefined) => void
   ): Promise<UnlistenFn> {
-    return await listen<ChangePayload<T>>('store://change', (event) => {
-      if (event.payload.resourceId === this.rid && event.payload.key === key) {
-        cb(event.payload.exists ? event.payload.value : undefined)
+    return await this.onChange<T>((changedKey, value) => {
+      if (changedKey === key) {
+        cb(value)
       }
     })
   }
@@ -302,12 +363,51 @@ export class Store extends Resource implements IStore {
   async onChange<T>(
     cb: (key: string, value: T | undefined) => void
   ): Promise<UnlistenFn> {
-    return await listen<ChangePayload<T>>('store://change', (event) => {
+    const unlistenChange = await listen<ChangePayload<T>>(
+      'store://change',
+      (event) => {
+        if (event.payload.resourceId === this.rid) {
+          cb(
+            event.payload.key,
+            event.payload.exists ? event.payload.value : undefined
+          )
+        }
+      }
+    )
+    // the entries changed by a batch are reported one by one
+    const unlistenBatch = await this.onBatchChange<T>((changes) => {
+      for (const [key, value] of changes) {
+        cb(key, value)
+      }
+    })
+    return () => {
+      unlistenChange()
+      unlistenBatch()
+    }
+  }
+
+  async onBatchChange<T>(
+    cb: (changes: Array<[key: string, value: T | undefined]>) => void
+  ): Promise<UnlistenFn> {
+    return await listen<BatchChangePayload<T>>(
+      'store://batch-change',
+      (event) => {
+        if (event.payload.resourceId === this.rid) {
+          cb(
+            event.payload.changes.map(({ key, value, exists }) => [
+              key,
+              exists ? value : undefined
+            ])
+          )
+        }
+      }
+    )
+  }
+
+  async onExpired(cb: (key: string) => void): Promise<UnlistenFn> {
+    return await listen<ExpiredPayload>('store://expired', (event) => {
       if (event.payload.resourceId === this.rid) {
-        cb(
-          event.payload.key,
-          event.payload.exists ? event.payload.value : undefined
-        )
+        cb(event.payload.key)
       }
     })
   }
@@ -317,11 +417,47 @@ interface IStore {
   /**
    * Inserts a key-value pair into the store.
    *
+   * With a `ttlMs` option, the entry is removed once it elapsed.
+   * Setting the key again without it removes its TTL.
+   *
+   * @example
+   * ```typescript
+   * await store.set('token', token, { ttlMs: 60 * 60 * 1000 });
+   * ```
+   *
    * @param key
    * @param value
+   * @param options
+   * @returns
+   */
+  set(key: string, value: unknown, options?: SetOptions): Promise<void>
+
+  /**
+   * Returns when the entry for the given `key` expires,
+   * or `undefined` if the key does not exist or was set without a TTL.
+   *
+   * @param key
+   * @returns
+   */
+  expiresAt(key: string): Promise<Date | undefined>
+
+  /**
+   * Applies the operations atomically: either all of them are applied or none is,
+   * the store is saved once and the change listeners are notified once they're all applied.
+   *
+   * @example
+   * ```typescript
+   * await store.batch([
+   *   { type: 'set', key: 'user', value: user },
+   *   { type: 'set', key: 'token', value: token, ttlMs: 60 * 60 * 1000 },
+   *   { type: 'delete', key: 'guest' }
+   * ]);
+   * ```
+   *
+   * @param operations
    * @returns
    */
-  set(key: string, value: unknown): Promise<void>
+  batch(operations: BatchOperation[]): Promise<void>
 
   /**
    * Returns the value for the given `key` or `undefined` if the key does not exist.
@@ -431,6 +567,23 @@ interface IStore {
     cb: (key: string, value: T | undefined) => void
   ): Promise<UnlistenFn>
 
+  /**
+   * Listen to the changes made by a {@linkcode batch}, with the entries it changed.
+   * They're also passed to the {@linkcode onChange} and {@linkcode onKeyChange} listeners.
+   * @param cb
+   * @returns A promise resolving to a function to unlisten to the event.
+   */
+  onBatchChange<T>(
+    cb: (changes: Array<[key: string, value: T | undefined]>) => void
+  ): Promise<UnlistenFn>
+
+  /**
+   * Listen to the entries of the store removed once their TTL elapsed.
+   * @param cb
+   * @returns A promise resolving to a function to unlisten to the event.
+   */
+  onExpired(cb: (key: string) => void): Promise<UnlistenFn>
+
   /**
    * Close the store and cleans up this resource from memory.
    * **You should not call any method on this object anymore and should drop any reference to it.**
@@ -16,9 +16,12 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, UNIX_EPOCH},
+};
+pub use store::{
+    resolve_store_path, BatchOperation, DeserializeFn, SerializeFn, Store, StoreBuilder,
+    StoreTransaction,
 };
-pub use store::{resolve_store_path, DeserializeFn, SerializeFn, Store, StoreBuilder};
 use tauri::{
     plugin::{self, TauriPlugin},
     AppHandle, Manager, ResourceId, RunEvent, Runtime, State,
@@ -37,6 +40,64 @@ struct ChangePayload<'a> {
     exists: bool,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct KeyChange<'a> {
+    key: &'a str,
+    value: Option<&'a JsonValue>,
+    exists: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct BatchChangePayload<'a> {
+    path: &'a Path,
+    resource_id: Option<u32>,
+    changes: Vec<KeyChange<'a>>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ExpiredPayload<'a> {
+    path: &'a Path,
+    resource_id: Option<u32>,
+    key: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Operation {
+    #[serde(rename_all = "camelCase")]
+    Set {
+        key: String,
+        value: JsonValue,
+        ttl_ms: Option<u64>,
+    },
+    Delete {
+        key: String,
+    },
+}
+
+impl From<Operation> for BatchOperation {
+    fn from(operation: Operation) -> Self {
+        match operation {
+            Operation::Set { key, value, ttl_ms } => Self::Set {
+                key,
+                value,
+                ttl: ttl_ms.map(Duration::from_millis),
+            },
+            Operation::Delete { key } => Self::Delete { key },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetOptions {
+    /// Time after which the entry is removed, in milliseconds.
+    ttl_ms: Option<u64>,
+}
+
 #[derive(Debug)]
 struct StoreState {
     stores: Arc<Mutex<HashMap<PathBuf, ResourceId>>>,
@@ -137,12 +198,42 @@ async fn set<R: Runtime>(
     rid: ResourceId,
     key: String,
     value: JsonValue,
+    options: Option<SetOptions>,
 ) -> Result<()> {
     let store = app.resources_table().get::<Store<R>>(rid)?;
-    store.set(key, value);
+    match options.and_then(|options| options.ttl_ms) {
+        Some(ttl) => store.set_with_ttl(key, value, Duration::from_millis(ttl)),
+        None => store.set(key, value),
+    }
     Ok(())
 }
 
+#[tauri::command]
+async fn batch<R: Runtime>(
+    app: AppHandle<R>,
+    rid: ResourceId,
+    operations: Vec<Operation>,
+) -> Result<()> {
+    let store = app.resources_table().get::<Store<R>>(rid)?;
+    store.batch(operations.into_iter().map(Into::into).collect());
+    Ok(())
+}
+
+/// Returns when the entry expires in milliseconds since the Unix epoch, `None` if it has no TTL.
+#[tauri::command]
+async fn expires_at<R: Runtime>(
+    app: AppHandle<R>,
+    rid: ResourceId,
+    key: String,
+) -> Result<Option<u64>> {
+    let store = app.resources_table().get::<Store<R>>(rid)?;
+    Ok(store.expires_at(key).map(|at| {
+        at.duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default()
+    }))
+}
+
 #[tauri::command]
 async fn get<R: Runtime>(
     app: AppHandle<R>,
@@ -413,7 +504,7 @@ impl Builder {
         plugin::Builder::new("store")
             .invoke_handler(tauri::generate_handler![
                 load, get_store, set, get, has, delete, clear, reset, keys, values, length,
-                entries, reload, save,
+                entries, reload, save, expires_at, batch,
             ])
             .setup(move |app_handle, _api| {
                 app_handle.manage(StoreState {
@@ -2,22 +2,29 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{ChangePayload, StoreState};
+use crate::{BatchChangePayload, ChangePayload, ExpiredPayload, KeyChange, StoreState};
 use serde_json::Value as JsonValue;
 use std::{
     collections::HashMap,
     fs,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
-    time::Duration,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, Resource, ResourceId, Runtime};
 use tokio::{
     select,
-    sync::mpsc::{unbounded_channel, UnboundedSender},
+    sync::{
+        mpsc::{unbounded_channel, UnboundedSender},
+        Notify,
+    },
     time::sleep,
 };
 
+/// The extension of the file the expiration times of the entries are saved to next to the store,
+/// in milliseconds since the Unix epoch, so they don't share the keys of the store.
+const EXPIRATIONS_EXTENSION: &str = "expirations";
+
 pub type SerializeFn =
     fn(&HashMap<String, JsonValue>) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
 pub type DeserializeFn =
@@ -212,9 +219,11 @@ impl<R: Runtime> StoreBuilder<R> {
             auto_save: self.auto_save,
             auto_save_debounce_sender: Arc::new(Mutex::new(None)),
             store: Arc::new(Mutex::new(store_inner)),
+            expiration_notify: Arc::new(Notify::new()),
         };
 
         let store = Arc::new(store);
+        spawn_expiration_task(&store);
         let rid = self.app.resources_table().add_arc(store.clone());
         stores.insert(self.path, rid);
 
@@ -240,6 +249,92 @@ impl<R: Runtime> StoreBuilder<R> {
     }
 }
 
+/// An operation of a batch applied atomically with [`Store::batch`].
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    /// Inserts a key-value pair, removed once the TTL elapsed if any.
+    Set {
+        key: String,
+        value: JsonValue,
+        ttl: Option<Duration>,
+    },
+    /// Removes a key-value pair.
+    Delete { key: String },
+}
+
+impl BatchOperation {
+    fn key(&self) -> &str {
+        match self {
+            Self::Set { key, .. } | Self::Delete { key } => key,
+        }
+    }
+}
+
+/// The operations of a transaction, see [`Store::transaction`].
+///
+/// The reads see the operations of the transaction, which are only applied once it succeeds.
+pub struct StoreTransaction<'a> {
+    cache: &'a HashMap<String, JsonValue>,
+    /// The values set or deleted by the transaction.
+    staged: HashMap<String, Option<JsonValue>>,
+    operations: Vec<BatchOperation>,
+}
+
+impl StoreTransaction<'_> {
+    /// Inserts a key-value pair into the store.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<JsonValue>) {
+        self.push(BatchOperation::Set {
+            key: key.into(),
+            value: value.into(),
+            ttl: None,
+        });
+    }
+
+    /// Inserts a key-value pair into the store, removed once the `ttl` elapsed.
+    pub fn set_with_ttl(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+        ttl: Duration,
+    ) {
+        self.push(BatchOperation::Set {
+            key: key.into(),
+            value: value.into(),
+            ttl: Some(ttl),
+        });
+    }
+
+    /// Removes a key-value pair from the store, returning whether it existed.
+    pub fn delete(&mut self, key: impl Into<String>) -> bool {
+        let key = key.into();
+        let existed = self.has(&key);
+        self.push(BatchOperation::Delete { key });
+        existed
+    }
+
+    /// Returns the value for the given `key` or `None` if the key does not exist.
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&JsonValue> {
+        match self.staged.get(key.as_ref()) {
+            Some(value) => value.as_ref(),
+            None => self.cache.get(key.as_ref()),
+        }
+    }
+
+    /// Returns `true` if the given `key` exists in the store.
+    pub fn has(&self, key: impl AsRef<str>) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn push(&mut self, operation: BatchOperation) {
+        let value = match &operation {
+            BatchOperation::Set { value, .. } => Some(value.clone()),
+            BatchOperation::Delete { .. } => None,
+        };
+        self.staged.insert(operation.key().to_owned(), value);
+        self.operations.push(operation);
+    }
+}
+
 enum AutoSaveMessage {
     Reset,
     Cancel,
@@ -250,11 +345,20 @@ struct StoreInner<R: Runtime> {
     app: AppHandle<R>,
     path: PathBuf,
     cache: HashMap<String, JsonValue>,
+    /// The expiration times of the entries set with a TTL, in milliseconds since the Unix epoch.
+    expirations: HashMap<String, u64>,
     defaults: Option<HashMap<String, JsonValue>>,
     serialize_fn: SerializeFn,
     deserialize_fn: DeserializeFn,
 }
 
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
 impl<R: Runtime> StoreInner<R> {
     fn new(
         app: AppHandle<R>,
@@ -267,12 +371,21 @@ impl<R: Runtime> StoreInner<R> {
             app,
             path,
             cache: defaults.clone().unwrap_or_default(),
+            expirations: HashMap::new(),
             defaults,
             serialize_fn,
             deserialize_fn,
         }
     }
 
+    /// The path of the file the expiration times are saved to, e.g. `settings.json.expirations`.
+    fn expirations_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".");
+        path.push(EXPIRATIONS_EXTENSION);
+        path.into()
+    }
+
     /// Saves the store to disk at the store's `path`.
     pub fn save(&self) -> crate::Result<()> {
         fs::create_dir_all(self.path.parent().expect("invalid store path"))?;
@@ -280,6 +393,16 @@ impl<R: Runtime> StoreInner<R> {
         let bytes = (self.serialize_fn)(&self.cache).map_err(crate::Error::Serialize)?;
         fs::write(&self.path, bytes)?;
 
+        let expirations_path = self.expirations_path();
+        if self.expirations.is_empty() {
+            match fs::remove_file(&expirations_path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        } else {
+            fs::write(expirations_path, serde_json::to_vec(&self.expirations)?)?;
+        }
+
         Ok(())
     }
 
@@ -287,8 +410,27 @@ impl<R: Runtime> StoreInner<R> {
     pub fn load(&mut self) -> crate::Result<()> {
         let bytes = fs::read(&self.path)?;
 
-        self.cache
-            .extend((self.deserialize_fn)(&bytes).map_err(crate::Error::Deserialize)?);
+        let cache = (self.deserialize_fn)(&bytes).map_err(crate::Error::Deserialize)?;
+        self.cache.extend(cache);
+
+        // the entries keep their TTL, an unreadable file only makes them persistent
+        let expirations_path = self.expirations_path();
+        if expirations_path.exists() {
+            match fs::read(&expirations_path)
+                .map_err(crate::Error::from)
+                .and_then(|bytes| Ok(serde_json::from_slice::<HashMap<String, u64>>(&bytes)?))
+            {
+                Ok(expirations) => self.expirations.extend(
+                    expirations
+                        .into_iter()
+                        .filter(|(key, _)| self.cache.contains_key(key)),
+                ),
+                Err(e) => tracing::warn!(
+                    "failed to load the expiration times from {}: {e}",
+                    expirations_path.display()
+                ),
+            }
+        }
 
         Ok(())
     }
@@ -297,10 +439,55 @@ impl<R: Runtime> StoreInner<R> {
     pub fn set(&mut self, key: impl Into<String>, value: impl Into<JsonValue>) {
         let key = key.into();
         let value = value.into();
+        self.expirations.remove(&key);
         self.cache.insert(key.clone(), value.clone());
         let _ = self.emit_change_event(&key, Some(&value));
     }
 
+    /// Inserts a key-value pair into the store, removed once the `ttl` elapsed.
+    pub fn set_with_ttl(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+        ttl: Duration,
+    ) {
+        let key = key.into();
+        self.set(key.clone(), value);
+        self.expirations
+            .insert(key, now_ms().saturating_add(ttl.as_millis() as u64));
+    }
+
+    /// Returns when the entry expires, `None` if it doesn't exist or has no TTL.
+    pub fn expires_at(&self, key: impl AsRef<str>) -> Option<SystemTime> {
+        self.expirations
+            .get(key.as_ref())
+            .map(|at| UNIX_EPOCH + Duration::from_millis(*at))
+    }
+
+    /// Returns the earliest expiration time of the entries, in milliseconds since the Unix epoch.
+    fn next_expiration(&self) -> Option<u64> {
+        self.expirations.values().min().copied()
+    }
+
+    /// Removes the expired entries, returning whether any was removed.
+    fn remove_expired(&mut self) -> bool {
+        let now = now_ms();
+        let expired: Vec<String> = self
+            .expirations
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.expirations.remove(key);
+            if self.cache.remove(key).is_some() {
+                let _ = self.emit_change_event(key, None);
+                let _ = self.emit_expired_event(key);
+            }
+        }
+        !expired.is_empty()
+    }
+
     /// Returns a reference to the value corresponding to the key.
     pub fn get(&self, key: impl AsRef<str>) -> Option<&JsonValue> {
         self.cache.get(key.as_ref())
@@ -311,8 +498,53 @@ impl<R: Runtime> StoreInner<R> {
         self.cache.contains_key(key.as_ref())
     }
 
+    /// Applies the operations, emitting a single change event with the entries they changed.
+    fn apply(&mut self, operations: Vec<BatchOperation>) {
+        // the values before the batch, in the order the keys were first changed
+        let mut previous: Vec<(String, Option<JsonValue>)> = Vec::new();
+        for operation in operations {
+            if !previous.iter().any(|(key, _)| key == operation.key()) {
+                let key = operation.key().to_owned();
+                let value = self.cache.get(&key).cloned();
+                previous.push((key, value));
+            }
+            match operation {
+                BatchOperation::Set { key, value, ttl } => {
+                    match ttl {
+                        Some(ttl) => self
+                            .expirations
+                            .insert(key.clone(), now_ms().saturating_add(ttl.as_millis() as u64)),
+                        None => self.expirations.remove(&key),
+                    };
+                    self.cache.insert(key, value);
+                }
+                BatchOperation::Delete { key } => {
+                    self.expirations.remove(&key);
+                    self.cache.remove(&key);
+                }
+            }
+        }
+
+        let changes: Vec<KeyChange> = previous
+            .iter()
+            .filter(|(key, value)| self.cache.get(key) != value.as_ref())
+            .map(|(key, _)| {
+                let value = self.cache.get(key);
+                KeyChange {
+                    key,
+                    value,
+                    exists: value.is_some(),
+                }
+            })
+            .collect();
+        if !changes.is_empty() {
+            let _ = self.emit_batch_change_event(changes);
+        }
+    }
+
     /// Removes a key-value pair from the store.
     pub fn delete(&mut self, key: impl AsRef<str>) -> bool {
+        self.expirations.remove(key.as_ref());
         let flag = self.cache.remove(key.as_ref()).is_some();
         if flag {
             let _ = self.emit_change_event(key.as_ref(), None);
@@ -326,6 +558,7 @@ impl<R: Runtime> StoreInner<R> {
     pub fn clear(&mut self) {
         let keys: Vec<String> = self.cache.keys().cloned().collect();
         self.cache.clear();
+        self.expirations.clear();
         for key in &keys {
             let _ = self.emit_change_event(key, None);
         }
@@ -347,6 +580,7 @@ impl<R: Runtime> StoreInner<R> {
                 }
             }
             self.cache.clone_from(defaults);
+            self.expirations.clear();
         } else {
             self.clear()
         }
@@ -393,6 +627,34 @@ impl<R: Runtime> StoreInner<R> {
         )?;
         Ok(())
     }
+
+    fn emit_batch_change_event(&self, changes: Vec<KeyChange>) -> crate::Result<()> {
+        let state = self.app.state::<StoreState>();
+        let stores = state.stores.lock().unwrap();
+        self.app.emit(
+            "store://batch-change",
+            BatchChangePayload {
+                path: &self.path,
+                resource_id: stores.get(&self.path).copied(),
+                changes,
+            },
+        )?;
+        Ok(())
+    }
+
+    fn emit_expired_event(&self, key: &str) -> crate::Result<()> {
+        let state = self.app.state::<StoreState>();
+        let stores = state.stores.lock().unwrap();
+        self.app.emit(
+            "store://expired",
+            ExpiredPayload {
+                path: &self.path,
+                resource_id: stores.get(&self.path).copied(),
+                key,
+            },
+        )?;
+        Ok(())
+    }
 }
 
 impl<R: Runtime> std::fmt::Debug for StoreInner<R> {
@@ -400,6 +662,7 @@ impl<R: Runtime> std::fmt::Debug for StoreInner<R> {
         f.debug_struct("Store")
             .field("path", &self.path)
             .field("cache", &self.cache)
+            .field("expirations", &self.expirations)
             .finish()
     }
 }
@@ -408,6 +671,35 @@ pub struct Store<R: Runtime> {
     auto_save: Option<Duration>,
     auto_save_debounce_sender: Arc<Mutex<Option<UnboundedSender<AutoSaveMessage>>>>,
     store: Arc<Mutex<StoreInner<R>>>,
+    /// Wakes the expiration task up when an entry is set with a TTL or the store is dropped.
+    expiration_notify: Arc<Notify>,
+}
+
+/// Removes the entries of the store once they expire, until the store is dropped.
+fn spawn_expiration_task<R: Runtime>(store: &Arc<Store<R>>) {
+    let weak: Weak<Store<R>> = Arc::downgrade(store);
+    let notify = store.expiration_notify.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let Some(store) = weak.upgrade() else {
+                return;
+            };
+            store.remove_expired();
+            let next_expiration = store.store.lock().unwrap().next_expiration();
+            drop(store);
+
+            match next_expiration {
+                Some(at) => {
+                    let delay = Duration::from_millis(at.saturating_sub(now_ms()));
+                    select! {
+                        _ = sleep(delay) => {}
+                        _ = notify.notified() => {}
+                    }
+                }
+                None => notify.notified().await,
+            }
+        }
+    });
 }
 
 impl<R: Runtime> Resource for Store<R> {
@@ -433,14 +725,137 @@ impl<R: Runtime> Store<R> {
         let _ = self.trigger_auto_save();
     }
 
+    /// Inserts a key-value pair into the store, removed once the `ttl` elapsed.
+    ///
+    /// Expired entries are removed when the store is read and in the background,
+    /// emitting a `store://expired` event. Setting the key again with [`Self::set`] removes its TTL.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use tauri_plugin_store::StoreExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = app.store("cache.json")?;
+    ///     store.set_with_ttl("token", "secret", Duration::from_secs(3600));
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn set_with_ttl(&self, key: impl Into<String>, value: impl Into<JsonValue>, ttl: Duration) {
+        self.store
+            .lock()
+            .unwrap()
+            .set_with_ttl(key.into(), value.into(), ttl);
+        self.expiration_notify.notify_one();
+        let _ = self.trigger_auto_save();
+    }
+
+    /// Applies the operations atomically: they're all applied at once, the store is saved once
+    /// and a single `store://batch-change` event is emitted with the entries they changed.
+    ///
+    /// No `store://change` event is emitted for these entries, so the Rust listeners
+    /// of the changes must listen to `store://batch-change` too.
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::{BatchOperation, StoreExt};
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = app.store("session.json")?;
+    ///     store.batch(vec![
+    ///       BatchOperation::Set { key: "user".into(), value: "alice".into(), ttl: None },
+    ///       BatchOperation::Delete { key: "guest".into() },
+    ///     ]);
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn batch(&self, operations: Vec<BatchOperation>) {
+        let _: Result<(), std::convert::Infallible> = self.transaction(|tx| {
+            for operation in operations {
+                tx.push(operation);
+            }
+            Ok(())
+        });
+    }
+
+    /// Runs a transaction, applying its operations atomically like [`Self::batch`] if it succeeds
+    /// and discarding them if it fails. The store is locked while the transaction runs,
+    /// so it must not use the store itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::StoreExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = app.store("accounts.json")?;
+    ///     store.transaction(|tx| {
+    ///       let balance = tx.get("balance").and_then(|v| v.as_u64()).unwrap_or_default();
+    ///       if balance < 10 {
+    ///         return Err("insufficient balance");
+    ///       }
+    ///       tx.set("balance", balance - 10);
+    ///       tx.set("last_payment", 10);
+    ///       Ok(())
+    ///     })?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn transaction<T, E>(
+        &self,
+        f: impl FnOnce(&mut StoreTransaction<'_>) -> std::result::Result<T, E>,
+    ) -> std::result::Result<T, E> {
+        let mut store = self.store.lock().unwrap();
+        let expired = store.remove_expired();
+
+        let mut tx = StoreTransaction {
+            cache: &store.cache,
+            staged: HashMap::new(),
+            operations: Vec::new(),
+        };
+        let result = f(&mut tx);
+        let operations = tx.operations;
+
+        let changed = match result {
+            Ok(_) if !operations.is_empty() => {
+                let ttl = operations
+                    .iter()
+                    .any(|op| matches!(op, BatchOperation::Set { ttl: Some(_), .. }));
+                store.apply(operations);
+                if ttl {
+                    self.expiration_notify.notify_one();
+                }
+                true
+            }
+            _ => false,
+        };
+        drop(store);
+
+        if changed || expired {
+            let _ = self.trigger_auto_save();
+        }
+        result
+    }
+
+    /// Returns when the entry for the given `key` expires,
+    /// `None` if the key does not exist or was set without a TTL.
+    pub fn expires_at(&self, key: impl AsRef<str>) -> Option<SystemTime> {
+        self.read(|store| store.expires_at(key))
+    }
+
     /// Returns the value for the given `key` or `None` if the key does not exist.
     pub fn get(&self, key: impl AsRef<str>) -> Option<JsonValue> {
-        self.store.lock().unwrap().get(key).cloned()
+        self.read(|store| store.get(key).cloned())
     }
 
     /// Returns `true` if the given `key` exists in the store.
     pub fn has(&self, key: impl AsRef<str>) -> bool {
-        self.store.lock().unwrap().has(key)
+        self.read(|store| store.has(key))
     }
 
     /// Removes a key-value pair from the store.
@@ -470,37 +885,40 @@ impl<R: Runtime> Store<R> {
 
     /// Returns a list of all keys in the store.
     pub fn keys(&self) -> Vec<String> {
-        self.store.lock().unwrap().keys().cloned().collect()
+        self.read(|store| store.keys().cloned().collect())
     }
 
     /// Returns a list of all values in the store.
     pub fn values(&self) -> Vec<JsonValue> {
-        self.store.lock().unwrap().values().cloned().collect()
+        self.read(|store| store.values().cloned().collect())
     }
 
     /// Returns a list of all key-value pairs in the store.
     pub fn entries(&self) -> Vec<(String, JsonValue)> {
-        self.store
-            .lock()
-            .unwrap()
-            .entries()
-            .map(|(k, v)| (k.to_owned(), v.to_owned()))
-            .collect()
+        self.read(|store| {
+            store
+                .entries()
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                .collect()
+        })
     }
 
     /// Returns the number of elements in the store.
     pub fn length(&self) -> usize {
-        self.store.lock().unwrap().len()
+        self.read(|store| store.len())
     }
 
     /// Returns true if the store contains no elements.
     pub fn is_empty(&self) -> bool {
-        self.store.lock().unwrap().is_empty()
+        self.read(|store| store.is_empty())
     }
 
     /// Update the store from the on-disk state
     pub fn reload(&self) -> crate::Result<()> {
-        self.store.lock().unwrap().load()
+        self.store.lock().unwrap().load()?;
+        // the entries loaded may expire before the ones already scheduled
+        self.expiration_notify.notify_one();
+        Ok(())
     }
 
     /// Saves the store to disk at the store's `path`.
@@ -524,6 +942,25 @@ impl<R: Runtime> Store<R> {
         }
     }
 
+    /// Reads the store once its expired entries are removed.
+    fn read<T>(&self, f: impl FnOnce(&StoreInner<R>) -> T) -> T {
+        let mut store = self.store.lock().unwrap();
+        let expired = store.remove_expired();
+        let result = f(&store);
+        drop(store);
+        if expired {
+            let _ = self.trigger_auto_save();
+        }
+        result
+    }
+
+    /// Removes the expired entries, saving the store if any was removed.
+    fn remove_expired(&self) {
+        if self.store.lock().unwrap().remove_expired() {
+            let _ = self.trigger_auto_save();
+        }
+    }
+
     fn trigger_auto_save(&self) -> crate::Result<()> {
         let Some(auto_save_delay) = self.auto_save else {
             return Ok(());
@@ -572,5 +1009,264 @@ impl<R: Runtime> Store<R> {
 impl<R: Runtime> Drop for Store<R> {
     fn drop(&mut self) {
         self.apply_pending_auto_save();
+        // stops the expiration task
+        self.expiration_notify.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use tauri::test::{mock_builder, mock_context, noop_assets, MockRuntime};
+
+    use super::*;
+    use crate::StoreExt;
+
+    fn app() -> tauri::App<MockRuntime> {
+        mock_builder()
+            .plugin(crate::Builder::new().build())
+            .build(mock_context(noop_assets()))
+            .unwrap()
+    }
+
+    /// An empty directory for the stores of a test.
+    fn store_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("tauri-plugin-store-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn store(app: &tauri::App<MockRuntime>, path: &Path) -> Arc<Store<MockRuntime>> {
+        app.store_builder(path).disable_auto_save().build().unwrap()
+    }
+
+    #[test]
+    fn ttl_expires() {
+        let app = app();
+        let store = store(&app, &store_dir("ttl-expires").join("store.json"));
+
+        store.set_with_ttl("token", "secret", Duration::from_millis(50));
+        assert_eq!(store.get("token"), Some("secret".into()));
+        assert!(store.expires_at("token").is_some());
+
+        sleep(Duration::from_millis(100));
+        assert_eq!(store.get("token"), None);
+        assert!(!store.has("token"));
+        assert_eq!(store.expires_at("token"), None);
+    }
+
+    #[test]
+    fn set_clears_ttl() {
+        let app = app();
+        let store = store(&app, &store_dir("set-clears-ttl").join("store.json"));
+
+        store.set_with_ttl("token", "secret", Duration::from_millis(50));
+        store.set("token", "persistent");
+        assert_eq!(store.expires_at("token"), None);
+
+        sleep(Duration::from_millis(100));
+        assert_eq!(store.get("token"), Some("persistent".into()));
+    }
+
+    #[test]
+    fn ttl_survives_reload() {
+        let app = app();
+        let path = store_dir("ttl-reload").join("store.json");
+        let store = store(&app, &path);
+
+        store.set_with_ttl("token", "secret", Duration::from_secs(3600));
+        store.set("theme", "dark");
+        let expires_at = store.expires_at("token").unwrap();
+        store.save().unwrap();
+        store.close_resource();
+        drop(store);
+
+        // the expiration times are not part of the store file
+        let saved: HashMap<String, JsonValue> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved.len(), 2);
+
+        let store = self::store(&app, &path);
+        assert_eq!(store.get("token"), Some("secret".into()));
+        assert_eq!(store.expires_at("token"), Some(expires_at));
+        assert_eq!(store.expires_at("theme"), None);
+    }
+
+    #[test]
+    fn expirations_file_is_removed_without_ttl() {
+        let app = app();
+        let path = store_dir("expirations-file").join("store.json");
+        let store = store(&app, &path);
+        let expirations_path = store.store.lock().unwrap().expirations_path();
+
+        store.set_with_ttl("token", "secret", Duration::from_secs(3600));
+        store.save().unwrap();
+        assert!(expirations_path.exists());
+
+        store.delete("token");
+        store.save().unwrap();
+        assert!(!expirations_path.exists());
+    }
+
+    #[test]
+    fn any_key_can_be_stored() {
+        let app = app();
+        let path = store_dir("any-key").join("store.json");
+        let store = store(&app, &path);
+
+        store.set("__expirations__", "user data");
+        store.set_with_ttl("token", "secret", Duration::from_secs(3600));
+        store.save().unwrap();
+        store.close_resource();
+        drop(store);
+
+        let store = self::store(&app, &path);
+        assert_eq!(store.get("__expirations__"), Some("user data".into()));
+        assert!(store.expires_at("token").is_some());
+    }
+
+    /// Collects the payloads of the `store://batch-change` events.
+    fn batch_changes(app: &tauri::App<MockRuntime>) -> Arc<Mutex<Vec<JsonValue>>> {
+        use tauri::Listener;
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let changes_ = changes.clone();
+        app.listen("store://batch-change", move |event| {
+            let payload: JsonValue = serde_json::from_str(event.payload()).unwrap();
+            changes_.lock().unwrap().push(payload["changes"].clone());
+        });
+        changes
+    }
+
+    #[test]
+    fn transaction_reads_its_changes() {
+        let app = app();
+        let store = store(&app, &store_dir("transaction-reads").join("store.json"));
+        store.set("a", 1);
+        store.set("b", 2);
+
+        store
+            .transaction(|tx| {
+                tx.set("a", 10);
+                assert!(tx.delete("b"));
+                assert!(!tx.delete("c"));
+                assert_eq!(tx.get("a"), Some(&10.into()));
+                assert!(!tx.has("b"));
+
+                // the store is unchanged until the transaction succeeds
+                tx.set("b", 20);
+                assert_eq!(tx.get("b"), Some(&20.into()));
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+
+        assert_eq!(store.get("a"), Some(10.into()));
+        assert_eq!(store.get("b"), Some(20.into()));
+    }
+
+    #[test]
+    fn failed_transaction_is_discarded() {
+        let app = app();
+        let changes = batch_changes(&app);
+        let store = store(&app, &store_dir("transaction-rollback").join("store.json"));
+        store.set_with_ttl("a", 1, Duration::from_secs(3600));
+        let expires_at = store.expires_at("a");
+
+        let result: Result<(), &str> = store.transaction(|tx| {
+            tx.set("a", 10);
+            tx.delete("a");
+            tx.set("b", 2);
+            Err("aborted")
+        });
+
+        assert_eq!(result, Err("aborted"));
+        assert_eq!(store.get("a"), Some(1.into()));
+        assert_eq!(store.expires_at("a"), expires_at);
+        assert!(!store.has("b"));
+        assert!(changes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn batch_emits_the_changed_entries() {
+        let app = app();
+        let changes = batch_changes(&app);
+        let store = store(&app, &store_dir("batch-diff").join("store.json"));
+        store.set("same", 1);
+        store.set("changed", 1);
+        store.set("deleted", 1);
+
+        store.batch(vec![
+            // set to the value it already has
+            BatchOperation::Set {
+                key: "same".into(),
+                value: 1.into(),
+                ttl: None,
+            },
+            BatchOperation::Set {
+                key: "changed".into(),
+                value: 2.into(),
+                ttl: None,
+            },
+            BatchOperation::Delete {
+                key: "deleted".into(),
+            },
+            // created then deleted
+            BatchOperation::Set {
+                key: "temporary".into(),
+                value: 1.into(),
+                ttl: None,
+            },
+            BatchOperation::Delete {
+                key: "temporary".into(),
+            },
+            // deleted while missing
+            BatchOperation::Delete {
+                key: "missing".into(),
+            },
+        ]);
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![serde_json::json!([
+                { "key": "changed", "value": 2, "exists": true },
+                { "key": "deleted", "value": null, "exists": false },
+            ])]
+        );
+
+        // no event without any change
+        store.batch(vec![BatchOperation::Delete {
+            key: "missing".into(),
+        }]);
+        assert_eq!(changes.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn batch_sets_ttl() {
+        let app = app();
+        let store = store(&app, &store_dir("batch-ttl").join("store.json"));
+        store.set_with_ttl("persistent", 1, Duration::from_millis(50));
+
+        store.batch(vec![
+            BatchOperation::Set {
+                key: "token".into(),
+                value: "secret".into(),
+                ttl: Some(Duration::from_millis(50)),
+            },
+            // a set without TTL clears it
+            BatchOperation::Set {
+                key: "persistent".into(),
+                value: 2.into(),
+                ttl: None,
+            },
+        ]);
+        assert!(store.expires_at("token").is_some());
+        assert_eq!(store.expires_at("persistent"), None);
+
+        sleep(Duration::from_millis(100));
+        assert!(!store.has("token"));
+        assert_eq!(store.get("persistent"), Some(2.into()));
     }
 }